@@ -3,6 +3,7 @@ mod common;
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 
 use common::*;
+use rasn::{AsnType, Encode, types::OctetString};
 
 fn rasn(c: &mut Criterion) {
     let decoded = black_box(bench_default());
@@ -22,6 +23,35 @@ fn rasn(c: &mut Criterion) {
     bench_encoding_rules!(ber, der, cer, uper, oer);
 }
 
+/// A tree of `SEQUENCE`s, to measure how encoding scales with nesting.
+#[derive(AsnType, Encode)]
+struct Node {
+    data: OctetString,
+    children: Vec<Node>,
+}
+
+fn node(depth: usize, width: usize) -> Node {
+    Node {
+        data: OctetString::from(vec![0x5A; 64]),
+        children: if depth == 0 {
+            Vec::new()
+        } else {
+            (0..width).map(|_| node(depth - 1, width)).collect()
+        },
+    }
+}
+
+fn nested(c: &mut Criterion) {
+    let mut group = c.benchmark_group("DER - Nested");
+    for (depth, width) in [(32, 1), (6, 4)] {
+        let value = black_box(node(depth, width));
+        group.bench_function(format!("depth {depth}, width {width}"), |b| {
+            b.iter_with_large_drop(|| black_box(rasn::der::encode(&value).unwrap()))
+        });
+    }
+    group.finish();
+}
+
 #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 fn x509_decode(c: &mut Criterion) {
     let data: &[u8] = include_bytes!("../standards/pkix/tests/data/letsencrypt-x3.crt");
@@ -131,8 +161,8 @@ fn x509_rtt(c: &mut Criterion) {
 }
 
 #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
-criterion_group!(codec, x509_decode, x509_encode, x509_rtt, rasn);
+criterion_group!(codec, x509_decode, x509_encode, x509_rtt, rasn, nested);
 
 #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
-criterion_group!(codec, rasn);
+criterion_group!(codec, rasn, nested);
 criterion_main!(codec);
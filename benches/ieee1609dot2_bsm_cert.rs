@@ -178,5 +178,21 @@ fn ectl_list_enc_dec(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, oer_enc_dec, ectl_list_enc_dec);
+fn der_enc(c: &mut Criterion) {
+    let cert = build_sample();
+    let tlm_data: &[u8] = include_bytes!("../standards/its/tests/data/CE4CF6C19BFED720.oer");
+    let ectl = rasn::coer::decode::<Ieee1609Dot2Data>(tlm_data).unwrap();
+    let mut buffer = Vec::<u8>::new();
+
+    c.bench_function(
+        "RASN/ encode DER ieee1609dot2 - bsm with certificate",
+        |b| b.iter(|| rasn::der::encode_buf(&cert, &mut buffer).unwrap()),
+    );
+    c.bench_function(
+        "RASN/ encode DER ieee1609dot2 - ECTL - European Certificate Trust List",
+        |b| b.iter(|| rasn::der::encode_buf(&ectl, &mut buffer).unwrap()),
+    );
+}
+
+criterion_group!(benches, oer_enc_dec, ectl_list_enc_dec, der_enc);
 criterion_main!(benches);
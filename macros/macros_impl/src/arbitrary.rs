@@ -0,0 +1,176 @@
+//! Generation of arbitrary values that satisfy the constraints of their fields.
+
+use crate::config::{Config, FieldConfig, VariantConfig};
+
+pub fn derive_arbitrary(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    data: &syn::Data,
+    config: &Config,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let crate_root = &config.crate_root;
+    let (body, uses_constraints) = match data {
+        syn::Data::Struct(container) if config.delegate => {
+            let mut fields = container.fields.iter();
+            let ty = &fields.next().unwrap().ty;
+            let phantom_fields = fields.map(|_| quote!(<_>::default()));
+            (
+                quote! {
+                    Ok(Self(
+                        <#ty as #crate_root::arbitrary::ArbitraryConstrained<'arbitrary>>::arbitrary_constrained(u, constraints)?,
+                        #(#phantom_fields),*
+                    ))
+                },
+                true,
+            )
+        }
+        syn::Data::Struct(container) => {
+            let value = construct(quote!(Self), &container.fields, config)?;
+            let unused = container.fields.is_empty().then(|| quote!(let _ = u;));
+            (quote!(#unused Ok(#value)), false)
+        }
+        syn::Data::Enum(data) if config.enumerated => {
+            let count = data.variants.len();
+            let variants = data.variants.iter().enumerate().map(|(i, variant)| {
+                let ident = &variant.ident;
+                quote!(#i => Self::#ident)
+            });
+            (
+                quote! {
+                    Ok(match u.choose_index(#count)? {
+                        #(#variants,)*
+                        _ => unreachable!(),
+                    })
+                },
+                false,
+            )
+        }
+        syn::Data::Enum(data) => {
+            let count = data.variants.len();
+            let variants = data
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(i, variant)| {
+                    let ident = &variant.ident;
+                    let variant_config = VariantConfig::new(variant, generics, config, i)?;
+                    let value = if let syn::Fields::Unnamed(_) = &variant.fields {
+                        let ty = &variant.fields.iter().next().unwrap().ty;
+                        let value = arbitrary_value(ty, &variant_config.constraints, config);
+                        quote!(Self::#ident(#value))
+                    } else {
+                        construct(quote!(Self::#ident), &variant.fields, config)?
+                    };
+                    Ok(quote!(#i => #value))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            (
+                quote! {
+                    Ok(match u.choose_index(#count)? {
+                        #(#variants,)*
+                        _ => unreachable!(),
+                    })
+                },
+                false,
+            )
+        }
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(
+                name.span(),
+                "Union types are not supported.",
+            ));
+        }
+    };
+
+    let mut impl_generics = generics.clone();
+    for param in &mut impl_generics.params {
+        if let syn::GenericParam::Type(type_param) = param {
+            type_param.bounds.push(syn::parse_quote!(
+                #crate_root::arbitrary::ArbitraryConstrained<'arbitrary>
+            ));
+        }
+    }
+    impl_generics
+        .params
+        .insert(0, syn::parse_quote!('arbitrary));
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let constraints = if uses_constraints {
+        quote!(constraints)
+    } else {
+        quote!(_)
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_root::arbitrary::ArbitraryConstrained<'arbitrary> for #name #ty_generics #where_clause {
+            fn arbitrary_constrained(
+                u: &mut #crate_root::arbitrary::Unstructured<'arbitrary>,
+                #constraints: #crate_root::types::Constraints,
+            ) -> #crate_root::arbitrary::Result<Self> {
+                #body
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_root::arbitrary::Arbitrary<'arbitrary> for #name #ty_generics #where_clause {
+            fn arbitrary(
+                u: &mut #crate_root::arbitrary::Unstructured<'arbitrary>,
+            ) -> #crate_root::arbitrary::Result<Self> {
+                <Self as #crate_root::arbitrary::ArbitraryConstrained<'arbitrary>>::arbitrary_constrained(
+                    u,
+                    <Self as #crate_root::AsnType>::CONSTRAINTS,
+                )
+            }
+        }
+    })
+}
+
+/// Constructs `path` from values generated for each of `fields`.
+fn construct(
+    path: proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    config: &Config,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let values = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let field_config = FieldConfig::new(field, config, i)?;
+            Ok(arbitrary_value(
+                &field.ty,
+                &field_config.constraints,
+                config,
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(match fields {
+        syn::Fields::Named(_) => {
+            let idents = fields.iter().map(|field| &field.ident);
+            quote!(#path { #(#idents: #values),* })
+        }
+        syn::Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+        syn::Fields::Unit => path,
+    })
+}
+
+/// Generates a value of `ty` that satisfies the constraints of `ty` and the
+/// `constraints` declared on its field.
+fn arbitrary_value(
+    ty: &syn::Type,
+    constraints: &crate::config::Constraints,
+    config: &Config,
+) -> proc_macro2::TokenStream {
+    let crate_root = &config.crate_root;
+    let constraints = match constraints.const_expr(crate_root) {
+        Some(constraints) => {
+            quote!(<#ty as #crate_root::AsnType>::CONSTRAINTS.intersect(const { #constraints }))
+        }
+        None => quote!(<#ty as #crate_root::AsnType>::CONSTRAINTS),
+    };
+
+    quote! {
+        <#ty as #crate_root::arbitrary::ArbitraryConstrained<'arbitrary>>::arbitrary_constrained(u, #constraints)?
+    }
+}
@@ -114,6 +114,15 @@ pub fn derive_struct_impl(
         |id| quote!(const IDENTIFIER: #crate_root::types::Identifier = #crate_root::types::Identifier(Some(#id));),
     );
 
+    let xer_instructions_def = (!config.xer.is_empty()).then(|| {
+        let xer = config.xer.to_tokens(crate_root);
+        quote!(const XER_INSTRUCTIONS: #crate_root::types::instructions::XerInstructions = #xer;)
+    });
+    let jer_instructions_def = (!config.jer.is_empty()).then(|| {
+        let jer = config.jer.to_tokens(crate_root);
+        quote!(const JER_INSTRUCTIONS: #crate_root::types::instructions::JerInstructions = #jer;)
+    });
+
     Ok(quote! {
         #constructed_impl
 
@@ -126,6 +135,8 @@ pub fn derive_struct_impl(
             };
             #alt_identifier
             #constraints_def
+            #xer_instructions_def
+            #jer_instructions_def
         }
     })
 }
@@ -0,0 +1,152 @@
+//! Generation of builders and checked constructors that validate constraints.
+
+use crate::config::{Config, FieldConfig};
+
+pub fn derive_builder(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    container: &syn::DataStruct,
+    config: &Config,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if config.delegate {
+        return Ok(checked_constructor(name, vis, generics, container, config));
+    }
+
+    let syn::Fields::Named(_) = &container.fields else {
+        return Err(syn::Error::new_spanned(
+            &container.fields,
+            "`#[rasn(builder)]` is only supported on structs with named fields and delegate newtypes",
+        ));
+    };
+
+    let crate_root = &config.crate_root;
+    let builder = quote::format_ident!("{name}Builder");
+    let builder_doc = format!("A builder of [`{name}`] values.");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_configs = container
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| FieldConfig::new(f, config, i))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fields = field_configs
+        .iter()
+        .map(|field| field.field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let types = field_configs
+        .iter()
+        .map(|field| &field.field.ty)
+        .collect::<Vec<_>>();
+    let setter_docs = fields
+        .iter()
+        .map(|field| format!("Sets the `{field}` field."))
+        .collect::<Vec<_>>();
+
+    let initializers = field_configs.iter().map(|field| {
+        let ident = field.field.ident.as_ref().unwrap();
+        let field_name = ident.to_string();
+        if let Some(default_fn) = field.default_fn() {
+            quote!(#ident: self.#ident.unwrap_or_else(#default_fn))
+        } else if field.is_option_type() {
+            quote!(#ident: self.#ident.unwrap_or_default())
+        } else {
+            quote! {
+                #ident: self.#ident.ok_or(#crate_root::error::BuildError::MissingField {
+                    name: #field_name,
+                })?
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder #impl_generics #where_clause {
+            #(#fields: Option<#types>,)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics Default for #builder #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#fields: None,)*
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns a builder of values, which checks the constraints of
+            /// the type when the value is built.
+            #vis fn builder() -> #builder #ty_generics {
+                #builder::default()
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(
+                #[doc = #setter_docs]
+                #[must_use]
+                #vis fn #fields(mut self, value: impl Into<#types>) -> Self {
+                    self.#fields = Some(value.into());
+                    self
+                }
+            )*
+
+            /// Builds the value, filling the fields that weren't set with their
+            /// default values.
+            ///
+            /// # Errors
+            /// Returns an error if a required field wasn't set, or the value
+            /// doesn't satisfy the constraints of its type.
+            #vis fn build(self) -> Result<#name #ty_generics, #crate_root::error::BuildError>
+            where
+                #name #ty_generics: #crate_root::Encode,
+            {
+                let value = #name {
+                    #(#initializers,)*
+                };
+                #crate_root::enc::check_constraints(&value)?;
+                Ok(value)
+            }
+        }
+    })
+}
+
+/// Generates a `new` constructor of a delegate newtype, which checks the
+/// constraints of the newtype.
+fn checked_constructor(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    container: &syn::DataStruct,
+    config: &Config,
+) -> proc_macro2::TokenStream {
+    let crate_root = &config.crate_root;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut fields = container.fields.iter();
+    let inner_type = &fields.next().unwrap().ty;
+    let phantom_fields = fields.map(|_| quote!(<_>::default()));
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates a new value, checking that it satisfies the constraints
+            /// of the type.
+            ///
+            /// # Errors
+            /// Returns an error if the value doesn't satisfy the constraints of
+            /// the type.
+            #vis fn new(
+                value: impl Into<#inner_type>,
+            ) -> Result<Self, #crate_root::error::BuildError>
+            where
+                Self: #crate_root::Encode,
+            {
+                let value = Self(value.into(), #(#phantom_fields),*);
+                #crate_root::enc::check_constraints(&value)?;
+                Ok(value)
+            }
+        }
+    }
+}
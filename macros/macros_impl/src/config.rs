@@ -191,14 +191,19 @@ impl Constraints {
     fn from_def(&self, crate_root: &syn::Path) -> Option<proc_macro2::TokenStream> {
         self.from.as_ref().map(|value| {
             let extensible = value.extensible.is_some();
-            let StringValue(set) = &value.constraint;
+            let StringValue(ranges) = &value.constraint;
+            let ranges = ranges.iter().map(|(start, end)| {
+                quote!(#crate_root::types::constraints::CharacterRange::new(#start, #end))
+            });
 
             quote!(
                 #crate_root::types::Constraint::PermittedAlphabet(
                     #crate_root::types::constraints::Extensible::new(
-                        #crate_root::types::constraints::PermittedAlphabet::new(
-                            &[#(#set,)*]
-                        )
+                        #crate_root::types::constraints::PermittedAlphabet::from_ranges({
+                            const RANGES: &[#crate_root::types::constraints::CharacterRange] =
+                                &#crate_root::types::constraints::CharacterRange::indexed([#(#ranges,)*]);
+                            RANGES
+                        })
                     ).set_extensible(#extensible)
                 )
             )
@@ -209,9 +214,10 @@ impl Constraints {
     fn from_attr(&self) -> Option<proc_macro2::TokenStream> {
         self.from.as_ref().map(|value| {
             let extensible = value.extensible.is_some().then_some(quote!(extensible));
-            let StringValue(set) = &value.constraint;
+            let StringValue(ranges) = &value.constraint;
+            let ranges = ranges.iter().map(|(start, end)| quote!(#start..=#end));
 
-            quote!(from(#(#set,)*, #extensible))
+            quote!(from(#(#ranges,)* #extensible))
         })
     }
 
@@ -223,6 +229,65 @@ impl Constraints {
     fn has_constraints(&self) -> bool {
         self.extensible || self.from.is_some() || self.size.is_some() || self.value.is_some()
     }
+
+    /// Checks that the value constraint fits into `ty` when `ty` is a
+    /// fixed-width primitive integer (or an `Option` of one), so a constrained
+    /// `INTEGER` mapped straight to `u8`…`i64` can't be truncated by the codecs.
+    pub fn check_integer_type(&self, ty: &Type) -> syn::Result<()> {
+        let Some(value) = &self.value else {
+            return Ok(());
+        };
+        let Some((name, min, max)) = primitive_integer_bounds(ty) else {
+            return Ok(());
+        };
+        let (start, end) = match value.constraint {
+            Value::Single(value) => (Some(value), Some(value)),
+            Value::Range(start, end) => (start, end),
+        };
+
+        if start.is_some_and(|start| start < min) || end.is_some_and(|end| end > max) {
+            return Err(syn::Error::new(
+                ty.span(),
+                format!("the value constraint doesn't fit into `{name}`, use a wider integer type"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the name and the bounds of `ty` if it is a fixed-width primitive
+/// integer, looking through `Option`.
+fn primitive_integer_bounds(ty: &Type) -> Option<(String, i128, i128)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+
+    if segment.ident == "Option" {
+        let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+        return match arguments.args.first()? {
+            syn::GenericArgument::Type(inner) => primitive_integer_bounds(inner),
+            _ => None,
+        };
+    }
+
+    let name = segment.ident.to_string();
+    let (min, max) = match name.as_str() {
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" | "usize" => (0, u64::MAX.into()),
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" | "isize" => (i64::MIN.into(), i64::MAX.into()),
+        _ => return None,
+    };
+
+    Some((name, min, max))
 }
 
 #[derive(Clone, Debug)]
@@ -234,8 +299,14 @@ pub struct Config {
     pub set: bool,
     pub automatic_tags: bool,
     pub delegate: bool,
+    pub serde: bool,
+    pub builder: bool,
+    pub arbitrary: bool,
+    pub named_bits: Option<Vec<NamedBit>>,
     pub tag: Option<Tag>,
     pub constraints: Constraints,
+    pub xer: XerInstructions,
+    pub jer: JerInstructions,
 }
 
 impl Config {
@@ -251,7 +322,13 @@ impl Config {
         let mut size = None;
         let mut value = None;
         let mut delegate = false;
+        let mut serde = false;
+        let mut builder = false;
+        let mut arbitrary = false;
+        let mut named_bits = None;
         let mut extensible = false;
+        let mut xer = XerInstructions::default();
+        let mut jer = JerInstructions::default();
 
         for attr in &input.attrs {
             if attr.path().is_ident("non_exhaustive") {
@@ -282,12 +359,26 @@ impl Config {
                         tag = Some(Tag::from_meta(&meta)?);
                     } else if path.is_ident("delegate") {
                         delegate = true;
+                    } else if path.is_ident("serde") {
+                        serde = true;
+                    } else if path.is_ident("builder") {
+                        builder = true;
+                    } else if path.is_ident("arbitrary") {
+                        arbitrary = true;
+                    } else if path.is_ident("named_bits") {
+                        named_bits = Some(NamedBit::list_from_meta(&meta)?);
                     } else if path.is_ident("from") {
                         from = Some(StringValue::from_meta(&meta)?);
                     } else if path.is_ident("size") {
                         size = Some(Value::from_meta(&meta)?);
                     } else if path.is_ident("value") {
                         value = Some(Value::from_meta(&meta)?);
+                    } else if path.is_ident("xer") {
+                        xer = XerInstructions::from_meta(&meta)?;
+                        xer.check_type_instructions(path.span())?;
+                    } else if path.is_ident("jer") {
+                        jer = JerInstructions::from_meta(&meta)?;
+                        jer.check_type_instructions(path.span())?;
                     } else {
                         return Err(meta.error(format!(
                             "unknown input provided: {}",
@@ -311,6 +402,16 @@ impl Config {
                 input.ident.span(),
                 "Enums cannot be annotated with `#[rasn(set)]`.",
             ));
+        } else if is_enum && builder {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Enums cannot be annotated with `#[rasn(builder)]`.",
+            ));
+        } else if is_enum && named_bits.is_some() {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Enums cannot be annotated with `#[rasn(named_bits)]`.",
+            ));
         } else if is_enum && ((choice && enumerated) || (!choice && !enumerated)) {
             return Err(syn::Error::new(
                 input.ident.span(),
@@ -318,6 +419,8 @@ impl Config {
             ));
         }
 
+        // A named bit list is a `BIT STRING` wrapped in a newtype.
+        let delegate = delegate || named_bits.is_some();
         let mut invalid_delegate = false;
 
         if is_enum && delegate {
@@ -356,20 +459,35 @@ impl Config {
             ));
         }
 
+        let constraints = Constraints {
+            extensible,
+            from,
+            size,
+            value,
+        };
+
+        if delegate
+            && let syn::Data::Struct(data) = &input.data
+            && let Some(field) = data.fields.iter().next()
+        {
+            constraints.check_integer_type(&field.ty)?;
+        }
+
         Ok(Self {
             automatic_tags,
             choice,
             delegate,
+            serde,
+            builder,
+            arbitrary,
+            named_bits,
             enumerated,
             set,
             tag,
             identifier,
-            constraints: Constraints {
-                extensible,
-                from,
-                size,
-                value,
-            },
+            constraints,
+            xer,
+            jer,
             crate_root: crate_root.unwrap_or_else(|| {
                 syn::LitStr::new(crate::CRATE_NAME, proc_macro2::Span::call_site())
                     .parse()
@@ -411,6 +529,39 @@ impl Config {
     }
 }
 
+/// A bit of a `BIT STRING` named with `#[rasn(named_bits(name = position))]`.
+#[derive(Clone, Debug)]
+pub struct NamedBit {
+    pub name: Ident,
+    pub position: syn::LitInt,
+}
+
+impl NamedBit {
+    fn list_from_meta(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Vec<Self>> {
+        let mut bits: Vec<Self> = Vec::new();
+        meta.parse_nested_meta(|meta| {
+            let name = meta.path.require_ident()?.clone();
+            let position: syn::LitInt = meta.value()?.parse()?;
+            let value = position.base10_parse::<usize>()?;
+            for bit in &bits {
+                if bit.position.base10_parse::<usize>()? == value {
+                    return Err(syn::Error::new(
+                        position.span(),
+                        format!("`{}` already names bit {value}", bit.name),
+                    ));
+                }
+            }
+            bits.push(Self { name, position });
+            Ok(())
+        })?;
+
+        if bits.is_empty() {
+            return Err(meta.error("`named_bits` requires at least one named bit"));
+        }
+        Ok(bits)
+    }
+}
+
 pub(crate) fn is_option_type(ty: &syn::Type) -> bool {
     match ty {
         syn::Type::Path(path) => path
@@ -558,6 +709,12 @@ impl<'config> VariantConfig<'config> {
         self.tag.as_ref().is_some_and(|tag| tag.is_explicit())
     }
 
+    /// The name of the alternative in the spans emitted with the `tracing`
+    /// feature.
+    pub fn trace_name(&self) -> String {
+        self.variant.ident.to_string()
+    }
+
     pub fn decode(&self, name: &syn::Ident) -> syn::Result<proc_macro2::TokenStream> {
         let context = self.context;
         let crate_root = &self.container_config.crate_root;
@@ -652,8 +809,10 @@ impl<'config> VariantConfig<'config> {
             }
         };
 
+        let trace_name = self.trace_name();
         Ok(quote! {
             if #crate_root::types::TagTree::tag_contains(&tag, &[#tag_tree]) {
+                #crate_root::__trace_field!(#trace_name);
                 #const_constraint
                 return #decode_op
             }
@@ -737,6 +896,8 @@ pub struct FieldConfig<'a> {
     pub extension_addition_group: bool,
     pub constraints: Constraints,
     pub context: usize,
+    pub xer: XerInstructions,
+    pub jer: JerInstructions,
 }
 
 pub enum FieldType {
@@ -760,6 +921,8 @@ impl<'a> FieldConfig<'a> {
         let mut extensible = false;
         let mut extension_addition = false;
         let mut extension_addition_group = false;
+        let mut xer = XerInstructions::default();
+        let mut jer = JerInstructions::default();
         /*if !field.attrs.is_empty() {
             panic!("{:?}", field)
         }*/
@@ -800,6 +963,11 @@ impl<'a> FieldConfig<'a> {
                     extension_addition = true;
                 } else if path.is_ident("extension_addition_group") {
                     extension_addition_group = true;
+                } else if path.is_ident("xer") {
+                    xer = XerInstructions::from_meta(&meta)?;
+                    xer.check_field_instructions(path.span())?;
+                } else if path.is_ident("jer") {
+                    jer = JerInstructions::from_meta(&meta)?;
                 } else {
                     return Err(meta.error(format!(
                         "unknown field tag {:?}",
@@ -817,6 +985,14 @@ impl<'a> FieldConfig<'a> {
             ));
         }
 
+        let constraints = Constraints {
+            extensible,
+            from,
+            size,
+            value,
+        };
+        constraints.check_integer_type(&field.ty)?;
+
         Ok(Self {
             container_config,
             default,
@@ -825,13 +1001,10 @@ impl<'a> FieldConfig<'a> {
             tag,
             extension_addition,
             extension_addition_group,
-            constraints: Constraints {
-                extensible,
-                from,
-                size,
-                value,
-            },
+            constraints,
             context,
+            xer,
+            jer,
         })
     }
 
@@ -1003,9 +1176,22 @@ impl<'a> FieldConfig<'a> {
         name: &syn::Ident,
         type_params: &[Ident],
     ) -> syn::Result<proc_macro2::TokenStream> {
+        let crate_root = &self.container_config.crate_root;
         let lhs = self.field.ident.as_ref().map(|i| quote!(#i :));
+        let trace_name = self.trace_name();
         let decode_op = self.decode(name, type_params)?;
-        Ok(quote!(#lhs #decode_op))
+        Ok(quote!(#lhs {
+            #crate_root::__trace_field!(#trace_name);
+            #decode_op
+        }))
+    }
+
+    /// The name of the field in the spans emitted with the `tracing` feature.
+    pub fn trace_name(&self) -> String {
+        self.field
+            .ident
+            .as_ref()
+            .map_or_else(|| self.context.to_string(), ToString::to_string)
     }
 
     pub fn decode(
@@ -1063,16 +1249,18 @@ impl<'a> FieldConfig<'a> {
             quote!()
         };
 
+        // `DEFAULT` fields record whether their value was missing from the
+        // encoding, so that a present default value can be checked afterwards.
+        let absent_default_fn = default_fn
+            .as_ref()
+            .map(|default_fn| quote!(|| { __rasn_default_present = false; (#default_fn)() }));
         let decode = if self.extension_addition_group {
             quote!(decoder.decode_extension_addition_group() #or_else)
         } else {
             match (
                 (self.tag.is_some() || self.container_config.automatic_tags)
                     .then(|| self.tag.as_ref().is_some_and(|tag| tag.is_explicit())),
-                self.default.as_ref().map(|path| {
-                    path.as_ref()
-                        .map_or(quote!(<_>::default), |path| quote!(#path))
-                }),
+                absent_default_fn.clone(),
                 self.constraints.has_constraints(),
             ) {
                 (Some(true), _, _) => {
@@ -1082,7 +1270,7 @@ impl<'a> FieldConfig<'a> {
                         )
                     } else if self.is_default_type() {
                         quote!(
-                            decoder.decode_optional_with_explicit_prefix(#tag)?.unwrap_or_else(#default_fn)
+                            decoder.decode_default_with_explicit_prefix(#tag, #absent_default_fn) #or_else
                         )
                     } else {
                         // False positive
@@ -1236,6 +1424,17 @@ impl<'a> FieldConfig<'a> {
                     }
                 }
             }
+        } else if let Some(default_fn) = default_fn.filter(|_| !self.extension_addition_group) {
+            quote!({
+                let mut __rasn_default_present = true;
+                let value = #decode;
+                if __rasn_default_present {
+                    use #crate_root::de::default_value::{ComparableDefault as _, IncomparableDefault as _};
+                    let is_default = (&#crate_root::de::default_value::DefaultValue(&value, &(#default_fn)())).is_default();
+                    decoder.check_encoded_default(is_default) #or_else;
+                }
+                value
+            })
         } else {
             quote!({
                 #decode
@@ -1313,7 +1512,24 @@ impl<'a> FieldConfig<'a> {
             }
         );
 
-        quote!({ #crate_root::types::fields::Field::#constructor(#context, #tag, #tag_tree, #name) })
+        let xer = (!self.xer.is_empty()).then(|| {
+            let xer = self.xer.to_tokens(crate_root);
+            quote!(.with_xer_instructions(#xer))
+        });
+        let jer = (!self.jer.is_empty()).then(|| {
+            let jer = self.jer.to_tokens(crate_root);
+            quote!(.with_jer_instructions(#jer))
+        });
+
+        let ty = &self.field.ty;
+
+        quote!({
+            #crate_root::types::fields::Field::#constructor(#context, #tag, #tag_tree, #name)
+                #xer
+                .with_type_xer_instructions(<#ty as #crate_root::AsnType>::XER_INSTRUCTIONS)
+                #jer
+                .with_type_jer_instructions(<#ty as #crate_root::AsnType>::JER_INSTRUCTIONS)
+        })
     }
 
     pub fn field_type(&self) -> FieldType {
@@ -1366,18 +1582,14 @@ impl<T> From<T> for Constraint<T> {
     }
 }
 
+/// A permitted alphabet, as sorted and disjoint inclusive ranges of characters.
 #[derive(Clone, Debug)]
-pub struct StringValue(pub Vec<u32>);
+pub struct StringValue(pub Vec<(u32, u32)>);
 
 impl StringValue {
     fn from_meta(item: &syn::meta::ParseNestedMeta) -> syn::Result<Constraint<StringValue>> {
         let mut values = Vec::new();
-        let mut extensible: Option<_> = None;
-
-        enum StringRange {
-            Single(u32),
-            Range(u32, u32),
-        }
+        let mut extensible: Option<Vec<(u32, u32)>> = None;
 
         fn parse_character(string: &str) -> Option<u32> {
             string.chars().map(u32::from).next()
@@ -1386,83 +1598,112 @@ impl StringValue {
         let content;
         parenthesized!(content in item.input);
         while !content.is_empty() {
-            let (span, string) = if content.peek(syn::LitStr) {
-                let str: syn::LitStr = content.parse()?;
-
-                (str.span(), str.value())
-            } else if content.peek(syn::Ident) {
-                let path: syn::Path = content.parse()?;
-                (path.span(), path.require_ident()?.to_string())
+            let range = if content.peek(syn::LitInt) {
+                // Character codes, e.g. `0x41` or `0x41..=0x5A`.
+                let start: syn::LitInt = content.parse()?;
+                let start = start.base10_parse::<u32>()?;
+                let end = if content.peek(Token![..=]) {
+                    let _: Token![..=] = content.parse()?;
+                    let end: syn::LitInt = content.parse()?;
+                    end.base10_parse::<u32>()?
+                } else if content.peek(Token![..]) {
+                    let _: Token![..] = content.parse()?;
+                    let end: syn::LitInt = content.parse()?;
+                    let span = end.span();
+                    end.base10_parse::<u32>()?
+                        .checked_sub(1)
+                        .ok_or_else(|| syn::Error::new(span, "empty range of characters"))?
+                } else {
+                    start
+                };
+                (start, end)
             } else {
-                return Err(content.error(format!("Unsupported meta item: {content:?}")));
-            };
-            if string == "extensible" {
-                extensible = Some(Vec::new());
-                skip_comma(&content);
-                continue;
-            }
+                let (span, string) = if content.peek(syn::LitStr) {
+                    let str: syn::LitStr = content.parse()?;
 
-            if string.len() == 1 {
-                values.push(parse_character(&string).map(StringRange::Single).unwrap());
-                skip_comma(&content);
-                continue;
-            }
+                    (str.span(), str.value())
+                } else if content.peek(syn::Ident) {
+                    let path: syn::Path = content.parse()?;
+                    (path.span(), path.require_ident()?.to_string())
+                } else {
+                    return Err(content.error(format!("Unsupported meta item: {content:?}")));
+                };
+                if string == "extensible" {
+                    extensible = Some(Vec::new());
+                    skip_comma(&content);
+                    continue;
+                }
 
-            let Some((start, mut end)) = string.split_once("..") else {
-                return Err(syn::Error::new(
-                    span,
-                    format!(
-                        "unknown format: {string}, must be a single character or range of characters (`..`, `..=`)"
-                    ),
-                ));
-            };
+                if string.chars().count() == 1 {
+                    let character = parse_character(&string).unwrap();
+                    (character, character)
+                } else {
+                    let Some((start, mut end)) = string.split_once("..") else {
+                        return Err(syn::Error::new(
+                            span,
+                            format!(
+                                "unknown format: {string}, must be a single character or range of characters (`..`, `..=`)"
+                            ),
+                        ));
+                    };
 
-            let Some(start) = parse_character(start) else {
-                return Err(syn::Error::new(
-                    span,
-                    format!("start of range was an invalid character: {start}"),
-                ));
-            };
+                    let Some(start) = parse_character(start) else {
+                        return Err(syn::Error::new(
+                            span,
+                            format!("start of range was an invalid character: {start}"),
+                        ));
+                    };
 
-            let is_inclusive = end.starts_with('=');
-            if is_inclusive {
-                end = &end[1..];
-            }
+                    let is_inclusive = end.starts_with('=');
+                    if is_inclusive {
+                        end = &end[1..];
+                    }
 
-            let Some(end) = parse_character(end) else {
-                return Err(syn::Error::new(
-                    span,
-                    format!("end of range was an invalid character: {end}"),
-                ));
+                    let Some(end) = parse_character(end) else {
+                        return Err(syn::Error::new(
+                            span,
+                            format!("end of range was an invalid character: {end}"),
+                        ));
+                    };
+                    let Some(end) = end.checked_sub(u32::from(!is_inclusive)) else {
+                        return Err(syn::Error::new(span, "empty range of characters"));
+                    };
+
+                    (start, end)
+                }
             };
 
+            if range.0 > range.1 {
+                return Err(content.error("empty range of characters"));
+            }
             if let Some(extensible_values) = extensible.as_mut() {
-                extensible_values.push(StringRange::Range(start, end + is_inclusive as u32));
+                extensible_values.push(range);
             } else {
-                values.push(StringRange::Range(start, end + is_inclusive as u32));
+                values.push(range);
             }
             skip_comma(&content);
         }
-        let into_flat_set = |constraints: Vec<_>| {
-            let mut set = constraints
-                .iter()
-                .flat_map(|from| match from {
-                    StringRange::Single(value) => vec![*value],
-                    StringRange::Range(start, end) => (*start..*end).collect::<Vec<u32>>(),
-                })
-                .collect::<Vec<u32>>();
-            set.sort();
-            set.dedup();
-            set
-        };
 
         Ok(Constraint {
-            constraint: Self((into_flat_set)(values)),
-            extensible: extensible.map(|values| vec![Self((into_flat_set)(values))]),
+            constraint: Self(merge_ranges(values)),
+            extensible: extensible.map(|values| vec![Self(merge_ranges(values))]),
         })
     }
 }
 
+/// Sorts inclusive character ranges, and joins the ones that overlap or touch.
+fn merge_ranges(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
 #[derive(Clone, Debug)]
 pub enum Value {
     Single(i128),
@@ -1571,6 +1812,214 @@ impl Value {
     }
 }
 
+/// E-XER encoding instructions set with `#[rasn(xer(...))]`.
+#[derive(Clone, Debug, Default)]
+pub struct XerInstructions {
+    pub name: Option<LitStr>,
+    pub attribute: bool,
+    pub untagged: bool,
+    pub list: bool,
+    pub text: bool,
+    pub default_for_empty: Option<LitStr>,
+    pub namespace: Option<LitStr>,
+    pub prefix: Option<LitStr>,
+    pub use_type: bool,
+}
+
+impl XerInstructions {
+    fn from_meta(item: &syn::meta::ParseNestedMeta) -> syn::Result<Self> {
+        let mut instructions = Self::default();
+        item.parse_nested_meta(|meta| {
+            let path = &meta.path;
+            if path.is_ident("name") {
+                instructions.name = Some(meta.value()?.parse()?);
+            } else if path.is_ident("attribute") {
+                instructions.attribute = true;
+            } else if path.is_ident("untagged") {
+                instructions.untagged = true;
+            } else if path.is_ident("list") {
+                instructions.list = true;
+            } else if path.is_ident("text") {
+                instructions.text = true;
+            } else if path.is_ident("default_for_empty") {
+                instructions.default_for_empty = Some(meta.value()?.parse()?);
+            } else if path.is_ident("namespace") {
+                instructions.namespace = Some(meta.value()?.parse()?);
+            } else if path.is_ident("prefix") {
+                instructions.prefix = Some(meta.value()?.parse()?);
+            } else if path.is_ident("use_type") {
+                instructions.use_type = true;
+            } else {
+                return Err(meta.error(format!(
+                    "unknown XER encoding instruction: {}",
+                    path.to_token_stream()
+                )));
+            }
+            Ok(())
+        })?;
+
+        if instructions.attribute && instructions.untagged {
+            return Err(item.error("`attribute` and `untagged` cannot be used together"));
+        }
+        if instructions.prefix.is_some() && instructions.namespace.is_none() {
+            return Err(item.error("`prefix` requires a `namespace`"));
+        }
+
+        Ok(instructions)
+    }
+
+    /// Rejects instructions that only apply to components of a `SEQUENCE` or `SET`.
+    fn check_type_instructions(&self, span: Span) -> syn::Result<()> {
+        if self.name.is_some()
+            || self.attribute
+            || self.untagged
+            || self.list
+            || self.default_for_empty.is_some()
+        {
+            return Err(syn::Error::new(
+                span,
+                "`name`, `attribute`, `untagged`, `list` and `default_for_empty` can only be used on fields",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rejects instructions that only apply to types.
+    fn check_field_instructions(&self, span: Span) -> syn::Result<()> {
+        if self.namespace.is_some() || self.use_type {
+            return Err(syn::Error::new(
+                span,
+                "`namespace` and `use_type` can only be used on types",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && !self.attribute
+            && !self.untagged
+            && !self.list
+            && !self.text
+            && self.default_for_empty.is_none()
+            && self.namespace.is_none()
+            && !self.use_type
+    }
+
+    pub fn to_tokens(&self, crate_root: &syn::Path) -> proc_macro2::TokenStream {
+        fn optional(value: Option<&LitStr>) -> proc_macro2::TokenStream {
+            value.map_or(quote!(None), |value| quote!(Some(#value)))
+        }
+
+        let name = optional(self.name.as_ref());
+        let default_for_empty = optional(self.default_for_empty.as_ref());
+        let namespace = optional(self.namespace.as_ref());
+        let prefix = optional(self.prefix.as_ref());
+        let Self {
+            attribute,
+            untagged,
+            list,
+            text,
+            use_type,
+            ..
+        } = self;
+
+        quote!(#crate_root::types::instructions::XerInstructions {
+            name: #name,
+            attribute: #attribute,
+            untagged: #untagged,
+            list: #list,
+            text: #text,
+            default_for_empty: #default_for_empty,
+            namespace: #namespace,
+            prefix: #prefix,
+            use_type: #use_type,
+        })
+    }
+}
+
+/// JER encoding instructions set with `#[rasn(jer(...))]`.
+#[derive(Clone, Debug, Default)]
+pub struct JerInstructions {
+    pub name: Option<LitStr>,
+    pub array: bool,
+    pub unwrapped: bool,
+    pub base64: bool,
+    pub text: bool,
+    pub object: bool,
+}
+
+impl JerInstructions {
+    fn from_meta(item: &syn::meta::ParseNestedMeta) -> syn::Result<Self> {
+        let mut instructions = Self::default();
+        item.parse_nested_meta(|meta| {
+            let path = &meta.path;
+            if path.is_ident("name") {
+                instructions.name = Some(meta.value()?.parse()?);
+            } else if path.is_ident("array") {
+                instructions.array = true;
+            } else if path.is_ident("unwrapped") {
+                instructions.unwrapped = true;
+            } else if path.is_ident("base64") {
+                instructions.base64 = true;
+            } else if path.is_ident("text") {
+                instructions.text = true;
+            } else if path.is_ident("object") {
+                instructions.object = true;
+            } else {
+                return Err(meta.error(format!(
+                    "unknown JER encoding instruction: {}",
+                    path.to_token_stream()
+                )));
+            }
+            Ok(())
+        })?;
+
+        Ok(instructions)
+    }
+
+    /// Rejects instructions that only apply to components of a `SEQUENCE` or `SET`.
+    fn check_type_instructions(&self, span: Span) -> syn::Result<()> {
+        if self.name.is_some() {
+            return Err(syn::Error::new(span, "`name` can only be used on fields"));
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && !self.array
+            && !self.unwrapped
+            && !self.base64
+            && !self.text
+            && !self.object
+    }
+
+    pub fn to_tokens(&self, crate_root: &syn::Path) -> proc_macro2::TokenStream {
+        let name = self
+            .name
+            .as_ref()
+            .map_or(quote!(None), |name| quote!(Some(#name)));
+        let Self {
+            array,
+            unwrapped,
+            base64,
+            text,
+            object,
+            ..
+        } = self;
+
+        quote!(#crate_root::types::instructions::JerInstructions {
+            name: #name,
+            array: #array,
+            unwrapped: #unwrapped,
+            base64: #base64,
+            text: #text,
+            object: #object,
+        })
+    }
+}
+
 fn skip_comma(content: &syn::parse::ParseBuffer) {
     if content.peek(Token![,]) {
         let _: Token![,] = content.parse().unwrap();
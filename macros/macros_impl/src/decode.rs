@@ -147,6 +147,7 @@ pub fn derive_struct_impl(
                     quote!(<_>::decode(decoder)?)
                 };
                 let ident = &config.field.ident;
+                let trace_name = config.trace_name();
 
                 let set_field_impl = if config.extension_addition || config.extension_addition_group {
                     quote! {
@@ -166,7 +167,10 @@ pub fn derive_struct_impl(
 
                 (
                     quote!(const #const_name: #crate_root::types::Tag = #tag;),
-                    quote!((#context, #const_name) => { #choice_name::#field_name(#decode_impl) }),
+                    quote!((#context, #const_name) => {
+                        #crate_root::__trace_field!(#trace_name);
+                        #choice_name::#field_name(#decode_impl)
+                    }),
                     quote!(#choice_name::#field_name(value) => { #set_field_impl })
                 )
             }));
@@ -39,14 +39,20 @@ pub fn derive_struct_impl(
             number_root_fields += 1;
         }
 
+        let trace_name = field_config.trace_name();
         field_encodings.push(quote! {
-            #field_encoding
+            {
+                #crate_root::__trace_field!(#trace_name);
+                #field_encoding
+            }
         });
     }
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let encode_impl = if config.delegate {
+    let encode_impl = if config.named_bits.is_some() {
+        crate::named_bits::encode_impl(config)
+    } else if config.delegate {
         let ty = &container.fields.iter().next().unwrap().ty;
 
         if config.tag.as_ref().is_some_and(|tag| tag.is_explicit()) {
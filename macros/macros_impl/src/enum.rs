@@ -175,6 +175,14 @@ impl Enum<'_> {
             quote!(const IDENTIFIER: #crate_root::types::Identifier = #crate_root::types::Identifier(Some(#name_literal));),
             |id| quote!(const IDENTIFIER: #crate_root::types::Identifier = #crate_root::types::Identifier(Some(#id));),
         );
+        let xer_instructions_def = (!self.config.xer.is_empty()).then(|| {
+            let xer = self.config.xer.to_tokens(crate_root);
+            quote!(const XER_INSTRUCTIONS: #crate_root::types::instructions::XerInstructions = #xer;)
+        });
+        let jer_instructions_def = (!self.config.jer.is_empty()).then(|| {
+            let jer = self.config.jer.to_tokens(crate_root);
+            quote!(const JER_INSTRUCTIONS: #crate_root::types::instructions::JerInstructions = #jer;)
+        });
         let is_choice = if self.config.choice {
             quote!(true)
         } else {
@@ -197,6 +205,8 @@ impl Enum<'_> {
                 #alt_identifier
                 #constraints_def
                 const IS_CHOICE: bool = #is_choice;
+                #xer_instructions_def
+                #jer_instructions_def
 
             }
 
@@ -401,6 +411,7 @@ impl Enum<'_> {
             .identifier
             .clone()
             .unwrap_or(LitStr::new(&variant_config.variant.ident.to_string(), Span::call_site()));
+            let trace_name = variant_config.trace_name();
 
             match &v.fields {
                 syn::Fields::Named(_) => {
@@ -425,7 +436,10 @@ impl Enum<'_> {
                         None,
                     );
 
-                    quote!(#name::#ident { #(#idents: #idents_prefixed),* } => { #encode_impl.map(|_| #tag_tokens) })
+                    quote!(#name::#ident { #(#idents: #idents_prefixed),* } => {
+                        #crate_root::__trace_field!(#trace_name);
+                        #encode_impl.map(|_| #tag_tokens)
+                    })
                 }
                 syn::Fields::Unnamed(_) => {
                     // Assert already checked in FieldConfig
@@ -457,6 +471,7 @@ impl Enum<'_> {
 
                     quote! {
                         #name::#ident(value) => {
+                            #crate_root::__trace_field!(#trace_name);
                             #encode_operation.map(|_| #variant_tag)
                         }
                     }
@@ -469,7 +484,10 @@ impl Enum<'_> {
                         quote!(encoder.encode_null(#variant_tag, #crate_root::types::Identifier(Some(#variant_identifier))))
                     };
 
-                    quote!(#name::#ident => #encode_operation.map(|_| #variant_tag))
+                    quote!(#name::#ident => {
+                        #crate_root::__trace_field!(#trace_name);
+                        #encode_operation.map(|_| #variant_tag)
+                    })
                 }
             }
         });
@@ -1,12 +1,16 @@
 #[macro_use]
 extern crate quote;
 
+mod arbitrary;
 mod asn_type;
+mod builder;
 mod config;
 mod decode;
 mod encode;
 mod r#enum;
 mod ext;
+mod named_bits;
+mod serde;
 mod tag;
 
 use crate::ext::GenericsExt;
@@ -21,13 +25,16 @@ pub fn decode_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::Token
     let mut generics = input.generics;
     let crate_root = &config.crate_root;
     generics.add_trait_bounds(crate_root, quote::format_ident!("Decode"));
+    let deserialize = config
+        .serde
+        .then(|| serde::deserialize_impl(name, &generics, crate_root));
 
-    match input.data {
+    let decode = match input.data {
         // Unit structs are treated as ASN.1 NULL values.
         syn::Data::Struct(DataStruct {
             fields: syn::Fields::Unit,
             ..
-        }) => Ok(quote! {
+        }) => quote! {
             impl #crate_root::Decode for #name {
                 fn decode_with_tag_and_constraints<D: #crate_root::Decoder>(
                     decoder: &mut D,
@@ -37,20 +44,24 @@ pub fn decode_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::Token
                     decoder.decode_null(tag).map(|_| #name)
                 }
             }
-        }),
-        syn::Data::Struct(v) => decode::derive_struct_impl(name, generics, v, &config),
+        },
+        syn::Data::Struct(v) => decode::derive_struct_impl(name, generics, v, &config)?,
         syn::Data::Enum(syn::DataEnum { variants, .. }) => r#enum::Enum {
             name,
             generics: &generics,
             variants: &variants,
             config: &config,
         }
-        .impl_decode(),
-        _ => Err(syn::Error::new(
-            name.span(),
-            "Union types are not supported.",
-        )),
-    }
+        .impl_decode()?,
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "Union types are not supported.",
+            ));
+        }
+    };
+
+    Ok(quote!(#decode #deserialize))
 }
 
 pub fn encode_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
@@ -59,8 +70,11 @@ pub fn encode_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::Token
     let mut generics = input.generics;
     let crate_root = &config.crate_root;
     generics.add_trait_bounds(crate_root, quote::format_ident!("Encode"));
+    let serialize = config
+        .serde
+        .then(|| serde::serialize_impl(name, &generics, crate_root));
 
-    Ok(match input.data {
+    let encode = match input.data {
         // Unit structs are treated as ASN.1 NULL values.
         syn::Data::Struct(DataStruct {
             fields: syn::Fields::Unit,
@@ -92,7 +106,9 @@ pub fn encode_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::Token
                 "Union types are not supported.",
             ));
         }
-    })
+    };
+
+    Ok(quote!(#encode #serialize))
 }
 
 pub fn asn_type_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
@@ -108,8 +124,23 @@ pub fn asn_type_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::Tok
         }
     }
 
-    Ok(match input.data {
-        syn::Data::Struct(v) => asn_type::derive_struct_impl(name, generics, v, &config)?,
+    let arbitrary = config
+        .arbitrary
+        .then(|| arbitrary::derive_arbitrary(name, &generics, &input.data, &config))
+        .transpose()?;
+
+    let asn_type = match input.data {
+        syn::Data::Struct(v) => {
+            let builder = config
+                .builder
+                .then(|| builder::derive_builder(name, &input.vis, &generics, &v, &config))
+                .transpose()?;
+            let accessors = config.named_bits.as_deref().map(|named_bits| {
+                named_bits::derive_accessors(name, &input.vis, &generics, named_bits, &config)
+            });
+            let asn_type = asn_type::derive_struct_impl(name, generics, v, &config)?;
+            quote!(#asn_type #builder #accessors)
+        }
         syn::Data::Enum(syn::DataEnum { variants, .. }) => r#enum::Enum {
             name,
             generics: &generics,
@@ -123,5 +154,7 @@ pub fn asn_type_derive_inner(input: DeriveInput) -> syn::Result<proc_macro2::Tok
                 "Union types are not supported.",
             ));
         }
-    })
+    };
+
+    Ok(quote!(#asn_type #arbitrary))
 }
@@ -0,0 +1,76 @@
+//! Generation of accessors and encoding for `BIT STRING`s with named bit lists.
+
+use syn::ext::IdentExt;
+
+use crate::config::{Config, NamedBit};
+
+pub fn derive_accessors(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    named_bits: &[NamedBit],
+    config: &Config,
+) -> proc_macro2::TokenStream {
+    let crate_root = &config.crate_root;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let accessors = named_bits.iter().map(|NamedBit { name, position }| {
+        let bit = name.unraw().to_string();
+        let constant = quote::format_ident!("{}", bit.to_uppercase());
+        let setter = quote::format_ident!("set_{}", name);
+        let constant_doc = format!("The position of the `{bit}` bit.");
+        let getter_doc = format!("Returns whether the `{bit}` bit is set.");
+        let setter_doc = format!("Sets or clears the `{bit}` bit.");
+
+        quote! {
+            #[doc = #constant_doc]
+            #vis const #constant: usize = #position;
+
+            #[doc = #getter_doc]
+            #[must_use]
+            #vis fn #name(&self) -> bool {
+                self.0.get(Self::#constant).is_some_and(|bit| *bit)
+            }
+
+            #[doc = #setter_doc]
+            #vis fn #setter(&mut self, value: bool) {
+                #crate_root::types::set_named_bit(&mut self.0, Self::#constant, value);
+            }
+        }
+    });
+
+    quote! {
+        // Not every named bit has to be read or set, so not every accessor is
+        // used.
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    }
+}
+
+/// Encodes the wrapped `BIT STRING` without its trailing zero bits, which
+/// the canonical encoding rules require for named bit lists.
+pub fn encode_impl(config: &Config) -> proc_macro2::TokenStream {
+    let crate_root = &config.crate_root;
+
+    if config.has_explicit_tag() {
+        quote! {
+            let bits = #crate_root::types::trim_named_bits(&self.0, &constraints).into_owned();
+            encoder
+                .encode_explicit_prefix(tag, &bits, identifier.or(Self::IDENTIFIER))
+                .map(drop)
+        }
+    } else {
+        quote! {
+            encoder
+                .encode_bit_string(
+                    tag,
+                    constraints,
+                    &#crate_root::types::trim_named_bits(&self.0, &constraints),
+                    identifier.or(Self::IDENTIFIER),
+                )
+                .map(drop)
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Generation of `serde` implementations that use the JER mapping of a type.
+
+use syn::parse_quote;
+
+pub fn serialize_impl(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    crate_root: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_root::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<S: #crate_root::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                #crate_root::serde::jer::serialize(self, serializer)
+            }
+        }
+    }
+}
+
+pub fn deserialize_impl(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    crate_root: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let mut de_generics = generics.clone();
+    de_generics.params.insert(0, parse_quote!('de));
+    let (impl_generics, _, _) = de_generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_root::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+            fn deserialize<D: #crate_root::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                #crate_root::serde::jer::deserialize(deserializer)
+            }
+        }
+    }
+}
@@ -46,12 +46,28 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 ///   specified with this attribute. E.g. `#[rasn(tag(context, 0))]`, you can also
 ///   wrapp `[class], number` in `explicit` to mark it as a explicit tag
 ///   (e.g.  `#[rasn(tag(explicit(0)))]`.)
+/// - *`value(range)`* — a value constraint for an `INTEGER`, e.g.
+///   `#[rasn(value("0..=255"))]`. When the type is a primitive integer such as
+///   `u8` (or an `Option` of one), the range must fit into it, which is checked
+///   at compile time.
+/// - *`from(characters)`* — a permitted alphabet constraint for a string, made
+///   of single characters and ranges of characters, given either as strings or
+///   as character codes, e.g. `#[rasn(from("A..=Z", "0..=9", 0x5F))]`.
 ///
 /// ##### Container Attributes
 /// - `crate_root` The path to the `rasn` library to use in the macro.
 /// - `enumerated/choice` Use either `#[rasn(choice)]` or `#[rasn(enumerated)]`
 /// - `delegate` Only available for newtype wrappers (e.g. `struct Delegate(T)`);
 ///   uses the inner `T` type for implementing the trait. Tuple-struct can have more than one field if other fields are `PhantomData` types.
+/// - `serde` Additionally implements `serde::Serialize` when deriving `Encode`
+///   and `serde::Deserialize` when deriving `Decode`, using the JER mapping of
+///   the type. Requires the `serde` feature of `rasn`.
+/// - `named_bits(name = position, ...)` Only available for newtype wrappers of
+///   `BitString`; defines a `BIT STRING` with a named bit list. Generates a
+///   `NAME` constant with the position of each bit, and `name()` and
+///   `set_name(bool)` accessors. Trailing zero bits are removed when encoding,
+///   as X.690 11.2.2 and X.691 16.2 require for named bit lists.
+///   E.g. `#[rasn(named_bits(digital_signature = 0, non_repudiation = 1))]`.
 #[proc_macro_derive(AsnType, attributes(rasn))]
 pub fn asn_type_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -28,6 +28,14 @@ pub fn encode<T: crate::Encode>(
     crate::per::encode(enc::EncoderOptions::aligned(), value)
 }
 
+/// Returns the length of the APER-CANONICAL encoding of `value`, without
+/// encoding it.
+/// # Errors
+/// Returns error specific to APER encoder if encoding is not possible.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::error::EncodeError> {
+    crate::per::encoded_len(enc::EncoderOptions::aligned(), value)
+}
+
 /// Encodes `value` to APER-CANONICAL into an existing `buffer`, reusing its allocation.
 /// The buffer is cleared before encoding.
 /// # Errors
@@ -58,7 +66,9 @@ pub fn encode_with_constraints<T: crate::Encode>(
 #[cfg(test)]
 mod tests {
     use crate::{
+        Codec,
         prelude::*,
+        testing::{round_trip_encoding, round_trip_encoding_with_constraints},
         types::{constraints::*, *},
     };
 
@@ -113,11 +123,10 @@ mod tests {
         // N ::= BIT STRING (SIZE(0..65535))
         // O ::= BIT STRING (SIZE(0..65536))
 
-        round_trip!(
-            aper,
-            BitString,
-            bitvec::bitvec![u8, Msb0; 0, 1, 0, 0],
-            &[0x04, 0x40]
+        round_trip_encoding::<BitString>(
+            Codec::Aper,
+            &bitvec::bitvec![u8, Msb0; 0, 1, 0, 0],
+            &[0x04, 0x40],
         );
         // round_trip!(
         //     aper,
@@ -134,16 +143,15 @@ mod tests {
         //         bytes
         //     }
         // );
-        round_trip!(
-            aper,
-            BitString,
-            BitString::from_vec([0x55; 2048].into()),
-            &*{
+        round_trip_encoding::<BitString>(
+            Codec::Aper,
+            &BitString::from_vec([0x55; 2048].into()),
+            &{
                 let mut bytes = vec![0xc1];
                 bytes.extend([0x55; 2048]);
                 bytes.push(0x00);
                 bytes
-            }
+            },
         );
         // round_trip!(aper, B, (b'\x12\x80', 9), b'\x12\x80');
         // round_trip!(aper, C, (b'\x34', 6), b'\x40\x34');
@@ -221,113 +229,109 @@ mod tests {
             c: bool,
         }
 
-        round_trip!(aper, Integer, 32768.into(), &[0x03, 0x00, 0x80, 0x00]);
-        round_trip!(aper, Integer, 32767.into(), &[0x02, 0x7f, 0xff]);
-        round_trip!(aper, Integer, 256.into(), &[0x02, 0x01, 0x00]);
-        round_trip!(aper, Integer, 255.into(), &[0x02, 0x00, 0xff]);
-        round_trip!(aper, Integer, 128.into(), &[0x02, 0x00, 0x80]);
-        round_trip!(aper, Integer, 127.into(), &[0x01, 0x7f]);
-        round_trip!(aper, Integer, 1.into(), &[0x01, 0x01]);
-        round_trip!(aper, Integer, 0.into(), &[0x01, 0x00]);
-        round_trip!(aper, Integer, (-1).into(), &[0x01, 0xff]);
-        round_trip!(aper, Integer, (-128).into(), &[0x01, 0x80]);
-        round_trip!(aper, Integer, (-129).into(), &[0x02, 0xff, 0x7f]);
-        round_trip!(aper, Integer, (-256).into(), &[0x02, 0xff, 0x00]);
-        round_trip!(aper, Integer, (-32768).into(), &[0x02, 0x80, 0x00]);
-        round_trip!(aper, Integer, (-32769).into(), &[0x03, 0xff, 0x7f, 0xff]);
-        round_trip!(aper, B, B::new(5), &[0x00]);
-        round_trip!(aper, B, B::new(6), &[0x02]);
-        round_trip!(aper, B, B::new(99), &[0xbc]);
-        round_trip!(
-            aper,
-            C,
-            C {
+        round_trip_encoding::<Integer>(Codec::Aper, &32768.into(), &[0x03, 0x00, 0x80, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Aper, &32767.into(), &[0x02, 0x7f, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Aper, &256.into(), &[0x02, 0x01, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Aper, &255.into(), &[0x02, 0x00, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Aper, &128.into(), &[0x02, 0x00, 0x80]);
+        round_trip_encoding::<Integer>(Codec::Aper, &127.into(), &[0x01, 0x7f]);
+        round_trip_encoding::<Integer>(Codec::Aper, &1.into(), &[0x01, 0x01]);
+        round_trip_encoding::<Integer>(Codec::Aper, &0.into(), &[0x01, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Aper, &(-1).into(), &[0x01, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Aper, &(-128).into(), &[0x01, 0x80]);
+        round_trip_encoding::<Integer>(Codec::Aper, &(-129).into(), &[0x02, 0xff, 0x7f]);
+        round_trip_encoding::<Integer>(Codec::Aper, &(-256).into(), &[0x02, 0xff, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Aper, &(-32768).into(), &[0x02, 0x80, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Aper, &(-32769).into(), &[0x03, 0xff, 0x7f, 0xff]);
+        round_trip_encoding::<B>(Codec::Aper, &B::new(5), &[0x00]);
+        round_trip_encoding::<B>(Codec::Aper, &B::new(6), &[0x02]);
+        round_trip_encoding::<B>(Codec::Aper, &B::new(99), &[0xbc]);
+        round_trip_encoding::<C>(
+            Codec::Aper,
+            &(C {
                 a: true,
                 b: Integer::from(43_554_344_223_i64),
                 c: false,
-                d: Integer::from(-9)
-            },
-            &[0x80, 0x05, 0x0a, 0x24, 0x0a, 0x8d, 0x1f, 0x00, 0x00, 0x01]
+                d: Integer::from(-9),
+            }),
+            &[0x80, 0x05, 0x0a, 0x24, 0x0a, 0x8d, 0x1f, 0x00, 0x00, 0x01],
         );
-        round_trip!(aper, D, D::new(253), &[0xfd]);
-        round_trip!(aper, E, E::new(253), &[0xfd]);
-        round_trip!(aper, F, F::new(253), &[0x00, 0xfd]);
-        round_trip!(aper, G, G::new(253), &[0x00, 0xfd]);
-        round_trip!(aper, H, H::new(253), &[0x00, 0xfd]);
-        round_trip!(aper, H, H::new(256), &[0x40, 0x01, 0x00]);
-        round_trip!(aper, H, H::new(65536), &[0x80, 0x01, 0x00, 0x00]);
-        round_trip!(aper, I, I::new(0), &[0x00, 0x00]);
-        round_trip!(aper, I, I::new(1), &[0x00, 0x01]);
-        round_trip!(
-            aper,
-            I,
-            I::new(10_000_000_000_i64),
-            &[0x80, 0x02, 0x54, 0x0b, 0xe4, 0x00]
+        round_trip_encoding::<D>(Codec::Aper, &D::new(253), &[0xfd]);
+        round_trip_encoding::<E>(Codec::Aper, &E::new(253), &[0xfd]);
+        round_trip_encoding::<F>(Codec::Aper, &F::new(253), &[0x00, 0xfd]);
+        round_trip_encoding::<G>(Codec::Aper, &G::new(253), &[0x00, 0xfd]);
+        round_trip_encoding::<H>(Codec::Aper, &H::new(253), &[0x00, 0xfd]);
+        round_trip_encoding::<H>(Codec::Aper, &H::new(256), &[0x40, 0x01, 0x00]);
+        round_trip_encoding::<H>(Codec::Aper, &H::new(65536), &[0x80, 0x01, 0x00, 0x00]);
+        round_trip_encoding::<I>(Codec::Aper, &I::new(0), &[0x00, 0x00]);
+        round_trip_encoding::<I>(Codec::Aper, &I::new(1), &[0x00, 0x01]);
+        round_trip_encoding::<I>(
+            Codec::Aper,
+            &I::new(10_000_000_000_i64),
+            &[0x80, 0x02, 0x54, 0x0b, 0xe4, 0x00],
         );
-        round_trip!(
-            aper,
-            J,
-            J {
+        round_trip_encoding::<J>(
+            Codec::Aper,
+            &(J {
                 a: false,
                 b: Integer::from(253),
                 c: Integer::from(253),
                 d: false,
-                e: Integer::from(253)
-            },
-            &[0x7e, 0x80, 0xfd, 0x00, 0x00, 0xfd]
+                e: Integer::from(253),
+            }),
+            &[0x7e, 0x80, 0xfd, 0x00, 0x00, 0xfd],
         );
-        round_trip!(
-            aper,
-            L,
-            L {
-                a: Integer::from(7)
-            },
-            &[]
+        round_trip_encoding::<L>(
+            Codec::Aper,
+            &(L {
+                a: Integer::from(7),
+            }),
+            &[],
         );
         // round_trip!(aper, M, 103.into(), &[0x80, 0x01, 0x67]);
-        round_trip!(aper, N, N::new(1), &[0x00, 0x01]);
-        round_trip!(aper, N, N::new(255), &[0x00, 0xff]);
-        round_trip!(aper, N, N::new(256), &[0x01, 0x00]);
-        round_trip!(aper, N, N::new(65535), &[0xff, 0xff]);
-        round_trip!(aper, O, O::new(1), &[0x00, 0x01]);
-        round_trip!(aper, O, O::new(255), &[0x00, 0xff]);
-        round_trip!(aper, O, O::new(256), &[0x40, 0x01, 0x00]);
-        round_trip!(aper, O, O::new(65535), &[0x40, 0xff, 0xff]);
-        round_trip!(aper, O, O::new(65536), &[0x80, 0x01, 0x00, 0x00]);
-        round_trip!(aper, P, P::new(1), &[0x00, 0x01]);
-        round_trip!(aper, P, P::new(255), &[0x00, 0xff]);
-        round_trip!(aper, P, P::new(256), &[0x40, 0x01, 0x00]);
-        round_trip!(aper, P, P::new(65535), &[0x40, 0xff, 0xff]);
-        round_trip!(aper, P, P::new(65536), &[0x80, 0x01, 0x00, 0x00]);
-        round_trip!(aper, P, P::new(16_777_215), &[0x80, 0xff, 0xff, 0xff]);
-        round_trip!(aper, P, P::new(16_777_216), &[0xc0, 0x01, 0x00, 0x00, 0x00]);
-        round_trip!(
-            aper,
-            P,
-            P::new(100_000_000),
-            &[0xc0, 0x05, 0xf5, 0xe1, 0x00]
+        round_trip_encoding::<N>(Codec::Aper, &N::new(1), &[0x00, 0x01]);
+        round_trip_encoding::<N>(Codec::Aper, &N::new(255), &[0x00, 0xff]);
+        round_trip_encoding::<N>(Codec::Aper, &N::new(256), &[0x01, 0x00]);
+        round_trip_encoding::<N>(Codec::Aper, &N::new(65535), &[0xff, 0xff]);
+        round_trip_encoding::<O>(Codec::Aper, &O::new(1), &[0x00, 0x01]);
+        round_trip_encoding::<O>(Codec::Aper, &O::new(255), &[0x00, 0xff]);
+        round_trip_encoding::<O>(Codec::Aper, &O::new(256), &[0x40, 0x01, 0x00]);
+        round_trip_encoding::<O>(Codec::Aper, &O::new(65535), &[0x40, 0xff, 0xff]);
+        round_trip_encoding::<O>(Codec::Aper, &O::new(65536), &[0x80, 0x01, 0x00, 0x00]);
+        round_trip_encoding::<P>(Codec::Aper, &P::new(1), &[0x00, 0x01]);
+        round_trip_encoding::<P>(Codec::Aper, &P::new(255), &[0x00, 0xff]);
+        round_trip_encoding::<P>(Codec::Aper, &P::new(256), &[0x40, 0x01, 0x00]);
+        round_trip_encoding::<P>(Codec::Aper, &P::new(65535), &[0x40, 0xff, 0xff]);
+        round_trip_encoding::<P>(Codec::Aper, &P::new(65536), &[0x80, 0x01, 0x00, 0x00]);
+        round_trip_encoding::<P>(Codec::Aper, &P::new(16_777_215), &[0x80, 0xff, 0xff, 0xff]);
+        round_trip_encoding::<P>(
+            Codec::Aper,
+            &P::new(16_777_216),
+            &[0xc0, 0x01, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<P>(
+            Codec::Aper,
+            &P::new(100_000_000),
+            &[0xc0, 0x05, 0xf5, 0xe1, 0x00],
         );
-        round_trip!(
-            aper,
-            Q,
-            Q::new(4_294_967_295_u64),
-            &[0xc0, 0xff, 0xff, 0xff, 0xff]
+        round_trip_encoding::<Q>(
+            Codec::Aper,
+            &Q::new(4_294_967_295_u64),
+            &[0xc0, 0xff, 0xff, 0xff, 0xff],
         );
-        round_trip!(
-            aper,
-            R,
-            R::new(4_294_967_296_u64),
-            &[0x80, 0x01, 0x00, 0x00, 0x00, 0x00]
+        round_trip_encoding::<R>(
+            Codec::Aper,
+            &R::new(4_294_967_296_u64),
+            &[0x80, 0x01, 0x00, 0x00, 0x00, 0x00],
         );
-        round_trip!(
-            aper,
-            S,
-            S {
+        round_trip_encoding::<S>(
+            Codec::Aper,
+            &(S {
                 a: true,
                 b: 0.into(),
-                c: true
-            },
-            &[0x90, 0x27, 0x10, 0x80]
+                c: true,
+            }),
+            &[0x90, 0x27, 0x10, 0x80],
         );
     }
 
@@ -462,21 +466,19 @@ mod tests {
             ]),
             size_constraint!(1, 255)
         );
-        round_trip_with_constraints!(
-            aper,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Aper,
             PERMITTED_CONSTRAINT,
-            VisibleString::try_from("hej").unwrap(),
-            &[0x02, 0x68, 0x65, 0x6a]
+            &VisibleString::try_from("hej").unwrap(),
+            &[0x02, 0x68, 0x65, 0x6a],
         );
         const PERMITTED_CONSTRAINT_2: Constraints =
             constraints!(permitted_alphabet_constraint!(&[b'a' as u32]));
-        round_trip_with_constraints!(
-            aper,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Aper,
             PERMITTED_CONSTRAINT_2,
-            VisibleString::try_from("a").unwrap(),
-            &[0x01]
+            &VisibleString::try_from("a").unwrap(),
+            &[0x01],
         );
     }
 
@@ -504,7 +506,56 @@ mod tests {
 
         let msg = Message::Updates(Updates { updates: vec![1] });
 
-        round_trip!(aper, Message, msg, &[0, 1, 1]);
+        round_trip_encoding::<Message>(Codec::Aper, &msg, &[0, 1, 1]);
+    }
+
+    #[test]
+    fn aligned_elements_after_constrained_length() {
+        #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+        #[rasn(automatic_tags, crate_root = "crate")]
+        struct Tags {
+            #[rasn(size("1..=4"))]
+            tags: SequenceOf<VisibleString>,
+        }
+
+        // The two bits of the length are followed by the padding of the
+        // string's length, which is octet-aligned.
+        round_trip_encoding(
+            Codec::Aper,
+            &Tags {
+                tags: vec![VisibleString::try_from("AA").unwrap()],
+            },
+            &[0x00, 0x02, 0x41, 0x41],
+        );
+    }
+
+    #[test]
+    fn aligned_sequence_after_choice_index() {
+        #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+        #[rasn(automatic_tags, crate_root = "crate")]
+        struct Inner {
+            flag: bool,
+            data: OctetString,
+        }
+
+        #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+        #[rasn(choice, automatic_tags, crate_root = "crate")]
+        enum Choice {
+            A(()),
+            B(()),
+            C(Inner),
+        }
+
+        // The padding before the length of `data` accounts for the two bits
+        // of the index as well as the boolean.
+        round_trip_encoding(
+            Codec::Aper,
+            &Choice::C(Inner {
+                flag: true,
+                data: vec![0xAB].into(),
+            }),
+            &[0xA0, 0x01, 0xAB],
+        );
     }
 
     #[test]
@@ -512,7 +563,7 @@ mod tests {
         use crate as rasn;
         use crate::prelude::*;
 
-        const T124_IDENTIFIER_KEY: &Oid = Oid::const_new(&[0, 0, 20, 124, 0, 1]);
+        const T124_IDENTIFIER_KEY: &Oid = crate::oid!([0, 0, 20, 124, 0, 1]);
         #[derive(Debug, AsnType, Encode, rasn::Decode)]
         #[rasn(choice, automatic_tags)]
         enum Key {
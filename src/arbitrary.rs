@@ -0,0 +1,503 @@
+//! Generation of arbitrary values for property-based testing and fuzzing,
+//! using [`arbitrary`](https://docs.rs/arbitrary).
+//!
+//! A value produced by [`Arbitrary`] on its own knows nothing about the
+//! ASN.1 constraints on the field it is stored in, so most of it wouldn't
+//! encode. [`ArbitraryConstrained`] instead generates values that satisfy a
+//! set of [`Constraints`]: integers stay within their value constraint,
+//! strings and collections within their size constraint, and characters
+//! within the permitted alphabet of the field and the character set of the
+//! string type. When a constraint is extensible, a value outside of its root
+//! is occasionally generated as well.
+//!
+//! Types deriving [`AsnType`](crate::AsnType) can opt in with
+//! `#[rasn(arbitrary)]`, which implements both traits with the constraints of
+//! every field. Paired with `testing::round_trip_all_codecs`, from the
+//! `testing` feature, this checks that every codec accepts whatever values a
+//! schema allows.
+//!
+//! ```
+//! use rasn::{arbitrary::{Arbitrary, Unstructured}, prelude::*};
+//!
+//! #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+//! #[rasn(automatic_tags, arbitrary)]
+//! struct Reading {
+//!     #[rasn(size("1..=8"), from("a..=z"))]
+//!     sensor: Ia5String,
+//!     #[rasn(value("-40..=125"))]
+//!     celsius: i16,
+//! }
+//!
+//! let data = [0x5a; 64];
+//! let reading = Reading::arbitrary(&mut Unstructured::new(&data)).unwrap();
+//! # #[cfg(feature = "testing")]
+//! rasn::testing::round_trip_all_codecs(&reading);
+//! ```
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::types::{
+    AsnType, BitString, BmpString, ConstrainedInteger, Constraints, Date, Explicit, FixedBitString,
+    FixedOctetString, GeneralString, GeneralizedTime, GraphicString, Ia5String, Implicit, Integer,
+    IntegerType, NumericString, ObjectIdentifier, OctetString, PrintableString, SetOf,
+    TeletexString, UtcTime, VisibleString, constraints::Bounded, strings::StaticPermittedAlphabet,
+};
+
+pub use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+/// The longest value generated for a size constraint without an upper bound,
+/// or past the lower bound of a size constraint.
+const MAX_LENGTH: usize = 256;
+
+/// The number of arcs after the first two of a generated object identifier.
+const MAX_EXTRA_ARCS: usize = 8;
+
+/// A type whose values can be generated so that they satisfy a set of ASN.1
+/// constraints.
+pub trait ArbitraryConstrained<'a>: Sized {
+    /// Generates a value from `u` that satisfies `constraints`.
+    ///
+    /// # Errors
+    /// Returns an error if `u` doesn't contain enough data, or if no value
+    /// can satisfy `constraints`.
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self>;
+}
+
+/// Generates an integer within `min..=max` that satisfies the value constraint
+/// in `constraints`.
+fn value(
+    u: &mut Unstructured<'_>,
+    constraints: &Constraints,
+    min: i128,
+    max: i128,
+) -> Result<i128> {
+    let Some(value) = constraints.value() else {
+        return u.int_in_range(min..=max);
+    };
+
+    if value.extensible.is_some() && u.ratio(1, 8)? {
+        return u.int_in_range(min..=max);
+    }
+
+    let (start, end) = match *value.constraint {
+        Bounded::None => (min, max),
+        Bounded::Single(value) => (value, value),
+        Bounded::Range { start, end } => (
+            start.map_or(min, |start| start.max(min)),
+            end.map_or(max, |end| end.min(max)),
+        ),
+    };
+
+    if start > end {
+        return Err(Error::IncorrectFormat);
+    }
+
+    u.int_in_range(start..=end)
+}
+
+/// Generates a length that satisfies the size constraint in `constraints`.
+fn size(u: &mut Unstructured<'_>, constraints: &Constraints) -> Result<usize> {
+    let Some(size) = constraints.size() else {
+        return u.int_in_range(0..=MAX_LENGTH);
+    };
+
+    let (start, end) = match size.constraint.0 {
+        Bounded::None => (0, MAX_LENGTH),
+        Bounded::Single(length) => (length, length),
+        Bounded::Range { start, end } => (start.unwrap_or(0), end.unwrap_or(usize::MAX)),
+    };
+
+    if size.extensible.is_some() && end < usize::MAX - 4 && u.ratio(1, 8)? {
+        return u.int_in_range(end + 1..=end + 4);
+    }
+
+    if start > end {
+        return Err(Error::IncorrectFormat);
+    }
+
+    u.int_in_range(start..=end.min(start.saturating_add(MAX_LENGTH)))
+}
+
+/// Generates `length` characters, each of them chosen from `alphabet`.
+fn characters(u: &mut Unstructured<'_>, alphabet: &[u32], length: usize) -> Result<Vec<u32>> {
+    if alphabet.is_empty() && length > 0 {
+        return Err(Error::IncorrectFormat);
+    }
+
+    (0..length).map(|_| u.choose(alphabet).copied()).collect()
+}
+
+/// Generates a restricted character string, whose characters are in both the
+/// character set of the string type and the permitted alphabet constraint.
+fn restricted_string<S: StaticPermittedAlphabet>(
+    u: &mut Unstructured<'_>,
+    constraints: &Constraints,
+) -> Result<S> {
+    let alphabet: Vec<u32> = match constraints.permitted_alphabet() {
+        Some(alphabet) => alphabet
+            .constraint
+            .characters()
+            .filter(|ch| S::contains_char(*ch))
+            .collect(),
+        None => S::CHARACTER_SET.characters().collect(),
+    };
+    let length = size(u, constraints)?;
+    let mut string = S::default();
+    for ch in characters(u, &alphabet, length)? {
+        string.push_char(ch);
+    }
+    Ok(string)
+}
+
+macro_rules! impl_integers {
+    ($($int:ty),+ $(,)?) => {
+        $(
+            impl<'a> ArbitraryConstrained<'a> for $int {
+                fn arbitrary_constrained(
+                    u: &mut Unstructured<'a>,
+                    constraints: Constraints,
+                ) -> Result<Self> {
+                    value(u, &constraints, <$int>::MIN as i128, <$int>::MAX as i128)
+                        .and_then(|value| Self::try_from(value).map_err(|_| Error::IncorrectFormat))
+                }
+            }
+        )+
+    };
+}
+
+impl_integers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl<'a> ArbitraryConstrained<'a> for Integer {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        value(u, &constraints, i128::MIN, i128::MAX).map(Self::from)
+    }
+}
+
+impl<'a, const START: i128, const END: i128, T: IntegerType> ArbitraryConstrained<'a>
+    for ConstrainedInteger<START, END, T>
+{
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        let value = value(u, &constraints, START, END)?;
+        Self::try_from(value).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for bool {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        u.arbitrary()
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for () {
+    fn arbitrary_constrained(_: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl<'a, T> ArbitraryConstrained<'a> for core::marker::PhantomData<T> {
+    fn arbitrary_constrained(_: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for OctetString {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        let length = size(u, &constraints)?;
+        u.bytes(length).map(|bytes| Self::from(bytes.to_vec()))
+    }
+}
+
+impl<'a, const N: usize> ArbitraryConstrained<'a> for FixedOctetString<N> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        u.arbitrary().map(Self::new)
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for BitString {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        let length = size(u, &constraints)?;
+        (0..length).map(|_| u.arbitrary::<bool>()).collect()
+    }
+}
+
+impl<'a, const N: usize> ArbitraryConstrained<'a> for FixedBitString<N> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        u.arbitrary().map(Self::new)
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for String {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        let length = size(u, &constraints)?;
+        match constraints.permitted_alphabet() {
+            Some(alphabet) => {
+                let alphabet: Vec<u32> = alphabet
+                    .constraint
+                    .characters()
+                    .filter(|ch| char::from_u32(*ch).is_some())
+                    .collect();
+                Ok(characters(u, &alphabet, length)?
+                    .into_iter()
+                    .filter_map(char::from_u32)
+                    .collect())
+            }
+            None => (0..length).map(|_| u.arbitrary::<char>()).collect(),
+        }
+    }
+}
+
+macro_rules! impl_restricted_strings {
+    ($($string:ty),+ $(,)?) => {
+        $(
+            impl<'a> ArbitraryConstrained<'a> for $string {
+                fn arbitrary_constrained(
+                    u: &mut Unstructured<'a>,
+                    constraints: Constraints,
+                ) -> Result<Self> {
+                    restricted_string(u, &constraints)
+                }
+            }
+        )+
+    };
+}
+
+impl_restricted_strings!(
+    BmpString,
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    TeletexString,
+    VisibleString,
+);
+
+impl<'a> ArbitraryConstrained<'a> for ObjectIdentifier {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        let mut arcs = Vec::from([u.int_in_range(0..=2)?, u.int_in_range(0..=39)?]);
+        for _ in 0..u.int_in_range(0..=MAX_EXTRA_ARCS)? {
+            arcs.push(u.arbitrary()?);
+        }
+        Self::new(arcs).ok_or(Error::IncorrectFormat)
+    }
+}
+
+/// Picks a date in `years`, as its year, month and day.
+fn date(u: &mut Unstructured<'_>, years: core::ops::RangeInclusive<u16>) -> Result<(u16, u8, u8)> {
+    let year = u.int_in_range(years)?;
+    let month = u.int_in_range(1..=12)?;
+    let day = u.int_in_range(1..=crate::types::time_string::days_in_month(year, month))?;
+    Ok((year, month, day))
+}
+
+/// Picks a time of day without leap seconds, as `hhmmss`.
+fn time_of_day(u: &mut Unstructured<'_>) -> Result<String> {
+    let hour: u8 = u.int_in_range(0..=23)?;
+    let minute: u8 = u.int_in_range(0..=59)?;
+    let second: u8 = u.int_in_range(0..=59)?;
+    Ok(alloc::format!("{hour:02}{minute:02}{second:02}"))
+}
+
+// The time values are picked as canonical text and parsed, so that they are
+// generated the same way for each date and time backend.
+
+impl<'a> ArbitraryConstrained<'a> for UtcTime {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        // UTCTime only has two digits for the year, covering 1950 to 2049.
+        let (year, month, day) = date(u, 1950..=2049)?;
+        let text = alloc::format!("{:02}{month:02}{day:02}{}Z", year % 100, time_of_day(u)?);
+        crate::ber::de::Decoder::parse_canonical_utc_time_string(&text)
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for GeneralizedTime {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        // The years 0001 to 9999, which fit the four digits of the year.
+        let (year, month, day) = date(u, 1..=9999)?;
+        let text = alloc::format!("{year:04}{month:02}{day:02}{}Z", time_of_day(u)?);
+        crate::ber::de::Decoder::parse_canonical_generalized_time_string(text)
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> ArbitraryConstrained<'a> for Date {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        let (year, month, day) = date(u, 1..=9999)?;
+        let text = alloc::format!("{year:04}{month:02}{day:02}");
+        crate::ber::de::Decoder::parse_date_string(&text).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a, T: ArbitraryConstrained<'a>> ArbitraryConstrained<'a> for Option<T> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        if u.arbitrary()? {
+            T::arbitrary_constrained(u, constraints).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, T: ArbitraryConstrained<'a>> ArbitraryConstrained<'a> for Box<T> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        T::arbitrary_constrained(u, constraints).map(Box::new)
+    }
+}
+
+impl<'a, T: AsnType + ArbitraryConstrained<'a>> ArbitraryConstrained<'a> for Vec<T> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        let length = size(u, &constraints)?;
+        (0..length)
+            .map(|_| T::arbitrary_constrained(u, T::CONSTRAINTS))
+            .collect()
+    }
+}
+
+impl<'a, T: AsnType + ArbitraryConstrained<'a>> ArbitraryConstrained<'a> for SetOf<T> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        Vec::<T>::arbitrary_constrained(u, constraints).map(Self::from_vec)
+    }
+}
+
+impl<'a, T: AsnType + ArbitraryConstrained<'a>, const N: usize> ArbitraryConstrained<'a>
+    for [T; N]
+{
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, _: Constraints) -> Result<Self> {
+        (0..N)
+            .map(|_| T::arbitrary_constrained(u, T::CONSTRAINTS))
+            .collect::<Result<Vec<_>>>()?
+            .try_into()
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a, T, V: AsnType + ArbitraryConstrained<'a>> ArbitraryConstrained<'a> for Implicit<T, V> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        V::arbitrary_constrained(u, V::CONSTRAINTS.intersect(constraints)).map(Self::new)
+    }
+}
+
+impl<'a, T, V: AsnType + ArbitraryConstrained<'a>> ArbitraryConstrained<'a> for Explicit<T, V> {
+    fn arbitrary_constrained(u: &mut Unstructured<'a>, constraints: Constraints) -> Result<Self> {
+        V::arbitrary_constrained(u, V::CONSTRAINTS.intersect(constraints)).map(Self::new)
+    }
+}
+
+/// Implements [`Arbitrary`] for types of this crate, with the constraints of
+/// the type.
+macro_rules! impl_arbitrary {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl<'a> Arbitrary<'a> for $ty {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    Self::arbitrary_constrained(u, <Self as AsnType>::CONSTRAINTS)
+                }
+            }
+        )+
+    };
+}
+
+impl_arbitrary!(
+    Integer,
+    OctetString,
+    ObjectIdentifier,
+    BmpString,
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    TeletexString,
+    VisibleString,
+);
+
+impl<'a, const START: i128, const END: i128, T: IntegerType> Arbitrary<'a>
+    for ConstrainedInteger<START, END, T>
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Self::arbitrary_constrained(u, <Self as AsnType>::CONSTRAINTS)
+    }
+}
+
+impl<'a, const N: usize> Arbitrary<'a> for FixedOctetString<N> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Self::arbitrary_constrained(u, <Self as AsnType>::CONSTRAINTS)
+    }
+}
+
+impl<'a, T: AsnType + ArbitraryConstrained<'a>> Arbitrary<'a> for SetOf<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Self::arbitrary_constrained(u, <Self as AsnType>::CONSTRAINTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn honours_constraints() {
+        const VALUE: Constraints = constraints!(
+            macros::value_constraint!(-5, 5),
+            macros::size_constraint!(2, 4)
+        );
+        const ALPHABET: Constraints = constraints!(
+            macros::permitted_alphabet_constraint!(&[b'a' as u32, b'b' as u32, 0x263A]),
+            macros::size_constraint!(1, 3)
+        );
+
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..64 {
+            let integer = i32::arbitrary_constrained(&mut u, VALUE).unwrap();
+            assert!((-5..=5).contains(&integer));
+
+            let octets = OctetString::arbitrary_constrained(&mut u, VALUE).unwrap();
+            assert!((2..=4).contains(&octets.len()));
+
+            let string = Ia5String::arbitrary_constrained(&mut u, ALPHABET).unwrap();
+            let string = string.to_string();
+            assert!((1..=3).contains(&string.len()));
+            assert!(string.chars().all(|ch| ch == 'a' || ch == 'b'));
+
+            let string = String::arbitrary_constrained(&mut u, ALPHABET).unwrap();
+            assert!(string.chars().all(|ch| ch == 'a' || ch == 'b' || ch == '☺'));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+        #[rasn(automatic_tags, crate_root = "crate", arbitrary)]
+        #[non_exhaustive]
+        struct Record {
+            #[rasn(value("-9223372036854775808..=9223372036854775807"))]
+            id: Integer,
+            #[rasn(value("0..=255", extensible))]
+            level: u16,
+            #[rasn(size("1..=4"))]
+            tags: SequenceOf<VisibleString>,
+            name: Option<Utf8String>,
+            kind: Kind,
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+        #[rasn(choice, automatic_tags, crate_root = "crate", arbitrary)]
+        enum Kind {
+            Flag(bool),
+            #[rasn(size(3))]
+            Code(PrintableString),
+            Oid(ObjectIdentifier),
+        }
+
+        let data: Vec<u8> = (0..=255).rev().cycle().take(8192).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..16 {
+            let record = Record::arbitrary(&mut u).unwrap();
+            assert!((1..=4).contains(&record.tags.len()));
+            crate::testing::round_trip_all_codecs(&record);
+        }
+    }
+}
@@ -184,7 +184,11 @@ mod tests {
         round_trip_avn!(
             ObjectIdentifier,
             ObjectIdentifier::from(Oid::JOINT_ISO_ITU_T_DS_NAME_FORM),
-            "{ 2 5 15 }"
+            "{ joint-iso-itu-t(2) ds(5) 15 }"
+        );
+        assert_eq!(
+            crate::avn::decode::<ObjectIdentifier>("{ 2 5 15 }").unwrap(),
+            Oid::JOINT_ISO_ITU_T_DS_NAME_FORM
         );
     }
 
@@ -24,8 +24,7 @@ use crate::{
     types::{
         Any, BitString, BmpString, Constraints, Constructed, Date, DecodeChoice, Enumerated,
         GeneralString, GeneralizedTime, GraphicString, Ia5String, NumericString, ObjectIdentifier,
-        Oid, PrintableString, SetOf, Tag, TeletexString, UtcTime, Utf8String, VisibleString,
-        variants,
+        PrintableString, SetOf, Tag, TeletexString, UtcTime, Utf8String, VisibleString, variants,
     },
 };
 
@@ -42,6 +41,19 @@ fn parse_identifier_str(input: &str) -> IResult<&str, &str> {
     ))(input)
 }
 
+/// Parses an OBJECT IDENTIFIER arc in `NameAndNumberForm`, such as `iso(1)`,
+/// into its number.
+fn parse_name_and_number(input: &str) -> IResult<&str, AvnValue> {
+    let (input, _) = parse_identifier_str(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char('(')(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, number) = digit1(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char(')')(input)?;
+    Ok((input, AvnValue::Integer(number.into())))
+}
+
 fn parse_hex_or_bin_string(input: &str) -> IResult<&str, AvnValue> {
     let (input, _) = char('\'')(input)?;
     let (input, raw) = take_while(|c: char| c != '\'')(input)?;
@@ -176,7 +188,10 @@ fn parse_brace(input: &str, depth: usize) -> IResult<&str, AvnValue> {
         let (after_ws, _) = multispace0(current)?;
         current = after_ws;
 
-        if let Ok((after_id, id)) = parse_identifier_str(current) {
+        if let Ok((rest, arc)) = parse_name_and_number(current) {
+            items.push(BraceItem::Bare(arc));
+            current = rest;
+        } else if let Ok((after_id, id)) = parse_identifier_str(current) {
             let (after_ws2, _) = multispace0(after_id)?;
             if let Some(after_colon) = after_ws2.strip_prefix(':') {
                 // CHOICE element inside braces: id : value
@@ -217,10 +232,11 @@ fn parse_brace(input: &str, depth: usize) -> IResult<&str, AvnValue> {
             break;
         } else if current.starts_with(',') {
             current = &current[1..];
-        } else if current
+        } else if (current
             .chars()
             .next()
             .is_some_and(|c| c.is_ascii_digit() || c == '-')
+            || parse_name_and_number(current).is_ok())
             && items
                 .iter()
                 .all(|i| matches!(i, BraceItem::Bare(AvnValue::Integer(_))))
@@ -405,7 +421,10 @@ impl crate::Decoder for Decoder {
             .pop()
             .ok_or_else(|| DecodeError::from(AvnDecodeErrorKind::eoi()))?
             .ok_or_else(|| DecodeError::from(AvnDecodeErrorKind::eoi()))?;
-        Ok(Any::new(alloc::format!("{value}").into_bytes()))
+        Ok(Any::with_codec(
+            alloc::format!("{value}").into_bytes(),
+            crate::Codec::Avn,
+        ))
     }
 
     fn decode_bool(&mut self, _t: Tag) -> Result<bool, Self::Error> {
@@ -645,7 +664,7 @@ impl crate::Decoder for Decoder {
         decode_avn_value!(|v| self.sequence_of_from_value(v), self.stack)
     }
 
-    fn decode_set_of<D: Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: Decode>(
         &mut self,
         _t: Tag,
         _c: Constraints,
@@ -872,20 +891,19 @@ impl Decoder {
 
     fn oid_from_value(value: AvnValue) -> Result<ObjectIdentifier, DecodeError> {
         // OID is encoded as { arc1 arc2 ... } which parses as SequenceOf([Integer, ...])
-        let arcs: alloc::vec::Vec<u32> = match value {
-            AvnValue::Oid(arcs) => arcs,
+        let dotted = match value {
+            AvnValue::Oid(oid) => return Ok(oid),
             AvnValue::SequenceOf(items) => items
                 .into_iter()
                 .map(|item| match item {
-                    AvnValue::Integer(s) => s.parse::<u32>().map_err(|_| {
-                        DecodeError::from(AvnDecodeErrorKind::InvalidOid { value: s })
-                    }),
+                    AvnValue::Integer(s) => Ok(s),
                     other => Err(DecodeError::from(AvnDecodeErrorKind::AvnTypeMismatch {
                         needed: "OID arc (integer)",
                         found: alloc::format!("{other:?}"),
                     })),
                 })
-                .collect::<Result<_, _>>()?,
+                .collect::<Result<alloc::vec::Vec<_>, _>>()?
+                .join("."),
             other => {
                 return Err(DecodeError::from(AvnDecodeErrorKind::AvnTypeMismatch {
                     needed: "OID as { arc... }",
@@ -893,11 +911,8 @@ impl Decoder {
                 }));
             }
         };
-        Oid::new(&arcs).map(ObjectIdentifier::from).ok_or_else(|| {
-            DecodeError::from(AvnDecodeErrorKind::InvalidOid {
-                value: alloc::format!("{arcs:?}"),
-            })
-        })
+        ObjectIdentifier::from_dotted(&dotted)
+            .map_err(|_| DecodeError::from(AvnDecodeErrorKind::InvalidOid { value: dotted }))
     }
 
     fn bit_string_from_value(value: AvnValue) -> Result<BitString, DecodeError> {
@@ -977,10 +992,7 @@ impl Decoder {
             .collect()
     }
 
-    fn set_of_from_value<D: Decode + Eq + core::hash::Hash>(
-        &mut self,
-        value: AvnValue,
-    ) -> Result<SetOf<D>, DecodeError> {
+    fn set_of_from_value<D: Decode>(&mut self, value: AvnValue) -> Result<SetOf<D>, DecodeError> {
         let items = match value {
             AvnValue::SequenceOf(items) => items,
             AvnValue::Sequence(fields) if fields.is_empty() => alloc::vec![],
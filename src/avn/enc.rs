@@ -123,10 +123,10 @@ impl crate::Encoder<'_> for Encoder {
     fn encode_object_identifier(
         &mut self,
         _t: Tag,
-        value: &[u32],
+        value: &crate::types::Oid,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        self.update_root_or_constructed(AvnValue::Oid(value.to_vec()))
+        self.update_root_or_constructed(AvnValue::Oid(value.into()))
     }
 
     fn encode_integer<I: IntegerType>(
@@ -433,7 +433,7 @@ impl crate::Encoder<'_> for Encoder {
         self.encode_sequence::<RL, EL, C, F>(tag, value, Identifier::EMPTY)
     }
 
-    fn encode_set_of<E: crate::Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: crate::Encode>(
         &mut self,
         _t: Tag,
         value: &crate::types::SetOf<E>,
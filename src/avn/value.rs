@@ -20,8 +20,9 @@ pub enum AvnValue {
     },
     /// NULL value: `NULL`
     Null,
-    /// OBJECT IDENTIFIER as a vec of arcs; displayed as `{ 1 2 3 }`
-    Oid(alloc::vec::Vec<u32>),
+    /// OBJECT IDENTIFIER; displayed as `{ iso(1) member-body(2) 3 }`,
+    /// naming the arcs registered in the [`oid_registry`](crate::types::oid_registry)
+    Oid(crate::types::ObjectIdentifier),
     /// REAL value as a decimal string or special keyword
     Real(alloc::string::String),
     /// ENUMERATED value as an identifier string
@@ -116,13 +117,7 @@ impl fmt::Display for AvnFmt<'_> {
 
             AvnValue::Null => f.write_str("NULL"),
 
-            AvnValue::Oid(arcs) => {
-                f.write_str("{")?;
-                for arc in arcs {
-                    write!(f, " {arc}")?;
-                }
-                f.write_str(" }")
-            }
+            AvnValue::Oid(oid) => write!(f, "{}", oid.arc_notation()),
 
             AvnValue::Real(s) => f.write_str(s),
 
@@ -243,8 +238,11 @@ mod tests {
 
     #[test]
     fn oid_display() {
-        let v = AvnValue::Oid(alloc::vec![1, 2, 840, 113549]);
-        assert_eq!(v.to_string(), "{ 1 2 840 113549 }");
+        let v = AvnValue::Oid(crate::types::Oid::ISO_MEMBER_BODY_US_RSADSI.into());
+        assert_eq!(
+            v.to_string(),
+            "{ iso(1) member-body(2) us(840) rsadsi(113549) }"
+        );
     }
 
     #[test]
@@ -35,11 +35,23 @@ pub fn encode<T: crate::Encode>(
 ) -> Result<alloc::vec::Vec<u8>, crate::error::EncodeError> {
     let mut enc = enc::Encoder::new(enc::EncoderOptions::ber());
 
+    enc.size_ahead(value)?;
     value.encode(&mut enc)?;
 
     Ok(enc.output())
 }
 
+/// Returns the length of the BER encoding of `value`, without encoding it.
+/// # Errors
+/// Returns error specific to BER encoder if encoding is not possible.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::error::EncodeError> {
+    let mut enc = crate::ber::enc::Encoder::new_counting(crate::ber::enc::EncoderOptions::ber());
+
+    value.encode(&mut enc)?;
+
+    Ok(enc.encoded_len())
+}
+
 /// Encodes `value` to BER into an existing `buffer`, reusing its allocation.
 /// The buffer is cleared before encoding.
 /// # Errors
@@ -50,6 +62,7 @@ pub fn encode_buf<T: crate::Encode>(
 ) -> Result<(), crate::error::EncodeError> {
     let mut enc =
         enc::Encoder::new_with_buffer(enc::EncoderOptions::ber(), core::mem::take(buffer));
+    enc.size_ahead(value)?;
     value.encode(&mut enc)?;
     *buffer = enc.output();
     Ok(())
@@ -75,10 +88,13 @@ mod tests {
     use alloc::vec;
     use alloc::vec::Vec;
     use bitvec::order::Msb0;
+    #[cfg(feature = "chrono")]
     use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 
     use crate::{
+        Codec,
         ber::{decode, encode},
+        testing::round_trip_encoding,
         types::*,
     };
 
@@ -286,6 +302,7 @@ mod tests {
         }
     }
     #[test]
+    #[cfg(feature = "chrono")]
     fn test_generalized_time() {
         // "20801009130005.342Z"
         let offset = chrono::FixedOffset::east_opt(0).unwrap();
@@ -294,14 +311,13 @@ mod tests {
             .and_hms_micro_opt(13, 0, 5, 342_000)
             .unwrap()
             .and_local_timezone(offset);
-        round_trip!(
-            ber,
-            GeneralizedTime,
-            GeneralizedTime::from(dt.unwrap(),),
+        round_trip_encoding::<GeneralizedTime>(
+            Codec::Ber,
+            &GeneralizedTime::from(dt.unwrap()),
             &[
                 0x18, 0x13, 0x32, 0x30, 0x38, 0x30, 0x31, 0x30, 0x30, 0x39, 0x31, 0x33, 0x30, 0x30,
-                0x30, 0x35, 0x2e, 0x33, 0x34, 0x32, 0x5a
-            ]
+                0x30, 0x35, 0x2e, 0x33, 0x34, 0x32, 0x5a,
+            ],
         );
 
         // https://github.com/XAMPPRocky/rasn/issues/57
@@ -312,36 +328,34 @@ mod tests {
         assert!(crate::ber::decode::<crate::types::Open>(&data).is_err());
 
         // "20180122132900Z"
-        round_trip!(
-            ber,
-            GeneralizedTime,
-            GeneralizedTime::from(
+        round_trip_encoding::<GeneralizedTime>(
+            Codec::Ber,
+            &GeneralizedTime::from(
                 NaiveDate::from_ymd_opt(2018, 1, 22)
                     .unwrap()
                     .and_hms_opt(13, 29, 0)
                     .unwrap()
-                    .and_utc()
+                    .and_utc(),
             ),
             &[
                 0x18, 0x0f, 0x32, 0x30, 0x31, 0x38, 0x30, 0x31, 0x32, 0x32, 0x31, 0x33, 0x32, 0x39,
-                0x30, 0x30, 0x5a
-            ]
+                0x30, 0x30, 0x5a,
+            ],
         );
         // "20180122130000Z"
-        round_trip!(
-            ber,
-            GeneralizedTime,
-            GeneralizedTime::from(
+        round_trip_encoding::<GeneralizedTime>(
+            Codec::Ber,
+            &GeneralizedTime::from(
                 NaiveDate::from_ymd_opt(2018, 1, 22)
                     .unwrap()
                     .and_hms_opt(13, 0, 0)
                     .unwrap()
-                    .and_utc()
+                    .and_utc(),
             ),
             &[
                 0x18, 0x0f, 0x32, 0x30, 0x31, 0x38, 0x30, 0x31, 0x32, 0x32, 0x31, 0x33, 0x30, 0x30,
-                0x30, 0x30, 0x5a
-            ]
+                0x30, 0x30, 0x5a,
+            ],
         );
 
         // "20230122130000-0500" - converts to canonical form "20230122180000Z"
@@ -354,14 +368,13 @@ mod tests {
                 .and_local_timezone(offset)
                 .unwrap(),
         ));
-        round_trip!(
-            ber,
-            GeneralizedTime,
-            dt1,
+        round_trip_encoding::<GeneralizedTime>(
+            Codec::Ber,
+            &dt1,
             &[
                 0x18, 0x0f, 0x32, 0x30, 0x32, 0x33, 0x30, 0x31, 0x32, 0x32, 0x31, 0x38, 0x30, 0x30,
-                0x30, 0x30, 0x5a
-            ]
+                0x30, 0x30, 0x5a,
+            ],
         );
         // "20230122130000-0500" as bytes
         let data = [
@@ -388,22 +401,22 @@ mod tests {
         assert!(result.is_err());
     }
     #[test]
+    #[cfg(feature = "chrono")]
     fn test_utc_time() {
         // "180122132900Z"
-        round_trip!(
-            ber,
-            UtcTime,
-            UtcTime::from(
+        round_trip_encoding::<UtcTime>(
+            Codec::Ber,
+            &UtcTime::from(
                 NaiveDate::from_ymd_opt(2018, 1, 22)
                     .unwrap()
                     .and_hms_opt(13, 29, 0)
                     .unwrap()
-                    .and_utc()
+                    .and_utc(),
             ),
             &[
                 0x17, 0x0d, 0x31, 0x38, 0x30, 0x31, 0x32, 0x32, 0x31, 0x33, 0x32, 0x39, 0x30, 0x30,
-                0x5a
-            ]
+                0x5a,
+            ],
         );
         // "230122130000-0500" - converts to canonical form "230122180000Z"
         let offset = FixedOffset::east_opt(-3600 * 5).unwrap();
@@ -414,14 +427,13 @@ mod tests {
                 .unwrap(),
             offset,
         );
-        round_trip!(
-            ber,
-            UtcTime,
-            dt1.into(),
+        round_trip_encoding::<UtcTime>(
+            Codec::Ber,
+            &dt1.into(),
             &[
                 0x17, 0x0d, 0x32, 0x33, 0x30, 0x31, 0x32, 0x32, 0x31, 0x38, 0x30, 0x30, 0x30, 0x30,
-                0x5a
-            ]
+                0x5a,
+            ],
         );
         // "230122130000-0500" as bytes
         let data = [
@@ -433,16 +445,46 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
     fn test_date() {
-        round_trip!(
-            ber,
-            Date,
-            NaiveDate::from_ymd_opt(2012, 12, 21).unwrap(),
+        round_trip_encoding::<Date>(
+            Codec::Ber,
+            &NaiveDate::from_ymd_opt(2012, 12, 21).unwrap(),
             &[
-                0x1f, 0x1f, 0x08, 0x32, 0x30, 0x31, 0x32, 0x31, 0x32, 0x32, 0x31
-            ]
+                0x1f, 0x1f, 0x08, 0x32, 0x30, 0x31, 0x32, 0x31, 0x32, 0x32, 0x31,
+            ],
         );
     }
+    #[test]
+    fn canonical_times() {
+        // "20801009130005.342Z", "180122132900Z" and "20121221"
+        let generalized_time = &[
+            0x18, 0x13, 0x32, 0x30, 0x38, 0x30, 0x31, 0x30, 0x30, 0x39, 0x31, 0x33, 0x30, 0x30,
+            0x30, 0x35, 0x2e, 0x33, 0x34, 0x32, 0x5a,
+        ];
+        let utc_time = &[
+            0x17, 0x0d, 0x31, 0x38, 0x30, 0x31, 0x32, 0x32, 0x31, 0x33, 0x32, 0x39, 0x30, 0x30,
+            0x5a,
+        ];
+        let date = &[
+            0x1f, 0x1f, 0x08, 0x32, 0x30, 0x31, 0x32, 0x31, 0x32, 0x32, 0x31,
+        ];
+
+        let value: GeneralizedTime = crate::der::decode(generalized_time).unwrap();
+        assert_eq!(crate::der::encode(&value).unwrap(), generalized_time);
+        let value: UtcTime = crate::der::decode(utc_time).unwrap();
+        assert_eq!(crate::der::encode(&value).unwrap(), utc_time);
+        let value: Date = crate::der::decode(date).unwrap();
+        assert_eq!(crate::der::encode(&value).unwrap(), date);
+
+        // "20230122130000-0500" isn't canonical.
+        let data = [
+            24, 19, 50, 48, 50, 51, 48, 49, 50, 50, 49, 51, 48, 48, 48, 48, 45, 48, 53, 48, 48,
+        ];
+        assert!(decode::<GeneralizedTime>(&data).is_ok());
+        assert!(crate::der::decode::<GeneralizedTime>(&data).is_err());
+    }
+
     #[test]
     fn test_extended_sequence() {
         use crate as rasn;
@@ -454,13 +496,12 @@ mod tests {
             #[rasn(extension_addition)]
             pub extension: Option<u64>,
         }
-        round_trip!(
-            ber,
-            ExtendedInteger,
-            ExtendedInteger {
-                extension: Some(42)
-            },
-            &[0x30, 0x03, 0x80, 0x01, 0x2A]
+        round_trip_encoding::<ExtendedInteger>(
+            Codec::Ber,
+            &(ExtendedInteger {
+                extension: Some(42),
+            }),
+            &[0x30, 0x03, 0x80, 0x01, 0x2A],
         );
         #[derive(AsnType, Debug, Clone, Encode, Decode, PartialEq)]
         #[non_exhaustive]
@@ -470,11 +511,10 @@ mod tests {
             pub extension: u64,
         }
 
-        round_trip!(
-            ber,
-            ExtendedExplicitInteger,
-            ExtendedExplicitInteger { extension: 42 },
-            &[0x30, 0x05, 0xA5, 0x03, 0x02, 0x01, 0x2A]
+        round_trip_encoding::<ExtendedExplicitInteger>(
+            Codec::Ber,
+            &(ExtendedExplicitInteger { extension: 42 }),
+            &[0x30, 0x05, 0xA5, 0x03, 0x02, 0x01, 0x2A],
         );
     }
     #[test]
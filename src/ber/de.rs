@@ -1,18 +1,17 @@
 //! # Decoding BER
 
 mod config;
-pub(super) mod parser;
+pub(crate) mod parser;
 
 use super::identifier::Identifier;
 use crate::{
     Decode,
     types::{
-        self, Constraints, Enumerated, Tag,
-        oid::{MAX_OID_FIRST_OCTET, MAX_OID_SECOND_OCTET},
+        self, Constraints, DateString, Enumerated, GeneralizedTimeString, Tag, TimeProfile,
+        UtcTimeString, time_backend,
     },
 };
 use alloc::{borrow::Cow, borrow::ToOwned, string::ToString, vec::Vec};
-use chrono::{DateTime, NaiveDate, NaiveDateTime};
 use parser::ParseNumberError;
 
 pub use self::config::DecoderOptions;
@@ -28,6 +27,10 @@ pub struct Decoder<'input> {
     input: &'input [u8],
     config: DecoderOptions,
     initial_len: usize,
+    /// The offset in octets of `input` from the start of the outermost
+    /// input, which offsets are traced from.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    start: usize,
 }
 
 impl<'input> Decoder<'input> {
@@ -48,6 +51,7 @@ impl<'input> Decoder<'input> {
             input,
             config,
             initial_len: input.len(),
+            start: 0,
         }
     }
 
@@ -56,9 +60,22 @@ impl<'input> Decoder<'input> {
     pub fn decoded_len(&self) -> usize {
         self.initial_len - self.input.len()
     }
+
+    /// Returns the offset in octets of the remaining input from the start of
+    /// the outermost input.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn offset(&self) -> usize {
+        self.start + self.decoded_len()
+    }
     /// Peek the value of the next tag
     pub fn peek_tag(&self) -> Result<Tag> {
-        let (_, tag_ident) =
+        Ok(self.peek_identifier()?.tag)
+    }
+
+    /// Peek the identifier of the next value, including whether it uses the
+    /// constructed encoding.
+    pub(crate) fn peek_identifier(&self) -> Result<Identifier> {
+        let (_, identifier) =
             self::parser::parse_identifier_octet(self.input).map_err(|e| match e {
                 ParseNumberError::Nom(e) => {
                     DecodeError::map_nom_err(e, self.config.current_codec())
@@ -67,7 +84,14 @@ impl<'input> Decoder<'input> {
                     DecodeError::integer_overflow(32u32, self.config.current_codec())
                 }
             })?;
-        Ok(tag_ident.tag)
+        Ok(identifier)
+    }
+
+    /// Returns whether the remaining input of a constructed value has been
+    /// consumed, either because it is empty or because the end-of-contents
+    /// marker of an indefinite length value follows.
+    pub(crate) fn is_end_of_contents(&self) -> bool {
+        self.input.is_empty() || self.input.starts_with(EOC)
     }
     /// Generic helper used by the optional decoders.
     /// The function will peek the upcoming tag and only invoke `f` when the tags match.
@@ -103,20 +127,35 @@ impl<'input> Decoder<'input> {
     pub(crate) fn parse_value(&mut self, tag: Tag) -> Result<(Identifier, Option<&'input [u8]>)> {
         let (input, (identifier, contents)) =
             self::parser::parse_value(self.config, self.input, Some(tag))?;
+        trace_event!(
+            offset = self.offset(),
+            tag = %identifier.tag,
+            length = ?contents.map(<[u8]>::len),
+            "value"
+        );
         self.input = input;
         Ok((identifier, contents))
     }
 
     pub(crate) fn parse_primitive_value(&mut self, tag: Tag) -> Result<(Identifier, &'input [u8])> {
-        let (input, (identifier, contents)) =
-            self::parser::parse_value(self.config, self.input, Some(tag))?;
-        self.input = input;
+        let (identifier, contents) = self.parse_value(tag)?;
         match contents {
             Some(contents) => Ok((identifier, contents)),
             None => Err(BerDecodeErrorKind::IndefiniteLengthNotAllowed.into()),
         }
     }
 
+    /// Returns whether `error`, from decoding the next component of a
+    /// constructed value, has to be reported instead of ending the components:
+    /// either the nesting is too deep, or the component is incomplete, as when
+    /// a value of an indefinite length is still arriving.
+    fn is_fatal_component_error(&self, error: &DecodeError) -> bool {
+        error.matches_root_cause(|kind| {
+            matches!(kind, DecodeErrorKind::ExceedsMaxParseDepth)
+                || (matches!(kind, DecodeErrorKind::Incomplete { .. }) && !self.input.is_empty())
+        })
+    }
+
     fn check_recursion_depth(&self) -> Result<()> {
         if self.config.remaining_depth == 0 {
             return Err(DecodeError::from_kind(
@@ -130,7 +169,7 @@ impl<'input> Decoder<'input> {
     /// Parses a constructed ASN.1 value, checking the `tag`, and optionally
     /// checking if the identifier is marked as encoded. This should be true
     /// in all cases except explicit prefixes.
-    fn parse_constructed_contents<D, F>(
+    pub(crate) fn parse_constructed_contents<D, F>(
         &mut self,
         tag: Tag,
         check_identifier: bool,
@@ -155,6 +194,7 @@ impl<'input> Decoder<'input> {
 
         let mut inner = Self::new(contents, self.config);
         inner.config.remaining_depth = inner.config.remaining_depth.saturating_sub(1);
+        inner.start = self.offset() - if streaming { 0 } else { contents.len() };
 
         let result = (decode_fn)(&mut inner)?;
 
@@ -176,186 +216,56 @@ impl<'input> Decoder<'input> {
         &self,
         data: &[u8],
     ) -> Result<crate::types::ObjectIdentifier, DecodeError> {
-        let (mut contents, root_octets) =
-            parser::parse_base128_number(data).map_err(|e| match e {
-                ParseNumberError::Nom(e) => DecodeError::map_nom_err(e, self.codec()),
-                ParseNumberError::Overflow => DecodeError::integer_overflow(32u32, self.codec()),
-            })?;
-        let first: u32;
-        let second: u32;
-        const MAX_OID_THRESHOLD: u32 = MAX_OID_SECOND_OCTET + 1;
-        if root_octets > MAX_OID_FIRST_OCTET * MAX_OID_THRESHOLD + MAX_OID_SECOND_OCTET {
-            first = MAX_OID_FIRST_OCTET;
-            second = root_octets - MAX_OID_FIRST_OCTET * MAX_OID_THRESHOLD;
-        } else {
-            second = root_octets % MAX_OID_THRESHOLD;
-            first = (root_octets - second) / MAX_OID_THRESHOLD;
-        }
-
-        // preallocate some capacity for the OID arcs, maxing out at 16 elements
-        // to prevent excessive preallocation from malformed or malicious
-        // packets
-        let mut buffer = alloc::vec::Vec::with_capacity(core::cmp::min(contents.len() + 2, 16));
-        buffer.push(first);
-        buffer.push(second);
-
-        while !contents.is_empty() {
-            let (c, number) = parser::parse_base128_number(contents).map_err(|e| match e {
-                ParseNumberError::Nom(e) => DecodeError::map_nom_err(e, self.codec()),
-                ParseNumberError::Overflow => DecodeError::integer_overflow(32u32, self.codec()),
-            })?;
-            contents = c;
-            buffer.push(number);
-        }
-        crate::types::ObjectIdentifier::new(buffer)
+        crate::types::ObjectIdentifier::from_ber_contents(data)
             .ok_or_else(|| BerDecodeErrorKind::InvalidObjectIdentifier.into())
     }
-    /// Parse any GeneralizedTime string, allowing for any from ASN.1 definition
-    /// TODO, move to type itself?
+    /// Parse any GeneralizedTime string, allowing for any from ASN.1 definition.
+    /// A local time without a time zone is read as UTC.
     pub fn parse_any_generalized_time_string(
         string: alloc::string::String,
     ) -> Result<types::GeneralizedTime, DecodeError> {
-        // Reference https://obj-sys.com/asn1tutorial/node14.html
-        // If data contains ., 3 decimal places of seconds are expected
-        // If data contains explict Z, result is UTC
-        // If data contains + or -, explicit timezone is given
-        // If neither Z nor + nor -, purely local time is implied
-        // Replace comma with dot for fractional seconds.
-        let mut s = if string.contains(',') {
-            string.replace(',', ".")
-        } else {
-            string
-        };
-        if s.ends_with("Z") {
-            s.pop(); // We default to UTC
-        }
-        // Timezone offset markers are in static location if present
-        let has_offset = s.len() >= 5 && {
-            let bytes = s.as_bytes();
-            bytes[s.len() - 5] == b'+' || bytes[s.len() - 5] == b'-'
-        };
-        let format_candidates: &[&str] = if s.contains('.') {
-            if has_offset {
-                &["%Y%m%d%H%M%S%.f%z", "%Y%m%d%H%M%.f%z", "%Y%m%d%H%.f%z"] // We don't know the count of fractions
-            } else {
-                &["%Y%m%d%H%M%S%.f", "%Y%m%d%H%M%.f", "%Y%m%d%H%.f"] // We don't know the count of fractions
-            }
-        } else if has_offset {
-            match s.len() {
-                // Length including timezone offset (YYYYMMDDHHMMSS+HHMM)
-                19 => &["%Y%m%d%H%M%S%z"],
-                17 => &["%Y%m%d%H%M%z"],
-                15 => &["%Y%m%d%H%z"],
-                _ => &["%Y%m%d%H%M%S%z", "%Y%m%d%H%M%z", "%Y%m%d%H%z"],
-            }
-        } else {
-            // For local times without timezone, default to UTC later
-            match s.len() {
-                8 => &["%Y%m%d"],
-                10 => &["%Y%m%d%H"],
-                12 => &["%Y%m%d%H%M"],
-                14 => &["%Y%m%d%H%M%S"],
-                _ => &[],
-            }
-        };
-        for fmt in format_candidates {
-            if has_offset {
-                if let Ok(dt) = DateTime::parse_from_str(&s, fmt) {
-                    return Ok(dt);
-                }
-            } else if let Ok(dt) = NaiveDateTime::parse_from_str(&s, fmt) {
-                return Ok(dt.and_utc().into());
-            }
-        }
-        Err(BerDecodeErrorKind::invalid_date(s).into())
+        Self::parse_generalized_time_string(string, TimeProfile::Basic)
     }
     /// Enforce CER/DER restrictions defined in Section 11.7, strictly raise error on non-compliant
     pub fn parse_canonical_generalized_time_string(
         string: alloc::string::String,
     ) -> Result<types::GeneralizedTime, DecodeError> {
-        let len = string.len();
-        // Helper function to deal with fractions of seconds and without timezone
-        let parse_without_timezone =
-            |string: &str| -> core::result::Result<NaiveDateTime, DecodeError> {
-                let len = string.len();
-                if string.contains('.') {
-                    // https://github.com/chronotope/chrono/issues/238#issuecomment-378737786
-                    NaiveDateTime::parse_from_str(string, "%Y%m%d%H%M%S%.f")
-                        .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()).into())
-                } else if len == 14 {
-                    NaiveDateTime::parse_from_str(string, "%Y%m%d%H%M%S")
-                        .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()).into())
-                } else {
-                    // CER/DER encoding rules don't allow for timezone offset +/
-                    // Or missing seconds/minutes/hours
-                    // Or comma , instead of dot .
-                    // Or local time without timezone
-                    Err(BerDecodeErrorKind::invalid_date(string.to_string()).into())
-                }
-            };
-        if string.ends_with('Z') {
-            let naive = parse_without_timezone(&string[..len - 1])?;
-            Ok(naive.and_utc().into())
-        } else {
-            Err(BerDecodeErrorKind::invalid_date(string.to_string()).into())
-        }
+        Self::parse_generalized_time_string(string, TimeProfile::Canonical)
+    }
+    fn parse_generalized_time_string(
+        string: alloc::string::String,
+        profile: TimeProfile,
+    ) -> Result<types::GeneralizedTime, DecodeError> {
+        GeneralizedTimeString::with_profile(string.as_str(), profile)
+            .and_then(time_backend::generalized_time)
+            .map_err(|_| BerDecodeErrorKind::invalid_date(string).into())
     }
     /// Parse any UTCTime string, can be any from ASN.1 definition
-    /// TODO, move to type itself?
     pub fn parse_any_utc_time_string(
         string: alloc::string::String,
     ) -> Result<types::UtcTime, DecodeError> {
-        // When compared to GeneralizedTime, UTC time has no fractions.
-        let len = string.len();
-        // Largest string, e.g. "820102070000-0500".len() == 17
-        if len > 17 {
-            return Err(BerDecodeErrorKind::invalid_date(string.to_string()).into());
-        }
-        let format = if string.contains('Z') {
-            if len == 11 {
-                "%y%m%d%H%MZ"
-            } else {
-                "%y%m%d%H%M%SZ"
-            }
-        } else if len == 15 {
-            "%y%m%d%H%M%z"
-        } else {
-            "%y%m%d%H%M%S%z"
-        };
-        match len {
-            11 | 13 => {
-                let naive = NaiveDateTime::parse_from_str(&string, format)
-                    .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()))?;
-                Ok(naive.and_utc())
-            }
-            15 | 17 => Ok(DateTime::parse_from_str(&string, format)
-                .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()))?
-                .into()),
-            _ => Err(BerDecodeErrorKind::invalid_date(string.to_string()).into()),
-        }
+        Self::parse_utc_time_string(&string, TimeProfile::Basic)
     }
 
     /// Enforce CER/DER restrictions defined in Section 11.8, strictly raise error on non-compliant
     pub fn parse_canonical_utc_time_string(string: &str) -> Result<types::UtcTime, DecodeError> {
-        let len = string.len();
-        if string.ends_with('Z') {
-            let naive = match len {
-                13 => NaiveDateTime::parse_from_str(string, "%y%m%d%H%M%SZ")
-                    .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()))?,
-                _ => Err(BerDecodeErrorKind::invalid_date(string.to_string()))?,
-            };
-            Ok(naive.and_utc())
-        } else {
-            Err(BerDecodeErrorKind::invalid_date(string.to_string()).into())
-        }
+        Self::parse_utc_time_string(string, TimeProfile::Canonical)
+    }
+
+    fn parse_utc_time_string(
+        string: &str,
+        profile: TimeProfile,
+    ) -> Result<types::UtcTime, DecodeError> {
+        UtcTimeString::with_profile(string, profile)
+            .and_then(time_backend::utc_time)
+            .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()).into())
     }
 
     /// X.690 8.26.2 and 11.9 -> YYYYMMDD
     pub fn parse_date_string(string: &str) -> Result<types::Date, DecodeError> {
-        let date = NaiveDate::parse_from_str(string, "%Y%m%d")
-            .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()))?;
-
-        Ok(date)
+        DateString::new(string)
+            .and_then(time_backend::date)
+            .map_err(|_| BerDecodeErrorKind::invalid_date(string.to_string()).into())
     }
 
     fn check_size_constraint(
@@ -410,10 +320,10 @@ impl<'input> crate::Decoder for Decoder<'input> {
         // Only the data format is validated, when not in Sequence/Set - afterwards just pass the original data.
         let any = if tag.is_none() {
             let diff = self.input.len() - input.len();
-            types::Any::new(self.input[..diff].to_vec())
+            types::Any::with_codec(self.input[..diff].to_vec(), self.codec())
         } else {
             // Outermost TLV stripped in sequence/set
-            types::Any::new(contents)
+            types::Any::with_codec(contents, self.codec())
         };
         self.input = input;
         Ok(any)
@@ -436,6 +346,11 @@ impl<'input> crate::Decoder for Decoder<'input> {
     }
 
     fn decode_enumerated<E: Enumerated>(&mut self, tag: Tag) -> Result<E> {
+        trace_span!(
+            "enumerated",
+            ty = core::any::type_name::<E>(),
+            offset = self.offset()
+        );
         let discriminant = self.decode_integer::<isize>(tag, Constraints::default())?;
 
         E::from_discriminant(discriminant)
@@ -734,6 +649,11 @@ impl<'input> crate::Decoder for Decoder<'input> {
         tag: Tag,
         constraints: Constraints,
     ) -> Result<Vec<D>, Self::Error> {
+        trace_span!(
+            "sequence of",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
         let items = self.parse_constructed_contents(tag, true, |decoder| {
             decoder.config.remaining_depth = decoder.config.remaining_depth.saturating_sub(1);
             let mut items = Vec::new();
@@ -751,9 +671,7 @@ impl<'input> crate::Decoder for Decoder<'input> {
                         }
                     }
                     Err(e) => {
-                        if e.matches_root_cause(|kind| {
-                            matches!(kind, DecodeErrorKind::ExceedsMaxParseDepth)
-                        }) {
+                        if decoder.is_fatal_component_error(&e) {
                             return Err(e);
                         }
                         break;
@@ -767,24 +685,38 @@ impl<'input> crate::Decoder for Decoder<'input> {
         Ok(items)
     }
 
-    fn decode_set_of<D: Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: Decode>(
         &mut self,
         tag: Tag,
         constraints: Constraints,
     ) -> Result<types::SetOf<D>, Self::Error> {
+        trace_span!(
+            "set of",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
         let items = self.parse_constructed_contents(tag, true, |decoder| {
             decoder.config.remaining_depth = decoder.config.remaining_depth.saturating_sub(1);
             let mut items = types::SetOf::new();
+            let verify_order =
+                decoder.config.verify_set_of_order && decoder.config.encoding_rules.is_der();
+            let mut previous: &[u8] = &[];
 
             loop {
+                let input = decoder.input;
                 match D::decode(decoder) {
                     Ok(item) => {
+                        if verify_order {
+                            let encoding = &input[..input.len() - decoder.input.len()];
+                            if encoding < previous {
+                                return Err(DerDecodeErrorKind::SetOfNotSorted.into());
+                            }
+                            previous = encoding;
+                        }
                         items.insert(item);
                     }
                     Err(e) => {
-                        if e.matches_root_cause(|kind| {
-                            matches!(kind, DecodeErrorKind::ExceedsMaxParseDepth)
-                        }) {
+                        if decoder.is_fatal_component_error(&e) {
                             return Err(e);
                         }
                         break;
@@ -810,6 +742,11 @@ impl<'input> crate::Decoder for Decoder<'input> {
         default_initializer_fn: Option<DF>,
         decode_fn: F,
     ) -> Result<D> {
+        trace_span!(
+            "sequence",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
         self.parse_constructed_contents(tag, true, |decoder| {
             // If there are no fields, or the input is empty and we know that
             // all fields are optional or default fields, we call the default
@@ -854,6 +791,11 @@ impl<'input> crate::Decoder for Decoder<'input> {
         D: Fn(&mut Self, usize, Tag) -> Result<FIELDS, Self::Error>,
         F: FnOnce(Vec<FIELDS>) -> Result<SET, Self::Error>,
     {
+        trace_span!(
+            "set",
+            ty = core::any::type_name::<SET>(),
+            offset = self.offset()
+        );
         self.parse_constructed_contents(tag, true, |decoder| {
             let mut fields = Vec::new();
 
@@ -861,9 +803,7 @@ impl<'input> crate::Decoder for Decoder<'input> {
                 match FIELDS::decode(decoder) {
                     Ok(value) => fields.push(value),
                     Err(e) => {
-                        if e.matches_root_cause(|kind| {
-                            matches!(kind, DecodeErrorKind::ExceedsMaxParseDepth)
-                        }) {
+                        if decoder.is_fatal_component_error(&e) {
                             return Err(e);
                         }
                         break;
@@ -913,6 +853,12 @@ impl<'input> crate::Decoder for Decoder<'input> {
             ParseNumberError::Nom(e) => DecodeError::map_nom_err(e, self.codec()),
             ParseNumberError::Overflow => DecodeError::integer_overflow(32u32, self.codec()),
         })?;
+        trace_span!(
+            "choice",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
+        trace_event!(tag = %identifier.tag, "choice tag");
         D::from_tag(self, identifier.tag)
     }
 
@@ -1105,6 +1051,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
     fn utc_time() {
         let time =
             crate::types::GeneralizedTime::parse_from_str("991231235959+0000", "%y%m%d%H%M%S%z")
@@ -1137,6 +1084,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
     fn generalized_time() {
         let time = crate::types::GeneralizedTime::parse_from_str(
             "20001231205959.999+0000",
@@ -1262,12 +1210,7 @@ mod tests {
     #[test]
     fn any() {
         let expected = &[0x1A, 0x05, 0x4A, 0x6F, 0x6E, 0x65, 0x73];
-        assert_eq!(
-            Any {
-                contents: expected.to_vec()
-            },
-            decode(expected).unwrap()
-        );
+        assert_eq!(Any::new(expected.to_vec()), decode(expected).unwrap());
     }
 
     #[test]
@@ -1276,12 +1219,7 @@ mod tests {
             0x30, 0x80, 0x2C, 0x80, 0x04, 0x03, 0x4A, 0x6F, 0x6E, 0x04, 0x02, 0x65, 0x73, 0x00,
             0x00, 0x00, 0x00,
         ];
-        assert_eq!(
-            Any {
-                contents: any.to_vec()
-            },
-            decode(any).unwrap(),
-        );
+        assert_eq!(Any::new(any.to_vec()), decode(any).unwrap(),);
     }
 
     #[test]
@@ -1293,6 +1231,25 @@ mod tests {
         assert!(decode::<Any>(any).is_err());
     }
 
+    #[test]
+    fn incomplete_components_of_indefinite_length() {
+        // SEQUENCE OF constructed OCTET STRINGs, all of an indefinite length.
+        let encoded = [
+            0x30, 0x80, 0x24, 0x80, 0x04, 0x01, 0xAB, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+        ];
+        for length in 0..encoded.len() {
+            let error = decode::<Vec<OctetString>>(&encoded[..length]).unwrap_err();
+            assert!(
+                matches!(*error.kind, DecodeErrorKind::Incomplete { .. }),
+                "{length}: {error}"
+            );
+        }
+        assert_eq!(
+            decode::<Vec<OctetString>>(&encoded).unwrap(),
+            [OctetString::from(alloc::vec![0xAB]), OctetString::default()]
+        );
+    }
+
     #[test]
     fn decoding_oid() {
         use crate::Decoder;
@@ -1455,4 +1412,35 @@ mod tests {
             DecodeErrorKind::SizeConstraintNotSatisfied { size: Some(0), .. }
         ));
     }
+
+    #[test]
+    fn set_of_order_verification() {
+        let unsorted = [0x31, 0x07, 0x02, 0x02, 0x01, 0x2c, 0x02, 0x01, 0x02];
+        let sorted = [0x31, 0x07, 0x02, 0x01, 0x02, 0x02, 0x02, 0x01, 0x2c];
+        let verified = DecoderOptions::der().with_set_of_order_verification(true);
+
+        let decode_with =
+            |options, input: &[u8]| SetOf::<u16>::decode(&mut Decoder::new(input, options));
+
+        assert_eq!(
+            decode_with(DecoderOptions::der(), &unsorted).unwrap(),
+            SetOf::from_vec(alloc::vec![2, 300])
+        );
+        assert_eq!(
+            decode_with(verified, &sorted).unwrap(),
+            SetOf::from_vec(alloc::vec![2, 300])
+        );
+        let err = decode_with(verified, &unsorted).unwrap_err();
+        assert!(matches!(
+            *err.kind,
+            DecodeErrorKind::CodecSpecific {
+                inner: CodecDecodeError::Der(DerDecodeErrorKind::SetOfNotSorted)
+            }
+        ));
+
+        let set = SortedSetOf::<u16>::decode(&mut Decoder::new(&unsorted, verified));
+        assert!(set.is_err());
+        let set = SortedSetOf::<u16>::decode(&mut Decoder::new(&unsorted, DecoderOptions::der()));
+        assert_eq!(set.unwrap().as_slice(), [2, 300]);
+    }
 }
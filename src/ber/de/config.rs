@@ -6,6 +6,7 @@ pub struct DecoderOptions {
     pub(crate) encoding_rules: EncodingRules,
     // limit decoding to prevent stack overflow from deep or circular references
     pub(crate) remaining_depth: usize,
+    pub(crate) verify_set_of_order: bool,
 }
 
 impl DecoderOptions {
@@ -15,6 +16,7 @@ impl DecoderOptions {
         Self {
             encoding_rules: EncodingRules::Ber,
             remaining_depth: 128,
+            verify_set_of_order: false,
         }
     }
 
@@ -24,6 +26,7 @@ impl DecoderOptions {
         Self {
             encoding_rules: EncodingRules::Cer,
             remaining_depth: 128,
+            verify_set_of_order: false,
         }
     }
 
@@ -33,9 +36,21 @@ impl DecoderOptions {
         Self {
             encoding_rules: EncodingRules::Der,
             remaining_depth: 128,
+            verify_set_of_order: false,
         }
     }
 
+    /// Returns the configuration with checking whether the elements of `SET
+    /// OF` values are sorted enabled or disabled. When enabled, decoding DER
+    /// fails if the elements of a `SET OF` are not in ascending order of
+    /// their encodings. Disabled by default, which accepts the elements in
+    /// any order.
+    #[must_use]
+    pub const fn with_set_of_order_verification(mut self, verify: bool) -> Self {
+        self.verify_set_of_order = verify;
+        self
+    }
+
     /// Returns the currently selected codec.
     #[must_use]
     pub fn current_codec(&self) -> crate::Codec {
@@ -127,26 +127,6 @@ impl<Input> From<nom::Err<nom::error::Error<Input>>> for ParseNumberError<Input>
     }
 }
 
-pub fn parse_base128_number(input: &[u8]) -> Result<(&[u8], u32), ParseNumberError<&[u8]>> {
-    let (input, body) = nom::bytes::streaming::take_while(|i| i & 0x80 != 0)(input)?;
-    let (input, end) = nom::bytes::streaming::take(1usize)(input)?;
-
-    let mut number = 0u32;
-    for byte in body {
-        number = match number.checked_shl(7) {
-            Some(n) => n,
-            None => return Err(ParseNumberError::Overflow),
-        };
-        number |= u32::from(*byte & 0x7F);
-    }
-    number = match number.checked_shl(7) {
-        Some(n) => n,
-        None => return Err(ParseNumberError::Overflow),
-    };
-    number |= u32::from(end[0]);
-    Ok((input, number))
-}
-
 fn parse_initial_octet(input: &[u8]) -> IResult<&[u8], Identifier> {
     let (input, octet) = nom::bytes::streaming::take(1usize)(input)?;
     let initial_octet = octet[0];
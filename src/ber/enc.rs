@@ -2,42 +2,28 @@
 
 mod config;
 
-use alloc::{borrow::ToOwned, collections::VecDeque, string::ToString, vec::Vec};
-use chrono::Timelike;
+use alloc::{borrow::ToOwned, collections::VecDeque, vec::Vec};
 
 use super::Identifier;
 use crate::{
     Codec, Encode,
-    types::{
-        self, Constraints, Enumerated, IntegerType, Tag,
-        oid::{MAX_OID_FIRST_OCTET, MAX_OID_SECOND_OCTET},
-    },
+    types::{self, Constraints, Enumerated, IntegerType, Oid, Tag},
 };
 
 pub use crate::error::{BerEncodeErrorKind, EncodeError, EncodeErrorKind};
-pub use config::EncoderOptions;
+pub use config::{EncoderOptions, SetOfOrdering};
 
 const START_OF_CONTENTS: u8 = 0x80;
 const END_OF_CONTENTS: &[u8] = &[0, 0];
 
 /// Encodes an object identifier into `buffer` in BER format.
 /// Reusable by other codecs without constructing an [`Encoder`].
-pub fn object_identifier_as_bytes(oid: &[u32], buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
-    if oid.len() < 2 {
-        return Err(BerEncodeErrorKind::invalid_object_identifier(oid.to_owned()).into());
-    }
-
-    let first = oid[0];
-    let second = oid[1];
-
-    if first > MAX_OID_FIRST_OCTET {
-        return Err(BerEncodeErrorKind::invalid_object_identifier(oid.to_owned()).into());
-    }
-    encode_as_base128((first * (MAX_OID_SECOND_OCTET + 1)) + second, buffer);
-    for component in oid.iter().skip(2) {
-        encode_as_base128(*component, buffer);
+pub fn object_identifier_as_bytes(oid: &Oid, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+    if oid.write_ber_contents(buffer) {
+        Ok(())
+    } else {
+        Err(BerEncodeErrorKind::invalid_object_identifier(oid.to_owned()).into())
     }
-    Ok(())
 }
 
 pub(super) fn encode_as_base128(number: u32, buffer: &mut Vec<u8>) {
@@ -72,6 +58,16 @@ pub struct Encoder {
     /// so that sibling fields at the same nesting level reuse one heap allocation
     /// rather than allocating a fresh `Vec<u8>` per field.
     worker: Vec<u8>,
+    /// Whether the bytes of the encoding are only counted, not written.
+    is_counting: bool,
+    /// The bytes counted instead of written when `is_counting`.
+    counted: usize,
+    /// The contents lengths of the definite-length constructed values, in
+    /// the order they begin. Recorded when `is_counting`, and otherwise
+    /// consumed from `next_length` to write each length up front.
+    lengths: Vec<usize>,
+    /// The next entry of `lengths` to write.
+    next_length: usize,
 }
 
 /// A convenience type around results needing to return one or many bytes.
@@ -90,6 +86,10 @@ impl Encoder {
             output: <_>::default(),
             set_buffer: <_>::default(),
             worker: <_>::default(),
+            is_counting: false,
+            counted: 0,
+            lengths: <_>::default(),
+            next_length: 0,
         }
     }
 
@@ -104,11 +104,8 @@ impl Encoder {
     #[must_use]
     pub fn new_set(config: EncoderOptions) -> Self {
         Self {
-            config,
             is_set_encoding: true,
-            output: <_>::default(),
-            set_buffer: <_>::default(),
-            worker: <_>::default(),
+            ..Self::new(config)
         }
     }
 
@@ -121,10 +118,75 @@ impl Encoder {
         buffer.clear();
         Self {
             output: buffer,
-            config,
-            is_set_encoding: false,
-            set_buffer: <_>::default(),
-            worker: <_>::default(),
+            ..Self::new(config)
+        }
+    }
+
+    /// Creates a new instance from the given `config` that counts the bytes
+    /// of the encoding instead of writing them, see [`Encoder::encoded_len`].
+    ///
+    /// ```rust
+    /// use rasn::ber::enc::{Encoder, EncoderOptions};
+    /// use rasn::Encode;
+    ///
+    /// let value = vec![vec![1u8, 2], vec![3]];
+    /// let mut encoder = Encoder::new_counting(EncoderOptions::der());
+    /// value.encode(&mut encoder).unwrap();
+    ///
+    /// assert_eq!(encoder.encoded_len(), rasn::der::encode(&value).unwrap().len());
+    /// ```
+    #[must_use]
+    pub fn new_counting(config: EncoderOptions) -> Self {
+        Self {
+            is_counting: true,
+            ..Self::new(config)
+        }
+    }
+
+    /// Sizes `value` with a counting pass ahead of encoding it with this
+    /// encoder, so that the length of each definite-length constructed value
+    /// is written before its contents, instead of the contents being moved
+    /// over when the length needs more than one byte. The output is also
+    /// allocated up front to the size of the encoding.
+    ///
+    /// `value` has to be the next value encoded.
+    ///
+    /// # Errors
+    /// Returns the error of encoding `value`.
+    pub fn size_ahead<E: Encode>(&mut self, value: &E) -> Result<(), EncodeError> {
+        let mut counter = Self::new_counting(self.config);
+        value.encode(&mut counter)?;
+        self.output.reserve(counter.counted);
+        self.lengths = counter.lengths;
+        self.next_length = 0;
+        Ok(())
+    }
+
+    /// Returns the contents lengths of the definite-length constructed
+    /// values counted so far, in the order the values begin.
+    ///
+    /// ```rust
+    /// use rasn::ber::enc::{Encoder, EncoderOptions};
+    /// use rasn::Encode;
+    ///
+    /// let value = vec![vec![1u8, 2], vec![3]];
+    /// let mut encoder = Encoder::new_counting(EncoderOptions::der());
+    /// value.encode(&mut encoder).unwrap();
+    ///
+    /// assert_eq!(encoder.constructed_lengths(), [13, 6, 3]);
+    /// ```
+    #[must_use]
+    pub fn constructed_lengths(&self) -> &[usize] {
+        &self.lengths
+    }
+
+    /// Returns the length in bytes of the values encoded so far.
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        if self.is_counting {
+            self.counted
+        } else {
+            self.output.len() + self.set_buffer.values().map(Vec::len).sum::<usize>()
         }
     }
 
@@ -143,10 +205,37 @@ impl Encoder {
         }
     }
 
+    /// Writes `bytes` to the output, or only counts them.
+    fn write(&mut self, bytes: &[u8]) {
+        if self.is_counting {
+            self.counted += bytes.len();
+        } else {
+            self.output.extend_from_slice(bytes);
+        }
+    }
+
+    /// Writes `byte` to the output, or only counts it.
+    fn write_byte(&mut self, byte: u8) {
+        if self.is_counting {
+            self.counted += 1;
+        } else {
+            self.output.push(byte);
+        }
+    }
+
+    /// Returns the bytes written to or counted for the current value so far.
+    fn position(&self) -> usize {
+        if self.is_counting {
+            self.counted
+        } else {
+            self.output.len()
+        }
+    }
+
     fn append_byte_or_bytes(&mut self, bytes: ByteOrBytes) {
         match bytes {
-            ByteOrBytes::Single(b) => self.output.push(b),
-            ByteOrBytes::Many(mut bs) => self.output.append(&mut bs),
+            ByteOrBytes::Single(b) => self.write_byte(b),
+            ByteOrBytes::Many(bs) => self.write(&bs),
         }
     }
 
@@ -197,11 +286,11 @@ impl Encoder {
         if identifier.is_primitive() || !self.config.encoding_rules.is_cer() {
             let len_bytes = self.encode_definite_length(value.len());
             self.append_byte_or_bytes(len_bytes);
-            self.output.extend_from_slice(value);
+            self.write(value);
         } else {
-            self.output.push(START_OF_CONTENTS);
-            self.output.extend_from_slice(value);
-            self.output.extend_from_slice(END_OF_CONTENTS);
+            self.write_byte(START_OF_CONTENTS);
+            self.write(value);
+            self.write(END_OF_CONTENTS);
         }
     }
 
@@ -243,13 +332,13 @@ impl Encoder {
             let ident_bytes = self.encode_identifier(Identifier::from_tag(tag, true));
             self.append_byte_or_bytes(ident_bytes);
 
-            self.output.push(START_OF_CONTENTS);
+            self.write_byte(START_OF_CONTENTS);
 
             for chunk in value.chunks(max_string_length) {
                 self.encode_primitive(nested_tag, chunk);
             }
 
-            self.output.extend_from_slice(END_OF_CONTENTS);
+            self.write(END_OF_CONTENTS);
             self.encode_to_set(tag);
         } else {
             self.encode_primitive(tag, value);
@@ -262,12 +351,14 @@ impl Encoder {
         self.encode_value(Identifier::from_tag(tag, false), value);
     }
 
-    fn encode_constructed(&mut self, tag: Tag, value: &[u8]) {
-        self.encode_value(Identifier::from_tag(tag, true), value);
-    }
-
     /// Encodes a given ASN.1 BER value with the `identifier`.
     fn encode_value(&mut self, identifier: Identifier, value: &[u8]) {
+        trace_event!(
+            offset = self.position(),
+            tag = %identifier.tag,
+            length = value.len(),
+            "value"
+        );
         let ident_bytes = self.encode_identifier(identifier);
         self.append_byte_or_bytes(ident_bytes);
         self.encode_length(identifier, value);
@@ -277,7 +368,8 @@ impl Encoder {
     /// Runs at the end of a complete value encoding to decide whether to sort
     /// the output by the tag of each value.
     fn encode_to_set(&mut self, tag: Tag) {
-        if self.is_set_encoding {
+        // Counted components have no output to sort.
+        if self.is_set_encoding && !self.is_counting {
             self.set_buffer
                 .insert(tag, core::mem::take(&mut self.output));
         }
@@ -285,42 +377,25 @@ impl Encoder {
 
     #[must_use]
     /// Canonical byte presentation for CER/DER as defined in X.690 section 11.7.
-    /// Also used for BER on this crate.
-    pub fn datetime_to_canonical_generalized_time_bytes(
-        value: &chrono::DateTime<chrono::FixedOffset>,
-    ) -> Vec<u8> {
-        let mut string;
-        // Convert to UTC so we can always append Z.
-        let value = value.naive_utc();
-        if value.nanosecond() > 0 {
-            string = value.format("%Y%m%d%H%M%S.%f").to_string();
-            // No trailing zeros with fractions
-            while string.ends_with('0') {
-                string.pop();
-            }
-        } else {
-            string = value.format("%Y%m%d%H%M%S").to_string();
-        }
-        string.push('Z');
-        string.into_bytes()
+    /// Also used for BER on this crate. Without the `chrono` and `time`
+    /// features the time is written as it is held.
+    pub fn datetime_to_canonical_generalized_time_bytes(value: &types::GeneralizedTime) -> Vec<u8> {
+        types::time_backend::generalized_time_text(value).into_bytes()
     }
 
     #[must_use]
     /// Canonical byte presentation for CER/DER UTCTime as defined in X.690 section 11.8.
-    /// Also used for BER on this crate.
-    pub fn datetime_to_canonical_utc_time_bytes(value: &chrono::DateTime<chrono::Utc>) -> Vec<u8> {
-        value
-            .naive_utc()
-            .format("%y%m%d%H%M%SZ")
-            .to_string()
-            .into_bytes()
+    /// Also used for BER on this crate. Without the `chrono` and `time`
+    /// features the time is written as it is held.
+    pub fn datetime_to_canonical_utc_time_bytes(value: &types::UtcTime) -> Vec<u8> {
+        types::time_backend::utc_time_text(value).into_bytes()
     }
 
     #[must_use]
     /// Canonical byte presentation for CER/DER DATE as defined in X.690 section 8.26.2
     /// Also used for BER on this crate.
-    pub fn naivedate_to_date_bytes(value: &chrono::NaiveDate) -> Vec<u8> {
-        value.format("%Y%m%d").to_string().into_bytes()
+    pub fn naivedate_to_date_bytes(value: &types::Date) -> Vec<u8> {
+        types::time_backend::date_text(value).into_bytes()
     }
 
     fn check_encode_size_constraint(
@@ -359,22 +434,134 @@ impl Encoder {
         Ok(())
     }
 
-    /// Creates a child encoder that uses the parent's spare `worker` allocation
-    /// as its output buffer, avoiding a fresh heap allocation for sibling fields.
-    fn take_child_encoder(&mut self) -> Self {
-        Self::new_with_buffer(self.config, core::mem::take(&mut self.worker))
+    /// Encodes a constructed value with the contents written by `encode_fn`,
+    /// with the length form of the encoding rules.
+    fn encode_constructed_with(
+        &mut self,
+        tag: Tag,
+        encode_fn: impl FnOnce(&mut Self) -> Result<(), EncodeError>,
+    ) -> Result<(), EncodeError> {
+        let definite = !self.config.encoding_rules.is_cer();
+        self.encode_constructed_with_length(tag, definite, encode_fn)
+    }
+
+    /// Encodes a constructed value with the contents written by `encode_fn`,
+    /// with a definite length when `definite` is set and an indefinite length
+    /// otherwise.
+    ///
+    /// The contents are written in place after the identifier. A definite
+    /// length is written up front when the value was sized with
+    /// [`Encoder::size_ahead`], otherwise a byte is reserved for it and the
+    /// contents are moved over when it needs more.
+    fn encode_constructed_with_length(
+        &mut self,
+        tag: Tag,
+        definite: bool,
+        encode_fn: impl FnOnce(&mut Self) -> Result<(), EncodeError>,
+    ) -> Result<(), EncodeError> {
+        let _offset = self.position();
+        let ident_bytes = self.encode_identifier(Identifier::from_tag(tag, true));
+        self.append_byte_or_bytes(ident_bytes);
+
+        // The contents are part of this value, not components of a set.
+        let is_set_encoding = core::mem::replace(&mut self.is_set_encoding, false);
+        let contents = if definite {
+            self.encode_definite_contents(encode_fn)
+        } else {
+            self.write_byte(START_OF_CONTENTS);
+            let start = self.position();
+            let contents = (encode_fn)(self).map(|()| self.position() - start);
+            self.write(END_OF_CONTENTS);
+            contents
+        };
+        self.is_set_encoding = is_set_encoding;
+        let _length = contents?;
+
+        trace_event!(offset = _offset, %tag, length = _length, "value");
+        self.encode_to_set(tag);
+        Ok(())
+    }
+
+    /// Writes the contents written by `encode_fn` preceded by their definite
+    /// length, and returns the length of the contents.
+    fn encode_definite_contents(
+        &mut self,
+        encode_fn: impl FnOnce(&mut Self) -> Result<(), EncodeError>,
+    ) -> Result<usize, EncodeError> {
+        if self.is_counting {
+            // The slot is taken before the contents, so that the lengths are
+            // in the order the values begin.
+            let slot = self.lengths.len();
+            self.lengths.push(0);
+            let start = self.counted;
+            (encode_fn)(self)?;
+            let contents = self.counted - start;
+            self.lengths[slot] = contents;
+            let len_bytes = self.encode_definite_length(contents);
+            self.append_byte_or_bytes(len_bytes);
+            return Ok(contents);
+        }
+
+        let length_at = self.output.len();
+        let sized = self.lengths.get(self.next_length).copied();
+        let header = match sized {
+            Some(length) => {
+                self.next_length += 1;
+                match self.encode_definite_length(length) {
+                    ByteOrBytes::Single(length) => self.output.push(length),
+                    ByteOrBytes::Many(length) => self.output.extend_from_slice(&length),
+                }
+                self.output.len() - length_at
+            }
+            None => {
+                self.output.push(0);
+                1
+            }
+        };
+        (encode_fn)(self)?;
+        let contents = self.output.len() - length_at - header;
+        if sized != Some(contents) {
+            // Unsized, or encoded differently than it was counted.
+            match self.encode_definite_length(contents) {
+                ByteOrBytes::Single(length) if header == 1 => self.output[length_at] = length,
+                ByteOrBytes::Single(length) => {
+                    self.output.splice(length_at..length_at + header, [length]);
+                }
+                ByteOrBytes::Many(length) => {
+                    self.output.splice(length_at..length_at + header, length);
+                }
+            }
+        }
+        Ok(contents)
     }
 
-    /// Extracts the child's output buffer and stores it back as the parent's
-    /// `worker`, so the next sibling field reuses the same heap allocation.
-    /// Returns the encoded bytes for the caller to write to the parent output.
-    fn reclaim_worker(&mut self, mut child: Encoder) -> Vec<u8> {
-        let output = core::mem::take(&mut child.output);
+    /// Creates a child encoder that counts when this encoder counts, and
+    /// uses the parent's spare `worker` allocation as its output buffer,
+    /// avoiding a fresh heap allocation for sibling fields.
+    fn take_child_encoder(&mut self, is_set_encoding: bool) -> Self {
+        Self {
+            output: core::mem::take(&mut self.worker),
+            is_set_encoding,
+            is_counting: self.is_counting,
+            lengths: core::mem::take(&mut self.lengths),
+            next_length: self.next_length,
+            ..Self::new(self.config)
+        }
+    }
+
+    /// Adds the bytes counted by the child to this encoder's count, and
+    /// stores the child's spare allocation back as the parent's `worker`, so
+    /// the next sibling field reuses the same heap allocation. Returns the
+    /// encoded bytes for the caller to write to the parent output.
+    fn reclaim_child(&mut self, mut child: Encoder) -> Vec<u8> {
+        self.counted += child.counted;
+        self.lengths = core::mem::take(&mut child.lengths);
+        self.next_length = child.next_length;
         // Keep the larger capacity as the next worker; drop the smaller one.
         if child.worker.capacity() > self.worker.capacity() {
             self.worker = core::mem::take(&mut child.worker);
         }
-        output
+        child.output()
     }
 }
 
@@ -396,6 +583,7 @@ impl crate::Encoder<'_> for Encoder {
         if self.is_set_encoding {
             return Err(BerEncodeErrorKind::AnyInSet.into());
         }
+        value.check_codec(self.codec())?;
 
         let inner = value.as_bytes();
         if inner.is_empty() {
@@ -410,7 +598,7 @@ impl crate::Encoder<'_> for Encoder {
             self.encode_length(ident, inner);
             self.encode_to_set(ident.tag);
         } else {
-            self.output.extend_from_slice(inner);
+            self.write(inner);
         }
 
         Ok(())
@@ -457,6 +645,11 @@ impl crate::Encoder<'_> for Encoder {
         encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!(
+            "choice",
+            ty = core::any::type_name::<E>(),
+            offset = self.position()
+        );
         (encode_fn)(self).map(drop)
     }
 
@@ -466,6 +659,11 @@ impl crate::Encoder<'_> for Encoder {
         value: &E,
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!(
+            "enumerated",
+            ty = core::any::type_name::<E>(),
+            offset = self.position()
+        );
         let value = E::discriminant(value);
         self.encode_integer(
             tag,
@@ -510,9 +708,24 @@ impl crate::Encoder<'_> for Encoder {
     fn encode_object_identifier(
         &mut self,
         tag: Tag,
-        oid: &[u32],
+        oid: &Oid,
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        if self.is_counting {
+            // Only the length of the contents is needed to count them.
+            let contents = oid.ber_contents_len().ok_or_else(|| {
+                EncodeError::from(BerEncodeErrorKind::invalid_object_identifier(
+                    oid.to_owned(),
+                ))
+            })?;
+            let ident_bytes = self.encode_identifier(Identifier::from_tag(tag, false));
+            self.append_byte_or_bytes(ident_bytes);
+            let len_bytes = self.encode_definite_length(contents);
+            self.append_byte_or_bytes(len_bytes);
+            self.counted += contents;
+            return Ok(());
+        }
+
         let mut buffer = Vec::new();
         object_identifier_as_bytes(oid, &mut buffer)?;
         self.encode_primitive(tag, &buffer);
@@ -632,8 +845,7 @@ impl crate::Encoder<'_> for Encoder {
         value: &str,
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        // UTF8String SIZE constraint is in Unicode characters, not bytes.
-        Self::check_encode_size_constraint(value.chars().count(), &constraints, self.codec())?;
+        crate::enc::check_utf8_string(value, &constraints, self.codec())?;
         self.encode_octet_string_(tag, value.as_bytes())
     }
 
@@ -731,24 +943,17 @@ impl crate::Encoder<'_> for Encoder {
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         Self::check_encode_size_constraint(values.len(), &constraints, self.codec())?;
-        let mut sequence_encoder = self.take_child_encoder();
-
-        for value in values {
-            value.encode(&mut sequence_encoder)?;
-        }
-
-        let child_output = self.reclaim_worker(sequence_encoder);
-        self.encode_constructed(tag, &child_output);
-        self.worker = {
-            let mut w = child_output;
-            w.clear();
-            w
-        };
-
-        Ok(())
+        trace_span!(
+            "sequence of",
+            ty = core::any::type_name::<E>(),
+            offset = self.position()
+        );
+        self.encode_constructed_with(tag, |encoder| {
+            values.iter().try_for_each(|value| value.encode(encoder))
+        })
     }
 
-    fn encode_set_of<E: Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: Encode>(
         &mut self,
         tag: Tag,
         values: &types::SetOf<E>,
@@ -756,42 +961,78 @@ impl crate::Encoder<'_> for Encoder {
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         Self::check_encode_size_constraint(values.len(), &constraints, self.codec())?;
-        // Encode every element sequentially into one buffer, recording each
-        // element's byte range so we can sort without extra allocations.
-        let mut combined = core::mem::take(&mut self.worker);
-        combined.clear();
-        let mut elem_enc = Self::new(self.config);
-        let mut ranges: Vec<(usize, usize)> = Vec::with_capacity(values.len());
-
-        for val in values.to_vec() {
-            elem_enc.output.clear();
-            val.encode(&mut elem_enc)?;
-            let start = combined.len();
-            combined.extend_from_slice(&elem_enc.output);
-            ranges.push((start, combined.len()));
+        trace_span!(
+            "set of",
+            ty = core::any::type_name::<E>(),
+            offset = self.position()
+        );
+        if self.config.set_of_ordering == SetOfOrdering::Preserve
+            && self.config.encoding_rules.is_ber()
+        {
+            return self.encode_constructed_with(tag, |encoder| {
+                values.iter().try_for_each(|value| value.encode(encoder))
+            });
         }
+        // Sorted SET OF values are always written with a definite length.
+        self.encode_constructed_with_length(tag, true, |encoder| {
+            if encoder.is_counting {
+                // Counted elements have no output to sort.
+                return values.iter().try_for_each(|value| value.encode(encoder));
+            }
 
-        // The encodings of the component values of a set-of value shall appear
-        // in ascending order, the encodings being compared as octet strings.
-        ranges.sort_by(|(as_, ae), (bs, be)| combined[*as_..*ae].cmp(&combined[*bs..*be]));
+            // Encode every element sequentially into one buffer, recording each
+            // element's byte range so we can sort without extra allocations.
+            let mut elements = encoder.take_child_encoder(false);
+            let mut ranges: Vec<(usize, usize)> = Vec::with_capacity(values.len());
+            for value in values.iter() {
+                let start = elements.output.len();
+                value.encode(&mut elements)?;
+                ranges.push((start, elements.output.len()));
+            }
+            let combined = encoder.reclaim_child(elements);
 
-        // Total content length is invariant under sorting, so write the outer
-        // tag + definite length before streaming the sorted elements directly
-        // into self.output — no intermediate sorted-concatenation buffer needed.
-        let ident = Identifier::from_tag(tag, true);
-        let ident_bytes = self.encode_identifier(ident);
-        self.append_byte_or_bytes(ident_bytes);
-        let len_bytes = self.encode_definite_length(combined.len());
-        self.append_byte_or_bytes(len_bytes);
-        for (start, end) in ranges {
-            self.output.extend_from_slice(&combined[start..end]);
-        }
-        self.encode_to_set(tag);
+            // The encodings of the component values of a set-of value shall appear
+            // in ascending order, the encodings being compared as octet strings.
+            ranges.sort_by(|(as_, ae), (bs, be)| combined[*as_..*ae].cmp(&combined[*bs..*be]));
+            for (start, end) in ranges {
+                encoder.write(&combined[start..end]);
+            }
+
+            encoder.worker = {
+                let mut w = combined;
+                w.clear();
+                w
+            };
+            Ok(())
+        })
+    }
 
-        combined.clear();
-        self.worker = combined;
+    fn encode_sorted_set_of<E: Encode>(
+        &mut self,
+        tag: Tag,
+        values: &types::SortedSetOf<E>,
+        constraints: Constraints,
+        identifier: crate::types::Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        // The stored encodings are DER, which CER encodes some values
+        // differently from, so the elements are encoded again for CER.
+        if self.config.encoding_rules.is_cer() {
+            let elements = types::SetOf::from_vec(values.iter().collect());
+            return self.encode_set_of(tag, &elements, constraints, identifier);
+        }
 
-        Ok(())
+        Self::check_encode_size_constraint(values.len(), &constraints, self.codec())?;
+        trace_span!(
+            "sorted set of",
+            ty = core::any::type_name::<E>(),
+            offset = self.position()
+        );
+        self.encode_constructed_with_length(tag, true, |encoder| {
+            for encoding in values.encodings() {
+                encoder.write(encoding);
+            }
+            Ok(())
+        })
     }
 
     fn encode_explicit_prefix<V: Encode>(
@@ -801,15 +1042,7 @@ impl crate::Encoder<'_> for Encoder {
         _: crate::types::Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         if value.is_present() {
-            let mut encoder = self.take_child_encoder();
-            value.encode(&mut encoder)?;
-            let child_output = self.reclaim_worker(encoder);
-            self.encode_constructed(tag, &child_output);
-            self.worker = {
-                let mut w = child_output;
-                w.clear();
-                w
-            };
+            self.encode_constructed_with(tag, |encoder| value.encode(encoder))?;
         }
         Ok(())
     }
@@ -824,19 +1057,12 @@ impl crate::Encoder<'_> for Encoder {
         C: crate::types::Constructed<RC, EC>,
         F: FnOnce(&mut Self::AnyEncoder<'b, 0, 0>) -> Result<(), Self::Error>,
     {
-        let mut encoder = self.take_child_encoder();
-
-        (encoder_scope)(&mut encoder)?;
-
-        let child_output = self.reclaim_worker(encoder);
-        self.encode_constructed(tag, &child_output);
-        self.worker = {
-            let mut w = child_output;
-            w.clear();
-            w
-        };
-
-        Ok(())
+        trace_span!(
+            "sequence",
+            ty = core::any::type_name::<C>(),
+            offset = self.position()
+        );
+        self.encode_constructed_with(tag, encoder_scope)
     }
 
     fn encode_set<'b, const RC: usize, const EC: usize, C, F>(
@@ -849,22 +1075,24 @@ impl crate::Encoder<'_> for Encoder {
         C: crate::types::Constructed<RC, EC>,
         F: FnOnce(&mut Self::AnyEncoder<'b, 0, 0>) -> Result<(), Self::Error>,
     {
-        let mut encoder = Self::new_set(self.config);
-        encoder.worker = core::mem::take(&mut self.worker);
-
-        (encoder_scope)(&mut encoder)?;
-
-        // output() merges the set_buffer entries in tag order into a fresh Vec.
-        // We recycle that Vec as the new worker after copying into self.output.
-        let merged = encoder.output();
-        self.encode_constructed(tag, &merged);
-        self.worker = {
-            let mut w = merged;
-            w.clear();
-            w
-        };
-
-        Ok(())
+        trace_span!(
+            "set",
+            ty = core::any::type_name::<C>(),
+            offset = self.position()
+        );
+        self.encode_constructed_with(tag, |encoder| {
+            // The components are buffered to be written in order by tag.
+            let mut set = encoder.take_child_encoder(true);
+            (encoder_scope)(&mut set)?;
+            let components = encoder.reclaim_child(set);
+            encoder.write(&components);
+            encoder.worker = {
+                let mut w = components;
+                w.clear();
+                w
+            };
+            Ok(())
+        })
     }
 
     fn encode_extension_addition<E: Encode>(
@@ -897,6 +1125,7 @@ impl crate::Encoder<'_> for Encoder {
 
 #[cfg(test)]
 mod tests {
+    use crate::Encoder as _;
     use crate::ber::enc::{Encoder, EncoderOptions};
     use crate::{Encode, types::*};
     use alloc::borrow::ToOwned;
@@ -944,10 +1173,11 @@ mod tests {
 
     #[test]
     fn encoding_oid() {
-        fn oid_to_bytes(oid: &[u32]) -> Vec<u8> {
+        fn oid_to_bytes(oid: &'static [u32]) -> Vec<u8> {
             use crate::Encoder;
+            let oid = crate::types::ObjectIdentifier::new(oid).unwrap();
             let mut enc = self::Encoder::new(EncoderOptions::ber());
-            enc.encode_object_identifier(Tag::OBJECT_IDENTIFIER, oid, Identifier::EMPTY)
+            enc.encode_object_identifier(Tag::OBJECT_IDENTIFIER, &oid, Identifier::EMPTY)
                 .unwrap();
             enc.output
         }
@@ -1004,9 +1234,7 @@ mod tests {
         let bitstring = BitString::from_vec([0x0A, 0x3B, 0x5F, 0x29, 0x1C, 0xD0][..].to_owned());
 
         let primitive_encoded = &[0x03, 0x07, 0x00, 0x0A, 0x3B, 0x5F, 0x29, 0x1C, 0xD0][..];
-        let any = Any {
-            contents: primitive_encoded.into(),
-        };
+        let any = Any::new(primitive_encoded.into());
 
         assert_eq!(primitive_encoded, super::super::encode(&bitstring).unwrap());
         assert_eq!(
@@ -1257,4 +1485,250 @@ mod tests {
             EncodeErrorKind::SizeConstraintNotSatisfied { size: 4, .. }
         ));
     }
+
+    fn encode_with<T: Encode>(options: EncoderOptions, value: &T) -> Vec<u8> {
+        let mut encoder = Encoder::new(options);
+        value.encode(&mut encoder).unwrap();
+        encoder.output()
+    }
+
+    #[test]
+    fn set_of_ordering() {
+        use crate::ber::enc::SetOfOrdering;
+
+        let value = SetOf::from_vec(vec![300u16, 2]);
+        let sorted = [0x31, 0x07, 0x02, 0x01, 0x02, 0x02, 0x02, 0x01, 0x2c];
+        let preserved = [0x31, 0x07, 0x02, 0x02, 0x01, 0x2c, 0x02, 0x01, 0x02];
+
+        let ber = EncoderOptions::ber();
+        assert_eq!(encode_with(ber, &value), sorted);
+        let ber = ber.with_set_of_ordering(SetOfOrdering::Preserve);
+        assert_eq!(encode_with(ber, &value), preserved);
+
+        // CER and DER always sort.
+        for options in [EncoderOptions::cer(), EncoderOptions::der()] {
+            let options = options.with_set_of_ordering(SetOfOrdering::Preserve);
+            assert_eq!(encode_with(options, &value), sorted);
+        }
+    }
+
+    #[test]
+    fn sorted_set_of() {
+        #[derive(AsnType, Encode)]
+        #[rasn(crate_root = "crate")]
+        struct Sorted {
+            set: SortedSetOf<Vec<OctetString>>,
+            after: Vec<OctetString>,
+        }
+
+        #[derive(AsnType, Encode)]
+        #[rasn(crate_root = "crate")]
+        struct Unsorted {
+            set: SetOf<Vec<OctetString>>,
+            after: Vec<OctetString>,
+        }
+
+        let elements = vec![
+            vec![OctetString::from(vec![0xAB; 1500])],
+            vec![],
+            vec![OctetString::from_static(&[1]), OctetString::default()],
+        ];
+        let after = vec![OctetString::from_static(&[2])];
+        let sorted = Sorted {
+            set: SortedSetOf::from_vec(elements.clone()).unwrap(),
+            after: after.clone(),
+        };
+        let unsorted = Unsorted {
+            set: SetOf::from_vec(elements),
+            after,
+        };
+
+        for options in [
+            EncoderOptions::ber(),
+            EncoderOptions::cer(),
+            EncoderOptions::der(),
+        ] {
+            assert_eq!(
+                encode_with(options, &sorted),
+                encode_with(options, &unsorted)
+            );
+        }
+    }
+
+    #[derive(AsnType, Encode)]
+    #[rasn(crate_root = "crate")]
+    struct Nested {
+        #[rasn(tag(explicit(0)))]
+        id: Integer,
+        data: OctetString,
+        names: SetOf<Utf8String>,
+        #[rasn(tag(1))]
+        extra: Option<Vec<bool>>,
+    }
+
+    fn nested() -> Nested {
+        Nested {
+            id: Integer::from(-129),
+            data: OctetString::from(vec![0xAB; 2000]),
+            names: SetOf::from_vec(vec!["b".into(), "a".into()]),
+            extra: Some(vec![true; 3]),
+        }
+    }
+
+    #[derive(AsnType, Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[rasn(enumerated, crate_root = "crate")]
+    enum Colour {
+        Red,
+        Green,
+    }
+
+    #[derive(AsnType, Encode)]
+    #[rasn(choice, crate_root = "crate")]
+    enum Pick {
+        #[rasn(tag(0))]
+        Flag(bool),
+        #[rasn(tag(1))]
+        Number(Integer),
+    }
+
+    #[derive(AsnType, Encode)]
+    #[rasn(set, crate_root = "crate")]
+    struct Pair {
+        #[rasn(tag(1))]
+        second: Vec<Integer>,
+        #[rasn(tag(0))]
+        first: bool,
+    }
+
+    /// A value of every type the BER encoder supports.
+    #[derive(AsnType, Encode)]
+    #[rasn(crate_root = "crate")]
+    struct Everything {
+        boolean: bool,
+        small: Integer,
+        big: Integer,
+        bits: BitString,
+        octets: OctetString,
+        null: (),
+        oid: ObjectIdentifier,
+        colour: Colour,
+        utf8: Utf8String,
+        numeric: NumericString,
+        printable: PrintableString,
+        teletex: TeletexString,
+        visible: VisibleString,
+        ia5: Ia5String,
+        general: GeneralString,
+        graphic: GraphicString,
+        bmp: BmpString,
+        utc: UtcTime,
+        generalized: GeneralizedTime,
+        date: Date,
+        sequence_of: Vec<Integer>,
+        set_of: SetOf<OctetString>,
+        sorted_set_of: SortedSetOf<Integer>,
+        any: Any,
+        picks: Vec<Pick>,
+        pair: Pair,
+        #[rasn(tag(200))]
+        long_tag: Integer,
+        #[rasn(tag(explicit(application, 5)))]
+        explicit: Vec<bool>,
+        #[rasn(tag(2))]
+        absent: Option<Integer>,
+        #[rasn(tag(3))]
+        present: Option<Integer>,
+        nested: Vec<Nested>,
+    }
+
+    fn everything() -> Everything {
+        use crate::types::strings::StaticPermittedAlphabet;
+
+        let mut teletex = TeletexString::default();
+        let mut bmp = BmpString::default();
+        for ch in "Grüße".chars() {
+            teletex.push_char(ch.into());
+            bmp.push_char(ch.into());
+        }
+
+        Everything {
+            boolean: true,
+            small: Integer::from(0),
+            big: Integer::from(i128::MIN),
+            bits: [true, false, true].repeat(5).into_iter().collect(),
+            octets: OctetString::from(vec![0x5A; 2500]),
+            null: (),
+            oid: ObjectIdentifier::new(vec![1, 2, 840, 113_549, 1, 1, 11]).unwrap(),
+            colour: Colour::Green,
+            utf8: "Grüße, 世界".into(),
+            numeric: NumericString::try_from("12 34").unwrap(),
+            printable: PrintableString::try_from("Hello").unwrap(),
+            teletex,
+            visible: VisibleString::try_from("visible").unwrap(),
+            ia5: Ia5String::try_from("ia5@example.com").unwrap(),
+            general: GeneralString::try_from("general").unwrap(),
+            graphic: GraphicString::try_from("graphic").unwrap(),
+            bmp,
+            utc: crate::der::decode(b"\x17\x0D230102030405Z").unwrap(),
+            generalized: crate::der::decode(b"\x18\x1320230102030405.125Z").unwrap(),
+            date: crate::der::decode(b"\x1F\x1F\x0820230102").unwrap(),
+            sequence_of: (0..100).map(Integer::from).collect(),
+            set_of: SetOf::from_vec(vec![OctetString::from(vec![2; 200]), vec![1].into()]),
+            sorted_set_of: SortedSetOf::from_vec(vec![Integer::from(300), Integer::from(2)])
+                .unwrap(),
+            any: Any::new(crate::der::encode(&vec![true; 3]).unwrap()),
+            picks: vec![Pick::Flag(true), Pick::Number(Integer::from(300))],
+            pair: Pair {
+                second: vec![Integer::from(2); 100],
+                first: false,
+            },
+            long_tag: Integer::from(1),
+            explicit: vec![true; 200],
+            absent: None,
+            present: Some(Integer::from(-1)),
+            nested: vec![nested(), nested()],
+        }
+    }
+
+    #[test]
+    fn counts_encoded_length() {
+        let value = everything();
+        for options in [
+            EncoderOptions::ber(),
+            EncoderOptions::cer(),
+            EncoderOptions::der(),
+        ] {
+            let mut encoder = Encoder::new_counting(options);
+            value.encode(&mut encoder).unwrap();
+            assert_eq!(encoder.encoded_len(), encode_with(options, &value).len());
+        }
+
+        // Values the encoder rejects are rejected when counting too.
+        let options = EncoderOptions::der();
+        let foreign = Any::with_codec(vec![0x05, 0x00], crate::Codec::Uper);
+        assert!(foreign.encode(&mut Encoder::new(options)).is_err());
+        assert!(foreign.encode(&mut Encoder::new_counting(options)).is_err());
+        #[cfg(feature = "f64")]
+        {
+            assert!(1.5f64.encode(&mut Encoder::new(options)).is_err());
+            assert!(1.5f64.encode(&mut Encoder::new_counting(options)).is_err());
+        }
+    }
+
+    #[test]
+    fn sized_encoding() {
+        let value = everything();
+        for options in [
+            EncoderOptions::ber(),
+            EncoderOptions::cer(),
+            EncoderOptions::der(),
+        ] {
+            let mut encoder = Encoder::new(options);
+            encoder.size_ahead(&value).unwrap();
+            value.encode(&mut encoder).unwrap();
+            // Every length written up front was the one counted.
+            assert_eq!(encoder.next_length, encoder.lengths.len());
+            assert_eq!(encoder.output(), encode_with(options, &value));
+        }
+    }
 }
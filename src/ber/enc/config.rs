@@ -4,6 +4,21 @@ use crate::ber::EncodingRules;
 #[derive(Clone, Copy, Debug)]
 pub struct EncoderOptions {
     pub(crate) encoding_rules: EncodingRules,
+    pub(crate) set_of_ordering: SetOfOrdering,
+}
+
+/// The order the elements of `SET OF` values are encoded in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SetOfOrdering {
+    /// Sort the elements in ascending order of their encodings, as CER and
+    /// DER require. Every element is encoded apart from the output so that
+    /// the encodings can be compared.
+    #[default]
+    Sorted,
+    /// Encode the elements in the order they are stored in, straight into the
+    /// output, which BER allows. Ignored with CER and DER, which always sort
+    /// the elements.
+    Preserve,
 }
 
 impl EncoderOptions {
@@ -12,6 +27,7 @@ impl EncoderOptions {
     pub const fn ber() -> Self {
         Self {
             encoding_rules: EncodingRules::Ber,
+            set_of_ordering: SetOfOrdering::Sorted,
         }
     }
 
@@ -20,6 +36,7 @@ impl EncoderOptions {
     pub const fn cer() -> Self {
         Self {
             encoding_rules: EncodingRules::Cer,
+            set_of_ordering: SetOfOrdering::Sorted,
         }
     }
 
@@ -28,9 +45,18 @@ impl EncoderOptions {
     pub const fn der() -> Self {
         Self {
             encoding_rules: EncodingRules::Der,
+            set_of_ordering: SetOfOrdering::Sorted,
         }
     }
 
+    /// Returns the configuration with the elements of `SET OF` values
+    /// encoded in the given `ordering`.
+    #[must_use]
+    pub const fn with_set_of_ordering(mut self, ordering: SetOfOrdering) -> Self {
+        self.set_of_ordering = ordering;
+        self
+    }
+
     /// Returns the currently selected codec.
     #[must_use]
     pub fn current_codec(&self) -> crate::Codec {
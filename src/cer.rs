@@ -25,11 +25,23 @@ pub fn encode<T: crate::Encode>(
 ) -> Result<alloc::vec::Vec<u8>, crate::error::EncodeError> {
     let mut enc = crate::ber::enc::Encoder::new(crate::ber::enc::EncoderOptions::cer());
 
+    enc.size_ahead(value)?;
     value.encode(&mut enc)?;
 
     Ok(enc.output())
 }
 
+/// Returns the length of the CER encoding of `value`, without encoding it.
+/// # Errors
+/// Returns error specific to CER encoder if encoding is not possible.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::error::EncodeError> {
+    let mut enc = crate::ber::enc::Encoder::new_counting(crate::ber::enc::EncoderOptions::cer());
+
+    value.encode(&mut enc)?;
+
+    Ok(enc.encoded_len())
+}
+
 /// Encodes `value` to CER into an existing `buffer`, reusing its allocation.
 /// The buffer is cleared before encoding.
 /// # Errors
@@ -42,6 +54,7 @@ pub fn encode_buf<T: crate::Encode>(
         crate::ber::enc::EncoderOptions::cer(),
         core::mem::take(buffer),
     );
+    enc.size_ahead(value)?;
     value.encode(&mut enc)?;
     *buffer = enc.output();
     Ok(())
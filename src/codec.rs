@@ -23,6 +23,8 @@ pub enum Codec {
     Coer,
     /// X.693 — XML Encoding Rules
     Xer,
+    /// X.693 — Canonical XML Encoding Rules
+    Cxer,
     /// ASN.1 Value Notation (X.680 text format)
     Avn,
 }
@@ -39,6 +41,7 @@ impl core::fmt::Display for Codec {
             Self::Oer => write!(f, "OER"),
             Self::Coer => write!(f, "COER"),
             Self::Xer => write!(f, "XER"),
+            Self::Cxer => write!(f, "CXER"),
             Self::Avn => write!(f, "AVN"),
         }
     }
@@ -64,10 +67,41 @@ impl Codec {
             Self::Oer => crate::oer::encode(value),
             Self::Coer => crate::coer::encode(value),
             Self::Xer => crate::xer::encode(value),
+            Self::Cxer => crate::cxer::encode(value),
             Self::Avn => crate::avn::encode(value).map(alloc::string::String::into_bytes),
         }
     }
 
+    /// Returns the length in bytes of the encoding of `value` based on the
+    /// value of `Codec`, without encoding it.
+    ///
+    /// The binary codecs count the length of the encoding without producing
+    /// it. Use [`Codec::encode_to_binary`] and the length of its output for
+    /// the text codecs.
+    ///
+    /// # Errors
+    /// - If the value fails to be encoded, or if the codec is a text codec,
+    ///   returns `EncodeError` struct.
+    pub fn encoded_len<T: Encode>(self, value: &T) -> Result<usize, crate::error::EncodeError> {
+        match self {
+            Self::Aper => crate::aper::encoded_len(value),
+            Self::Ber => crate::ber::encoded_len(value),
+            Self::Cer => crate::cer::encoded_len(value),
+            Self::Der => crate::der::encoded_len(value),
+            Self::Uper => crate::uper::encoded_len(value),
+            Self::Oer => crate::oer::encoded_len(value),
+            Self::Coer => crate::coer::encoded_len(value),
+            codec => Err(crate::error::EncodeError::from_kind(
+                crate::error::EncodeErrorKind::Custom {
+                    msg: alloc::format!(
+                        "{codec} can't count the length of an encoding. Call `Codec::encode_to_binary` instead."
+                    ),
+                },
+                codec,
+            )),
+        }
+    }
+
     /// Decodes `input` to `D` based on the value of `Codec`.
     /// This method shall be used when using binary-based encoding rules.
     ///
@@ -86,6 +120,7 @@ impl Codec {
             Self::Oer => crate::oer::decode(input),
             Self::Coer => crate::coer::decode(input),
             Self::Xer => crate::xer::decode(input),
+            Self::Cxer => crate::cxer::decode(input),
             Self::Jer => alloc::string::String::from_utf8(input.to_vec()).map_or_else(
                 |e| {
                     Err(crate::error::DecodeError::from_kind(
@@ -129,6 +164,12 @@ impl Codec {
                 },
                 *self,
             )),
+            Self::Cxer => Err(crate::error::DecodeError::from_kind(
+                crate::error::DecodeErrorKind::Custom {
+                    msg: "CXER does not support decoding with remainder.".into(),
+                },
+                *self,
+            )),
             Self::Jer => Err(crate::error::DecodeError::from_kind(
                 crate::error::DecodeErrorKind::Custom {
                     msg: "JER does not support decoding with remainder. ".into(),
@@ -144,6 +185,85 @@ impl Codec {
         }
     }
 
+    /// Transcodes `input` from the `from` encoding rules to the `to` encoding
+    /// rules, by decoding it as a `T` and encoding the result.
+    ///
+    /// Decoders skip extension additions that are unknown to `T`, so they are
+    /// not part of the output.
+    ///
+    /// ```rust
+    /// use rasn::{Codec, types::Integer};
+    ///
+    /// let jer = Codec::transcode::<Integer>(Codec::Uper, Codec::Jer, &[0x01, 0x2A]).unwrap();
+    /// assert_eq!(jer, b"42");
+    /// ```
+    ///
+    /// # Errors
+    /// - If `input` isn't a valid `from` encoding of a `T`, or if the value
+    ///   fails to be encoded with `to`, returns `TranscodeError` enum.
+    pub fn transcode<T: Encode + Decode>(
+        from: Self,
+        to: Self,
+        input: &[u8],
+    ) -> Result<alloc::vec::Vec<u8>, crate::error::TranscodeError> {
+        let value: T = from
+            .decode_from_binary(input)
+            .map_err(|source| crate::error::TranscodeError::Decode { source })?;
+        to.encode_to_binary(&value)
+            .map_err(|source| crate::error::TranscodeError::Encode { source })
+    }
+
+    /// Transcodes `input` from the `from` encoding rules to the `to` encoding
+    /// rules without a schema, by decoding it as a [`Value`](crate::value::Value).
+    ///
+    /// Only BER, CER and DER are self-describing enough to be the source.
+    /// Values that `to` can't represent without a schema are handled as
+    /// `unrepresentable` asks. There are no extension additions without a
+    /// schema, every decoded value is transcoded.
+    ///
+    /// A `SEQUENCE` or `SET` can't be told apart from a `SEQUENCE OF` or
+    /// `SET OF` without a schema, so its components are transcoded as the
+    /// items of a `SEQUENCE OF` or `SET OF`. The names of the components are
+    /// unknown and aren't part of the output, JER writes a JSON array instead
+    /// of an object, and XER fails as it needs a name for every element. Such
+    /// output only decodes as a `SEQUENCE OF` or `SET OF`, use
+    /// [`Codec::transcode`] with the Rust type of the value to keep the
+    /// component names.
+    ///
+    /// ```rust
+    /// use rasn::{Codec, value::Unrepresentable};
+    ///
+    /// let jer = Codec::transcode_schemaless(
+    ///     Codec::Ber,
+    ///     Codec::Jer,
+    ///     &[0x02, 0x01, 0x2A],
+    ///     Unrepresentable::Error,
+    /// ).unwrap();
+    /// assert_eq!(jer, b"42");
+    ///
+    /// // SEQUENCE { INTEGER 5, BOOLEAN TRUE }
+    /// let jer = Codec::transcode_schemaless(
+    ///     Codec::Ber,
+    ///     Codec::Jer,
+    ///     &[0x30, 0x06, 0x02, 0x01, 0x05, 0x01, 0x01, 0xFF],
+    ///     Unrepresentable::Error,
+    /// ).unwrap();
+    /// assert_eq!(jer, b"[5,true]");
+    /// ```
+    ///
+    /// # Errors
+    /// - If `from` isn't BER, CER or DER, if `input` fails to be decoded, or if
+    ///   the value fails to be encoded with `to`, returns `TranscodeError`
+    ///   enum.
+    pub fn transcode_schemaless(
+        from: Self,
+        to: Self,
+        input: &[u8],
+        unrepresentable: crate::value::Unrepresentable,
+    ) -> Result<alloc::vec::Vec<u8>, crate::error::TranscodeError> {
+        crate::value::transcode(from, to, input, unrepresentable)
+    }
+
     /// Encodes a given value based on the value of `Codec`.
     /// This method shall be used when using text-based encoding rules.
     ///
@@ -47,6 +47,22 @@ pub fn encode_buf<T: crate::Encode>(
     value.encode(&mut enc)?;
     Ok(())
 }
+
+/// Returns the length of the COER encoding of `value`, without encoding it.
+///
+/// # Errors
+/// Returns `EncodeError` if `value` cannot be encoded as COER, usually meaning that constraints
+/// are not met.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, EncodeError> {
+    let (mut output, mut worker) = (enc::Counter::default(), enc::Counter::default());
+    let mut enc = Encoder::<0, 0, enc::Counter>::from_buffer(
+        enc::EncoderOptions::coer(),
+        &mut output,
+        &mut worker,
+    );
+    value.encode(&mut enc)?;
+    Ok(enc.encoded_len())
+}
 /// Attempts to decode `T` from `input` using OER with constraints.
 ///
 /// # Errors
@@ -80,119 +96,109 @@ pub fn encode_with_constraints<T: crate::Encode>(
 mod tests {
     use crate::prelude::*;
     use crate::{self as rasn};
+    use crate::{
+        Codec,
+        testing::{round_trip_encoding, round_trip_encoding_with_constraints},
+    };
     use bitvec::prelude::*;
     #[test]
     fn bool() {
-        round_trip!(coer, bool, true, &[0xff]);
-        round_trip!(coer, bool, false, &[0]);
+        round_trip_encoding::<bool>(Codec::Coer, &true, &[0xff]);
+        round_trip_encoding::<bool>(Codec::Coer, &false, &[0]);
     }
     #[test]
     #[allow(clippy::too_many_lines)]
     fn integer_no_constraints() {
         // Without constraints, all integers should be encoded as signed, with length determinant,
         // and without padding.
-        round_trip!(coer, Integer, 0.into(), &[0x01u8, 0x00]);
-        round_trip!(coer, Integer, 1.into(), &[0x01u8, 0x01]);
-        round_trip!(coer, Integer, (-1).into(), &[0x01u8, 0xff]);
-        round_trip!(coer, Integer, 255.into(), &[0x02u8, 0x00, 0xff]);
-        round_trip!(coer, Integer, (-255).into(), &[0x02u8, 0xff, 0x01]);
-        round_trip!(coer, Integer, i16::MAX.into(), &[0x02u8, 0x7f, 0xff]);
-        round_trip!(coer, Integer, i16::MIN.into(), &[0x02u8, 0x80, 0x00]);
-        round_trip!(
-            coer,
-            Integer,
-            (i32::from(i16::MAX) + 1).into(),
-            &[0x03u8, 0x00u8, 0x80, 0x00]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            (i32::from(i16::MIN) - 1).into(),
-            &[0x03u8, 0xff, 0x7f, 0xff]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            i32::MAX.into(),
-            &[0x04u8, 0x7f, 0xff, 0xff, 0xff]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            i32::MIN.into(),
-            &[0x04u8, 0x80, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            (i64::from(i32::MAX) + 1).into(),
-            &[0x05u8, 0x00, 0x80, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            (i64::from(i32::MIN) - 1).into(),
-            &[0x05u8, 0xff, 0x7f, 0xff, 0xff, 0xff]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            i64::MAX.into(),
-            &[0x08u8, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            i64::MIN.into(),
-            &[0x08u8, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            (i128::from(i64::MAX) + 1).into(),
-            &[0x09u8, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            (i128::from(i64::MIN) - 1).into(),
-            &[0x09u8, 0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
-        );
-        round_trip!(
-            coer,
-            Integer,
-            i128::MAX.into(),
+        round_trip_encoding::<Integer>(Codec::Coer, &0.into(), &[0x01u8, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Coer, &1.into(), &[0x01u8, 0x01]);
+        round_trip_encoding::<Integer>(Codec::Coer, &(-1).into(), &[0x01u8, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Coer, &255.into(), &[0x02u8, 0x00, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Coer, &(-255).into(), &[0x02u8, 0xff, 0x01]);
+        round_trip_encoding::<Integer>(Codec::Coer, &i16::MAX.into(), &[0x02u8, 0x7f, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Coer, &i16::MIN.into(), &[0x02u8, 0x80, 0x00]);
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(i32::from(i16::MAX) + 1).into(),
+            &[0x03u8, 0x00u8, 0x80, 0x00],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(i32::from(i16::MIN) - 1).into(),
+            &[0x03u8, 0xff, 0x7f, 0xff],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &i32::MAX.into(),
+            &[0x04u8, 0x7f, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &i32::MIN.into(),
+            &[0x04u8, 0x80, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(i64::from(i32::MAX) + 1).into(),
+            &[0x05u8, 0x00, 0x80, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(i64::from(i32::MIN) - 1).into(),
+            &[0x05u8, 0xff, 0x7f, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &i64::MAX.into(),
+            &[0x08u8, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &i64::MIN.into(),
+            &[0x08u8, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(i128::from(i64::MAX) + 1).into(),
+            &[0x09u8, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(i128::from(i64::MIN) - 1).into(),
+            &[0x09u8, 0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &i128::MAX.into(),
             &[
                 0x10u8, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-                0xff, 0xff, 0xff, 0xff
-            ]
+                0xff, 0xff, 0xff, 0xff,
+            ],
         );
-        round_trip!(
-            coer,
-            Integer,
-            i128::MIN.into(),
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &i128::MIN.into(),
             &[
                 0x10u8, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-                0x00, 0x00, 0x00, 0x00
-            ]
+                0x00, 0x00, 0x00, 0x00,
+            ],
         );
-        round_trip!(
-            coer,
-            Integer,
-            Integer::from(i128::MAX) + 1,
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(Integer::from(i128::MAX) + 1),
             &[
                 0x11u8, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-                0x00, 0x00, 0x00, 0x00, 0x00
-            ]
+                0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
         );
-        round_trip!(
-            coer,
-            Integer,
-            Integer::from(i128::MIN) - 1,
+        round_trip_encoding::<Integer>(
+            Codec::Coer,
+            &(Integer::from(i128::MIN) - 1),
             &[
                 0x11u8, 0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-                0xff, 0xff, 0xff, 0xff, 0xff
-            ]
+                0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
         );
     }
     #[test]
@@ -204,35 +210,32 @@ mod tests {
         type E = ConstrainedInteger<0, { i128::MAX }>;
         type F = ConstrainedInteger<2, { u16::MAX as i128 }>;
 
-        round_trip!(coer, A, A::new(0), &[0x00]);
-        round_trip!(coer, A, A::new(5), &[0x05]);
-        round_trip!(coer, A, A::new(255), &[0xff]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(0), &[0x00]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(5), &[0x05]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(255), &[0xff]);
         // Paddings are expected
-        round_trip!(coer, B, B::new(0), &[0x00, 0x00]);
-        round_trip!(coer, B, B::new(255), &[0x00, 0xff]);
-        round_trip!(coer, C, C::new(0), &[0x00, 0x00, 0x00, 0x00]);
-        round_trip!(coer, C, C::new(u16::MAX), &[0x00, 0x00, 0xff, 0xff]);
-        round_trip!(
-            coer,
-            D,
-            0.try_into().unwrap(),
-            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            D,
-            i128::from(u32::MAX).try_into().unwrap(),
-            &[0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff]
+        round_trip_encoding::<B>(Codec::Coer, &B::new(0), &[0x00, 0x00]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(255), &[0x00, 0xff]);
+        round_trip_encoding::<C>(Codec::Coer, &C::new(0), &[0x00, 0x00, 0x00, 0x00]);
+        round_trip_encoding::<C>(Codec::Coer, &C::new(u16::MAX), &[0x00, 0x00, 0xff, 0xff]);
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &0.try_into().unwrap(),
+            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &i128::from(u32::MAX).try_into().unwrap(),
+            &[0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff],
         );
         // Use length determinant when upper range above u64 max
-        round_trip!(
-            coer,
-            E,
-            (i128::from(u64::MAX) + 1).try_into().unwrap(),
-            &[0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        round_trip_encoding::<E>(
+            Codec::Coer,
+            &(i128::from(u64::MAX) + 1).try_into().unwrap(),
+            &[0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
         );
-        round_trip!(coer, E, E::new(0), &[0x01, 0x00]);
-        round_trip!(coer, F, F::new(2), &[0x00, 0x02]);
+        round_trip_encoding::<E>(Codec::Coer, &E::new(0), &[0x01, 0x00]);
+        round_trip_encoding::<F>(Codec::Coer, &F::new(2), &[0x00, 0x02]);
         // Error expected, outside of range constraints
         encode_error!(coer, A, A::new(-1));
         encode_error!(coer, B, B::new(-1));
@@ -244,6 +247,17 @@ mod tests {
         encode_error!(coer, B, B::new(u32::from(u16::MAX) + 1));
         encode_error!(coer, C, C::new(u64::from(u32::MAX) + 1));
         encode_error!(coer, D, D::new(u128::from(u64::MAX) + 1));
+
+        type G = ConstrainedInteger<0, { u16::MAX as i128 }, u16>;
+        type H = ConstrainedInteger<0, { u64::MAX as i128 }, u64>;
+
+        round_trip_encoding::<G>(Codec::Coer, &G::try_from(255).unwrap(), &[0x00, 0xff]);
+        round_trip_encoding::<H>(
+            Codec::Coer,
+            &H::try_from(u64::MAX).unwrap(),
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        );
+        assert!(G::try_from(-1).is_err());
     }
     #[test]
     fn test_integer_with_signed_constraints() {
@@ -253,63 +267,65 @@ mod tests {
         type D = ConstrainedInteger<{ i64::MIN as i128 }, { i64::MAX as i128 }>;
         type E = ConstrainedInteger<-5, 5>;
 
-        round_trip!(coer, A, A::new(0), &[0x00]);
-        round_trip!(coer, A, A::new(-1), &[0xff]);
-        round_trip!(coer, A, A::new(i8::MIN), &[0x80]);
-        round_trip!(coer, A, A::new(i8::MAX), &[0x7f]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(0), &[0x00]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(-1), &[0xff]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(i8::MIN), &[0x80]);
+        round_trip_encoding::<A>(Codec::Coer, &A::new(i8::MAX), &[0x7f]);
         // Paddings (0xff as 2's complement) are sometimes expected
-        round_trip!(coer, B, B::new(0), &[0x00, 0x00]);
-        round_trip!(coer, B, B::new(-1), &[0xff, 0xff]);
-        round_trip!(coer, B, B::new(i8::MIN), &[0xff, 0x80]);
-        round_trip!(coer, B, B::new(i8::MAX), &[0x00, 0x7f]);
-        round_trip!(coer, B, B::new(i16::MIN), &[0x80, 0x00]);
-        round_trip!(coer, B, B::new(i16::MAX), &[0x7f, 0xff]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(0), &[0x00, 0x00]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(-1), &[0xff, 0xff]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(i8::MIN), &[0xff, 0x80]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(i8::MAX), &[0x00, 0x7f]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(i16::MIN), &[0x80, 0x00]);
+        round_trip_encoding::<B>(Codec::Coer, &B::new(i16::MAX), &[0x7f, 0xff]);
 
-        round_trip!(coer, C, 0.try_into().unwrap(), &[0x00, 0x00, 0x00, 0x00]);
-        round_trip!(coer, C, (-1).try_into().unwrap(), &[0xff, 0xff, 0xff, 0xff]);
-        round_trip!(coer, C, C::new(i16::MIN), &[0xff, 0xff, 0x80, 0x00]);
-        round_trip!(coer, C, C::new(i16::MAX), &[0x00, 0x00, 0x7f, 0xff]);
-        round_trip!(coer, C, C::new(i32::MIN), &[0x80, 0x00, 0x00, 0x00]);
-        round_trip!(coer, C, C::new(i32::MAX), &[0x7f, 0xff, 0xff, 0xff]);
+        round_trip_encoding::<C>(
+            Codec::Coer,
+            &0.try_into().unwrap(),
+            &[0x00, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<C>(
+            Codec::Coer,
+            &(-1).try_into().unwrap(),
+            &[0xff, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<C>(Codec::Coer, &C::new(i16::MIN), &[0xff, 0xff, 0x80, 0x00]);
+        round_trip_encoding::<C>(Codec::Coer, &C::new(i16::MAX), &[0x00, 0x00, 0x7f, 0xff]);
+        round_trip_encoding::<C>(Codec::Coer, &C::new(i32::MIN), &[0x80, 0x00, 0x00, 0x00]);
+        round_trip_encoding::<C>(Codec::Coer, &C::new(i32::MAX), &[0x7f, 0xff, 0xff, 0xff]);
 
-        round_trip!(
-            coer,
-            D,
-            D::new(0),
-            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            D,
-            D::new(-1),
-            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
-        );
-        round_trip!(
-            coer,
-            D,
-            D::new(i32::MIN),
-            &[0xff, 0xff, 0xff, 0xff, 0x80, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            D,
-            D::new(i32::MAX),
-            &[0x00, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff]
-        );
-        round_trip!(
-            coer,
-            D,
-            D::new(i64::MIN),
-            &[0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
-        );
-        round_trip!(
-            coer,
-            D,
-            D::new(i64::MAX),
-            &[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
-        );
-        round_trip!(coer, E, E::new(4), &[0x04]);
-        round_trip!(coer, E, E::new(-4), &[0xfc]);
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &D::new(0),
+            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &D::new(-1),
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &D::new(i32::MIN),
+            &[0xff, 0xff, 0xff, 0xff, 0x80, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &D::new(i32::MAX),
+            &[0x00, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &D::new(i64::MIN),
+            &[0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        );
+        round_trip_encoding::<D>(
+            Codec::Coer,
+            &D::new(i64::MAX),
+            &[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        );
+        round_trip_encoding::<E>(Codec::Coer, &E::new(4), &[0x04]);
+        round_trip_encoding::<E>(Codec::Coer, &E::new(-4), &[0xfc]);
 
         // Error expected, outside of range constraints
         encode_error!(coer, A, A::new(i16::from(i8::MIN) - 1));
@@ -325,7 +341,12 @@ mod tests {
     #[test]
     fn test_integer_single_constraint() {
         const CONSTRAINTS: Constraints = constraints!(value_constraint!(5));
-        round_trip_with_constraints!(coer, Integer, CONSTRAINTS, 5.into(), &[0x05]);
+        round_trip_encoding_with_constraints::<Integer>(
+            Codec::Coer,
+            CONSTRAINTS,
+            &5.into(),
+            &[0x05],
+        );
     }
     #[test]
     fn invalid_integer() {
@@ -361,9 +382,9 @@ mod tests {
             Red,
             Blue,
         }
-        round_trip!(coer, Enum1, Enum1::Green, &[0x00]);
-        round_trip!(coer, Enum1, Enum1::Red, &[0x01]);
-        round_trip!(coer, Enum1, Enum1::Blue, &[0x02]);
+        round_trip_encoding::<Enum1>(Codec::Coer, &Enum1::Green, &[0x00]);
+        round_trip_encoding::<Enum1>(Codec::Coer, &Enum1::Red, &[0x01]);
+        round_trip_encoding::<Enum1>(Codec::Coer, &Enum1::Blue, &[0x02]);
         // TODO, check correctness https://github.com/XAMPPRocky/rasn/discussions/124#discussioncomment-6724973
         #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
         #[rasn(enumerated, crate_root = "crate")]
@@ -376,9 +397,9 @@ mod tests {
             Yellow,
             Purple,
         }
-        round_trip!(coer, Enum2, Enum2::Red, &[0x00]);
-        round_trip!(coer, Enum2, Enum2::Yellow, &[0x03]);
-        round_trip!(coer, Enum2, Enum2::Purple, &[0x04]);
+        round_trip_encoding::<Enum2>(Codec::Coer, &Enum2::Red, &[0x00]);
+        round_trip_encoding::<Enum2>(Codec::Coer, &Enum2::Yellow, &[0x03]);
+        round_trip_encoding::<Enum2>(Codec::Coer, &Enum2::Purple, &[0x04]);
         #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
         #[rasn(enumerated, crate_root = "crate")]
         #[allow(clippy::items_after_statements)]
@@ -387,9 +408,9 @@ mod tests {
             Blue = 6,
             Green = 7,
         }
-        round_trip!(coer, Enum3, Enum3::Red, &[0x05]);
-        round_trip!(coer, Enum3, Enum3::Blue, &[0x06]);
-        round_trip!(coer, Enum3, Enum3::Green, &[0x07]);
+        round_trip_encoding::<Enum3>(Codec::Coer, &Enum3::Red, &[0x05]);
+        round_trip_encoding::<Enum3>(Codec::Coer, &Enum3::Blue, &[0x06]);
+        round_trip_encoding::<Enum3>(Codec::Coer, &Enum3::Green, &[0x07]);
 
         #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
         #[rasn(enumerated, crate_root = "crate")]
@@ -398,18 +419,17 @@ mod tests {
             Yes = 1000,
             No = -1000,
         }
-        round_trip!(coer, Enum4, Enum4::Yes, &[0x82, 0x03, 0xe8]);
-        round_trip!(coer, Enum4, Enum4::No, &[0x82, 0xfc, 0x18]);
+        round_trip_encoding::<Enum4>(Codec::Coer, &Enum4::Yes, &[0x82, 0x03, 0xe8]);
+        round_trip_encoding::<Enum4>(Codec::Coer, &Enum4::No, &[0x82, 0xfc, 0x18]);
     }
     #[test]
     fn test_bit_string() {
-        round_trip!(
-            coer,
-            BitString,
-            BitString::from_slice(&[0x01]),
-            &[0x02, 0x00, 0x01]
+        round_trip_encoding::<BitString>(
+            Codec::Coer,
+            &BitString::from_slice(&[0x01]),
+            &[0x02, 0x00, 0x01],
         );
-        round_trip!(coer, BitString, BitString::from_slice(&[]), &[0x01, 0x00]);
+        round_trip_encoding::<BitString>(Codec::Coer, &BitString::from_slice(&[]), &[0x01, 0x00]);
         let mut bv = bitvec![u8, Msb0;];
         bv.extend_from_raw_slice(&[0xff]);
         bv.push(false);
@@ -417,18 +437,16 @@ mod tests {
         bv.extend([false; 4].iter());
         // bv should be 14 bits now
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(14));
-        round_trip_with_constraints!(
-            coer,
-            BitString,
+        round_trip_encoding_with_constraints::<BitString>(
+            Codec::Coer,
             CONSTRAINT_1,
-            BitString::from_bitslice(&bv),
-            &[0b1111_1111, 0b0100_0000]
+            &BitString::from_bitslice(&bv),
+            &[0b1111_1111, 0b0100_0000],
         );
-        round_trip!(
-            coer,
-            BitString,
-            BitString::from_bitslice(&bv),
-            &[0x03u8, 0x02, 0b1111_1111, 0b0100_0000]
+        round_trip_encoding::<BitString>(
+            Codec::Coer,
+            &BitString::from_bitslice(&bv),
+            &[0x03u8, 0x02, 0b1111_1111, 0b0100_0000],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(15));
         encode_error_with_constraints!(
@@ -440,27 +458,24 @@ mod tests {
     }
     #[test]
     fn test_octet_string() {
-        round_trip!(
-            coer,
-            OctetString,
-            OctetString::from_static(&[0x01]),
-            &[0x01, 0x01]
+        round_trip_encoding::<OctetString>(
+            Codec::Coer,
+            &OctetString::from_static(&[0x01]),
+            &[0x01, 0x01],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(5));
-        round_trip_with_constraints!(
-            coer,
-            OctetString,
+        round_trip_encoding_with_constraints::<OctetString>(
+            Codec::Coer,
             CONSTRAINT_1,
-            OctetString::from_static(&[0x01u8, 0x02, 0x03, 0x04, 0x05]),
-            &[0x01u8, 0x02, 0x03, 0x04, 0x05]
+            &OctetString::from_static(&[0x01u8, 0x02, 0x03, 0x04, 0x05]),
+            &[0x01u8, 0x02, 0x03, 0x04, 0x05],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(3, 6));
-        round_trip_with_constraints!(
-            coer,
-            OctetString,
+        round_trip_encoding_with_constraints::<OctetString>(
+            Codec::Coer,
             CONSTRAINT_2,
-            OctetString::from_static(&[0x01u8, 0x02, 0x03, 0x04, 0x05]),
-            &[0x05u8, 0x01, 0x02, 0x03, 0x04, 0x05]
+            &OctetString::from_static(&[0x01u8, 0x02, 0x03, 0x04, 0x05]),
+            &[0x05u8, 0x01, 0x02, 0x03, 0x04, 0x05],
         );
         const CONSTRAINT_3: Constraints = constraints!(size_constraint!(5));
         encode_error_with_constraints!(
@@ -488,17 +503,15 @@ mod tests {
     fn test_object_identifier() {
         // ('A',                   '1.2', b'\x01\x2a'),
         // ('A',              '1.2.3321', b'\x03\x2a\x99\x79')
-        round_trip!(
-            coer,
-            ObjectIdentifier,
-            ObjectIdentifier::new(vec![1u32, 2]).unwrap(),
-            &[0x01u8, 0x2a]
+        round_trip_encoding::<ObjectIdentifier>(
+            Codec::Coer,
+            &ObjectIdentifier::new(vec![1u32, 2]).unwrap(),
+            &[0x01u8, 0x2a],
         );
-        round_trip!(
-            coer,
-            ObjectIdentifier,
-            ObjectIdentifier::new(vec![1, 2, 3321]).unwrap(),
-            &[0x03u8, 0x2a, 0x99, 0x79]
+        round_trip_encoding::<ObjectIdentifier>(
+            Codec::Coer,
+            &ObjectIdentifier::new(vec![1, 2, 3321]).unwrap(),
+            &[0x03u8, 0x2a, 0x99, 0x79],
         );
     }
     #[test]
@@ -514,23 +527,20 @@ mod tests {
             #[rasn(extension_addition)]
             Medium(Integer),
         }
-        round_trip!(
-            coer,
-            Choice,
-            Choice::Normal(333.into()),
-            &[0x80, 0x02, 0x01, 0x4d]
+        round_trip_encoding::<Choice>(
+            Codec::Coer,
+            &Choice::Normal(333.into()),
+            &[0x80, 0x02, 0x01, 0x4d],
         );
-        round_trip!(
-            coer,
-            Choice,
-            Choice::High(333.into()),
-            &[0x81, 0x02, 0x01, 0x4d]
+        round_trip_encoding::<Choice>(
+            Codec::Coer,
+            &Choice::High(333.into()),
+            &[0x81, 0x02, 0x01, 0x4d],
         );
-        round_trip!(
-            coer,
-            Choice,
-            Choice::Medium(333.into()),
-            &[0x82, 0x03, 0x02, 0x01, 0x4d]
+        round_trip_encoding::<Choice>(
+            Codec::Coer,
+            &Choice::Medium(333.into()),
+            &[0x82, 0x03, 0x02, 0x01, 0x4d],
         );
 
         #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
@@ -543,13 +553,12 @@ mod tests {
             B(bool),
             C(Choice),
         }
-        round_trip!(coer, BoolChoice, BoolChoice::A(true), &[0x80, 0xff]);
-        round_trip!(coer, BoolChoice, BoolChoice::B(true), &[0x81, 0x01, 0xff]);
-        round_trip!(
-            coer,
-            BoolChoice,
-            BoolChoice::C(Choice::Normal(333.into())),
-            &[0x82, 0x80, 0x02, 0x01, 0x4d]
+        round_trip_encoding::<BoolChoice>(Codec::Coer, &BoolChoice::A(true), &[0x80, 0xff]);
+        round_trip_encoding::<BoolChoice>(Codec::Coer, &BoolChoice::B(true), &[0x81, 0x01, 0xff]);
+        round_trip_encoding::<BoolChoice>(
+            Codec::Coer,
+            &BoolChoice::C(Choice::Normal(333.into())),
+            &[0x82, 0x80, 0x02, 0x01, 0x4d],
         );
         #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
         #[rasn(choice, automatic_tags)]
@@ -558,12 +567,11 @@ mod tests {
             A(bool),
             B(BoolChoice),
         }
-        round_trip!(coer, TripleChoice, TripleChoice::A(true), &[0x80, 0xff]);
-        round_trip!(
-            coer,
-            TripleChoice,
-            TripleChoice::B(BoolChoice::C(Choice::Normal(333.into()))),
-            &[0x81, 0x82, 0x80, 0x02, 0x01, 0x4d]
+        round_trip_encoding::<TripleChoice>(Codec::Coer, &TripleChoice::A(true), &[0x80, 0xff]);
+        round_trip_encoding::<TripleChoice>(
+            Codec::Coer,
+            &TripleChoice::B(BoolChoice::C(Choice::Normal(333.into()))),
+            &[0x81, 0x82, 0x80, 0x02, 0x01, 0x4d],
         );
         #[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
         #[rasn(crate_root = "crate")]
@@ -597,7 +605,7 @@ mod tests {
                 choice: TestChoice::Number1(()),
             })),
         );
-        round_trip!(coer, TopLevel, test_value, &[1, 130, 2, 128]);
+        round_trip_encoding::<TopLevel>(Codec::Coer, &test_value, &[1, 130, 2, 128]);
     }
     #[test]
     fn test_tag_max_size() {
@@ -613,119 +621,105 @@ mod tests {
             #[rasn(tag(4_294_967_295u32))]
             Medium(Integer),
         }
-        round_trip!(
-            coer,
-            Choice,
-            Choice::Medium(333.into()),
-            &[191, 143, 255, 255, 255, 127, 2, 1, 77]
+        round_trip_encoding::<Choice>(
+            Codec::Coer,
+            &Choice::Medium(333.into()),
+            &[191, 143, 255, 255, 255, 127, 2, 1, 77],
         );
     }
     #[test]
     fn test_numeric_string() {
-        round_trip!(
-            coer,
-            NumericString,
-            "123".try_into().unwrap(),
-            &[0x03, 0x31, 0x32, 0x33]
+        round_trip_encoding::<NumericString>(
+            Codec::Coer,
+            &"123".try_into().unwrap(),
+            &[0x03, 0x31, 0x32, 0x33],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(3));
-        round_trip_with_constraints!(
-            coer,
-            NumericString,
+        round_trip_encoding_with_constraints::<NumericString>(
+            Codec::Coer,
             CONSTRAINT_1,
-            "123".try_into().unwrap(),
-            &[0x31, 0x32, 0x33]
+            &"123".try_into().unwrap(),
+            &[0x31, 0x32, 0x33],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(3, 7));
-        round_trip_with_constraints!(
-            coer,
-            NumericString,
+        round_trip_encoding_with_constraints::<NumericString>(
+            Codec::Coer,
             CONSTRAINT_2,
-            "123".try_into().unwrap(),
-            &[0x03, 0x31, 0x32, 0x33]
+            &"123".try_into().unwrap(),
+            &[0x03, 0x31, 0x32, 0x33],
         );
     }
     #[test]
     fn test_printable_string() {
-        round_trip!(
-            coer,
-            PrintableString,
-            "foo".try_into().unwrap(),
-            &[0x03, 0x66, 0x6f, 0x6f]
-        );
-        round_trip!(
-            coer,
-            PrintableString,
-            " '()+,-./:=?".try_into().unwrap(),
+        round_trip_encoding::<PrintableString>(
+            Codec::Coer,
+            &"foo".try_into().unwrap(),
+            &[0x03, 0x66, 0x6f, 0x6f],
+        );
+        round_trip_encoding::<PrintableString>(
+            Codec::Coer,
+            &" '()+,-./:=?".try_into().unwrap(),
             &[
-                0x0c, 0x20, 0x27, 0x28, 0x29, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f, 0x3a, 0x3d, 0x3f
-            ]
+                0x0c, 0x20, 0x27, 0x28, 0x29, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f, 0x3a, 0x3d, 0x3f,
+            ],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(3));
-        round_trip_with_constraints!(
-            coer,
-            PrintableString,
+        round_trip_encoding_with_constraints::<PrintableString>(
+            Codec::Coer,
             CONSTRAINT_1,
-            "foo".try_into().unwrap(),
-            &[0x66, 0x6f, 0x6f]
+            &"foo".try_into().unwrap(),
+            &[0x66, 0x6f, 0x6f],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(3, 7));
-        round_trip_with_constraints!(
-            coer,
-            PrintableString,
+        round_trip_encoding_with_constraints::<PrintableString>(
+            Codec::Coer,
             CONSTRAINT_2,
-            "foo".try_into().unwrap(),
-            &[0x03, 0x66, 0x6f, 0x6f]
+            &"foo".try_into().unwrap(),
+            &[0x03, 0x66, 0x6f, 0x6f],
         );
     }
     #[test]
     fn test_visible_string() {
-        round_trip!(
-            coer,
-            VisibleString,
-            "foo".try_into().unwrap(),
-            &[0x03, 0x66, 0x6f, 0x6f]
+        round_trip_encoding::<VisibleString>(
+            Codec::Coer,
+            &"foo".try_into().unwrap(),
+            &[0x03, 0x66, 0x6f, 0x6f],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(3));
-        round_trip_with_constraints!(
-            coer,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Coer,
             CONSTRAINT_1,
-            "foo".try_into().unwrap(),
-            &[0x66, 0x6f, 0x6f]
+            &"foo".try_into().unwrap(),
+            &[0x66, 0x6f, 0x6f],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(3, 7));
-        round_trip_with_constraints!(
-            coer,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Coer,
             CONSTRAINT_2,
-            "foo".try_into().unwrap(),
-            &[0x03, 0x66, 0x6f, 0x6f]
+            &"foo".try_into().unwrap(),
+            &[0x03, 0x66, 0x6f, 0x6f],
         );
     }
     #[test]
     fn test_ia5_string() {
-        round_trip!(
-            coer,
-            Ia5String,
-            "foo".try_into().unwrap(),
-            &[0x03, 0x66, 0x6f, 0x6f]
+        round_trip_encoding::<Ia5String>(
+            Codec::Coer,
+            &"foo".try_into().unwrap(),
+            &[0x03, 0x66, 0x6f, 0x6f],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(3));
-        round_trip_with_constraints!(
-            coer,
-            Ia5String,
+        round_trip_encoding_with_constraints::<Ia5String>(
+            Codec::Coer,
             CONSTRAINT_1,
-            "foo".try_into().unwrap(),
-            &[0x66, 0x6f, 0x6f]
+            &"foo".try_into().unwrap(),
+            &[0x66, 0x6f, 0x6f],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(3, 7));
-        round_trip_with_constraints!(
-            coer,
-            Ia5String,
+        round_trip_encoding_with_constraints::<Ia5String>(
+            Codec::Coer,
             CONSTRAINT_2,
-            "foo".try_into().unwrap(),
-            &[0x03, 0x66, 0x6f, 0x6f]
+            &"foo".try_into().unwrap(),
+            &[0x03, 0x66, 0x6f, 0x6f],
         );
     }
     // https://github.com/librasn/rasn/issues/260
@@ -736,62 +730,81 @@ mod tests {
     }
     #[test]
     fn test_general_string() {
-        round_trip!(
-            coer,
-            GeneralString,
-            GeneralString::from_bytes("".as_bytes()).unwrap(),
-            &[0x00]
+        round_trip_encoding::<GeneralString>(
+            Codec::Coer,
+            &GeneralString::from_bytes("".as_bytes()).unwrap(),
+            &[0x00],
         );
-        round_trip!(
-            coer,
-            GeneralString,
-            GeneralString::from_bytes("2".as_bytes()).unwrap(),
-            &[0x01, 0x32]
+        round_trip_encoding::<GeneralString>(
+            Codec::Coer,
+            &GeneralString::from_bytes("2".as_bytes()).unwrap(),
+            &[0x01, 0x32],
         );
     }
     #[test]
     fn test_utf8_string() {
-        round_trip!(coer, Utf8String, String::new(), &[0x00]);
-        round_trip!(coer, Utf8String, "2".into(), &[0x01, 0x32]);
-        round_trip!(
-            coer,
-            Utf8String,
-            "2".repeat(128),
+        use crate::types::constraints::{CharacterRange, PermittedAlphabet};
+
+        round_trip_encoding::<Utf8String>(Codec::Coer, &String::new(), &[0x00]);
+        round_trip_encoding::<Utf8String>(Codec::Coer, &"2".into(), &[0x01, 0x32]);
+        round_trip_encoding::<Utf8String>(
+            Codec::Coer,
+            &"2".repeat(128),
             &[0x81, 0x80]
                 .iter()
                 .chain("2".repeat(128).as_bytes().iter())
                 .copied()
-                .collect::<Vec<_>>()
+                .collect::<Vec<_>>(),
         );
-        round_trip!(
-            coer,
-            Utf8String,
-            "ÄÖÄÖÄÖÄÖ12e4Ä".into(),
+        round_trip_encoding::<Utf8String>(
+            Codec::Coer,
+            &"ÄÖÄÖÄÖÄÖ12e4Ä".into(),
             &[
                 0x16, 0xc3, 0x84, 0xc3, 0x96, 0xc3, 0x84, 0xc3, 0x96, 0xc3, 0x84, 0xc3, 0x96, 0xc3,
-                0x84, 0xc3, 0x96, 0x31, 0x32, 0x65, 0x34, 0xc3, 0x84
-            ]
+                0x84, 0xc3, 0x96, 0x31, 0x32, 0x65, 0x34, 0xc3, 0x84,
+            ],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(3));
-        round_trip_with_constraints!(
-            coer,
-            Utf8String,
+        round_trip_encoding_with_constraints::<Utf8String>(
+            Codec::Coer,
             CONSTRAINT_1,
-            "foo".into(),
-            &[0x66, 0x6f, 0x6f]
+            &"foo".into(),
+            &[0x03, 0x66, 0x6f, 0x6f],
         );
+        // The size is counted in characters and isn't OER-visible.
+        round_trip_encoding_with_constraints::<Utf8String>(
+            Codec::Coer,
+            CONSTRAINT_1,
+            &"ÄÖÄ".into(),
+            &[0x06, 0xc3, 0x84, 0xc3, 0x96, 0xc3, 0x84],
+        );
+        encode_error_with_constraints!(coer, Utf8String, CONSTRAINT_1, "fooo".into());
+        const CONSTRAINT_2: Constraints = constraints!(
+            size_constraint!(1, 8),
+            Constraint::PermittedAlphabet(Extensible::new(PermittedAlphabet::from_ranges(
+                &CharacterRange::indexed([CharacterRange::new('a' as u32, 'z' as u32)])
+            )))
+        );
+        round_trip_encoding_with_constraints::<Utf8String>(
+            Codec::Coer,
+            CONSTRAINT_2,
+            &"foo".into(),
+            &[0x03, 0x66, 0x6f, 0x6f],
+        );
+        encode_error_with_constraints!(coer, Utf8String, CONSTRAINT_2, "Foo".into());
+        encode_error_with_constraints!(coer, Utf8String, CONSTRAINT_2, String::new());
     }
     #[test]
     fn test_teletext_string() {
         // For now, Teletex string needs to be aligned for 4 bytes
-        round_trip!(
-            coer,
-            TeletexString,
-            TeletexString::from_bytes("1234".as_bytes()).unwrap(),
-            &[0x04, 0x31, 0x32, 0x33, 0x34]
+        round_trip_encoding::<TeletexString>(
+            Codec::Coer,
+            &TeletexString::from_bytes("1234".as_bytes()).unwrap(),
+            &[0x04, 0x31, 0x32, 0x33, 0x34],
         );
     }
     #[test]
+    #[cfg(feature = "chrono")]
     fn test_generalized_time() {
         use chrono::NaiveDate;
         let offset = chrono::FixedOffset::east_opt(0).unwrap();
@@ -800,14 +813,13 @@ mod tests {
             .and_hms_micro_opt(13, 0, 5, 342_000)
             .unwrap()
             .and_local_timezone(offset);
-        round_trip!(
-            coer,
-            GeneralizedTime,
-            GeneralizedTime::from(dt.unwrap(),),
+        round_trip_encoding::<GeneralizedTime>(
+            Codec::Coer,
+            &GeneralizedTime::from(dt.unwrap()),
             &[
                 0x13, 0x32, 0x30, 0x38, 0x30, 0x31, 0x30, 0x30, 0x39, 0x31, 0x33, 0x30, 0x30, 0x30,
-                0x35, 0x2e, 0x33, 0x34, 0x32, 0x5a
-            ]
+                0x35, 0x2e, 0x33, 0x34, 0x32, 0x5a,
+            ],
         );
 
         let data = [
@@ -817,22 +829,22 @@ mod tests {
         assert!(crate::der::decode::<crate::types::Open>(&data).is_err());
     }
     #[test]
+    #[cfg(feature = "chrono")]
     fn test_utc_time() {
         // 2019-10-09 13:00:05 UTC
         // 191009130005Z
-        round_trip!(
-            coer,
-            UtcTime,
-            UtcTime::from(
+        round_trip_encoding::<UtcTime>(
+            Codec::Coer,
+            &UtcTime::from(
                 chrono::NaiveDate::from_ymd_opt(2019, 10, 9)
                     .unwrap()
                     .and_hms_opt(13, 0, 5)
                     .unwrap()
-                    .and_utc()
+                    .and_utc(),
             ),
             &[
-                0x0d, 0x31, 0x39, 0x31, 0x30, 0x30, 0x39, 0x31, 0x33, 0x30, 0x30, 0x30, 0x35, 0x5a
-            ]
+                0x0d, 0x31, 0x39, 0x31, 0x30, 0x30, 0x39, 0x31, 0x33, 0x30, 0x30, 0x30, 0x35, 0x5a,
+            ],
         );
     }
     #[test]
@@ -846,14 +858,13 @@ mod tests {
             a: Integer,
             b: Integer,
         }
-        round_trip!(
-            coer,
-            Sequence1,
-            Sequence1 {
+        round_trip_encoding::<Sequence1>(
+            Codec::Coer,
+            &(Sequence1 {
                 a: 1.into(),
-                b: 2.into()
-            },
-            &[0x01, 0x01, 0x01, 0x02]
+                b: 2.into(),
+            }),
+            &[0x01, 0x01, 0x01, 0x02],
         );
 
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
@@ -861,7 +872,7 @@ mod tests {
         struct Sequence2 {
             a: bool,
         }
-        round_trip!(coer, Sequence2, Sequence2 { a: true }, &[0xff]);
+        round_trip_encoding::<Sequence2>(Codec::Coer, &(Sequence2 { a: true }), &[0xff]);
 
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -869,17 +880,16 @@ mod tests {
             a: bool,
             b: Sequence1,
         }
-        round_trip!(
-            coer,
-            Sequence3,
-            Sequence3 {
+        round_trip_encoding::<Sequence3>(
+            Codec::Coer,
+            &(Sequence3 {
                 a: true,
                 b: Sequence1 {
                     a: 1.into(),
-                    b: 2.into()
-                }
-            },
-            &[0xff, 0x01, 0x01, 0x01, 0x02]
+                    b: 2.into(),
+                },
+            }),
+            &[0xff, 0x01, 0x01, 0x01, 0x02],
         );
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(crate_root = "crate", choice, automatic_tags)]
@@ -899,20 +909,19 @@ mod tests {
             a: bool,
             b: Sequence4,
         }
-        round_trip!(
-            coer,
-            Sequence5,
-            Sequence5 {
+        round_trip_encoding::<Sequence5>(
+            Codec::Coer,
+            &(Sequence5 {
                 a: true,
                 b: Sequence4 {
                     a: 1.into(),
                     b: Choice1::B(Sequence1 {
                         a: 1.into(),
-                        b: 2.into()
-                    })
-                }
-            },
-            &[0xff, 0x01, 0x01, 0x81, 0x01, 0x01, 0x01, 0x02]
+                        b: 2.into(),
+                    }),
+                },
+            }),
+            &[0xff, 0x01, 0x01, 0x81, 0x01, 0x01, 0x01, 0x02],
         );
     }
     #[test]
@@ -926,12 +935,11 @@ mod tests {
             #[rasn(default = "default_a")]
             a: Integer,
         }
-        round_trip!(coer, Sequence1, Sequence1 { a: 0.into() }, &[0x00]);
-        round_trip!(
-            coer,
-            Sequence1,
-            Sequence1 { a: 1.into() },
-            &[0b1000_0000, 0x01, 0x01]
+        round_trip_encoding::<Sequence1>(Codec::Coer, &(Sequence1 { a: 0.into() }), &[0x00]);
+        round_trip_encoding::<Sequence1>(
+            Codec::Coer,
+            &(Sequence1 { a: 1.into() }),
+            &[0b1000_0000, 0x01, 0x01],
         );
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -939,23 +947,21 @@ mod tests {
             a: Integer,
             b: Option<Integer>,
         }
-        round_trip!(
-            coer,
-            Sequence2,
-            Sequence2 {
+        round_trip_encoding::<Sequence2>(
+            Codec::Coer,
+            &(Sequence2 {
                 a: 1.into(),
-                b: Some(2.into())
-            },
-            &[0b1000_0000, 0x01, 0x01, 0x01, 0x02]
+                b: Some(2.into()),
+            }),
+            &[0b1000_0000, 0x01, 0x01, 0x01, 0x02],
         );
-        round_trip!(
-            coer,
-            Sequence2,
-            Sequence2 {
+        round_trip_encoding::<Sequence2>(
+            Codec::Coer,
+            &(Sequence2 {
                 a: 1.into(),
-                b: None
-            },
-            &[0x00, 0x01, 0x01]
+                b: None,
+            }),
+            &[0x00, 0x01, 0x01],
         );
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -964,23 +970,21 @@ mod tests {
             a: Integer, // default is 0
             b: Option<Integer>,
         }
-        round_trip!(
-            coer,
-            Sequence4,
-            Sequence4 {
+        round_trip_encoding::<Sequence4>(
+            Codec::Coer,
+            &(Sequence4 {
                 a: 0.into(),
-                b: None
-            },
-            &[0x00]
+                b: None,
+            }),
+            &[0x00],
         );
-        round_trip!(
-            coer,
-            Sequence4,
-            Sequence4 {
+        round_trip_encoding::<Sequence4>(
+            Codec::Coer,
+            &(Sequence4 {
                 a: 1.into(),
-                b: Some(3.into())
-            },
-            &[0b1100_0000, 0x01, 0x01, 0x01, 0x03]
+                b: Some(3.into()),
+            }),
+            &[0b1100_0000, 0x01, 0x01, 0x01, 0x03],
         );
     }
     #[test]
@@ -992,7 +996,7 @@ mod tests {
         struct Sequence1 {
             a: bool,
         }
-        round_trip!(coer, Sequence1, Sequence1 { a: true }, &[0x00, 0xff]);
+        round_trip_encoding::<Sequence1>(Codec::Coer, &(Sequence1 { a: true }), &[0x00, 0xff]);
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
         #[non_exhaustive]
@@ -1003,15 +1007,14 @@ mod tests {
             #[rasn(extension_addition)]
             c: Option<bool>,
         }
-        round_trip!(
-            coer,
-            Sequence2,
-            Sequence2 {
+        round_trip_encoding::<Sequence2>(
+            Codec::Coer,
+            &(Sequence2 {
                 a: true,
                 b: Some(true),
-                c: Some(true)
-            },
-            &[0x80, 0xff, 0x02, 0x06, 0xc0, 0x01, 0xff, 0x01, 0xff]
+                c: Some(true),
+            }),
+            &[0x80, 0xff, 0x02, 0x06, 0xc0, 0x01, 0xff, 0x01, 0xff],
         );
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -1026,20 +1029,18 @@ mod tests {
         struct Sequence4 {
             a: bool,
         }
-        round_trip!(
-            coer,
-            Sequence3,
-            Sequence3 { a: true, b: None },
-            &[0x00, 0xff]
-        );
-        round_trip!(
-            coer,
-            Sequence3,
-            Sequence3 {
+        round_trip_encoding::<Sequence3>(
+            Codec::Coer,
+            &(Sequence3 { a: true, b: None }),
+            &[0x00, 0xff],
+        );
+        round_trip_encoding::<Sequence3>(
+            Codec::Coer,
+            &(Sequence3 {
                 a: true,
-                b: Some(Sequence4 { a: true })
-            },
-            &[0x80, 0xff, 0x02, 0x07, 0x80, 0x01, 0xff]
+                b: Some(Sequence4 { a: true }),
+            }),
+            &[0x80, 0xff, 0x02, 0x07, 0x80, 0x01, 0xff],
         );
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -1049,20 +1050,18 @@ mod tests {
             #[rasn(extension_addition)]
             b: Option<bool>,
         }
-        round_trip!(
-            coer,
-            Sequence5,
-            Sequence5 { a: true, b: None },
-            &[0x00, 0xff]
-        );
-        round_trip!(
-            coer,
-            Sequence5,
-            Sequence5 {
+        round_trip_encoding::<Sequence5>(
+            Codec::Coer,
+            &(Sequence5 { a: true, b: None }),
+            &[0x00, 0xff],
+        );
+        round_trip_encoding::<Sequence5>(
+            Codec::Coer,
+            &(Sequence5 {
                 a: true,
-                b: Some(true)
-            },
-            &[0x80, 0xff, 0x02, 0x07, 0x80, 0x01, 0xff]
+                b: Some(true),
+            }),
+            &[0x80, 0xff, 0x02, 0x07, 0x80, 0x01, 0xff],
         );
 
         #[derive(AsnType, Debug, Decode, Encode, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
@@ -1085,10 +1084,9 @@ mod tests {
             #[rasn(extension_addition)]
             pub octet5: Option<OctetString>,
         }
-        round_trip!(
-            coer,
-            ExtendedOptional,
-            ExtendedOptional {
+        round_trip_encoding::<ExtendedOptional>(
+            Codec::Coer,
+            &(ExtendedOptional {
                 value: 1.into(),
                 integer1: Some(1_230_066_625_199_609_624u64.into()),
                 octet1: None,
@@ -1099,14 +1097,13 @@ mod tests {
                 integer4: None,
                 octet4: None,
                 integer5: None,
-                octet5: None
-            },
-            &[68, 1, 1, 8, 17, 18, 19, 20, 21, 22, 23, 24, 1, 1]
+                octet5: None,
+            }),
+            &[68, 1, 1, 8, 17, 18, 19, 20, 21, 22, 23, 24, 1, 1],
         );
-        round_trip!(
-            coer,
-            ExtendedOptional,
-            ExtendedOptional {
+        round_trip_encoding::<ExtendedOptional>(
+            Codec::Coer,
+            &(ExtendedOptional {
                 value: 0.into(),
                 integer1: Some(1.into()),
                 octet1: None,
@@ -1117,8 +1114,8 @@ mod tests {
                 integer4: Some(7.into()),
                 octet4: None,
                 integer5: Some(8.into()),
-                octet5: None
-            },
+                octet5: None,
+            }),
             &[
                 0b1101_0110, // optional/default bitfield
                 0x01,
@@ -1141,8 +1138,8 @@ mod tests {
                 0x07,
                 0x02,
                 0x01,
-                0x08
-            ]
+                0x08,
+            ],
         );
         // Preamble that takes two bytes
         #[derive(AsnType, Debug, Decode, Encode, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
@@ -1162,10 +1159,9 @@ mod tests {
             pub integer8: Option<Integer>,
             pub integer9: Option<Integer>,
         }
-        round_trip!(
-            coer,
-            ManyOptional,
-            ManyOptional {
+        round_trip_encoding::<ManyOptional>(
+            Codec::Coer,
+            &(ManyOptional {
                 value: 1.into(),
                 integer1: Some(1_230_066_625_199_609_624u64.into()),
                 octet1: None,
@@ -1178,26 +1174,24 @@ mod tests {
                 integer6: Some(1.into()),
                 integer7: None,
                 integer8: None,
-                integer9: None
-            },
+                integer9: None,
+            }),
             &[
-                136, 128, 1, 1, 8, 17, 18, 19, 20, 21, 22, 23, 24, 1, 1, 1, 1
-            ]
+                136, 128, 1, 1, 8, 17, 18, 19, 20, 21, 22, 23, 24, 1, 1, 1, 1,
+            ],
         );
     }
     #[test]
     fn test_sequence_of() {
-        round_trip!(
-            coer,
-            SequenceOf::<Integer>,
-            SequenceOf::<Integer>::from(vec![]),
-            &[0x01, 0x00]
+        round_trip_encoding::<SequenceOf<Integer>>(
+            Codec::Coer,
+            &SequenceOf::<Integer>::from(vec![]),
+            &[0x01, 0x00],
         );
-        round_trip!(
-            coer,
-            SequenceOf::<Integer>,
-            SequenceOf::<Integer>::from(vec![1.into(), 2.into()]),
-            &[0x01, 0x02, 0x01, 0x01, 0x01, 0x02]
+        round_trip_encoding::<SequenceOf<Integer>>(
+            Codec::Coer,
+            &SequenceOf::<Integer>::from(vec![1.into(), 2.into()]),
+            &[0x01, 0x02, 0x01, 0x01, 0x01, 0x02],
         );
     }
     #[test]
@@ -1212,26 +1206,23 @@ mod tests {
             #[rasn(tag(application, 9))]
             c: Option<Integer>,
         }
-        round_trip!(
-            coer,
-            Foo,
-            Foo {
+        round_trip_encoding::<Foo>(
+            Codec::Coer,
+            &(Foo {
                 a: Some(5.into()),
                 b: Some(6.into()),
                 c: Some(7.into()),
-            },
-            &[0b1110_0000, 0x01, 0x07, 0x01, 0x06, 0x01, 0x05]
+            }),
+            &[0b1110_0000, 0x01, 0x07, 0x01, 0x06, 0x01, 0x05],
         );
-        round_trip!(
-            coer,
-            Foo,
-            Foo {
+        round_trip_encoding::<Foo>(
+            Codec::Coer,
+            &(Foo {
                 a: None,
                 b: None,
                 c: Some(1.into()),
-            },
-            // Also preamble is ordered by tag
-            &[0b1000_0000, 0x01, 0x01]
+            }), // Also preamble is ordered by tag
+            &[0b1000_0000, 0x01, 0x01],
         );
     }
     #[test]
@@ -1248,17 +1239,16 @@ mod tests {
             a: Integer,
             b: Option<Sequence1>,
         }
-        round_trip!(
-            coer,
-            Sequence2,
-            Sequence2 {
+        round_trip_encoding::<Sequence2>(
+            Codec::Coer,
+            &(Sequence2 {
                 a: 1.into(),
                 b: Some(Sequence1 {
                     a: 2.into(),
-                    b: Some(3.into())
-                })
-            },
-            &[0x80, 0x01, 0x01, 0x80, 0x01, 0x02, 0x01, 0x03]
+                    b: Some(3.into()),
+                }),
+            }),
+            &[0x80, 0x01, 0x01, 0x80, 0x01, 0x02, 0x01, 0x03],
         );
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -1266,20 +1256,19 @@ mod tests {
             a: Integer,
             b: Sequence2,
         }
-        round_trip!(
-            coer,
-            Sequence3,
-            Sequence3 {
+        round_trip_encoding::<Sequence3>(
+            Codec::Coer,
+            &(Sequence3 {
                 a: 1.into(),
                 b: Sequence2 {
                     a: 2.into(),
                     b: Some(Sequence1 {
                         a: 3.into(),
-                        b: Some(4.into())
-                    })
-                }
-            },
-            &[0x01, 0x01, 0x80, 0x01, 0x02, 0x80, 0x01, 0x03, 0x01, 0x04]
+                        b: Some(4.into()),
+                    }),
+                },
+            }),
+            &[0x01, 0x01, 0x80, 0x01, 0x02, 0x80, 0x01, 0x03, 0x01, 0x04],
         );
     }
     #[test]
@@ -1296,17 +1285,16 @@ mod tests {
             a: Integer,
             b: Box<Sequence1>,
         }
-        round_trip!(
-            coer,
-            Sequence2,
-            Sequence2 {
+        round_trip_encoding::<Sequence2>(
+            Codec::Coer,
+            &(Sequence2 {
                 a: 1.into(),
                 b: Box::new(Sequence1 {
                     a: 2.into(),
-                    b: Some(3.into())
-                })
-            },
-            &[0x01, 0x01, 0x80, 0x01, 0x02, 0x01, 0x03]
+                    b: Some(3.into()),
+                }),
+            }),
+            &[0x01, 0x01, 0x80, 0x01, 0x02, 0x01, 0x03],
         );
     }
     #[test]
@@ -1323,17 +1311,16 @@ mod tests {
             a: Option<Integer>,
             b: Choice1,
         }
-        round_trip!(
-            coer,
-            Sequence1,
-            Sequence1 {
+        round_trip_encoding::<Sequence1>(
+            Codec::Coer,
+            &(Sequence1 {
                 a: Some(1.into()),
                 b: Choice1::B(Box::new(Sequence1 {
                     a: Some(2.into()),
-                    b: Choice1::A(true)
-                }))
-            },
-            &[0x80, 0x01, 0x01, 0x81, 0x80, 0x01, 0x02, 0x80, 0xff]
+                    b: Choice1::A(true),
+                })),
+            }),
+            &[0x80, 0x01, 0x01, 0x81, 0x80, 0x01, 0x02, 0x80, 0xff],
         );
     }
     #[test]
@@ -1341,7 +1328,7 @@ mod tests {
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
         struct Sequence1 {}
-        round_trip!(coer, Sequence1, Sequence1 {}, &[]);
+        round_trip_encoding::<Sequence1>(Codec::Coer, &(Sequence1 {}), &[]);
 
         // Only optional fields, all empty
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
@@ -1350,7 +1337,7 @@ mod tests {
             a: Option<Integer>,
             b: Option<Integer>,
         }
-        round_trip!(coer, Sequence2, Sequence2 { a: None, b: None }, &[0x00]);
+        round_trip_encoding::<Sequence2>(Codec::Coer, &(Sequence2 { a: None, b: None }), &[0x00]);
         // Only default values
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
         #[rasn(automatic_tags)]
@@ -1366,14 +1353,13 @@ mod tests {
         fn default_b() -> Integer {
             1.into()
         }
-        round_trip!(
-            coer,
-            Sequence3,
-            Sequence3 {
+        round_trip_encoding::<Sequence3>(
+            Codec::Coer,
+            &(Sequence3 {
                 a: 0.into(),
-                b: 1.into()
-            },
-            &[0x00]
+                b: 1.into(),
+            }),
+            &[0x00],
         );
     }
     #[test]
@@ -1387,19 +1373,17 @@ mod tests {
         pub struct ConstrainedOptions {
             pub a: Option<HashedId3>,
         }
-        round_trip!(
-            coer,
-            ConstrainedOptions,
-            ConstrainedOptions { a: None },
-            &[0x00]
-        );
-        round_trip!(
-            coer,
-            ConstrainedOptions,
-            ConstrainedOptions {
-                a: Some(HashedId3(OctetString::from_static(&[0x01, 0x02, 0x03])))
-            },
-            &[0x80, 0x01, 0x02, 0x03]
+        round_trip_encoding::<ConstrainedOptions>(
+            Codec::Coer,
+            &(ConstrainedOptions { a: None }),
+            &[0x00],
+        );
+        round_trip_encoding::<ConstrainedOptions>(
+            Codec::Coer,
+            &(ConstrainedOptions {
+                a: Some(HashedId3(OctetString::from_static(&[0x01, 0x02, 0x03]))),
+            }),
+            &[0x80, 0x01, 0x02, 0x03],
         );
     }
     #[test]
@@ -1412,14 +1396,13 @@ mod tests {
             #[rasn(extension_addition)]
             pub omitted: Option<()>,
         }
-        round_trip!(
-            coer,
-            Omitted,
-            Omitted {
+        round_trip_encoding::<Omitted>(
+            Codec::Coer,
+            &(Omitted {
                 a: Some(OctetString::from_static(&[0x00, 0x01, 0x02])),
-                omitted: Some(())
-            },
-            &[192, 3, 0, 1, 2, 2, 7, 128, 0]
+                omitted: Some(()),
+            }),
+            &[192, 3, 0, 1, 2, 2, 7, 128, 0],
         );
     }
     #[test]
@@ -1432,15 +1415,14 @@ mod tests {
             pub is: Option<OctetString>,
             pub late: Option<Integer>,
         }
-        round_trip!(
-            coer,
-            SequenceOptionals,
-            SequenceOptionals {
+        round_trip_encoding::<SequenceOptionals>(
+            Codec::Coer,
+            &(SequenceOptionals {
                 it: 1.into(),
                 is: Some(OctetString::from_static(&[0x01, 0x02, 0x03])),
-                late: None
-            },
-            &[0b1000_0000, 0x01, 0x01, 0x03, 0x01, 0x02, 0x03]
+                late: None,
+            }),
+            &[0b1000_0000, 0x01, 0x01, 0x03, 0x01, 0x02, 0x03],
         );
 
         #[derive(AsnType, Decode, Encode, Clone, Debug, PartialEq, Eq)]
@@ -1452,15 +1434,14 @@ mod tests {
             #[rasn(tag(explicit(2)))]
             pub late: Option<Integer>,
         }
-        round_trip!(
-            coer,
-            SequenceOptionalsExplicit,
-            SequenceOptionalsExplicit {
+        round_trip_encoding::<SequenceOptionalsExplicit>(
+            Codec::Coer,
+            &(SequenceOptionalsExplicit {
                 it: 42.into(),
                 is: None,
-                late: None
-            },
-            &[0, 1, 42]
+                late: None,
+            }),
+            &[0, 1, 42],
         );
         #[derive(AsnType, Decode, Encode, Clone, Debug, PartialEq, Eq)]
         #[non_exhaustive]
@@ -1471,15 +1452,14 @@ mod tests {
             #[rasn(extension_addition)]
             pub today: OctetString,
         }
-        round_trip!(
-            coer,
-            SequenceDuplicatesExtended,
-            SequenceDuplicatesExtended {
+        round_trip_encoding::<SequenceDuplicatesExtended>(
+            Codec::Coer,
+            &(SequenceDuplicatesExtended {
                 it: 1.into(),
                 is: Some(OctetString::from_static(&[0x02, 0x03, 0x04])),
                 late: None,
-                today: OctetString::from_static(&[0x05, 0x06, 0x07])
-            },
+                today: OctetString::from_static(&[0x05, 0x06, 0x07]),
+            }),
             &[
                 0b1100_0000,
                 0x01,
@@ -1495,8 +1475,8 @@ mod tests {
                 0x03,
                 0x05,
                 0x06,
-                0x07
-            ]
+                0x07,
+            ],
         );
     }
     #[test]
@@ -0,0 +1,178 @@
+//! # Canonical XML Encoding Rules
+//!
+//! CXER is the canonical variant of XER defined in X.693. Every value has
+//! exactly one CXER encoding, which makes it suitable for XML values that are
+//! hashed or signed. The decoder is strict and rejects encodings that are not
+//! in canonical form.
+pub use super::xer::*;
+
+/// Attempts to decode `T` from `input` using CXER.
+///
+/// # Errors
+/// Returns `DecodeError` if `input` is not a valid CXER encoding of the expected type.
+pub fn decode<T: crate::Decode>(input: &[u8]) -> Result<T, crate::error::DecodeError> {
+    de::Decoder::new_with_options(input, de::DecoderOptions::cxer())
+        .and_then(|mut decoder| T::decode(&mut decoder))
+        .map_err(crate::error::DecodeError::into_cxer)
+}
+
+/// Attempts to encode `value` to CXER.
+///
+/// # Errors
+/// Returns `EncodeError` if `value` cannot be encoded as CXER.
+pub fn encode<T: crate::Encode>(
+    value: &T,
+) -> Result<alloc::vec::Vec<u8>, crate::error::EncodeError> {
+    let mut encoder = enc::Encoder::new_with_options(enc::EncoderOptions::cxer());
+    value
+        .encode(&mut encoder)
+        .map_err(crate::error::EncodeError::into_cxer)?;
+    Ok(encoder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq)]
+    #[rasn(automatic_tags)]
+    #[rasn(crate_root = "crate")]
+    struct Flags {
+        #[rasn(default = "default_enabled")]
+        enabled: bool,
+        values: SetOf<i32>,
+    }
+
+    fn default_enabled() -> bool {
+        true
+    }
+
+    /// A value without `PartialEq`, so its default value can't be detected.
+    #[derive(AsnType, Debug, Decode)]
+    #[rasn(delegate)]
+    #[rasn(crate_root = "crate")]
+    struct Opaque(bool);
+
+    #[derive(AsnType, Debug, Decode)]
+    #[rasn(automatic_tags)]
+    #[rasn(crate_root = "crate")]
+    struct Unchecked {
+        #[rasn(default = "default_opaque")]
+        enabled: Opaque,
+    }
+
+    fn default_opaque() -> Opaque {
+        Opaque(true)
+    }
+
+    fn encode_str<T: Encode>(value: &T) -> String {
+        String::from_utf8(crate::cxer::encode(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn empty_elements_without_padding() {
+        assert_eq!("<BOOLEAN><true/></BOOLEAN>", encode_str(&true));
+        assert_eq!("<NULL/>", encode_str(&()));
+        assert!(crate::cxer::decode::<bool>(b"<BOOLEAN><false/></BOOLEAN>").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "f64")]
+    fn canonical_real() {
+        assert_eq!("<REAL>1.1234E0</REAL>", encode_str(&1.1234_f64));
+        assert_eq!("<REAL>-2.5E-3</REAL>", encode_str(&-0.0025_f64));
+        assert_eq!("<REAL>1E3</REAL>", encode_str(&1000.0_f64));
+        assert_eq!("<REAL>0</REAL>", encode_str(&0.0_f64));
+        assert_eq!(
+            1000.0_f64,
+            crate::cxer::decode::<f64>(b"<REAL>1E3</REAL>").unwrap()
+        );
+        assert!(crate::cxer::decode::<f64>(b"<REAL>1000</REAL>").is_err());
+    }
+
+    #[test]
+    fn sorted_set_of_and_omitted_default() {
+        let value = Flags {
+            enabled: true,
+            values: SetOf::from_vec(vec![3, -1, 20]),
+        };
+        let encoded = encode_str(&value);
+        assert_eq!(
+            "<Flags><values><INTEGER>-1</INTEGER><INTEGER>20</INTEGER><INTEGER>3</INTEGER></values></Flags>",
+            encoded
+        );
+        assert_eq!(value, crate::cxer::decode(encoded.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn rejects_non_canonical_input() {
+        assert!(crate::xer::decode::<bool>(b"<BOOLEAN> <true/> </BOOLEAN>").is_ok());
+        assert!(crate::cxer::decode::<bool>(b"<BOOLEAN> <true/> </BOOLEAN>").is_err());
+        assert!(crate::cxer::decode::<Integer>(b"<INTEGER>+5</INTEGER>").is_err());
+        assert!(crate::cxer::decode::<Integer>(b"<INTEGER>007</INTEGER>").is_err());
+        assert!(crate::cxer::decode::<OctetString>(b"<OCTET_STRING>0a</OCTET_STRING>").is_err());
+        assert!(crate::cxer::decode::<BitString>(b"<BIT_STRING>1 0</BIT_STRING>").is_err());
+
+        let error = crate::cxer::decode::<Integer>(b"<INTEGER>+5</INTEGER>").unwrap_err();
+        assert_eq!(crate::Codec::Cxer, error.codec);
+        assert!(matches!(
+            *error.kind,
+            crate::error::DecodeErrorKind::CodecSpecific {
+                inner: crate::error::CodecDecodeError::Cxer(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_unsorted_set_of_and_encoded_default() {
+        let unsorted = b"<Flags><values><INTEGER>3</INTEGER><INTEGER>-1</INTEGER></values></Flags>";
+        assert!(crate::xer::decode::<Flags>(unsorted).is_ok());
+        assert!(crate::cxer::decode::<Flags>(unsorted).is_err());
+
+        let with_default = b"<Flags><enabled><true/></enabled><values/></Flags>";
+        assert!(crate::xer::decode::<Flags>(with_default).is_ok());
+        assert!(crate::cxer::decode::<Flags>(with_default).is_err());
+        assert_eq!(
+            Flags {
+                enabled: false,
+                values: SetOf::new(),
+            },
+            crate::cxer::decode(b"<Flags><enabled><false/></enabled><values/></Flags>").unwrap()
+        );
+        assert!(
+            crate::cxer::decode::<Unchecked>(b"<Unchecked><enabled><true/></enabled></Unchecked>")
+                .unwrap()
+                .enabled
+                .0
+        );
+    }
+
+    #[test]
+    fn canonical_times() {
+        let time: GeneralizedTime =
+            crate::xer::decode(b"<GeneralizedTime>20230101120000.50+0100</GeneralizedTime>")
+                .unwrap();
+        assert_eq!(
+            "<GeneralizedTime>20230101110000.5Z</GeneralizedTime>",
+            encode_str(&time)
+        );
+        for input in [
+            &b"<GeneralizedTime>20230101120000+0100</GeneralizedTime>"[..],
+            b"<GeneralizedTime>20230101110000.50Z</GeneralizedTime>",
+            b"<GeneralizedTime>202301011100Z</GeneralizedTime>",
+        ] {
+            assert!(crate::xer::decode::<GeneralizedTime>(input).is_ok());
+            assert!(crate::cxer::decode::<GeneralizedTime>(input).is_err());
+        }
+        let canonical = crate::cxer::decode::<GeneralizedTime>(
+            b"<GeneralizedTime>20230101110000.5Z</GeneralizedTime>",
+        )
+        .unwrap();
+        assert_eq!(encode_str(&time), encode_str(&canonical));
+
+        let input = b"<UTCTime>2301011200+0100</UTCTime>";
+        let time: UtcTime = crate::xer::decode(input).unwrap();
+        assert!(crate::cxer::decode::<UtcTime>(input).is_err());
+        assert_eq!("<UTCTime>230101110000Z</UTCTime>", encode_str(&time));
+    }
+}
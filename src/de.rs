@@ -13,6 +13,36 @@ use crate::types::{self, AsnType, Constraints, Enumerated, SetOf, Tag};
 pub use nom::Needed;
 pub use rasn_derive::Decode;
 
+/// Helpers for the derive macros, which compare a decoded `DEFAULT` component
+/// with its default value when the type implements `PartialEq`.
+#[doc(hidden)]
+pub mod default_value {
+    /// A decoded value next to its default value.
+    pub struct DefaultValue<'a, T>(pub &'a T, pub &'a T);
+
+    /// Compares values that implement `PartialEq`.
+    pub trait ComparableDefault {
+        fn is_default(&self) -> bool;
+    }
+
+    impl<T: PartialEq> ComparableDefault for DefaultValue<'_, T> {
+        fn is_default(&self) -> bool {
+            self.0 == self.1
+        }
+    }
+
+    /// Fallback for values that can't be compared.
+    pub trait IncomparableDefault {
+        fn is_default(&self) -> bool;
+    }
+
+    impl<T> IncomparableDefault for &DefaultValue<'_, T> {
+        fn is_default(&self) -> bool {
+            false
+        }
+    }
+}
+
 /// A generic ASN.1 decoding iterator. JER and XER are not supported.
 #[must_use]
 pub fn iter<D: Decode>(input: &[u8], codec: crate::codec::Codec) -> Iter<'_, D> {
@@ -94,6 +124,12 @@ impl<'input, D: Decode> Iter<'input, D> {
     pub fn append_bytes(&mut self, bytes: &'input [u8]) {
         self.buf.extend(bytes);
     }
+
+    /// Returns the input that hasn't been decoded yet.
+    #[must_use]
+    pub fn remaining(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
 }
 
 impl<D: Decode> Iterator for Iter<'_, D> {
@@ -238,7 +274,7 @@ pub trait Decoder<const RCL: usize = 0, const ECL: usize = 0>: Sized {
         constraints: Constraints,
     ) -> Result<Vec<D>, Self::Error>;
     /// Decode a `SET OF D` where `D: Decode` identified by `tag` from the available input.
-    fn decode_set_of<D: Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: Decode>(
         &mut self,
         tag: Tag,
         constraints: Constraints,
@@ -423,6 +459,26 @@ pub trait Decoder<const RCL: usize = 0, const ECL: usize = 0>: Sized {
             .unwrap_or_else(default_fn))
     }
 
+    /// Decode a `DEFAULT` value in a `SEQUENCE` or `SET` that has an explicit
+    /// `tag`, with a given `default_fn`.
+    fn decode_default_with_explicit_prefix<D: Decode, F: FnOnce() -> D>(
+        &mut self,
+        tag: Tag,
+        default_fn: F,
+    ) -> Result<D, Self::Error> {
+        Ok(self
+            .decode_optional_with_explicit_prefix::<D>(tag)?
+            .unwrap_or_else(default_fn))
+    }
+
+    /// Checks a `DEFAULT` component that was present in the encoding, where
+    /// `is_default` tells whether it holds its default value. Canonical
+    /// encoding rules such as CXER forbid encoding default values, other
+    /// codecs accept them.
+    fn check_encoded_default(&self, _is_default: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Decode an extension addition value in a `SEQUENCE` or `SET`.
     fn decode_extension_addition<D>(&mut self) -> Result<Option<D>, Self::Error>
     where
@@ -525,6 +581,27 @@ pub trait Decoder<const RCL: usize = 0, const ECL: usize = 0>: Sized {
     >(
         &mut self,
     ) -> Result<Option<D>, Self::Error>;
+
+    /// Decode a value of a `SEQUENCE`, `SET`, `SEQUENCE OF`, `SET OF`,
+    /// `CHOICE` or `ENUMERATED` type only known at runtime.
+    ///
+    /// By default it returns an error, for codecs that can't decode values of
+    /// such types.
+    fn decode_typed_value(
+        &mut self,
+        ty: &crate::value::Type,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<crate::value::Value, Self::Error> {
+        let _ = (ty, tag, constraints);
+        Err(Self::Error::custom(
+            alloc::format!(
+                "types described at runtime can't be decoded from {}",
+                self.codec()
+            ),
+            self.codec(),
+        ))
+    }
 }
 
 /// A generic error that can occur while decoding ASN.1.
@@ -631,15 +708,16 @@ impl_integers! {
     BigInt
 }
 
-impl<const START: i128, const END: i128> Decode for types::ConstrainedInteger<START, END> {
+impl<const START: i128, const END: i128, T: types::IntegerType> Decode
+    for types::ConstrainedInteger<START, END, T>
+{
     fn decode_with_tag_and_constraints<D: Decoder>(
         decoder: &mut D,
         tag: Tag,
         constraints: Constraints,
     ) -> Result<Self, D::Error> {
-        decoder
-            .decode_integer::<types::Integer>(tag, constraints)
-            .map(Self)
+        let () = Self::RANGE_FITS;
+        decoder.decode_integer::<T>(tag, constraints).map(Self)
     }
 }
 
@@ -802,7 +880,7 @@ impl<T: Decode> Decode for alloc::vec::Vec<T> {
     }
 }
 
-impl<T: Decode + Eq + core::hash::Hash> Decode for SetOf<T> {
+impl<T: Decode> Decode for SetOf<T> {
     fn decode_with_tag_and_constraints<D: Decoder>(
         decoder: &mut D,
         tag: Tag,
@@ -812,6 +890,17 @@ impl<T: Decode + Eq + core::hash::Hash> Decode for SetOf<T> {
     }
 }
 
+impl<T: Decode + crate::Encode> Decode for types::SortedSetOf<T> {
+    fn decode_with_tag_and_constraints<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Self, D::Error> {
+        let elements = decoder.decode_set_of(tag, constraints)?.into_vec();
+        Self::from_vec(elements).map_err(|error| D::Error::custom(error, decoder.codec()))
+    }
+}
+
 impl<T: Decode, const N: usize> Decode for [T; N] {
     fn decode_with_tag_and_constraints<D: Decoder>(
         decoder: &mut D,
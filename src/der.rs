@@ -27,11 +27,23 @@ pub fn encode<T: crate::Encode>(
 ) -> Result<alloc::vec::Vec<u8>, crate::error::EncodeError> {
     let mut enc = crate::ber::enc::Encoder::new(crate::ber::enc::EncoderOptions::der());
 
+    enc.size_ahead(value)?;
     value.encode(&mut enc)?;
 
     Ok(enc.output())
 }
 
+/// Returns the length of the DER encoding of `value`, without encoding it.
+/// # Errors
+/// Returns error specific to DER encoder if encoding is not possible.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::error::EncodeError> {
+    let mut enc = crate::ber::enc::Encoder::new_counting(crate::ber::enc::EncoderOptions::der());
+
+    value.encode(&mut enc)?;
+
+    Ok(enc.encoded_len())
+}
+
 /// Encodes `value` to DER into an existing `buffer`, reusing its allocation.
 /// The buffer is cleared before encoding.
 /// # Errors
@@ -44,6 +56,7 @@ pub fn encode_buf<T: crate::Encode>(
         crate::ber::enc::EncoderOptions::der(),
         core::mem::take(buffer),
     );
+    enc.size_ahead(value)?;
     value.encode(&mut enc)?;
     *buffer = enc.output();
     Ok(())
@@ -1,5 +1,7 @@
 //! Generic ASN.1 encoding framework.
 
+mod check;
+
 use crate::types::{self, AsnType, Constraints, Enumerated, IntegerType, SetOf, Tag};
 use crate::types::{Identifier, RealType};
 use num_bigint::BigInt;
@@ -160,7 +162,7 @@ pub trait Encoder<'encoder, const RCL: usize = 0, const ECL: usize = 0> {
     fn encode_object_identifier(
         &mut self,
         tag: Tag,
-        value: &[u32],
+        value: &types::Oid,
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error>;
 
@@ -173,6 +175,21 @@ pub trait Encoder<'encoder, const RCL: usize = 0, const ECL: usize = 0> {
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error>;
 
+    /// Encode a `INTEGER` value whose type constrains it to `START..=END`,
+    /// such as a [`types::ConstrainedInteger`]. Encoders can work out the
+    /// layout of the value from the bounds at compile time, as long as
+    /// `constraints` don't narrow them; by default the value is encoded with
+    /// [`Self::encode_integer`].
+    fn encode_constrained_integer<const START: i128, const END: i128, I: IntegerType>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &I,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_integer(tag, constraints, value, identifier)
+    }
+
     /// Encode a `REAL` value.
     fn encode_real<R: RealType>(
         &mut self,
@@ -351,7 +368,7 @@ pub trait Encoder<'encoder, const RCL: usize = 0, const ECL: usize = 0> {
         F: FnOnce(&mut Self::AnyEncoder<'b, RC, EC>) -> Result<(), Self::Error>;
 
     /// Encode a `SET OF` value.
-    fn encode_set_of<E: Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: Encode>(
         &mut self,
         tag: Tag,
         value: &types::SetOf<E>,
@@ -359,6 +376,21 @@ pub trait Encoder<'encoder, const RCL: usize = 0, const ECL: usize = 0> {
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error>;
 
+    /// Encode a `SET OF` value whose elements are already sorted by their
+    /// DER encodings. Encoders for codecs that sort the elements of `SET OF`
+    /// values can use the encodings stored in `value` instead of encoding the
+    /// elements again.
+    fn encode_sorted_set_of<E: Encode>(
+        &mut self,
+        tag: Tag,
+        value: &types::SortedSetOf<E>,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        let elements = types::SetOf::from_vec(value.iter().collect());
+        self.encode_set_of(tag, &elements, constraints, identifier)
+    }
+
     /// Encode the value a field or skip if it matches the default..
     fn encode_or_default<E: Encode + Default + PartialEq>(
         &mut self,
@@ -526,6 +558,28 @@ pub trait Encoder<'encoder, const RCL: usize = 0, const ECL: usize = 0> {
     ) -> Result<Self::Ok, Self::Error>
     where
         E: Encode + crate::types::Constructed<RC, EC>;
+
+    /// Encode a value of a `SEQUENCE`, `SET`, `SEQUENCE OF`, `SET OF`,
+    /// `CHOICE` or `ENUMERATED` type only known at runtime.
+    ///
+    /// Codecs that encode such values from the tags of their components alone
+    /// never call this, and by default it returns an error.
+    fn encode_typed_value(
+        &mut self,
+        value: crate::value::TypedValue<'_>,
+        tag: Tag,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        let _ = (value, tag, constraints, identifier);
+        Err(Self::Error::custom(
+            alloc::format!(
+                "types described at runtime can't be encoded with {}",
+                self.codec()
+            ),
+            self.codec(),
+        ))
+    }
 }
 
 /// A generic error that occurred while trying to encode ASN.1.
@@ -540,6 +594,80 @@ impl Error for core::convert::Infallible {
     }
 }
 
+/// Checks that `value` satisfies the size, value and permitted alphabet
+/// constraints of its type and of its components, without producing an
+/// encoding.
+///
+/// ```rust
+/// use rasn::prelude::*;
+///
+/// #[derive(AsnType, Encode)]
+/// #[rasn(delegate, size("1..=4"))]
+/// struct Name(Utf8String);
+///
+/// assert!(rasn::enc::check_constraints(&Name("rasn".into())).is_ok());
+/// assert!(rasn::enc::check_constraints(&Name("rasn!".into())).is_err());
+/// ```
+pub fn check_constraints<T: Encode>(value: &T) -> Result<(), crate::error::EncodeError> {
+    value.encode(&mut check::ConstraintChecker)
+}
+
+/// Returns whether `constraints` limit the value of an integer to exactly the
+/// non-extensible `START..=END`, so that an encoder can rely on the bounds of
+/// [`Encoder::encode_constrained_integer`].
+pub(crate) fn is_exact_value_range<const START: i128, const END: i128>(
+    constraints: &Constraints,
+) -> bool {
+    !constraints.extensible()
+        && constraints.value().is_some_and(|value| {
+            value.constraint.value.start_and_end() == (Some(&START), Some(&END))
+        })
+}
+
+/// Checks a `UTF8String` against its non-extensible size and permitted
+/// alphabet constraints. Neither is PER or OER-visible for `UTF8String`, so
+/// the codecs only validate them; the size is counted in characters.
+pub(crate) fn check_utf8_string(
+    value: &str,
+    constraints: &Constraints,
+    codec: crate::Codec,
+) -> Result<(), crate::error::EncodeError> {
+    check_characters(|| value.chars().map(u32::from), constraints, codec)
+}
+
+/// Checks the characters of a string against its non-extensible size and
+/// permitted alphabet constraints, with the size counted in characters.
+fn check_characters<I: Iterator<Item = u32>>(
+    characters: impl Fn() -> I,
+    constraints: &Constraints,
+    codec: crate::Codec,
+) -> Result<(), crate::error::EncodeError> {
+    if let Some(size) = constraints.size()
+        && size.extensible.is_none()
+    {
+        let len = characters().count();
+        if !size.constraint.contains(&len) {
+            return Err(crate::error::EncodeError::size_constraint_not_satisfied(
+                len,
+                &size.constraint,
+                codec,
+            ));
+        }
+    }
+    if let Some(alphabet) = constraints.permitted_alphabet()
+        && alphabet.extensible.is_none()
+        && let Some(character) = characters().find(|ch| !alphabet.constraint.contains(*ch))
+    {
+        return Err(
+            crate::error::EncodeError::alphabet_constraint_not_satisfied(
+                crate::error::strings::PermittedAlphabetError::CharacterNotFound { character },
+                codec,
+            ),
+        );
+    }
+    Ok(())
+}
+
 impl<E: Encode> Encode for &'_ E {
     fn encode<'b, EN: Encoder<'b>>(&self, encoder: &mut EN) -> Result<(), EN::Error> {
         E::encode(self, encoder)
@@ -768,7 +896,9 @@ impl Encode for BigInt {
     }
 }
 
-impl<const START: i128, const END: i128> Encode for types::ConstrainedInteger<START, END> {
+impl<const START: i128, const END: i128, T: types::IntegerType> Encode
+    for types::ConstrainedInteger<START, END, T>
+{
     fn encode_with_tag_and_constraints<'b, E: Encoder<'b>>(
         &self,
         encoder: &mut E,
@@ -776,8 +906,14 @@ impl<const START: i128, const END: i128> Encode for types::ConstrainedInteger<ST
         constraints: Constraints,
         identifier: Identifier,
     ) -> Result<(), E::Error> {
+        let () = Self::RANGE_FITS;
         encoder
-            .encode_integer(tag, constraints, &**self, identifier.or(Self::IDENTIFIER))
+            .encode_constrained_integer::<START, END, T>(
+                tag,
+                constraints,
+                &**self,
+                identifier.or(Self::IDENTIFIER),
+            )
             .map(drop)
     }
 }
@@ -1044,7 +1180,7 @@ impl<E: Encode> Encode for alloc::vec::Vec<E> {
     }
 }
 
-impl<E: Encode + Eq + core::hash::Hash> Encode for SetOf<E> {
+impl<E: Encode> Encode for SetOf<E> {
     fn encode_with_tag_and_constraints<'b, EN: Encoder<'b>>(
         &self,
         encoder: &mut EN,
@@ -1058,6 +1194,20 @@ impl<E: Encode + Eq + core::hash::Hash> Encode for SetOf<E> {
     }
 }
 
+impl<E: Encode> Encode for types::SortedSetOf<E> {
+    fn encode_with_tag_and_constraints<'b, EN: Encoder<'b>>(
+        &self,
+        encoder: &mut EN,
+        tag: Tag,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<(), EN::Error> {
+        encoder
+            .encode_sorted_set_of(tag, self, constraints, identifier.or(Self::IDENTIFIER))
+            .map(drop)
+    }
+}
+
 impl<E: Encode, const N: usize> Encode for [E; N] {
     fn encode_with_tag_and_constraints<'b, EN: Encoder<'b>>(
         &self,
@@ -0,0 +1,490 @@
+use super::Encode;
+use crate::{
+    Codec,
+    error::{EncodeError, strings::PermittedAlphabetError},
+    types::{
+        self, Constraints, Enumerated, Identifier, IntegerType, Oid, Tag, constraints::Extensible,
+        strings::StaticPermittedAlphabet,
+    },
+};
+
+/// Walks a value checking the size, value and permitted alphabet constraints
+/// of it and its components, without encoding anything. Errors report DER as
+/// their codec.
+///
+/// The encoders accept any value outside of the root of an extensible
+/// constraint, as a later version of the type may allow it. This version only
+/// allows the values in the root and in the extension additions it lists, so
+/// those are the ones checked for.
+#[derive(Debug, Default)]
+pub(crate) struct ConstraintChecker;
+
+const CODEC: Codec = Codec::Der;
+
+/// Returns whether the root of `constraint`, or one of its extension
+/// additions, satisfies `is_satisfied`.
+fn is_satisfied<T>(constraint: &Extensible<T>, is_satisfied: impl Fn(&T) -> bool) -> bool {
+    is_satisfied(&constraint.constraint)
+        || constraint
+            .extensible
+            .is_some_and(|additions| additions.iter().any(&is_satisfied))
+}
+
+fn check_size(len: usize, constraints: &Constraints) -> Result<(), EncodeError> {
+    if let Some(size) = constraints.size()
+        && !is_satisfied(size, |size| size.contains(&len))
+    {
+        return Err(EncodeError::size_constraint_not_satisfied(
+            len,
+            &size.constraint,
+            CODEC,
+        ));
+    }
+    Ok(())
+}
+
+/// Checks a string, with its size counted in characters.
+fn check_characters<I: Iterator<Item = u32>>(
+    characters: impl Fn() -> I,
+    constraints: &Constraints,
+) -> Result<(), EncodeError> {
+    if constraints.size().is_some() {
+        check_size(characters().count(), constraints)?;
+    }
+    if let Some(alphabet) = constraints.permitted_alphabet()
+        && let Some(character) = characters()
+            .find(|&character| !is_satisfied(alphabet, |alphabet| alphabet.contains(character)))
+    {
+        return Err(EncodeError::alphabet_constraint_not_satisfied(
+            PermittedAlphabetError::CharacterNotFound { character },
+            CODEC,
+        ));
+    }
+    Ok(())
+}
+
+fn check_string<S: StaticPermittedAlphabet>(
+    value: &S,
+    constraints: &Constraints,
+) -> Result<(), EncodeError> {
+    check_characters(|| value.chars(), constraints)
+}
+
+impl crate::Encoder<'_> for ConstraintChecker {
+    type Ok = ();
+    type Error = EncodeError;
+    type AnyEncoder<'this, const R: usize, const E: usize> = ConstraintChecker;
+
+    fn codec(&self) -> Codec {
+        CODEC
+    }
+
+    fn encode_any(&mut self, _: Tag, _: &types::Any, _: Identifier) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_bool(&mut self, _: Tag, _: bool, _: Identifier) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_bit_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::BitStr,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_size(value.len(), &constraints)
+    }
+
+    fn encode_enumerated<E: Enumerated>(
+        &mut self,
+        _: Tag,
+        _: &E,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_object_identifier(
+        &mut self,
+        _: Tag,
+        _: &Oid,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_integer<I: IntegerType>(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &I,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        if let Some(value_constraint) = constraints.value()
+            && !is_satisfied(value_constraint, |constraint| constraint.in_bound(value))
+        {
+            return Err(EncodeError::value_constraint_not_satisfied(
+                value.to_bigint().unwrap_or_default(),
+                &value_constraint.constraint.value,
+                CODEC,
+            ));
+        }
+        Ok(())
+    }
+
+    fn encode_real<R: types::RealType>(
+        &mut self,
+        _: Tag,
+        _: Constraints,
+        _: &R,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_null(&mut self, _: Tag, _: Identifier) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_octet_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &[u8],
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_size(value.len(), &constraints)
+    }
+
+    fn encode_general_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::GeneralString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_graphic_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::GraphicString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_utf8_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &str,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_characters(|| value.chars().map(u32::from), &constraints)
+    }
+
+    fn encode_visible_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::VisibleString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_ia5_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::Ia5String,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_printable_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::PrintableString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_numeric_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::NumericString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_teletex_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::TeletexString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_bmp_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+        value: &types::BmpString,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_string(value, &constraints)
+    }
+
+    fn encode_generalized_time(
+        &mut self,
+        _: Tag,
+        _: &types::GeneralizedTime,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_utc_time(
+        &mut self,
+        _: Tag,
+        _: &types::UtcTime,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_date(&mut self, _: Tag, _: &types::Date, _: Identifier) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_explicit_prefix<V: Encode>(
+        &mut self,
+        _: Tag,
+        value: &V,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        value.encode(self)
+    }
+
+    fn encode_sequence<'b, const RC: usize, const EC: usize, C, F>(
+        &'b mut self,
+        _: Tag,
+        encoder_scope: F,
+        _: Identifier,
+    ) -> Result<(), EncodeError>
+    where
+        C: crate::types::Constructed<RC, EC>,
+        F: FnOnce(&mut Self::AnyEncoder<'b, 0, 0>) -> Result<(), EncodeError>,
+    {
+        (encoder_scope)(self)
+    }
+
+    fn encode_sequence_of<E: Encode>(
+        &mut self,
+        _: Tag,
+        values: &[E],
+        constraints: Constraints,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_size(values.len(), &constraints)?;
+        values.iter().try_for_each(|value| value.encode(self))
+    }
+
+    fn encode_set<'b, const RC: usize, const EC: usize, C, F>(
+        &'b mut self,
+        _: Tag,
+        encoder_scope: F,
+        _: Identifier,
+    ) -> Result<(), EncodeError>
+    where
+        C: crate::types::Constructed<RC, EC>,
+        F: FnOnce(&mut Self::AnyEncoder<'b, 0, 0>) -> Result<(), EncodeError>,
+    {
+        (encoder_scope)(self)
+    }
+
+    fn encode_set_of<E: Encode>(
+        &mut self,
+        _: Tag,
+        values: &types::SetOf<E>,
+        constraints: Constraints,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        check_size(values.len(), &constraints)?;
+        values.iter().try_for_each(|value| value.encode(self))
+    }
+
+    fn encode_some<E: Encode>(&mut self, value: &E, _: Identifier) -> Result<(), EncodeError> {
+        value.encode(self)
+    }
+
+    fn encode_some_with_tag_and_constraints<E: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &E,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        value.encode_with_tag_and_constraints(self, tag, constraints, Identifier::EMPTY)
+    }
+
+    fn encode_none<E: Encode>(&mut self, _: Identifier) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_none_with_tag(&mut self, _: Tag, _: Identifier) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn encode_choice<E: Encode + crate::types::Choice>(
+        &mut self,
+        _: Constraints,
+        _: Tag,
+        encode_fn: impl FnOnce(&mut Self) -> Result<Tag, EncodeError>,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        (encode_fn)(self).map(drop)
+    }
+
+    fn encode_extension_addition<E: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: E,
+        _: Identifier,
+    ) -> Result<(), EncodeError> {
+        value.encode_with_tag_and_constraints(self, tag, constraints, Identifier::EMPTY)
+    }
+
+    fn encode_extension_addition_group<const RC: usize, const EC: usize, E>(
+        &mut self,
+        value: Option<&E>,
+        _: Identifier,
+    ) -> Result<(), EncodeError>
+    where
+        E: Encode + crate::types::Constructed<RC, EC>,
+    {
+        value.encode(self)
+    }
+}
+
+#[cfg(all(test, feature = "f64"))]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(AsnType, Encode)]
+    #[rasn(crate_root = "crate", automatic_tags)]
+    struct Measurement {
+        value: f64,
+        #[rasn(size("1..=8"), from("A..=Z"))]
+        unit: VisibleString,
+        #[rasn(value("0..=100"))]
+        confidence: u8,
+    }
+
+    #[derive(AsnType, Encode)]
+    #[rasn(crate_root = "crate", automatic_tags)]
+    struct Reading {
+        #[rasn(value("0..=10", extensible))]
+        level: Integer,
+        #[rasn(size("1..=2", extensible), from("0..=9", extensible))]
+        code: NumericString,
+    }
+
+    fn measurement(unit: &str, confidence: u8) -> Measurement {
+        Measurement {
+            value: 1.5,
+            unit: VisibleString::try_from(unit).unwrap(),
+            confidence,
+        }
+    }
+
+    fn reading(level: i32, code: &str) -> Reading {
+        Reading {
+            level: level.into(),
+            code: NumericString::try_from(code).unwrap(),
+        }
+    }
+
+    #[test]
+    fn checks_constraints_of_components() {
+        assert!(crate::enc::check_constraints(&measurement("KM", 90)).is_ok());
+
+        let error = crate::enc::check_constraints(&measurement("km", 90)).unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            crate::error::EncodeErrorKind::AlphabetConstraintNotSatisfied { .. }
+        ));
+        let error = crate::enc::check_constraints(&measurement("KILOMETRE", 90)).unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            crate::error::EncodeErrorKind::SizeConstraintNotSatisfied { .. }
+        ));
+        let error = crate::enc::check_constraints(&measurement("KM", 101)).unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            crate::error::EncodeErrorKind::ValueConstraintNotSatisfied { .. }
+        ));
+    }
+
+    #[test]
+    fn checks_root_of_extensible_constraints() {
+        assert!(crate::enc::check_constraints(&reading(10, "42")).is_ok());
+
+        let error = crate::enc::check_constraints(&reading(11, "42")).unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            crate::error::EncodeErrorKind::ValueConstraintNotSatisfied { .. }
+        ));
+        let error = crate::enc::check_constraints(&reading(10, "421")).unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            crate::error::EncodeErrorKind::SizeConstraintNotSatisfied { .. }
+        ));
+        let error = crate::enc::check_constraints(&reading(10, "4 ")).unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            crate::error::EncodeErrorKind::AlphabetConstraintNotSatisfied { .. }
+        ));
+
+        // The encoders still accept values outside of the root.
+        assert!(crate::uper::encode(&reading(11, "421")).is_ok());
+    }
+
+    #[test]
+    fn accepts_extension_additions() {
+        use crate::types::constraints::{Bounded, Constraint, Extensible, Value};
+
+        const ROOT: Value = Value::new(Bounded::const_new(0, 10));
+        const ADDITIONS: &[Value] = &[Value::new(Bounded::const_new(20, 30))];
+        let constraints = Constraints::new(&[Constraint::Value(Extensible::new_extensible(
+            ROOT, ADDITIONS,
+        ))]);
+
+        let check = |value: u8| {
+            crate::Encoder::encode_integer(
+                &mut super::ConstraintChecker,
+                Tag::INTEGER,
+                constraints,
+                &value,
+                Identifier::EMPTY,
+            )
+        };
+        assert!(check(5).is_ok());
+        assert!(check(25).is_ok());
+        assert!(check(15).is_err());
+    }
+}
@@ -1,13 +1,20 @@
 //! Error module includes all encode and decode errors among all codecs.
 //!
 //! Encoding can result to `EncodeError` and decoding can result to `DecodeError`.
+//! Transcoding between codecs can result to `TranscodeError`, wrapping either.
+//! Reading and writing framed values can result to `FramedError`.
+//! Builders and checked constructors can result to `BuildError`.
 //! Backtraces are enabled by default with `backtraces` feature.
 //! See submodules for other error types.
 #![allow(clippy::module_name_repetitions)]
+mod build;
 mod components;
 mod decode;
 mod encode;
+#[cfg(feature = "tokio")]
+mod framed;
 mod string;
+mod transcode;
 
 pub mod strings {
     //! Errors specific to string conversions, permitted alphabets, and other type problems.
@@ -30,4 +37,10 @@ pub use encode::{
     XerEncodeErrorKind,
 };
 
+pub use build::BuildError;
 pub use components::InnerSubtypeConstraintError;
+pub use transcode::TranscodeError;
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use framed::FramedError;
@@ -0,0 +1,23 @@
+use snafu::Snafu;
+
+use super::EncodeError;
+
+/// An error building a value with a builder or checked constructor generated
+/// by `#[rasn(builder)]`.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum BuildError {
+    /// A required field wasn't set.
+    #[snafu(display("missing required field `{name}`"))]
+    MissingField {
+        /// The name of the field.
+        name: &'static str,
+    },
+    /// The value doesn't satisfy the constraints of its type.
+    #[snafu(display("constraint not satisfied: {source}"), context(false))]
+    Constraint {
+        /// The error of the constraint check.
+        source: EncodeError,
+    },
+}
@@ -28,6 +28,7 @@ pub enum CodecDecodeError {
     Oer(OerDecodeErrorKind),
     Coer(CoerDecodeErrorKind),
     Xer(XerDecodeErrorKind),
+    Cxer(XerDecodeErrorKind),
     Avn(AvnDecodeErrorKind),
 }
 
@@ -43,6 +44,7 @@ impl core::fmt::Display for CodecDecodeError {
             CodecDecodeError::Oer(kind) => write!(f, "OER decoding error: {kind}"),
             CodecDecodeError::Coer(kind) => write!(f, "COER decoding error: {kind}"),
             CodecDecodeError::Xer(kind) => write!(f, "XER decoding error: {kind}"),
+            CodecDecodeError::Cxer(kind) => write!(f, "CXER decoding error: {kind}"),
             CodecDecodeError::Avn(kind) => write!(f, "AVN decoding error: {kind}"),
         }
     }
@@ -424,6 +426,28 @@ impl DecodeError {
         }
     }
 
+    /// Reports the XER errors of a decoder running in canonical mode as CXER
+    /// errors.
+    #[must_use]
+    pub(crate) fn into_cxer(mut self) -> Self {
+        if self.codec == Codec::Xer {
+            self.codec = Codec::Cxer;
+        }
+        self.kind = Box::new(match *self.kind {
+            DecodeErrorKind::CodecSpecific {
+                inner: CodecDecodeError::Xer(kind),
+            } => DecodeErrorKind::CodecSpecific {
+                inner: CodecDecodeError::Cxer(kind),
+            },
+            DecodeErrorKind::FieldError { name, nested } => DecodeErrorKind::FieldError {
+                name,
+                nested: Box::new(nested.into_cxer()),
+            },
+            kind => kind,
+        });
+        self
+    }
+
     #[must_use]
     fn from_codec_kind(inner: CodecDecodeError) -> Self {
         let codec = match inner {
@@ -439,6 +463,7 @@ impl DecodeError {
             CodecDecodeError::Oer(_) => crate::Codec::Oer,
             CodecDecodeError::Coer(_) => crate::Codec::Coer,
             CodecDecodeError::Xer(_) => crate::Codec::Xer,
+            CodecDecodeError::Cxer(_) => crate::Codec::Cxer,
             CodecDecodeError::Avn(_) => crate::Codec::Avn,
         };
         Self {
@@ -825,6 +850,10 @@ pub enum DerDecodeErrorKind {
     /// An error when constructed encoding encountered but not allowed.
     #[snafu(display("Constructed encoding encountered but not allowed"))]
     ConstructedEncodingNotAllowed,
+    /// An error when the elements of a `SET OF` are not in ascending order
+    /// of their encodings.
+    #[snafu(display("SET OF elements are not sorted by their encodings"))]
+    SetOfNotSorted,
 }
 
 /// An error that occurred when decoding JER.
@@ -856,6 +885,9 @@ pub enum JerDecodeErrorKind {
     /// An error when the JSON value is not a valid octet string.
     #[snafu(display("Found invalid character in octet string"))]
     InvalidJerOctetString {},
+    /// An error when the JSON value is not a valid base64 octet string.
+    #[snafu(display("Found invalid base64 octet string"))]
+    InvalidJerBase64OctetString {},
     /// An error when the JSON value is not a valid OID string.
     #[snafu(display("Failed to construct OID from value {value}",))]
     InvalidOIDString {
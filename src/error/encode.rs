@@ -20,6 +20,7 @@ pub enum CodecEncodeError {
     Jer(JerEncodeErrorKind),
     Coer(CoerEncodeErrorKind),
     Xer(XerEncodeErrorKind),
+    Cxer(XerEncodeErrorKind),
     Avn(AvnEncodeErrorKind),
 }
 
@@ -34,6 +35,7 @@ impl core::fmt::Display for CodecEncodeError {
             CodecEncodeError::Jer(kind) => write!(f, "JER encoding error: {kind}"),
             CodecEncodeError::Coer(kind) => write!(f, "COER encoding error: {kind}"),
             CodecEncodeError::Xer(kind) => write!(f, "XER encoding error: {kind}"),
+            CodecEncodeError::Cxer(kind) => write!(f, "CXER encoding error: {kind}"),
             CodecEncodeError::Avn(kind) => write!(f, "AVN encoding error: {kind}"),
         }
     }
@@ -238,6 +240,13 @@ impl EncodeError {
         Self::from_kind(EncodeErrorKind::RealNotSuppored, codec)
     }
 
+    /// Returns an encode error when an `Any` holding a `found` encoding is
+    /// encoded with `codec`, which can't embed it.
+    #[must_use]
+    pub fn any_codec_mismatch(found: crate::Codec, codec: crate::Codec) -> Self {
+        Self::from_kind(EncodeErrorKind::AnyCodecMismatch { found }, codec)
+    }
+
     /// A helper function to construct an `EncodeError` from the given `kind` and `codec`.
     #[must_use]
     pub fn from_kind(kind: EncodeErrorKind, codec: crate::Codec) -> Self {
@@ -249,6 +258,24 @@ impl EncodeError {
         }
     }
 
+    /// Reports the XER errors of an encoder running in canonical mode as CXER
+    /// errors.
+    #[must_use]
+    pub(crate) fn into_cxer(mut self) -> Self {
+        if self.codec == crate::Codec::Xer {
+            self.codec = crate::Codec::Cxer;
+        }
+        self.kind = Box::new(match *self.kind {
+            EncodeErrorKind::CodecSpecific {
+                inner: CodecEncodeError::Xer(kind),
+            } => EncodeErrorKind::CodecSpecific {
+                inner: CodecEncodeError::Cxer(kind),
+            },
+            kind => kind,
+        });
+        self
+    }
+
     #[must_use]
     fn from_codec_kind(inner: CodecEncodeError) -> Self {
         let codec = match inner {
@@ -264,6 +291,7 @@ impl EncodeError {
             CodecEncodeError::Jer(_) => crate::Codec::Jer,
             CodecEncodeError::Coer(_) => crate::Codec::Coer,
             CodecEncodeError::Xer(_) => crate::Codec::Xer,
+            CodecEncodeError::Cxer(_) => crate::Codec::Cxer,
             CodecEncodeError::Avn(_) => crate::Codec::Avn,
         };
         Self {
@@ -360,6 +388,14 @@ pub enum EncodeErrorKind {
     /// Error when we try to encode a `REAL` type with an unspported codec.
     #[snafu(display("Encoder doesn't support `REAL` type"))]
     RealNotSuppored,
+
+    /// Error when an `Any` holds an encoding made with a codec that the
+    /// encoder can't embed.
+    #[snafu(display("Cannot encode an `Any` holding a {found} encoding"))]
+    AnyCodecMismatch {
+        /// The codec the contents of the `Any` were encoded with.
+        found: crate::Codec,
+    },
 }
 /// `EncodeError` kinds of `Kind::CodecSpecific` which are specific for BER.
 #[derive(Snafu, Debug)]
@@ -369,20 +405,22 @@ pub enum BerEncodeErrorKind {
     /// Error to be thrown when the BER encoder encounters an `ANY` type in a `SET` field.
     #[snafu(display("Cannot encode `ANY` types in `SET` fields"))]
     AnyInSet,
-    /// `OBJECT IDENTIFIER` must have at least two components.
+    /// `OBJECT IDENTIFIER` must have at least two components. The object
+    /// identifier is displayed in arc-name notation, naming the arcs found in
+    /// the [`oid_registry`][crate::types::oid_registry].
     #[snafu(display(
-        "Invalid Object Identifier: must have at least two components and first octet must be 0, 1 or 2. Provided: {:?}",
-        oid
+        "Invalid Object Identifier: must have at least two components and first octet must be 0, 1 or 2. Provided: {}",
+        oid.arc_notation()
     ))]
     InvalidObjectIdentifier {
-        /// Bytes of the invalid object identifier
-        oid: alloc::vec::Vec<u32>,
+        /// The invalid object identifier
+        oid: crate::types::ObjectIdentifier,
     },
 }
 impl BerEncodeErrorKind {
     /// Create an error [`BerEncodeErrorKind::InvalidObjectIdentifier`}.
     #[must_use]
-    pub fn invalid_object_identifier(oid: alloc::vec::Vec<u32>) -> Self {
+    pub fn invalid_object_identifier(oid: crate::types::ObjectIdentifier) -> Self {
         Self::InvalidObjectIdentifier { oid }
     }
 }
@@ -420,7 +458,7 @@ pub enum JerEncodeErrorKind {
         msg: alloc::string::String,
     },
     /// Error to be thrown when encoding large integers than the supported range
-    #[snafu(display("Exceeds supported integer range -2^63..2^63 ({:?})", value))]
+    #[snafu(display("Exceeds supported integer range -2^63..2^64 ({:?})", value))]
     ExceedsSupportedIntSize {
         /// value failed to encode
         value: BigInt,
@@ -530,7 +568,7 @@ mod tests {
         let oid_encoded = crate::Codec::Ber.encode_to_binary(&oid);
         assert!(oid_encoded.is_ok());
 
-        let oid = vec![3, 5, 4, 3];
+        let oid = ObjectIdentifier::new_unchecked(vec![3, 5, 4, 3].into());
 
         let mut enc = enc::Encoder::new(enc::EncoderOptions::ber());
         let result = enc.encode_object_identifier(Tag::OBJECT_IDENTIFIER, &oid, Identifier::EMPTY);
@@ -546,18 +584,19 @@ mod tests {
             },
             _ => panic!("Unexpected OK!"),
         }
+
+        let oid = ObjectIdentifier::new_unchecked(vec![1].into());
+        let error = enc
+            .encode_object_identifier(Tag::OBJECT_IDENTIFIER, &oid, Identifier::EMPTY)
+            .unwrap_err();
+        assert!(error.to_string().contains("Provided: { iso(1) }"));
         // Debug output should look something like this:
         // dbg!(result.err());
         // EncodeError {
         //     kind: CodecSpecific {
         //         inner: Ber(
         //             InvalidObjectIdentifier {
-        //                 oid: [
-        //                     3,
-        //                     5,
-        //                     4,
-        //                     3,
-        //                 ],
+        //                 oid: ObjectIdentifier(3.5.4.3),
         //             },
         //         ),
         //     },
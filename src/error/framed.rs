@@ -0,0 +1,37 @@
+use snafu::Snafu;
+
+use super::{DecodeError, EncodeError};
+
+/// An error reading or writing framed values on an asynchronous stream.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum FramedError {
+    /// The underlying stream failed.
+    #[snafu(display("I/O error: {source}"), context(false))]
+    Io {
+        /// The error of the stream.
+        source: std::io::Error,
+    },
+    /// A complete frame could not be decoded.
+    #[snafu(display("failed to decode the frame: {source}"), context(false))]
+    Decode {
+        /// The error of the codec.
+        source: DecodeError,
+    },
+    /// A value could not be encoded.
+    #[snafu(display("failed to encode the frame: {source}"), context(false))]
+    Encode {
+        /// The error of the codec.
+        source: EncodeError,
+    },
+    /// A frame is longer than the maximum length allowed.
+    #[snafu(display("frame of {length} bytes exceeds the maximum of {max} bytes"))]
+    FrameTooLong {
+        /// The length of the frame, or of the buffered input when the frame
+        /// is not complete yet.
+        length: usize,
+        /// The maximum length allowed.
+        max: usize,
+    },
+}
@@ -0,0 +1,25 @@
+use snafu::Snafu;
+
+use super::{DecodeError, EncodeError};
+
+/// An error transcoding a value from one set of encoding rules to another.
+///
+/// Transcoding decodes the input with the source codec and encodes the result
+/// with the target codec, so the error is the one of the step that failed.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum TranscodeError {
+    /// The input could not be decoded with the source codec.
+    #[snafu(display("failed to decode the input: {source}"))]
+    Decode {
+        /// The error of the source codec.
+        source: DecodeError,
+    },
+    /// The decoded value could not be encoded with the target codec.
+    #[snafu(display("failed to encode the output: {source}"))]
+    Encode {
+        /// The error of the target codec.
+        source: EncodeError,
+    },
+}
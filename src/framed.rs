@@ -0,0 +1,405 @@
+//! Framing of ASN.1 values on asynchronous byte streams.
+//!
+//! [`AsnCodec`] implements the [`tokio_util::codec`] traits, so that a stream
+//! such as a `TcpStream` can be turned into a `Stream` and `Sink` of values with
+//! `FramedRead`, `FramedWrite` or `Framed`.
+//!
+//! BER, CER and DER values describe their own length, so each frame is one
+//! complete value, delimited by its identifier and length octets. Values of an
+//! indefinite length are decoded incrementally with [`crate::de::Iter`] as the
+//! input arrives, until their end-of-contents octets. The other encoding rules don't, so their values are framed with a
+//! four byte big-endian length prefix, which can also be used for BER, CER and
+//! DER.
+//!
+//! ```rust
+//! use bytes::BytesMut;
+//! use rasn::{Codec, framed::AsnCodec};
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! let mut codec = AsnCodec::<Vec<bool>>::new(Codec::Der);
+//! let mut buffer = BytesMut::new();
+//! codec.encode(vec![true, false], &mut buffer).unwrap();
+//!
+//! // Only half of the frame has arrived so far.
+//! let mut input = buffer.split_to(4);
+//! assert_eq!(codec.decode(&mut input).unwrap(), None);
+//!
+//! input.unsplit(buffer);
+//! assert_eq!(codec.decode(&mut input).unwrap(), Some(vec![true, false]));
+//! ```
+
+use bytes::{Buf, BufMut, BytesMut};
+use nom::Needed;
+
+use crate::{
+    Codec, Decode, Encode,
+    error::{DecodeErrorKind, FramedError},
+};
+
+/// The default maximum length of a frame, 8 MiB.
+const DEFAULT_MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+/// The width of the length prefix of [`Framing::LengthPrefixed`] frames.
+const LENGTH_PREFIX_WIDTH: usize = core::mem::size_of::<u32>();
+
+/// How values are delimited on the stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Framing {
+    /// Each frame is one BER, CER or DER value, delimited by its own length.
+    Value,
+    /// Each frame is prefixed with its length as a four byte big-endian
+    /// integer.
+    LengthPrefixed,
+}
+
+/// A [`tokio_util::codec`] decoder and encoder of `T` values in the encoding
+/// rules of a [`Codec`].
+#[derive(Debug)]
+pub struct AsnCodec<T> {
+    codec: Codec,
+    framing: Framing,
+    max_frame_length: usize,
+    /// The length the buffered input has to reach before decoding a
+    /// [`Framing::Value`] frame is tried again.
+    needed: usize,
+    _value: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> AsnCodec<T> {
+    /// Creates a new instance for `codec`, framing BER, CER and DER values by
+    /// their own length, and values of the other codecs with a length prefix.
+    #[must_use]
+    pub fn new(codec: Codec) -> Self {
+        let framing = match codec {
+            Codec::Ber | Codec::Cer | Codec::Der => Framing::Value,
+            _ => Framing::LengthPrefixed,
+        };
+
+        Self {
+            codec,
+            framing,
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+            needed: 0,
+            _value: core::marker::PhantomData,
+        }
+    }
+
+    /// Creates a new instance for `codec`, framing values with a length
+    /// prefix.
+    #[must_use]
+    pub fn length_prefixed(codec: Codec) -> Self {
+        Self {
+            framing: Framing::LengthPrefixed,
+            ..Self::new(codec)
+        }
+    }
+
+    /// Sets the maximum length of a frame, excluding any length prefix.
+    /// Defaults to 8 MiB.
+    #[must_use]
+    pub fn with_max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length;
+        self
+    }
+
+    /// Returns the codec of the values.
+    #[must_use]
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Returns how the values are delimited.
+    #[must_use]
+    pub fn framing(&self) -> Framing {
+        self.framing
+    }
+
+    /// Returns the maximum length of a frame, excluding any length prefix.
+    #[must_use]
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_length
+    }
+
+    fn check_frame_length(&self, length: usize) -> Result<(), FramedError> {
+        if length > self.max_frame_length {
+            return Err(FramedError::FrameTooLong {
+                length,
+                max: self.max_frame_length,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<T: Decode> AsnCodec<T> {
+    /// Decodes the value at the start of `src`. The identifier and length
+    /// octets of the value delimit the frame when its length is definite, and
+    /// the frame is only decoded once all of it has arrived, so a value that
+    /// overruns its own length is reported as invalid rather than waited on.
+    fn decode_value(&mut self, src: &mut BytesMut) -> Result<Option<T>, FramedError> {
+        if src.len() < self.needed {
+            return Ok(None);
+        }
+
+        let options = match self.codec {
+            Codec::Cer => crate::ber::de::DecoderOptions::cer(),
+            Codec::Der => crate::ber::de::DecoderOptions::der(),
+            _ => crate::ber::de::DecoderOptions::ber(),
+        };
+        let length = match crate::ber::de::parser::parse_value(options, src, None) {
+            Ok((rest, (_, Some(_)))) => src.len() - rest.len(),
+            Ok((_, (_, None))) => return self.decode_indefinite_value(src),
+            Err(error) => return self.wait_for_input(src, error),
+        };
+        self.needed = 0;
+        self.check_frame_length(length)?;
+
+        // The frame is consumed even if it fails to decode, so that the
+        // stream can carry on with the next one.
+        let frame = src.split_to(length);
+        Ok(Some(self.codec.decode_from_binary(&frame)?))
+    }
+
+    /// Decodes the value of an indefinite length at the start of `src` with
+    /// [`crate::de::Iter`], as only its end-of-contents octets delimit it.
+    fn decode_indefinite_value(&mut self, src: &mut BytesMut) -> Result<Option<T>, FramedError> {
+        let (value, remaining) = {
+            let mut values = crate::de::iter::<T>(src, self.codec);
+            let value = values.next();
+            (value, values.remaining().len())
+        };
+        match value {
+            Some(Ok(value)) => {
+                self.needed = 0;
+                let length = src.len() - remaining;
+                self.check_frame_length(length)?;
+                src.advance(length);
+                Ok(Some(value))
+            }
+            Some(Err(error)) => self.wait_for_input(src, error),
+            None => Ok(None),
+        }
+    }
+
+    /// Records how much more input the frame at the start of `src` needs at
+    /// least when `error` reports it as incomplete, so that decoding isn't
+    /// tried again until it has arrived, and returns any other error.
+    fn wait_for_input(
+        &mut self,
+        src: &mut BytesMut,
+        error: crate::error::DecodeError,
+    ) -> Result<Option<T>, FramedError> {
+        let needed = match *error.kind {
+            DecodeErrorKind::Incomplete {
+                needed: Needed::Size(needed),
+            } => needed.get(),
+            DecodeErrorKind::Incomplete {
+                needed: Needed::Unknown,
+            } => 1,
+            _ => {
+                self.needed = 0;
+                return Err(error.into());
+            }
+        };
+        self.needed = src.len().saturating_add(needed);
+        self.check_frame_length(self.needed)?;
+        src.reserve(self.needed - src.len());
+        Ok(None)
+    }
+
+    /// Decodes the frame after the length prefix at the start of `src`.
+    fn decode_length_prefixed(&mut self, src: &mut BytesMut) -> Result<Option<T>, FramedError> {
+        let Some(prefix) = src.get(..LENGTH_PREFIX_WIDTH) else {
+            return Ok(None);
+        };
+        let length = u32::from_be_bytes(prefix.try_into().unwrap()) as usize;
+        self.check_frame_length(length)?;
+
+        let frame_end = LENGTH_PREFIX_WIDTH + length;
+        if src.len() < frame_end {
+            src.reserve(frame_end - src.len());
+            return Ok(None);
+        }
+
+        // The frame is consumed even if it fails to decode, so that the
+        // stream can carry on with the next one.
+        src.advance(LENGTH_PREFIX_WIDTH);
+        let frame = src.split_to(length);
+        Ok(Some(self.codec.decode_from_binary(&frame)?))
+    }
+}
+
+impl<T> Clone for AsnCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            codec: self.codec,
+            framing: self.framing,
+            max_frame_length: self.max_frame_length,
+            needed: self.needed,
+            _value: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Decode> tokio_util::codec::Decoder for AsnCodec<T> {
+    type Item = T;
+    type Error = FramedError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, FramedError> {
+        match self.framing {
+            Framing::Value => self.decode_value(src),
+            Framing::LengthPrefixed => self.decode_length_prefixed(src),
+        }
+    }
+}
+
+impl<T: Encode> tokio_util::codec::Encoder<T> for AsnCodec<T> {
+    type Error = FramedError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), FramedError> {
+        let frame = self.codec.encode_to_binary(&item)?;
+        self.check_frame_length(frame.len())?;
+
+        if self.framing == Framing::LengthPrefixed {
+            let length = u32::try_from(frame.len()).map_err(|_| FramedError::FrameTooLong {
+                length: frame.len(),
+                max: u32::MAX as usize,
+            })?;
+            dst.reserve(LENGTH_PREFIX_WIDTH + frame.len());
+            dst.put_u32(length);
+        }
+        dst.extend_from_slice(&frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Integer, OctetString};
+    use alloc::{vec, vec::Vec};
+    use tokio_util::codec::{Decoder as _, Encoder as _};
+
+    #[test]
+    fn frames_values() {
+        let mut codec = AsnCodec::<OctetString>::new(Codec::Ber);
+        // A constructed OCTET STRING of an indefinite length.
+        let mut src = BytesMut::from(&[0x24, 0x80, 0x04, 0x01, 0xAB, 0x04, 0x01][..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+
+        src.extend_from_slice(&[0xCD, 0x00, 0x00, 0x04, 0x00]);
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(OctetString::from(vec![0xAB, 0xCD]))
+        );
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(OctetString::default())
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn waits_for_needed_input() {
+        let mut codec = AsnCodec::<OctetString>::new(Codec::Der);
+        let mut src = BytesMut::from(&[0x04, 0x05, 0x01, 0x02][..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(codec.needed, 7);
+
+        src.extend_from_slice(&[0x03, 0x04]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(codec.needed, 7);
+
+        src.extend_from_slice(&[0x05, 0x04]);
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(OctetString::from(vec![1, 2, 3, 4, 5]))
+        );
+        assert_eq!(codec.needed, 0);
+        assert_eq!(&src[..], [0x04]);
+    }
+
+    #[test]
+    fn frames_values_of_indefinite_length() {
+        let mut codec = AsnCodec::<Vec<OctetString>>::new(Codec::Ber);
+        // SEQUENCE OF constructed OCTET STRINGs, all of an indefinite length.
+        let encoded = [
+            0x30, 0x80, 0x24, 0x80, 0x04, 0x01, 0xAB, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+        ];
+        let mut src = BytesMut::new();
+        for &byte in &encoded[..encoded.len() - 1] {
+            src.extend_from_slice(&[byte]);
+            assert!(codec.decode(&mut src).unwrap().is_none());
+        }
+
+        src.extend_from_slice(&encoded[encoded.len() - 1..]);
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(vec![OctetString::from(vec![0xAB]), OctetString::default()])
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn reports_values_overrunning_their_frame() {
+        let mut codec = AsnCodec::<Vec<OctetString>>::new(Codec::Der);
+        // A SEQUENCE of three octets holding an OCTET STRING of five.
+        let mut src = BytesMut::from(&[0x30, 0x03, 0x04, 0x05, 0x01][..]);
+        src.extend_from_slice(&crate::der::encode(&vec![OctetString::from(vec![1])]).unwrap());
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(FramedError::Decode { .. })
+        ));
+
+        // The invalid frame is skipped, and the next one is decoded.
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(vec![OctetString::from(vec![1])])
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn reports_invalid_values() {
+        // DER doesn't allow values of an indefinite length.
+        let mut codec = AsnCodec::<OctetString>::new(Codec::Der);
+        let mut src = BytesMut::from(&[0x24, 0x80, 0x04, 0x00, 0x00, 0x00][..]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(FramedError::Decode { .. })
+        ));
+    }
+
+    #[test]
+    fn frames_with_length_prefix() {
+        let mut codec = AsnCodec::<Integer>::new(Codec::Uper);
+        let mut dst = BytesMut::new();
+        codec.encode(Integer::from(256), &mut dst).unwrap();
+        codec.encode(Integer::from(-1), &mut dst).unwrap();
+        assert_eq!(&dst[..7], [0, 0, 0, 3, 0x02, 0x01, 0x00]);
+
+        let mut src = dst.split_to(8);
+        assert_eq!(codec.decode(&mut src).unwrap(), Some(Integer::from(256)));
+        assert!(codec.decode(&mut src).unwrap().is_none());
+
+        src.unsplit(dst);
+        assert_eq!(codec.decode(&mut src).unwrap(), Some(Integer::from(-1)));
+    }
+
+    #[test]
+    fn rejects_long_frames() {
+        let mut codec = AsnCodec::<OctetString>::new(Codec::Der).with_max_frame_length(4);
+        let mut src = BytesMut::from(&[0x04, 0x05, 0, 0, 0][..]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(FramedError::FrameTooLong { length: 7, max: 4 })
+        ));
+
+        let mut dst = BytesMut::new();
+        assert!(matches!(
+            codec.encode(OctetString::from(vec![0; 3]), &mut dst),
+            Err(FramedError::FrameTooLong { length: 5, max: 4 })
+        ));
+    }
+}
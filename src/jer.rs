@@ -7,7 +7,7 @@ pub mod enc;
 /// # Errors
 /// Returns error specific to JER decoder if decoding is not possible.
 pub fn decode<T: crate::Decode>(input: &str) -> Result<T, crate::error::DecodeError> {
-    T::decode(&mut de::Decoder::new(input)?)
+    T::decode(&mut de::Decoder::new(input)?.with_instructions(T::JER_INSTRUCTIONS))
 }
 
 /// Attempts to encode `value` to JER.
@@ -16,7 +16,7 @@ pub fn decode<T: crate::Decode>(input: &str) -> Result<T, crate::error::DecodeEr
 pub fn encode<T: crate::Encode>(
     value: &T,
 ) -> Result<alloc::string::String, crate::error::EncodeError> {
-    let mut encoder = enc::Encoder::new();
+    let mut encoder = enc::Encoder::new().with_instructions(T::JER_INSTRUCTIONS);
     value.encode(&mut encoder)?;
     Ok(encoder.to_string())
 }
@@ -404,4 +404,132 @@ mod tests {
 
         round_trip_jer!(Renumed, Renumed::Test1("hel".into()), r#"{"test-1":"hel"}"#);
     }
+
+    #[test]
+    fn encoding_instructions() {
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(automatic_tags)]
+        #[rasn(crate_root = "crate")]
+        struct Entry {
+            key: Utf8String,
+            value: Integer,
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(automatic_tags, choice, jer(unwrapped))]
+        #[rasn(crate_root = "crate")]
+        enum Id {
+            Number(u32),
+            Name(Utf8String),
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(automatic_tags)]
+        #[rasn(crate_root = "crate")]
+        struct Point {
+            x: i32,
+            y: i32,
+            label: Option<Utf8String>,
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(automatic_tags)]
+        #[rasn(crate_root = "crate")]
+        struct Resource {
+            #[rasn(jer(name = "resource-id"))]
+            id: Id,
+            #[rasn(jer(base64))]
+            payload: OctetString,
+            #[rasn(jer(text))]
+            enabled: bool,
+            #[rasn(jer(object))]
+            counters: SequenceOf<Entry>,
+            #[rasn(jer(array))]
+            origin: Point,
+            wrapped: SimpleChoice,
+        }
+
+        round_trip_jer!(
+            Resource,
+            Resource {
+                id: Id::Name("printer".into()),
+                payload: OctetString::from_static(b"hello"),
+                enabled: true,
+                counters: alloc::vec![
+                    Entry {
+                        key: "jams".into(),
+                        value: 1.into()
+                    },
+                    Entry {
+                        key: "pages".into(),
+                        value: 12.into()
+                    },
+                ],
+                origin: Point {
+                    x: 3,
+                    y: -4,
+                    label: None
+                },
+                wrapped: SimpleChoice::Test1(1),
+            },
+            r#"{"counters":{"jams":1,"pages":12},"enabled":"true","origin":[3,-4,null],"payload":"aGVsbG8=","resource-id":"printer","wrapped":{"Test1":1}}"#
+        );
+        round_trip_jer!(Id, Id::Number(7), "7");
+        round_trip_jer!(
+            SequenceOf<Id>,
+            alloc::vec![Id::Number(7), Id::Name("seven".into())],
+            r#"[7,"seven"]"#
+        );
+
+        assert!(crate::jer::decode::<Resource>(r#"{"counters":[],"enabled":true,"origin":[0,0],"payload":"","resource-id":1,"wrapped":{"Test1":1}}"#).is_err());
+    }
+
+    #[test]
+    fn nested_type_instructions() {
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(automatic_tags)]
+        #[rasn(crate_root = "crate", jer(array))]
+        struct Pair {
+            first: i32,
+            second: i32,
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(choice, automatic_tags)]
+        #[rasn(crate_root = "crate", jer(unwrapped))]
+        enum Id {
+            Number(u32),
+            Name(Utf8String),
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+        #[rasn(automatic_tags)]
+        #[rasn(crate_root = "crate")]
+        struct Line {
+            id: Id,
+            start: Pair,
+            end: Option<Pair>,
+            points: SequenceOf<Pair>,
+        }
+
+        round_trip_jer!(
+            Line,
+            Line {
+                id: Id::Number(4),
+                start: Pair {
+                    first: 1,
+                    second: 2
+                },
+                end: Some(Pair {
+                    first: 3,
+                    second: 4
+                }),
+                points: alloc::vec![Pair {
+                    first: 5,
+                    second: 6
+                }],
+            },
+            r#"{"end":[3,4],"id":4,"points":[[5,6]],"start":[1,2]}"#
+        );
+    }
 }
@@ -9,16 +9,17 @@ use crate::{
     types::{
         Any, BitString, BmpString, Constraints, Constructed, Date, DecodeChoice, Enumerated,
         GeneralString, GeneralizedTime, GraphicString, Ia5String, NumericString, ObjectIdentifier,
-        Oid, PrintableString, SequenceOf, SetOf, Tag, TeletexString, UtcTime, Utf8String,
-        VisibleString, variants,
+        PrintableString, SequenceOf, SetOf, Tag, TeletexString, UtcTime, Utf8String, VisibleString,
+        fields::Field, instructions::JerInstructions, variants,
     },
+    value::Type,
 };
 
 macro_rules! decode_jer_value {
     ($decoder_fn:expr, $input:expr) => {
         $input
             .pop()
-            .flatten()
+            .and_then(|(value, _)| value)
             .ok_or_else(|| DecodeError::from(JerDecodeErrorKind::eoi()))
             .and_then($decoder_fn)
     };
@@ -28,7 +29,8 @@ macro_rules! decode_jer_value {
 pub struct Decoder {
     // `None` = field absent from the JSON object (OPTIONAL not present).
     // `Some(v)` = field present with value `v` (including `Some(Value::Null)` for ASN.1 NULL).
-    stack: alloc::vec::Vec<Option<Value>>,
+    // Every value is paired with the encoding instructions of its field.
+    stack: alloc::vec::Vec<(Option<Value>, JerInstructions)>,
 }
 
 impl Decoder {
@@ -41,15 +43,24 @@ impl Decoder {
             )
         })?;
         Ok(Self {
-            stack: alloc::vec![Some(root)],
+            stack: alloc::vec![(Some(root), JerInstructions::NONE)],
         })
     }
+
+    /// Sets the encoding instructions of the decoded root value.
+    #[must_use]
+    pub fn with_instructions(mut self, instructions: JerInstructions) -> Self {
+        if let Some((_, root_instructions)) = self.stack.first_mut() {
+            *root_instructions = instructions;
+        }
+        self
+    }
 }
 
 impl From<Value> for Decoder {
     fn from(value: Value) -> Self {
         Self {
-            stack: alloc::vec![Some(value)],
+            stack: alloc::vec![(Some(value), JerInstructions::NONE)],
         }
     }
 }
@@ -85,11 +96,7 @@ impl crate::Decoder for Decoder {
             })?;
             (value, *size)
         } else {
-            let last = self
-                .stack
-                .pop()
-                .flatten()
-                .ok_or_else(JerDecodeErrorKind::eoi)?;
+            let (last, _) = self.pop()?;
             let value_map = last
                 .as_object()
                 .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
@@ -148,7 +155,8 @@ impl crate::Decoder for Decoder {
     }
 
     fn decode_bool(&mut self, _t: Tag) -> Result<bool, Self::Error> {
-        decode_jer_value!(Self::boolean_from_value, self.stack)
+        let (value, instructions) = self.pop()?;
+        Self::boolean_from_value(value, instructions.text)
     }
 
     fn decode_enumerated<E: Enumerated>(&mut self, _t: Tag) -> Result<E, Self::Error> {
@@ -189,27 +197,15 @@ impl crate::Decoder for Decoder {
         D: Constructed<RC, EC>,
         F: FnOnce(&mut Self) -> Result<D, Self::Error>,
     {
-        let mut last = self
-            .stack
-            .pop()
-            .flatten()
-            .ok_or_else(JerDecodeErrorKind::eoi)?;
-        let value_map = last
-            .as_object_mut()
-            .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
-                needed: "object",
-                found: "unknown".into(),
-            })?;
-        let mut field_names = D::FIELDS
-            .iter()
-            .map(|f| f.name)
-            .collect::<alloc::vec::Vec<&str>>();
+        let (last, _) = self.pop()?;
+        let mut fields = D::FIELDS.iter().collect::<alloc::vec::Vec<Field>>();
         if let Some(extended_fields) = D::EXTENDED_FIELDS {
-            field_names.extend(extended_fields.iter().map(|f| f.name));
+            fields.extend(extended_fields.iter());
         }
-        field_names.reverse();
-        for name in field_names {
-            self.stack.push(value_map.remove(name));
+        let mut value_map = Self::constructed_from_value(last, &fields)?;
+        for field in fields.into_iter().rev() {
+            self.stack
+                .push((value_map.remove(field.jer_name()), field.jer));
         }
 
         (decode_fn)(self)
@@ -220,10 +216,11 @@ impl crate::Decoder for Decoder {
         _t: Tag,
         _c: Constraints,
     ) -> Result<SequenceOf<D>, Self::Error> {
-        decode_jer_value!(|v| self.sequence_of_from_value(v), self.stack)
+        let (value, instructions) = self.pop()?;
+        self.sequence_of_from_value(value, instructions)
     }
 
-    fn decode_set_of<D: crate::Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: crate::Decode>(
         &mut self,
         _t: Tag,
         _c: Constraints,
@@ -236,7 +233,12 @@ impl crate::Decoder for Decoder {
         _: Tag,
         _c: Constraints,
     ) -> Result<T, Self::Error> {
-        decode_jer_value!(Self::octet_string_from_value, self.stack).map(T::from)
+        let (value, instructions) = self.pop()?;
+        if instructions.base64 {
+            Self::octet_string_from_base64_value(value).map(T::from)
+        } else {
+            Self::octet_string_from_value(value).map(T::from)
+        }
     }
 
     fn decode_utf8_string(&mut self, _t: Tag, _c: Constraints) -> Result<Utf8String, Self::Error> {
@@ -389,17 +391,12 @@ impl crate::Decoder for Decoder {
         D: Fn(&mut Self::AnyDecoder<RC, EC>, usize, Tag) -> Result<FIELDS, Self::Error>,
         F: FnOnce(alloc::vec::Vec<FIELDS>) -> Result<SET, Self::Error>,
     {
-        let mut last = self
-            .stack
-            .pop()
-            .flatten()
-            .ok_or_else(JerDecodeErrorKind::eoi)?;
-        let value_map = last
-            .as_object_mut()
-            .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
-                needed: "object",
-                found: "unknown".into(),
-            })?;
+        let (last, _) = self.pop()?;
+        let mut all_fields = SET::FIELDS.iter().collect::<alloc::vec::Vec<Field>>();
+        if let Some(extended_fields) = SET::EXTENDED_FIELDS {
+            all_fields.extend(extended_fields.iter());
+        }
+        let mut value_map = Self::constructed_from_value(last, &all_fields)?;
         let mut field_indices = SET::FIELDS
             .iter()
             .enumerate()
@@ -407,7 +404,8 @@ impl crate::Decoder for Decoder {
         let mut fields = alloc::vec![];
         field_indices.sort_by_key(|(_, a)| a.tag_tree.smallest_tag());
         for (index, field) in field_indices.into_iter() {
-            self.stack.push(value_map.remove(field.name));
+            self.stack
+                .push((value_map.remove(field.jer_name()), field.jer));
             fields.push((decode_fn)(self, index, field.tag)?);
         }
 
@@ -416,7 +414,8 @@ impl crate::Decoder for Decoder {
             .flat_map(|fields| fields.iter())
             .enumerate()
         {
-            self.stack.push(value_map.remove(field.name));
+            self.stack
+                .push((value_map.remove(field.jer_name()), field.jer));
             fields.push((decode_fn)(self, index + SET::FIELDS.len(), field.tag)?);
         }
 
@@ -427,15 +426,20 @@ impl crate::Decoder for Decoder {
     where
         D: DecodeChoice,
     {
-        decode_jer_value!(|v| self.choice_from_value::<D>(v), self.stack)
+        let (value, instructions) = self.pop()?;
+        if instructions.union(D::JER_INSTRUCTIONS).unwrapped {
+            self.unwrapped_choice_from_value::<D>(value)
+        } else {
+            self.choice_from_value::<D>(value)
+        }
     }
 
     fn decode_optional<D: crate::Decode>(&mut self) -> Result<Option<D>, Self::Error> {
         match self.stack.pop() {
             None => Err(DecodeError::from(JerDecodeErrorKind::eoi())),
-            Some(None) => Ok(None),
-            Some(Some(v)) => {
-                self.stack.push(Some(v));
+            Some((None, _)) => Ok(None),
+            Some((Some(v), instructions)) => {
+                self.stack.push((Some(v), instructions));
                 Some(D::decode(self)).transpose()
             }
         }
@@ -495,6 +499,16 @@ impl crate::Decoder for Decoder {
         self.decode_optional()
     }
 
+    fn decode_typed_value(
+        &mut self,
+        ty: &Type,
+        _: Tag,
+        _: Constraints,
+    ) -> Result<crate::value::Value, Self::Error> {
+        let (value, _) = self.pop()?;
+        self.typed_value_from_value(ty, value)
+    }
+
     fn codec(&self) -> crate::Codec {
         crate::Codec::Jer
     }
@@ -507,11 +521,148 @@ impl crate::Decoder for Decoder {
 // -------------------------------------------------------------------
 
 impl Decoder {
+    /// Pops the next value and its encoding instructions off the stack.
+    fn pop(&mut self) -> Result<(Value, JerInstructions), DecodeError> {
+        match self.stack.pop() {
+            Some((Some(value), instructions)) => Ok((value, instructions)),
+            _ => Err(JerDecodeErrorKind::eoi().into()),
+        }
+    }
+
+    /// Returns the members of a `SEQUENCE` or `SET` value, which is either a
+    /// JSON object or, with the `ARRAY` instruction, a JSON array of the
+    /// components in declaration order.
+    fn constructed_from_value(
+        value: Value,
+        fields: &[Field],
+    ) -> Result<serde_json::Map<alloc::string::String, Value>, DecodeError> {
+        match value {
+            Value::Object(members) => Ok(members),
+            Value::Array(components) => Ok(fields
+                .iter()
+                .zip(components)
+                .filter(|(field, component)| {
+                    !(component.is_null() && field.is_optional_or_default())
+                })
+                .map(|(field, component)| (field.jer_name().into(), component))
+                .collect()),
+            value => Err(JerDecodeErrorKind::TypeMismatch {
+                needed: "object or array",
+                found: alloc::format!("{value}"),
+            }
+            .into()),
+        }
+    }
+
+    /// Decodes a value of a constructed or `ENUMERATED` type only known at
+    /// runtime. Members of objects which aren't components of the type, such
+    /// as the extension additions of later versions of it, are ignored.
+    fn typed_value_from_value(
+        &mut self,
+        ty: &Type,
+        value: Value,
+    ) -> Result<crate::value::Value, DecodeError> {
+        let codec = crate::Codec::Jer;
+        let mismatch = |needed, value: &Value| {
+            DecodeError::from(JerDecodeErrorKind::TypeMismatch {
+                needed,
+                found: alloc::format!("{value}"),
+            })
+        };
+        Ok(match ty {
+            Type::Sequence { .. } | Type::Set { .. } => {
+                let Value::Object(mut members) = value else {
+                    return Err(mismatch("object", &value));
+                };
+                let values = ty
+                    .all_components()
+                    .map(|component| {
+                        members
+                            .remove(&component.name)
+                            .map(|member| self.typed_member_from_value(&component.ty, member))
+                            .transpose()
+                    })
+                    .collect::<Result<_, _>>()?;
+                ty.components_value(values, codec)?
+            }
+            Type::Choice { .. } => {
+                let (name, member) = value
+                    .as_object()
+                    .and_then(|members| members.iter().next())
+                    .ok_or_else(|| mismatch("object", &value))?;
+                let alternative = ty
+                    .alternatives()
+                    .find(|alternative| alternative.name == *name)
+                    .ok_or_else(|| mismatch("alternative of the CHOICE type", &value))?;
+                crate::value::Value::Choice(
+                    alternative.name.clone(),
+                    alloc::boxed::Box::new(
+                        self.typed_member_from_value(&alternative.ty, member.clone())?,
+                    ),
+                )
+            }
+            Type::Enumerated { .. } => {
+                let identifier = value
+                    .as_str()
+                    .ok_or_else(|| mismatch("enumerated item as string", &value))?;
+                ty.enumeration_named(identifier).ok_or_else(|| {
+                    JerDecodeErrorKind::InvalidEnumDiscriminant {
+                        discriminant: identifier.into(),
+                    }
+                })?
+            }
+            Type::SequenceOf(item) | Type::SetOf(item) => {
+                let Value::Array(items) = value else {
+                    return Err(mismatch("array", &value));
+                };
+                let items = items
+                    .into_iter()
+                    .map(|value| self.typed_member_from_value(item, value))
+                    .collect::<Result<_, _>>()?;
+                match ty {
+                    Type::SetOf(_) => crate::value::Value::SetOf(SetOf::from_vec(items)),
+                    _ => crate::value::Value::SequenceOf(items),
+                }
+            }
+            _ => {
+                return Err(DecodeError::custom(
+                    alloc::format!("{} is not a constructed type", ty.name()),
+                    codec,
+                ));
+            }
+        })
+    }
+
+    /// Decodes `value` as a value of `ty`, which is a component, alternative
+    /// or item of a value of a type only known at runtime.
+    fn typed_member_from_value(
+        &mut self,
+        ty: &Type,
+        value: Value,
+    ) -> Result<crate::value::Value, DecodeError> {
+        self.stack.push((Some(value), JerInstructions::NONE));
+        ty.decode_value(self, None, Constraints::default())
+    }
+
     fn any_from_value(value: Value) -> Result<Any, <Self as crate::de::Decoder>::Error> {
-        Ok(Any::new(alloc::format!("{value}").as_bytes().to_vec()))
+        Ok(Any::with_codec(
+            alloc::format!("{value}").as_bytes().to_vec(),
+            crate::Codec::Jer,
+        ))
     }
 
-    fn boolean_from_value(value: Value) -> Result<bool, DecodeError> {
+    fn boolean_from_value(value: Value, text: bool) -> Result<bool, DecodeError> {
+        if text {
+            return match value.as_str() {
+                Some("true") => Ok(true),
+                Some("false") => Ok(false),
+                _ => Err(JerDecodeErrorKind::TypeMismatch {
+                    needed: "boolean as string",
+                    found: alloc::format!("{value}"),
+                }
+                .into()),
+            };
+        }
         Ok(value
             .as_bool()
             .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
@@ -535,14 +686,15 @@ impl Decoder {
     }
 
     fn integer_from_value<I: crate::types::IntegerType>(value: Value) -> Result<I, DecodeError> {
-        value
+        let value = value
             .as_i64()
+            .map(i128::from)
+            .or_else(|| value.as_u64().map(i128::from))
             .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
-                needed: "number (supported range -2^63..2^63)",
+                needed: "number (supported range -2^63..2^64)",
                 found: alloc::format!("{value}"),
-            })?
-            .try_into()
-            .map_err(|_| DecodeError::integer_overflow(I::WIDTH, crate::Codec::Jer))
+            })?;
+        I::try_from(value).map_err(|_| DecodeError::integer_overflow(I::WIDTH, crate::Codec::Jer))
     }
 
     fn real_from_value<R: crate::types::RealType>(value: Value) -> Result<R, DecodeError> {
@@ -592,44 +744,51 @@ impl Decoder {
             .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
                 needed: "number array",
                 found: alloc::format!("{value}"),
-            })?
-            .split('.')
-            .map(|arc| {
-                arc.parse::<u32>()
-                    .map_err(|_| JerDecodeErrorKind::TypeMismatch {
-                        needed: "OID arc number",
-                        found: arc.into(),
-                    })
             })
-            .collect::<Result<alloc::vec::Vec<u32>, _>>()
-            .ok()
-            .and_then(|arcs| Oid::new(&arcs).map(ObjectIdentifier::from))
-            .ok_or_else(|| JerDecodeErrorKind::InvalidOIDString { value })?)
+            .map(ObjectIdentifier::from_dotted)?
+            .map_err(|_| JerDecodeErrorKind::InvalidOIDString { value })?)
     }
 
     fn sequence_of_from_value<D: Decode>(
         &mut self,
         value: Value,
+        instructions: JerInstructions,
     ) -> Result<SequenceOf<D>, DecodeError> {
-        value
-            .as_array()
-            .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
-                needed: "array",
-                found: alloc::format!("{value}"),
-            })?
-            .clone()
+        let (items, item_instructions) = match value {
+            // Each member is decoded as a `[key, value]` array.
+            Value::Object(members) if instructions.object => (
+                members
+                    .into_iter()
+                    .map(|(key, value)| Value::Array(alloc::vec![Value::String(key), value]))
+                    .collect(),
+                D::JER_INSTRUCTIONS.union(JerInstructions {
+                    array: true,
+                    ..JerInstructions::NONE
+                }),
+            ),
+            Value::Array(items) if !instructions.object => (items, D::JER_INSTRUCTIONS),
+            value => {
+                return Err(JerDecodeErrorKind::TypeMismatch {
+                    needed: if instructions.object {
+                        "object"
+                    } else {
+                        "array"
+                    },
+                    found: alloc::format!("{value}"),
+                }
+                .into());
+            }
+        };
+        items
             .into_iter()
             .map(|v| {
-                self.stack.push(Some(v));
+                self.stack.push((Some(v), item_instructions));
                 D::decode(self)
             })
             .collect()
     }
 
-    fn set_of_from_value<D: Decode + Eq + core::hash::Hash>(
-        &mut self,
-        value: Value,
-    ) -> Result<SetOf<D>, DecodeError> {
+    fn set_of_from_value<D: Decode>(&mut self, value: Value) -> Result<SetOf<D>, DecodeError> {
         value
             .as_array()
             .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
@@ -639,7 +798,7 @@ impl Decoder {
             .clone()
             .into_iter()
             .try_fold(SetOf::new(), |mut acc, v| {
-                self.stack.push(Some(v));
+                self.stack.push((Some(v), D::JER_INSTRUCTIONS));
                 acc.insert(D::decode(self)?);
                 Ok(acc)
             })
@@ -681,7 +840,7 @@ impl Decoder {
                 .get(i)
                 {
                     Some(t) => {
-                        self.stack.push(Some(v.clone()));
+                        self.stack.push((Some(v.clone()), JerInstructions::NONE));
                         *t
                     }
                     None => Tag::EOC,
@@ -690,6 +849,43 @@ impl Decoder {
         D::from_tag(self, tag)
     }
 
+    /// Decodes a `CHOICE` encoded with the `UNWRAPPED` instruction by trying
+    /// its alternatives in order.
+    fn unwrapped_choice_from_value<D>(&mut self, value: Value) -> Result<D, DecodeError>
+    where
+        D: DecodeChoice,
+    {
+        let depth = self.stack.len();
+        for tag in variants::Variants::from_slice(
+            &[D::VARIANTS, D::EXTENDED_VARIANTS.unwrap_or(&[])].concat(),
+        )
+        .iter()
+        {
+            self.stack
+                .push((Some(value.clone()), JerInstructions::NONE));
+            match D::from_tag(self, *tag) {
+                Ok(choice) => return Ok(choice),
+                Err(_) => self.stack.truncate(depth),
+            }
+        }
+        Err(JerDecodeErrorKind::TypeMismatch {
+            needed: "value of a CHOICE alternative",
+            found: alloc::format!("{value}"),
+        }
+        .into())
+    }
+
+    fn octet_string_from_base64_value(value: Value) -> Result<alloc::vec::Vec<u8>, DecodeError> {
+        let octet_string = value
+            .as_str()
+            .ok_or_else(|| JerDecodeErrorKind::TypeMismatch {
+                needed: "base64 string",
+                found: alloc::format!("{value}"),
+            })?;
+        bytes_from_base64(octet_string)
+            .ok_or(JerDecodeErrorKind::InvalidJerBase64OctetString {}.into())
+    }
+
     fn octet_string_from_value(value: Value) -> Result<alloc::vec::Vec<u8>, DecodeError> {
         let octet_string = value
             .as_str()
@@ -701,7 +897,7 @@ impl Decoder {
             .ok_or(JerDecodeErrorKind::InvalidJerOctetString {}.into())
     }
 
-    fn utc_time_from_value(value: Value) -> Result<chrono::DateTime<chrono::Utc>, DecodeError> {
+    fn utc_time_from_value(value: Value) -> Result<UtcTime, DecodeError> {
         crate::ber::de::Decoder::parse_any_utc_time_string(
             value
                 .as_str()
@@ -713,9 +909,7 @@ impl Decoder {
         )
     }
 
-    fn general_time_from_value(
-        value: Value,
-    ) -> Result<chrono::DateTime<chrono::FixedOffset>, DecodeError> {
+    fn general_time_from_value(value: Value) -> Result<GeneralizedTime, DecodeError> {
         crate::ber::de::Decoder::parse_any_generalized_time_string(
             value
                 .as_str()
@@ -727,7 +921,7 @@ impl Decoder {
         )
     }
 
-    fn date_from_value(value: Value) -> Result<chrono::NaiveDate, DecodeError> {
+    fn date_from_value(value: Value) -> Result<Date, DecodeError> {
         crate::ber::de::Decoder::parse_date_string(value.as_str().ok_or_else(|| {
             JerDecodeErrorKind::TypeMismatch {
                 needed: "date string",
@@ -738,7 +932,7 @@ impl Decoder {
 }
 
 /// Parses a hex string into bytes.
-fn bytes_from_hexstring(hex_string: &str) -> Option<alloc::vec::Vec<u8>> {
+pub(crate) fn bytes_from_hexstring(hex_string: &str) -> Option<alloc::vec::Vec<u8>> {
     if !hex_string.len().is_multiple_of(2) {
         return None;
     }
@@ -764,6 +958,44 @@ fn nibble_from_hexdigit(c: char) -> Option<u8> {
     }
 }
 
+/// Parses a padded base64 string into bytes.
+fn bytes_from_base64(base64: &str) -> Option<alloc::vec::Vec<u8>> {
+    if !base64.len().is_multiple_of(4) {
+        return None;
+    }
+    let data = base64.trim_end_matches('=');
+    if base64.len() - data.len() > 2 {
+        return None;
+    }
+    let mut bytes = alloc::vec::Vec::with_capacity(data.len() * 3 / 4);
+    let mut group = 0u32;
+    for (i, c) in data.chars().enumerate() {
+        group = (group << 6) | u32::from(sextet_from_base64_digit(c)?);
+        if i % 4 == 3 {
+            bytes.extend_from_slice(&group.to_be_bytes()[1..]);
+            group = 0;
+        }
+    }
+    match data.len() % 4 {
+        0 => {}
+        2 => bytes.push((group >> 4) as u8),
+        3 => bytes.extend_from_slice(&((group >> 2) as u16).to_be_bytes()),
+        _ => return None,
+    }
+    Some(bytes)
+}
+
+fn sextet_from_base64_digit(c: char) -> Option<u8> {
+    match c {
+        'A'..='Z' => Some(c as u8 - b'A'),
+        'a'..='z' => Some(c as u8 - b'a' + 26),
+        '0'..='9' => Some(c as u8 - b'0' + 52),
+        '+' => Some(62),
+        '/' => Some(63),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -802,6 +1034,36 @@ mod tests {
         assert_eq!(bytes_from_hexstring("🙈🙉🙊"), None);
     }
 
+    #[test]
+    fn test_bytes_from_base64() {
+        // Test vectors from RFC 4648, section 10.
+        for (encoded, bytes) in [
+            ("Zg==", &b"f"[..]),
+            ("Zm8=", b"fo"),
+            ("Zm9v", b"foo"),
+            ("Zm9vYg==", b"foob"),
+            ("Zm9vYmE=", b"fooba"),
+            ("Zm9vYmFy", b"foobar"),
+            ("+/+/", &[0xFB, 0xFF, 0xBF]),
+        ] {
+            assert_eq!(bytes_from_base64(encoded).as_deref(), Some(bytes));
+        }
+        assert_eq!(bytes_from_base64(""), Some(vec![]));
+        assert_eq!(bytes_from_base64("AA=="), Some(vec![0]));
+        assert_eq!(bytes_from_base64("/w=="), Some(vec![0xFF]));
+        assert_eq!(bytes_from_base64("AQI="), Some(vec![1, 2]));
+        assert_eq!(bytes_from_base64("AQID"), Some(vec![1, 2, 3]));
+        assert_eq!(
+            bytes_from_base64("aGVsbG8gd29ybGQ="),
+            Some(b"hello world".to_vec())
+        );
+
+        assert_eq!(bytes_from_base64("AA"), None);
+        assert_eq!(bytes_from_base64("A==="), None);
+        assert_eq!(bytes_from_base64("AQ-D"), None);
+        assert_eq!(bytes_from_base64("😎"), None);
+    }
+
     #[test]
     fn test_nibble_from_hexdigit() {
         for c in '\u{0}'..'\u{1024}' {
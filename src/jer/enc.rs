@@ -8,16 +8,21 @@ pub(crate) type ValueMap = Map<alloc::string::String, Value>;
 
 use crate::{
     error::{EncodeError, JerEncodeErrorKind},
-    types::{Constraints, Identifier, IntegerType, Tag, variants},
+    types::{
+        Constraints, Identifier, IntegerType, Tag, fields::Field, instructions::JerInstructions,
+        variants,
+    },
+    value::{TypedValue, typed::Structure},
 };
 
 use crate::types::RealType;
 
 /// Encodes Rust structures into JSON Encoding Rules data.
 pub struct Encoder {
-    stack: alloc::vec::Vec<&'static str>,
+    stack: alloc::vec::Vec<(&'static str, JerInstructions)>,
     constructed_stack: alloc::vec::Vec<ValueMap>,
     root_value: Option<Value>,
+    root_instructions: JerInstructions,
 }
 
 impl Default for Encoder {
@@ -33,9 +38,17 @@ impl Encoder {
             stack: alloc::vec![],
             constructed_stack: alloc::vec![],
             root_value: None,
+            root_instructions: JerInstructions::NONE,
         }
     }
 
+    /// Sets the encoding instructions of the encoded root value.
+    #[must_use]
+    pub fn with_instructions(mut self, instructions: JerInstructions) -> Self {
+        self.root_instructions = instructions;
+        self
+    }
+
     /// Returns the complete encoded JSON value, consuming the encoder.
     pub fn to_json(self) -> Result<Value, EncodeError> {
         Ok(self
@@ -49,9 +62,27 @@ impl Encoder {
         self.root_value.map_or(<_>::default(), |v| v.to_string())
     }
 
+    /// Returns the encoding instructions of the value that is encoded next.
+    fn instructions(&self) -> JerInstructions {
+        self.stack
+            .last()
+            .map_or(self.root_instructions, |(_, instructions)| *instructions)
+    }
+
+    /// Encodes `value` on its own, as is done for the items of `SEQUENCE OF`
+    /// and `SET OF`.
+    fn encode_item<E: crate::Encode>(
+        value: &E,
+        instructions: JerInstructions,
+    ) -> Result<Value, EncodeError> {
+        let mut item_encoder = Self::new().with_instructions(instructions);
+        value.encode(&mut item_encoder)?;
+        item_encoder.to_json()
+    }
+
     fn update_root_or_constructed(&mut self, value: Value) -> Result<(), EncodeError> {
         match self.stack.pop() {
-            Some(id) => {
+            Some((id, _)) => {
                 self.constructed_stack
                     .last_mut()
                     .ok_or_else(|| JerEncodeErrorKind::JsonEncoder {
@@ -88,7 +119,12 @@ impl crate::Encoder<'_> for Encoder {
     }
 
     fn encode_bool(&mut self, _: Tag, value: bool, _: Identifier) -> Result<Self::Ok, Self::Error> {
-        self.update_root_or_constructed(Value::Bool(value))
+        let value = if self.instructions().text {
+            Value::String(value.to_string())
+        } else {
+            Value::Bool(value)
+        };
+        self.update_root_or_constructed(value)
     }
 
     fn encode_bit_string(
@@ -132,16 +168,10 @@ impl crate::Encoder<'_> for Encoder {
     fn encode_object_identifier(
         &mut self,
         _t: Tag,
-        value: &[u32],
+        value: &crate::types::Oid,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        self.update_root_or_constructed(Value::String(
-            value
-                .iter()
-                .map(|arc| alloc::format!("{arc}"))
-                .collect::<alloc::vec::Vec<alloc::string::String>>()
-                .join("."),
-        ))
+        self.update_root_or_constructed(Value::String(value.to_string()))
     }
 
     fn encode_integer<I: IntegerType>(
@@ -153,6 +183,8 @@ impl crate::Encoder<'_> for Encoder {
     ) -> Result<Self::Ok, Self::Error> {
         if let Some(as_i64) = value.to_i64() {
             self.update_root_or_constructed(Value::Number(as_i64.into()))
+        } else if let Some(as_u64) = value.to_u64() {
+            self.update_root_or_constructed(Value::Number(as_u64.into()))
         } else {
             Err(JerEncodeErrorKind::ExceedsSupportedIntSize {
                 value: value.to_bigint().unwrap_or_default(),
@@ -202,6 +234,9 @@ impl crate::Encoder<'_> for Encoder {
         value: &[u8],
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        if self.instructions().base64 {
+            return self.update_root_or_constructed(Value::String(base64_from_bytes(value)));
+        }
         self.update_root_or_constructed(Value::String(value.iter().fold(
             alloc::string::String::new(),
             |mut acc, bit| {
@@ -383,26 +418,31 @@ impl crate::Encoder<'_> for Encoder {
         C: crate::types::Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
-        let mut field_names = C::FIELDS
-            .iter()
-            .map(|f| f.name)
-            .collect::<alloc::vec::Vec<&str>>();
+        let instructions = self.instructions();
+        let mut fields = C::FIELDS.iter().collect::<alloc::vec::Vec<Field>>();
         if let Some(extended_fields) = C::EXTENDED_FIELDS {
-            field_names.extend(extended_fields.iter().map(|f| f.name));
+            fields.extend(extended_fields.iter());
         }
-        field_names.reverse();
-        for name in field_names {
-            self.stack.push(name);
+        for field in fields.iter().rev() {
+            self.stack.push((field.jer_name(), field.jer));
         }
         self.constructed_stack.push(ValueMap::new());
         (encoder_scope)(self)?;
-        let value_map =
+        let mut value_map =
             self.constructed_stack
                 .pop()
                 .ok_or_else(|| JerEncodeErrorKind::JsonEncoder {
                     msg: "Internal stack mismatch!".into(),
                 })?;
-        self.update_root_or_constructed(Value::Object(value_map))
+        if instructions.array {
+            let components = fields
+                .iter()
+                .map(|field| value_map.remove(field.jer_name()).unwrap_or(Value::Null))
+                .collect();
+            self.update_root_or_constructed(Value::Array(components))
+        } else {
+            self.update_root_or_constructed(Value::Object(value_map))
+        }
     }
 
     fn encode_sequence_of<E: crate::Encode>(
@@ -412,14 +452,42 @@ impl crate::Encoder<'_> for Encoder {
         _c: crate::types::Constraints,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        self.update_root_or_constructed(Value::Array(value.iter().try_fold(
-            alloc::vec![],
-            |mut acc, v| {
-                let mut item_encoder = Self::new();
-                v.encode(&mut item_encoder)
-                    .and(item_encoder.to_json().map(|rv| acc.push(rv)).map(|_| acc))
-            },
-        )?))
+        if !self.instructions().object {
+            let items = value
+                .iter()
+                .map(|item| Self::encode_item(item, E::JER_INSTRUCTIONS))
+                .collect::<Result<_, _>>()?;
+            return self.update_root_or_constructed(Value::Array(items));
+        }
+
+        // Each item is encoded as a `[key, value]` array and becomes one member.
+        let item_instructions = E::JER_INSTRUCTIONS.union(JerInstructions {
+            array: true,
+            ..JerInstructions::NONE
+        });
+        let mut members = ValueMap::new();
+        for item in value {
+            match Self::encode_item(item, item_instructions)? {
+                Value::Array(pair) => match <[Value; 2]>::try_from(pair) {
+                    Ok([Value::String(key), member]) => {
+                        members.insert(key, member);
+                    }
+                    _ => {
+                        return Err(JerEncodeErrorKind::JsonEncoder {
+                            msg: "`OBJECT` requires items with a string key and a value".into(),
+                        }
+                        .into());
+                    }
+                },
+                _ => {
+                    return Err(JerEncodeErrorKind::JsonEncoder {
+                        msg: "`OBJECT` requires items of a `SEQUENCE` type".into(),
+                    }
+                    .into());
+                }
+            }
+        }
+        self.update_root_or_constructed(Value::Object(members))
     }
 
     fn encode_set<'b, const RL: usize, const EL: usize, C, F>(
@@ -435,21 +503,19 @@ impl crate::Encoder<'_> for Encoder {
         self.encode_sequence::<RL, EL, C, F>(tag, value, Identifier::EMPTY)
     }
 
-    fn encode_set_of<E: crate::Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: crate::Encode>(
         &mut self,
         _t: Tag,
         value: &crate::types::SetOf<E>,
         _c: crate::types::Constraints,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        self.update_root_or_constructed(Value::Array(value.to_vec().iter().try_fold(
-            alloc::vec![],
-            |mut acc, v| {
-                let mut item_encoder = Self::new();
-                v.encode(&mut item_encoder)
-                    .and(item_encoder.to_json().map(|rv| acc.push(rv)).map(|_| acc))
-            },
-        )?))
+        let items = value
+            .to_vec()
+            .iter()
+            .map(|item| Self::encode_item(*item, E::JER_INSTRUCTIONS))
+            .collect::<Result<_, _>>()?;
+        self.update_root_or_constructed(Value::Array(items))
     }
 
     fn encode_some<E: crate::Encode>(
@@ -503,9 +569,11 @@ impl crate::Encoder<'_> for Encoder {
 
         if variants.is_empty() {
             self.update_root_or_constructed(Value::Object(ValueMap::new()))
+        } else if self.instructions().union(E::JER_INSTRUCTIONS).unwrapped {
+            (encode_fn)(self).map(drop)
         } else {
             self.constructed_stack.push(ValueMap::new());
-            self.stack.push(identifier);
+            self.stack.push((identifier, JerInstructions::NONE));
             (encode_fn)(self)?;
             let value_map =
                 self.constructed_stack
@@ -541,7 +609,87 @@ impl crate::Encoder<'_> for Encoder {
         }
     }
 
+    fn encode_typed_value(
+        &mut self,
+        value: TypedValue<'_>,
+        _: Tag,
+        _: Constraints,
+        _: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        let value = match value.structure::<EncodeError>(self.codec())? {
+            Structure::Sequence(fields) | Structure::Set(fields) => Value::Object(
+                fields
+                    .present()
+                    .map(|(component, value)| {
+                        Self::encode_item(&value, JerInstructions::NONE)
+                            .map(|value| (component.name.clone(), value))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            Structure::Choice(alternative) => {
+                let mut value_map = ValueMap::new();
+                value_map.insert(
+                    alternative.component.name.clone(),
+                    Self::encode_item(&alternative.value, JerInstructions::NONE)?,
+                );
+                Value::Object(value_map)
+            }
+            Structure::Enumerated(enumeration) => Value::String(enumeration.name.into()),
+            Structure::SequenceOf(items) | Structure::SetOf(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| Self::encode_item(item, JerInstructions::NONE))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        self.update_root_or_constructed(value)
+    }
+
     fn codec(&self) -> crate::Codec {
         crate::Codec::Jer
     }
 }
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as a padded base64 string, as required by the JER `BASE64`
+/// encoding instruction.
+fn base64_from_bytes(bytes: &[u8]) -> alloc::string::String {
+    let mut encoded = alloc::string::String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, byte)| {
+            acc | (u32::from(*byte) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[((group >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_from_bytes() {
+        // Test vectors from RFC 4648, section 10.
+        for (bytes, encoded) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_from_bytes(bytes), encoded);
+        }
+        assert_eq!(base64_from_bytes(&[0xFB, 0xFF, 0xBF]), "+/+/");
+    }
+}
@@ -65,7 +65,8 @@ macro_rules! constraints {
         $crate::types::constraints::Constraints::new(&[$($constraint),+])
     };
 }
-/// Helper macro to create a permitted alphabet constraint.
+/// Helper macro to create a permitted alphabet constraint. The characters
+/// must be listed in ascending order.
 ///
 /// Usage:
 /// ```rust
@@ -80,6 +81,8 @@ macro_rules! constraints {
 ///     b'5' as u32
 /// ]));
 /// ```
+/// Larger alphabets can be given as ranges of characters with
+/// [`PermittedAlphabet::from_ranges`](crate::types::constraints::PermittedAlphabet::from_ranges).
 #[macro_export]
 macro_rules! permitted_alphabet_constraint {
     ( $alphabet:expr) => {
@@ -187,15 +190,18 @@ macro_rules! bounded_constraint {
     };
 }
 
-/// Helper macro to create a `&'static Oid` from a string literal. The string
-/// literal must conform to the standard OID format and must be a valid OID
-/// (first arc must be <= 2), but also accepts OIDs with and without a leading
-/// `.`.
+/// Helper macro to create a `&'static Oid` in a constant, either from a
+/// string literal or from an array of arcs. The string literal must conform
+/// to the standard OID format and must be a valid OID (first arc must be
+/// <= 2), but also accepts OIDs with and without a leading `.`. Arcs can be
+/// as large as a `u128`.
 ///
 /// Usage:
 /// ```rust
 /// const SYS_DESCR: &'static rasn::types::Oid = rasn::oid!(".1.3.6.1.2.1.1.1.0");
-/// assert_eq!(SYS_DESCR, rasn::types::Oid::new(&[1, 3, 6, 1, 2, 1, 1, 1, 0]).unwrap());
+/// const SYS_NAME: &'static rasn::types::Oid = rasn::oid!([1, 3, 6, 1, 2, 1, 1, 5, 0]);
+/// assert_eq!(SYS_DESCR, &[1, 3, 6, 1, 2, 1, 1, 1, 0]);
+/// assert_eq!(SYS_NAME.to_string(), "1.3.6.1.2.1.1.5.0");
 /// ```
 #[macro_export]
 macro_rules! oid {
@@ -245,16 +251,26 @@ macro_rules! oid {
                 component_count
             };
 
-            const COMPONENTS: [u32; COMPONENT_LEN] = const {
+            const COMPONENTS: [u128; COMPONENT_LEN] = const {
                 let mut bytes_index = 0;
 
-                let mut components = [0u32; COMPONENT_LEN];
+                let mut components = [0u128; COMPONENT_LEN];
                 let mut index = 0;
                 while bytes_index < BYTE_STRING.len() {
                     let byte = BYTE_STRING[bytes_index];
                     match byte {
                         b'0'..=b'9' => {
-                            components[index] = components[index] * 10 + ((byte - b'0') as u32)
+                            components[index] = match components[index].checked_mul(10) {
+                                Some(component) => {
+                                    match component.checked_add((byte - b'0') as u128) {
+                                        Some(component) => component,
+                                        None => core::panic!(
+                                            "OID string literal arcs must fit in a u128"
+                                        ),
+                                    }
+                                }
+                                None => core::panic!("OID string literal arcs must fit in a u128"),
+                            }
                         }
                         b'.' => index += 1,
                         _ => core::unreachable!(),
@@ -268,7 +284,18 @@ macro_rules! oid {
                 components
             };
 
-            $crate::types::Oid::new(&COMPONENTS).unwrap()
+            $crate::oid!(COMPONENTS)
+        };
+
+        OID
+    }};
+    ($arcs:expr) => {{
+        const OID: &'static $crate::types::Oid = const {
+            const ARCS: &[u128] = &$arcs;
+            const ENCODED: [u8; $crate::types::Oid::__encoded_len(ARCS)] =
+                $crate::types::Oid::__encode(ARCS);
+
+            $crate::types::Oid::const_from_encoded_arcs(&ENCODED)
         };
 
         OID
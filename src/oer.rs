@@ -54,6 +54,22 @@ pub fn encode_buf<T: crate::Encode>(
     Ok(())
 }
 
+/// Returns the length of the COER encoding of `value`, without encoding it.
+///
+/// # Errors
+/// Returns `EncodeError` if `value` cannot be encoded as COER, usually meaning that constraints
+/// are not met.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, EncodeError> {
+    let (mut output, mut worker) = (enc::Counter::default(), enc::Counter::default());
+    let mut enc = Encoder::<0, 0, enc::Counter>::from_buffer(
+        enc::EncoderOptions::coer(),
+        &mut output,
+        &mut worker,
+    );
+    value.encode(&mut enc)?;
+    Ok(enc.encoded_len())
+}
+
 /// Attempts to decode `T` from `input` using OER with constraints.
 ///
 /// # Errors
@@ -119,6 +135,7 @@ mod tests {
     // On some cases, COER is more stricter than OER.
     use crate as rasn;
     use crate::prelude::*;
+    use crate::{Codec, testing::round_trip_encoding};
 
     #[test]
     fn test_bool() {
@@ -148,24 +165,22 @@ mod tests {
     #[test]
     #[cfg(feature = "f32")]
     fn real_f32() {
-        round_trip!(oer, f32, 1.0, &[0x3f, 0x80, 0x00, 0x00]);
-        round_trip!(oer, f32, -1.0, &[0xbf, 0x80, 0x00, 0x00]);
+        round_trip_encoding::<f32>(Codec::Oer, &1.0, &[0x3f, 0x80, 0x00, 0x00]);
+        round_trip_encoding::<f32>(Codec::Oer, &-1.0, &[0xbf, 0x80, 0x00, 0x00]);
     }
 
     #[test]
     #[cfg(feature = "f64")]
     fn real_f64() {
-        round_trip!(
-            oer,
-            f64,
-            1.0,
-            &[0x3f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        round_trip_encoding::<f64>(
+            Codec::Oer,
+            &1.0,
+            &[0x3f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
         );
-        round_trip!(
-            oer,
-            f64,
-            -1.0,
-            &[0xbf, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        round_trip_encoding::<f64>(
+            Codec::Oer,
+            &-1.0,
+            &[0xbf, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
         );
     }
 
@@ -205,8 +220,8 @@ mod tests {
         // Leading zeroes
         decode_error!(coer, Test, &[0x00, 0x00, 0x00, 0x01, 0x01]);
         // Leading zeros not allowed for enumerated values in any case
-        round_trip!(oer, Test, Test::A, &[0x01]);
-        round_trip!(oer, TestDefaults, TestDefaults::A, &[0x00]);
+        round_trip_encoding::<Test>(Codec::Oer, &Test::A, &[0x01]);
+        round_trip_encoding::<TestDefaults>(Codec::Oer, &TestDefaults::A, &[0x00]);
         // Unfortunately, below is correct since we just parse the first byte and do not check the
         // remainder in reality
         decode_ok!(oer, TestDefaults, &[0x00, 0x00], TestDefaults::A);
@@ -232,15 +247,14 @@ mod tests {
         let data = [0x10, 0x01, 0x2A];
         decode_error!(coer, SequenceOptionals, &data);
         let data = [0x0, 0x01, 0x2A];
-        round_trip!(
-            oer,
-            SequenceOptionals,
-            SequenceOptionals {
+        round_trip_encoding::<SequenceOptionals>(
+            Codec::Oer,
+            &(SequenceOptionals {
                 it: 42.into(),
                 is: None,
-                late: None
-            },
-            &data
+                late: None,
+            }),
+            &data,
         );
     }
 
@@ -260,6 +274,6 @@ mod tests {
             is: None,
             late: None,
         };
-        round_trip!(oer, SequenceOptionals, test_seq, &[0x00, 0x01, 0x2A]);
+        round_trip_encoding::<SequenceOptionals>(Codec::Oer, &test_seq, &[0x00, 0x01, 0x2A]);
     }
 }
@@ -7,6 +7,7 @@
 
 use alloc::{
     borrow::Cow,
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
@@ -24,6 +25,7 @@ use crate::{
         ObjectIdentifier, PrintableString, SetOf, Tag, TeletexString, UtcTime, VisibleString,
         fields::{Field, Fields},
     },
+    value::{Addition, Component, Type, Value, typed::canonical_order},
 };
 
 use bitvec::{order::Msb0, view::BitView};
@@ -73,6 +75,10 @@ pub struct Decoder<'input, const RFC: usize = 0, const EFC: usize = 0> {
     fields: ([Option<Field>; RFC], usize),
     extension_fields: Option<Fields<EFC>>,
     extensions_present: Option<Option<([Option<Field>; EFC], usize)>>,
+    /// The length of the input in octets when decoding started, which offsets
+    /// are traced from.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    origin: usize,
 }
 
 impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
@@ -85,9 +91,26 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
             fields: ([None; RFC], 0),
             extension_fields: <_>::default(),
             extensions_present: <_>::default(),
+            origin: input.len(),
         }
     }
 
+    /// Creates a decoder for the contents of a constructed value, continuing
+    /// from the current position in the input.
+    fn nested<const RC: usize, const EC: usize>(&self) -> Decoder<'input, RC, EC> {
+        let mut decoder = Decoder::new(self.input, self.options);
+        decoder.options.remaining_depth = decoder.options.remaining_depth.saturating_sub(1);
+        decoder.origin = self.origin;
+        decoder
+    }
+
+    /// Returns the offset in octets of the remaining input from the start of
+    /// the input.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn offset(&self) -> usize {
+        self.origin - self.input.len()
+    }
+
     #[must_use]
     fn codec(&self) -> Codec {
         self.options.current_codec()
@@ -153,6 +176,14 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
     /// In short form one octet is used and the leftmost bit is always zero; length is less than 128
     /// Max length for data type could be 2^1016 - 1 octets, however on this implementation it is limited to `usize::MAX`
     fn decode_length(&mut self) -> Result<usize, DecodeError> {
+        #[cfg(feature = "tracing")]
+        let offset = self.offset();
+        let length = self.decode_length_determinant()?;
+        trace_event!(offset, length, "length");
+        Ok(length)
+    }
+
+    fn decode_length_determinant(&mut self) -> Result<usize, DecodeError> {
         let possible_length = self.parse_one_byte()?;
         if possible_length < 128 {
             Ok(usize::from(possible_length))
@@ -400,6 +431,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
             Some(None) => (),
             None => return Ok(false),
         }
+        trace_event!(offset = self.offset(), "extension bitmap");
         let extensions_length = self.decode_length()?;
         // If length is 0, then there is only initial octet
         if extensions_length < 1u8.into() {
@@ -451,6 +483,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         let is_extensible = D::IS_EXTENSIBLE;
         let preamble_width =
             D::FIELDS.number_of_optional_and_default_fields() + usize::from(is_extensible);
+        trace_event!(offset = self.offset(), bits = preamble_width, "preamble");
         let bytes = self.extract_data_by_length(preamble_width.div_ceil(8))?;
 
         let mut result = [false; RC];
@@ -484,6 +517,245 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         Ok((result, extensible_present))
     }
 
+    /// Parses the value of an enumeration, in its short form below 128 and
+    /// as a signed integer with its length otherwise.
+    fn parse_enumerated_discriminant(&mut self) -> Result<isize, DecodeError> {
+        let byte = self.parse_one_byte()?;
+        if byte < 128 {
+            // Short form, use value directly as unsigned integer
+            return Ok(isize::from(byte));
+        }
+        // Long form, value as signed integer. Previous byte is length of the subsequent octets
+        let length = byte & 0x7fu8;
+        let discriminant: isize = self
+            .decode_integer_from_bytes(true, Some(length.into()))
+            .map_err(|e| {
+                if matches!(&*e.kind, DecodeErrorKind::IntegerOverflow { .. }) {
+                    DecodeError::length_exceeds_platform_width(
+                        "Enumerated discriminant value too large for this platform.".to_string(),
+                        self.codec(),
+                    )
+                } else {
+                    e
+                }
+            })?;
+
+        if (0..128).contains(&discriminant) && self.options.encoding_rules.is_coer() {
+            return Err(CoerDecodeErrorKind::NotValidCanonicalEncoding {
+                msg: "Enumerated discriminant should have been encoded in short form.".to_string(),
+            }
+            .into());
+        }
+        Ok(discriminant)
+    }
+
+    /// Parses the quantity of items of a `SEQUENCE OF` or `SET OF` value.
+    fn parse_quantity(&mut self) -> Result<usize, DecodeError> {
+        let length_of_quantity = self.decode_length()?;
+        let coer = self.options.encoding_rules.is_coer();
+        let length_bytes = self.extract_data_by_length(length_of_quantity)?;
+        if coer && length_bytes.first() == Some(&0) && length_bytes.len() > 1 {
+            return Err(CoerDecodeErrorKind::NotValidCanonicalEncoding {
+                msg: "Quantity value in 'sequence/set of' should not have leading zeroes in COER"
+                    .to_string(),
+            }
+            .into());
+        }
+        let length = usize::try_from_unsigned_bytes(length_bytes, self.codec())?;
+        trace_event!(quantity = length, "quantity");
+        Ok(length)
+    }
+
+    /// Parses the `width` bits of a preamble, checking that the bits filling
+    /// its last octet are zero.
+    fn parse_preamble_bits(&mut self, width: usize) -> Result<Vec<bool>, DecodeError> {
+        trace_event!(offset = self.offset(), bits = width, "preamble");
+        let bytes = self.extract_data_by_length(width.div_ceil(8))?;
+        let bits = bytes.view_bits::<Msb0>();
+        if bits[width..].any() {
+            return Err(OerDecodeErrorKind::invalid_preamble(
+                "Preamble unused bits should be all zero.".to_string(),
+            ));
+        }
+        Ok(bits[..width].iter().map(|bit| *bit).collect())
+    }
+
+    /// Parses the extension addition presence bitmap of a `SEQUENCE` or `SET`
+    /// value, with as many bits as the encoder knew extension additions.
+    fn parse_extension_bitmap(&mut self) -> Result<Vec<bool>, DecodeError> {
+        trace_event!(offset = self.offset(), "extension bitmap");
+        let length = self.decode_length()?;
+        let bytes = self.extract_data_by_length(length)?;
+        let (unused_bits, bitfield) = bytes.split_first().ok_or_else(|| {
+            OerDecodeErrorKind::invalid_extension_header("Missing initial octet".to_string())
+        })?;
+        let unused_bits = usize::from(*unused_bits);
+        if unused_bits > 7 || unused_bits > bitfield.len() * 8 {
+            return Err(OerDecodeErrorKind::invalid_extension_header(
+                "Invalid extension bitfield initial octet".to_string(),
+            ));
+        }
+        let bits = bitfield.view_bits::<Msb0>();
+        Ok(bits[..bits.len() - unused_bits]
+            .iter()
+            .map(|bit| *bit)
+            .collect())
+    }
+
+    /// Decodes a value of a constructed or `ENUMERATED` type only known at
+    /// runtime.
+    fn decode_typed_contents(&mut self, ty: &Type) -> Result<Value, DecodeError> {
+        let codec = self.codec();
+        Ok(match ty {
+            Type::Sequence {
+                components,
+                extensions,
+            }
+            | Type::Set {
+                components,
+                extensions,
+            } => {
+                let (mut values, is_extended) = self.decode_typed_components(
+                    components,
+                    extensions.is_some(),
+                    matches!(ty, Type::Set { .. }),
+                )?;
+                let additions = extensions.as_deref().unwrap_or_default();
+                let bitmap = if is_extended {
+                    self.parse_extension_bitmap()?
+                } else {
+                    Vec::new()
+                };
+                for (index, is_present) in bitmap.into_iter().enumerate() {
+                    // Additions of later versions of the type are skipped.
+                    let bytes = if is_present {
+                        let length = self.decode_length()?;
+                        self.extract_data_by_length(length)?
+                    } else {
+                        &[]
+                    };
+                    let Some(addition) = additions.get(index) else {
+                        continue;
+                    };
+                    if !is_present {
+                        values.extend(addition.components().iter().map(|_| None));
+                        continue;
+                    }
+                    let mut decoder = Decoder::<0, 0>::new(bytes, self.options);
+                    match addition {
+                        Addition::Component(component) => {
+                            values.push(Some(component.ty.decode_value(
+                                &mut decoder,
+                                None,
+                                Constraints::default(),
+                            )?))
+                        }
+                        Addition::Group(components) => {
+                            values.extend(
+                                decoder.decode_typed_components(components, false, false)?.0,
+                            );
+                        }
+                    }
+                }
+                ty.components_value(values, codec)?
+            }
+            Type::Choice {
+                alternatives,
+                extensions,
+            } => {
+                let tag = self.parse_tag()?;
+                trace_event!(%tag, "choice tag");
+                let matches = |alternative: &&Component| alternative.ty.matches_tag(tag);
+                let root = alternatives.iter().find(matches);
+                let extension = extensions.iter().flatten().find(matches);
+                let (alternative, value) = match (root, extension) {
+                    (Some(alternative), _) => (
+                        alternative,
+                        alternative
+                            .ty
+                            .decode_value(self, None, Constraints::default())?,
+                    ),
+                    (None, Some(alternative)) => {
+                        let length = self.decode_length()?;
+                        let mut decoder = Decoder::<0, 0>::new(
+                            self.extract_data_by_length(length)?,
+                            self.options,
+                        );
+                        (
+                            alternative,
+                            alternative.ty.decode_value(
+                                &mut decoder,
+                                None,
+                                Constraints::default(),
+                            )?,
+                        )
+                    }
+                    _ => {
+                        return Err(OerDecodeErrorKind::invalid_tag_variant_on_choice(
+                            tag,
+                            extensions.is_some(),
+                        ));
+                    }
+                };
+                Value::Choice(alternative.name.clone(), Box::new(value))
+            }
+            Type::Enumerated { .. } => {
+                let discriminant = self.parse_enumerated_discriminant()?;
+                ty.enumeration_of(discriminant.into(), codec)?
+            }
+            Type::SequenceOf(item) | Type::SetOf(item) => {
+                let quantity = self.parse_quantity()?;
+                let mut items = Vec::with_capacity(quantity.min(self.input.len()));
+                for _ in 0..quantity {
+                    items.push(item.decode_value(self, None, Constraints::default())?);
+                }
+                match ty {
+                    Type::SetOf(_) => Value::SetOf(SetOf::from_vec(items)),
+                    _ => Value::SequenceOf(items),
+                }
+            }
+            _ => {
+                return Err(DecodeError::custom(
+                    alloc::format!("{} is not a constructed type", ty.name()),
+                    codec,
+                ));
+            }
+        })
+    }
+
+    /// Decodes the preamble and the present components of a `SEQUENCE` or
+    /// `SET` value, or of an extension addition group, of a type only known at
+    /// runtime, in declaration order, and whether extension additions follow.
+    fn decode_typed_components(
+        &mut self,
+        components: &[Component],
+        is_extensible: bool,
+        is_set: bool,
+    ) -> Result<(Vec<Option<Value>>, bool), DecodeError> {
+        let optional = components.iter().filter(|c| c.is_optional()).count();
+        let preamble = self.parse_preamble_bits(usize::from(is_extensible) + optional)?;
+        let is_extended = is_extensible && preamble[0];
+        let mut presence = preamble.into_iter().skip(usize::from(is_extensible));
+
+        let order = if is_set {
+            canonical_order(components)
+        } else {
+            (0..components.len()).collect()
+        };
+        let mut values: Vec<Option<Value>> = components.iter().map(|_| None).collect();
+        for index in order {
+            let component = &components[index];
+            if !component.is_optional() || presence.next().unwrap_or_default() {
+                values[index] = Some(component.ty.decode_value(
+                    self,
+                    None,
+                    Constraints::default(),
+                )?);
+            }
+        }
+        Ok((values, is_extended))
+    }
+
     fn check_recursion_depth(&self) -> Result<(), DecodeError> {
         if self.options.remaining_depth == 0 {
             return Err(DecodeError::from_kind(
@@ -505,8 +777,9 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
 
     /// In OER, an alias for decoding an open type and obtaining the underlying type's encoded bytes.
     fn decode_any(&mut self, tag: Tag) -> Result<Any, Self::Error> {
-        Ok(Any::new(
+        Ok(Any::with_codec(
             self.decode_octet_string(tag, Constraints::default())?,
+            self.codec(),
         ))
     }
 
@@ -536,39 +809,14 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
     }
 
     fn decode_enumerated<E: Enumerated>(&mut self, _: Tag) -> Result<E, Self::Error> {
-        let byte = self.parse_one_byte()?;
-        if byte < 128 {
-            // Short form, use value directly as unsigned integer
-            E::from_discriminant(isize::from(byte))
-                .ok_or_else(|| DecodeError::discriminant_value_not_found(byte.into(), self.codec()))
-        } else {
-            // Long form, value as signed integer. Previous byte is length of the subsequent octets
-            let length = byte & 0x7fu8;
-            let discriminant: isize = self
-                .decode_integer_from_bytes(true, Some(length.into()))
-                .map_err(|e| {
-                    if matches!(&*e.kind, DecodeErrorKind::IntegerOverflow { .. }) {
-                        DecodeError::length_exceeds_platform_width(
-                            "Enumerated discriminant value too large for this platform."
-                                .to_string(),
-                            self.codec(),
-                        )
-                    } else {
-                        e
-                    }
-                })?;
-
-            if (0..128).contains(&discriminant) && self.options.encoding_rules.is_coer() {
-                return Err(CoerDecodeErrorKind::NotValidCanonicalEncoding {
-                    msg: "Enumerated discriminant should have been encoded in short form."
-                        .to_string(),
-                }
-                .into());
-            }
-            E::from_discriminant(discriminant).ok_or_else(|| {
-                DecodeError::discriminant_value_not_found(discriminant, self.codec())
-            })
-        }
+        trace_span!(
+            "enumerated",
+            ty = core::any::type_name::<E>(),
+            offset = self.offset()
+        );
+        let discriminant = self.parse_enumerated_discriminant()?;
+        E::from_discriminant(discriminant)
+            .ok_or_else(|| DecodeError::discriminant_value_not_found(discriminant, self.codec()))
     }
 
     fn decode_integer<I: crate::types::IntegerType>(
@@ -576,6 +824,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         _: Tag,
         constraints: Constraints,
     ) -> Result<I, Self::Error> {
+        trace_span!("integer", offset = self.offset());
         self.decode_integer_with_constraints::<I>(&constraints)
     }
 
@@ -611,6 +860,11 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         F: FnOnce(&mut Self::AnyDecoder<RC, EC>) -> Result<D, Self::Error>,
     {
         self.check_recursion_depth()?;
+        trace_span!(
+            "sequence",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
 
         // If there are no fields then the sequence is empty
         // Or if all fields are optional and default and there is no data
@@ -643,9 +897,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
             });
 
         let value = {
-            let mut sequence_decoder = Decoder::new(self.input, self.options);
-            sequence_decoder.options.remaining_depth =
-                sequence_decoder.options.remaining_depth.saturating_sub(1);
+            let mut sequence_decoder = self.nested();
             sequence_decoder.extension_fields = D::EXTENDED_FIELDS;
             sequence_decoder.extensions_present = extensible_present.then_some(None);
             sequence_decoder.fields = fields;
@@ -664,22 +916,15 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         _: Constraints,
     ) -> Result<Vec<D>, Self::Error> {
         self.check_recursion_depth()?;
+        trace_span!(
+            "sequence of",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
 
-        let length_of_quantity = self.decode_length()?;
-        let coer = self.options.encoding_rules.is_coer();
-        let length_bytes = self.extract_data_by_length(length_of_quantity)?;
-        if coer && length_bytes.first() == Some(&0) && length_bytes.len() > 1 {
-            return Err(CoerDecodeErrorKind::NotValidCanonicalEncoding {
-                msg: "Quantity value in 'sequence/set of' should not have leading zeroes in COER"
-                    .to_string(),
-            }
-            .into());
-        }
-        let length = usize::try_from_unsigned_bytes(length_bytes, self.codec())?;
+        let length = self.parse_quantity()?;
         let mut sequence_of: Vec<D> = Vec::with_capacity(length);
-        let mut options = self.options;
-        options.remaining_depth = options.remaining_depth.saturating_sub(1);
-        let mut decoder = Self::new(self.input, options);
+        let mut decoder: Self = self.nested();
         for _ in 0..length {
             let value = D::decode(&mut decoder)?;
             self.input = decoder.input;
@@ -688,7 +933,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         Ok(sequence_of)
     }
 
-    fn decode_set_of<D: Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: Decode>(
         &mut self,
         tag: Tag,
         constraints: Constraints,
@@ -722,12 +967,8 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         Ok(T::from(data))
     }
 
-    fn decode_utf8_string(
-        &mut self,
-        tag: Tag,
-        constraints: Constraints,
-    ) -> Result<String, Self::Error> {
-        self.decode_octet_string(tag, constraints)
+    fn decode_utf8_string(&mut self, tag: Tag, _: Constraints) -> Result<String, Self::Error> {
+        self.decode_octet_string(tag, Constraints::default())
             .and_then(|bytes| {
                 String::from_utf8(bytes).map_err(|e| {
                     DecodeError::string_conversion_failed(
@@ -867,6 +1108,11 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         F: FnOnce(Vec<FIELDS>) -> Result<SET, Self::Error>,
     {
         self.check_recursion_depth()?;
+        trace_span!(
+            "set",
+            ty = core::any::type_name::<SET>(),
+            offset = self.offset()
+        );
 
         let (bitmap, extensible_present) = self.parse_preamble::<RC, EC, SET>()?;
 
@@ -887,9 +1133,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         let fields = {
             let extended_fields_len = SET::EXTENDED_FIELDS.map_or(0, |fields| fields.len());
             let mut fields = Vec::with_capacity(SET::FIELDS.len() + extended_fields_len);
-            let mut set_decoder = Decoder::new(self.input, self.options);
-            set_decoder.options.remaining_depth =
-                set_decoder.options.remaining_depth.saturating_sub(1);
+            let mut set_decoder = self.nested();
             set_decoder.extension_fields = SET::EXTENDED_FIELDS;
             set_decoder.extensions_present = extensible_present.then_some(None);
             set_decoder.fields = field_map;
@@ -930,9 +1174,15 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         D: DecodeChoice,
     {
         self.check_recursion_depth()?;
+        trace_span!(
+            "choice",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset()
+        );
 
         let is_extensible = constraints.extensible();
         let tag: Tag = self.parse_tag()?;
+        trace_event!(%tag, "choice tag");
         let is_root_extension = crate::types::TagTree::tag_contains(&tag, D::VARIANTS);
         let is_extended_extension =
             crate::types::TagTree::tag_contains(&tag, D::EXTENDED_VARIANTS.unwrap_or(&[]));
@@ -1053,6 +1303,20 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         let mut decoder = Decoder::<0, 0>::new(&bytes, options);
         D::decode(&mut decoder).map(Some)
     }
+
+    fn decode_typed_value(
+        &mut self,
+        ty: &Type,
+        _: Tag,
+        _: Constraints,
+    ) -> Result<Value, Self::Error> {
+        self.check_recursion_depth()?;
+        trace_span!("typed value", ty = ty.name(), offset = self.offset());
+        let mut decoder = self.nested::<0, 0>();
+        let value = decoder.decode_typed_contents(ty)?;
+        self.input = decoder.input;
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 //! Encoding Rust structures into Octet Encoding Rules data.
 
+mod output;
+
 use alloc::vec::Vec;
 use bitvec::prelude::*;
 use num_traits::ToPrimitive;
@@ -11,6 +13,11 @@ use crate::{
         Any, BitStr, BmpString, Choice, Constraints, Constructed, Date, Enumerated, GeneralString,
         GeneralizedTime, GraphicString, Ia5String, Identifier, IntegerType, NumericString,
         PrintableString, RealType, SetOf, Tag, TeletexString, UtcTime, VisibleString,
+        constraints::Bounded,
+    },
+    value::{
+        TypedValue,
+        typed::{Alternative, Enumeration, Fields, Structure},
     },
 };
 
@@ -19,6 +26,8 @@ use crate::{
 /// Basic-OER is not supported and it might be that never will.
 use crate::error::{CoerEncodeErrorKind, EncodeError, EncodeErrorKind};
 
+pub use output::{Counter, Output};
+
 /// The current supported edition of the ITU X.696 standard.
 pub const ITU_T_X696_OER_EDITION: f32 = 3.0;
 
@@ -129,11 +138,13 @@ impl<const RC: usize, const EC: usize> ConstructedCursor<RC, EC> {
 ///
 /// Const `RCL` is the count of root components in the root component list of a sequence or set.
 /// Const `ECL` is the count of extension additions in the extension addition component type list in a sequence or set.
+/// `O` is the [`Output`] of the encoder, a [`Counter`] to count the length of
+/// an encoding instead of writing it.
 #[derive(Debug)]
-pub struct Encoder<'buffer, const RCL: usize = 0, const ECL: usize = 0> {
+pub struct Encoder<'buffer, const RCL: usize = 0, const ECL: usize = 0, O: Output = Vec<u8>> {
     options: EncoderOptions,
-    output: &'buffer mut Vec<u8>,
-    set_output: alloc::collections::BTreeMap<Tag, Vec<u8>>,
+    output: &'buffer mut O,
+    set_output: alloc::collections::BTreeMap<Tag, O>,
     is_extension_sequence: bool,
     root_bitfield: (usize, [(bool, Tag); RCL]),
     extension_bitfield: (usize, [bool; ECL]),
@@ -141,7 +152,7 @@ pub struct Encoder<'buffer, const RCL: usize = 0, const ECL: usize = 0> {
     cursor: ConstructedCursor<RCL, ECL>,
     // Sometimes we need to encode data into separate buffer before length can be calculated.
     // Using a separate buffer comes with a trade-off of reduced allocation count vs. peak memory usage.
-    worker: &'buffer mut Vec<u8>,
+    worker: &'buffer mut O,
 }
 
 // ITU-T X.696 8.2.1 Only the following constraints are OER-visible:
@@ -160,13 +171,25 @@ pub struct Encoder<'buffer, const RCL: usize = 0, const ECL: usize = 0> {
 
 // Tags are encoded only as part of the encoding of a choice type, where the tag indicates
 // which alternative of the choice type is the chosen alternative (see 20.1).
-impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
+impl<'buffer, const RCL: usize, const ECL: usize, O: Output> Encoder<'buffer, RCL, ECL, O> {
     #[must_use]
     /// Constructs a new encoder from options and existing `buffer` and worker buffer.
+    ///
+    /// ```rust
+    /// use rasn::oer::enc::{Counter, Encoder, EncoderOptions};
+    /// use rasn::Encode;
+    ///
+    /// let value = vec![vec![1u8, 2], vec![3]];
+    /// let (mut output, mut worker) = (Counter::default(), Counter::default());
+    /// let mut encoder = Encoder::<0, 0, Counter>::from_buffer(EncoderOptions::coer(), &mut output, &mut worker);
+    /// value.encode(&mut encoder).unwrap();
+    ///
+    /// assert_eq!(encoder.encoded_len(), rasn::coer::encode(&value).unwrap().len());
+    /// ```
     pub fn from_buffer(
         options: EncoderOptions,
-        output: &'buffer mut Vec<u8>,
-        worker: &'buffer mut Vec<u8>,
+        output: &'buffer mut O,
+        worker: &'buffer mut O,
     ) -> Self {
         Self {
             options,
@@ -186,20 +209,22 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
 
     /// Takes and returns the current output buffer, clearing the internal storage.
     #[must_use]
-    pub fn output(&mut self) -> Vec<u8> {
+    pub fn output(&mut self) -> O {
         core::mem::take(self.output)
     }
 
+    /// Returns the length in octets of the output, or of the octets counted
+    /// by a [`Counter`].
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        self.output.len()
+    }
+
     // `BTreeMap` is used to maintain the order of the fields in [SET], relying on the `Ord` trait of the [Tag] type.
     fn collect_set(&mut self) {
-        self.output.append(
-            self.set_output
-                .values()
-                .flatten()
-                .copied()
-                .collect::<Vec<u8>>()
-                .as_mut(),
-        );
+        for field in self.set_output.values_mut() {
+            self.output.append(field);
+        }
     }
 
     /// Sets the presence of a `OPTIONAL` or `DEFAULT` field in the bitfield.
@@ -345,7 +370,8 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
     /// `Length` of the data should be provided as full bytes.
     ///
     /// COER tries to use the shortest possible encoding and avoids leading zeros.
-    fn encode_length(buffer: &mut Vec<u8>, length: usize) -> Result<(), EncodeError> {
+    fn encode_length(buffer: &mut O, length: usize) -> Result<(), EncodeError> {
+        trace_event!(offset = buffer.len(), length, "length");
         let (bytes, needed) = length.to_unsigned_bytes_be();
         if length < 128 {
             // First bit should be always zero when below 128: ITU-T X.696 8.6.4
@@ -513,10 +539,8 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
     // if we do it early, we avoid most extra allocations
     fn extension_bitmap_reserve(&mut self) {
         self.cursor.set_extension_bitmap_cursor(self.output.len());
-        self.output.extend(core::iter::repeat_n(
-            0,
-            self.cursor.extension_bitmap_total_width,
-        ));
+        self.output
+            .extend_zeros(self.cursor.extension_bitmap_total_width);
     }
 
     /// Encode a constructed type.`RC` is the number root components, `EC` is the number of extension components.
@@ -524,7 +548,7 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
     fn encode_constructed<const RC: usize, const EC: usize, C: Constructed<RC, EC>>(
         &mut self,
         tag: Tag,
-        set_output: Option<&mut alloc::collections::BTreeMap<Tag, Vec<u8>>>,
+        set_output: Option<&mut alloc::collections::BTreeMap<Tag, O>>,
     ) -> Result<(), EncodeError> {
         // ### PREAMBLE ###
         // Section 16.2.2
@@ -550,6 +574,11 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
             self.root_bitfield
         };
         debug_assert!(C::FIELDS.number_of_optional_and_default_fields() == needed);
+        trace_event!(
+            offset = self.cursor.preamble_cursor,
+            bitmap = ?&option_bitfield[..needed],
+            "preamble"
+        );
         for (bit, _tag) in &option_bitfield[..needed] {
             preamble.set(preamble_index, *bit);
             preamble_index += 1;
@@ -566,9 +595,10 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
                     self.output
                         .extend_from_slice(&preamble.as_raw_slice()[..self.cursor.preamble_width]);
                 } else {
-                    self.output[self.cursor.preamble_cursor
-                        ..self.cursor.preamble_cursor + self.cursor.preamble_width]
-                        .copy_from_slice(&preamble.as_raw_slice()[..self.cursor.preamble_width]);
+                    self.output.overwrite(
+                        self.cursor.preamble_cursor,
+                        &preamble.as_raw_slice()[..self.cursor.preamble_width],
+                    );
                 }
             }
         }
@@ -586,19 +616,26 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
         debug_assert_ne!(self.cursor.extension_bitmap_cursor, 0);
         // We have pre-reserved space for the extension bitmap
         // Replace bytes
+        trace_event!(
+            offset = self.cursor.extension_bitmap_cursor,
+            bitmap = ?&self.extension_bitfield.1,
+            "extension bitmap"
+        );
         let mut extension_bitmap_buffer: BitArray<[u8; EC], Msb0> = BitArray::default();
         Self::encode_length(self.worker, self.cursor.extension_bitmap_width)?;
         let mut cursor = self.cursor.extension_bitmap_cursor + self.worker.len();
-        self.output[self.cursor.extension_bitmap_cursor..cursor].copy_from_slice(self.worker);
+        self.output
+            .overwrite(self.cursor.extension_bitmap_cursor, self.worker.octets());
         self.worker.clear();
-        self.output[cursor..=cursor]
-            .copy_from_slice(&self.cursor.extension_missing_bits.to_be_bytes());
+        self.output
+            .overwrite(cursor, &self.cursor.extension_missing_bits.to_be_bytes());
         cursor += 1;
         for (i, bit) in self.extension_bitfield.1.iter().enumerate() {
             extension_bitmap_buffer.set(i, *bit);
         }
         // The size of EC is always at least 1 byte if extensions present, so full octet will always fit
-        self.output[cursor..cursor + self.cursor.extension_bitfield_width].copy_from_slice(
+        self.output.overwrite(
+            cursor,
             &extension_bitmap_buffer.as_raw_slice()[..self.cursor.extension_bitfield_width],
         );
 
@@ -613,12 +650,151 @@ impl<'buffer, const RCL: usize, const ECL: usize> Encoder<'buffer, RCL, ECL> {
     }
 }
 
-impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
-    for Encoder<'buffer, RFC, EFC>
+impl<const RCL: usize, const ECL: usize, O: Output> Encoder<'_, RCL, ECL, O> {
+    /// Encodes the preamble and the components of a `SEQUENCE` or `SET` value
+    /// of a type only known at runtime, in the order of `fields`.
+    fn encode_typed_components(
+        &mut self,
+        tag: Tag,
+        fields: &Fields<'_>,
+    ) -> Result<(), EncodeError> {
+        let extensions = fields
+            .extensions
+            .as_deref()
+            .filter(|extensions| extensions.iter().any(|extension| extension.is_present()));
+        let preamble: Vec<bool> = fields
+            .extensions
+            .as_ref()
+            .map(|_| extensions.is_some())
+            .into_iter()
+            .chain(
+                fields
+                    .root
+                    .iter()
+                    .filter(|field| field.component.is_optional())
+                    .map(|field| field.value.is_some()),
+            )
+            .collect();
+        trace_event!(offset = self.output.len(), bitmap = ?preamble, "preamble");
+        self.output.extend_from_slice(&pack_bits(&preamble));
+
+        let mut encoder = Encoder::<0, 0, O>::from_buffer(
+            self.options.without_set_encoding(),
+            self.output,
+            self.worker,
+        );
+        for value in fields.root.iter().filter_map(|field| field.value) {
+            value.encode(&mut encoder)?;
+        }
+
+        if let Some(extensions) = extensions {
+            let bitmap: Vec<bool> = extensions
+                .iter()
+                .map(|extension| extension.is_present())
+                .collect();
+            trace_event!(offset = self.output.len(), bitmap = ?bitmap, "extension bitmap");
+            let octets = pack_bits(&bitmap);
+            Self::encode_length(self.output, 1 + octets.len())?;
+            self.output.push((octets.len() * 8 - bitmap.len()) as u8);
+            self.output.extend_from_slice(&octets);
+            for extension in extensions.iter().filter(|extension| extension.is_present()) {
+                // Extension additions are open types, and a group is encoded
+                // as a `SEQUENCE` of its components.
+                let (mut output, mut worker) = (O::default(), O::default());
+                let mut encoder = Encoder::<0, 0, O>::from_buffer(
+                    self.options.without_set_encoding(),
+                    &mut output,
+                    &mut worker,
+                );
+                if extension.group {
+                    let group = Fields {
+                        root: extension.fields.clone(),
+                        extensions: None,
+                    };
+                    encoder.encode_typed_components(Tag::SEQUENCE, &group)?;
+                } else if let Some(value) = extension.fields[0].value {
+                    value.encode(&mut encoder)?;
+                }
+                Self::encode_length(self.output, output.len())?;
+                self.output.append(&mut output);
+            }
+        }
+
+        self.extend(tag);
+        Ok(())
+    }
+
+    /// Encodes the tag and the value of the chosen alternative of a `CHOICE`
+    /// value of a type only known at runtime.
+    fn encode_typed_alternative(
+        &mut self,
+        tag: Tag,
+        alternative: &Alternative<'_>,
+    ) -> Result<(), EncodeError> {
+        let alternative_tag = alternative.component.ty.canonical_tag();
+        trace_event!(%alternative_tag, "choice tag");
+        let mut tag_buffer: BitArray<[u8; core::mem::size_of::<Tag>() + 1], Msb0> =
+            BitArray::default();
+        let needed = self.encode_tag(alternative_tag, tag_buffer.as_mut_bitslice());
+        self.output
+            .extend_from_slice(&tag_buffer.as_raw_slice()[..(needed / 8)]);
+
+        if alternative.is_extension {
+            let (mut output, mut worker) = (O::default(), O::default());
+            alternative
+                .value
+                .encode(&mut Encoder::<0, 0, O>::from_buffer(
+                    self.options.without_set_encoding(),
+                    &mut output,
+                    &mut worker,
+                ))?;
+            Self::encode_length(self.output, output.len())?;
+            self.output.append(&mut output);
+        } else {
+            alternative
+                .value
+                .encode(&mut Encoder::<0, 0, O>::from_buffer(
+                    self.options.without_set_encoding(),
+                    self.output,
+                    self.worker,
+                ))?;
+        }
+        self.extend(tag);
+        Ok(())
+    }
+
+    /// Encodes an `ENUMERATED` value of a type only known at runtime.
+    fn encode_typed_enumeration(
+        &mut self,
+        tag: Tag,
+        enumeration: &Enumeration<'_>,
+    ) -> Result<(), EncodeError> {
+        let number = enumeration.discriminant;
+        if (0..=i8::MAX.into()).contains(&number) {
+            self.encode_constrained_integer_with_padding(1, &number, false)?;
+        } else {
+            self.encode_unconstrained_enum_index(number)?;
+        }
+        self.extend(tag);
+        Ok(())
+    }
+}
+
+/// Packs `bits` into octets, padding the last octet with zeros.
+fn pack_bits(bits: &[bool]) -> Vec<u8> {
+    let mut octets = alloc::vec![0; bits.len().div_ceil(8)];
+    for (index, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        octets[index / 8] |= 0x80 >> (index % 8);
+    }
+    octets
+}
+
+impl<'buffer, const RFC: usize, const EFC: usize, O: Output> crate::Encoder<'buffer>
+    for Encoder<'buffer, RFC, EFC, O>
 {
     type Ok = ();
     type Error = EncodeError;
-    type AnyEncoder<'this, const R: usize, const E: usize> = Encoder<'this, R, E>;
+    type AnyEncoder<'this, const R: usize, const E: usize> = Encoder<'this, R, E, O>;
 
     fn codec(&self) -> Codec {
         self.options.current_codec()
@@ -630,6 +806,7 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         value: &Any,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        value.check_codec(self.codec())?;
         self.encode_octet_string(
             tag,
             <Constraints>::default(),
@@ -736,6 +913,11 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         // the values of the enumerated type.
         // max size for enumerated value is currently only isize MIN/MAX
         // Spec allows between –2^1015 and 2^1015 – 1
+        trace_span!(
+            "enumerated",
+            ty = core::any::type_name::<E>(),
+            offset = self.output.len()
+        );
         let number = value.discriminant();
         if 0isize <= number && number <= i8::MAX.into() {
             self.encode_constrained_integer_with_padding(1, &number, false)?;
@@ -751,13 +933,13 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
     fn encode_object_identifier(
         &mut self,
         tag: Tag,
-        value: &[u32],
+        value: &crate::types::Oid,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         let mut octets = Vec::new();
         crate::ber::enc::object_identifier_as_bytes(value, &mut octets)?;
         Self::encode_length(self.output, octets.len())?;
-        self.output.append(&mut octets);
+        self.output.extend_from_slice(&octets);
         self.extend(tag);
         Ok(())
     }
@@ -769,9 +951,38 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         value: &I,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!("integer", offset = self.output.len());
         self.encode_integer_with_constraints(tag, &constraints, value)
     }
 
+    fn encode_constrained_integer<const START: i128, const END: i128, I: IntegerType>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &I,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        // The bounds fix the number of octets, and whether they're signed
+        // doesn't matter once the value is known to be within them.
+        let octets = const { Bounded::const_new(START, END).range_in_bytes().1 };
+        let value_in_range = value
+            .to_i128()
+            .filter(|value| (START..=END).contains(value));
+        let (Some(octets), Some(value_in_range), true) = (
+            octets,
+            value_in_range,
+            crate::enc::is_exact_value_range::<START, END>(&constraints),
+        ) else {
+            return self.encode_integer(tag, constraints, value, identifier);
+        };
+
+        trace_span!("integer", offset = self.output.len());
+        self.output
+            .extend_from_slice(&value_in_range.to_be_bytes()[16 - usize::from(octets)..]);
+        self.extend(tag);
+        Ok(())
+    }
+
     fn encode_real<R: RealType>(
         &mut self,
         tag: Tag,
@@ -837,7 +1048,13 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         value: &str,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        self.encode_octet_string(tag, constraints, value.as_bytes(), Identifier::EMPTY)
+        crate::enc::check_utf8_string(value, &constraints, self.codec())?;
+        self.encode_octet_string(
+            tag,
+            Constraints::default(),
+            value.as_bytes(),
+            Identifier::EMPTY,
+        )
     }
 
     fn encode_visible_string(
@@ -968,7 +1185,12 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         C: Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
-        let mut encoder = Encoder::<'_, RL, EL>::from_buffer(
+        trace_span!(
+            "sequence",
+            ty = core::any::type_name::<C>(),
+            offset = self.output.len()
+        );
+        let mut encoder = Encoder::<'_, RL, EL, O>::from_buffer(
             self.options.without_set_encoding(),
             self.output,
             self.worker,
@@ -1001,10 +1223,16 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         // It seems that constraints here are not C/OER visible? No mention in standard...
+        trace_span!(
+            "sequence of",
+            ty = core::any::type_name::<E>(),
+            offset = self.output.len()
+        );
+        trace_event!(quantity = value.len(), "quantity");
         self.encode_unconstrained_integer(&value.len(), false)?;
         self.output.reserve(core::mem::size_of_val(value));
 
-        let mut encoder = Encoder::<0>::from_buffer(self.options, self.output, self.worker);
+        let mut encoder = Encoder::<0, 0, O>::from_buffer(self.options, self.output, self.worker);
         {
             for one in value {
                 E::encode(one, &mut encoder)?;
@@ -1024,9 +1252,14 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         C: Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
+        trace_span!(
+            "set",
+            ty = core::any::type_name::<C>(),
+            offset = self.output.len()
+        );
         let mut options = self.options;
         options.set_encoding = true;
-        let mut encoder = Encoder::<RL, EL>::from_buffer(options, self.output, self.worker);
+        let mut encoder = Encoder::<RL, EL, O>::from_buffer(options, self.output, self.worker);
         let cursor = ConstructedCursor::<RL, EL>::new(
             C::FIELDS.number_of_optional_and_default_fields(),
             C::IS_EXTENSIBLE,
@@ -1042,7 +1275,7 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         Ok(())
     }
 
-    fn encode_set_of<E: Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: Encode>(
         &mut self,
         tag: Tag,
         value: &SetOf<E>,
@@ -1089,6 +1322,12 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!(
+            "choice",
+            ty = core::any::type_name::<E>(),
+            offset = self.output.len()
+        );
+        trace_event!(%tag, "choice tag");
         // Encode tag
         let mut tag_buffer: BitArray<[u8; core::mem::size_of::<Tag>() + 1], Msb0> =
             BitArray::default();
@@ -1131,7 +1370,8 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
             // Since we need to calculate the unknown length of the data length, data must be encoded at first
             // Swap the buffers here to avoid extra alloactions
             // Also helps us on playing with mutability checks...
-            let mut encoder = Encoder::<0>::from_buffer(self.options, self.worker, self.output);
+            let mut encoder =
+                Encoder::<0, 0, O>::from_buffer(self.options, self.worker, self.output);
             E::encode_with_tag_and_constraints(
                 &value,
                 &mut encoder,
@@ -1169,7 +1409,7 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         // Swap the buffers here to avoid extra alloactions
         // Also helps us on playing with mutability checks...
         let cursor = self.output.len();
-        let mut encoder = Encoder::<0>::from_buffer(self.options, self.worker, self.output);
+        let mut encoder = Encoder::<0, 0, O>::from_buffer(self.options, self.worker, self.output);
         value.encode(&mut encoder)?;
         // Truncate the actual output buffer to the original state
         encoder.worker.truncate(cursor);
@@ -1179,6 +1419,27 @@ impl<'buffer, const RFC: usize, const EFC: usize> crate::Encoder<'buffer>
         self.set_extension_presence(true);
         Ok(())
     }
+
+    fn encode_typed_value(
+        &mut self,
+        value: TypedValue<'_>,
+        tag: Tag,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        match value.structure::<EncodeError>(self.codec())? {
+            Structure::Sequence(fields) => self.encode_typed_components(tag, &fields),
+            Structure::Set(mut fields) => {
+                fields.sort_canonically();
+                self.encode_typed_components(tag, &fields)
+            }
+            Structure::Choice(alternative) => self.encode_typed_alternative(tag, &alternative),
+            Structure::Enumerated(enumeration) => self.encode_typed_enumeration(tag, &enumeration),
+            Structure::SequenceOf(items) | Structure::SetOf(items) => {
+                self.encode_sequence_of(tag, &items, constraints, identifier)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1290,4 +1551,57 @@ mod tests {
 
         assert_eq!(encoder.output(), &[128, 2, 1, 77]);
     }
+
+    #[test]
+    fn constrained_integer_fast_path() {
+        use crate::types::{ConstrainedInteger, Integer};
+
+        #[derive(AsnType, Encode)]
+        #[rasn(crate_root = "crate")]
+        struct Fast {
+            a: ConstrainedInteger<-5, 100, i8>,
+            b: ConstrainedInteger<0, 65535, u16>,
+            c: ConstrainedInteger<-40_000, 40_000, i32>,
+            d: ConstrainedInteger<0, 4_294_967_295, u32>,
+        }
+
+        #[derive(AsnType, Encode)]
+        #[rasn(crate_root = "crate")]
+        struct General {
+            #[rasn(value("-5..=100"))]
+            a: Integer,
+            #[rasn(value("0..=65535"))]
+            b: Integer,
+            #[rasn(value("-40000..=40000"))]
+            c: Integer,
+            #[rasn(value("0..=4294967295"))]
+            d: Integer,
+        }
+
+        for (a, b, c, d) in [
+            (-5, 0, -40_000, 0),
+            (100, 65535, 40_000, u32::MAX),
+            (7, 300, -1, 1),
+        ] {
+            let fast = Fast {
+                a: a.try_into().unwrap(),
+                b: b.try_into().unwrap(),
+                c: c.try_into().unwrap(),
+                d: d.try_into().unwrap(),
+            };
+            let general = General {
+                a: a.into(),
+                b: b.into(),
+                c: c.into(),
+                d: d.into(),
+            };
+            assert_eq!(
+                crate::oer::encode(&fast).unwrap(),
+                crate::oer::encode(&general).unwrap()
+            );
+        }
+
+        let max = ConstrainedInteger::<0, 18_446_744_073_709_551_615, u64>::new(u64::MAX);
+        assert_eq!(crate::oer::encode(&max).unwrap(), [0xFF; 8]);
+    }
 }
@@ -0,0 +1,153 @@
+//! Where the Octet Encoding Rules encoder writes its output.
+
+use alloc::vec::Vec;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for alloc::vec::Vec<u8> {}
+    impl Sealed for super::Counter {}
+}
+
+/// The output of an [`Encoder`](super::Encoder), the octets of the encoding
+/// in a `Vec<u8>`, or only their number in a [`Counter`].
+///
+/// Positions are octet offsets from the start of the output, which octets
+/// reserved for the preamble and extension bitmap are overwritten at once
+/// their values are known.
+pub trait Output: sealed::Sealed + core::fmt::Debug + Default + 'static {
+    /// Returns the number of octets written.
+    fn len(&self) -> usize;
+
+    /// Returns whether no octets were written.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the octets written, none when they're only counted.
+    fn octets(&self) -> &[u8];
+
+    /// Appends `octet`.
+    fn push(&mut self, octet: u8);
+
+    /// Appends `octets`.
+    fn extend_from_slice(&mut self, octets: &[u8]);
+
+    /// Appends `count` zero octets.
+    fn extend_zeros(&mut self, count: usize);
+
+    /// Overwrites the octets written at `at` with `octets`.
+    fn overwrite(&mut self, at: usize, octets: &[u8]);
+
+    /// Moves the octets of `other` to the end of the output, leaving `other`
+    /// empty.
+    fn append(&mut self, other: &mut Self);
+
+    /// Splits the output at `at`, returning the octets after it.
+    fn split_off(&mut self, at: usize) -> Self;
+
+    /// Shortens the output to `len` octets.
+    fn truncate(&mut self, len: usize);
+
+    /// Removes every octet.
+    fn clear(&mut self);
+
+    /// Reserves capacity for at least `additional` more octets.
+    fn reserve(&mut self, additional: usize);
+}
+
+impl Output for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn octets(&self) -> &[u8] {
+        self
+    }
+
+    fn push(&mut self, octet: u8) {
+        Vec::push(self, octet);
+    }
+
+    fn extend_from_slice(&mut self, octets: &[u8]) {
+        Vec::extend_from_slice(self, octets);
+    }
+
+    fn extend_zeros(&mut self, count: usize) {
+        self.resize(self.len() + count, 0);
+    }
+
+    fn overwrite(&mut self, at: usize, octets: &[u8]) {
+        self[at..at + octets.len()].copy_from_slice(octets);
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        Vec::append(self, other);
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Vec::split_off(self, at)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len);
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+}
+
+/// Counts the octets of an encoding instead of writing them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counter {
+    len: usize,
+}
+
+impl Output for Counter {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn octets(&self) -> &[u8] {
+        &[]
+    }
+
+    fn push(&mut self, _: u8) {
+        self.len += 1;
+    }
+
+    fn extend_from_slice(&mut self, octets: &[u8]) {
+        self.len += octets.len();
+    }
+
+    fn extend_zeros(&mut self, count: usize) {
+        self.len += count;
+    }
+
+    fn overwrite(&mut self, _: usize, _: &[u8]) {}
+
+    fn append(&mut self, other: &mut Self) {
+        self.len += core::mem::take(&mut other.len);
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len.saturating_sub(at);
+        self.len -= len;
+        Self { len }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn reserve(&mut self, _: usize) {}
+}
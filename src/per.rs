@@ -51,6 +51,18 @@ pub(crate) fn encode<T: crate::Encode>(
     Ok(enc.output())
 }
 
+/// Returns the length of the PER encoding of `value`, without encoding it.
+pub(crate) fn encoded_len<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+) -> Result<usize, crate::error::EncodeError> {
+    let mut enc = crate::per::enc::Encoder::<0, 0>::new_counting(options);
+
+    value.encode(&mut enc)?;
+
+    Ok(enc.encoded_len())
+}
+
 /// Encodes `value` to PER into an existing `buffer`, reusing its allocation.
 /// The buffer is cleared before encoding.
 pub(crate) fn encode_buf<T: crate::Encode>(
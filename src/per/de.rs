@@ -1,8 +1,11 @@
 //! Decoding Packed Encoding Rules data into Rust structures.
 
-use alloc::{borrow::Cow, string::ToString, vec::Vec};
-use bitvec::{field::BitField, view::BitView};
+mod cursor;
 
+use alloc::{borrow::Cow, boxed::Box, string::ToString, vec::Vec};
+use bitvec::view::BitView;
+
+use self::cursor::BitCursor;
 use super::{
     FOURTY_EIGHT_K, LARGE_UNSIGNED_CONSTRAINT, SIXTEEN_K, SIXTY_FOUR_K, SMALL_UNSIGNED_CONSTRAINT,
     THIRTY_TWO_K,
@@ -16,14 +19,16 @@ use crate::{
         fields::{Field, Fields},
         strings::{StaticPermittedAlphabet, should_be_indexed},
     },
+    value::{
+        Addition, Component, Type, Value,
+        typed::{canonical_order, index_constraints},
+    },
 };
 
 pub use crate::error::DecodeError;
 use crate::error::DecodeErrorKind;
 type Result<T, E = DecodeError> = core::result::Result<T, E>;
 
-type InputSlice<'input> = nom_bitvec::BSlice<'input, u8, bitvec::order::Msb0>;
-
 /// Options for configuring the [`Decoder`].
 #[derive(Clone, Copy, Debug)]
 pub struct DecoderOptions {
@@ -64,7 +69,7 @@ impl DecoderOptions {
 
 /// Decodes Packed Encoding Rules (PER) data into Rust data structures.
 pub struct Decoder<'input, const RFC: usize = 0, const EFC: usize = 0> {
-    input: InputSlice<'input>,
+    input: BitCursor<'input>,
     options: DecoderOptions,
     /// Presence bitmap for optional/default root fields. The cursor advances as each
     /// field is consumed; `None` entries mark slots past the filled count.
@@ -75,6 +80,10 @@ pub struct Decoder<'input, const RFC: usize = 0, const EFC: usize = 0> {
     /// `Some(Some((cursor, data)))` = header parsed.
     #[allow(clippy::type_complexity)]
     extensions_present: Option<Option<(usize, [Option<(Field, bool)>; EFC])>>,
+    /// The length of the input in bits when decoding started, which offsets
+    /// are traced from.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    origin: usize,
 }
 
 impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
@@ -88,18 +97,46 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
     #[must_use]
     pub fn new(input: &'input crate::types::BitStr, options: DecoderOptions) -> Self {
         Self {
-            input: input.into(),
+            input: BitCursor::new(input),
             options,
             fields: (0, [None; RFC]),
             extension_fields: None,
             extensions_present: None,
+            origin: input.len(),
+        }
+    }
+
+    /// Creates a decoder for the contents of a constructed value, continuing
+    /// from the current position in the input.
+    fn nested<const RC: usize, const EC: usize>(&self) -> Decoder<'input, RC, EC> {
+        Decoder {
+            origin: self.origin,
+            ..Decoder::new(self.input(), self.options)
         }
     }
 
+    /// Returns the offset in bits of `input` from the start of the input.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn offset(&self, input: BitCursor<'input>) -> usize {
+        self.origin - input.len()
+    }
+
+    /// Passes `length`, decoded from a length determinant or a size
+    /// constraint, to `decode_fn` along with the contents that follow it.
+    fn decode_contents(
+        &self,
+        input: BitCursor<'input>,
+        length: usize,
+        decode_fn: &mut impl FnMut(BitCursor<'input>, usize) -> Result<BitCursor<'input>>,
+    ) -> Result<BitCursor<'input>> {
+        trace_event!(offset = self.offset(input), length, "length");
+        (decode_fn)(input, length)
+    }
+
     /// Returns the remaining input, if any.
     #[must_use]
     pub fn input(&self) -> &'input crate::types::BitStr {
-        self.input.0
+        self.input.as_bitslice()
     }
 
     #[track_caller]
@@ -121,11 +158,18 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
     fn parse_extensible_bit(&mut self, constraints: &Constraints) -> Result<bool> {
         constraints
             .extensible()
-            .then(|| self.parse_one_bit())
+            .then(|| self.parse_extension_bit())
             .transpose()
             .map(core::option::Option::unwrap_or_default)
     }
 
+    /// Parses the bit marking whether a value of an extensible type is
+    /// outside of the root of the type.
+    fn parse_extension_bit(&mut self) -> Result<bool> {
+        trace_event!(offset = self.offset(self.input), "extension bit");
+        self.parse_one_bit()
+    }
+
     fn extension_is_present(&mut self) -> Result<Option<(Field, bool)>> {
         let codec = self.codec();
         let inner = self
@@ -142,7 +186,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         Ok(result)
     }
 
-    fn parse_padding(&self, input: InputSlice<'input>) -> Result<InputSlice<'input>> {
+    fn parse_padding(&self, input: BitCursor<'input>) -> Result<BitCursor<'input>> {
         if self.options.aligned {
             self.force_parse_padding(input)
         } else {
@@ -150,24 +194,23 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         }
     }
 
-    fn force_parse_padding(&self, input: InputSlice<'input>) -> Result<InputSlice<'input>> {
+    fn force_parse_padding(&self, input: BitCursor<'input>) -> Result<BitCursor<'input>> {
         if input.len().is_multiple_of(8) {
             Ok(input)
         } else {
-            let (input, _) = nom::bytes::streaming::take(input.len() % 8)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-            Ok(input)
+            input.skip(input.len() % 8, self.codec())
         }
     }
 
     fn parse_optional_and_default_field_bitmap<const RC: usize>(
         &mut self,
         fields: &Fields<RC>,
-    ) -> Result<InputSlice<'input>> {
-        let (input, bitset) =
-            nom::bytes::streaming::take(fields.number_of_optional_and_default_fields())(self.input)
-                .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
+    ) -> Result<&'input types::BitStr> {
+        let (input, bitset) = self
+            .input
+            .take(fields.number_of_optional_and_default_fields(), self.codec())?;
 
+        trace_event!(offset = self.offset(self.input), bitmap = ?bitset, "preamble");
         self.input = input;
         Ok(bitset)
     }
@@ -176,7 +219,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         &mut self,
         constraints: &Constraints,
         is_large_string: bool,
-        mut decode_fn: impl FnMut(InputSlice<'input>, usize) -> Result<InputSlice<'input>>,
+        mut decode_fn: impl FnMut(BitCursor<'input>, usize) -> Result<BitCursor<'input>>,
     ) -> Result<()> {
         let extensible_is_present = self.parse_extensible_bit(constraints)?;
         let constraints = constraints.size().filter(|_| !extensible_is_present);
@@ -189,7 +232,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
     fn decode_extensible_container(
         &mut self,
         constraints: Constraints,
-        mut decode_fn: impl FnMut(InputSlice<'input>, usize) -> Result<InputSlice<'input>>,
+        mut decode_fn: impl FnMut(BitCursor<'input>, usize) -> Result<BitCursor<'input>>,
     ) -> Result<()> {
         let extensible_is_present = self.parse_extensible_bit(&constraints)?;
         let constraints = constraints.size().filter(|_| !extensible_is_present);
@@ -198,15 +241,12 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         Ok(())
     }
 
-    fn decode_octets(&mut self) -> Result<types::BitString> {
-        let mut buffer = types::BitString::default();
+    fn decode_octets(&mut self) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
         let codec = self.codec();
 
         let input = self.decode_length(self.input, <_>::default(), &mut |input, length| {
-            let (input, data) = nom::bytes::streaming::take(length * 8)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, codec))?;
-            buffer.extend(&*data);
-            Ok(input)
+            input.read_octets_into(length, &mut buffer, codec)
         })?;
 
         self.input = input;
@@ -215,31 +255,24 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
 
     fn decode_unknown_length(
         &mut self,
-        mut input: InputSlice<'input>,
-        decode_fn: &mut impl FnMut(InputSlice<'input>, usize) -> Result<InputSlice<'input>>,
-    ) -> Result<InputSlice<'input>> {
+        mut input: BitCursor<'input>,
+        decode_fn: &mut impl FnMut(BitCursor<'input>, usize) -> Result<BitCursor<'input>>,
+    ) -> Result<BitCursor<'input>> {
         input = self.parse_padding(input)?;
-        let (input, mask) = nom::bytes::streaming::take(1u8)(input)
-            .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-
-        if !mask[0] {
-            let (input, length) = nom::bytes::streaming::take(7u8)(input)
-                .map(|(i, bs)| (i, bs.to_bitvec()))
-                .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-            (decode_fn)(input, length.load_be::<usize>())
+        let (input, mask) = input.read_bit(self.codec())?;
+
+        if !mask {
+            let (input, length) = input.read_bits(7, self.codec())?;
+            self.decode_contents(input, length as usize, decode_fn)
         } else {
-            let (input, mask) = nom::bytes::streaming::take(1u8)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-
-            if !mask[0] {
-                let (input, length) = nom::bytes::streaming::take(14u8)(input)
-                    .map(|(i, bs)| (i, bs.to_bitvec()))
-                    .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-                (decode_fn)(input, length.load_be::<usize>())
+            let (input, mask) = input.read_bit(self.codec())?;
+
+            if !mask {
+                let (input, length) = input.read_bits(14, self.codec())?;
+                self.decode_contents(input, length as usize, decode_fn)
             } else {
-                let (input, mask) = nom::bytes::streaming::take(6u8)(input)
-                    .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-                let length: usize = match mask.load_be::<u8>() {
+                let (input, mask) = input.read_bits(6, self.codec())?;
+                let length: usize = match mask {
                     1 => SIXTEEN_K.into(),
                     2 => THIRTY_TWO_K.into(),
                     3 => FOURTY_EIGHT_K.into(),
@@ -252,7 +285,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
                     }
                 };
 
-                let mut input = (decode_fn)(input, length)?;
+                let mut input = self.decode_contents(input, length, decode_fn)?;
 
                 loop {
                     let new_input = self.decode_length(input, <_>::default(), decode_fn)?;
@@ -260,7 +293,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
                     if input.len() == new_input.len() || new_input.is_empty() {
                         break;
                     } else {
-                        input = (decode_fn)(new_input, length)?;
+                        input = self.decode_contents(new_input, length, decode_fn)?;
                     }
                 }
 
@@ -271,11 +304,11 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
 
     fn decode_string_length(
         &mut self,
-        mut input: InputSlice<'input>,
+        mut input: BitCursor<'input>,
         constraints: Option<&Extensible<constraints::Size>>,
         is_large_string: bool,
-        decode_fn: &mut impl FnMut(InputSlice<'input>, usize) -> Result<InputSlice<'input>>,
-    ) -> Result<InputSlice<'input>> {
+        decode_fn: &mut impl FnMut(BitCursor<'input>, usize) -> Result<BitCursor<'input>>,
+    ) -> Result<BitCursor<'input>> {
         let Some(constraints) = constraints else {
             return self.decode_unknown_length(input, decode_fn);
         };
@@ -291,7 +324,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
                 if self.options.aligned {
                     input = self.parse_padding(input)?;
                 }
-                (decode_fn)(input, size_constraint.minimum())
+                self.decode_contents(input, size_constraint.minimum(), decode_fn)
             } else {
                 let range = if self.options.aligned && range > 256 {
                     input = self.parse_padding(input)?;
@@ -306,16 +339,14 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
                 };
 
                 let (mut input, length) =
-                    nom::bytes::streaming::take(crate::num::log2(range))(input)
-                        .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
+                    input.read_bits(crate::num::log2(range) as usize, self.codec())?;
                 if is_large_string {
                     input = self.parse_padding(input)?;
                 }
-                length
-                    .load_be::<usize>()
+                (length as usize)
                     .checked_add(size_constraint.minimum())
                     .ok_or_else(|| DecodeError::exceeds_max_length(usize::MAX.into(), self.codec()))
-                    .and_then(|sum| (decode_fn)(input, sum))
+                    .and_then(|sum| self.decode_contents(input, sum, decode_fn))
             }
         } else {
             self.decode_unknown_length(input, decode_fn)
@@ -324,10 +355,10 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
 
     fn decode_length(
         &mut self,
-        mut input: InputSlice<'input>,
+        mut input: BitCursor<'input>,
         constraints: Option<&Extensible<constraints::Size>>,
-        decode_fn: &mut impl FnMut(InputSlice<'input>, usize) -> Result<InputSlice<'input>>,
-    ) -> Result<InputSlice<'input>> {
+        decode_fn: &mut impl FnMut(BitCursor<'input>, usize) -> Result<BitCursor<'input>>,
+    ) -> Result<BitCursor<'input>> {
         let Some(constraints) = constraints else {
             return self.decode_unknown_length(input, decode_fn);
         };
@@ -340,7 +371,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
             if range == 0 {
                 Ok(input)
             } else if range == 1 {
-                (decode_fn)(input, size_constraint.minimum())
+                self.decode_contents(input, size_constraint.minimum(), decode_fn)
             } else {
                 let range = if self.options.aligned && range > 256 {
                     input = self.parse_padding(input)?;
@@ -355,14 +386,12 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
                 };
 
                 let (mut input, length) =
-                    nom::bytes::streaming::take(crate::num::log2(range))(input)
-                        .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
+                    input.read_bits(crate::num::log2(range) as usize, self.codec())?;
                 input = self.parse_padding(input)?;
-                length
-                    .load_be::<usize>()
+                (length as usize)
                     .checked_add(size_constraint.minimum())
                     .ok_or_else(|| DecodeError::exceeds_max_length(usize::MAX.into(), self.codec()))
-                    .and_then(|sum| (decode_fn)(input, sum))
+                    .and_then(|sum| self.decode_contents(input, sum, decode_fn))
             }
         } else {
             self.decode_unknown_length(input, decode_fn)
@@ -370,10 +399,9 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
     }
 
     fn parse_one_bit(&mut self) -> Result<bool> {
-        let (input, boolean) = nom::bytes::streaming::take(1u8)(self.input)
-            .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
+        let (input, boolean) = self.input.read_bit(self.codec())?;
         self.input = input;
-        Ok(boolean[0])
+        Ok(boolean)
     }
 
     fn parse_normally_small_integer<I: IntegerType>(&mut self) -> Result<I> {
@@ -410,18 +438,24 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         &mut self,
         bits: usize,
     ) -> Result<I> {
-        let (input, data) = nom::bytes::streaming::take(bits)(self.input)
-            .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
-        self.input = input;
         if bits == 0 {
             return Ok(I::ZERO);
         }
         let needed_bytes = bits.div_ceil(8);
+        if bits <= 64 {
+            // Load the whole field at once rather than copying it bit by bit.
+            let (input, value) = self.input.read_bits(bits, self.codec())?;
+            self.input = input;
+            let bytes = value.to_be_bytes();
+            return I::try_from_unsigned_bytes(&bytes[8 - needed_bytes..], self.codec());
+        }
+        let (input, data) = self.input.take(bits, self.codec())?;
+        self.input = input;
         let pad_bits = needed_bytes * 8 - bits;
         let mut buf = [0u8; 16];
         {
             let buf_bits = buf[16 - needed_bytes..].view_bits_mut::<bitvec::order::Msb0>();
-            buf_bits[pad_bits..].copy_from_bitslice(&data);
+            buf_bits[pad_bits..].copy_from_bitslice(data);
         }
         I::try_from_unsigned_bytes(&buf[16 - needed_bytes..], self.codec())
     }
@@ -432,7 +466,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
 
         let Some(value_constraint) = value_constraint.filter(|_| !extensible) else {
             let bytes = &self.decode_octets()?;
-            return I::try_from_bytes(bytes.as_raw_slice(), self.codec());
+            return I::try_from_bytes(bytes, self.codec());
         };
 
         const K64: i128 = SIXTY_FOUR_K as i128;
@@ -486,8 +520,8 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         } else {
             let bytes = &self.decode_octets()?;
             let number = value_constraint.constraint.as_start().map_or_else(
-                || I::try_from_signed_bytes(bytes.as_raw_slice(), self.codec()),
-                |_| I::try_from_unsigned_bytes(bytes.as_raw_slice(), self.codec()),
+                || I::try_from_signed_bytes(bytes, self.codec()),
+                |_| I::try_from_unsigned_bytes(bytes, self.codec()),
             )?;
 
             return minimum
@@ -506,8 +540,8 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
 
         // The length bitfield has a lower bound of `1..`
         let extensions_length = self.parse_normally_small_length()? + 1;
-        let (input, bitfield) = nom::bytes::streaming::take(extensions_length)(self.input)
-            .map_err(|e| DecodeError::map_nom_err(e, self.codec()))?;
+        let (input, bitfield) = self.input.take(extensions_length, self.codec())?;
+        trace_event!(offset = self.offset(self.input), bitmap = ?bitfield, "extension bitmap");
         self.input = input;
 
         let mut data = [None; EFC];
@@ -549,6 +583,174 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
         Ok(())
     }
 
+    /// Decodes a value of a constructed or `ENUMERATED` type only known at
+    /// runtime.
+    fn decode_typed_contents(&mut self, ty: &Type, constraints: Constraints) -> Result<Value> {
+        let codec = self.codec();
+        Ok(match ty {
+            Type::Sequence {
+                components,
+                extensions,
+            }
+            | Type::Set {
+                components,
+                extensions,
+            } => {
+                let is_extended = extensions.is_some() && self.parse_extension_bit()?;
+                let mut values =
+                    self.decode_typed_components(components, matches!(ty, Type::Set { .. }))?;
+                let additions = extensions.as_deref().unwrap_or_default();
+                if is_extended {
+                    let length = self.parse_normally_small_length()? + 1;
+                    let (input, bitmap) = self.input.take(length, codec)?;
+                    trace_event!(offset = self.offset(self.input), bitmap = ?bitmap, "extension bitmap");
+                    self.input = input;
+                    for (index, is_present) in bitmap.iter().map(|bit| *bit).enumerate() {
+                        // Additions of later versions of the type are skipped.
+                        let bytes = if is_present {
+                            self.decode_octets()?
+                        } else {
+                            Vec::new()
+                        };
+                        let Some(addition) = additions.get(index) else {
+                            continue;
+                        };
+                        if !is_present {
+                            values.extend(addition.components().iter().map(|_| None));
+                            continue;
+                        }
+                        let mut decoder = self.open_type(&bytes);
+                        match addition {
+                            Addition::Component(component) => {
+                                values.push(Some(component.ty.decode_value(
+                                    &mut decoder,
+                                    None,
+                                    Constraints::default(),
+                                )?))
+                            }
+                            Addition::Group(components) => {
+                                values.extend(decoder.decode_typed_components(components, false)?);
+                            }
+                        }
+                    }
+                }
+                ty.components_value(values, codec)?
+            }
+            Type::Choice {
+                alternatives,
+                extensions,
+            } => {
+                let is_extension = extensions.is_some() && self.parse_extension_bit()?;
+                let (alternative, value) = if is_extension {
+                    let index = self.parse_normally_small_integer::<usize>()?;
+                    trace_event!(index, extended = true, "choice index");
+                    let alternative = ty.alternative_at(index, true, codec)?;
+                    let bytes = self.decode_octets()?;
+                    let value = alternative.ty.decode_value(
+                        &mut self.open_type(&bytes),
+                        None,
+                        Constraints::default(),
+                    )?;
+                    (alternative, value)
+                } else {
+                    let index = if alternatives.len() > 1 {
+                        self.parse_integer::<usize>(index_constraints(alternatives.len()))?
+                    } else {
+                        0
+                    };
+                    trace_event!(index, extended = false, "choice index");
+                    let alternative = ty.alternative_at(index, false, codec)?;
+                    let value = alternative
+                        .ty
+                        .decode_value(self, None, Constraints::default())?;
+                    (alternative, value)
+                };
+                Value::Choice(alternative.name.clone(), Box::new(value))
+            }
+            Type::Enumerated {
+                enumerations,
+                extensions,
+            } => {
+                let is_extension = extensions.is_some() && self.parse_extension_bit()?;
+                let index = if is_extension {
+                    self.parse_normally_small_integer::<usize>()?
+                } else {
+                    self.parse_non_negative_binary_integer::<usize>(enumerations.len() as i128)?
+                };
+                trace_event!(index, extended = is_extension, "enumeration index");
+                ty.enumeration_at(index, is_extension, codec)?
+            }
+            Type::SequenceOf(item) | Type::SetOf(item) => {
+                let mut items = Vec::new();
+                let options = self.options;
+                let origin = self.origin;
+                self.decode_extensible_container(constraints, |mut input, length| {
+                    for _ in 0..length {
+                        let mut decoder = Self::new(input.as_bitslice(), options);
+                        decoder.origin = origin;
+                        items.push(item.decode_value(
+                            &mut decoder,
+                            None,
+                            Constraints::default(),
+                        )?);
+                        input = decoder.input;
+                    }
+                    Ok(input)
+                })?;
+                match ty {
+                    Type::SetOf(_) => Value::SetOf(SetOf::from_vec(items)),
+                    _ => Value::SequenceOf(items),
+                }
+            }
+            _ => {
+                return Err(DecodeError::custom(
+                    alloc::format!("{} is not a constructed type", ty.name()),
+                    codec,
+                ));
+            }
+        })
+    }
+
+    /// Decodes the presence bitmap and the present components of a
+    /// `SEQUENCE` or `SET` value, or of an extension addition group, of a type
+    /// only known at runtime, in declaration order.
+    fn decode_typed_components(
+        &mut self,
+        components: &[Component],
+        is_set: bool,
+    ) -> Result<Vec<Option<Value>>> {
+        let optional = components.iter().filter(|c| c.is_optional()).count();
+        let (input, bitmap) = self.input.take(optional, self.codec())?;
+        trace_event!(offset = self.offset(self.input), bitmap = ?bitmap, "preamble");
+        self.input = input;
+
+        let order = if is_set {
+            canonical_order(components)
+        } else {
+            (0..components.len()).collect()
+        };
+        let mut presence = bitmap.iter().map(|bit| *bit);
+        let mut values: Vec<Option<Value>> = components.iter().map(|_| None).collect();
+        for index in order {
+            let component = &components[index];
+            if !component.is_optional() || presence.next().unwrap_or_default() {
+                values[index] = Some(component.ty.decode_value(
+                    self,
+                    None,
+                    Constraints::default(),
+                )?);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Creates a decoder for the contents of an open type.
+    fn open_type<'bytes>(&self, bytes: &'bytes [u8]) -> Decoder<'bytes, 0, 0> {
+        let mut decoder = Decoder::new(types::BitStr::from_slice(bytes), self.options);
+        decoder.options.remaining_depth = decoder.options.remaining_depth.saturating_sub(1);
+        decoder
+    }
+
     #[allow(clippy::too_many_lines)]
     fn parse_fixed_width_string<ALPHABET: StaticPermittedAlphabet>(
         &mut self,
@@ -621,9 +823,8 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
                 return Ok(input);
             }
 
-            let (input, part) = nom::bytes::streaming::take(length * char_width)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, codec))?;
-            bit_string.extend(&*part);
+            let (input, part) = input.take(length * char_width, codec)?;
+            bit_string.extend_from_bitslice(part);
             Ok(input)
         })?;
 
@@ -631,7 +832,7 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
             constraints.permitted_alphabet(),
             should_be_indexed(
                 ALPHABET::CHARACTER_SET_WIDTH as u32,
-                ALPHABET::CHARACTER_SET,
+                &ALPHABET::CHARACTER_SET,
             ),
             constraints.permitted_alphabet().map(|alphabet| {
                 ALPHABET::CHARACTER_SET_WIDTH
@@ -651,21 +852,17 @@ impl<'input, const RFC: usize, const EFC: usize> Decoder<'input, RFC, EFC> {
             }),
         ) {
             (Some(alphabet), true, _) | (Some(alphabet), _, Some(true)) => {
-                if alphabet.constraint.len() == 1 {
+                if let (1, Some(character)) = (
+                    alphabet.constraint.len(),
+                    alphabet.constraint.character_at(0),
+                ) {
                     let mut string = ALPHABET::default();
                     for _ in 0..total_length {
-                        string.push_char(alphabet.constraint[0]);
+                        string.push_char(character);
                     }
                     Ok(string)
                 } else {
-                    let map = alphabet
-                        .constraint
-                        .iter()
-                        .copied()
-                        .enumerate()
-                        .map(|(i, e)| (i as u32, e))
-                        .collect();
-                    ALPHABET::try_from_permitted_alphabet(bit_string, Some(&map))
+                    ALPHABET::try_from_permitted_alphabet(bit_string, Some(&alphabet.constraint))
                         .map_err(|e| DecodeError::permitted_alphabet_error(e, self.codec()))
                 }
             }
@@ -702,17 +899,14 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         Self::codec(self)
     }
     fn decode_any(&mut self, _tag: Tag) -> Result<types::Any> {
-        let mut octet_string = types::BitString::default();
+        let mut octet_string = Vec::new();
         let codec = self.codec();
 
         self.decode_extensible_container(Constraints::default(), |input, length| {
-            let (input, part) = nom::bytes::streaming::take(length * 8)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, codec))?;
-            octet_string.extend(&*part);
-            Ok(input)
+            input.read_octets_into(length, &mut octet_string, codec)
         })?;
 
-        Ok(types::Any::new(octet_string.as_raw_slice().to_vec()))
+        Ok(types::Any::with_codec(octet_string, codec))
     }
 
     fn decode_bool(&mut self, _: Tag) -> Result<bool> {
@@ -720,18 +914,25 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
     }
 
     fn decode_enumerated<E: Enumerated>(&mut self, _: Tag) -> Result<E> {
+        trace_span!(
+            "enumerated",
+            ty = core::any::type_name::<E>(),
+            offset = self.offset(self.input)
+        );
         let extensible = E::EXTENDED_VARIANTS
             .is_some()
-            .then(|| self.parse_one_bit())
+            .then(|| self.parse_extension_bit())
             .transpose()?
             .unwrap_or_default();
 
         if extensible {
             let index: usize = self.parse_normally_small_integer()?;
+            trace_event!(index, extended = true, "enumeration index");
             E::from_extended_enumeration_index(index)
                 .ok_or_else(|| DecodeError::enumeration_index_not_found(index, true, self.codec()))
         } else {
             let index = self.parse_non_negative_binary_integer::<usize>(E::variance() as i128)?;
+            trace_event!(index, extended = false, "enumeration index");
             E::from_enumeration_index(index)
                 .ok_or_else(|| DecodeError::enumeration_index_not_found(index, false, self.codec()))
         }
@@ -742,6 +943,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         _: Tag,
         constraints: Constraints,
     ) -> Result<I> {
+        trace_span!("integer", offset = self.offset(self.input));
         self.parse_integer::<I>(constraints)
     }
 
@@ -758,19 +960,22 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         _: Tag,
         constraints: Constraints,
     ) -> Result<T> {
-        let mut octet_string = Vec::new();
+        let mut octet_string: Option<Cow<'input, [u8]>> = None;
         let codec = self.codec();
 
         self.decode_extensible_container(constraints, |input, length| {
-            let (input, part) = nom::bytes::streaming::take(length * 8)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, codec))?;
-
-            let mut bytes = part.to_bitvec();
-            bytes.force_align();
-            octet_string.extend_from_slice(bytes.as_raw_slice());
+            let (input, part) = input.read_octets(length, codec)?;
+            match &mut octet_string {
+                Some(octet_string) => octet_string.to_mut().extend_from_slice(&part),
+                None => octet_string = Some(part),
+            }
             Ok(input)
         })?;
-        Ok(T::from(octet_string))
+
+        Ok(match octet_string.unwrap_or_default() {
+            Cow::Borrowed(octets) => T::from(octets),
+            Cow::Owned(octets) => T::from(octets),
+        })
     }
 
     fn decode_null(&mut self, _: Tag) -> Result<()> {
@@ -778,7 +983,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
     }
 
     fn decode_object_identifier(&mut self, _: Tag) -> Result<crate::types::ObjectIdentifier> {
-        let octets = self.decode_octets()?.into_vec();
+        let octets = self.decode_octets()?;
         let decoder = crate::ber::de::Decoder::new(&octets, crate::ber::de::DecoderOptions::ber());
         decoder.decode_object_identifier_from_bytes(&octets)
     }
@@ -788,9 +993,8 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         let codec = self.codec();
 
         self.decode_extensible_container(constraints, |input, length| {
-            let (input, part) = nom::bytes::streaming::take(length)(input)
-                .map_err(|e| DecodeError::map_nom_err(e, codec))?;
-            bit_string.extend(&*part);
+            let (input, part) = input.take(length, codec)?;
+            bit_string.extend_from_bitslice(part);
             Ok(input)
         })?;
 
@@ -837,12 +1041,8 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         todo!()
     }
 
-    fn decode_utf8_string(
-        &mut self,
-        tag: Tag,
-        constraints: Constraints,
-    ) -> Result<types::Utf8String> {
-        self.decode_octet_string(tag, constraints)
+    fn decode_utf8_string(&mut self, tag: Tag, _: Constraints) -> Result<types::Utf8String> {
+        self.decode_octet_string(tag, Constraints::default())
             .and_then(|bytes| {
                 alloc::string::String::from_utf8(bytes).map_err(|e| {
                     DecodeError::string_conversion_failed(
@@ -908,14 +1108,21 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         constraints: Constraints,
     ) -> Result<Vec<D>, Self::Error> {
         self.check_recursion_depth()?;
+        trace_span!(
+            "sequence of",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset(self.input)
+        );
         let mut sequence_of = Vec::new();
         let mut options = self.options;
         options.remaining_depth = options.remaining_depth.saturating_sub(1);
+        let origin = self.origin;
         self.decode_extensible_container(constraints, |mut input, length| {
             sequence_of.append(
                 &mut (0..length)
                     .map(|_| {
-                        let mut decoder = Self::new(input.0, options);
+                        let mut decoder = Self::new(input.as_bitslice(), options);
+                        decoder.origin = origin;
                         let value = D::decode(&mut decoder)?;
                         input = decoder.input;
                         Ok(value)
@@ -929,7 +1136,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         Ok(sequence_of)
     }
 
-    fn decode_set_of<D: Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: Decode>(
         &mut self,
         tag: Tag,
         constraints: Constraints,
@@ -950,15 +1157,20 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         F: FnOnce(&mut Self::AnyDecoder<RC, EC>) -> Result<D, Self::Error>,
     {
         self.check_recursion_depth()?;
+        trace_span!(
+            "sequence",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset(self.input)
+        );
 
         let is_extensible = D::IS_EXTENSIBLE
-            .then(|| self.parse_one_bit())
+            .then(|| self.parse_extension_bit())
             .transpose()?
             .unwrap_or_default();
         let bitmap = self.parse_optional_and_default_field_bitmap(&D::FIELDS)?;
 
         let value = {
-            let mut sequence_decoder = Decoder::new(self.input(), self.options);
+            let mut sequence_decoder = self.nested();
             sequence_decoder.options.remaining_depth =
                 sequence_decoder.options.remaining_depth.saturating_sub(1);
             sequence_decoder.extension_fields = D::EXTENDED_FIELDS;
@@ -1009,9 +1221,14 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         F: FnOnce(Vec<FIELDS>) -> Result<SET, Self::Error>,
     {
         self.check_recursion_depth()?;
+        trace_span!(
+            "set",
+            ty = core::any::type_name::<SET>(),
+            offset = self.offset(self.input)
+        );
 
         let is_extensible = SET::IS_EXTENSIBLE
-            .then(|| self.parse_one_bit())
+            .then(|| self.parse_extension_bit())
             .transpose()?
             .unwrap_or_default();
 
@@ -1031,7 +1248,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
 
         let fields = {
             let mut fields = Vec::new();
-            let mut set_decoder = Decoder::new(self.input(), self.options);
+            let mut set_decoder = self.nested();
             set_decoder.options.remaining_depth =
                 set_decoder.options.remaining_depth.saturating_sub(1);
             set_decoder.extension_fields = SET::EXTENDED_FIELDS;
@@ -1123,6 +1340,11 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         D: crate::types::DecodeChoice,
     {
         self.check_recursion_depth()?;
+        trace_span!(
+            "choice",
+            ty = core::any::type_name::<D>(),
+            offset = self.offset(self.input)
+        );
         let is_extensible = self.parse_extensible_bit(&constraints)?;
         let variants = crate::types::variants::Variants::from_static(if is_extensible {
             D::EXTENDED_VARIANTS.unwrap_or(&[])
@@ -1154,6 +1376,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         } else {
             0
         };
+        trace_event!(index, extended = is_extensible, "choice index");
 
         let tag = variants.get(index).ok_or_else(|| {
             DecodeError::choice_index_not_found(index, variants.clone(), self.codec())
@@ -1161,7 +1384,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
 
         if is_extensible {
             let bytes = self.decode_octets()?;
-            let mut decoder = Decoder::<0, 0>::new(&bytes, self.options);
+            let mut decoder = Decoder::<0, 0>::new(types::BitStr::from_slice(&bytes), self.options);
             decoder.options.remaining_depth = decoder.options.remaining_depth.saturating_sub(1);
             D::from_tag(&mut decoder, *tag)
         } else {
@@ -1193,7 +1416,7 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         }
 
         let bytes = self.decode_octets()?;
-        let mut decoder = Decoder::<RC, EC>::new(&bytes, self.options);
+        let mut decoder = Decoder::<RC, EC>::new(types::BitStr::from_slice(&bytes), self.options);
 
         D::decode(&mut decoder).map(Some)
     }
@@ -1231,10 +1454,24 @@ impl<'input, const RFC: usize, const EFC: usize> crate::Decoder for Decoder<'inp
         }
 
         let bytes = self.decode_octets()?;
-        let mut decoder = Decoder::<0, 0>::new(&bytes, self.options);
+        let mut decoder = Decoder::<0, 0>::new(types::BitStr::from_slice(&bytes), self.options);
 
         D::decode_with_constraints(&mut decoder, constraints).map(Some)
     }
+
+    fn decode_typed_value(&mut self, ty: &Type, _: Tag, constraints: Constraints) -> Result<Value> {
+        self.check_recursion_depth()?;
+        trace_span!(
+            "typed value",
+            ty = ty.name(),
+            offset = self.offset(self.input)
+        );
+        let mut decoder = self.nested::<0, 0>();
+        decoder.options.remaining_depth = decoder.options.remaining_depth.saturating_sub(1);
+        let value = decoder.decode_typed_contents(ty, constraints)?;
+        self.input = decoder.input;
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
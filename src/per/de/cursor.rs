@@ -0,0 +1,257 @@
+//! A cursor over the unread bits of Packed Encoding Rules input.
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use bitvec::domain::Domain;
+
+use crate::{
+    Codec,
+    de::Error as _,
+    error::{DecodeError, DecodeErrorKind},
+    types::BitStr,
+};
+
+type Result<T, E = DecodeError> = core::result::Result<T, E>;
+
+/// The unread bits of the input. Reads split bits off the front and return
+/// the cursor for the rest, so a cursor can be copied to look ahead or to
+/// hand the same position to a nested decoder.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct BitCursor<'input> {
+    bits: &'input BitStr,
+}
+
+impl<'input> BitCursor<'input> {
+    pub(super) fn new(bits: &'input BitStr) -> Self {
+        Self { bits }
+    }
+
+    pub(super) fn as_bitslice(self) -> &'input BitStr {
+        self.bits
+    }
+
+    pub(super) fn len(self) -> usize {
+        self.bits.len()
+    }
+
+    pub(super) fn is_empty(self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Splits off the next `bits` bits.
+    pub(super) fn take(self, bits: usize, codec: Codec) -> Result<(Self, &'input BitStr)> {
+        if bits > self.len() {
+            return Err(DecodeError::from_kind(
+                DecodeErrorKind::Incomplete {
+                    needed: nom::Needed::new(bits - self.len()),
+                },
+                codec,
+            ));
+        }
+        let (taken, rest) = self.bits.split_at(bits);
+        Ok((Self::new(rest), taken))
+    }
+
+    /// Skips the next `bits` bits.
+    pub(super) fn skip(self, bits: usize, codec: Codec) -> Result<Self> {
+        self.take(bits, codec).map(|(rest, _)| rest)
+    }
+
+    pub(super) fn read_bit(self, codec: Codec) -> Result<(Self, bool)> {
+        self.take(1, codec).map(|(rest, bit)| (rest, bit[0]))
+    }
+
+    /// Reads the next `bits` bits, at most 64, as a big-endian unsigned
+    /// integer.
+    pub(super) fn read_bits(self, bits: usize, codec: Codec) -> Result<(Self, u64)> {
+        debug_assert!(bits <= 64);
+        let (rest, taken) = self.take(bits, codec)?;
+        let mut value = 0u64;
+        for_each_chunk(taken, |chunk, width| {
+            value = (value << width) | u64::from(chunk);
+        });
+        Ok((rest, value))
+    }
+
+    /// Reads the next `octets` octets, borrowing them from the input when
+    /// they are octet-aligned, as in APER.
+    pub(super) fn read_octets(
+        self,
+        octets: usize,
+        codec: Codec,
+    ) -> Result<(Self, Cow<'input, [u8]>)> {
+        let (rest, taken) = self.take_octets(octets, codec)?;
+        let octets = match aligned_octets(taken) {
+            Some(octets) => Cow::Borrowed(octets),
+            None => {
+                let mut buffer = Vec::with_capacity(octets);
+                extend_from_unaligned_octets(&mut buffer, taken);
+                Cow::Owned(buffer)
+            }
+        };
+        Ok((rest, octets))
+    }
+
+    /// Appends the next `octets` octets to `buffer`.
+    pub(super) fn read_octets_into(
+        self,
+        octets: usize,
+        buffer: &mut Vec<u8>,
+        codec: Codec,
+    ) -> Result<Self> {
+        let (rest, taken) = self.take_octets(octets, codec)?;
+        match aligned_octets(taken) {
+            Some(octets) => buffer.extend_from_slice(octets),
+            None => extend_from_unaligned_octets(buffer, taken),
+        }
+        Ok(rest)
+    }
+
+    fn take_octets(self, octets: usize, codec: Codec) -> Result<(Self, &'input BitStr)> {
+        let bits = octets
+            .checked_mul(8)
+            .ok_or_else(|| DecodeError::exceeds_max_length(usize::MAX.into(), codec))?;
+        self.take(bits, codec)
+    }
+}
+
+/// Returns the bytes of `bits` if it starts and ends on a byte boundary.
+fn aligned_octets(bits: &BitStr) -> Option<&[u8]> {
+    match bits.domain() {
+        Domain::Region {
+            head: None,
+            body,
+            tail: None,
+        } => Some(body),
+        _ if bits.is_empty() => Some(&[]),
+        _ => None,
+    }
+}
+
+/// Appends the whole octets in `bits`, which starts inside a byte, so that
+/// every octet is the end of one input byte followed by the start of the
+/// next.
+fn extend_from_unaligned_octets(buffer: &mut Vec<u8>, bits: &BitStr) {
+    let Domain::Region {
+        head: Some(head),
+        body,
+        tail: Some(tail),
+    } = bits.domain()
+    else {
+        unreachable!("unaligned octets straddle input bytes");
+    };
+    let shift = head.head().into_inner();
+    buffer.reserve(bits.len() / 8);
+    let mut previous = head.load_value();
+    for &byte in body {
+        buffer.push((previous << shift) | (byte >> (8 - shift)));
+        previous = byte;
+    }
+    buffer.push((previous << shift) | (tail.load_value() >> (8 - shift)));
+}
+
+/// Passes the bits of `bits` in order to `push` as `(value, width)` pairs of
+/// at most a byte each, right-aligned in `value`.
+fn for_each_chunk(bits: &BitStr, mut push: impl FnMut(u8, u32)) {
+    match bits.domain() {
+        Domain::Enclave(element) => {
+            let end = element.tail().into_inner();
+            let width = end - element.head().into_inner();
+            push(element.load_value() >> (8 - end), u32::from(width));
+        }
+        Domain::Region { head, body, tail } => {
+            if let Some(element) = head {
+                push(
+                    element.load_value(),
+                    u32::from(8 - element.head().into_inner()),
+                );
+            }
+            for &byte in body {
+                push(byte, 8);
+            }
+            if let Some(element) = tail {
+                let end = element.tail().into_inner();
+                push(element.load_value() >> (8 - end), u32::from(end));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn read_bits() {
+        let input = bits![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 1, 1, 0, 1];
+        for start in 0..input.len() {
+            for bits in 0..=input.len() - start {
+                let (rest, value) = BitCursor::new(&input[start..])
+                    .read_bits(bits, Codec::Uper)
+                    .unwrap();
+                let expected = match bits {
+                    0 => 0,
+                    _ => input[start..start + bits].load_be::<u64>(),
+                };
+                assert_eq!(value, expected);
+                assert_eq!(rest.len(), input.len() - start - bits);
+            }
+        }
+
+        let error = BitCursor::new(&input[10..])
+            .read_bits(8, Codec::Uper)
+            .unwrap_err();
+        assert!(matches!(
+            *error.kind,
+            DecodeErrorKind::Incomplete { needed } if needed == nom::Needed::new(1)
+        ));
+    }
+
+    #[test]
+    fn read_octets_into() {
+        let bits = bits![u8, Msb0; 1, 1, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+
+        let mut aligned = Vec::new();
+        BitCursor::new(&bits[..16])
+            .read_octets_into(2, &mut aligned, Codec::Aper)
+            .unwrap();
+        assert_eq!(aligned, [0xC7, 0x7F]);
+
+        let mut unaligned = Vec::new();
+        let rest = BitCursor::new(&bits[2..])
+            .read_octets_into(2, &mut unaligned, Codec::Uper)
+            .unwrap();
+        assert_eq!(unaligned, [0x1D, 0xFF]);
+        assert!(rest.is_empty());
+
+        let mut inside = Vec::new();
+        BitCursor::new(&bits[1..9])
+            .read_octets_into(1, &mut inside, Codec::Uper)
+            .unwrap();
+        assert_eq!(inside, [0x8E]);
+    }
+
+    #[test]
+    fn read_octets() {
+        let bits = bits![u8, Msb0; 1, 1, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+
+        let (_, aligned) = BitCursor::new(&bits[8..])
+            .read_octets(1, Codec::Aper)
+            .unwrap();
+        assert!(matches!(aligned, Cow::Borrowed([0x7F])));
+
+        let (_, unaligned) = BitCursor::new(&bits[2..])
+            .read_octets(2, Codec::Uper)
+            .unwrap();
+        assert!(matches!(unaligned, Cow::Owned(_)));
+        assert_eq!(*unaligned, [0x1D, 0xFF]);
+
+        let (rest, empty) = BitCursor::new(&bits[3..])
+            .read_octets(0, Codec::Uper)
+            .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(rest.len(), 15);
+    }
+}
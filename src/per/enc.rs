@@ -1,5 +1,7 @@
 //! Encoding Rust structures into Packed Encoding Rules data.
 
+mod writer;
+
 use alloc::{string::ToString, vec::Vec};
 
 use bitvec::prelude::*;
@@ -17,8 +19,14 @@ use crate::{
             BitStr, DynConstrainedCharacterString, StaticPermittedAlphabet, should_be_indexed,
         },
     },
+    value::{
+        TypedValue,
+        typed::{Alternative, Enumeration, Fields, Structure, index_constraints},
+    },
 };
 
+use writer::{BitBuffer, BitWriter};
+
 pub use crate::error::EncodeError as Error;
 type Result<T, E = Error> = core::result::Result<T, E>;
 
@@ -70,19 +78,19 @@ impl EncoderOptions {
 #[derive(Debug)]
 pub struct Encoder<const RCL: usize = 0, const ECL: usize = 0> {
     options: EncoderOptions,
-    output: BitString,
+    output: BitBuffer,
     /// Scratch buffer reused across encode_* calls to avoid repeated heap allocations.
     /// Each method takes ownership via `mem::take`, clears it, uses it, then puts it back.
-    work: BitString,
+    work: BitBuffer,
     /// Preamble bits already present in `output` before this encoder's own field data begins.
     /// Set when a parent encoder moves its buffer into this child to avoid a separate allocation.
     /// Subtracted from `number_optional_default_fields` in `output_length` to avoid double-counting.
     preamble_pre_reserved: usize,
-    set_output: alloc::collections::BTreeMap<Tag, BitString>,
+    set_output: alloc::collections::BTreeMap<Tag, BitBuffer>,
     number_optional_default_fields: usize,
     root_bitfield: (usize, [(bool, Tag); RCL]),
     extension_bitfield: (usize, [bool; ECL]),
-    extension_fields: [Option<Vec<u8>>; ECL],
+    extension_fields: [Option<BitBuffer>; ECL],
     is_extension_sequence: bool,
     parent_output_length: Option<usize>,
 }
@@ -90,29 +98,39 @@ pub struct Encoder<const RCL: usize = 0, const ECL: usize = 0> {
 impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
     /// Constructs a new encoder from the provided options.
     pub fn new(options: EncoderOptions) -> Self {
-        Self {
-            options,
-            output: <_>::default(),
-            work: BitString::new(),
-            preamble_pre_reserved: 0,
-            set_output: <_>::default(),
-            number_optional_default_fields: 0,
-            root_bitfield: (0, [(false, Tag::new_private(0)); RCL]),
-            extension_bitfield: (0, [false; ECL]),
-            is_extension_sequence: <_>::default(),
-            extension_fields: [(); ECL].map(|_| None),
-            parent_output_length: <_>::default(),
-        }
+        Self::with_output(options, BitBuffer::default())
     }
 
     /// Constructs a new encoder from the provided options, reusing the given
     /// bit buffer's allocation. The buffer is cleared before encoding begins.
-    pub fn new_with_output(options: EncoderOptions, mut output: BitString) -> Self {
+    pub fn new_with_output(options: EncoderOptions, output: BitString) -> Self {
+        Self::with_output(options, output.into())
+    }
+
+    /// Constructs a new encoder from the provided options that counts the
+    /// bits of the encoding instead of writing them, see
+    /// [`Encoder::encoded_len`].
+    ///
+    /// ```rust
+    /// use rasn::uper::enc::{Encoder, EncoderOptions};
+    /// use rasn::Encode;
+    ///
+    /// let value = vec![vec![1u8, 2], vec![3]];
+    /// let mut encoder = Encoder::<0, 0>::new_counting(EncoderOptions::unaligned());
+    /// value.encode(&mut encoder).unwrap();
+    ///
+    /// assert_eq!(encoder.encoded_len(), rasn::uper::encode(&value).unwrap().len());
+    /// ```
+    pub fn new_counting(options: EncoderOptions) -> Self {
+        Self::with_output(options, BitBuffer::counting())
+    }
+
+    fn with_output(options: EncoderOptions, mut output: BitBuffer) -> Self {
         output.clear();
         Self {
             options,
+            work: output.empty_like(),
             output,
-            work: BitString::new(),
             preamble_pre_reserved: 0,
             set_output: <_>::default(),
             number_optional_default_fields: 0,
@@ -133,7 +151,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
     ) -> Encoder<RL, EL> {
         let mut options = self.options;
         options.set_encoding = true;
-        let mut encoder = Encoder::<RL, EL>::new(options);
+        let mut encoder = self.new_child::<RL, EL>(options);
         encoder.number_optional_default_fields = C::FIELDS.number_of_optional_and_default_fields();
         encoder.is_extension_sequence = C::IS_EXTENSIBLE;
         encoder.parent_output_length = Some(self.output_length());
@@ -147,34 +165,65 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
     >(
         &self,
     ) -> Encoder<RL, EL> {
-        let mut encoder = Encoder::<RL, EL>::new(self.options.without_set_encoding());
+        let mut encoder = self.new_child::<RL, EL>(self.options.without_set_encoding());
         encoder.number_optional_default_fields = C::FIELDS.number_of_optional_and_default_fields();
         encoder.is_extension_sequence = C::IS_EXTENSIBLE;
         encoder.parent_output_length = Some(self.output_length());
         encoder
     }
 
-    /// Returns the octet aligned output for the encoder.
+    /// Creates an encoder for a component that writes or counts as this
+    /// encoder does.
+    fn new_child<const RL: usize, const EL: usize>(
+        &self,
+        options: EncoderOptions,
+    ) -> Encoder<RL, EL> {
+        Encoder::with_output(options, self.output.empty_like())
+    }
+
+    /// Returns the octet aligned output for the encoder. A counting encoder
+    /// has no output.
     pub fn output(&mut self) -> Vec<u8> {
-        let mut output = self.bitstring_output();
-        Self::force_pad_to_alignment(&mut output);
-        output.as_raw_slice().to_vec()
+        self.octet_output().into_bitstring().as_raw_slice().to_vec()
     }
 
     /// Consumes the encoder and returns the octet-aligned output, reusing the
-    /// internal buffer's allocation instead of copying it.
+    /// internal buffer's allocation instead of copying it. A counting
+    /// encoder has no output.
     pub fn output_into_vec(mut self) -> Vec<u8> {
+        self.octet_output().into_bitstring().into_vec()
+    }
+
+    /// Returns the length in octets of the output, or of the bits counted
+    /// by a counting encoder.
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        let bits = if self.options.set_encoding {
+            self.set_output.values().map(BitBuffer::len).sum()
+        } else {
+            self.output.len()
+        };
+        bits.div_ceil(8)
+    }
+
+    /// Returns the octet aligned bit level output for the encoder.
+    fn octet_output(&mut self) -> BitBuffer {
         let mut output = self.bitstring_output();
-        Self::force_pad_to_alignment(&mut output);
-        output.into_vec()
+        output.pad_to_octet();
+        output
     }
 
     /// Returns the bit level output for the encoder.
-    fn bitstring_output(&mut self) -> BitString {
+    fn bitstring_output(&mut self) -> BitBuffer {
+        let empty = self.output.empty_like();
         if self.options.set_encoding {
-            self.set_output.values().flatten().collect::<BitString>()
+            let mut output = empty;
+            for bits in self.set_output.values() {
+                output.append(bits);
+            }
+            output
         } else {
-            core::mem::take(&mut self.output)
+            core::mem::replace(&mut self.output, empty)
         }
     }
 
@@ -214,48 +263,35 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
         output_length += self.parent_output_length.unwrap_or_default();
 
         if self.options.set_encoding {
-            output_length += self
-                .set_output
-                .values()
-                .map(bitvec::vec::BitVec::len)
-                .sum::<usize>();
+            output_length += self.set_output.values().map(BitBuffer::len).sum::<usize>();
         }
 
         output_length
     }
 
-    fn pad_to_alignment(&self, buffer: &mut BitString) {
+    fn pad_to_alignment(&self, buffer: &mut BitBuffer) {
         if self.options.aligned {
             let mut output_length = self.output_length();
             output_length += buffer.len();
             if !output_length.is_multiple_of(8) {
-                for _ in 0..(8 - output_length % 8) {
-                    buffer.push(false);
-                }
-            }
-        }
-    }
-
-    fn force_pad_to_alignment(buffer: &mut BitString) {
-        const BYTE_WIDTH: usize = 8;
-        if !buffer.len().is_multiple_of(BYTE_WIDTH) {
-            let mut string = BitString::new();
-            for _ in 0..BYTE_WIDTH - (buffer.len() % 8) {
-                string.push(false);
+                buffer.resize(buffer.len() + 8 - output_length % 8);
             }
-            buffer.extend(string);
-            debug_assert_eq!(0, buffer.len() % 8);
         }
     }
 
     fn encode_extensible_bit(
         &mut self,
         constraints: &Constraints,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
         extensible_condition: impl FnOnce() -> bool,
     ) -> bool {
         if constraints.extensible() {
             let is_in_constraints = !(extensible_condition)();
+            trace_event!(
+                offset = buffer.len(),
+                extended = is_in_constraints,
+                "extension bit"
+            );
             buffer.push(is_in_constraints);
             is_in_constraints
         } else {
@@ -313,7 +349,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
         match (
             constraints.permitted_alphabet(),
-            should_be_indexed(S::CHARACTER_SET_WIDTH as u32, S::CHARACTER_SET),
+            should_be_indexed(S::CHARACTER_SET_WIDTH as u32, &S::CHARACTER_SET),
             constraints.permitted_alphabet().map(|alphabet| {
                 S::CHARACTER_SET_WIDTH
                     > self.character_width(
@@ -333,12 +369,15 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                         .then(|| -> Extensible<Size> { <_>::default() })
                         .as_ref()
                         .or(constraints.size()),
-                    |range| Ok(characters[range].to_bitvec()),
+                    |range, buffer| {
+                        buffer.write_bitslice(&characters[range]);
+                        Ok(())
+                    },
                 )?;
             }
             (None, true, _) => {
                 let characters =
-                    &DynConstrainedCharacterString::from_bits(value.chars(), S::CHARACTER_SET)
+                    &DynConstrainedCharacterString::from_bits(value.chars(), &S::CHARACTER_SET)
                         .map_err(|e| Error::alphabet_constraint_not_satisfied(e, self.codec()))?;
 
                 self.encode_length(
@@ -348,20 +387,30 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                         .then(|| -> Extensible<Size> { <_>::default() })
                         .as_ref()
                         .or(constraints.size()),
-                    |range| Ok(characters[range].to_bitvec()),
+                    |range, buffer| {
+                        buffer.write_bitslice(&characters[range]);
+                        Ok(())
+                    },
                 )?;
             }
             _ => {
                 let char_length = value.len();
-                let octet_aligned_value = self.options.aligned.then(|| {
-                    if S::CHARACTER_SET_WIDTH <= self.character_width(S::CHARACTER_SET_WIDTH) {
-                        value.to_octet_aligned_string()
-                    } else {
-                        value.to_octet_aligned_index_string()
-                    }
-                });
+                let octet_aligned_value = self
+                    .options
+                    .aligned
+                    .then(|| {
+                        if S::CHARACTER_SET_WIDTH <= self.character_width(S::CHARACTER_SET_WIDTH) {
+                            Ok(value.to_octet_aligned_string())
+                        } else {
+                            value.to_octet_aligned_index_string()
+                        }
+                    })
+                    .transpose()
+                    .map_err(|e| Error::alphabet_constraint_not_satisfied(e, self.codec()))?;
                 // 30.5.4 Rec. ITU-T X.691 (02/2021)
-                let value = value.to_index_or_value_bitstring();
+                let value = value
+                    .to_index_or_value_bitstring()
+                    .map_err(|e| Error::alphabet_constraint_not_satisfied(e, self.codec()))?;
 
                 let octet_aligned_value = &octet_aligned_value;
                 self.encode_string_length(
@@ -372,11 +421,14 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                         .then(|| -> Extensible<Size> { <_>::default() })
                         .as_ref()
                         .or(constraints.size()),
-                    |range| {
-                        Ok(match octet_aligned_value {
-                            Some(value) => types::BitString::from_slice(&value[range]),
-                            None => value[S::char_range_to_bit_range(range)].to_bitvec(),
-                        })
+                    |range, buffer| {
+                        match octet_aligned_value {
+                            Some(value) => buffer.write_octets(&value[range]),
+                            None => {
+                                buffer.write_bitslice(&value[S::char_range_to_bit_range(range)])
+                            }
+                        }
+                        Ok(())
                     },
                 )?;
             }
@@ -426,9 +478,14 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
         if !self.options.set_encoding && !extensions_present {
             let preamble_bits = (C::IS_EXTENSIBLE as usize) + needed;
             let preamble_start = self.output.len();
+            trace_event!(
+                offset = preamble_start,
+                bitmap = ?&option_bitfield[..needed],
+                "preamble"
+            );
             if preamble_bits > 0 {
                 // All bits initialise to false; only set the true presence bits.
-                self.output.resize(preamble_start + preamble_bits, false);
+                self.output.resize(preamble_start + preamble_bits);
                 // extensibility bit (index 0 when IS_EXTENSIBLE) stays false (not present).
                 let presence_start = preamble_start + (C::IS_EXTENSIBLE as usize);
                 for (i, (bit, _)) in option_bitfield[..needed].iter().enumerate() {
@@ -437,14 +494,19 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                     }
                 }
             }
-            let mut out = encoder.bitstring_output();
-            self.output.append(&mut out);
+            let out = encoder.bitstring_output();
+            self.output.append(&out);
             return Ok(());
         }
 
         // Slow path: SET encoding or extensions present — use an intermediate buffer.
         let required_present = C::FIELDS.has_required_field();
-        let mut buffer = BitString::with_capacity(core::mem::size_of::<C>());
+        let mut buffer = self.output.with_capacity_like(core::mem::size_of::<C>());
+        trace_event!(
+            offset = self.output.len(),
+            bitmap = ?&option_bitfield[..needed],
+            "preamble"
+        );
         if C::IS_EXTENSIBLE {
             buffer.push(extensions_present);
         }
@@ -454,30 +516,40 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
             }
         }
         if option_bitfield[..needed].iter().any(|(bit, _tag)| *bit) || required_present {
-            let mut out = encoder.bitstring_output();
-            buffer.append(&mut out);
+            let out = encoder.bitstring_output();
+            buffer.append(&out);
         }
 
         if !C::IS_EXTENSIBLE || !extensions_present {
             self.extend(tag, &buffer);
             return Ok(());
         }
+        trace_event!(
+            offset = self.output.len() + buffer.len(),
+            "extension bitmap"
+        );
         self.encode_normally_small_length(EL, &mut buffer)?;
         for bit in encoder.extension_fields.iter() {
             buffer.push(bit.is_some());
         }
 
         for field in encoder.extension_fields.iter().filter_map(Option::as_ref) {
-            self.encode_length(&mut buffer, field.len(), <_>::default(), |range| {
-                Ok(BitString::from_slice(&field[range]))
-            })?;
+            self.encode_length(
+                &mut buffer,
+                field.len() / 8,
+                <_>::default(),
+                |range, buffer| {
+                    buffer.append_octets(field, range);
+                    Ok(())
+                },
+            )?;
         }
         self.extend(tag, &buffer);
 
         Ok(())
     }
 
-    fn encode_normally_small_length(&mut self, value: usize, buffer: &mut BitString) -> Result<()> {
+    fn encode_normally_small_length(&mut self, value: usize, buffer: &mut BitBuffer) -> Result<()> {
         debug_assert!(value >= 1);
         let value = if value >= 64 { value } else { value - 1 };
         self.encode_normally_small_integer(value, buffer)
@@ -486,7 +558,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
     fn encode_normally_small_integer(
         &mut self,
         value: usize,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
     ) -> Result<()> {
         let is_large = value >= 64;
         buffer.push(is_large);
@@ -500,15 +572,16 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
     fn encode_string_length(
         &self,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
         is_large_string: bool,
         length: usize,
         constraints: Option<&Extensible<constraints::Size>>,
-        encode_fn: impl Fn(core::ops::Range<usize>) -> Result<BitString>,
+        encode_fn: impl Fn(core::ops::Range<usize>, &mut BitBuffer) -> Result<()>,
     ) -> Result<()> {
         let Some(constraints) = constraints else {
             return self.encode_unconstrained_length(buffer, length, None, encode_fn);
         };
+        trace_event!(offset = buffer.len(), length, "length");
 
         if constraints.extensible.is_none() {
             Error::check_length(length, &constraints.constraint, self.codec())?;
@@ -523,7 +596,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                 if range == 0 {
                     Ok(())
                 } else if range == 1 {
-                    buffer.extend((encode_fn)(0..length)?);
+                    (encode_fn)(0..length, buffer)?;
                     Ok(())
                 } else if range <= SIXTY_FOUR_K as usize {
                     let effective_length = constraints.effective_value(length).into_inner();
@@ -548,7 +621,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                         self.pad_to_alignment(buffer);
                     }
 
-                    buffer.extend((encode_fn)(0..length)?);
+                    (encode_fn)(0..length, buffer)?;
                     Ok(())
                 } else {
                     self.encode_unconstrained_length(buffer, length, None, encode_fn)
@@ -560,31 +633,32 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
     fn encode_length(
         &self,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
         length: usize,
         constraints: Option<&Extensible<constraints::Size>>,
-        encode_fn: impl Fn(core::ops::Range<usize>) -> Result<BitString>,
+        encode_fn: impl Fn(core::ops::Range<usize>, &mut BitBuffer) -> Result<()>,
     ) -> Result<()> {
         self.encode_string_length(buffer, false, length, constraints, encode_fn)
     }
 
     fn encode_unconstrained_length(
         &self,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
         mut length: usize,
         min: Option<usize>,
-        encode_fn: impl Fn(core::ops::Range<usize>) -> Result<BitString>,
+        encode_fn: impl Fn(core::ops::Range<usize>, &mut BitBuffer) -> Result<()>,
     ) -> Result<()> {
         let mut min = min.unwrap_or_default();
 
         self.pad_to_alignment(&mut *buffer);
+        trace_event!(offset = buffer.len(), length, "length");
         if length <= 127 {
-            buffer.extend((length as u8).to_be_bytes());
-            buffer.extend((encode_fn)(0..length)?);
+            buffer.write_bits(length as u64, 8);
+            (encode_fn)(0..length, buffer)?;
         } else if length < SIXTEEN_K.into() {
             const SIXTEENTH_BIT: u16 = 0x8000;
-            buffer.extend((SIXTEENTH_BIT | length as u16).to_be_bytes());
-            buffer.extend((encode_fn)(0..length)?);
+            buffer.write_bits((SIXTEENTH_BIT | length as u16).into(), 16);
+            (encode_fn)(0..length, buffer)?;
         } else {
             loop {
                 // Hack to get around no exclusive syntax.
@@ -611,14 +685,14 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                 };
 
                 const FRAGMENT_MARKER: u8 = 0xC0;
-                buffer.extend(&[FRAGMENT_MARKER | fragment_index]);
+                buffer.write_bits((FRAGMENT_MARKER | fragment_index).into(), 8);
 
-                buffer.extend((encode_fn)(min..min + amount)?);
+                (encode_fn)(min..min + amount, buffer)?;
                 min += amount;
 
                 if length == SIXTEEN_K as usize {
                     // Add final fragment in the frame.
-                    buffer.extend(&[0]);
+                    buffer.write_bits(0, 8);
                     break;
                 }
                 length = length.saturating_sub(amount);
@@ -629,8 +703,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
     }
 
     fn extend<'input>(&mut self, tag: Tag, input: impl Into<Input<'input>>) {
-        use bitvec::field::BitField;
-        let mut set_buffer = <_>::default();
+        let mut set_buffer = self.output.empty_like();
         let buffer = if self.options.set_encoding {
             &mut set_buffer
         } else {
@@ -639,16 +712,16 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
         match input.into() {
             Input::Bits(bits) => {
-                buffer.extend_from_bitslice(bits);
+                buffer.append(bits);
             }
             Input::Bit(bit) => {
                 buffer.push(bit);
             }
             Input::Byte(byte) => {
-                buffer.store_be(byte);
+                buffer.write_bits(byte.into(), 8);
             }
             Input::Bytes(bytes) => {
-                buffer.extend(bytes);
+                buffer.write_octets(bytes);
             }
         }
         if self.options.set_encoding {
@@ -660,7 +733,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
         &mut self,
         constraints: Constraints,
         value: &[u8],
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
     ) -> Result<()> {
         let octet_string_length = value.len();
         let extensible_is_present = self.encode_extensible_bit(&constraints, buffer, || {
@@ -670,29 +743,33 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
             })
         });
         let Some(size) = constraints.size() else {
-            return self.encode_length(buffer, value.len(), <_>::default(), |range| {
-                Ok(BitString::from_slice(&value[range]))
+            return self.encode_length(buffer, value.len(), <_>::default(), |range, buffer| {
+                buffer.write_octets(&value[range]);
+                Ok(())
             });
         };
 
         if extensible_is_present {
-            self.encode_length(buffer, value.len(), <_>::default(), |range| {
-                Ok(BitString::from_slice(&value[range]))
+            self.encode_length(buffer, value.len(), <_>::default(), |range, buffer| {
+                buffer.write_octets(&value[range]);
+                Ok(())
             })?;
         } else if Some(0) == size.constraint.range() {
             // ITU-T X.691 (02/2021) §11.9.3.3: If "n" is zero there shall be no further addition to the field-list.
         } else if size.constraint.range() == Some(1) && size.constraint.as_start() <= Some(&2) {
             // ITU-T X.691 (02/2021) §17 NOTE: Octet strings of fixed length less than or equal to two octets are not octet-aligned.
             // All other octet strings are octet-aligned in the ALIGNED variant.
-            self.encode_length(buffer, value.len(), Some(size), |range| {
-                Ok(BitString::from_slice(&value[range]))
+            self.encode_length(buffer, value.len(), Some(size), |range, buffer| {
+                buffer.write_octets(&value[range]);
+                Ok(())
             })?;
         } else {
             if size.constraint.range() == Some(1) {
                 self.pad_to_alignment(buffer);
             }
-            self.encode_string_length(buffer, true, value.len(), Some(size), |range| {
-                Ok(BitString::from_slice(&value[range]))
+            self.encode_string_length(buffer, true, value.len(), Some(size), |range, buffer| {
+                buffer.write_octets(&value[range]);
+                Ok(())
             })?;
         }
 
@@ -704,7 +781,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
         &mut self,
         constraints: Constraints,
         value: &I,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
     ) -> Result<()> {
         let is_extended_value = self.encode_extensible_bit(&constraints, buffer, || {
             constraints.value().is_some_and(|value_range| {
@@ -714,8 +791,9 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
         let value_range = if is_extended_value || constraints.value().is_none() {
             let (bytes, needed) = value.to_signed_bytes_be();
-            self.encode_length(buffer, needed, constraints.size(), |range| {
-                Ok(BitString::from_slice(&bytes.as_ref()[..needed][range]))
+            self.encode_length(buffer, needed, constraints.size(), |range, buffer| {
+                buffer.write_octets(&bytes.as_ref()[..needed][range]);
+                Ok(())
             })?;
             return Ok(());
         } else {
@@ -820,8 +898,9 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
                 }
             }
         } else {
-            self.encode_length(buffer, needed, <_>::default(), |range| {
-                Ok(BitString::from_slice(&bytes[..needed][range]))
+            self.encode_length(buffer, needed, <_>::default(), |range, buffer| {
+                buffer.write_octets(&bytes[..needed][range]);
+                Ok(())
             })?;
         }
 
@@ -830,7 +909,7 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
     fn encode_non_negative_binary_integer(
         &self,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
         range: i128,
         bytes: &[u8],
     ) {
@@ -843,15 +922,160 @@ impl<const RCL: usize, const ECL: usize> Encoder<RCL, ECL> {
 
     fn encode_non_negative_binary_integer_bits(
         &self,
-        buffer: &mut BitString,
+        buffer: &mut BitBuffer,
         bits: usize,
         bytes: &[u8],
     ) {
+        if bits <= 64 {
+            let value = bytes[bytes.len().saturating_sub(8)..]
+                .iter()
+                .fold(0, |value, &byte| (value << 8) | u64::from(byte));
+            buffer.write_bits(value, bits);
+            return;
+        }
         let mut buf = [0u8; 16];
         let nbytes = bytes.len().min(16);
         buf[16 - nbytes..].copy_from_slice(&bytes[bytes.len() - nbytes..]);
-        buffer.extend_from_bitslice(&buf.view_bits::<Msb0>()[128 - bits..]);
+        buffer.write_bitslice(&buf.view_bits::<Msb0>()[128 - bits..]);
+    }
+
+    /// Encodes the components of a `SEQUENCE` or `SET` value of a type only
+    /// known at runtime, in the order of `fields`.
+    fn encode_typed_components(&mut self, tag: Tag, fields: &Fields<'_>) -> Result<()> {
+        let mut buffer = self.output.empty_like();
+        let extensions = fields
+            .extensions
+            .as_deref()
+            .filter(|extensions| extensions.iter().any(|extension| extension.is_present()));
+        if fields.extensions.is_some() {
+            buffer.push(extensions.is_some());
+        }
+        for field in fields
+            .root
+            .iter()
+            .filter(|field| field.component.is_optional())
+        {
+            buffer.push(field.value.is_some());
+        }
+        for value in fields.root.iter().filter_map(|field| field.value) {
+            let mut encoder = self.new_child::<0, 0>(self.options.without_set_encoding());
+            encoder.parent_output_length = Some(self.output_length() + buffer.len());
+            value.encode(&mut encoder)?;
+            buffer.append(&encoder.bitstring_output());
+        }
+
+        if let Some(extensions) = extensions {
+            self.encode_normally_small_length(extensions.len(), &mut buffer)?;
+            for extension in extensions {
+                buffer.push(extension.is_present());
+            }
+            for extension in extensions.iter().filter(|extension| extension.is_present()) {
+                // Extension additions are open types, which start their own
+                // alignment; a group is encoded as a `SEQUENCE` of its
+                // components.
+                let mut encoder = self.new_child::<0, 0>(self.options.without_set_encoding());
+                if extension.group {
+                    let group = Fields {
+                        root: extension.fields.clone(),
+                        extensions: None,
+                    };
+                    encoder.encode_typed_components(Tag::SEQUENCE, &group)?;
+                } else if let Some(value) = extension.fields[0].value {
+                    value.encode(&mut encoder)?;
+                }
+                self.encode_open_type(&mut buffer, encoder.octet_output())?;
+            }
+        }
+
+        self.extend(tag, &buffer);
+        Ok(())
+    }
+
+    /// Encodes the chosen alternative of a `CHOICE` value of a type only known
+    /// at runtime.
+    fn encode_typed_alternative(&mut self, tag: Tag, alternative: &Alternative<'_>) -> Result<()> {
+        let mut buffer = self.output.empty_like();
+        if alternative.extensible {
+            buffer.push(alternative.is_extension);
+        }
+        let mut encoder = self.new_child::<0, 0>(self.options.without_set_encoding());
+        if alternative.is_extension {
+            self.encode_normally_small_integer(alternative.index, &mut buffer)?;
+            alternative.value.encode(&mut encoder)?;
+            self.encode_open_type(&mut buffer, encoder.octet_output())?;
+        } else {
+            if alternative.root_len > 1 {
+                let variance = index_constraints(alternative.root_len);
+                self.encode_integer_into_buffer::<usize>(
+                    variance,
+                    &alternative.index,
+                    &mut buffer,
+                )?;
+            }
+            encoder.parent_output_length = Some(self.output_length() + buffer.len());
+            alternative.value.encode(&mut encoder)?;
+            buffer.append(&encoder.bitstring_output());
+        }
+        self.extend(tag, &buffer);
+        Ok(())
     }
+
+    /// Encodes an `ENUMERATED` value of a type only known at runtime.
+    fn encode_typed_enumeration(&mut self, tag: Tag, enumeration: &Enumeration<'_>) -> Result<()> {
+        let mut buffer = self.output.empty_like();
+        if enumeration.extensible {
+            buffer.push(enumeration.is_extension);
+        }
+        if enumeration.is_extension {
+            self.encode_normally_small_integer(enumeration.index, &mut buffer)?;
+        } else {
+            self.encode_non_negative_binary_integer(
+                &mut buffer,
+                enumeration.root_len as i128,
+                &enumeration.index.to_be_bytes(),
+            );
+        }
+        self.extend(tag, &buffer);
+        Ok(())
+    }
+
+    /// Encodes the octets of an open type with their length, writing at least
+    /// one octet for an empty encoding.
+    fn encode_open_type(&mut self, buffer: &mut BitBuffer, mut output: BitBuffer) -> Result<()> {
+        if output.is_empty() {
+            output.write_bits(0, 8);
+        }
+        self.encode_length(buffer, output.len() / 8, <_>::default(), |range, buffer| {
+            buffer.append_octets(&output, range);
+            Ok(())
+        })
+    }
+}
+
+/// The layout of an integer constrained to `START..=END`, as its offset from
+/// `START` in a number of bits, worked out at compile time. `None` when the
+/// offset is encoded with a length, or doesn't fit in a `u64`.
+struct IntegerLayout<const START: i128, const END: i128>;
+
+impl<const START: i128, const END: i128> IntegerLayout<START, END> {
+    const RANGE: Option<i128> = match END.checked_sub(START) {
+        Some(difference) => difference.checked_add(1),
+        None => None,
+    };
+
+    /// The width of the offset in UPER.
+    const UNALIGNED: Option<usize> = match Self::RANGE {
+        Some(range) if range <= 1 << 64 => Some(crate::num::log2(range) as usize),
+        _ => None,
+    };
+
+    /// Whether the offset is octet-aligned, and its width, in APER.
+    const ALIGNED: Option<(bool, usize)> = match Self::RANGE {
+        Some(range) if range <= 255 => Some((false, crate::num::log2(range) as usize)),
+        Some(256) => Some((true, 8)),
+        Some(range) if range <= SIXTY_FOUR_K as i128 => Some((true, 16)),
+        _ => None,
+    };
 }
 
 impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC> {
@@ -869,6 +1093,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         value: &types::Any,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        value.check_codec(self.codec())?;
         self.encode_octet_string(
             tag,
             Constraints::default(),
@@ -896,8 +1121,9 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         let size = constraints.size();
 
         if extensible_is_present || size.is_none() {
-            self.encode_length(&mut work, value.len(), <_>::default(), |range| {
-                Ok(BitString::from(&value[range]))
+            self.encode_length(&mut work, value.len(), <_>::default(), |range, buffer| {
+                buffer.write_bitslice(&value[range]);
+                Ok(())
             })?;
         } else if size.and_then(|size| size.constraint.range()) == Some(0) {
             // NO-OP
@@ -906,16 +1132,29 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         }) {
             // ITU-T X.691 (02/2021) §16: Bitstrings constrained to a fixed length less than or equal to 16 bits
             // do not cause octet alignment. Larger bitstrings are octet-aligned in the ALIGNED variant.
-            self.encode_length(&mut work, value.len(), constraints.size(), |range| {
-                Ok(BitString::from(&value[range]))
-            })?;
+            self.encode_length(
+                &mut work,
+                value.len(),
+                constraints.size(),
+                |range, buffer| {
+                    buffer.write_bitslice(&value[range]);
+                    Ok(())
+                },
+            )?;
         } else {
             if size.and_then(|size| size.constraint.range()) == Some(1) {
                 self.pad_to_alignment(&mut work);
             }
-            self.encode_string_length(&mut work, true, value.len(), constraints.size(), |range| {
-                Ok(BitString::from(&value[range]))
-            })?;
+            self.encode_string_length(
+                &mut work,
+                true,
+                value.len(),
+                constraints.size(),
+                |range, buffer| {
+                    buffer.write_bitslice(&value[range]);
+                    Ok(())
+                },
+            )?;
         }
 
         self.extend(tag, &work);
@@ -939,9 +1178,19 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         value: &E,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!(
+            "enumerated",
+            ty = core::any::type_name::<E>(),
+            offset = self.output.len()
+        );
         let mut work = core::mem::take(&mut self.work);
         work.clear();
         let index = value.enumeration_index();
+        trace_event!(
+            index,
+            extended = value.is_extended_variant(),
+            "enumeration index"
+        );
         if E::EXTENDED_VARIANTS.is_some() {
             work.push(value.is_extended_variant());
         }
@@ -980,6 +1229,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         value: &I,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!("integer", offset = self.output.len());
         let mut work = core::mem::take(&mut self.work);
         work.clear();
         self.encode_integer_into_buffer(constraints, value, &mut work)?;
@@ -988,6 +1238,43 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         Ok(())
     }
 
+    fn encode_constrained_integer<const START: i128, const END: i128, I: IntegerType>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &I,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        let layout = if self.options.aligned {
+            IntegerLayout::<START, END>::ALIGNED
+        } else {
+            IntegerLayout::<START, END>::UNALIGNED.map(|bits| (false, bits))
+        };
+        // Values out of range take the general path, which reports them.
+        let offset = value
+            .to_i128()
+            .filter(|value| (START..=END).contains(value))
+            .map(|value| (value - START) as u64);
+        let (Some((is_aligned, bits)), Some(offset), true) = (
+            layout,
+            offset,
+            crate::enc::is_exact_value_range::<START, END>(&constraints),
+        ) else {
+            return self.encode_integer(tag, constraints, value, identifier);
+        };
+
+        trace_span!("integer", offset = self.output.len());
+        let mut work = core::mem::take(&mut self.work);
+        work.clear();
+        if is_aligned {
+            self.pad_to_alignment(&mut work);
+        }
+        work.write_bits(offset, bits);
+        self.extend(tag, &work);
+        self.work = work;
+        Ok(())
+    }
+
     fn encode_real<R: types::RealType>(
         &mut self,
         _: Tag,
@@ -1005,7 +1292,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
     fn encode_object_identifier(
         &mut self,
         tag: Tag,
-        oid: &[u32],
+        oid: &crate::types::Oid,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         let mut buf = Vec::new();
@@ -1111,10 +1398,11 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
     fn encode_utf8_string(
         &mut self,
         tag: Tag,
-        _: Constraints,
+        constraints: Constraints,
         value: &str,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        crate::enc::check_utf8_string(value, &constraints, self.codec())?;
         self.encode_octet_string(
             tag,
             Constraints::default(),
@@ -1172,6 +1460,11 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         constraints: Constraints,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!(
+            "sequence of",
+            ty = core::any::type_name::<E>(),
+            offset = self.output.len()
+        );
         let mut work = core::mem::take(&mut self.work);
         work.clear();
         let options = self.options;
@@ -1182,37 +1475,40 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
                     && size_constraint.constraint.contains(&values.len())
             })
         });
-        let extension_bits_len = work.len();
-
-        self.encode_length(&mut work, values.len(), constraints.size(), |range| {
-            let mut acc = BitString::default();
-            // Recycle both the output buffer and the work buffer across element encoders
-            // to avoid repeated heap allocations.
-            let mut reusable_buf = BitString::default();
-            let mut reusable_work = BitString::new();
-            let mut first_round = true;
-            for value in &values[range] {
-                let mut encoder = Self::new_with_output(options, reusable_buf);
-                encoder.work = reusable_work;
-                if first_round {
-                    encoder.parent_output_length = Some(extension_bits_len);
-                    first_round = false;
+        let output_length = self.output_length();
+
+        self.encode_length(
+            &mut work,
+            values.len(),
+            constraints.size(),
+            |range, buffer| {
+                // Recycle both the output buffer and the work buffer across element encoders
+                // to avoid repeated heap allocations.
+                let mut reusable_buf = buffer.empty_like();
+                let mut reusable_work = buffer.empty_like();
+                for value in &values[range] {
+                    let mut encoder = Self::with_output(options, reusable_buf);
+                    encoder.work = reusable_work;
+                    // Elements are aligned to where they end up in the output, after
+                    // the length and the elements before them.
+                    encoder.parent_output_length = Some(output_length + buffer.len());
+                    E::encode(value, &mut encoder)?;
+                    reusable_work = core::mem::take(&mut encoder.work);
+                    let mut bits = encoder.bitstring_output();
+                    buffer.append(&bits);
+                    bits.clear();
+                    reusable_buf = bits;
                 }
-                E::encode(value, &mut encoder)?;
-                reusable_work = core::mem::take(&mut encoder.work);
-                let mut bits = encoder.bitstring_output();
-                acc.append(&mut bits);
-                reusable_buf = bits;
-            }
-            Ok(acc)
-        })?;
+                Ok(())
+            },
+        )?;
 
         self.extend(tag, &work);
         self.work = work;
         Ok(())
     }
 
-    fn encode_set_of<E: Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: Encode>(
         &mut self,
         tag: Tag,
         values: &types::SetOf<E>,
@@ -1285,6 +1581,11 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         C: crate::types::Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
+        trace_span!(
+            "sequence",
+            ty = core::any::type_name::<C>(),
+            offset = self.output.len()
+        );
         // Fast path: non-extensible, non-SET sequences.
         // Move self.output into the child encoder so its fields are written directly into the
         // parent's buffer, avoiding a separate allocation and the subsequent bit-copy.
@@ -1292,8 +1593,10 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
             let needed = C::FIELDS.number_of_optional_and_default_fields();
             let preamble_start = self.output.len();
             if needed > 0 {
-                self.output.resize(preamble_start + needed, false);
+                self.output.resize(preamble_start + needed);
             }
+            // Bits preceding this encoder's own output still count for alignment.
+            let parent_output_length = self.output_length() - self.output.len();
             let mut child = Encoder::<RL, EL> {
                 options: self.options,
                 output: core::mem::take(&mut self.output),
@@ -1305,7 +1608,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
                 extension_bitfield: (0, [false; EL]),
                 is_extension_sequence: false,
                 extension_fields: [(); EL].map(|_| None),
-                parent_output_length: None,
+                parent_output_length: Some(parent_output_length),
             };
             (encoder_scope)(&mut child)?;
             // Move the buffers back; reclaim any grown work allocation from the child.
@@ -1334,6 +1637,11 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         C: crate::types::Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
+        trace_span!(
+            "set",
+            ty = core::any::type_name::<C>(),
+            offset = self.output.len()
+        );
         let mut set = self.new_set_encoder::<RL, EL, C>();
 
         (encoder_scope)(&mut set)?;
@@ -1348,6 +1656,11 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        trace_span!(
+            "choice",
+            ty = core::any::type_name::<E>(),
+            offset = self.output.len()
+        );
         let mut work = core::mem::take(&mut self.work);
         work.clear();
 
@@ -1364,6 +1677,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
             .enumerate()
             .find_map(|(i, &variant_tag)| (tag == variant_tag).then_some(i))
             .ok_or_else(|| Error::variant_not_in_choice(self.codec()))?;
+        trace_event!(index, extended = !is_root_extension, "choice index");
 
         let bounds = if is_root_extension {
             let variance = variants.len();
@@ -1377,7 +1691,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
             Some(None)
         };
 
-        let mut choice_encoder = Self::new(self.options.without_set_encoding());
+        let mut choice_encoder = self.new_child::<RFC, EFC>(self.options.without_set_encoding());
         // Extensibility and index encoding size must be noted for byte alignment
         let mut choice_bits_len = 0;
         if E::EXTENDED_VARIANTS.is_some() && self.options.aligned {
@@ -1389,7 +1703,12 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
             0
         };
 
-        choice_encoder.parent_output_length = Some(choice_bits_len);
+        // Values of extension alternatives are encoded as open types, which
+        // start their own alignment; root alternatives follow the index.
+        choice_encoder.parent_output_length = Some(match bounds {
+            Some(None) => choice_bits_len,
+            _ => self.output_length() + choice_bits_len,
+        });
         let _tag = (encode_fn)(&mut choice_encoder)?;
 
         match (index, bounds) {
@@ -1400,19 +1719,27 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
                     &mut work,
                 )?;
 
-                work.extend(choice_encoder.output);
+                work.append(&choice_encoder.output);
             }
             (index, Some(None)) => {
                 self.encode_normally_small_integer(index, &mut work)?;
-                let mut output = choice_encoder.output();
+                let mut output = choice_encoder.octet_output();
 
                 if output.is_empty() {
-                    output.push(0);
+                    output.write_bits(0, 8);
                 }
-                self.encode_octet_string_into_buffer(Constraints::default(), &output, &mut work)?;
+                self.encode_length(
+                    &mut work,
+                    output.len() / 8,
+                    <_>::default(),
+                    |range, buffer| {
+                        buffer.append_octets(&output, range);
+                        Ok(())
+                    },
+                )?;
             }
             (_, None) => {
-                work.extend(choice_encoder.output);
+                work.append(&choice_encoder.output);
             }
         }
 
@@ -1428,7 +1755,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         value: E,
         _: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        let mut encoder = Self::new(self.options.without_set_encoding());
+        let mut encoder = self.new_child::<RFC, EFC>(self.options.without_set_encoding());
         if value.is_present() {
             E::encode_with_tag_and_constraints(
                 &value,
@@ -1437,7 +1764,7 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
                 constraints,
                 Identifier::EMPTY,
             )?;
-            self.extension_fields[self.extension_bitfield.0] = Some(encoder.output());
+            self.extension_fields[self.extension_bitfield.0] = Some(encoder.octet_output());
             self.set_extension_presence(true);
         } else {
             self.extension_fields[self.extension_bitfield.0] = None;
@@ -1459,14 +1786,19 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
             self.set_extension_presence(false);
             return Ok(());
         };
-        // Must use an owned-buffer encoder here — we need to capture the output as
-        // Vec<u8> for storage in extension_fields. Never use the ext fast path.
-        let mut encoder = Encoder::<RL, EL>::new(self.options.without_set_encoding());
-        encoder.is_extension_sequence = true;
-        encoder.number_optional_default_fields = E::FIELDS.number_of_optional_and_default_fields();
-        encoder.parent_output_length = Some(self.output_length());
-        value.encode(&mut encoder)?;
-        let out = encoder.output();
+        // Must use an owned-buffer encoder here — we need to capture the output
+        // for storage in extension_fields. Never use the ext fast path.
+        let encode_group = |output: BitBuffer| {
+            let mut encoder =
+                Encoder::<RL, EL>::with_output(self.options.without_set_encoding(), output);
+            encoder.is_extension_sequence = true;
+            encoder.number_optional_default_fields =
+                E::FIELDS.number_of_optional_and_default_fields();
+            encoder.parent_output_length = Some(self.output_length());
+            value.encode(&mut encoder).map(|()| encoder)
+        };
+        let mut encoder = encode_group(self.output.empty_like())?;
+        let out = encoder.octet_output();
 
         let all_absent = if E::FIELDS.has_required_field() {
             false
@@ -1474,8 +1806,14 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
             encoder.root_bitfield.1[..encoder.root_bitfield.0]
                 .iter()
                 .all(|(present, _)| !present)
+        } else if let Some(not_any) = out.not_any() {
+            not_any
         } else {
-            out.iter().all(|&b| b == 0)
+            // Counted bits are unknown, so the group is written to check them.
+            encode_group(BitBuffer::default())?
+                .octet_output()
+                .not_any()
+                .unwrap_or_default()
         };
 
         if all_absent {
@@ -1487,18 +1825,39 @@ impl<const RFC: usize, const EFC: usize> crate::Encoder<'_> for Encoder<RFC, EFC
         }
         Ok(())
     }
+
+    fn encode_typed_value(
+        &mut self,
+        value: TypedValue<'_>,
+        tag: Tag,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        match value.structure::<Error>(self.codec())? {
+            Structure::Sequence(fields) => self.encode_typed_components(tag, &fields),
+            Structure::Set(mut fields) => {
+                fields.sort_canonically();
+                self.encode_typed_components(tag, &fields)
+            }
+            Structure::Choice(alternative) => self.encode_typed_alternative(tag, &alternative),
+            Structure::Enumerated(enumeration) => self.encode_typed_enumeration(tag, &enumeration),
+            Structure::SequenceOf(items) | Structure::SetOf(items) => {
+                self.encode_sequence_of(tag, &items, constraints, identifier)
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 enum Input<'input> {
     Bit(bool),
     Byte(u8),
-    Bits(&'input BitString),
+    Bits(&'input BitBuffer),
     Bytes(&'input [u8]),
 }
 
-impl<'input> From<&'input BitString> for Input<'input> {
-    fn from(value: &'input BitString) -> Self {
+impl<'input> From<&'input BitBuffer> for Input<'input> {
+    fn from(value: &'input BitBuffer) -> Self {
         Self::Bits(value)
     }
 }
@@ -1562,7 +1921,7 @@ mod tests {
     #[test]
     fn length() {
         let encoder = Encoder::<0, 0>::new(EncoderOptions::unaligned());
-        let mut buffer = types::BitString::new();
+        let mut buffer = BitBuffer::default();
         encoder
             .encode_length(
                 &mut buffer,
@@ -1570,10 +1929,10 @@ mod tests {
                 Some(&Extensible::new(constraints::Size::new(
                     constraints::Bounded::new(1, 64),
                 ))),
-                |_| Ok(<_>::default()),
+                |_, _| Ok(()),
             )
             .unwrap();
-        assert_eq!(&[0xC], buffer.as_raw_slice());
+        assert_eq!(&[0xC], buffer.into_bitstring().as_raw_slice());
     }
 
     #[test]
@@ -1589,12 +1948,12 @@ mod tests {
     #[test]
     fn normally_small_integer() {
         let mut encoder = Encoder::<0, 0>::new(EncoderOptions::unaligned());
-        let mut buffer = types::BitString::new();
+        let mut buffer = BitBuffer::default();
         encoder
             .encode_normally_small_integer(2, &mut buffer)
             .unwrap();
         assert_eq!(buffer.len(), 7);
-        assert_eq!(bitvec::bits![0, 0, 0, 0, 0, 1, 0], buffer);
+        assert_eq!(bitvec::bits![0, 0, 0, 0, 0, 1, 0], buffer.into_bitstring());
     }
 
     #[test]
@@ -1646,26 +2005,26 @@ mod tests {
             .encode_integer::<i128>(Tag::INTEGER, CONSTRAINT_1, &4096.into(), Identifier::EMPTY)
             .unwrap();
 
-        assert_eq!(&[2, 0b00010000, 1], &*encoder.output.clone().into_vec());
+        assert_eq!(&[2, 0b00010000, 1], &*encoder.output());
         encoder.output.clear();
         const CONSTRAINT_2: Constraints = constraints!(value_constraint!(start: 1));
         encoder
             .encode_integer::<i128>(Tag::INTEGER, CONSTRAINT_2, &127.into(), Identifier::EMPTY)
             .unwrap();
-        assert_eq!(&[1, 0b01111110], &*encoder.output.clone().into_vec());
+        assert_eq!(&[1, 0b01111110], &*encoder.output());
         encoder.output.clear();
         const CONSTRAINT_3: Constraints = constraints!(value_constraint!(start: 0));
         encoder
             .encode_integer::<i128>(Tag::INTEGER, CONSTRAINT_3, &128.into(), Identifier::EMPTY)
             .unwrap();
-        assert_eq!(&[1, 0b10000000], &*encoder.output.into_vec());
+        assert_eq!(&[1, 0b10000000], &*encoder.output());
     }
 
     #[track_caller]
     fn assert_encode<T: Encode>(options: EncoderOptions, value: T, expected: &[u8]) {
         let mut encoder = Encoder::<0, 0>::new(options);
         T::encode(&value, &mut encoder).unwrap();
-        let output = encoder.output.clone().into_vec();
+        let output = encoder.output();
         assert_eq!(
             expected
                 .iter()
@@ -1840,4 +2199,95 @@ mod tests {
                 .starts_with(&[0b10000010, 0b10111000])
         );
     }
+
+    #[test]
+    fn character_outside_of_alphabet() {
+        use crate::error::strings::PermittedAlphabetError;
+
+        let mut value = types::NumericString::from_bytes(b"12").unwrap();
+        value.push_char(u32::from(b'A'));
+
+        for error in [
+            crate::uper::encode(&value).unwrap_err(),
+            crate::aper::encode(&value).unwrap_err(),
+        ] {
+            assert!(matches!(
+                *error.kind,
+                crate::error::EncodeErrorKind::AlphabetConstraintNotSatisfied {
+                    reason: PermittedAlphabetError::CharacterNotFound { character: 0x41 },
+                }
+            ));
+        }
+        assert!(matches!(
+            value.to_index_string(),
+            Err(PermittedAlphabetError::CharacterNotFound { character: 0x41 })
+        ));
+        assert!(value.to_octet_aligned_index_string().is_err());
+    }
+
+    #[test]
+    fn constrained_integer_fast_path() {
+        use crate::types::{ConstrainedInteger, Integer};
+
+        #[derive(crate::AsnType, crate::Encode)]
+        #[rasn(crate_root = "crate")]
+        struct Fast {
+            a: bool,
+            b: ConstrainedInteger<-5, 249, i16>,
+            c: bool,
+            d: ConstrainedInteger<0, 255, u8>,
+            e: ConstrainedInteger<1, 65536, u32>,
+            f: ConstrainedInteger<0, 70000, u32>,
+        }
+
+        #[derive(crate::AsnType, crate::Encode)]
+        #[rasn(crate_root = "crate")]
+        struct General {
+            a: bool,
+            #[rasn(value("-5..=249"))]
+            b: Integer,
+            c: bool,
+            #[rasn(value("0..=255"))]
+            d: Integer,
+            #[rasn(value("1..=65536"))]
+            e: Integer,
+            #[rasn(value("0..=70000"))]
+            f: Integer,
+        }
+
+        for (b, d, e, f) in [
+            (-5, 0, 1, 0),
+            (249, 255, 65536, 70000),
+            (7, 128, 300, 65537),
+        ] {
+            let fast = Fast {
+                a: true,
+                b: b.try_into().unwrap(),
+                c: false,
+                d: d.try_into().unwrap(),
+                e: e.try_into().unwrap(),
+                f: f.try_into().unwrap(),
+            };
+            let general = General {
+                a: true,
+                b: b.into(),
+                c: false,
+                d: d.into(),
+                e: e.into(),
+                f: f.into(),
+            };
+            assert_eq!(
+                crate::uper::encode(&fast).unwrap(),
+                crate::uper::encode(&general).unwrap()
+            );
+            assert_eq!(
+                crate::aper::encode(&fast).unwrap(),
+                crate::aper::encode(&general).unwrap()
+            );
+        }
+
+        // Values outside of the bounds are still reported.
+        let value = ConstrainedInteger::<0, 10, u8>::new(11u8);
+        assert!(crate::uper::encode(&value).is_err());
+    }
 }
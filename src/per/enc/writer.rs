@@ -0,0 +1,272 @@
+//! Appending bits to Packed Encoding Rules output.
+
+use core::ops::Range;
+
+use bitvec::{domain::Domain, field::BitField};
+
+use crate::types::{BitStr, BitString};
+
+/// Appends to an encoder's output a word or an octet at a time, rather than
+/// a bit at a time, the counterpart of the decoder's `BitCursor`.
+pub(super) trait BitWriter {
+    /// Appends the low `bits` bits, at most 64, of `value` in big-endian
+    /// order.
+    fn write_bits(&mut self, value: u64, bits: usize);
+
+    /// Appends `octets`, copying them straight into the output when it ends
+    /// on an octet boundary, as in APER.
+    fn write_octets(&mut self, octets: &[u8]);
+
+    /// Appends `bits`, as its partial leading and trailing bytes and the
+    /// whole octets between them.
+    fn write_bitslice(&mut self, bits: &BitStr);
+}
+
+impl BitWriter for BitString {
+    fn write_bits(&mut self, value: u64, bits: usize) {
+        debug_assert!(bits <= 64);
+        if bits == 0 {
+            return;
+        }
+        let start = self.len();
+        self.resize(start + bits, false);
+        self[start..].store_be(value);
+    }
+
+    fn write_octets(&mut self, octets: &[u8]) {
+        let start = self.len();
+        // Keeps the output's first bit at the start of its first byte, so
+        // that bit `start` is in byte `start / 8`.
+        self.force_align();
+        self.resize(start + octets.len() * 8, false);
+        let shift = start % 8;
+        let output = &mut self.as_raw_mut_slice()[start / 8..];
+        if shift == 0 {
+            output[..octets.len()].copy_from_slice(octets);
+            return;
+        }
+        // Every octet ends the output's last byte and starts the next one.
+        for (index, &octet) in octets.iter().enumerate() {
+            output[index] |= octet >> shift;
+            output[index + 1] = octet << (8 - shift);
+        }
+    }
+
+    fn write_bitslice(&mut self, bits: &BitStr) {
+        match bits.domain() {
+            Domain::Enclave(element) => {
+                let end = element.tail().into_inner();
+                let width = end - element.head().into_inner();
+                self.write_bits(u64::from(element.load_value() >> (8 - end)), width.into());
+            }
+            Domain::Region { head, body, tail } => {
+                if let Some(element) = head {
+                    self.write_bits(
+                        element.load_value().into(),
+                        (8 - element.head().into_inner()).into(),
+                    );
+                }
+                self.write_octets(body);
+                if let Some(element) = tail {
+                    let end = element.tail().into_inner();
+                    self.write_bits(u64::from(element.load_value() >> (8 - end)), end.into());
+                }
+            }
+        }
+    }
+}
+
+/// The bits appended by an encoder, or only their number when the encoder
+/// counts the length of an encoding instead of writing it.
+#[derive(Debug, Default)]
+pub(super) struct BitBuffer {
+    bits: BitString,
+    /// The number of bits appended, when they are only counted.
+    counted: Option<usize>,
+}
+
+impl BitBuffer {
+    /// Creates a buffer that only counts the bits appended to it.
+    pub(super) fn counting() -> Self {
+        Self {
+            bits: BitString::new(),
+            counted: Some(0),
+        }
+    }
+
+    /// Creates an empty buffer that writes or counts as `self` does.
+    pub(super) fn empty_like(&self) -> Self {
+        self.with_capacity_like(0)
+    }
+
+    /// Creates an empty buffer for `capacity` bits that writes or counts as
+    /// `self` does.
+    pub(super) fn with_capacity_like(&self, capacity: usize) -> Self {
+        match self.counted {
+            Some(_) => Self::counting(),
+            None => BitString::with_capacity(capacity).into(),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.counted.unwrap_or(self.bits.len())
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether every bit is zero. Bits that are only counted are unknown, so
+    /// this is only answered for written bits.
+    pub(super) fn not_any(&self) -> Option<bool> {
+        self.counted.is_none().then(|| self.bits.not_any())
+    }
+
+    pub(super) fn clear(&mut self) {
+        match &mut self.counted {
+            Some(counted) => *counted = 0,
+            None => self.bits.clear(),
+        }
+    }
+
+    pub(super) fn push(&mut self, bit: bool) {
+        match &mut self.counted {
+            Some(counted) => *counted += 1,
+            None => self.bits.push(bit),
+        }
+    }
+
+    /// Grows or shrinks the buffer to `len` bits, appending zeros.
+    pub(super) fn resize(&mut self, len: usize) {
+        match &mut self.counted {
+            Some(counted) => *counted = len,
+            None => self.bits.resize(len, false),
+        }
+    }
+
+    /// Sets the bit at `index`, which is only kept when written.
+    pub(super) fn set(&mut self, index: usize, bit: bool) {
+        if self.counted.is_none() {
+            self.bits.set(index, bit);
+        }
+    }
+
+    /// Appends the bits of `other`.
+    pub(super) fn append(&mut self, other: &Self) {
+        match &mut self.counted {
+            Some(counted) => *counted += other.len(),
+            None => self.bits.write_bitslice(&other.bits),
+        }
+    }
+
+    /// Appends the octets at `range` of `other`, which is octet aligned.
+    pub(super) fn append_octets(&mut self, other: &Self, range: Range<usize>) {
+        match &mut self.counted {
+            Some(counted) => *counted += range.len() * 8,
+            None => self
+                .bits
+                .write_bitslice(&other.bits[range.start * 8..range.end * 8]),
+        }
+    }
+
+    /// Appends zeros up to the next octet boundary.
+    pub(super) fn pad_to_octet(&mut self) {
+        self.resize(self.len().next_multiple_of(8));
+    }
+
+    pub(super) fn into_bitstring(self) -> BitString {
+        self.bits
+    }
+}
+
+impl From<BitString> for BitBuffer {
+    fn from(bits: BitString) -> Self {
+        Self {
+            bits,
+            counted: None,
+        }
+    }
+}
+
+impl BitWriter for BitBuffer {
+    fn write_bits(&mut self, value: u64, bits: usize) {
+        match &mut self.counted {
+            Some(counted) => *counted += bits,
+            None => self.bits.write_bits(value, bits),
+        }
+    }
+
+    fn write_octets(&mut self, octets: &[u8]) {
+        match &mut self.counted {
+            Some(counted) => *counted += octets.len() * 8,
+            None => self.bits.write_octets(octets),
+        }
+    }
+
+    fn write_bitslice(&mut self, bits: &BitStr) {
+        match &mut self.counted {
+            Some(counted) => *counted += bits.len(),
+            None => self.bits.write_bitslice(bits),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn write_bits() {
+        for start in 0..16 {
+            for bits in 0..=64 {
+                let value = 0xA5C3_0F96_5A3C_F069_u64;
+                let mut output = bitvec![u8, Msb0; 1; start];
+                output.write_bits(value, bits);
+
+                let mut expected = bitvec![u8, Msb0; 1; start];
+                expected.extend_from_bitslice(&value.view_bits::<Msb0>()[64 - bits..]);
+                assert_eq!(output, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn write_octets() {
+        let octets = [0xC7, 0x7F, 0x01];
+
+        let mut aligned = bitvec![u8, Msb0; 0; 8];
+        aligned.write_octets(&octets);
+        assert_eq!(aligned.as_raw_slice(), [0x00, 0xC7, 0x7F, 0x01]);
+
+        let mut unaligned = bitvec![u8, Msb0; 1; 3];
+        unaligned.write_octets(&octets);
+        assert_eq!(unaligned.len(), 27);
+        assert_eq!(unaligned.as_raw_slice(), [0xF8, 0xEF, 0xE0, 0x20]);
+
+        let mut offset = bitvec![u8, Msb0; 1; 12].split_off(5);
+        offset.write_octets(&octets[..1]);
+        assert_eq!(
+            offset,
+            bits![u8, Msb0; 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn write_bitslice() {
+        let input = bits![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 1, 1, 0, 1, 0, 1, 1];
+        for prefix in 0..9 {
+            for start in 0..input.len() {
+                for end in start..=input.len() {
+                    let mut output = bitvec![u8, Msb0; 0; prefix];
+                    output.write_bitslice(&input[start..end]);
+
+                    let mut expected = bitvec![u8, Msb0; 0; prefix];
+                    expected.extend_from_bitslice(&input[start..end]);
+                    assert_eq!(output, expected);
+                }
+            }
+        }
+    }
+}
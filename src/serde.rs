@@ -0,0 +1,489 @@
+//! Support for the [`serde`](https://serde.rs) data model.
+//!
+//! With the `serde` feature enabled, the types in [`crate::types`] implement
+//! `Serialize` and `Deserialize` using their JER (X.697) representation:
+//!
+//! - `OBJECT IDENTIFIER` is a dotted string such as `"1.3.6.1"`.
+//! - `OCTET STRING` and [`Any`] are hexadecimal strings such as `"CAFE"`.
+//! - `INTEGER` is a number when it fits in 64 bits, and a decimal string
+//!   otherwise.
+//! - The restricted character strings are strings.
+//! - [`GeneralizedTimeString`], [`UtcTimeString`] and [`DateString`] are
+//!   strings of their text, such as `"20240229120000Z"`.
+//! - `SET OF` is a sequence.
+//! - Tagged wrappers such as [`Implicit`] are transparent.
+//!
+//! `BIT STRING` and the `chrono` and `time` types used for the time types
+//! come from other crates, so they can't implement these traits here, and
+//! `ENUMERATED` types are defined by users. All of them, and every other type
+//! that implements [`Encode`] and [`Decode`], are mapped to JER by
+//! `#[rasn(serde)]` on derived types, the [`Jer`] wrapper, or the [`jer`]
+//! module with `#[serde(with = "rasn::serde::jer")]`.
+//!
+//! ```
+//! use rasn::prelude::*;
+//!
+//! #[derive(AsnType, Decode, Encode, Debug, Clone, Copy, PartialEq)]
+//! #[rasn(enumerated, serde)]
+//! enum Level {
+//!     Low,
+//!     High,
+//! }
+//!
+//! #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+//! #[rasn(automatic_tags, serde)]
+//! struct Message {
+//!     id: ObjectIdentifier,
+//!     flags: BitString,
+//!     level: Level,
+//! }
+//!
+//! let message = Message {
+//!     id: ObjectIdentifier::new(&[1, 3, 6, 1]).unwrap(),
+//!     flags: BitString::from_slice(&[0x80]),
+//!     level: Level::High,
+//! };
+//! let json = serde_json::to_string(&message).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"flags":{"length":8,"value":"80"},"id":"1.3.6.1","level":"High"}"#
+//! );
+//! assert_eq!(message, serde_json::from_str(&json).unwrap());
+//! assert_eq!(serde_json::to_string(&Level::Low).unwrap(), r#""Low""#);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+pub use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ::serde::{de, ser};
+
+use crate::{
+    Decode, Encode,
+    types::{
+        Any, BmpString, DateString, Explicit, FixedOctetString, GeneralString,
+        GeneralizedTimeString, GraphicString, Ia5String, Implicit, Integer, NumericString,
+        ObjectIdentifier, OctetString, Oid, PrintableString, SetOf, TeletexString, UtcTimeString,
+        VisibleString, strings::StaticPermittedAlphabet,
+    },
+};
+
+/// Serializes and deserializes any ASN.1 type using its JER mapping.
+///
+/// This module can be used with `#[serde(with = "rasn::serde::jer")]` on
+/// fields whose type does not implement `Serialize` itself.
+pub mod jer {
+    use super::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+    use crate::{Decode, Encode};
+
+    /// Serializes `value` as the JSON value of its JER encoding.
+    pub fn serialize<T: Encode, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut encoder = crate::jer::enc::Encoder::new().with_instructions(T::JER_INSTRUCTIONS);
+        value.encode(&mut encoder).map_err(ser::Error::custom)?;
+        encoder
+            .to_json()
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Deserializes a value from the JSON value of its JER encoding.
+    pub fn deserialize<'de, T: Decode, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        T::decode(&mut crate::jer::de::Decoder::from(value).with_instructions(T::JER_INSTRUCTIONS))
+            .map_err(de::Error::custom)
+    }
+}
+
+/// A wrapper that implements `Serialize` and `Deserialize` for any ASN.1 type
+/// using its JER mapping.
+///
+/// ```
+/// use rasn::{serde::Jer, types::BitString};
+///
+/// let bits = BitString::from_slice(&[0xF0]);
+/// let json = serde_json::to_string(&Jer(&bits)).unwrap();
+/// assert_eq!(json, r#"{"length":8,"value":"F0"}"#);
+/// let Jer(decoded): Jer<BitString> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(bits, decoded);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Jer<T>(pub T);
+
+impl<T: Encode> Serialize for Jer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        jer::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Decode> Deserialize<'de> for Jer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        jer::deserialize(deserializer).map(Self)
+    }
+}
+
+impl Serialize for Oid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for ObjectIdentifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectIdentifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        ObjectIdentifier::from_dotted(&string).map_err(|_| {
+            de::Error::invalid_value(de::Unexpected::Str(&string), &"a dotted object identifier")
+        })
+    }
+}
+
+/// Visits the hexadecimal string of an `OCTET STRING`.
+struct HexVisitor;
+
+impl de::Visitor<'_> for HexVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hexadecimal string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        crate::jer::de::bytes_from_hexstring(value)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+/// Serializes `bytes` as an uppercase hexadecimal string.
+fn serialize_hex<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    struct Hex<'a>(&'a [u8]);
+
+    impl fmt::Display for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+        }
+    }
+
+    serializer.collect_str(&Hex(bytes))
+}
+
+impl Serialize for OctetString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OctetString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(HexVisitor).map(Self::from)
+    }
+}
+
+impl<const N: usize> Serialize for FixedOctetString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(&**self, serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for FixedOctetString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserializer.deserialize_str(HexVisitor)?;
+        let length = bytes.len();
+        Self::try_from(bytes)
+            .map_err(|_| de::Error::invalid_length(length, &"a fixed size octet string"))
+    }
+}
+
+impl Serialize for Any {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Any {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(HexVisitor).map(Self::new)
+    }
+}
+
+macro_rules! impl_time_string {
+    ($($target:ty),* $(,)?) => {
+        $(
+            impl Serialize for $target {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_str())
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $target {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_time_string!(GeneralizedTimeString, UtcTimeString, DateString);
+
+impl Serialize for Integer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use num_traits::ToPrimitive;
+
+        if let Some(value) = self.to_i64() {
+            serializer.serialize_i64(value)
+        } else if let Some(value) = self.to_u64() {
+            serializer.serialize_u64(value)
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Integer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IntegerVisitor;
+
+        impl de::Visitor<'_> for IntegerVisitor {
+            type Value = Integer;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an integer or a decimal string")
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_i128<E: de::Error>(self, value: i128) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_u128<E: de::Error>(self, value: u128) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value
+                    .parse::<num_bigint::BigInt>()
+                    .map(Integer::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(IntegerVisitor)
+    }
+}
+
+macro_rules! impl_octet_aligned_string {
+    ($($target:ty),* $(,)?) => {
+        $(
+            impl Serialize for $target {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(core::str::from_utf8(self).map_err(ser::Error::custom)?)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_wide_string {
+    ($($target:ty),* $(,)?) => {
+        $(
+            impl Serialize for $target {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(
+                        &self
+                            .chars()
+                            .map(|ch| char::from_u32(ch).ok_or_else(|| ser::Error::custom("invalid character")))
+                            .collect::<Result<String, S::Error>>()?,
+                    )
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $target {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let mut string = Self::default();
+                    for ch in String::deserialize(deserializer)?.chars().map(u32::from) {
+                        if !Self::contains_char(ch) {
+                            return Err(de::Error::custom(Self::invalid_restricted_string(ch)));
+                        }
+                        string.push_char(ch);
+                    }
+                    Ok(string)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_string_deserialize {
+    ($($target:ty),* $(,)?) => {
+        $(
+            impl<'de> Deserialize<'de> for $target {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Self::try_from(String::deserialize(deserializer)?).map_err(de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_octet_aligned_string!(
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    VisibleString,
+);
+impl_wide_string!(BmpString, TeletexString);
+impl_string_deserialize!(
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    VisibleString,
+);
+
+impl<T: Serialize> Serialize for SetOf<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.to_vec())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SetOf<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from_vec)
+    }
+}
+
+macro_rules! impl_transparent_prefix {
+    ($($name:ident),+) => {
+        $(
+            impl<T, V: Serialize> Serialize for $name<T, V> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.value.serialize(serializer)
+                }
+            }
+
+            impl<'de, T, V: Deserialize<'de>> Deserialize<'de> for $name<T, V> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    V::deserialize(deserializer).map(Self::new)
+                }
+            }
+        )+
+    };
+}
+
+impl_transparent_prefix!(Implicit, Explicit);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::types::strings::StaticPermittedAlphabet;
+
+    #[test]
+    fn universal_types() {
+        let oid = ObjectIdentifier::new(alloc::vec![1, 3, 6, 1]).unwrap();
+        assert_eq!(serde_json::to_string(&oid).unwrap(), r#""1.3.6.1""#);
+        assert_eq!(
+            serde_json::from_str::<ObjectIdentifier>(r#""1.3.6.1""#).unwrap(),
+            oid
+        );
+        assert!(serde_json::from_str::<ObjectIdentifier>(r#""1.x""#).is_err());
+
+        let octets = OctetString::from_static(&[1, 2, 255]);
+        assert_eq!(serde_json::to_string(&octets).unwrap(), r#""0102FF""#);
+        assert_eq!(
+            serde_json::from_str::<OctetString>(r#""0102ff""#).unwrap(),
+            octets
+        );
+        assert!(serde_json::from_str::<OctetString>("[1,2,255]").is_err());
+        let fixed = FixedOctetString::<2>::from([0xCA, 0xFE]);
+        assert_eq!(serde_json::to_string(&fixed).unwrap(), r#""CAFE""#);
+        assert!(serde_json::from_str::<FixedOctetString<2>>(r#""CA""#).is_err());
+        assert_eq!(
+            serde_json::to_string(&Any::new(vec![0x05, 0x00])).unwrap(),
+            r#""0500""#
+        );
+
+        let time: GeneralizedTimeString = "20240229120000.5+0100".parse().unwrap();
+        let json = serde_json::to_string(&time).unwrap();
+        assert_eq!(json, r#""20240229120000.5+0100""#);
+        assert_eq!(
+            serde_json::from_str::<GeneralizedTimeString>(&json).unwrap(),
+            time
+        );
+        assert!(serde_json::from_str::<UtcTimeString>(r#""2402291200""#).is_err());
+        let date: DateString = serde_json::from_str(r#""20240229""#).unwrap();
+        assert_eq!(serde_json::to_string(&date).unwrap(), r#""20240229""#);
+
+        let big = Integer::from(u128::MAX);
+        let json = serde_json::to_string(&big).unwrap();
+        assert_eq!(json, format!("\"{}\"", u128::MAX));
+        assert_eq!(serde_json::from_str::<Integer>(&json).unwrap(), big);
+        assert_eq!(serde_json::to_string(&Integer::from(-5)).unwrap(), "-5");
+        assert_eq!(
+            serde_json::from_str::<Integer>("-5").unwrap(),
+            Integer::from(-5)
+        );
+
+        let ia5 = Ia5String::try_from("rasn").unwrap();
+        assert_eq!(serde_json::to_string(&ia5).unwrap(), r#""rasn""#);
+        assert_eq!(serde_json::from_str::<Ia5String>(r#""rasn""#).unwrap(), ia5);
+        assert!(serde_json::from_str::<NumericString>(r#""rasn""#).is_err());
+
+        let mut bmp = BmpString::default();
+        "äΩ".chars().for_each(|ch| bmp.push_char(ch.into()));
+        assert_eq!(serde_json::to_string(&bmp).unwrap(), r#""äΩ""#);
+        assert_eq!(serde_json::from_str::<BmpString>(r#""äΩ""#).unwrap(), bmp);
+        assert!(serde_json::from_str::<BmpString>(r#""😀""#).is_err());
+    }
+
+    #[test]
+    fn derived_types() {
+        #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+        #[rasn(automatic_tags, choice, serde)]
+        #[rasn(crate_root = "crate")]
+        enum Status {
+            Ok(bool),
+            Code(u16),
+        }
+
+        #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+        #[rasn(automatic_tags, serde)]
+        #[rasn(crate_root = "crate")]
+        struct Report {
+            name: Utf8String,
+            status: Status,
+            payload: OctetString,
+        }
+
+        let report = Report {
+            name: "probe".into(),
+            status: Status::Code(404),
+            payload: OctetString::from_static(&[0xCA, 0xFE]),
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"probe","payload":"CAFE","status":{"Code":404}}"#
+        );
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
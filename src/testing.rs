@@ -0,0 +1,161 @@
+//! Helpers for testing that values survive being encoded and decoded,
+//! available with the `testing` feature.
+
+use core::fmt::Debug;
+
+use crate::{Codec, Decode, Encode, error::DecodeError, error::EncodeError, types::Constraints};
+
+/// The codecs checked by [`round_trip_all_codecs`], which is every codec.
+///
+/// JER encodes integers as JSON numbers, which only cover the ranges of an
+/// `i64` and a `u64`, so integers outside of both have to be checked without
+/// [`Codec::Jer`].
+pub const ALL_CODECS: &[Codec] = &[
+    Codec::Ber,
+    Codec::Cer,
+    Codec::Der,
+    Codec::Uper,
+    Codec::Aper,
+    Codec::Oer,
+    Codec::Coer,
+    Codec::Jer,
+    Codec::Xer,
+    Codec::Cxer,
+    Codec::Avn,
+];
+
+/// Encodes `value` with each of the [`ALL_CODECS`], decodes it back, and checks that the decoded value is equal to `value`.
+///
+/// ```
+/// rasn::testing::round_trip_all_codecs(&rasn::types::Integer::from(42));
+/// ```
+///
+/// # Panics
+/// Panics if `value` fails to be encoded or decoded with any of the codecs,
+/// or if a decoded value isn't equal to `value`.
+#[track_caller]
+pub fn round_trip_all_codecs<T: Encode + Decode + PartialEq + Debug>(value: &T) {
+    round_trip(ALL_CODECS, value);
+}
+
+/// Encodes `value` with each of `codecs`, decodes it back, and checks that
+/// the decoded value is equal to `value`. The binary codecs also check that
+/// [`Codec::encoded_len`] counts the length of the encoding.
+///
+/// # Panics
+/// Panics if `value` fails to be encoded or decoded with any of `codecs`, if
+/// a counted length is wrong, or if a decoded value isn't equal to `value`.
+#[track_caller]
+pub fn round_trip<T: Encode + Decode + PartialEq + Debug>(codecs: &[Codec], value: &T) {
+    for codec in codecs {
+        let encoded = match codec.encode_to_binary(value) {
+            Ok(encoded) => encoded,
+            Err(error) => panic!("error encoding {value:?} with {codec}: {error}"),
+        };
+        check_encoded_len(*codec, value, &encoded);
+        let decoded: T = match codec.decode_from_binary(&encoded) {
+            Ok(decoded) => decoded,
+            Err(error) => panic!("error decoding {value:?} with {codec}: {error}"),
+        };
+        assert_eq!(
+            value, &decoded,
+            "{codec} decoded a different value from {encoded:02x?}"
+        );
+    }
+}
+
+/// Encodes `value` with `codec`, checks that the encoding is `expected`, and
+/// checks that decoding `expected` gives back `value`. Binary codecs also
+/// check that [`Codec::encoded_len`] counts the length of `expected`.
+///
+/// ```
+/// rasn::testing::round_trip_encoding(rasn::Codec::Ber, &true, &[0x01, 0x01, 0xFF]);
+/// ```
+///
+/// # Panics
+/// Panics if `value` fails to be encoded or decoded, if the encoding or its
+/// counted length isn't `expected`, or if the decoded value isn't equal to
+/// `value`.
+#[track_caller]
+pub fn round_trip_encoding<T: Encode + Decode + PartialEq + Debug>(
+    codec: Codec,
+    value: &T,
+    expected: &[u8],
+) {
+    check_round_trip(
+        codec,
+        value,
+        expected,
+        codec.encode_to_binary(value),
+        |encoded| codec.decode_from_binary(encoded),
+    );
+    check_encoded_len(codec, value, expected);
+}
+
+/// Same as [`round_trip_encoding`], but encodes and decodes `value` with
+/// `constraints` in place of the constraints of `T`. Only the PER and OER
+/// codecs are affected by constraints, so `codec` has to be one of them.
+///
+/// # Panics
+/// Panics if `codec` isn't a PER or OER codec, if `value` fails to be encoded
+/// or decoded, if the encoding isn't `expected`, or if the decoded value isn't
+/// equal to `value`.
+#[track_caller]
+pub fn round_trip_encoding_with_constraints<T: Encode + Decode + PartialEq + Debug>(
+    codec: Codec,
+    constraints: Constraints,
+    value: &T,
+    expected: &[u8],
+) {
+    let encoded = match codec {
+        Codec::Aper => crate::aper::encode_with_constraints(constraints, value),
+        Codec::Uper => crate::uper::encode_with_constraints(constraints, value),
+        Codec::Oer => crate::oer::encode_with_constraints(constraints, value),
+        Codec::Coer => crate::coer::encode_with_constraints(constraints, value),
+        _ => panic!("{codec} can't encode with other constraints"),
+    };
+    check_round_trip(codec, value, expected, encoded, |encoded| match codec {
+        Codec::Aper => crate::aper::decode_with_constraints(constraints, encoded),
+        Codec::Uper => crate::uper::decode_with_constraints(constraints, encoded),
+        Codec::Oer => crate::oer::decode_with_constraints(constraints, encoded),
+        _ => crate::coer::decode_with_constraints(constraints, encoded),
+    });
+}
+
+#[track_caller]
+fn check_round_trip<T: PartialEq + Debug>(
+    codec: Codec,
+    value: &T,
+    expected: &[u8],
+    encoded: Result<alloc::vec::Vec<u8>, EncodeError>,
+    decode: impl FnOnce(&[u8]) -> Result<T, DecodeError>,
+) {
+    let encoded = match encoded {
+        Ok(encoded) => encoded,
+        Err(error) => panic!("error encoding {value:?} with {codec}: {error}"),
+    };
+    assert_eq!(expected, &*encoded, "{codec} encoded {value:?} differently");
+    let decoded = match decode(&encoded) {
+        Ok(decoded) => decoded,
+        Err(error) => panic!("error decoding {value:?} with {codec}: {error}"),
+    };
+    assert_eq!(
+        value, &decoded,
+        "{codec} decoded a different value from {encoded:02x?}"
+    );
+}
+
+#[track_caller]
+fn check_encoded_len<T: Encode + Debug>(codec: Codec, value: &T, encoded: &[u8]) {
+    if matches!(codec, Codec::Jer | Codec::Xer | Codec::Cxer | Codec::Avn) {
+        return;
+    }
+    match codec.encoded_len(value) {
+        Ok(len) => assert_eq!(
+            encoded.len(),
+            len,
+            "{codec} counted a different length for {value:?}"
+        ),
+        Err(error) => panic!("error counting {value:?} with {codec}: {error}"),
+    }
+}
@@ -0,0 +1,47 @@
+//! Structured tracing of the steps taken by the codecs.
+//!
+//! With the `tracing` feature enabled, the BER, PER and OER encoders and
+//! decoders emit [`tracing`](https://docs.rs/tracing) spans and events at the
+//! `TRACE` level under the `rasn` target. Offsets count bits for PER and
+//! octets for BER and OER. Decoders trace offsets from the start of their
+//! input, while encoders trace offsets into the buffer that a value is being
+//! written to, which may be an intermediate buffer for nested values.
+//!
+//! Without the feature, these macros expand to nothing and their arguments
+//! are never evaluated.
+
+/// Enters a span until the end of the enclosing block.
+macro_rules! trace_span {
+    ($($args:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::trace_span!(target: "rasn", $($args)+).entered();
+    };
+}
+
+/// Emits an event.
+macro_rules! trace_event {
+    ($($args:tt)+) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!(target: "rasn", $($args)+);
+    };
+}
+
+/// Enters a span for a field of a `SEQUENCE`, `SET` or `CHOICE` until the end
+/// of the enclosing block. Used by the derive macros.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_field {
+    ($name:expr) => {
+        let _span = $crate::__tracing::trace_span!(target: "rasn", "field", name = $name).entered();
+    };
+}
+
+/// Enters a span for a field of a `SEQUENCE`, `SET` or `CHOICE` until the end
+/// of the enclosing block. Used by the derive macros.
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_field {
+    ($name:expr) => {};
+}
@@ -2,6 +2,20 @@
 //! The `types` modules is a collection of Rust types and data structures that
 //! are defined to represent various ASN.1 data types, and renamed to use
 //! ASN.1's terminology.
+//!
+//! ## Dates and times
+//! [`UtcTime`], [`GeneralizedTime`] and [`Date`] are `chrono` types with the
+//! `chrono` feature, which is enabled by default. With only the `time` feature
+//! they are `time` types, and without either they are [`UtcTimeString`],
+//! [`GeneralizedTimeString`] and [`DateString`], which keep the text of the
+//! value as it was written.
+//!
+//! The features can be enabled together, in which case `chrono` takes
+//! precedence for the aliases, and `time::UtcDateTime`,
+//! `time::OffsetDateTime` and `time::Date` still implement [`AsnType`],
+//! [`Encode`][crate::Encode] and [`Decode`][crate::Decode] by converting from
+//! and to the `chrono` types. Code written against the `time` types keeps
+//! working when another crate enables `chrono`.
 
 mod any;
 mod identifier;
@@ -13,6 +27,8 @@ mod tag;
 
 pub mod constraints;
 pub mod fields;
+pub mod instructions;
+pub mod oid_registry;
 pub mod variants;
 
 pub(crate) mod constructed;
@@ -23,6 +39,8 @@ pub(crate) mod oid;
 pub(crate) mod real;
 
 pub(crate) mod strings;
+pub(crate) mod time_backend;
+pub(crate) mod time_string;
 
 use crate::macros::constraints;
 use alloc::borrow::{Cow, ToOwned};
@@ -32,19 +50,22 @@ pub use {
     self::{
         any::Any,
         constraints::{Constraint, Constraints, Extensible, InnerSubtypeConstraint},
-        constructed::{Constructed, SequenceOf, SetOf},
+        constructed::{Constructed, SequenceOf, SetOf, SortedSetOf},
         identifier::Identifier,
         instance::InstanceOf,
         integer::{ConstrainedInteger, Integer, IntegerType},
-        oid::{ObjectIdentifier, Oid},
+        oid::{ArcNotation, Arcs, ObjectIdentifier, Oid, OidArc, ParseOidError},
         open::Open,
         prefix::{Explicit, Implicit},
         strings::{
             BitStr, BitString, BmpString, FixedBitString, FixedOctetString, GeneralString,
             GraphicString, Ia5String, NumericString, OctetString, PrintableString, TeletexString,
-            Utf8String, VisibleString,
+            Utf8String, VisibleString, set_named_bit, trim_named_bits,
         },
         tag::{Class, Tag, TagTree},
+        time_string::{
+            DateString, GeneralizedTimeString, ParseTimeError, TimeProfile, TimeZone, UtcTimeString,
+        },
     },
     rasn_derive::AsnType,
 };
@@ -53,12 +74,30 @@ pub use self::real::RealType;
 
 ///  The `UniversalString` type.
 pub type UniversalString = Implicit<tag::UNIVERSAL_STRING, Utf8String>;
-///  The `UTCTime` type.
-pub type UtcTime = chrono::DateTime<chrono::Utc>;
-///  The `GeneralizedTime` type.
-pub type GeneralizedTime = chrono::DateTime<chrono::FixedOffset>;
-/// The `Date` type.
-pub type Date = chrono::NaiveDate;
+cfg_if::cfg_if! {
+    if #[cfg(feature = "chrono")] {
+        ///  The `UTCTime` type.
+        pub type UtcTime = chrono::DateTime<chrono::Utc>;
+        ///  The `GeneralizedTime` type.
+        pub type GeneralizedTime = chrono::DateTime<chrono::FixedOffset>;
+        /// The `Date` type.
+        pub type Date = chrono::NaiveDate;
+    } else if #[cfg(feature = "time")] {
+        ///  The `UTCTime` type.
+        pub type UtcTime = time::UtcDateTime;
+        ///  The `GeneralizedTime` type.
+        pub type GeneralizedTime = time::OffsetDateTime;
+        /// The `Date` type.
+        pub type Date = time::Date;
+    } else {
+        ///  The `UTCTime` type.
+        pub type UtcTime = UtcTimeString;
+        ///  The `GeneralizedTime` type.
+        pub type GeneralizedTime = GeneralizedTimeString;
+        /// The `Date` type.
+        pub type Date = DateString;
+    }
+}
 
 /// A trait representing any type that can represented in ASN.1.
 pub trait AsnType {
@@ -82,6 +121,12 @@ pub trait AsnType {
     /// Whether the type is choice type. PER/OER encoding rules require this knowledge.
     const IS_CHOICE: bool = false;
 
+    /// E-XER encoding instructions that apply to the type itself.
+    const XER_INSTRUCTIONS: instructions::XerInstructions = instructions::XerInstructions::NONE;
+
+    /// JER encoding instructions that apply to the type itself.
+    const JER_INSTRUCTIONS: instructions::JerInstructions = instructions::JerInstructions::NONE;
+
     /// Whether the type is present with value. `OPTIONAL` fields are common in `SEQUENCE` or `SET`.
     ///
     /// Custom implementation is only used for `OPTIONAL` type.
@@ -301,6 +346,8 @@ impl<T: AsnType> AsnType for &'_ T {
     const TAG: Tag = T::TAG;
     const TAG_TREE: TagTree = T::TAG_TREE;
     const IDENTIFIER: Identifier = T::IDENTIFIER;
+    const XER_INSTRUCTIONS: instructions::XerInstructions = T::XER_INSTRUCTIONS;
+    const JER_INSTRUCTIONS: instructions::JerInstructions = T::JER_INSTRUCTIONS;
 
     fn is_present(&self) -> bool {
         (*self).is_present()
@@ -311,12 +358,16 @@ impl<T: AsnType> AsnType for Box<T> {
     const TAG: Tag = T::TAG;
     const TAG_TREE: TagTree = T::TAG_TREE;
     const IDENTIFIER: Identifier = T::IDENTIFIER;
+    const XER_INSTRUCTIONS: instructions::XerInstructions = T::XER_INSTRUCTIONS;
+    const JER_INSTRUCTIONS: instructions::JerInstructions = T::JER_INSTRUCTIONS;
 }
 
 impl<'a, T: 'a + ToOwned + AsnType> AsnType for Cow<'a, T> {
     const TAG: Tag = T::TAG;
     const TAG_TREE: TagTree = T::TAG_TREE;
     const IDENTIFIER: Identifier = T::IDENTIFIER;
+    const XER_INSTRUCTIONS: instructions::XerInstructions = T::XER_INSTRUCTIONS;
+    const JER_INSTRUCTIONS: instructions::JerInstructions = T::JER_INSTRUCTIONS;
 }
 
 impl<T: AsnType> AsnType for alloc::vec::Vec<T> {
@@ -328,6 +379,8 @@ impl<T: AsnType> AsnType for Option<T> {
     const TAG: Tag = T::TAG;
     const TAG_TREE: TagTree = T::TAG_TREE;
     const IDENTIFIER: Identifier = T::IDENTIFIER;
+    const XER_INSTRUCTIONS: instructions::XerInstructions = T::XER_INSTRUCTIONS;
+    const JER_INSTRUCTIONS: instructions::JerInstructions = T::JER_INSTRUCTIONS;
 
     fn is_present(&self) -> bool {
         self.is_some()
@@ -339,6 +392,11 @@ impl<T> AsnType for SetOf<T> {
     const IDENTIFIER: Identifier = Identifier::SET_OF;
 }
 
+impl<T> AsnType for SortedSetOf<T> {
+    const TAG: Tag = Tag::SET;
+    const IDENTIFIER: Identifier = Identifier::SET_OF;
+}
+
 impl<T: AsnType, const N: usize> AsnType for [T; N] {
     const TAG: Tag = Tag::SEQUENCE;
     const CONSTRAINTS: Constraints = constraints!(size_constraint!(N));
@@ -1,17 +1,71 @@
 use alloc::vec::Vec;
 
+use crate::{
+    Codec, Decode, Encode,
+    error::{DecodeError, EncodeError},
+    types::Tag,
+};
+
 /// Represents a complete encoded ASN.1 value of any type (an open type in modern ASN.1).
 /// Commonly associated with an [`ObjectIdentifier`][crate::types::ObjectIdentifier].
-#[derive(Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash)]
+///
+/// The contents are the encoding of the value with the codec the `Any` was
+/// decoded with, which [`Any::codec`] returns. For BER, CER and DER that is
+/// the complete encoding of the value including its identifier and length,
+/// and for the other codecs it is the encoding of the value an open type
+/// holds. An `Any` created with [`Any::new`] doesn't know its codec, and its
+/// contents are treated as BER.
+///
+/// The value is only decoded when asked for, with [`Any::decode`] or
+/// [`Any::decode_as`].
+///
+/// ```rust
+/// use rasn::{Codec, types::{Any, Tag}};
+///
+/// let any: Any = rasn::der::decode(&[0x02, 0x01, 0x2A]).unwrap();
+/// assert_eq!(any.codec(), Some(Codec::Der));
+/// assert_eq!(any.tag(), Some(Tag::INTEGER));
+/// assert_eq!(any.decode::<u8>().unwrap(), 42);
+///
+/// let open = Any::from_value(Codec::Uper, &42u8).unwrap();
+/// assert_eq!(open.as_bytes(), [0x2A]);
+/// assert_eq!(open.tag(), None);
+/// assert_eq!(open.decode::<u8>().unwrap(), 42);
+/// ```
+#[derive(Clone, Debug)]
 pub struct Any {
     pub(crate) contents: Vec<u8>,
+    codec: Option<Codec>,
 }
 
 impl Any {
     /// Creates a new wrapper around the opaque value.
     #[must_use]
     pub fn new(contents: Vec<u8>) -> Self {
-        Self { contents }
+        Self {
+            contents,
+            codec: None,
+        }
+    }
+
+    /// Creates a new wrapper around a value encoded with `codec`.
+    #[must_use]
+    pub fn with_codec(contents: Vec<u8>, codec: Codec) -> Self {
+        Self {
+            contents,
+            codec: Some(codec),
+        }
+    }
+
+    /// Encodes `value` with `codec`, so that it can be embedded in a value
+    /// encoded with the same codec.
+    ///
+    /// # Errors
+    /// If `value` fails to be encoded.
+    pub fn from_value<T: Encode>(codec: Codec, value: &T) -> Result<Self, EncodeError> {
+        codec
+            .encode_to_binary(value)
+            .map(|contents| Self::with_codec(contents, codec))
     }
 
     /// Provides the raw representation of the value as bytes.
@@ -25,6 +79,98 @@ impl Any {
     pub fn into_bytes(self) -> Vec<u8> {
         self.contents
     }
+
+    /// Returns the codec the value is encoded with, if it is known.
+    #[must_use]
+    pub fn codec(&self) -> Option<Codec> {
+        self.codec
+    }
+
+    /// Returns the BER identifier of the value, which holds its tag and
+    /// whether it is constructed. Only BER, CER and DER encodings start with
+    /// an identifier, so this is `None` for the other codecs.
+    #[must_use]
+    pub fn identifier(&self) -> Option<crate::ber::Identifier> {
+        if self.codec.is_some_and(|codec| family(codec) != Codec::Ber) {
+            return None;
+        }
+
+        crate::ber::de::parser::parse_identifier_octet(&self.contents)
+            .ok()
+            .map(|(_, identifier)| identifier)
+    }
+
+    /// Returns the tag of the value, if it is encoded with BER, CER or DER.
+    #[must_use]
+    pub fn tag(&self) -> Option<Tag> {
+        self.identifier().map(|identifier| identifier.tag)
+    }
+
+    /// Decodes the value as a `T` with the codec it is encoded with, or BER
+    /// if the codec isn't known.
+    ///
+    /// # Errors
+    /// If the value isn't a valid encoding of a `T`.
+    pub fn decode<T: Decode>(&self) -> Result<T, DecodeError> {
+        self.decode_as(self.codec.unwrap_or(Codec::Ber))
+    }
+
+    /// Decodes the value as a `T` encoded with `codec`.
+    ///
+    /// # Errors
+    /// If the value isn't a valid `codec` encoding of a `T`.
+    pub fn decode_as<T: Decode>(&self, codec: Codec) -> Result<T, DecodeError> {
+        codec.decode_from_binary(&self.contents)
+    }
+
+    /// Checks that the contents can be embedded in an encoding with `codec`
+    /// as they are.
+    pub(crate) fn check_codec(&self, codec: Codec) -> Result<(), EncodeError> {
+        match self.codec {
+            Some(found) if family(found) != family(codec) => {
+                Err(EncodeError::any_codec_mismatch(found, codec))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns the codec that encodings with `codec` are also valid in.
+fn family(codec: Codec) -> Codec {
+    match codec {
+        Codec::Cer | Codec::Der => Codec::Ber,
+        Codec::Coer => Codec::Oer,
+        Codec::Cxer => Codec::Xer,
+        codec => codec,
+    }
+}
+
+// Values are compared by their contents only, the codec describes where they
+// came from.
+impl PartialEq for Any {
+    fn eq(&self, other: &Self) -> bool {
+        self.contents == other.contents
+    }
+}
+
+impl Eq for Any {}
+
+impl PartialOrd for Any {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Any {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.contents.cmp(&other.contents)
+    }
+}
+
+impl core::hash::Hash for Any {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.contents.hash(state);
+    }
 }
 
 impl AsRef<[u8]> for Any {
@@ -38,3 +184,47 @@ impl From<Vec<u8>> for Any {
         Any::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsnType, Decoder as _};
+
+    #[test]
+    fn open_type_round_trip() {
+        #[derive(AsnType, Decode, Encode, Debug, PartialEq)]
+        #[rasn(crate_root = "crate")]
+        struct Message {
+            id: u8,
+            value: Any,
+        }
+
+        for codec in [Codec::Uper, Codec::Aper, Codec::Oer, Codec::Coer] {
+            let message = Message {
+                id: 1,
+                value: Any::from_value(codec, &alloc::vec![true, false]).unwrap(),
+            };
+            let encoded = codec.encode_to_binary(&message).unwrap();
+            let decoded: Message = codec.decode_from_binary(&encoded).unwrap();
+
+            assert_eq!(decoded, message);
+            assert_eq!(decoded.value.codec(), Some(codec));
+            assert_eq!(decoded.value.tag(), None);
+            assert_eq!(decoded.value.decode::<Vec<bool>>().unwrap(), [true, false]);
+        }
+    }
+
+    #[test]
+    fn codec_mismatch() {
+        let any: Any = crate::ber::decode(&[0x01, 0x01, 0xFF]).unwrap();
+        assert_eq!(any.codec(), Some(Codec::Ber));
+        assert_eq!(any.tag(), Some(Tag::BOOL));
+        assert!(!any.identifier().unwrap().is_constructed());
+
+        assert_eq!(crate::der::encode(&any).unwrap(), [0x01, 0x01, 0xFF]);
+        assert!(crate::uper::encode(&any).is_err());
+        assert!(crate::oer::encode(&any).is_err());
+        assert!(crate::uper::encode(&Any::new(any.clone().into_bytes())).is_ok());
+        assert!(any.decode_as::<bool>(Codec::Der).unwrap());
+    }
+}
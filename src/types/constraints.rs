@@ -518,22 +518,280 @@ impl core::ops::DerefMut for Size {
     }
 }
 
-/// A range of alphabet characters a type can have.
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct PermittedAlphabet(&'static [u32]);
+/// An inclusive range of characters in a [`PermittedAlphabet`], such as
+/// `"A".."Z"` in `FROM ("A".."Z" | "0".."9")`.
+///
+/// Besides its characters, a range records the index of its first character
+/// in the alphabet, which [`CharacterRange::indexed`] fills in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CharacterRange {
+    start: u32,
+    end: u32,
+    offset: u32,
+}
+
+impl CharacterRange {
+    /// Creates the range of characters from `start` to `end`, both included.
+    ///
+    /// # Panics
+    /// If `start` is after `end`.
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Self {
+        assert!(start <= end, "a character range can't start after its end");
+        Self {
+            start,
+            end,
+            offset: 0,
+        }
+    }
+
+    /// Creates a range of a single character.
+    #[must_use]
+    pub const fn single(character: u32) -> Self {
+        Self::new(character, character)
+    }
+
+    /// Records in each range the index of its first character in the
+    /// alphabet made of `ranges`, as [`PermittedAlphabet::from_ranges`]
+    /// requires.
+    ///
+    /// # Panics
+    /// If the ranges are not in ascending order, if they overlap, or if the
+    /// alphabet has more than `u32::MAX` characters.
+    #[must_use]
+    pub const fn indexed<const N: usize>(mut ranges: [Self; N]) -> [Self; N] {
+        Self::index(&mut ranges);
+        ranges
+    }
+
+    /// Records the index of the first character of each range, like
+    /// [`CharacterRange::indexed`] for ranges only known at runtime.
+    pub(crate) const fn index(ranges: &mut [Self]) {
+        let mut offset = 0u32;
+        let mut i = 0;
+        while i < ranges.len() {
+            assert!(
+                i == 0 || ranges[i - 1].end < ranges[i].start,
+                "the ranges of a permitted alphabet must be ascending and must not overlap"
+            );
+            ranges[i].offset = offset;
+            offset = match offset.checked_add(ranges[i].end - ranges[i].start) {
+                Some(last) if last < u32::MAX => last + 1,
+                _ => panic!("a permitted alphabet can't have more than `u32::MAX` characters"),
+            };
+            i += 1;
+        }
+    }
+
+    /// Returns the first character of the range.
+    #[must_use]
+    pub const fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the last character of the range.
+    #[must_use]
+    pub const fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Returns whether `character` is in the range.
+    #[must_use]
+    pub const fn contains(&self, character: u32) -> bool {
+        self.start <= character && character <= self.end
+    }
+
+    /// The number of characters in the range.
+    const fn size(&self) -> usize {
+        (self.end - self.start) as usize + 1
+    }
+}
+
+/// The characters a string type is permitted to have.
+///
+/// The alphabet is either a list of characters, or a list of ranges of
+/// characters, which keeps large alphabets such as the ones of `BMPString` or
+/// `FROM ("A".."Z" | "0".."9")` small and quick to look up. PER indexes the
+/// characters in the order they are listed, which for ranges is the ascending
+/// order X.691 uses.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PermittedAlphabet(Alphabet);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Alphabet {
+    Characters(&'static [u32]),
+    Ranges(&'static [CharacterRange]),
+}
+
+impl Default for PermittedAlphabet {
+    fn default() -> Self {
+        Self(Alphabet::Characters(&[]))
+    }
+}
 
 impl PermittedAlphabet {
-    /// Creates a new constraint from a given range.
+    /// Creates a new constraint from a list of characters.
+    ///
+    /// # Panics
+    /// If the characters are not in strictly ascending order.
     #[must_use]
-    pub const fn new(range: &'static [u32]) -> Self {
-        Self(range)
+    pub const fn new(characters: &'static [u32]) -> Self {
+        let mut i = 1;
+        while i < characters.len() {
+            assert!(
+                characters[i - 1] < characters[i],
+                "the characters of a permitted alphabet must be strictly ascending"
+            );
+            i += 1;
+        }
+        Self(Alphabet::Characters(characters))
+    }
+
+    /// Creates a new constraint from ranges of characters, which must have
+    /// gone through [`CharacterRange::indexed`].
+    ///
+    /// ```rust
+    /// use rasn::types::constraints::{CharacterRange, PermittedAlphabet};
+    ///
+    /// const ALPHABET: PermittedAlphabet = PermittedAlphabet::from_ranges(
+    ///     &CharacterRange::indexed([
+    ///         CharacterRange::new('0' as u32, '9' as u32),
+    ///         CharacterRange::new('A' as u32, 'Z' as u32),
+    ///     ]),
+    /// );
+    /// assert_eq!(Some(10), ALPHABET.index_of('A' as u32));
+    /// ```
+    ///
+    /// # Panics
+    /// If the ranges are not in ascending order, if they overlap, or if they
+    /// were not indexed.
+    #[must_use]
+    pub const fn from_ranges(ranges: &'static [CharacterRange]) -> Self {
+        let mut offset = 0;
+        let mut i = 0;
+        while i < ranges.len() {
+            assert!(
+                i == 0 || ranges[i - 1].end < ranges[i].start,
+                "the ranges of a permitted alphabet must be ascending and must not overlap"
+            );
+            assert!(
+                ranges[i].offset as usize == offset,
+                "the ranges of a permitted alphabet must be indexed with `CharacterRange::indexed`"
+            );
+            offset += ranges[i].size();
+            i += 1;
+        }
+        Self(Alphabet::Ranges(ranges))
     }
 
-    /// Returns the range of allowed possible values.
+    /// Returns the characters, if the alphabet is a list of characters.
     #[must_use]
-    pub const fn as_inner(&self) -> &'static [u32] {
-        self.0
+    pub const fn as_characters(&self) -> Option<&'static [u32]> {
+        match self.0 {
+            Alphabet::Characters(characters) => Some(characters),
+            Alphabet::Ranges(_) => None,
+        }
+    }
+
+    /// Returns the ranges, if the alphabet is made of ranges of characters.
+    #[must_use]
+    pub const fn as_ranges(&self) -> Option<&'static [CharacterRange]> {
+        match self.0 {
+            Alphabet::Characters(_) => None,
+            Alphabet::Ranges(ranges) => Some(ranges),
+        }
+    }
+
+    /// Returns the number of characters in the alphabet.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self.0 {
+            Alphabet::Characters(characters) => characters.len(),
+            Alphabet::Ranges([.., last]) => last.offset as usize + last.size(),
+            Alphabet::Ranges([]) => 0,
+        }
+    }
+
+    /// Returns whether the alphabet has no characters.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether `character` is in the alphabet.
+    #[must_use]
+    pub fn contains(&self, character: u32) -> bool {
+        match self.0 {
+            Alphabet::Characters(characters) => characters.binary_search(&character).is_ok(),
+            Alphabet::Ranges(ranges) => Self::range_of(ranges, character).is_ok(),
+        }
+    }
+
+    /// Returns the index of `character` in the alphabet, as used by PER.
+    #[must_use]
+    pub fn index_of(&self, character: u32) -> Option<u32> {
+        match self.0 {
+            Alphabet::Characters(characters) => characters
+                .binary_search(&character)
+                .ok()
+                .and_then(|index| u32::try_from(index).ok()),
+            Alphabet::Ranges(ranges) => {
+                let range = &ranges[Self::range_of(ranges, character).ok()?];
+                Some(range.offset + (character - range.start))
+            }
+        }
+    }
+
+    /// Returns the character at `index` in the alphabet, as used by PER.
+    #[must_use]
+    pub fn character_at(&self, index: u32) -> Option<u32> {
+        match self.0 {
+            Alphabet::Characters(characters) => characters.get(index as usize).copied(),
+            Alphabet::Ranges(ranges) => {
+                let range = ranges
+                    .partition_point(|range| range.offset <= index)
+                    .checked_sub(1)?;
+                let range = &ranges[range];
+                range
+                    .start
+                    .checked_add(index - range.offset)
+                    .filter(|&character| character <= range.end)
+            }
+        }
+    }
+
+    /// Returns the character with the largest value in the alphabet.
+    #[must_use]
+    pub fn largest(&self) -> Option<u32> {
+        match self.0 {
+            Alphabet::Characters(characters) => characters.last().copied(),
+            Alphabet::Ranges(ranges) => ranges.last().map(CharacterRange::end),
+        }
     }
+
+    /// Returns an iterator over the characters of the alphabet, in index order.
+    pub fn characters(&self) -> impl Iterator<Item = u32> + 'static {
+        match self.0 {
+            Alphabet::Characters(characters) => either::Left(characters.iter().copied()),
+            Alphabet::Ranges(ranges) => {
+                either::Right(ranges.iter().flat_map(|range| range.start..=range.end))
+            }
+        }
+    }
+
+    /// Finds the range holding `character` with a binary search.
+    fn range_of(ranges: &[CharacterRange], character: u32) -> Result<usize, usize> {
+        ranges.binary_search_by(|range| {
+            if range.end < character {
+                core::cmp::Ordering::Less
+            } else if range.start > character {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+    }
+
     /// Intersect between two `PermittedAlphabet` constraints.
     ///
     /// TODO not currently possible to intersect
@@ -541,15 +799,7 @@ impl PermittedAlphabet {
     /// so we just override for now.
     #[must_use]
     pub const fn intersect(&self, other: &Self) -> Self {
-        Self(other.0)
-    }
-}
-
-impl core::ops::Deref for PermittedAlphabet {
-    type Target = [u32];
-
-    fn deref(&self) -> &Self::Target {
-        self.0
+        *other
     }
 }
 
@@ -987,6 +1237,69 @@ mod tests {
         assert_eq!(256, constraints.range().unwrap());
     }
 
+    #[test]
+    fn permitted_alphabet_ranges() {
+        const ALPHABET: PermittedAlphabet =
+            PermittedAlphabet::from_ranges(&CharacterRange::indexed([
+                CharacterRange::new('0' as u32, '9' as u32),
+                CharacterRange::single('_' as u32),
+                CharacterRange::new('a' as u32, 'z' as u32),
+            ]));
+        assert_eq!(37, ALPHABET.len());
+        assert_eq!(Some('z' as u32), ALPHABET.largest());
+        assert!(ALPHABET.contains('_' as u32));
+        assert!(!ALPHABET.contains('A' as u32));
+        assert_eq!(Some(10), ALPHABET.index_of('_' as u32));
+        assert_eq!(Some(12), ALPHABET.index_of('b' as u32));
+        assert_eq!(None, ALPHABET.index_of('A' as u32));
+        assert_eq!(Some('b' as u32), ALPHABET.character_at(12));
+        assert_eq!(None, ALPHABET.character_at(37));
+
+        for (index, character) in ALPHABET.characters().enumerate() {
+            assert_eq!(Some(index as u32), ALPHABET.index_of(character));
+            assert_eq!(Some(character), ALPHABET.character_at(index as u32));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn permitted_alphabet_overlapping_ranges() {
+        let _ = CharacterRange::indexed([
+            CharacterRange::new('a' as u32, 'z' as u32),
+            CharacterRange::new('0' as u32, '9' as u32),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn permitted_alphabet_unindexed_ranges() {
+        static RANGES: [CharacterRange; 2] = [
+            CharacterRange::new('0' as u32, '9' as u32),
+            CharacterRange::new('a' as u32, 'z' as u32),
+        ];
+        let _ = PermittedAlphabet::from_ranges(&RANGES);
+    }
+
+    #[test]
+    fn permitted_alphabet_characters() {
+        const ALPHABET: PermittedAlphabet =
+            PermittedAlphabet::new(&[' ' as u32, '0' as u32, '1' as u32, 'x' as u32]);
+        assert_eq!(4, ALPHABET.len());
+        assert_eq!(Some('x' as u32), ALPHABET.largest());
+        assert!(ALPHABET.contains('1' as u32));
+        assert!(!ALPHABET.contains('2' as u32));
+        assert_eq!(Some(3), ALPHABET.index_of('x' as u32));
+        assert_eq!(None, ALPHABET.index_of('2' as u32));
+        assert_eq!(Some('0' as u32), ALPHABET.character_at(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn permitted_alphabet_unsorted_characters() {
+        static CHARACTERS: [u32; 2] = ['b' as u32, 'a' as u32];
+        let _ = PermittedAlphabet::new(&CHARACTERS);
+    }
+
     #[test]
     #[allow(clippy::too_many_lines)]
     fn test_bounded_intersections() {
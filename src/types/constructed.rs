@@ -46,10 +46,7 @@ pub struct SetOf<T> {
     elements: alloc::vec::Vec<T>,
 }
 
-impl<T> SetOf<T>
-where
-    T: Eq,
-{
+impl<T> SetOf<T> {
     /// Construct a new empty set of value.
     #[must_use]
     pub fn new() -> Self {
@@ -89,6 +86,30 @@ where
         self.elements.is_empty()
     }
 
+    /// Returns an iterator over the elements of the set, in the order they
+    /// were inserted.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    /// Convert the set to a `Vec<&T>`. `&T` refers to the original element in the set.
+    #[must_use]
+    pub fn to_vec(&self) -> alloc::vec::Vec<&T> {
+        self.elements.iter().collect()
+    }
+
+    /// Consumes the set and returns its elements, in the order they were
+    /// inserted.
+    #[must_use]
+    pub fn into_vec(self) -> alloc::vec::Vec<T> {
+        self.elements
+    }
+}
+
+impl<T> SetOf<T>
+where
+    T: Eq,
+{
     /// Remove an element from the set.
     pub fn remove(&mut self, item: &T) -> bool {
         if let Some(idx) = self.elements.iter().position(|i| i == item) {
@@ -109,12 +130,6 @@ where
         }
         false
     }
-
-    /// Convert the set to a `Vec<&T>`. `&T` refers to the original element in the set.
-    #[must_use]
-    pub fn to_vec(&self) -> alloc::vec::Vec<&T> {
-        self.elements.iter().collect()
-    }
 }
 
 impl<T> PartialEq for SetOf<T>
@@ -122,21 +137,7 @@ where
     T: Eq,
 {
     fn eq(&self, other: &Self) -> bool {
-        if self.elements.len() != other.len() {
-            return false;
-        }
-        for item in &self.elements {
-            if !other.contains(item) {
-                return false;
-            }
-        }
-        for item in &other.elements {
-            if !self.elements.contains(item) {
-                return false;
-            }
-        }
-
-        true
+        self == &other.elements
     }
 }
 
@@ -145,54 +146,298 @@ where
     T: Eq,
 {
     fn eq(&self, other: &alloc::vec::Vec<T>) -> bool {
+        // Sets are equal when they hold each element the same number of times.
         if self.elements.len() != other.len() {
             return false;
         }
-        for item in &self.elements {
-            if !other.contains(item) {
-                return false;
-            }
-        }
-        for item in other {
-            if !self.elements.contains(item) {
-                return false;
-            }
-        }
+        // Skip the elements both hold in the same position, which are all of
+        // them when the sets are in the same order.
+        let common = self
+            .elements
+            .iter()
+            .zip(other)
+            .take_while(|(lhs, rhs)| lhs == rhs)
+            .count();
+        let (lhs, rhs) = (&self.elements[common..], &other[common..]);
 
-        true
+        // Pairs every element with an unpaired equal element of the other set.
+        let mut paired = alloc::vec![false; rhs.len()];
+        lhs.iter().all(|element| {
+            rhs.iter()
+                .zip(&mut paired)
+                .find(|(other, paired)| !**paired && *other == element)
+                .map(|(_, paired)| *paired = true)
+                .is_some()
+        })
     }
 }
 impl<T> Eq for SetOf<T> where T: Eq {}
 
+impl<T> SetOf<T>
+where
+    T: Ord,
+{
+    /// Returns the elements in ascending order, which is the same for every
+    /// set holding the same elements.
+    fn sorted(&self) -> alloc::vec::Vec<&T> {
+        let mut elements = self.to_vec();
+        elements.sort_unstable();
+        elements
+    }
+}
+
+impl<T> PartialOrd for SetOf<T>
+where
+    T: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for SetOf<T>
+where
+    T: Ord,
+{
+    /// Orders sets by their elements in ascending order.
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.sorted().cmp(&other.sorted())
+    }
+}
+
 impl<T> core::hash::Hash for SetOf<T>
 where
-    T: Eq + core::hash::Hash,
+    T: core::hash::Hash,
 {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
-        for item in &self.elements {
-            item.hash(state);
+        self.elements.len().hash(state);
+        // Sets that are equal can hold their elements in any order, so each
+        // element is hashed on its own and the hashes are combined with an
+        // addition, which doesn't depend on the order.
+        let sum = self.elements.iter().fold(0u64, |sum, element| {
+            let mut hasher = Fnv1aHasher::default();
+            element.hash(&mut hasher);
+            sum.wrapping_add(core::hash::Hasher::finish(&hasher))
+        });
+        sum.hash(state);
+    }
+}
+
+/// The 64-bit FNV-1a hash, used to hash the elements of a [`SetOf`]
+/// independently of their order, as there is no default hasher without `std`.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl core::hash::Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
         }
     }
 }
 
-impl<T: Eq> From<alloc::vec::Vec<T>> for SetOf<T> {
+impl<'a, T> IntoIterator for &'a SetOf<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter()
+    }
+}
+
+impl<T> From<alloc::vec::Vec<T>> for SetOf<T> {
     fn from(vec: alloc::vec::Vec<T>) -> Self {
         Self::from_vec(vec)
     }
 }
-impl<T: Clone + Eq> From<&[T]> for SetOf<T> {
+impl<T: Clone> From<&[T]> for SetOf<T> {
     fn from(vec: &[T]) -> Self {
         Self::from_vec(vec.to_vec())
     }
 }
 
-impl<T: Clone + Eq, const N: usize> From<[T; N]> for SetOf<T> {
+impl<T: Clone, const N: usize> From<[T; N]> for SetOf<T> {
     fn from(array: [T; N]) -> Self {
         Self::from_vec(array.to_vec())
     }
 }
 
-impl<T: Eq> Default for SetOf<T> {
+impl<T> Default for SetOf<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A `SET OF` value that keeps its elements sorted by their DER encoding.
+///
+/// Every element is encoded once when it is inserted, and the encoding is
+/// kept alongside it. The distinguished and canonical encoding rules require
+/// the elements of a `SET OF` to appear in ascending order of their
+/// encodings, which [`SetOf`] achieves by encoding and sorting every element
+/// each time the set is encoded. `SortedSetOf` instead pays for one encoding
+/// per insertion, and encoding the set with BER or DER copies the stored
+/// encodings without encoding or sorting the elements again. This suits large
+/// sets that are built once and encoded often, or whose elements are
+/// expensive to encode, such as the `SignerInfos` of a signed CMS message.
+///
+/// Elements are compared by their encodings, so the element type doesn't
+/// have to implement `Eq`, `Hash` or `Ord`. Elements with equal encodings are
+/// kept in the order they were inserted in.
+///
+/// ```rust
+/// use rasn::types::SortedSetOf;
+///
+/// let mut set = SortedSetOf::new();
+/// set.insert(300u16).unwrap();
+/// set.insert(2u16).unwrap();
+///
+/// assert_eq!(set.as_slice(), [2, 300]);
+/// assert_eq!(
+///     rasn::der::encode(&set).unwrap(),
+///     [0x31, 0x07, 0x02, 0x01, 0x02, 0x02, 0x02, 0x01, 0x2c]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SortedSetOf<T> {
+    elements: alloc::vec::Vec<T>,
+    encodings: alloc::vec::Vec<alloc::vec::Vec<u8>>,
+}
+
+impl<T> SortedSetOf<T> {
+    /// Construct a new empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            elements: alloc::vec::Vec::new(),
+            encodings: alloc::vec::Vec::new(),
+        }
+    }
+
+    /// Get the number of elements in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns whether the set doesn't contain any elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the elements of the set, in ascending order of their DER
+    /// encodings.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns an iterator over the elements of the set, in ascending order
+    /// of their DER encodings.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    /// Returns the DER encodings of the elements of the set, in ascending
+    /// order.
+    #[must_use]
+    pub fn encodings(&self) -> &[alloc::vec::Vec<u8>] {
+        &self.encodings
+    }
+
+    /// Removes and returns the element at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.encodings.remove(index);
+        self.elements.remove(index)
+    }
+
+    /// Consumes the set and returns its elements, in ascending order of
+    /// their DER encodings.
+    #[must_use]
+    pub fn into_vec(self) -> alloc::vec::Vec<T> {
+        self.elements
+    }
+}
+
+impl<T: crate::Encode> SortedSetOf<T> {
+    /// Creates a set from `elements`, encoding each of them once.
+    ///
+    /// # Errors
+    /// If any of the elements fails to encode with DER.
+    pub fn from_vec(elements: alloc::vec::Vec<T>) -> Result<Self, crate::error::EncodeError> {
+        let mut entries = elements
+            .into_iter()
+            .map(|element| crate::der::encode(&element).map(|encoding| (encoding, element)))
+            .collect::<Result<alloc::vec::Vec<_>, _>>()?;
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let (encodings, elements) = entries.into_iter().unzip();
+
+        Ok(Self {
+            elements,
+            encodings,
+        })
+    }
+
+    /// Inserts `item` in its position in the set, after any elements with
+    /// the same encoding.
+    ///
+    /// # Errors
+    /// If `item` fails to encode with DER.
+    pub fn insert(&mut self, item: T) -> Result<(), crate::error::EncodeError> {
+        let encoding = crate::der::encode(&item)?;
+        let index = self.encodings.partition_point(|other| *other <= encoding);
+        self.encodings.insert(index, encoding);
+        self.elements.insert(index, item);
+        Ok(())
+    }
+
+    /// Check if the set contains an element with the same encoding as
+    /// `item`.
+    ///
+    /// # Errors
+    /// If `item` fails to encode with DER.
+    pub fn contains(&self, item: &T) -> Result<bool, crate::error::EncodeError> {
+        let encoding = crate::der::encode(item)?;
+        Ok(self.encodings.binary_search(&encoding).is_ok())
+    }
+}
+
+impl<T> PartialEq for SortedSetOf<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.encodings == other.encodings
+    }
+}
+
+impl<T> Eq for SortedSetOf<T> {}
+
+impl<T> core::hash::Hash for SortedSetOf<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.encodings.hash(state);
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SortedSetOf<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter()
+    }
+}
+
+impl<T> Default for SortedSetOf<T> {
     fn default() -> Self {
         Self::new()
     }
@@ -227,5 +472,76 @@ mod tests {
         // Duplicate test
         let set_d = SetOf::from_vec(alloc::vec![1, 1, 2, 2, 3, 3]);
         assert_ne!(set_a, set_d);
+        assert_ne!(
+            SetOf::from_vec(alloc::vec![1, 1, 2]),
+            SetOf::from_vec(alloc::vec![1, 2, 2])
+        );
+        assert_eq!(
+            SetOf::from_vec(alloc::vec![2, 1, 3, 1]),
+            alloc::vec![1, 3, 1, 2]
+        );
+        assert_eq!(
+            SetOf::from_vec(alloc::vec![2, 1, 3, 1]).cmp(&SetOf::from_vec(alloc::vec![1, 1, 2, 3])),
+            core::cmp::Ordering::Equal
+        );
+
+        // Elements only have to be comparable, not hashable.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Element(u8);
+        assert_eq!(
+            SetOf::from_vec(alloc::vec![Element(1), Element(2), Element(1)]),
+            SetOf::from_vec(alloc::vec![Element(2), Element(1), Element(1)])
+        );
+        assert_ne!(
+            SetOf::from_vec(alloc::vec![Element(1), Element(2), Element(2)]),
+            SetOf::from_vec(alloc::vec![Element(2), Element(1), Element(1)])
+        );
+    }
+
+    #[test]
+    fn set_of_hash() {
+        use core::hash::{Hash, Hasher};
+
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let set: SetOf<u8> = [1, 2, 3].into();
+        assert_eq!(hash(&set), hash(&SetOf::from([3u8, 1, 2])));
+        assert_ne!(hash(&set), hash(&SetOf::from([1u8, 2, 4])));
+        assert_ne!(hash(&SetOf::from([1, 1, 2])), hash(&SetOf::from([1, 2, 2])));
+    }
+
+    #[test]
+    fn sorted_set_of() {
+        let mut set = SortedSetOf::new();
+        set.insert(alloc::vec![3u8]).unwrap();
+        set.insert(alloc::vec![1u8, 2]).unwrap();
+        set.insert(alloc::vec![1u8]).unwrap();
+
+        assert_eq!(
+            set.as_slice(),
+            [alloc::vec![1u8], alloc::vec![3], alloc::vec![1, 2]]
+        );
+        assert!(set.contains(&alloc::vec![3]).unwrap());
+        assert!(!set.contains(&alloc::vec![2]).unwrap());
+
+        let from_vec = SortedSetOf::from_vec(alloc::vec![
+            alloc::vec![1u8, 2],
+            alloc::vec![1],
+            alloc::vec![3]
+        ])
+        .unwrap();
+        assert_eq!(set, from_vec);
+        assert_eq!(
+            crate::der::encode(&set).unwrap(),
+            crate::der::encode(&SetOf::from_vec(set.as_slice().to_vec())).unwrap()
+        );
+
+        assert_eq!(set.remove(0), [1]);
+        assert_eq!(set.len(), 2);
+        assert_ne!(set, from_vec);
     }
 }
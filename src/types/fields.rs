@@ -1,6 +1,9 @@
 //! Representing all fields for a `SEQUENCE` or `SET` type.
 
-use crate::types::{Tag, TagTree};
+use crate::types::{
+    Tag, TagTree,
+    instructions::{JerInstructions, XerInstructions},
+};
 
 /// Represents all of the values that make up a given value in ASN.1.
 #[derive(Debug, Clone, Copy)]
@@ -131,6 +134,10 @@ pub struct Field {
     pub presence: FieldPresence,
     /// The name of the field.
     pub name: &'static str,
+    /// The E-XER encoding instructions of the field.
+    pub xer: XerInstructions,
+    /// The JER encoding instructions of the field.
+    pub jer: JerInstructions,
 }
 
 impl Field {
@@ -148,6 +155,8 @@ impl Field {
             tag_tree,
             presence: FieldPresence::Required,
             name,
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         }
     }
 
@@ -163,6 +172,8 @@ impl Field {
             tag_tree: T::TAG_TREE,
             presence: FieldPresence::Required,
             name,
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         }
     }
 
@@ -180,6 +191,8 @@ impl Field {
             tag_tree,
             presence: FieldPresence::Optional,
             name,
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         }
     }
 
@@ -195,6 +208,8 @@ impl Field {
             tag_tree: T::TAG_TREE,
             presence: FieldPresence::Optional,
             name,
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         }
     }
 
@@ -212,6 +227,8 @@ impl Field {
             tag_tree,
             presence: FieldPresence::Default,
             name,
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         }
     }
 
@@ -227,11 +244,53 @@ impl Field {
             tag_tree: T::TAG_TREE,
             presence: FieldPresence::Default,
             name,
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         }
     }
 }
 
 impl Field {
+    /// Sets the E-XER encoding instructions of the field.
+    #[must_use]
+    pub const fn with_xer_instructions(mut self, xer: XerInstructions) -> Self {
+        self.xer = xer;
+        self
+    }
+
+    /// Adds the E-XER encoding instructions set on the type of the field,
+    /// which apply unless the field overrides them.
+    #[must_use]
+    pub const fn with_type_xer_instructions(mut self, xer: XerInstructions) -> Self {
+        self.xer = self.xer.with_type_instructions(xer);
+        self
+    }
+
+    /// Sets the JER encoding instructions of the field.
+    #[must_use]
+    pub const fn with_jer_instructions(mut self, jer: JerInstructions) -> Self {
+        self.jer = jer;
+        self
+    }
+
+    /// Adds the JER encoding instructions set on the type of the field, which
+    /// apply unless the field overrides them.
+    #[must_use]
+    pub const fn with_type_jer_instructions(mut self, jer: JerInstructions) -> Self {
+        self.jer = jer.union(self.jer);
+        self
+    }
+
+    /// Returns the JSON member name of the field, taking the JER `NAME`
+    /// instruction into account.
+    #[must_use]
+    pub const fn jer_name(&self) -> &'static str {
+        match self.jer.name {
+            Some(name) => name,
+            None => self.name,
+        }
+    }
+
     /// Returns whether the field is [`FieldPresence::Optional`] or`FieldPresence::Default`lt].
     #[must_use]
     pub const fn is_optional_or_default(&self) -> bool {
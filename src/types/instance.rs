@@ -2,7 +2,10 @@ use super::{AsnType, Class, Constraints, Identifier, ObjectIdentifier, Tag};
 use crate::{
     de::Decoder,
     enc::Encoder,
-    types::fields::{Field, FieldPresence, Fields},
+    types::{
+        fields::{Field, FieldPresence, Fields},
+        instructions::{JerInstructions, XerInstructions},
+    },
 };
 
 /// An instance of a defined object class.
@@ -68,6 +71,8 @@ impl<T: AsnType> crate::types::Constructed<2, 0> for InstanceOf<T> {
             tag_tree: ObjectIdentifier::TAG_TREE,
             presence: FieldPresence::Required,
             name: "type_id",
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         },
         Field {
             index: 1,
@@ -75,6 +80,8 @@ impl<T: AsnType> crate::types::Constructed<2, 0> for InstanceOf<T> {
             tag_tree: T::TAG_TREE,
             presence: FieldPresence::Required,
             name: "value",
+            xer: XerInstructions::NONE,
+            jer: JerInstructions::NONE,
         },
     ]);
 }
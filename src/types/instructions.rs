@@ -0,0 +1,153 @@
+//! Encoding instructions for the text-based encoding rules.
+//!
+//! Encoding instructions change how a type or a component is represented by a
+//! particular set of encoding rules without changing its abstract value. They
+//! are ignored by every other codec.
+
+/// X.693 Extended XER (E-XER) encoding instructions.
+///
+/// Instructions are usually set with the derive macros, for example
+/// `#[rasn(xer(attribute))]` on a field or `#[rasn(xer(namespace = "urn:example"))]`
+/// on a type, and are only applied by the XER encoder and decoder.
+///
+/// Instructions set on a type apply to every element of that type, at the top
+/// level, as a component of a `SEQUENCE` or `SET` and as an item of a
+/// `SEQUENCE OF` or `SET OF`.
+#[derive(Debug, Clone, Copy, Default, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub struct XerInstructions {
+    /// `NAME AS`: the XML name used instead of the ASN.1 identifier.
+    pub name: Option<&'static str>,
+    /// `ATTRIBUTE`: the component is encoded as an XML attribute of the
+    /// enclosing element.
+    pub attribute: bool,
+    /// `UNTAGGED`: the component is encoded without its enclosing element.
+    pub untagged: bool,
+    /// `LIST`: the items of a `SEQUENCE OF` are encoded as a white-space
+    /// separated list of values.
+    pub list: bool,
+    /// `TEXT`: `BOOLEAN` and `ENUMERATED` values are encoded as character data
+    /// instead of empty-element tags.
+    pub text: bool,
+    /// `DEFAULT-FOR-EMPTY`: the value used when the element is empty.
+    pub default_for_empty: Option<&'static str>,
+    /// `NAMESPACE`: the XML namespace of the type.
+    pub namespace: Option<&'static str>,
+    /// The prefix used for [`Self::namespace`].
+    pub prefix: Option<&'static str>,
+    /// `USE-TYPE`: the alternative of a `CHOICE` is identified by an
+    /// `xsi:type` attribute instead of a nested element.
+    pub use_type: bool,
+}
+
+impl XerInstructions {
+    /// No encoding instructions.
+    pub const NONE: Self = Self {
+        name: None,
+        attribute: false,
+        untagged: false,
+        list: false,
+        text: false,
+        default_for_empty: None,
+        namespace: None,
+        prefix: None,
+        use_type: false,
+    };
+
+    /// Returns whether no instruction is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && !self.attribute
+            && !self.untagged
+            && !self.list
+            && !self.text
+            && self.default_for_empty.is_none()
+            && self.namespace.is_none()
+            && self.prefix.is_none()
+            && !self.use_type
+    }
+
+    /// Adds the instructions set on the type of a component to the
+    /// instructions of the component itself, which take precedence.
+    #[must_use]
+    pub const fn with_type_instructions(mut self, instructions: Self) -> Self {
+        if self.namespace.is_none() {
+            self.namespace = instructions.namespace;
+            self.prefix = instructions.prefix;
+        }
+        self.text |= instructions.text;
+        self.use_type |= instructions.use_type;
+        self
+    }
+}
+
+/// X.697 JSON Encoding Rules (JER) encoding instructions.
+///
+/// Instructions are usually set with the derive macros, for example
+/// `#[rasn(jer(base64))]` on a field or `#[rasn(jer(unwrapped))]` on a
+/// `CHOICE`, and are only applied by the JER encoder and decoder.
+///
+/// Instructions set on a type apply to every value of that type, at the top
+/// level, as a component of a `SEQUENCE` or `SET` and as an item of a
+/// `SEQUENCE OF` or `SET OF`. Instructions set on a field always apply to that
+/// field.
+#[derive(Debug, Clone, Copy, Default, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub struct JerInstructions {
+    /// `NAME`: the JSON member name used instead of the ASN.1 identifier.
+    pub name: Option<&'static str>,
+    /// `ARRAY`: a `SEQUENCE` or `SET` is encoded as a JSON array of its
+    /// components in declaration order, with `null` for absent components.
+    pub array: bool,
+    /// `UNWRAPPED`: a `CHOICE` is encoded as the value of its chosen
+    /// alternative, without the enclosing JSON object.
+    pub unwrapped: bool,
+    /// `BASE64`: an `OCTET STRING` is encoded as a base64 string instead of a
+    /// hexadecimal string.
+    pub base64: bool,
+    /// `TEXT`: a `BOOLEAN` is encoded as the JSON string `"true"` or
+    /// `"false"`. `ENUMERATED` values are always encoded as text by JER.
+    pub text: bool,
+    /// `OBJECT`: a `SEQUENCE OF` whose items are a `SEQUENCE` of a string key
+    /// and a value is encoded as a JSON object.
+    pub object: bool,
+}
+
+impl JerInstructions {
+    /// No encoding instructions.
+    pub const NONE: Self = Self {
+        name: None,
+        array: false,
+        unwrapped: false,
+        base64: false,
+        text: false,
+        object: false,
+    };
+
+    /// Returns whether no instruction is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && !self.array
+            && !self.unwrapped
+            && !self.base64
+            && !self.text
+            && !self.object
+    }
+
+    /// Returns the instructions of `self` combined with the ones set in
+    /// `other`, with `other` taking precedence for [`Self::name`].
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self {
+            name: match other.name {
+                Some(name) => Some(name),
+                None => self.name,
+            },
+            array: self.array || other.array,
+            unwrapped: self.unwrapped || other.unwrapped,
+            base64: self.base64 || other.base64,
+            text: self.text || other.text,
+            object: self.object || other.object,
+        }
+    }
+}
@@ -334,18 +334,67 @@ impl_try_from_integer!(
 );
 
 /// An integer which has encoded constraint range between `START` and `END`.
-#[derive(Debug, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
-pub struct ConstrainedInteger<const START: i128, const END: i128>(pub(crate) Integer);
+///
+/// The value is kept as `T`, which is [`Integer`] unless stated otherwise.
+/// Naming a fixed-width primitive such as `u8` or `i32` keeps the value on
+/// the stack and lets the codecs work on it directly, and the range is checked
+/// to fit into that type at compile time. PER and OER also work out how the
+/// value is laid out from `START` and `END` at compile time.
+///
+/// ```
+/// use rasn::types::ConstrainedInteger;
+///
+/// type Percentage = ConstrainedInteger<0, 100, u8>;
+///
+/// let value = Percentage::try_from(42).unwrap();
+/// assert_eq!(*value, 42u8);
+/// assert!(Percentage::try_from(101).is_err());
+/// ```
+///
+/// A range which doesn't fit the type fails to build.
+///
+/// ```compile_fail
+/// use rasn::types::ConstrainedInteger;
+///
+/// let _ = ConstrainedInteger::<0, 256, u8>::try_from(0);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct ConstrainedInteger<const START: i128, const END: i128, T = Integer>(pub(crate) T);
+
+impl<const START: i128, const END: i128, T: IntegerType> ConstrainedInteger<START, END, T> {
+    /// Stops the build when `START..=END` is empty or doesn't fit into `T`.
+    pub(crate) const RANGE_FITS: () = assert!(
+        START <= END && range_fits::<T>(START, END),
+        "the range of a `ConstrainedInteger` must be non-empty and fit into its integer type"
+    );
 
-impl<const START: i128, const END: i128> ConstrainedInteger<START, END> {
     /// Unchecked `new` where constraint boundaries are not checked - used only in tests.
     #[cfg(test)]
-    pub(crate) fn new<T: Into<Integer>>(value: T) -> Self {
+    pub(crate) fn new<V: Into<T>>(value: V) -> Self {
         Self(value.into())
     }
+
+    /// Returns the inner integer.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Whether every integer in `start..=end` can be represented by `I`.
+const fn range_fits<I: IntegerType>(start: i128, end: i128) -> bool {
+    let start_fits = match I::MIN {
+        Some(min) => start >= min,
+        None => true,
+    };
+    let end_fits = match I::MAX {
+        Some(max) => end <= max,
+        None => true,
+    };
+    start_fits && end_fits
 }
 
-impl<const START: i128, const END: i128> AsnType for ConstrainedInteger<START, END> {
+impl<const START: i128, const END: i128, T> AsnType for ConstrainedInteger<START, END, T> {
     const TAG: Tag = Tag::INTEGER;
     const CONSTRAINTS: Constraints =
         Constraints::new(&[constraints::Constraint::Value(Extensible::new(
@@ -353,8 +402,8 @@ impl<const START: i128, const END: i128> AsnType for ConstrainedInteger<START, E
         ))]);
 }
 
-impl<const START: i128, const END: i128> core::ops::Deref for ConstrainedInteger<START, END> {
-    type Target = Integer;
+impl<const START: i128, const END: i128, T> core::ops::Deref for ConstrainedInteger<START, END, T> {
+    type Target = T;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -364,11 +413,13 @@ impl<const START: i128, const END: i128> core::ops::Deref for ConstrainedInteger
 macro_rules! impl_try_from_integer_constrained {
     ($($t:ty),*) => {
         $(
-            impl<const START: i128, const END: i128> TryFrom<$t> for ConstrainedInteger<START, END> {
+            impl<const START: i128, const END: i128, T: IntegerType> TryFrom<$t> for ConstrainedInteger<START, END, T> {
                 type Error = TryFromIntegerError;
                 fn try_from(value: $t) -> Result<Self, Self::Error> {
-                    if value as i128 >= START && value as i128 <= END {
-                        Ok(Self(value.into()))
+                    let () = Self::RANGE_FITS;
+                    let wide = value as i128;
+                    if (START..=END).contains(&wide) {
+                        T::try_from(wide).map(Self).map_err(|_| TryFromIntegerError::new(value.into()))
                     } else {
                         Err(TryFromIntegerError::new(value.into()))
                     }
@@ -377,7 +428,7 @@ macro_rules! impl_try_from_integer_constrained {
         )*
     }
 }
-impl_try_from_integer_constrained!(isize, i32, i64, i128);
+impl_try_from_integer_constrained!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
 
 /// Represents a integer type in Rust that can be decoded or encoded into any
 /// ASN.1 codec.
@@ -408,6 +459,12 @@ pub trait IntegerType:
     const BYTE_WIDTH: usize = Self::WIDTH as usize / 8;
     /// Represents `0` in a given integer.
     const ZERO: Self;
+    /// The smallest value of the type, or `None` when it has no lower bound
+    /// within `i128`.
+    const MIN: Option<i128> = None;
+    /// The largest value of the type, or `None` when it has no upper bound
+    /// within `i128`.
+    const MAX: Option<i128> = None;
     /// `Self` as an unsigned type with the same width.
     type UnsignedPair: IntegerType;
     /// `Self` as a signed type with one type size larger to prevent truncation, in case `Self` is unsigned. (e.g. u8 -> i16)
@@ -489,6 +546,8 @@ macro_rules! integer_type_impl {
         impl IntegerType for $t1 {
             const WIDTH: u32 = <$t1>::BITS;
             const ZERO: $t1 = 0 as $t1;
+            const MIN: Option<i128> = Some(<$t1>::MIN as i128);
+            const MAX: Option<i128> = Some(<$t1>::MAX as i128);
             type UnsignedPair = $t2;
             type SignedPair = $t1;
 
@@ -589,6 +648,12 @@ macro_rules! integer_type_impl {
         impl IntegerType for $t1 {
             const WIDTH: u32 = <$t1>::BITS;
             const ZERO: $t1 = 0 as $t1;
+            const MIN: Option<i128> = Some(0);
+            const MAX: Option<i128> = if <$t1>::BITS < i128::BITS {
+                Some(<$t1>::MAX as i128)
+            } else {
+                None
+            };
             type UnsignedPair = $t1;
             type SignedPair = $t2;
 
@@ -1,97 +1,481 @@
 #![allow(clippy::unreadable_literal)]
-use core::ops;
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{cmp::Ordering, ops};
+
+use num_bigint::BigUint;
+
+use super::oid_registry;
 
 pub(crate) const MAX_OID_FIRST_OCTET: u32 = 2;
 pub(crate) const MAX_OID_SECOND_OCTET: u32 = 39;
 
-const fn is_valid_oid(slice: &[u32]) -> bool {
-    !slice.is_empty() && slice[0] <= MAX_OID_FIRST_OCTET
+/// Set on every octet of a base-128 subidentifier except the last.
+const CONTINUATION_BIT: u8 = 0x80;
+
+/// The largest number of octets a subidentifier that fits in a `u128` has.
+const MAX_U128_SUBIDENTIFIER_LEN: usize = 19;
+
+/// Returns whether `encoded` is a sequence of minimally encoded base-128
+/// subidentifiers.
+const fn is_valid_subidentifiers(encoded: &[u8]) -> bool {
+    let Some(last) = encoded.last() else {
+        return false;
+    };
+    if *last & CONTINUATION_BIT != 0 {
+        return false;
+    }
+
+    let mut index = 0;
+    let mut at_start = true;
+    while index < encoded.len() {
+        if at_start && encoded[index] == CONTINUATION_BIT {
+            return false;
+        }
+        at_start = encoded[index] & CONTINUATION_BIT == 0;
+        index += 1;
+    }
+    true
+}
+
+const fn is_valid_oid(encoded: &[u8]) -> bool {
+    is_valid_subidentifiers(encoded) && encoded[0] <= MAX_OID_FIRST_OCTET as u8
+}
+
+/// Returns the number of octets `arc` takes as a base-128 subidentifier.
+const fn base128_len(arc: u128) -> usize {
+    if arc == 0 {
+        1
+    } else {
+        (u128::BITS - arc.leading_zeros()).div_ceil(7) as usize
+    }
+}
+
+fn push_subidentifier(arc: u128, encoded: &mut Vec<u8>) {
+    let len = base128_len(arc);
+    for digit in (0..len).rev() {
+        let octet = (arc >> (7 * digit)) as u8 & !CONTINUATION_BIT;
+        encoded.push(if digit == 0 {
+            octet
+        } else {
+            octet | CONTINUATION_BIT
+        });
+    }
+}
+
+fn push_big_subidentifier(arc: &BigUint, encoded: &mut Vec<u8>) {
+    let digits = arc.to_radix_be(128);
+    let (last, init) = digits
+        .split_last()
+        .expect("a number has at least one digit");
+    encoded.extend(init.iter().map(|digit| digit | CONTINUATION_BIT));
+    encoded.push(*last);
+}
+
+/// Encodes an arc written in decimal, which may be arbitrarily large.
+fn push_decimal_arc(arc: &str, encoded: &mut Vec<u8>) -> Result<(), ParseOidError> {
+    if arc.is_empty() || !arc.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ParseOidError::new(alloc::format!(
+            "`{arc}` is not a valid arc"
+        )));
+    }
+
+    match arc.parse::<u128>() {
+        Ok(arc) => push_subidentifier(arc, encoded),
+        Err(_) => push_big_subidentifier(
+            &BigUint::parse_bytes(arc.as_bytes(), 10).expect("arc only contains digits"),
+            encoded,
+        ),
+    }
+    Ok(())
 }
 
 /// A reference to a global unique identifier that identifies an concept, such
 /// as a organisation, or encoding rules.
-#[derive(Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+///
+/// The arcs are stored as base-128 subidentifiers, the same way the contents
+/// of a `RELATIVE-OID` are encoded in BER, so arcs can be arbitrarily large
+/// (such as the UUIDs beneath `2.25`) and comparing object identifiers only
+/// compares bytes. Use [`Oid::arcs`] to iterate over the arcs, and
+/// [`oid!`][crate::oid] to create object identifiers in constants.
+#[derive(Eq, Hash, PartialEq)]
 #[repr(transparent)]
-pub struct Oid([u32]);
+pub struct Oid([u8]);
 
 impl Oid {
-    /// Creates a new reference to a object identifier from `slice`.
+    /// Creates a new reference to a object identifier from arcs encoded as
+    /// base-128 subidentifiers, as returned by [`Oid::as_encoded_arcs`].
     ///
-    /// Returns `None` if `vec` is empty or the first
-    /// component is greater than 2.
+    /// Returns `None` if `encoded` is empty, is not a sequence of minimally
+    /// encoded subidentifiers, or the first arc is greater than 2.
     /// ```
     /// use rasn::types::Oid;
     ///
-    /// let internet = Oid::new(&[1, 3, 6, 1]).unwrap();
+    /// let oid = Oid::from_encoded_arcs(&[2, 25, 0x81, 0x80, 0x00]).unwrap();
+    /// assert_eq!(oid.to_string(), "2.25.16384");
+    /// assert!(Oid::from_encoded_arcs(&[2, 25, 0x81]).is_none());
     /// ```
     #[must_use]
-    pub const fn new(slice: &[u32]) -> Option<&Self> {
-        if is_valid_oid(slice) {
-            Some(Self::new_unchecked(slice))
+    pub const fn from_encoded_arcs(encoded: &[u8]) -> Option<&Self> {
+        if is_valid_oid(encoded) {
+            Some(Self::from_encoded_arcs_unchecked(encoded))
         } else {
             None
         }
     }
 
-    /// Creates a new reference to a object identifier from `slice`.
+    /// Creates a new reference to a object identifier from arcs encoded as
+    /// base-128 subidentifiers.
     ///
-    /// Panics if `vec` is empty or the first
-    /// component is greater than 2.
+    /// Panics if `encoded` is not a valid object identifier, which fails
+    /// compilation when used in a constant. Prefer [`oid!`][crate::oid],
+    /// which encodes the arcs for you.
     #[must_use]
-    pub const fn const_new(oid: &'static [u32]) -> &'static Self {
-        match Self::new(oid) {
+    pub const fn const_from_encoded_arcs(encoded: &'static [u8]) -> &'static Self {
+        match Self::from_encoded_arcs(encoded) {
             Some(oid) => oid,
             None => panic!("not a valid OID"),
         }
     }
 
-    /// Creates a new mutable reference to a object identifier from `slice`.
+    /// Creates a new reference to a object identifier from arcs encoded as
+    /// base-128 subidentifiers.
     ///
-    /// Returns `None` if `vec` is empty or the first
-    /// component is greater than 2.
+    /// # Validity
+    /// This allows you to create potentially invalid object identifiers which
+    /// may affect encoding validity.
+    #[must_use]
+    pub const fn from_encoded_arcs_unchecked(encoded: &[u8]) -> &Self {
+        // SAFETY: `Oid` is a `repr(transparent)` wrapper around `[u8]`.
+        unsafe { &*(core::ptr::from_ref::<[u8]>(encoded) as *const Self) }
+    }
+
+    /// Returns the arcs of the object identifier encoded as base-128
+    /// subidentifiers, one after another.
+    #[must_use]
+    pub const fn as_encoded_arcs(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns an iterator over the arcs of the object identifier.
     /// ```
     /// use rasn::types::Oid;
     ///
-    /// let internet = Oid::new(&[1, 3, 6, 1]).unwrap();
+    /// let arcs: Vec<u32> = Oid::ISO_MEMBER_BODY_US
+    ///     .arcs()
+    ///     .map(|arc| arc.to_u32().unwrap())
+    ///     .collect();
+    /// assert_eq!(arcs, [1, 2, 840]);
     /// ```
-    pub fn new_mut(slice: &mut [u32]) -> Option<&mut Self> {
-        if is_valid_oid(slice) {
-            Some(Self::new_unchecked_mut(slice))
-        } else {
-            None
-        }
+    #[must_use]
+    pub fn arcs(&self) -> Arcs<'_> {
+        Arcs(&self.0)
+    }
+
+    /// Returns the number of arcs in the object identifier.
+    #[must_use]
+    pub fn arc_count(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|octet| *octet & CONTINUATION_BIT == 0)
+            .count()
     }
 
-    /// Creates a new reference to a object identifier from `slice`.
+    /// Returns the object identifier without its last arc, or `None` if it
+    /// only has one arc.
+    /// ```
+    /// use rasn::types::Oid;
     ///
-    /// # Safety
-    /// This allows you to create potentially invalid object identifiers which
-    /// may affect encoding validity.
+    /// assert_eq!(Oid::ISO_MEMBER_BODY_US.parent(), Some(Oid::ISO_MEMBER_BODY));
+    /// assert_eq!(Oid::ISO.parent(), None);
+    /// ```
+    #[must_use]
+    pub fn parent(&self) -> Option<&Self> {
+        let (_, init) = self.0.split_last()?;
+        let end = init
+            .iter()
+            .rposition(|octet| octet & CONTINUATION_BIT == 0)?;
+        Some(Self::from_encoded_arcs_unchecked(&self.0[..=end]))
+    }
+
+    /// Returns whether the first arcs of the object identifier are the arcs
+    /// of `prefix`.
+    #[must_use]
+    pub fn starts_with(&self, prefix: &Self) -> bool {
+        // Every subidentifier ends with an octet without the continuation
+        // bit, so a prefix of the bytes is always a prefix of the arcs.
+        self.0.starts_with(&prefix.0)
+    }
+
+    /// Splits off the first two arcs, which BER encodes as one subidentifier,
+    /// returning `None` if they can't be encoded.
+    fn split_root(&self) -> Option<(u8, OidArc<'_>, &[u8])> {
+        let mut arcs = self.arcs();
+        let first = arcs.next()?.0[0];
+        let second = arcs.next()?;
+        if first > MAX_OID_FIRST_OCTET as u8 {
+            return None;
+        }
+        Some((first, second, &self.0[1 + second.0.len()..]))
+    }
+
+    /// Returns the subidentifier BER encodes the first two arcs as, if it
+    /// fits in a `u128`.
+    fn root_subidentifier(first: u8, second: OidArc<'_>) -> Option<u128> {
+        second
+            .to_u128()?
+            .checked_add(u128::from(first) * u128::from(MAX_OID_SECOND_OCTET + 1))
+    }
+
+    fn big_root_subidentifier(first: u8, second: OidArc<'_>) -> BigUint {
+        second.to_biguint() + u32::from(first) * (MAX_OID_SECOND_OCTET + 1)
+    }
+
+    /// Returns the length of the contents octets of the object identifier in
+    /// BER, or `None` if it has less than two arcs or the first is invalid.
+    pub(crate) fn ber_contents_len(&self) -> Option<usize> {
+        let (first, second, rest) = self.split_root()?;
+        let root_len = match Self::root_subidentifier(first, second) {
+            Some(root) => base128_len(root),
+            None => Self::big_root_subidentifier(first, second)
+                .bits()
+                .div_ceil(7) as usize,
+        };
+        Some(root_len + rest.len())
+    }
+
+    /// Writes the contents octets of the object identifier in BER (X.690
+    /// 8.19) to `buffer`, returning `false` if it has less than two arcs or
+    /// the first is invalid.
+    pub(crate) fn write_ber_contents(&self, buffer: &mut Vec<u8>) -> bool {
+        let Some((first, second, rest)) = self.split_root() else {
+            return false;
+        };
+        match Self::root_subidentifier(first, second) {
+            Some(root) => push_subidentifier(root, buffer),
+            None => push_big_subidentifier(&Self::big_root_subidentifier(first, second), buffer),
+        }
+        buffer.extend_from_slice(rest);
+        true
+    }
+
+    /// Returns the number of octets needed to encode `arcs`. Used by
+    /// [`oid!`][crate::oid].
+    #[doc(hidden)]
     #[must_use]
-    pub const fn new_unchecked(slice: &[u32]) -> &Self {
-        unsafe { &*(core::ptr::from_ref::<[u32]>(slice) as *const Self) }
+    pub const fn __encoded_len(arcs: &[u128]) -> usize {
+        let mut len = 0;
+        let mut index = 0;
+        while index < arcs.len() {
+            len += base128_len(arcs[index]);
+            index += 1;
+        }
+        len
+    }
+
+    /// Encodes `arcs` as base-128 subidentifiers. Used by [`oid!`][crate::oid].
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn __encode<const N: usize>(arcs: &[u128]) -> [u8; N] {
+        let mut encoded = [0; N];
+        let mut position = 0;
+        let mut index = 0;
+        while index < arcs.len() {
+            let arc = arcs[index];
+            let mut digit = base128_len(arc);
+            while digit > 0 {
+                digit -= 1;
+                let octet = (arc >> (7 * digit)) as u8 & !CONTINUATION_BIT;
+                encoded[position] = if digit == 0 {
+                    octet
+                } else {
+                    octet | CONTINUATION_BIT
+                };
+                position += 1;
+            }
+            index += 1;
+        }
+        encoded
     }
 
-    /// Creates a new object identifier from `slice`.
+    /// Returns the name registered for the object identifier in the
+    /// [`oid_registry`], if any.
+    /// ```
+    /// use rasn::types::Oid;
     ///
-    /// # Safety
-    /// This allows you to create potentially invalid object identifiers which
-    /// may affect encoding validity.
-    pub fn new_unchecked_mut(slice: &mut [u32]) -> &mut Self {
-        unsafe { &mut *(core::ptr::from_mut::<[u32]>(slice) as *mut Self) }
+    /// assert_eq!(Oid::ISO_MEMBER_BODY.name(), Some("member-body"));
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        oid_registry::lookup(self).map(|entry| entry.name)
+    }
+
+    /// Returns the description registered for the object identifier in the
+    /// [`oid_registry`], if any.
+    #[must_use]
+    pub fn description(&self) -> Option<&'static str> {
+        oid_registry::lookup(self).map(|entry| entry.description)
+    }
+
+    /// Formats the object identifier in arc-name notation, naming each arc
+    /// that has a name in the [`oid_registry`].
+    /// ```
+    /// let oid = rasn::oid!([1, 2, 840, 113549, 7]);
+    /// assert_eq!(
+    ///     oid.arc_notation().to_string(),
+    ///     "{ iso(1) member-body(2) us(840) rsadsi(113549) 7 }"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn arc_notation(&self) -> ArcNotation<'_> {
+        ArcNotation(self)
+    }
+}
+
+/// A single arc of an object identifier, which can be arbitrarily large.
+/// Returned by [`Oid::arcs`].
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct OidArc<'a>(&'a [u8]);
+
+impl<'a> OidArc<'a> {
+    /// Returns the arc encoded as a base-128 subidentifier.
+    #[must_use]
+    pub fn as_encoded(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns the arc as a `u32`, or `None` if it is too large.
+    #[must_use]
+    pub fn to_u32(self) -> Option<u32> {
+        self.to_u128()?.try_into().ok()
+    }
+
+    /// Returns the arc as a `u64`, or `None` if it is too large.
+    #[must_use]
+    pub fn to_u64(self) -> Option<u64> {
+        self.to_u128()?.try_into().ok()
+    }
+
+    /// Returns the arc as a `u128`, or `None` if it is too large.
+    #[must_use]
+    pub fn to_u128(self) -> Option<u128> {
+        if self.0.len() > MAX_U128_SUBIDENTIFIER_LEN {
+            return None;
+        }
+        self.0.iter().try_fold(0u128, |arc, octet| {
+            arc.checked_mul(128)
+                .map(|arc| arc | u128::from(octet & !CONTINUATION_BIT))
+        })
+    }
+
+    fn to_biguint(self) -> BigUint {
+        let digits: Vec<u8> = self
+            .0
+            .iter()
+            .map(|octet| octet & !CONTINUATION_BIT)
+            .collect();
+        BigUint::from_radix_be(&digits, 128).expect("base-128 digits are less than 128")
+    }
+}
+
+impl PartialEq<u32> for OidArc<'_> {
+    fn eq(&self, rhs: &u32) -> bool {
+        self.to_u32() == Some(*rhs)
+    }
+}
+
+impl PartialOrd for OidArc<'_> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Ord for OidArc<'_> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        // Subidentifiers are minimally encoded, so a longer one is larger.
+        self.0
+            .len()
+            .cmp(&rhs.0.len())
+            .then_with(|| self.0.cmp(rhs.0))
+    }
+}
+
+impl core::fmt::Display for OidArc<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.to_u128() {
+            Some(arc) => write!(f, "{arc}"),
+            None => write!(f, "{}", self.to_biguint()),
+        }
+    }
+}
+
+impl core::fmt::Debug for OidArc<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+/// An iterator over the arcs of an object identifier. Returned by
+/// [`Oid::arcs`].
+#[derive(Clone, Debug)]
+pub struct Arcs<'a>(&'a [u8]);
+
+impl<'a> Iterator for Arcs<'a> {
+    type Item = OidArc<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self
+            .0
+            .iter()
+            .position(|octet| octet & CONTINUATION_BIT == 0)?;
+        let (arc, rest) = self.0.split_at(end + 1);
+        self.0 = rest;
+        Some(OidArc(arc))
+    }
+}
+
+impl DoubleEndedIterator for Arcs<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (_, init) = self.0.split_last()?;
+        let start = init
+            .iter()
+            .rposition(|octet| octet & CONTINUATION_BIT == 0)
+            .map_or(0, |end| end + 1);
+        let (rest, arc) = self.0.split_at(start);
+        self.0 = rest;
+        Some(OidArc(arc))
+    }
+}
+
+impl core::iter::FusedIterator for Arcs<'_> {}
+
+/// Formats an object identifier in arc-name notation. Returned by
+/// [`Oid::arc_notation`].
+#[derive(Clone, Copy, Debug)]
+pub struct ArcNotation<'a>(&'a Oid);
+
+impl core::fmt::Display for ArcNotation<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("{")?;
+        let mut end = 0;
+        for arc in self.0.arcs() {
+            end += arc.0.len();
+            match Oid::from_encoded_arcs_unchecked(&self.0.0[..end]).name() {
+                Some(name) => write!(f, " {name}({arc})")?,
+                None => write!(f, " {arc}")?,
+            }
+        }
+        f.write_str(" }")
     }
 }
 
 impl core::fmt::Display for Oid {
     /// Formats the object identifier as dot separated components.
     /// ```
-    /// use rasn::types::Oid;
-    ///
-    /// let internet = Oid::new(&[1, 3, 6, 1]).unwrap();
+    /// let internet = rasn::oid!([1, 3, 6, 1]);
     /// assert_eq!(&format!("{internet}"), "1.3.6.1");
     /// ```
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut oid_iter = self.0.iter();
+        let mut oid_iter = self.arcs();
         if let Some(first) = oid_iter.next() {
             write!(f, "{first}")?;
             for sub_oid in oid_iter {
@@ -102,99 +486,175 @@ impl core::fmt::Display for Oid {
     }
 }
 
-impl alloc::borrow::ToOwned for Oid {
-    type Owned = ObjectIdentifier;
+impl core::fmt::Debug for Oid {
+    /// Formats the object identifier as dot separated components. Names from
+    /// the [`oid_registry`] are only looked up by [`Oid::arc_notation`].
+    /// ```
+    /// use rasn::types::Oid;
+    ///
+    /// assert_eq!(format!("{:?}", Oid::ISO_MEMBER_BODY), "Oid(1.2)");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        debug_oid(self, "Oid", f)
+    }
+}
 
-    fn to_owned(&self) -> Self::Owned {
-        Self::Owned::new_unchecked(self.0.to_owned().into())
+fn debug_oid(oid: &Oid, ty: &str, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_tuple(ty).field(&format_args!("{oid}")).finish()
+}
+
+impl PartialOrd for Oid {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
     }
 }
 
-impl AsRef<[u32]> for Oid {
-    fn as_ref(&self) -> &[u32] {
-        self.0.as_ref()
+impl Ord for Oid {
+    /// Orders object identifiers arc by arc.
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.arcs().cmp(rhs.arcs())
+    }
+}
+
+impl alloc::borrow::ToOwned for Oid {
+    type Owned = ObjectIdentifier;
+
+    fn to_owned(&self) -> Self::Owned {
+        ObjectIdentifier(Cow::Owned(self.0.to_vec()))
     }
 }
 
 impl PartialEq<[u32]> for Oid {
     fn eq(&self, rhs: &[u32]) -> bool {
-        &self.0 == rhs
+        self.arcs().eq(rhs.iter().copied())
     }
 }
 
 impl<const N: usize> PartialEq<[u32; N]> for Oid {
     fn eq(&self, rhs: &[u32; N]) -> bool {
-        &self.0 == rhs
+        *self == rhs[..]
     }
 }
 
 impl PartialEq<Oid> for [u32] {
     fn eq(&self, rhs: &Oid) -> bool {
-        self == &rhs.0
+        rhs == self
     }
 }
 
 impl PartialEq<Oid> for ObjectIdentifier {
     fn eq(&self, rhs: &Oid) -> bool {
-        *self.0 == rhs.0
+        **self == *rhs
     }
 }
 
 impl PartialEq<&Oid> for ObjectIdentifier {
     fn eq(&self, rhs: &&Oid) -> bool {
-        *self.0 == rhs.0
+        **self == **rhs
     }
 }
 
 impl PartialEq<Oid> for &ObjectIdentifier {
     fn eq(&self, rhs: &Oid) -> bool {
-        *self.0 == rhs.0
+        ***self == *rhs
     }
 }
 
 impl<const N: usize> PartialEq<Oid> for [u32; N] {
     fn eq(&self, rhs: &Oid) -> bool {
-        self == &rhs.0
-    }
-}
-
-impl ops::Deref for Oid {
-    type Target = [u32];
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl ops::DerefMut for Oid {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        rhs == self
     }
 }
 
 /// A global unique identifier that identifies an concept, such as a
 /// organisation, or encoding rules. The "owned" version of [`Oid`].
-#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
-pub struct ObjectIdentifier(alloc::borrow::Cow<'static, [u32]>);
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct ObjectIdentifier(Cow<'static, [u8]>);
 
 impl ObjectIdentifier {
-    /// Creates a new object identifier from `vec`.
+    /// Creates a new object identifier from `arcs`.
     ///
-    /// Returns `None` if `vec` contains less than two components or the first
-    /// component is greater than 2.
-    pub fn new(arcs: impl Into<alloc::borrow::Cow<'static, [u32]>>) -> Option<Self> {
+    /// Returns `None` if `arcs` is empty or the first component is greater
+    /// than 2.
+    pub fn new(arcs: impl Into<Cow<'static, [u32]>>) -> Option<Self> {
         let arcs = arcs.into();
-        is_valid_oid(&arcs).then_some(Self(arcs))
+        arcs.first()
+            .is_some_and(|first| *first <= MAX_OID_FIRST_OCTET)
+            .then(|| Self::new_unchecked(arcs))
     }
 
-    /// Creates a new object identifier from `vec`.
+    /// Creates a new object identifier from `arcs`.
     ///
-    /// # Safety
+    /// # Validity
     /// This allows you to create potentially invalid object identifiers which
     /// may affect encoding validity.
     #[must_use]
-    pub const fn new_unchecked(vec: alloc::borrow::Cow<'static, [u32]>) -> Self {
-        Self(vec)
+    pub fn new_unchecked(arcs: Cow<'static, [u32]>) -> Self {
+        let mut encoded = Vec::with_capacity(arcs.len() + 4);
+        for arc in arcs.iter() {
+            push_subidentifier(u128::from(*arc), &mut encoded);
+        }
+        Self(Cow::Owned(encoded))
+    }
+
+    /// Creates a new object identifier from arcs encoded as base-128
+    /// subidentifiers, as returned by [`Oid::as_encoded_arcs`].
+    ///
+    /// Returns `None` if `encoded` is empty, is not a sequence of minimally
+    /// encoded subidentifiers, or the first arc is greater than 2.
+    pub fn from_encoded_arcs(encoded: impl Into<Cow<'static, [u8]>>) -> Option<Self> {
+        let encoded = encoded.into();
+        is_valid_oid(&encoded).then_some(Self(encoded))
+    }
+
+    /// Creates an object identifier from its contents octets in BER (X.690
+    /// 8.19), returning `None` if they are not valid.
+    pub(crate) fn from_ber_contents(contents: &[u8]) -> Option<Self> {
+        const THRESHOLD: u128 = (MAX_OID_FIRST_OCTET * (MAX_OID_SECOND_OCTET + 1)) as u128;
+
+        if !is_valid_subidentifiers(contents) {
+            return None;
+        }
+        let root_len = contents
+            .iter()
+            .position(|octet| octet & CONTINUATION_BIT == 0)?
+            + 1;
+        let (root, rest) = contents.split_at(root_len);
+        let root = OidArc(root);
+
+        let mut encoded = Vec::with_capacity(contents.len() + 1);
+        match root.to_u128() {
+            Some(root) if root < THRESHOLD => {
+                let second = root % u128::from(MAX_OID_SECOND_OCTET + 1);
+                encoded.push(((root - second) / u128::from(MAX_OID_SECOND_OCTET + 1)) as u8);
+                push_subidentifier(second, &mut encoded);
+            }
+            Some(root) => {
+                encoded.push(MAX_OID_FIRST_OCTET as u8);
+                push_subidentifier(root - THRESHOLD, &mut encoded);
+            }
+            None => {
+                encoded.push(MAX_OID_FIRST_OCTET as u8);
+                push_big_subidentifier(&(root.to_biguint() - THRESHOLD as u32), &mut encoded);
+            }
+        }
+        encoded.extend_from_slice(rest);
+        Some(Self(Cow::Owned(encoded)))
+    }
+
+    /// Parses an object identifier from dot separated components.
+    pub(crate) fn from_dotted(s: &str) -> Result<Self, ParseOidError> {
+        let mut encoded = Vec::with_capacity(s.len());
+        for arc in s.split('.') {
+            push_decimal_arc(arc, &mut encoded)?;
+        }
+        Self::from_encoded(encoded)
+    }
+
+    fn from_encoded(encoded: Vec<u8>) -> Result<Self, ParseOidError> {
+        Self::from_encoded_arcs(encoded).ok_or_else(|| {
+            ParseOidError::new("expected at least one arc, with the first being 0, 1 or 2")
+        })
     }
 }
 
@@ -207,13 +667,124 @@ impl core::fmt::Display for ObjectIdentifier {
     /// assert_eq!(&format!("{internet}"), "1.3.6.1");
     /// ```
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        ops::Deref::deref(self).fmt(f)
+        core::fmt::Display::fmt(&**self, f)
     }
 }
 
-impl AsRef<[u32]> for ObjectIdentifier {
-    fn as_ref(&self) -> &[u32] {
-        self.0.as_ref()
+impl core::str::FromStr for ObjectIdentifier {
+    type Err = ParseOidError;
+
+    /// Parses an object identifier from either dot separated components, or
+    /// arc-name notation where arcs are written as numbers, names registered
+    /// in the [`oid_registry`], or both. Arcs can be arbitrarily large.
+    /// ```
+    /// use rasn::types::{ObjectIdentifier, Oid};
+    ///
+    /// let rsadsi = Oid::ISO_MEMBER_BODY_US_RSADSI;
+    /// assert_eq!("1.2.840.113549".parse::<ObjectIdentifier>().unwrap(), rsadsi);
+    /// assert_eq!("{ 1 2 840 113549 }".parse::<ObjectIdentifier>().unwrap(), rsadsi);
+    /// assert_eq!("{ iso member-body(2) us rsadsi }".parse::<ObjectIdentifier>().unwrap(), rsadsi);
+    ///
+    /// let uuid: ObjectIdentifier = "2.25.329800735698586629295641978511506172918".parse().unwrap();
+    /// assert_eq!(uuid.arcs().last().unwrap().to_u128(), Some(329800735698586629295641978511506172918));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.strip_prefix('{') {
+            Some(notation) => Self::from_encoded(parse_arc_notation(
+                notation
+                    .strip_suffix('}')
+                    .ok_or_else(|| ParseOidError::new("expected a closing `}`"))?,
+            )?),
+            None => Self::from_dotted(s),
+        }
+    }
+}
+
+/// Parses the arcs between the braces of an object identifier in arc-name
+/// notation, as defined by ITU-T X.680 32.3, returning them encoded.
+fn parse_arc_notation(notation: &str) -> Result<Vec<u8>, ParseOidError> {
+    let notation = notation.replace('(', " ( ").replace(')', " ) ");
+    let mut tokens = notation.split_whitespace().peekable();
+    let mut encoded = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            push_decimal_arc(token, &mut encoded)?;
+        } else if !token.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(ParseOidError::new(alloc::format!("unexpected `{token}`")));
+        } else if tokens.next_if_eq(&"(").is_some() {
+            let arc = tokens.next().ok_or_else(|| {
+                ParseOidError::new(alloc::format!("expected an arc for `{token}`"))
+            })?;
+            push_decimal_arc(arc, &mut encoded)?;
+            tokens.next_if_eq(&")").ok_or_else(|| {
+                ParseOidError::new(alloc::format!("expected `)` after `{token}`"))
+            })?;
+        } else if let Some(entry) = oid_registry::lookup_arc(
+            (!encoded.is_empty()).then(|| Oid::from_encoded_arcs_unchecked(&encoded)),
+            token,
+        ) {
+            let arc = entry.oid.arcs().next_back().unwrap();
+            encoded.extend_from_slice(arc.as_encoded());
+        } else if let Some(entry) = oid_registry::lookup_name(token).filter(|_| encoded.is_empty())
+        {
+            // The first arcs may be given by the name of any registered
+            // object identifier.
+            encoded.extend_from_slice(entry.oid.as_encoded_arcs());
+        } else {
+            return Err(ParseOidError::new(alloc::format!(
+                "`{token}` is not a registered name"
+            )));
+        }
+    }
+
+    Ok(encoded)
+}
+
+/// An error parsing an [`ObjectIdentifier`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOidError {
+    message: String,
+}
+
+impl ParseOidError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl core::fmt::Display for ParseOidError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid object identifier: {}", self.message)
+    }
+}
+
+impl core::error::Error for ParseOidError {}
+
+impl core::fmt::Debug for ObjectIdentifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        debug_oid(self, "ObjectIdentifier", f)
+    }
+}
+
+impl PartialOrd for ObjectIdentifier {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Ord for ObjectIdentifier {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        (**self).cmp(&**rhs)
+    }
+}
+
+impl AsRef<Oid> for ObjectIdentifier {
+    fn as_ref(&self) -> &Oid {
+        self
     }
 }
 
@@ -233,13 +804,7 @@ impl ops::Deref for ObjectIdentifier {
     type Target = Oid;
 
     fn deref(&self) -> &Self::Target {
-        Oid::new_unchecked(&self.0)
-    }
-}
-
-impl ops::DerefMut for ObjectIdentifier {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        Oid::new_unchecked_mut(self.0.to_mut())
+        Oid::from_encoded_arcs_unchecked(&self.0)
     }
 }
 
@@ -251,19 +816,19 @@ impl<const N: usize> PartialEq<ObjectIdentifier> for [u32; N] {
 
 impl PartialEq<ObjectIdentifier> for Oid {
     fn eq(&self, rhs: &ObjectIdentifier) -> bool {
-        self.0 == *rhs.0
+        *self == **rhs
     }
 }
 
 impl PartialEq<ObjectIdentifier> for &Oid {
     fn eq(&self, rhs: &ObjectIdentifier) -> bool {
-        self.0 == *rhs.0
+        **self == **rhs
     }
 }
 
 impl PartialEq<[u32]> for ObjectIdentifier {
     fn eq(&self, rhs: &[u32]) -> bool {
-        self.0 == rhs
+        **self == *rhs
     }
 }
 
@@ -272,7 +837,7 @@ macro_rules! oids {
         impl Oid {
             $(
                 #[allow(missing_docs)]
-                pub const $name: &'static Oid = Oid::const_new(&[$($num),+]);
+                pub const $name: &'static Oid = crate::oid!([$($num),+]);
             )+
         }
     }
@@ -772,12 +1337,128 @@ mod test {
     use super::Oid;
 
     #[test]
-    fn transmute() {
-        let mut oid = ObjectIdentifier::new_unchecked(alloc::vec![1, 3, 6].into());
+    fn large_arcs() {
+        let uuid: ObjectIdentifier = "2.25.329800735698586629295641978511506172918"
+            .parse()
+            .unwrap();
+        let arcs: alloc::vec::Vec<_> = uuid.arcs().collect();
+        assert_eq!(arcs.len(), 3);
+        assert_eq!(arcs[1], 25);
+        assert_eq!(arcs[2].to_u32(), None);
+        assert_eq!(
+            arcs[2].to_u128(),
+            Some(329800735698586629295641978511506172918)
+        );
+        assert_eq!(
+            uuid,
+            crate::oid!([2, 25, 329800735698586629295641978511506172918])
+        );
+        assert_eq!(
+            uuid,
+            *crate::oid!("2.25.329800735698586629295641978511506172918")
+        );
+
+        let huge = "1.3.6.1.4.1.123456789012345678901234567890123456789012345678901234567890.7";
+        let oid: ObjectIdentifier = huge.parse().unwrap();
+        assert_eq!(oid.arc_count(), 8);
+        assert_eq!(oid.to_string(), huge);
+        assert!(oid.starts_with(crate::oid!([1, 3, 6, 1, 4, 1])));
+        assert!(oid.parent().unwrap() < &*oid);
+    }
+
+    #[test]
+    fn ber_contents() {
+        for (contents, oid) in [
+            (&[0x2b, 0x06, 0x01][..], "1.3.6.1"),
+            (&[0x88, 0x37, 0x01][..], "2.999.1"),
+            (&[0x69, 0x81, 0x00][..], "2.25.128"),
+            (&[0x27][..], "0.39"),
+            (&[0x50][..], "2.0"),
+        ] {
+            let decoded = ObjectIdentifier::from_ber_contents(contents).unwrap();
+            assert_eq!(decoded.to_string(), oid);
+            let mut encoded = alloc::vec::Vec::new();
+            assert!(decoded.write_ber_contents(&mut encoded));
+            assert_eq!(encoded, contents);
+            assert_eq!(decoded.ber_contents_len(), Some(contents.len()));
+        }
+
+        let huge: ObjectIdentifier = "2.1234567890123456789012345678901234567890"
+            .parse()
+            .unwrap();
+        let mut encoded = alloc::vec::Vec::new();
+        assert!(huge.write_ber_contents(&mut encoded));
+        assert_eq!(huge.ber_contents_len(), Some(encoded.len()));
+        assert_eq!(ObjectIdentifier::from_ber_contents(&encoded).unwrap(), huge);
+
+        assert!(!Oid::ISO.write_ber_contents(&mut encoded));
+        for contents in [&[][..], &[0x2b, 0x86][..], &[0x2b, 0x80, 0x01][..]] {
+            assert!(ObjectIdentifier::from_ber_contents(contents).is_none());
+        }
+    }
 
-        assert_eq!([1u32, 3, 6][..], *oid);
-        oid.reverse();
-        assert_eq!([6u32, 3, 1][..], *oid);
+    #[test]
+    fn ordering() {
+        let mut oids = [
+            crate::oid!([1, 3, 200]),
+            crate::oid!([1, 3, 6, 1]),
+            crate::oid!([2, 5]),
+            crate::oid!([1, 3, 16384]),
+            crate::oid!([1, 3]),
+        ];
+        oids.sort();
+        assert_eq!(
+            oids.map(alloc::string::ToString::to_string),
+            ["1.3", "1.3.6.1", "1.3.200", "1.3.16384", "2.5"]
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let rsadsi = Oid::ISO_MEMBER_BODY_US_RSADSI;
+        for s in [
+            "1.2.840.113549",
+            "{ 1 2 840 113549 }",
+            "{iso(1) member-body(2) us(840) rsadsi(113549)}",
+            "{ iso member-body us rsadsi }",
+            "{ iso (1) member-body 840 rsadsi }",
+            "{ rsadsi }",
+        ] {
+            assert_eq!(s.parse::<ObjectIdentifier>().unwrap(), rsadsi, "{s}");
+        }
+        assert_eq!(
+            "{ joint-iso-itu-t ds 15 }"
+                .parse::<ObjectIdentifier>()
+                .unwrap(),
+            Oid::JOINT_ISO_ITU_T_DS_NAME_FORM
+        );
+
+        for s in [
+            "",
+            "3.1",
+            "1..2",
+            "1.2.x",
+            "1.+2",
+            "{ 1 2",
+            "{ }",
+            "{ iso unknown }",
+            "{ iso member-body( }",
+            "{ iso 2 rsadsi }",
+        ] {
+            assert!(s.parse::<ObjectIdentifier>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn arc_notation() {
+        assert_eq!(
+            Oid::JOINT_ISO_ITU_T_DS_NAME_FORM.arc_notation().to_string(),
+            "{ joint-iso-itu-t(2) ds(5) 15 }"
+        );
+        assert_eq!(
+            crate::oid!([1, 2, 3]).arc_notation().to_string(),
+            "{ iso(1) member-body(2) 3 }"
+        );
     }
 
     #[test]
@@ -800,5 +1481,7 @@ mod test {
             Oid::ISO_MEMBER_BODY,
             ObjectIdentifier::new(vec![1, 2]).unwrap()
         );
+        assert_eq!(*Oid::ISO_MEMBER_BODY_US, [1, 2, 840]);
+        assert_ne!(*Oid::ISO_MEMBER_BODY_US, [1, 2]);
     }
 }
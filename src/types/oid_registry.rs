@@ -0,0 +1,244 @@
+//! A registry of names and descriptions for object identifiers.
+//!
+//! The registry starts out with the arcs defined by ITU-T X.660 and a few
+//! well known arcs beneath them, and crates defining object identifiers can
+//! [`register`] their own. Registered names are used when formatting object
+//! identifiers with [`Oid::arc_notation`], such as in AVN output, and when
+//! parsing object identifiers written in arc-name notation.
+//!
+//! ```
+//! use rasn::types::{ObjectIdentifier, oid_registry::{self, OidEntry}};
+//!
+//! static ENTRIES: &[OidEntry] = &[OidEntry::new(
+//!     rasn::oid!([1, 3, 6, 1, 4, 1, 46609]),
+//!     "wInnForum",
+//!     "Wireless Innovation Forum",
+//! )];
+//!
+//! oid_registry::register(ENTRIES);
+//!
+//! let oid: ObjectIdentifier = "{ iso org(3) dod(6) internet(1) private(4) enterprise(1) wInnForum 1 }"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(oid.to_string(), "1.3.6.1.4.1.46609.1");
+//! let forum = oid.parent().unwrap();
+//! assert_eq!(oid_registry::lookup(forum).unwrap().description, "Wireless Innovation Forum");
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use spin::{Lazy, RwLock};
+
+use super::Oid;
+
+/// A name and description registered for an object identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OidEntry {
+    /// The object identifier.
+    pub oid: &'static Oid,
+    /// The name of the final arc of `oid`, as used in arc-name notation.
+    pub name: &'static str,
+    /// A human readable description of what `oid` identifies.
+    pub description: &'static str,
+}
+
+impl OidEntry {
+    /// Creates a new entry naming `oid`.
+    #[must_use]
+    pub const fn new(oid: &'static Oid, name: &'static str, description: &'static str) -> Self {
+        Self {
+            oid,
+            name,
+            description,
+        }
+    }
+}
+
+macro_rules! entries {
+    ($($name:literal => [$($arc:literal),+], $description:literal;)+) => {
+        &[$(OidEntry::new(crate::oid!([$($arc),+]), $name, $description)),+]
+    };
+}
+
+/// The entries the registry starts out with.
+pub static BUILTIN: &[OidEntry] = entries! {
+    "itu-t" => [0], "ITU-T";
+    "ccitt" => [0], "ITU-T";
+    "recommendation" => [0, 0], "ITU-T Recommendations";
+    "question" => [0, 1], "ITU-T study group questions";
+    "administration" => [0, 2], "ITU-T administrations";
+    "network-operator" => [0, 3], "ITU-T network operators";
+    "identified-organization" => [0, 4], "ITU-T identified organizations";
+    "data" => [0, 9], "ITU-T data";
+    "pss" => [0, 9, 2342], "Public Switched Data Network";
+    "ucl" => [0, 9, 2342, 19200300], "University College London";
+    "pilot" => [0, 9, 2342, 19200300, 100], "Pilot directory service";
+
+    "iso" => [1], "ISO";
+    "standard" => [1, 0], "ISO standards";
+    "member-body" => [1, 2], "ISO member bodies";
+    "identified-organization" => [1, 3], "ISO identified organizations";
+    "org" => [1, 3], "ISO identified organizations";
+    "us" => [1, 2, 840], "United States";
+    "x9-57" => [1, 2, 840, 10040], "ANSI X9.57";
+    "ansi-X9-62" => [1, 2, 840, 10045], "ANSI X9.62";
+    "rsadsi" => [1, 2, 840, 113549], "RSA Data Security, Inc.";
+    "pkcs" => [1, 2, 840, 113549, 1], "Public-Key Cryptography Standards";
+    "digestAlgorithm" => [1, 2, 840, 113549, 2], "RSADSI digest algorithms";
+    "encryptionAlgorithm" => [1, 2, 840, 113549, 3], "RSADSI encryption algorithms";
+    "dod" => [1, 3, 6], "United States Department of Defense";
+    "internet" => [1, 3, 6, 1], "Internet";
+    "directory" => [1, 3, 6, 1, 1], "Internet directory";
+    "mgmt" => [1, 3, 6, 1, 2], "Internet management";
+    "experimental" => [1, 3, 6, 1, 3], "Internet experiments";
+    "private" => [1, 3, 6, 1, 4], "Internet private";
+    "enterprise" => [1, 3, 6, 1, 4, 1], "IANA private enterprise numbers";
+    "security" => [1, 3, 6, 1, 5], "Internet security";
+    "mechanisms" => [1, 3, 6, 1, 5, 5], "Internet security mechanisms";
+    "pkix" => [1, 3, 6, 1, 5, 5, 7], "Public-Key Infrastructure using X.509";
+    "snmpV2" => [1, 3, 6, 1, 6], "SNMPv2";
+    "oiw" => [1, 3, 14], "OSI Implementors' Workshop";
+
+    "joint-iso-itu-t" => [2], "Joint ISO/ITU-T";
+    "joint-iso-ccitt" => [2], "Joint ISO/ITU-T";
+    "asn1" => [2, 1], "Abstract Syntax Notation One";
+    "ds" => [2, 5], "Directory services (X.500)";
+    "country" => [2, 16], "Countries";
+    "us" => [2, 16, 840], "United States";
+    "organization" => [2, 16, 840, 1], "United States organizations";
+    "gov" => [2, 16, 840, 1, 101], "United States government";
+    "csor" => [2, 16, 840, 1, 101, 3], "Computer Security Objects Register";
+    "nistAlgorithms" => [2, 16, 840, 1, 101, 3, 4], "NIST algorithms";
+    "registration-procedures" => [2, 17], "Registration procedures";
+    "international-organizations" => [2, 23], "International organizations";
+};
+
+/// The entries of the registry, indexed by object identifier, by name and by
+/// arc so that lookups don't have to scan every entry.
+struct Registry {
+    /// The sets of entries, in order of precedence.
+    sets: Vec<&'static [OidEntry]>,
+    by_oid: BTreeMap<&'static Oid, &'static OidEntry>,
+    by_name: BTreeMap<&'static str, &'static OidEntry>,
+    by_arc: BTreeMap<(Option<&'static Oid>, &'static str), &'static OidEntry>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let mut registry = Self {
+            sets: Vec::new(),
+            by_oid: BTreeMap::new(),
+            by_name: BTreeMap::new(),
+            by_arc: BTreeMap::new(),
+        };
+        registry.add(BUILTIN);
+        registry
+    }
+
+    /// Adds `entries` with a higher precedence than every set added before.
+    fn add(&mut self, entries: &'static [OidEntry]) {
+        self.sets.insert(0, entries);
+        // Within a set, earlier entries take precedence over later ones.
+        for entry in entries.iter().rev() {
+            self.by_oid.insert(entry.oid, entry);
+            self.by_name.insert(entry.name, entry);
+            self.by_arc.insert((entry.oid.parent(), entry.name), entry);
+        }
+    }
+}
+
+static REGISTRY: Lazy<RwLock<Registry>> = Lazy::new(|| RwLock::new(Registry::new()));
+
+/// Adds `entries` to the registry.
+///
+/// Entries registered later take precedence over entries registered earlier
+/// and over [`BUILTIN`], so a name can be overridden by registering it again.
+/// Registering the same slice more than once has no effect.
+pub fn register(entries: &'static [OidEntry]) {
+    let mut registry = REGISTRY.write();
+    if !registry.sets.iter().any(|set| core::ptr::eq(*set, entries)) {
+        registry.add(entries);
+    }
+}
+
+/// Iterates over every entry in the registry, in order of precedence.
+pub fn entries() -> impl Iterator<Item = &'static OidEntry> {
+    let sets = REGISTRY.read().sets.clone();
+    sets.into_iter().flatten()
+}
+
+/// Returns the entry registered for `oid`, if any.
+///
+/// ```
+/// use rasn::types::{Oid, oid_registry};
+///
+/// let entry = oid_registry::lookup(Oid::ISO_MEMBER_BODY_US_RSADSI).unwrap();
+/// assert_eq!(entry.name, "rsadsi");
+/// ```
+#[must_use]
+pub fn lookup(oid: &Oid) -> Option<&'static OidEntry> {
+    REGISTRY.read().by_oid.get(oid).copied()
+}
+
+/// Returns the entry registered with `name`, if any. Names only have to be
+/// unique among the arcs beneath the same parent, so the entry with the
+/// highest precedence is returned when several share `name`.
+#[must_use]
+pub fn lookup_name(name: &str) -> Option<&'static OidEntry> {
+    REGISTRY.read().by_name.get(name).copied()
+}
+
+/// Returns the entry registered with `name` for an arc directly beneath
+/// `parent`, or for a root arc if `parent` is `None`.
+#[must_use]
+pub fn lookup_arc(parent: Option<&Oid>, name: &str) -> Option<&'static OidEntry> {
+    REGISTRY.read().by_arc.get(&(parent, name)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ObjectIdentifier;
+
+    #[test]
+    fn builtin() {
+        assert_eq!(lookup(Oid::ISO).unwrap().name, "iso");
+        assert_eq!(lookup(Oid::ITU_T).unwrap().name, "itu-t");
+        assert_eq!(lookup_arc(None, "iso").unwrap().oid, Oid::ISO);
+        assert_eq!(
+            lookup_arc(Some(crate::oid!([2, 16])), "us").unwrap().oid,
+            &[2, 16, 840]
+        );
+        assert_eq!(
+            lookup_arc(Some(Oid::ISO_MEMBER_BODY), "us").unwrap().oid,
+            &[1, 2, 840]
+        );
+        assert!(lookup(crate::oid!([1, 2, 3])).is_none());
+    }
+
+    #[test]
+    fn registered_entries_take_precedence() {
+        static ENTRIES: &[OidEntry] = &[
+            OidEntry::new(crate::oid!([1, 3, 14]), "oiw-test", "OIW"),
+            OidEntry::new(crate::oid!([1, 3, 14, 3, 2, 26]), "sha1-test", "SHA-1"),
+        ];
+        register(ENTRIES);
+        register(ENTRIES);
+
+        assert_eq!(
+            entries().filter(|entry| entry.name == "sha1-test").count(),
+            1
+        );
+        assert_eq!(lookup(crate::oid!([1, 3, 14])).unwrap().name, "oiw-test");
+        assert_eq!(
+            Oid::ISO_IDENTIFIED_ORGANISATION_OIW_SECSIG_ALGORITHM_SHA1
+                .arc_notation()
+                .to_string(),
+            "{ iso(1) identified-organization(3) oiw-test(14) 3 2 sha1-test(26) }"
+        );
+        assert_eq!(
+            "{ oiw-test 3 2 26 }".parse::<ObjectIdentifier>().unwrap(),
+            Oid::ISO_IDENTIFIED_ORGANISATION_OIW_SECSIG_ALGORITHM_SHA1
+        );
+    }
+}
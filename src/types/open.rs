@@ -1,37 +1,779 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
 use super::{
-    AsnType, BitString, GeneralizedTime, InstanceOf, Integer, ObjectIdentifier, OctetString,
-    UniversalString, UtcTime, VisibleString,
+    AsnType, BitString, BmpString, Choice, Class, Constraint, Constraints, Date, DecodeChoice,
+    GeneralString, GeneralizedTime, GraphicString, Ia5String, Identifier, InstanceOf, Integer,
+    NumericString, ObjectIdentifier, OctetString, PrintableString, SequenceOf, SetOf, Tag, TagTree,
+    TeletexString, UniversalString, UtcTime, Utf8String, VisibleString, constraints,
 };
-use crate::{Decode, Encode};
+use crate::{Codec, Decode, Decoder, Encode, Encoder, de::Error as _, value::Value};
 
 /// An "open" type representing any valid ASN.1 type.
-#[derive(AsnType, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Decode, Encode)]
-#[rasn(crate_root = "crate")]
-#[rasn(choice)]
+///
+/// Every universal type has its own variant, and values of any other type are
+/// held by [`Open::Tagged`] with the tag they were encoded with. In BER, CER
+/// and DER the tagged value is an [`Open::OctetString`] with the contents of
+/// a primitive value, or an [`Open::Sequence`] with the components of a
+/// constructed value, so that it encodes back to the same bytes. Other codecs
+/// encode a tagged value as its tag class, tag number and value.
+///
+/// Without a schema a `SEQUENCE` can't be told apart from a `SEQUENCE OF`,
+/// so [`Open::Sequence`] and [`Open::Set`] hold the components of any
+/// `SEQUENCE` or `SET` value.
+///
+/// Unlike [`Value`], which can also hold the identifiers of components and
+/// alternatives but needs a [`Type`](crate::value::Type) to be encoded with
+/// codecs other than BER, CER and DER, `Open` is itself a `CHOICE` that every
+/// codec can encode and decode. Every `Open` converts to the [`Value`] that
+/// BER decodes from its encoding, and back with [`TryFrom`].
+///
+/// ```rust
+/// use rasn::types::{Open, Tag};
+///
+/// // SEQUENCE { INTEGER 5, [0] IMPLICIT OCTET STRING }
+/// let encoded = [0x30, 0x07, 0x02, 0x01, 0x05, 0x80, 0x02, 0xCA, 0xFE];
+/// let open: Open = rasn::der::decode(&encoded).unwrap();
+/// assert_eq!(
+///     open,
+///     Open::Sequence(vec![
+///         Open::Integer(5.into()),
+///         Open::Tagged {
+///             tag: Tag::new_context(0),
+///             value: Box::new(Open::OctetString(vec![0xCA, 0xFE].into())),
+///         },
+///     ])
+/// );
+/// assert_eq!(rasn::der::encode(&open).unwrap(), encoded);
+/// ```
+#[derive(Debug, Clone)]
 pub enum Open {
     /// A bit string value.
     BitString(BitString),
-    // BmpString(BmpString),
+    /// A BMP string value.
+    BmpString(BmpString),
     /// A bool value.
     Bool(bool),
+    /// A date value.
+    Date(Date),
+    /// An enumerated value, represented by its discriminant.
+    Enumerated(Integer),
+    /// A general string value.
+    GeneralString(GeneralString),
     /// A generalized time value.
     GeneralizedTime(GeneralizedTime),
-    // Ia5String(Ia5String),
+    /// A graphic string value.
+    GraphicString(GraphicString),
+    /// An IA5 string value.
+    Ia5String(Ia5String),
+    /// An "instance of" value.
+    InstanceOf(Box<InstanceOf<Open>>),
     /// A integer value.
     Integer(Integer),
     /// A null value.
     Null,
+    /// A numeric string value.
+    NumericString(NumericString),
     /// A object identifier value.
     ObjectIdentifier(ObjectIdentifier),
     /// A octet string value.
     OctetString(OctetString),
-    // PrintableString(PrintableString),
+    /// A printable string value.
+    PrintableString(PrintableString),
+    /// A real value.
+    #[cfg(feature = "f64")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "f64")))]
+    Real(f64),
+    /// A sequence value, as its components in order.
+    Sequence(SequenceOf<Open>),
+    /// A set value, as its components.
+    Set(SetOf<Open>),
+    /// A teletex string value.
+    TeletexString(TeletexString),
     /// A universal string value.
     UniversalString(UniversalString),
     /// A utc time value.
     UtcTime(UtcTime),
+    /// A UTF-8 string value.
+    Utf8String(Utf8String),
     /// A visible string value.
     VisibleString(VisibleString),
-    /// An "instance of" value.
-    InstanceOf(alloc::boxed::Box<InstanceOf<Open>>),
+    /// A value encoded with `tag` in place of its own tag.
+    Tagged {
+        /// The tag of the value.
+        tag: Tag,
+        /// The tagged value.
+        value: Box<Open>,
+    },
+}
+
+impl Open {
+    /// The tag standing for [`Open::Tagged`] in the list of variants, which
+    /// codecs that don't encode the tag of every value use to pick the
+    /// variant. BER, CER and DER encode the actual tag instead.
+    const TAGGED: Tag = Tag::new_private(0);
+
+    /// Returns the tag the value is encoded with.
+    #[must_use]
+    pub fn tag(&self) -> Tag {
+        match self {
+            Self::Tagged { tag, .. } => *tag,
+            _ => self.variant_tag(),
+        }
+    }
+
+    /// Returns the tag identifying the variant in [`Choice::VARIANTS`].
+    fn variant_tag(&self) -> Tag {
+        match self {
+            Self::BitString(_) => Tag::BIT_STRING,
+            Self::BmpString(_) => Tag::BMP_STRING,
+            Self::Bool(_) => Tag::BOOL,
+            Self::Date(_) => Tag::DATE,
+            Self::Enumerated(_) => Tag::ENUMERATED,
+            Self::GeneralString(_) => Tag::GENERAL_STRING,
+            Self::GeneralizedTime(_) => Tag::GENERALIZED_TIME,
+            Self::GraphicString(_) => Tag::GRAPHIC_STRING,
+            Self::Ia5String(_) => Tag::IA5_STRING,
+            Self::InstanceOf(_) => Tag::EXTERNAL,
+            Self::Integer(_) => Tag::INTEGER,
+            Self::Null => Tag::NULL,
+            Self::NumericString(_) => Tag::NUMERIC_STRING,
+            Self::ObjectIdentifier(_) => Tag::OBJECT_IDENTIFIER,
+            Self::OctetString(_) => Tag::OCTET_STRING,
+            Self::PrintableString(_) => Tag::PRINTABLE_STRING,
+            #[cfg(feature = "f64")]
+            Self::Real(_) => Tag::REAL,
+            Self::Sequence(_) => Tag::SEQUENCE,
+            Self::Set(_) => Tag::SET,
+            Self::TeletexString(_) => Tag::TELETEX_STRING,
+            Self::UniversalString(_) => Tag::UNIVERSAL_STRING,
+            Self::UtcTime(_) => Tag::UTC_TIME,
+            Self::Utf8String(_) => Tag::UTF8_STRING,
+            Self::VisibleString(_) => Tag::VISIBLE_STRING,
+            Self::Tagged { .. } => Self::TAGGED,
+        }
+    }
+
+    /// Returns the identifier of the variant for text-based encoding rules.
+    fn variant_identifier(&self) -> Identifier {
+        let index = Self::VARIANTS
+            .iter()
+            .position(|variant| *variant == TagTree::Leaf(self.variant_tag()))
+            .unwrap_or_default();
+
+        Identifier(Some(Self::IDENTIFIERS[index]))
+    }
+
+    /// Encodes the value of the variant implicitly tagged with `tag`.
+    fn encode_value<'b, E: Encoder<'b>>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        identifier: Identifier,
+    ) -> Result<(), E::Error> {
+        match self {
+            Self::BitString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::BmpString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::Bool(value) => value.encode_with_tag_and_identifier(encoder, tag, identifier),
+            Self::Date(value) => value.encode_with_tag_and_identifier(encoder, tag, identifier),
+            Self::Enumerated(value) | Self::Integer(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::GeneralString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::GeneralizedTime(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::GraphicString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::Ia5String(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::InstanceOf(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::Null => encoder.encode_null(tag, identifier).map(drop),
+            Self::NumericString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::ObjectIdentifier(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::OctetString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::PrintableString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            #[cfg(feature = "f64")]
+            Self::Real(value) => value.encode_with_tag_and_identifier(encoder, tag, identifier),
+            Self::Sequence(value) => value.encode_with_tag_and_identifier(encoder, tag, identifier),
+            Self::Set(value) => value.encode_with_tag_and_identifier(encoder, tag, identifier),
+            Self::TeletexString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::UniversalString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::UtcTime(value) => value.encode_with_tag_and_identifier(encoder, tag, identifier),
+            Self::Utf8String(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::VisibleString(value) => {
+                value.encode_with_tag_and_identifier(encoder, tag, identifier)
+            }
+            Self::Tagged { value, .. } if is_tag_based(encoder.codec()) => {
+                value.encode_value(encoder, tag, identifier)
+            }
+            Self::Tagged { tag, value } => TaggedForm {
+                class: tag.class.into(),
+                number: tag.value,
+                value: &**value,
+            }
+            .encode_with_identifier(encoder, identifier),
+        }
+    }
+
+    /// Decodes a value with a tag that isn't universal from a BER, CER or
+    /// DER `decoder`.
+    fn decode_tagged<D: Decoder>(decoder: &mut D, tag: Tag) -> Result<Self, D::Error> {
+        let options = match decoder.codec() {
+            Codec::Cer => crate::ber::de::DecoderOptions::cer(),
+            Codec::Der => crate::ber::de::DecoderOptions::der(),
+            _ => crate::ber::de::DecoderOptions::ber(),
+        };
+        let any = decoder.decode_any(Tag::EOC)?;
+        let decoder = &mut crate::ber::de::Decoder::new(any.as_bytes(), options);
+
+        let value = if decoder.peek_identifier()?.is_constructed() {
+            Self::Sequence(decoder.parse_constructed_contents(tag, true, |decoder| {
+                let mut components = Vec::new();
+                while !decoder.is_end_of_contents() {
+                    components.push(Self::decode(decoder)?);
+                }
+                Ok(components)
+            })?)
+        } else {
+            Self::OctetString(OctetString::decode_with_tag(decoder, tag)?)
+        };
+
+        Ok(Self::Tagged {
+            tag,
+            value: Box::new(value),
+        })
+    }
+}
+
+/// Returns whether `codec` encodes the tag of every value.
+fn is_tag_based(codec: Codec) -> bool {
+    matches!(codec, Codec::Ber | Codec::Cer | Codec::Der)
+}
+
+/// The class of the tag of an [`Open::Tagged`] value.
+#[derive(AsnType, Decode, Encode, Debug, Clone, Copy, PartialEq, Eq)]
+#[rasn(crate_root = "crate")]
+#[rasn(enumerated)]
+enum TagClass {
+    Universal,
+    Application,
+    Context,
+    Private,
+}
+
+impl From<Class> for TagClass {
+    fn from(class: Class) -> Self {
+        match class {
+            Class::Universal => Self::Universal,
+            Class::Application => Self::Application,
+            Class::Context => Self::Context,
+            Class::Private => Self::Private,
+        }
+    }
+}
+
+impl From<TagClass> for Class {
+    fn from(class: TagClass) -> Self {
+        match class {
+            TagClass::Universal => Self::Universal,
+            TagClass::Application => Self::Application,
+            TagClass::Context => Self::Context,
+            TagClass::Private => Self::Private,
+        }
+    }
+}
+
+/// An [`Open::Tagged`] value in codecs that don't encode tags.
+#[derive(AsnType, Decode, Encode)]
+#[rasn(crate_root = "crate")]
+struct TaggedForm<V> {
+    class: TagClass,
+    number: u32,
+    value: V,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    /// Orders values of different variants by the tag of their variant, and
+    /// `REAL` values by [`f64::total_cmp`].
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        match (self, other) {
+            (Self::BitString(a), Self::BitString(b)) => a.cmp(b),
+            (Self::BmpString(a), Self::BmpString(b)) => a.cmp(b),
+            (Self::Bool(a), Self::Bool(b)) => a.cmp(b),
+            (Self::Date(a), Self::Date(b)) => a.cmp(b),
+            (Self::Enumerated(a), Self::Enumerated(b)) => a.cmp(b),
+            (Self::GeneralString(a), Self::GeneralString(b)) => a.cmp(b),
+            (Self::GeneralizedTime(a), Self::GeneralizedTime(b)) => a.cmp(b),
+            (Self::GraphicString(a), Self::GraphicString(b)) => a.cmp(b),
+            (Self::Ia5String(a), Self::Ia5String(b)) => a.cmp(b),
+            (Self::InstanceOf(a), Self::InstanceOf(b)) => a.cmp(b),
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::NumericString(a), Self::NumericString(b)) => a.cmp(b),
+            (Self::ObjectIdentifier(a), Self::ObjectIdentifier(b)) => a.cmp(b),
+            (Self::OctetString(a), Self::OctetString(b)) => a.cmp(b),
+            (Self::PrintableString(a), Self::PrintableString(b)) => a.cmp(b),
+            #[cfg(feature = "f64")]
+            (Self::Real(a), Self::Real(b)) => a.total_cmp(b),
+            (Self::Sequence(a), Self::Sequence(b)) => a.cmp(b),
+            (Self::Set(a), Self::Set(b)) => a.cmp(b),
+            (Self::TeletexString(a), Self::TeletexString(b)) => a.cmp(b),
+            (Self::UniversalString(a), Self::UniversalString(b)) => a.cmp(b),
+            (Self::UtcTime(a), Self::UtcTime(b)) => a.cmp(b),
+            (Self::Utf8String(a), Self::Utf8String(b)) => a.cmp(b),
+            (Self::VisibleString(a), Self::VisibleString(b)) => a.cmp(b),
+            (
+                Self::Tagged { tag, value },
+                Self::Tagged {
+                    tag: other_tag,
+                    value: other_value,
+                },
+            ) => (tag, value).cmp(&(other_tag, other_value)),
+            _ => self.variant_tag().cmp(&other.variant_tag()),
+        }
+    }
+}
+
+impl AsnType for Open {
+    const TAG: Tag = Tag::EOC;
+    const TAG_TREE: TagTree = TagTree::Choice(Self::VARIANTS);
+    const IDENTIFIER: Identifier = Identifier(Some("Open"));
+    const IS_CHOICE: bool = true;
+}
+
+impl Choice for Open {
+    const VARIANTS: &'static [TagTree] = &[
+        TagTree::Leaf(Tag::BIT_STRING),
+        TagTree::Leaf(Tag::BMP_STRING),
+        TagTree::Leaf(Tag::BOOL),
+        TagTree::Leaf(Tag::DATE),
+        TagTree::Leaf(Tag::ENUMERATED),
+        TagTree::Leaf(Tag::GENERAL_STRING),
+        TagTree::Leaf(Tag::GENERALIZED_TIME),
+        TagTree::Leaf(Tag::GRAPHIC_STRING),
+        TagTree::Leaf(Tag::IA5_STRING),
+        TagTree::Leaf(Tag::EXTERNAL),
+        TagTree::Leaf(Tag::INTEGER),
+        TagTree::Leaf(Tag::NULL),
+        TagTree::Leaf(Tag::NUMERIC_STRING),
+        TagTree::Leaf(Tag::OBJECT_IDENTIFIER),
+        TagTree::Leaf(Tag::OCTET_STRING),
+        TagTree::Leaf(Tag::PRINTABLE_STRING),
+        #[cfg(feature = "f64")]
+        TagTree::Leaf(Tag::REAL),
+        TagTree::Leaf(Tag::SEQUENCE),
+        TagTree::Leaf(Tag::SET),
+        TagTree::Leaf(Tag::TELETEX_STRING),
+        TagTree::Leaf(Tag::UNIVERSAL_STRING),
+        TagTree::Leaf(Tag::UTC_TIME),
+        TagTree::Leaf(Tag::UTF8_STRING),
+        TagTree::Leaf(Tag::VISIBLE_STRING),
+        TagTree::Leaf(Self::TAGGED),
+    ];
+    const VARIANCE_CONSTRAINT: Constraints = Constraints::new(&[Constraint::Value(
+        constraints::Extensible::new(constraints::Value::new(constraints::Bounded::const_new(
+            0,
+            Self::VARIANTS.len() as i128 - 1,
+        )))
+        .set_extensible(false),
+    )]);
+    const IDENTIFIERS: &'static [&'static str] = &[
+        "BitString",
+        "BmpString",
+        "Bool",
+        "Date",
+        "Enumerated",
+        "GeneralString",
+        "GeneralizedTime",
+        "GraphicString",
+        "Ia5String",
+        "InstanceOf",
+        "Integer",
+        "Null",
+        "NumericString",
+        "ObjectIdentifier",
+        "OctetString",
+        "PrintableString",
+        #[cfg(feature = "f64")]
+        "Real",
+        "Sequence",
+        "Set",
+        "TeletexString",
+        "UniversalString",
+        "UtcTime",
+        "Utf8String",
+        "VisibleString",
+        "Tagged",
+    ];
+}
+
+impl DecodeChoice for Open {
+    fn from_tag<D: Decoder>(decoder: &mut D, tag: Tag) -> Result<Self, D::Error> {
+        match tag {
+            Tag::BIT_STRING => <_>::decode(decoder).map(Self::BitString),
+            Tag::BMP_STRING => <_>::decode(decoder).map(Self::BmpString),
+            Tag::BOOL => <_>::decode(decoder).map(Self::Bool),
+            Tag::DATE => <_>::decode(decoder).map(Self::Date),
+            Tag::ENUMERATED => Integer::decode_with_tag(decoder, tag).map(Self::Enumerated),
+            Tag::GENERAL_STRING => <_>::decode(decoder).map(Self::GeneralString),
+            Tag::GENERALIZED_TIME => <_>::decode(decoder).map(Self::GeneralizedTime),
+            Tag::GRAPHIC_STRING => <_>::decode(decoder).map(Self::GraphicString),
+            Tag::IA5_STRING => <_>::decode(decoder).map(Self::Ia5String),
+            Tag::EXTERNAL => <_>::decode(decoder).map(Self::InstanceOf),
+            Tag::INTEGER => <_>::decode(decoder).map(Self::Integer),
+            Tag::NULL => <()>::decode(decoder).map(|()| Self::Null),
+            Tag::NUMERIC_STRING => <_>::decode(decoder).map(Self::NumericString),
+            Tag::OBJECT_IDENTIFIER => <_>::decode(decoder).map(Self::ObjectIdentifier),
+            Tag::OCTET_STRING => <_>::decode(decoder).map(Self::OctetString),
+            Tag::PRINTABLE_STRING => <_>::decode(decoder).map(Self::PrintableString),
+            #[cfg(feature = "f64")]
+            Tag::REAL => <_>::decode(decoder).map(Self::Real),
+            Tag::SEQUENCE => <_>::decode(decoder).map(Self::Sequence),
+            Tag::SET => <_>::decode(decoder).map(Self::Set),
+            Tag::TELETEX_STRING => <_>::decode(decoder).map(Self::TeletexString),
+            Tag::UNIVERSAL_STRING => <_>::decode(decoder).map(Self::UniversalString),
+            Tag::UTC_TIME => <_>::decode(decoder).map(Self::UtcTime),
+            Tag::UTF8_STRING => <_>::decode(decoder).map(Self::Utf8String),
+            Tag::VISIBLE_STRING => <_>::decode(decoder).map(Self::VisibleString),
+            _ if is_tag_based(decoder.codec()) => Self::decode_tagged(decoder, tag),
+            Self::TAGGED => {
+                let form = TaggedForm::<Box<Open>>::decode(decoder)?;
+                Ok(Self::Tagged {
+                    tag: Tag::new(form.class.into(), form.number),
+                    value: form.value,
+                })
+            }
+            _ => Err(D::Error::no_valid_choice("Open", decoder.codec())),
+        }
+    }
+}
+
+impl Encode for Open {
+    fn encode<'b, E: Encoder<'b>>(&self, encoder: &mut E) -> Result<(), E::Error> {
+        self.encode_with_identifier(encoder, Self::IDENTIFIER)
+    }
+
+    fn encode_with_identifier<'b, E: Encoder<'b>>(
+        &self,
+        encoder: &mut E,
+        identifier: Identifier,
+    ) -> Result<(), E::Error> {
+        let variant_tag = self.variant_tag();
+        encoder
+            .encode_choice::<Self>(
+                Self::CONSTRAINTS,
+                variant_tag,
+                |encoder| {
+                    self.encode_value(encoder, self.tag(), self.variant_identifier())
+                        .map(|()| variant_tag)
+                },
+                identifier,
+            )
+            .map(drop)
+    }
+
+    fn encode_with_tag_and_constraints<'b, E: Encoder<'b>>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        _: Constraints,
+        identifier: Identifier,
+    ) -> Result<(), E::Error> {
+        encoder
+            .encode_explicit_prefix(tag, self, identifier)
+            .map(drop)
+    }
+}
+
+impl Decode for Open {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, D::Error> {
+        decoder.decode_choice(Self::CONSTRAINTS)
+    }
+
+    fn decode_with_tag_and_constraints<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        _: Constraints,
+    ) -> Result<Self, D::Error> {
+        decoder.decode_explicit_prefix(tag)
+    }
+}
+
+impl From<Open> for Value {
+    /// Converts the value without losing anything that is encoded in BER:
+    /// [`Open::Sequence`] and [`Open::Set`] become [`Value::SequenceOf`] and
+    /// [`Value::SetOf`], and an [`Open::InstanceOf`] becomes the
+    /// [`Value::Tagged`] that BER decodes an `EXTERNAL` value to.
+    fn from(open: Open) -> Self {
+        match open {
+            Open::BitString(value) => Self::BitString(value),
+            Open::BmpString(value) => Self::BmpString(value),
+            Open::Bool(value) => Self::Boolean(value),
+            Open::Date(value) => Self::Date(value),
+            Open::Enumerated(value) => Self::Enumerated(value),
+            Open::GeneralString(value) => Self::GeneralString(value),
+            Open::GeneralizedTime(value) => Self::GeneralizedTime(value),
+            Open::GraphicString(value) => Self::GraphicString(value),
+            Open::Ia5String(value) => Self::Ia5String(value),
+            Open::InstanceOf(instance) => {
+                let InstanceOf { type_id, value } = *instance;
+                Self::Tagged {
+                    tag: Tag::EXTERNAL,
+                    value: Box::new(Self::SequenceOf(vec![
+                        Self::ObjectIdentifier(type_id),
+                        Self::Tagged {
+                            tag: Tag::new(Class::Context, 0),
+                            value: Box::new(Self::SequenceOf(vec![value.into()])),
+                        },
+                    ])),
+                }
+            }
+            Open::Integer(value) => Self::Integer(value),
+            Open::Null => Self::Null,
+            Open::NumericString(value) => Self::NumericString(value),
+            Open::ObjectIdentifier(value) => Self::ObjectIdentifier(value),
+            Open::OctetString(value) => Self::OctetString(value),
+            Open::PrintableString(value) => Self::PrintableString(value),
+            #[cfg(feature = "f64")]
+            Open::Real(value) => Self::Real(value),
+            Open::Sequence(components) => {
+                Self::SequenceOf(components.into_iter().map(Self::from).collect())
+            }
+            Open::Set(components) => Self::SetOf(
+                components
+                    .into_vec()
+                    .into_iter()
+                    .map(Self::from)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            Open::TeletexString(value) => Self::TeletexString(value),
+            Open::UniversalString(value) => Self::UniversalString(value.value),
+            Open::UtcTime(value) => Self::UtcTime(value),
+            Open::Utf8String(value) => Self::Utf8String(value),
+            Open::VisibleString(value) => Self::VisibleString(value),
+            Open::Tagged { tag, value } => Self::Tagged {
+                tag,
+                value: Box::new((*value).into()),
+            },
+        }
+    }
+}
+
+impl TryFrom<Value> for Open {
+    type Error = Value;
+
+    /// Converts the value back from the form [`From<Open>`] gives it,
+    /// returning the component that has no [`Open`] equivalent, a
+    /// [`Value::Sequence`], [`Value::Set`] or [`Value::Choice`] whose
+    /// identifiers would be lost, as the error.
+    fn try_from(value: Value) -> Result<Self, Value> {
+        let components = |components: Vec<Value>| {
+            components
+                .into_iter()
+                .map(Self::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match value {
+            Value::Boolean(value) => Self::Bool(value),
+            Value::Integer(value) => Self::Integer(value),
+            Value::BitString(value) => Self::BitString(value),
+            Value::OctetString(value) => Self::OctetString(value),
+            Value::Null => Self::Null,
+            Value::ObjectIdentifier(value) => Self::ObjectIdentifier(value),
+            #[cfg(feature = "f64")]
+            Value::Real(value) => Self::Real(value),
+            Value::Enumerated(value) => Self::Enumerated(value),
+            Value::Utf8String(value) => Self::Utf8String(value),
+            Value::NumericString(value) => Self::NumericString(value),
+            Value::PrintableString(value) => Self::PrintableString(value),
+            Value::TeletexString(value) => Self::TeletexString(value),
+            Value::VisibleString(value) => Self::VisibleString(value),
+            Value::Ia5String(value) => Self::Ia5String(value),
+            Value::GeneralString(value) => Self::GeneralString(value),
+            Value::GraphicString(value) => Self::GraphicString(value),
+            Value::BmpString(value) => Self::BmpString(value),
+            Value::UniversalString(value) => Self::UniversalString(UniversalString::new(value)),
+            Value::UtcTime(value) => Self::UtcTime(value),
+            Value::GeneralizedTime(value) => Self::GeneralizedTime(value),
+            Value::Date(value) => Self::Date(value),
+            Value::SequenceOf(values) => Self::Sequence(components(values)?),
+            Value::SetOf(values) => Self::Set(components(values.into_vec())?.into()),
+            Value::Tagged { tag, value } => match *value {
+                Value::SequenceOf(components)
+                    if tag == Tag::EXTERNAL && is_instance_of(&components) =>
+                {
+                    let mut components = components.into_iter();
+                    let (Some(Value::ObjectIdentifier(type_id)), Some(Value::Tagged { value, .. })) =
+                        (components.next(), components.next())
+                    else {
+                        unreachable!("checked by `is_instance_of`")
+                    };
+                    let Value::SequenceOf(mut value) = *value else {
+                        unreachable!("checked by `is_instance_of`")
+                    };
+                    Self::InstanceOf(Box::new(InstanceOf {
+                        type_id,
+                        value: Self::try_from(value.remove(0))?,
+                    }))
+                }
+                value => Self::Tagged {
+                    tag,
+                    value: Box::new(Self::try_from(value)?),
+                },
+            },
+            value => return Err(value),
+        })
+    }
+}
+
+/// Returns whether `components` are those of an `EXTERNAL` value holding an
+/// [`InstanceOf`], an object identifier followed by an explicitly `[0]`
+/// tagged value.
+fn is_instance_of(components: &[Value]) -> bool {
+    matches!(
+        components,
+        [Value::ObjectIdentifier(_), Value::Tagged { tag, value }]
+            if *tag == Tag::new(Class::Context, 0)
+                && matches!(&**value, Value::SequenceOf(values) if values.len() == 1)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn universal_and_tagged_values() {
+        // SEQUENCE { ENUMERATED 1, UTF8String "hi", [APPLICATION 1] { NULL },
+        //            SET { BOOLEAN TRUE }, [PRIVATE 3] IMPLICIT OCTET STRING }
+        let encoded = [
+            0x30, 0x13, 0x0A, 0x01, 0x01, 0x0C, 0x02, b'h', b'i', 0x61, 0x02, 0x05, 0x00, 0x31,
+            0x03, 0x01, 0x01, 0xFF, 0xC3, 0x01, 0x2A,
+        ];
+        let open = Open::Sequence(vec![
+            Open::Enumerated(1.into()),
+            Open::Utf8String("hi".into()),
+            Open::Tagged {
+                tag: Tag::new_application(1),
+                value: Box::new(Open::Sequence(vec![Open::Null])),
+            },
+            Open::Set(SetOf::from_vec(vec![Open::Bool(true)])),
+            Open::Tagged {
+                tag: Tag::new_private(3),
+                value: Box::new(Open::OctetString(vec![0x2A].into())),
+            },
+        ]);
+
+        assert_eq!(crate::ber::decode::<Open>(&encoded).unwrap(), open);
+        assert_eq!(crate::der::encode(&open).unwrap(), encoded);
+
+        let json = crate::jer::encode(&open).unwrap();
+        assert_eq!(crate::jer::decode::<Open>(&json).unwrap(), open);
+
+        for codec in [Codec::Uper, Codec::Aper, Codec::Oer] {
+            let encoded = codec.encode_to_binary(&open).unwrap();
+            assert_eq!(codec.decode_from_binary::<Open>(&encoded).unwrap(), open);
+        }
+    }
+
+    #[test]
+    fn value_conversion() {
+        let open = Open::Sequence(vec![
+            Open::UniversalString(UniversalString::new("hi".into())),
+            Open::Set(SetOf::from_vec(vec![Open::Enumerated(2.into())])),
+            Open::InstanceOf(Box::new(InstanceOf {
+                type_id: ObjectIdentifier::new(vec![1, 2, 3]).unwrap(),
+                value: Open::Tagged {
+                    tag: Tag::new_context(1),
+                    value: Box::new(Open::OctetString(vec![0x2A].into())),
+                },
+            })),
+        ]);
+        let encoded = crate::der::encode(&open).unwrap();
+
+        let value = Value::from(open.clone());
+        assert_eq!(crate::ber::decode::<Value>(&encoded).unwrap(), value);
+        assert_eq!(crate::der::encode(&value).unwrap(), encoded);
+        assert_eq!(Open::try_from(value).unwrap(), open);
+
+        let choice = Value::Choice("flag".into(), Box::new(Value::Boolean(true)));
+        let value = Value::SequenceOf(vec![Value::Null, choice.clone()]);
+        assert_eq!(Open::try_from(value).unwrap_err(), choice);
+    }
+
+    #[test]
+    fn ordering() {
+        let tagged = |number, value| Open::Tagged {
+            tag: Tag::new_context(number),
+            value: Box::new(value),
+        };
+
+        assert!(Open::Integer(1.into()) < Open::Integer(2.into()));
+        assert!(Open::Bool(true) < Open::Integer(0.into()));
+        assert!(tagged(0, Open::Null) < tagged(1, Open::Null));
+        assert!(tagged(0, Open::Bool(false)) < tagged(0, Open::Bool(true)));
+        assert_eq!(
+            Open::Set(vec![Open::Null, Open::Bool(true)].into()),
+            Open::Set(vec![Open::Bool(true), Open::Null].into())
+        );
+
+        let mut values = vec![tagged(0, Open::Null), Open::Null, Open::Bool(false)];
+        values.sort();
+        assert_eq!(
+            values,
+            [Open::Bool(false), Open::Null, tagged(0, Open::Null)]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "f64")]
+    fn real() {
+        let open = Open::Real(1.5);
+        for codec in [Codec::Oer, Codec::Coer] {
+            let encoded = codec.encode_to_binary(&open).unwrap();
+            assert_eq!(codec.decode_from_binary::<Open>(&encoded).unwrap(), open);
+        }
+
+        let json = crate::jer::encode(&open).unwrap();
+        assert_eq!(crate::jer::decode::<Open>(&json).unwrap(), open);
+    }
 }
@@ -16,7 +16,7 @@ use nom::AsBytes;
 
 pub use {
     alloc::string::String as Utf8String,
-    bit::{BitStr, BitString, FixedBitString},
+    bit::{BitStr, BitString, FixedBitString, set_named_bit, trim_named_bits},
     bmp::BmpString,
     general::GeneralString,
     graphic::GraphicString,
@@ -32,18 +32,6 @@ pub(crate) use constrained::{
     DynConstrainedCharacterString, StaticPermittedAlphabet, should_be_indexed,
 };
 
-const fn bytes_to_chars<const N: usize>(input: [u8; N]) -> [u32; N] {
-    let mut chars: [u32; N] = [0; N];
-
-    let mut index = 0;
-    while index < N {
-        chars[index] = input[index] as u32;
-        index += 1;
-    }
-
-    chars
-}
-
 macro_rules! impl_restricted_core_traits {
     ($(($target:ty, $width:ty)),* $(,)?) => {
     $(
@@ -41,6 +41,64 @@ pub type FixedBitString<const N: usize> = bitvec::array::BitArray<[u8; N], bitve
 ///  A reference to a `BIT STRING` type.
 pub type BitStr = bitvec::slice::BitSlice<u8, bitvec::order::Msb0>;
 
+/// Returns `bits`, the value of a `BIT STRING` defined with a named bit list,
+/// as it is encoded.
+///
+/// Trailing zero bits are insignificant in such types (X.680 22.7), so they
+/// are removed as DER and CER require (X.690 11.2.2), while keeping the value
+/// at least as long as the lower bound of its size constraint, as PER
+/// requires (X.691 16.3). Types deriving `#[rasn(named_bits(...))]` do this
+/// when encoding.
+///
+/// ```rust
+/// use rasn::{macros::*, prelude::*};
+/// use bitvec::prelude::*;
+///
+/// let bits = bitvec![u8, Msb0; 1, 0, 1, 0, 0, 0];
+/// assert_eq!(&*trim_named_bits(&bits, &Constraints::default()), bits![u8, Msb0; 1, 0, 1]);
+/// assert_eq!(
+///     &*trim_named_bits(&bits, &constraints!(size_constraint!(5, 9))),
+///     bits![u8, Msb0; 1, 0, 1, 0, 0]
+/// );
+/// ```
+#[must_use]
+pub fn trim_named_bits<'a>(
+    bits: &'a BitStr,
+    constraints: &Constraints,
+) -> alloc::borrow::Cow<'a, BitStr> {
+    let minimum = constraints
+        .size()
+        .map_or(0, |size| size.constraint.minimum());
+    // `BitSlice::last_one` can overflow on slices ending mid-byte, so search
+    // from the end by hand.
+    let len = bits
+        .iter()
+        .by_vals()
+        .rposition(|bit| bit)
+        .map_or(0, |last| last + 1)
+        .max(minimum);
+
+    if len <= bits.len() {
+        alloc::borrow::Cow::Borrowed(&bits[..len])
+    } else {
+        let mut padded = bits.to_bitvec();
+        padded.resize(len, false);
+        alloc::borrow::Cow::Owned(padded)
+    }
+}
+
+/// Sets the bit at `position` of a `BIT STRING` defined with a named bit list
+/// to `value`, extending `bits` with zero bits when it is too short.
+pub fn set_named_bit(bits: &mut BitString, position: usize, value: bool) {
+    if position >= bits.len() {
+        if !value {
+            return;
+        }
+        bits.resize(position + 1, false);
+    }
+    bits.set(position, value);
+}
+
 impl AsnType for BitString {
     const TAG: Tag = Tag::BIT_STRING;
     const IDENTIFIER: Identifier = Identifier::BIT_STRING;
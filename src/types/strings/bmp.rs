@@ -2,15 +2,13 @@ use super::{
     AsnType, Constraints, Decode, Decoder, Encode, Encoder, Identifier, StaticPermittedAlphabet,
     Tag, constrained,
 };
+use crate::types::constraints::{CharacterRange, PermittedAlphabet};
 use alloc::vec::Vec;
-use once_cell::race::OnceBox;
 
 /// A Basic Multilingual Plane (BMP) string, which is a subtype of [`super::UniversalString`]
 /// containing only the BMP set of characters.
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BmpString(pub(super) Vec<u16>);
-static CHARACTER_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
-static INDEX_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
 
 impl BmpString {
     /// Converts the string into a set of big endian bytes.
@@ -22,15 +20,8 @@ impl BmpString {
 
 impl StaticPermittedAlphabet for BmpString {
     type T = u16;
-    const CHARACTER_SET: &'static [u32] = &{
-        let mut array = [0u32; 0xFFFE];
-        let mut i = 0;
-        while i < 0xFFFE {
-            array[i as usize] = i;
-            i += 1;
-        }
-        array
-    };
+    const CHARACTER_SET: PermittedAlphabet =
+        PermittedAlphabet::from_ranges(&CharacterRange::indexed([CharacterRange::new(0, 0xFFFD)]));
     const CHARACTER_SET_NAME: constrained::CharacterSetName = constrained::CharacterSetName::Bmp;
 
     fn push_char(&mut self, ch: u32) {
@@ -39,14 +30,6 @@ impl StaticPermittedAlphabet for BmpString {
     fn chars(&self) -> impl Iterator<Item = u32> + '_ {
         self.0.iter().map(|&byte| u32::from(byte))
     }
-
-    fn index_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        INDEX_MAP.get_or_init(Self::build_index_map)
-    }
-
-    fn character_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        CHARACTER_MAP.get_or_init(Self::build_character_map)
-    }
 }
 
 impl AsnType for BmpString {
@@ -1,13 +1,12 @@
 use core::fmt;
 
-use alloc::collections::BTreeMap;
 use num_traits::{AsPrimitive, FromPrimitive, PrimInt, ToPrimitive, Unsigned};
 
 use crate::error::strings::{InvalidRestrictedString, PermittedAlphabetError};
-use alloc::{boxed::Box, vec::Vec};
+use alloc::vec::Vec;
 use bitvec::prelude::*;
 
-use crate::types;
+use crate::types::{self, constraints::PermittedAlphabet};
 pub(crate) enum CharacterSetName {
     Bmp,
     General,
@@ -41,7 +40,7 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
         + AsPrimitive<u8>
         + AsPrimitive<u16>
         + AsPrimitive<u32>;
-    const CHARACTER_SET: &'static [u32];
+    const CHARACTER_SET: PermittedAlphabet;
     /// Bits needed to represent a character in the character set so that every character can be represented
     /// Encoding specific requirement
     const CHARACTER_SET_WIDTH: usize = crate::num::log2(Self::CHARACTER_SET.len() as i128) as usize;
@@ -50,7 +49,7 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
     fn push_char(&mut self, ch: u32);
     fn chars(&self) -> impl Iterator<Item = u32> + '_;
     fn contains_char(ch: u32) -> bool {
-        Self::CHARACTER_SET.contains(&ch)
+        Self::CHARACTER_SET.contains(ch)
     }
     fn invalid_restricted_string(ch: u32) -> InvalidRestrictedString {
         match Self::CHARACTER_SET_NAME {
@@ -121,8 +120,6 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
         }
         Ok(vec)
     }
-    fn index_map() -> &'static alloc::collections::BTreeMap<u32, u32>;
-    fn character_map() -> &'static alloc::collections::BTreeMap<u32, u32>;
     fn char_range_to_bit_range(mut range: core::ops::Range<usize>) -> core::ops::Range<usize> {
         let width = Self::CHARACTER_SET_WIDTH;
         range.start *= width;
@@ -130,34 +127,39 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
         range
     }
 
-    fn to_index_or_value_bitstring(&self) -> types::BitString {
-        if should_be_indexed(Self::CHARACTER_SET_WIDTH as u32, Self::CHARACTER_SET) {
+    fn to_index_or_value_bitstring(&self) -> Result<types::BitString, PermittedAlphabetError> {
+        if should_be_indexed(Self::CHARACTER_SET_WIDTH as u32, &Self::CHARACTER_SET) {
             self.to_index_string()
         } else {
-            self.to_bit_string()
+            Ok(self.to_bit_string())
         }
     }
 
-    fn to_index_string(&self) -> types::BitString {
-        let index_map = Self::index_map();
+    fn index_of(ch: u32) -> Result<u32, PermittedAlphabetError> {
+        Self::CHARACTER_SET
+            .index_of(ch)
+            .ok_or(PermittedAlphabetError::CharacterNotFound { character: ch })
+    }
+
+    fn to_index_string(&self) -> Result<types::BitString, PermittedAlphabetError> {
         let mut index_string = types::BitString::new();
         let width = Self::CHARACTER_SET_WIDTH;
         for ch in self.chars() {
-            let index = index_map.get(&ch).unwrap();
+            let index = Self::index_of(ch)?;
             index_string
                 .extend_from_bitslice(&index.view_bits::<Msb0>()[(u32::BITS as usize - width)..]);
         }
-        index_string
+        Ok(index_string)
     }
 
-    fn to_octet_aligned_index_string(&self) -> Vec<u8> {
-        let index_map = Self::index_map();
+    fn to_octet_aligned_index_string(&self) -> Result<Vec<u8>, PermittedAlphabetError> {
         let mut index_string = types::BitString::new();
         let width = Self::CHARACTER_SET_WIDTH;
         let new_width = self.octet_aligned_char_width();
 
         for ch in self.chars() {
-            let ch = &index_map[&ch].view_bits::<Msb0>()[(u32::BITS as usize - width)..];
+            let index = Self::index_of(ch)?;
+            let ch = &index.view_bits::<Msb0>()[(u32::BITS as usize - width)..];
             let mut padding = types::BitString::new();
             for _ in 0..(new_width - width) {
                 padding.push(false);
@@ -165,7 +167,7 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
             padding.extend_from_bitslice(ch);
             index_string.extend(padding);
         }
-        index_string.as_raw_slice().to_vec()
+        Ok(index_string.as_raw_slice().to_vec())
     }
 
     fn octet_aligned_char_width(&self) -> usize {
@@ -206,36 +208,11 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
         self.chars().count()
     }
 
-    #[allow(clippy::box_collection)]
-    fn build_index_map() -> Box<alloc::collections::BTreeMap<u32, u32>> {
-        Box::new(
-            Self::CHARACTER_SET
-                .iter()
-                .copied()
-                .enumerate()
-                .map(|(i, e)| (e, u32::from_usize(i).unwrap_or_default()))
-                .collect(),
-        )
-    }
-
-    #[allow(clippy::box_collection)]
-    fn build_character_map() -> Box<alloc::collections::BTreeMap<u32, u32>> {
-        Box::new(
-            Self::CHARACTER_SET
-                .iter()
-                .copied()
-                .enumerate()
-                .map(|(i, e)| (u32::from_usize(i).unwrap_or_default(), e))
-                .collect(),
-        )
-    }
-
     fn try_from_permitted_alphabet(
         input: crate::types::BitString,
-        alphabet: Option<&BTreeMap<u32, u32>>,
+        alphabet: Option<&PermittedAlphabet>,
     ) -> Result<Self, PermittedAlphabetError> {
-        let alphabet = alphabet.unwrap_or_else(|| Self::character_map());
-        try_from_permitted_alphabet(input, alphabet)
+        try_from_permitted_alphabet(input, alphabet.unwrap_or(&Self::CHARACTER_SET))
     }
 
     #[track_caller]
@@ -266,81 +243,67 @@ pub(crate) trait StaticPermittedAlphabet: Sized + Default {
 
 pub(crate) fn try_from_permitted_alphabet<S: StaticPermittedAlphabet>(
     input: crate::types::BitString,
-    alphabet: &BTreeMap<u32, u32>,
+    alphabet: &PermittedAlphabet,
 ) -> Result<S, PermittedAlphabetError> {
     let mut string = S::default();
     let permitted_alphabet_char_width = crate::num::log2(alphabet.len() as i128) as usize;
-    // Alphabet should be always indexed key-alphabetvalue pairs at this point
-    let values_only = alphabet.values().copied().collect::<Vec<u32>>();
-    if should_be_indexed(permitted_alphabet_char_width as u32, &values_only) {
+    if should_be_indexed(permitted_alphabet_char_width as u32, alphabet) {
         for ch in input.chunks_exact(permitted_alphabet_char_width) {
             let index = ch.load_be::<u32>();
-            string.push_char(*alphabet.get(&index).ok_or(
+            string.push_char(alphabet.character_at(index).ok_or(
                 PermittedAlphabetError::IndexNotFound {
                     index: index.to_usize().unwrap_or_default(),
                 },
             )?);
         }
     } else {
-        string = S::try_from_bits(input, permitted_alphabet_char_width)?
+        string = S::try_from_bits(input, permitted_alphabet_char_width)?;
+        if let Some(character) = string.chars().find(|&ch| !alphabet.contains(ch)) {
+            return Err(PermittedAlphabetError::CharacterNotFound { character });
+        }
     }
     Ok(string)
 }
-pub(crate) fn should_be_indexed(width: u32, character_set: &[u32]) -> bool {
-    let largest_value = character_set.iter().copied().max().unwrap_or_default();
+pub(crate) fn should_be_indexed(width: u32, character_set: &PermittedAlphabet) -> bool {
+    let largest_value = character_set.largest().unwrap_or_default();
     2u32.pow(width) <= largest_value
 }
 
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DynConstrainedCharacterString {
-    character_set: BTreeMap<u32, u32>,
+    character_width: usize,
     buffer: types::BitString,
 }
 
 impl DynConstrainedCharacterString {
     pub fn from_bits(
         data: impl Iterator<Item = u32>,
-        character_set: &[u32],
+        character_set: &PermittedAlphabet,
     ) -> Result<Self, PermittedAlphabetError> {
         let mut buffer = types::BitString::new();
         let char_width = crate::num::log2(character_set.len() as i128);
         let indexed = should_be_indexed(char_width, character_set);
-        let alphabet: BTreeMap<u32, u32>;
-        if indexed {
-            alphabet = character_set
-                .iter()
-                .enumerate()
-                .map(|(i, a)| (*a, i as u32))
-                .collect::<BTreeMap<_, _>>();
-            for ch in data {
-                let Some(index) = alphabet.get(&ch).copied() else {
-                    return Err(PermittedAlphabetError::CharacterNotFound { character: ch });
-                };
-                let range = ((u32::BITS - char_width) as usize)..(u32::BITS as usize);
-                let bit_ch = &index.view_bits::<Msb0>()[range];
-                buffer.extend_from_bitslice(bit_ch);
-            }
-        } else {
-            alphabet = character_set
-                .iter()
-                .enumerate()
-                .map(|(i, a)| (i as u32, *a))
-                .collect::<BTreeMap<_, _>>();
-            for ch in data {
-                let range = ((u32::BITS - char_width) as usize)..(u32::BITS as usize);
-                let bit_ch = &ch.view_bits::<Msb0>()[range];
-                buffer.extend_from_bitslice(bit_ch);
-            }
+        let range = ((u32::BITS - char_width) as usize)..(u32::BITS as usize);
+        for ch in data {
+            let value = if indexed {
+                character_set.index_of(ch)
+            } else {
+                character_set.contains(ch).then_some(ch)
+            };
+            let Some(value) = value else {
+                return Err(PermittedAlphabetError::CharacterNotFound { character: ch });
+            };
+            buffer.extend_from_bitslice(&value.view_bits::<Msb0>()[range.clone()]);
         }
 
         Ok(Self {
-            character_set: alphabet,
+            character_width: char_width as usize,
             buffer,
         })
     }
 
     pub fn character_width(&self) -> usize {
-        crate::num::log2(self.character_set.len() as i128) as usize
+        self.character_width
     }
 
     #[allow(unused)]
@@ -1,17 +1,15 @@
 use super::{
     AsnType, Constraints, Decode, Decoder, Encode, Encoder, Identifier, StaticPermittedAlphabet,
-    Tag, bytes_to_chars, constrained,
+    Tag, constrained,
 };
+use crate::types::constraints::{CharacterRange, PermittedAlphabet};
 
 use crate::error::strings::PermittedAlphabetError;
 use alloc::vec::Vec;
-use once_cell::race::OnceBox;
 
 /// A string which can only contain numbers or `SPACE` characters.
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct NumericString(pub(super) Vec<u8>);
-static CHARACTER_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
-static INDEX_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
 
 impl NumericString {
     /// Attempts to convert the provided bytes into [Self].
@@ -31,9 +29,11 @@ impl NumericString {
 
 impl StaticPermittedAlphabet for NumericString {
     type T = u8;
-    const CHARACTER_SET: &'static [u32] = &bytes_to_chars([
-        b' ', b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9',
-    ]);
+    const CHARACTER_SET: PermittedAlphabet =
+        PermittedAlphabet::from_ranges(&CharacterRange::indexed([
+            CharacterRange::single(b' ' as u32),
+            CharacterRange::new(b'0' as u32, b'9' as u32),
+        ]));
     const CHARACTER_SET_NAME: constrained::CharacterSetName =
         constrained::CharacterSetName::Numeric;
 
@@ -44,14 +44,6 @@ impl StaticPermittedAlphabet for NumericString {
     fn push_char(&mut self, ch: u32) {
         self.0.push(ch as u8);
     }
-
-    fn index_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        INDEX_MAP.get_or_init(Self::build_index_map)
-    }
-
-    fn character_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        CHARACTER_MAP.get_or_init(Self::build_character_map)
-    }
 }
 
 impl AsnType for NumericString {
@@ -1,11 +1,11 @@
 use super::{
     AsnType, Constraints, Decode, Decoder, Encode, Encoder, Identifier, StaticPermittedAlphabet,
-    Tag, bytes_to_chars, constrained,
+    Tag, constrained,
 };
+use crate::types::constraints::{CharacterRange, PermittedAlphabet};
 
 use crate::error::strings::PermittedAlphabetError;
 use alloc::vec::Vec;
-use once_cell::race::OnceBox;
 
 /// A string, which contains the characters defined in X.680 41.4 Section, Table 10.
 ///
@@ -13,8 +13,6 @@ use once_cell::race::OnceBox;
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[allow(clippy::module_name_repetitions)]
 pub struct PrintableString(pub(super) Vec<u8>);
-static CHARACTER_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
-static INDEX_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
 
 impl PrintableString {
     /// Construct a new `PrintableString` from a byte array.
@@ -37,13 +35,16 @@ impl StaticPermittedAlphabet for PrintableString {
     type T = u8;
     /// `PrintableString` contains only "printable" characters.
     /// Latin letters, digits, (space) '()+,-./:=?
-    const CHARACTER_SET: &'static [u32] = &bytes_to_chars([
-        b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O',
-        b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z', b'a', b'b', b'c', b'd',
-        b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', b'p', b'q', b'r', b's',
-        b't', b'u', b'v', b'w', b'x', b'y', b'z', b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7',
-        b'8', b'9', b' ', b'\'', b'(', b')', b'+', b',', b'-', b'.', b'/', b':', b'=', b'?',
-    ]);
+    const CHARACTER_SET: PermittedAlphabet =
+        PermittedAlphabet::from_ranges(&CharacterRange::indexed([
+            CharacterRange::single(b' ' as u32),
+            CharacterRange::new(b'\'' as u32, b')' as u32),
+            CharacterRange::new(b'+' as u32, b':' as u32),
+            CharacterRange::single(b'=' as u32),
+            CharacterRange::single(b'?' as u32),
+            CharacterRange::new(b'A' as u32, b'Z' as u32),
+            CharacterRange::new(b'a' as u32, b'z' as u32),
+        ]));
     const CHARACTER_SET_NAME: constrained::CharacterSetName =
         constrained::CharacterSetName::Printable;
 
@@ -54,14 +55,6 @@ impl StaticPermittedAlphabet for PrintableString {
     fn chars(&self) -> impl Iterator<Item = u32> + '_ {
         self.0.iter().map(|&byte| byte as u32)
     }
-
-    fn index_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        INDEX_MAP.get_or_init(Self::build_index_map)
-    }
-
-    fn character_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        CHARACTER_MAP.get_or_init(Self::build_character_map)
-    }
 }
 
 impl AsnType for PrintableString {
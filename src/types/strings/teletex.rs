@@ -2,15 +2,13 @@ use super::{
     AsnType, Constraints, Decode, Decoder, Encode, Encoder, Identifier, PermittedAlphabetError,
     StaticPermittedAlphabet, Tag, constrained,
 };
+use crate::types::constraints::PermittedAlphabet;
 
 use alloc::vec::Vec;
-use once_cell::race::OnceBox;
 
 /// A string, which contains the characters defined in T.61 standard.
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TeletexString(pub(super) Vec<u32>);
-static CHARACTER_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
-static INDEX_MAP: OnceBox<alloc::collections::BTreeMap<u32, u32>> = OnceBox::new();
 
 impl TeletexString {
     /// Converts the string into a set of big endian bytes.
@@ -30,7 +28,7 @@ impl TeletexString {
 impl StaticPermittedAlphabet for TeletexString {
     type T = u32;
     // TODO add correct character set, see https://github.com/mouse07410/asn1c/blob/84d3a59c1bb89c59be6ca0625bb14ebea9084ba5/skeletons/TeletexString.c
-    const CHARACTER_SET: &'static [u32] = &[0];
+    const CHARACTER_SET: PermittedAlphabet = PermittedAlphabet::new(&[0]);
     const CHARACTER_SET_NAME: constrained::CharacterSetName =
         constrained::CharacterSetName::Teletex;
     // TODO remove once correct character set is added
@@ -44,14 +42,6 @@ impl StaticPermittedAlphabet for TeletexString {
     fn chars(&self) -> impl Iterator<Item = u32> + '_ {
         self.0.iter().copied()
     }
-
-    fn index_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        INDEX_MAP.get_or_init(Self::build_index_map)
-    }
-
-    fn character_map() -> &'static alloc::collections::BTreeMap<u32, u32> {
-        CHARACTER_MAP.get_or_init(Self::build_character_map)
-    }
 }
 
 impl AsnType for TeletexString {
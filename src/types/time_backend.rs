@@ -0,0 +1,283 @@
+//! Conversions between the text of time values and the types of
+//! [`GeneralizedTime`], [`UtcTime`] and [`Date`], which come from `chrono`,
+//! `time` or this crate depending on the features that are enabled.
+
+use alloc::string::String;
+
+use super::{
+    Date, DateString, GeneralizedTime, GeneralizedTimeString, ParseTimeError, UtcTime,
+    UtcTimeString,
+};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "chrono")] {
+        use alloc::string::ToString;
+        use chrono::Timelike;
+
+        /// Converts a `GeneralizedTime`, reading a local time as UTC.
+        pub(crate) fn generalized_time(
+            value: GeneralizedTimeString,
+        ) -> Result<GeneralizedTime, ParseTimeError> {
+            if value.time_zone() == super::TimeZone::Local {
+                value
+                    .to_naive_date_time()
+                    .map(|time| time.and_utc().fixed_offset())
+            } else {
+                value.to_date_time()
+            }
+        }
+
+        pub(crate) fn utc_time(value: UtcTimeString) -> Result<UtcTime, ParseTimeError> {
+            value.to_date_time()
+        }
+
+        pub(crate) fn date(value: DateString) -> Result<Date, ParseTimeError> {
+            Date::try_from(&value)
+        }
+
+        /// Writes a `GeneralizedTime` in the canonical form of X.690.
+        pub(crate) fn generalized_time_text(value: &GeneralizedTime) -> String {
+            let mut string;
+            // Convert to UTC so we can always append Z.
+            let value = value.naive_utc();
+            if value.nanosecond() > 0 {
+                string = value.format("%Y%m%d%H%M%S.%f").to_string();
+                // No trailing zeros with fractions, nor a decimal sign
+                // without a fraction for a leap second.
+                while string.ends_with('0') {
+                    string.pop();
+                }
+                if string.ends_with('.') {
+                    string.pop();
+                }
+            } else {
+                string = value.format("%Y%m%d%H%M%S").to_string();
+            }
+            string.push('Z');
+            string
+        }
+
+        /// Writes a `UTCTime` in the canonical form of X.690.
+        pub(crate) fn utc_time_text(value: &UtcTime) -> String {
+            value.naive_utc().format("%y%m%d%H%M%SZ").to_string()
+        }
+
+        pub(crate) fn canonical_generalized_time_text(
+            value: &GeneralizedTime,
+        ) -> Result<String, ParseTimeError> {
+            Ok(generalized_time_text(value))
+        }
+
+        pub(crate) fn canonical_utc_time_text(value: &UtcTime) -> Result<String, ParseTimeError> {
+            Ok(utc_time_text(value))
+        }
+
+        pub(crate) fn date_text(value: &Date) -> String {
+            value.format("%Y%m%d").to_string()
+        }
+    } else if #[cfg(feature = "time")] {
+        use core::fmt::Write;
+
+        /// Converts a `GeneralizedTime`, reading a local time as UTC.
+        pub(crate) fn generalized_time(
+            value: GeneralizedTimeString,
+        ) -> Result<GeneralizedTime, ParseTimeError> {
+            if value.time_zone() == super::TimeZone::Local {
+                value
+                    .to_primitive_date_time()
+                    .map(time::PrimitiveDateTime::assume_utc)
+            } else {
+                GeneralizedTime::try_from(&value)
+            }
+        }
+
+        pub(crate) fn utc_time(value: UtcTimeString) -> Result<UtcTime, ParseTimeError> {
+            time::OffsetDateTime::try_from(&value).map(time::OffsetDateTime::to_utc)
+        }
+
+        pub(crate) fn date(value: DateString) -> Result<Date, ParseTimeError> {
+            Date::try_from(&value)
+        }
+
+        /// Writes a `GeneralizedTime` in the canonical form of X.690.
+        pub(crate) fn generalized_time_text(value: &GeneralizedTime) -> String {
+            let value = value.to_utc();
+            let mut string = date_text(&value.date());
+            let _ = write!(
+                string,
+                "{:02}{:02}{:02}",
+                value.hour(),
+                value.minute(),
+                value.second()
+            );
+            if value.nanosecond() > 0 {
+                let _ = write!(string, ".{:09}", value.nanosecond());
+                // No trailing zeros with fractions
+                while string.ends_with('0') {
+                    string.pop();
+                }
+            }
+            string.push('Z');
+            string
+        }
+
+        /// Writes a `UTCTime` in the canonical form of X.690.
+        pub(crate) fn utc_time_text(value: &UtcTime) -> String {
+            alloc::format!(
+                "{:02}{:02}{:02}{:02}{:02}{:02}Z",
+                value.year().rem_euclid(100),
+                u8::from(value.month()),
+                value.day(),
+                value.hour(),
+                value.minute(),
+                value.second()
+            )
+        }
+
+        pub(crate) fn canonical_generalized_time_text(
+            value: &GeneralizedTime,
+        ) -> Result<String, ParseTimeError> {
+            Ok(generalized_time_text(value))
+        }
+
+        pub(crate) fn canonical_utc_time_text(value: &UtcTime) -> Result<String, ParseTimeError> {
+            Ok(utc_time_text(value))
+        }
+
+        pub(crate) fn date_text(value: &Date) -> String {
+            alloc::format!(
+                "{:04}{:02}{:02}",
+                value.year(),
+                u8::from(value.month()),
+                value.day()
+            )
+        }
+    } else {
+        pub(crate) fn generalized_time(
+            value: GeneralizedTimeString,
+        ) -> Result<GeneralizedTime, ParseTimeError> {
+            Ok(value)
+        }
+
+        pub(crate) fn utc_time(value: UtcTimeString) -> Result<UtcTime, ParseTimeError> {
+            Ok(value)
+        }
+
+        pub(crate) fn date(value: DateString) -> Result<Date, ParseTimeError> {
+            Ok(value)
+        }
+
+        /// Returns the text of a `GeneralizedTime`, which is kept as it was
+        /// written rather than rewritten in the canonical form.
+        pub(crate) fn generalized_time_text(value: &GeneralizedTime) -> String {
+            value.as_str().into()
+        }
+
+        /// Returns the text of a `UTCTime`, which is kept as it was written
+        /// rather than rewritten in the canonical form.
+        pub(crate) fn utc_time_text(value: &UtcTime) -> String {
+            value.as_str().into()
+        }
+
+        /// Rewrites a `GeneralizedTime` in the canonical form of X.690.
+        pub(crate) fn canonical_generalized_time_text(
+            value: &GeneralizedTime,
+        ) -> Result<String, ParseTimeError> {
+            value.to_canonical().map(|time| time.as_str().into())
+        }
+
+        /// Rewrites a `UTCTime` in the canonical form of X.690.
+        pub(crate) fn canonical_utc_time_text(value: &UtcTime) -> Result<String, ParseTimeError> {
+            value.to_canonical().map(|time| time.as_str().into())
+        }
+
+        pub(crate) fn date_text(value: &Date) -> String {
+            value.as_str().into()
+        }
+    }
+}
+
+/// With both `chrono` and `time`, the aliases are `chrono` types, and the
+/// `time` types are encoded and decoded through them.
+#[cfg(all(feature = "chrono", feature = "time"))]
+mod time_types {
+    use super::{DateString, GeneralizedTimeString, UtcTimeString};
+    use crate::{
+        AsnType, Codec, Decode, Decoder, Encode, Encoder, de::Error as _, enc::Error as _,
+        types::Constraints, types::Identifier, types::Tag,
+    };
+
+    macro_rules! time_type {
+        ($time:ty, $chrono:ty, $text:ty, $tag:ident, $encode:ident, $decode:ident,
+         $into_text:expr, $from_text:expr) => {
+            impl AsnType for $time {
+                const TAG: Tag = Tag::$tag;
+                const IDENTIFIER: Identifier = Identifier::$tag;
+            }
+
+            impl Encode for $time {
+                fn encode_with_tag_and_constraints<'b, E: Encoder<'b>>(
+                    &self,
+                    encoder: &mut E,
+                    tag: Tag,
+                    _: Constraints,
+                    identifier: Identifier,
+                ) -> Result<(), E::Error> {
+                    let codec: Codec = encoder.codec();
+                    let value: $chrono = ($into_text)(*self)
+                        .and_then(|text: $text| <$chrono>::try_from(&text))
+                        .map_err(|error| E::Error::custom(error, codec))?;
+                    encoder
+                        .$encode(tag, &value, identifier.or(Self::IDENTIFIER))
+                        .map(drop)
+                }
+            }
+
+            impl Decode for $time {
+                fn decode_with_tag_and_constraints<D: Decoder>(
+                    decoder: &mut D,
+                    tag: Tag,
+                    _: Constraints,
+                ) -> Result<Self, D::Error> {
+                    let codec = decoder.codec();
+                    let value: $chrono = decoder.$decode(tag)?;
+                    <$text>::try_from(value)
+                        .and_then(|text| ($from_text)(&text))
+                        .map_err(|error| D::Error::custom(error, codec))
+                }
+            }
+        };
+    }
+
+    time_type!(
+        time::UtcDateTime,
+        chrono::DateTime<chrono::Utc>,
+        UtcTimeString,
+        UTC_TIME,
+        encode_utc_time,
+        decode_utc_time,
+        |value: time::UtcDateTime| UtcTimeString::try_from(time::OffsetDateTime::from(value)),
+        |text: &UtcTimeString| time::OffsetDateTime::try_from(text)
+            .map(time::OffsetDateTime::to_utc)
+    );
+    time_type!(
+        time::OffsetDateTime,
+        chrono::DateTime<chrono::FixedOffset>,
+        GeneralizedTimeString,
+        GENERALIZED_TIME,
+        encode_generalized_time,
+        decode_generalized_time,
+        GeneralizedTimeString::try_from,
+        time::OffsetDateTime::try_from
+    );
+    time_type!(
+        time::Date,
+        chrono::NaiveDate,
+        DateString,
+        DATE,
+        encode_date,
+        decode_date,
+        DateString::try_from,
+        time::Date::try_from
+    );
+}
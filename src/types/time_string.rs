@@ -0,0 +1,1423 @@
+//! `GeneralizedTime`, `UTCTime` and `DATE` values that keep the exact form
+//! they were written or encoded in.
+
+use alloc::string::String;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+use {
+    super::{AsnType, Constraints, Identifier, OctetString, Tag, VisibleString},
+    crate::{Codec, Decode, Decoder, Encode, Encoder, de::Error as _, error::EncodeError},
+    alloc::string::ToString,
+};
+
+/// A `GeneralizedTime` value that keeps the exact form it was decoded or
+/// parsed from.
+///
+/// With the `chrono` or `time` feature, [`GeneralizedTime`][super::GeneralizedTime]
+/// is a date and time of that crate, so decoding one drops whether the value
+/// was a local time or had an offset, the precision it was written with, the
+/// digits of its fraction and leap seconds. Encoding it again can then produce
+/// different bytes, which breaks the signatures over them. A
+/// `GeneralizedTimeString` encodes back to the same bytes it was decoded from,
+/// and can be converted to `chrono` or `time` when the point in time is
+/// needed. Without either feature, `GeneralizedTime` is this type.
+///
+/// Values are checked against X.680 when they are created. BER accepts any
+/// valid value, CER and DER require the canonical form of X.690, and
+/// [`GeneralizedTimeString::check`] checks a value against a stricter
+/// [`TimeProfile`], such as the one of RFC 5280.
+///
+/// Values are ordered by their text, which is chronological for values
+/// written in the same form, such as canonical ones.
+///
+/// ```rust
+/// use rasn::types::{GeneralizedTimeString, TimeProfile, TimeZone};
+///
+/// let encoded = b"\x18\x1220230102153000,50Z";
+/// let time: GeneralizedTimeString = rasn::ber::decode(encoded).unwrap();
+/// assert_eq!(time.as_str(), "20230102153000,50Z");
+/// assert_eq!(time.fraction(), Some("50"));
+/// assert_eq!(time.time_zone(), TimeZone::Utc);
+/// assert!(time.check(TimeProfile::Canonical).is_err());
+/// assert_eq!(rasn::ber::encode(&time).unwrap(), encoded);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GeneralizedTimeString {
+    text: String,
+    parts: Parts,
+}
+
+/// A `UTCTime` value that keeps the exact form it was decoded or parsed from.
+///
+/// With the `chrono` or `time` feature, [`UtcTime`][super::UtcTime] is a date
+/// and time in UTC, so decoding one drops the offset it was written with, and
+/// whether it had seconds. A `UtcTimeString` encodes back to the same bytes it
+/// was decoded from, see [`GeneralizedTimeString`]. Without either feature,
+/// `UtcTime` is this type.
+///
+/// Two digit years are read with the window of RFC 5280, so years from 50 to
+/// 99 are in the 1900s and years from 00 to 49 are in the 2000s.
+///
+/// ```rust
+/// use rasn::types::{TimeProfile, TimeZone, UtcTimeString};
+///
+/// let time: UtcTimeString = "9912312359-0500".parse().unwrap();
+/// assert_eq!(time.year(), 1999);
+/// assert_eq!(time.minute(), 59);
+/// assert_eq!(time.second(), None);
+/// assert_eq!(time.time_zone(), TimeZone::Offset(-300));
+/// assert!(time.check(TimeProfile::Rfc5280).is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct UtcTimeString {
+    text: String,
+    parts: Parts,
+}
+
+/// A `DATE` value, `YYYYMMDD`.
+///
+/// With the `chrono` or `time` feature, [`Date`][super::Date] is a date of
+/// that crate, and a `DateString` can be converted to and from it. Without
+/// either feature, `Date` is this type.
+///
+/// ```rust
+/// use rasn::types::DateString;
+///
+/// let date: DateString = "20240229".parse().unwrap();
+/// assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+/// assert!(DateString::new("20230229").is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateString {
+    text: String,
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+/// The time zone a time value is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeZone {
+    /// A local time, without an offset from UTC.
+    Local,
+    /// UTC, written as `Z`.
+    Utc,
+    /// An offset from UTC in minutes, written as `+hhmm` or `-hhmm`.
+    Offset(i16),
+}
+
+/// A set of rules restricting the forms a time value may be written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimeProfile {
+    /// Any form allowed by X.680.
+    #[default]
+    Basic,
+    /// The canonical form CER and DER require, from sections 11.7 and 11.8 of
+    /// X.690. Times are in UTC and have seconds, and the fraction of a
+    /// `GeneralizedTime` uses a full stop and has no trailing zeros.
+    Canonical,
+    /// The form RFC 5280 requires in certificates and CRLs, from sections
+    /// 4.1.2.5.1 and 4.1.2.5.2. Times are in UTC and have seconds, and a
+    /// `GeneralizedTime` has no fraction.
+    Rfc5280,
+}
+
+/// An error parsing a time value from a string, or converting one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTimeError {
+    message: String,
+}
+
+impl ParseTimeError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl core::fmt::Display for ParseTimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid time: {}", self.message)
+    }
+}
+
+impl core::error::Error for ParseTimeError {}
+
+/// The fields of a time value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Parts {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: Option<u8>,
+    second: Option<u8>,
+    /// The start and end of the digits of the fraction in the text.
+    fraction: Option<(usize, usize)>,
+    time_zone: TimeZone,
+}
+
+/// Reads the fields of a time value from left to right.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn next_is_digit(&self) -> bool {
+        self.peek().is_some_and(|byte| byte.is_ascii_digit())
+    }
+
+    /// Reads a number of exactly `digits` digits.
+    fn number(&mut self, digits: usize, field: &str) -> Result<u16, ParseTimeError> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + digits)
+            .filter(|bytes| bytes.iter().all(u8::is_ascii_digit))
+            .ok_or_else(|| {
+                ParseTimeError::new(alloc::format!("expected {digits} digits of {field}"))
+            })?;
+        self.position += digits;
+
+        Ok(bytes
+            .iter()
+            .fold(0, |number, digit| number * 10 + u16::from(digit - b'0')))
+    }
+
+    /// Reads a number of two digits in `range`.
+    fn field(
+        &mut self,
+        field: &str,
+        range: core::ops::RangeInclusive<u8>,
+    ) -> Result<u8, ParseTimeError> {
+        let number = self.number(2, field)?;
+        u8::try_from(number)
+            .ok()
+            .filter(|number| range.contains(number))
+            .ok_or_else(|| ParseTimeError::new(alloc::format!("{number} is not a valid {field}")))
+    }
+
+    /// Reads `Z`, `+hh[mm]` or `-hh[mm]`, if the value has a time zone.
+    fn time_zone(&mut self, needs_minutes: bool) -> Result<TimeZone, ParseTimeError> {
+        let sign = match self.peek() {
+            None => return Ok(TimeZone::Local),
+            Some(b'Z') => {
+                self.position += 1;
+                return Ok(TimeZone::Utc);
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            Some(_) => return Err(ParseTimeError::new("expected a time zone")),
+        };
+        self.position += 1;
+
+        let hours = self.field("offset hour", 0..=23)?;
+        let minutes = if needs_minutes || self.peek().is_some() {
+            self.field("offset minute", 0..=59)?
+        } else {
+            0
+        };
+
+        Ok(TimeZone::Offset(
+            sign * (i16::from(hours) * 60 + i16::from(minutes)),
+        ))
+    }
+
+    fn finish(&self) -> Result<(), ParseTimeError> {
+        if self.position == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(ParseTimeError::new("unexpected characters after the time"))
+        }
+    }
+}
+
+impl Parts {
+    /// Parses a `GeneralizedTime`, `YYYYMMDDHH[MM[SS]][(.|,)F+][Z|(+|-)hh[mm]]`.
+    fn parse_generalized_time(text: &str) -> Result<Self, ParseTimeError> {
+        let mut cursor = Cursor::new(text);
+        let year = cursor.number(4, "year")?;
+        let (month, day, hour) = Self::date_and_hour(&mut cursor, year)?;
+        let minute = cursor
+            .next_is_digit()
+            .then(|| cursor.field("minute", 0..=59))
+            .transpose()?;
+        let second = (minute.is_some() && cursor.next_is_digit())
+            .then(|| cursor.field("second", 0..=60))
+            .transpose()?;
+
+        let fraction = if matches!(cursor.peek(), Some(b'.' | b',')) {
+            cursor.position += 1;
+            let start = cursor.position;
+            while cursor.next_is_digit() {
+                cursor.position += 1;
+            }
+            if start == cursor.position {
+                return Err(ParseTimeError::new(
+                    "expected digits after the decimal sign",
+                ));
+            }
+            Some((start, cursor.position))
+        } else {
+            None
+        };
+
+        let time_zone = cursor.time_zone(false)?;
+        cursor.finish()?;
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            fraction,
+            time_zone,
+        })
+    }
+
+    /// Parses a `UTCTime`, `YYMMDDhhmm[ss](Z|(+|-)hhmm)`.
+    fn parse_utc_time(text: &str) -> Result<Self, ParseTimeError> {
+        let mut cursor = Cursor::new(text);
+        let year = match cursor.number(2, "year")? {
+            year @ 50.. => 1900 + year,
+            year => 2000 + year,
+        };
+        let (month, day, hour) = Self::date_and_hour(&mut cursor, year)?;
+        let minute = cursor.field("minute", 0..=59)?;
+        let second = cursor
+            .next_is_digit()
+            .then(|| cursor.field("second", 0..=60))
+            .transpose()?;
+
+        let time_zone = cursor.time_zone(true)?;
+        if time_zone == TimeZone::Local {
+            return Err(ParseTimeError::new("a UTCTime needs a time zone"));
+        }
+        cursor.finish()?;
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute: Some(minute),
+            second,
+            fraction: None,
+            time_zone,
+        })
+    }
+
+    fn date_and_hour(cursor: &mut Cursor<'_>, year: u16) -> Result<(u8, u8, u8), ParseTimeError> {
+        let month = cursor.field("month", 1..=12)?;
+        let day = cursor.field("day", 1..=days_in_month(year, month))?;
+        let hour = cursor.field("hour", 0..=23)?;
+        Ok((month, day, hour))
+    }
+
+    /// Checks the parts against the canonical and RFC 5280 rules shared by
+    /// both time types.
+    fn check_utc_with_seconds(&self, profile: TimeProfile) -> Result<(), ParseTimeError> {
+        if profile == TimeProfile::Basic {
+            return Ok(());
+        }
+        if self.time_zone != TimeZone::Utc {
+            return Err(ParseTimeError::new(
+                "the time must be in UTC, ending with `Z`",
+            ));
+        }
+        if self.second.is_none() {
+            return Err(ParseTimeError::new("the time must have seconds"));
+        }
+        Ok(())
+    }
+
+    /// Returns the digits of the fraction in `text`, if there are any.
+    fn fraction<'a>(&self, text: &'a str) -> Option<&'a str> {
+        self.fraction.map(|(start, end)| &text[start..end])
+    }
+
+    /// Writes the time in the canonical form of X.690, in UTC with seconds,
+    /// and for a `GeneralizedTime` a fraction of a second without trailing
+    /// zeros.
+    fn canonical_text(
+        &self,
+        fraction: Option<&str>,
+        generalized: bool,
+    ) -> Result<String, ParseTimeError> {
+        use core::fmt::Write;
+
+        // A fraction of an hour or a minute is moved into the seconds and
+        // the fraction of a second, which is exact as both units divide a
+        // power of ten times 3600.
+        let unit: u128 = match (self.minute, self.second) {
+            (None, _) => 3600,
+            (Some(_), None) => 60,
+            (Some(_), Some(_)) => 1,
+        };
+        let (extra_seconds, mut digits) = match fraction {
+            None => (0, String::new()),
+            Some(digits) if unit == 1 => (0, String::from(digits)),
+            Some(digits) if digits.len() <= 30 => {
+                let scale = 10u128.pow(digits.len() as u32);
+                let total = unit * digits.parse::<u128>().unwrap_or_default();
+                let width = digits.len();
+                (
+                    (total / scale) as i32,
+                    alloc::format!("{:0width$}", total % scale),
+                )
+            }
+            Some(_) => {
+                return Err(ParseTimeError::new(
+                    "the fraction has too many digits to be written in seconds",
+                ));
+            }
+        };
+        while digits.ends_with('0') {
+            digits.pop();
+        }
+
+        let offset = match self.time_zone {
+            TimeZone::Local => {
+                return Err(ParseTimeError::new("a local time can't be written in UTC"));
+            }
+            TimeZone::Utc => 0,
+            TimeZone::Offset(minutes) => i32::from(minutes),
+        };
+        let leap = self.second == Some(60);
+        let seconds = i32::from(self.second.unwrap_or_default().min(59)) + extra_seconds;
+        let mut minutes =
+            i32::from(self.hour) * 60 + i32::from(self.minute.unwrap_or_default()) + seconds / 60
+                - offset;
+        let second = if leap { 60 } else { seconds % 60 };
+
+        let (mut year, mut month, mut day) = (i32::from(self.year), self.month, self.day);
+        while minutes < 0 {
+            minutes += 24 * 60;
+            day -= 1;
+            if day == 0 {
+                month -= 1;
+                if month == 0 {
+                    month = 12;
+                    year -= 1;
+                }
+                day = days_in_month(u16::try_from(year).unwrap_or_default(), month);
+            }
+        }
+        while minutes >= 24 * 60 {
+            minutes -= 24 * 60;
+            day += 1;
+            if day > days_in_month(u16::try_from(year).unwrap_or_default(), month) {
+                day = 1;
+                month += 1;
+                if month > 12 {
+                    month = 1;
+                    year += 1;
+                }
+            }
+        }
+
+        let mut text = String::new();
+        if generalized {
+            if !(0..=9999).contains(&year) {
+                return Err(ParseTimeError::new(alloc::format!(
+                    "a GeneralizedTime can't hold the year {year}"
+                )));
+            }
+            let _ = write!(text, "{year:04}");
+        } else {
+            if !(1950..=2049).contains(&year) {
+                return Err(ParseTimeError::new(alloc::format!(
+                    "a UTCTime can't hold the year {year}"
+                )));
+            }
+            let _ = write!(text, "{:02}", year % 100);
+        }
+        let _ = write!(
+            text,
+            "{month:02}{day:02}{:02}{:02}{second:02}",
+            minutes / 60,
+            minutes % 60
+        );
+        if !digits.is_empty() {
+            let _ = write!(text, ".{digits}");
+        }
+        text.push('Z');
+
+        Ok(text)
+    }
+
+    /// Returns the hour, minute, second and nanosecond of the time in the
+    /// form `chrono` uses, where a leap second is the second 59 with more
+    /// than a billion nanoseconds.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn time_of_day(&self, fraction: Option<&str>) -> (u32, u32, u32, u32) {
+        const NANOS: u128 = 1_000_000_000;
+        let unit = match (self.minute, self.second) {
+            (None, _) => 3600 * NANOS,
+            (Some(_), None) => 60 * NANOS,
+            (Some(_), Some(_)) => NANOS,
+        };
+        // Digits past the eighteenth are below a nanosecond even in hours.
+        let fraction = fraction.map_or(0, |digits| {
+            let digits = &digits[..digits.len().min(18)];
+            let scale = 10u128.pow(digits.len() as u32);
+            unit * digits.parse::<u128>().unwrap_or_default() / scale
+        });
+
+        let leap = self.second == Some(60);
+        let seconds = u32::from(self.hour) * 3600
+            + u32::from(self.minute.unwrap_or_default()) * 60
+            + u32::from(self.second.unwrap_or_default().min(59))
+            + (fraction / NANOS) as u32;
+        let nanos = (fraction % NANOS) as u32 + if leap { NANOS as u32 } else { 0 };
+
+        (seconds / 3600, seconds / 60 % 60, seconds % 60, nanos)
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn offset_seconds(&self) -> Result<i32, ParseTimeError> {
+        match self.time_zone {
+            TimeZone::Local => Err(ParseTimeError::new(
+                "a local time isn't a point in time without an offset from UTC",
+            )),
+            TimeZone::Utc => Ok(0),
+            TimeZone::Offset(minutes) => Ok(i32::from(minutes) * 60),
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    fn to_chrono(
+        self,
+        fraction: Option<&str>,
+    ) -> Result<chrono::DateTime<chrono::FixedOffset>, ParseTimeError> {
+        let (hour, minute, second, nano) = self.time_of_day(fraction);
+        let out_of_range = || ParseTimeError::new("the time is out of range for `chrono`");
+        let offset =
+            chrono::FixedOffset::east_opt(self.offset_seconds()?).ok_or_else(out_of_range)?;
+
+        chrono::NaiveDate::from_ymd_opt(i32::from(self.year), self.month.into(), self.day.into())
+            .and_then(|date| date.and_hms_nano_opt(hour, minute, second, nano))
+            .and_then(|time| time.and_local_timezone(offset).single())
+            .ok_or_else(out_of_range)
+    }
+
+    #[cfg(feature = "time")]
+    fn to_time(self, fraction: Option<&str>) -> Result<::time::OffsetDateTime, ParseTimeError> {
+        if self.second == Some(60) {
+            return Err(ParseTimeError::new("`time` can't represent leap seconds"));
+        }
+        let (hour, minute, second, nano) = self.time_of_day(fraction);
+        let out_of_range = |_| ParseTimeError::new("the time is out of range for `time`");
+
+        let date = ::time::Date::from_calendar_date(
+            i32::from(self.year),
+            ::time::Month::try_from(self.month).map_err(out_of_range)?,
+            self.day,
+        )
+        .map_err(out_of_range)?;
+        let time = ::time::Time::from_hms_nano(hour as u8, minute as u8, second as u8, nano)
+            .map_err(out_of_range)?;
+        let offset =
+            ::time::UtcOffset::from_whole_seconds(self.offset_seconds()?).map_err(out_of_range)?;
+
+        Ok(::time::PrimitiveDateTime::new(date, time).assume_offset(offset))
+    }
+}
+
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Writes a UTC time in the canonical form of X.690, with a four digit year
+/// for a `GeneralizedTime` and a two digit year for a `UTCTime`.
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[allow(clippy::too_many_arguments)]
+fn canonical_text(
+    generalized: bool,
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+) -> Result<String, ParseTimeError> {
+    use core::fmt::Write;
+
+    let mut text = String::new();
+    if generalized {
+        if !(0..=9999).contains(&year) {
+            return Err(ParseTimeError::new(alloc::format!(
+                "a GeneralizedTime can't hold the year {year}"
+            )));
+        }
+        let _ = write!(text, "{year:04}");
+    } else {
+        if !(1950..=2049).contains(&year) {
+            return Err(ParseTimeError::new(alloc::format!(
+                "a UTCTime can't hold the year {year}"
+            )));
+        }
+        let _ = write!(text, "{:02}", year % 100);
+    }
+    let _ = write!(text, "{month:02}{day:02}{hour:02}{minute:02}{second:02}");
+
+    if generalized && nanosecond > 0 {
+        let _ = write!(text, ".{nanosecond:09}");
+        while text.ends_with('0') {
+            text.pop();
+        }
+    }
+    text.push('Z');
+
+    Ok(text)
+}
+
+/// Splits a `chrono` time into UTC fields, turning its representation of
+/// leap seconds into the second 60.
+#[cfg(feature = "chrono")]
+fn chrono_fields<Tz: chrono::TimeZone>(
+    value: &chrono::DateTime<Tz>,
+) -> (i32, u32, u32, u32, u32, u32, u32) {
+    use chrono::{Datelike, Timelike};
+
+    let value = value.naive_utc();
+    let (second, nanosecond) = match value.nanosecond() {
+        nanosecond @ 1_000_000_000.. => (60, nanosecond - 1_000_000_000),
+        nanosecond => (value.second(), nanosecond),
+    };
+
+    (
+        value.year(),
+        value.month(),
+        value.day(),
+        value.hour(),
+        value.minute(),
+        second,
+        nanosecond,
+    )
+}
+
+/// Implements the codec traits for a type holding the text of a time value.
+///
+/// Without the `chrono` and `time` features the type is the one of
+/// [`GeneralizedTime`][super::GeneralizedTime], [`UtcTime`][super::UtcTime] or
+/// [`Date`][super::Date], and is encoded by the encoders themselves.
+macro_rules! text_codec {
+    ($name:ident, $tag:ident, $parse:expr) => {
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        impl AsnType for $name {
+            const TAG: Tag = Tag::$tag;
+            const IDENTIFIER: Identifier = Identifier::$tag;
+        }
+
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        impl Encode for $name {
+            fn encode_with_tag_and_constraints<'b, E: Encoder<'b>>(
+                &self,
+                encoder: &mut E,
+                tag: Tag,
+                _: Constraints,
+                identifier: Identifier,
+            ) -> Result<(), E::Error> {
+                let codec = encoder.codec();
+                // PER holds time values as the octets of their DER encoding,
+                // like it does for the date and time types.
+                if matches!(codec, Codec::Aper | Codec::Uper) {
+                    let contents = crate::der::encode(self)?;
+                    return encoder
+                        .encode_octet_string(
+                            tag,
+                            Constraints::default(),
+                            &contents,
+                            Identifier::EMPTY,
+                        )
+                        .map(drop);
+                }
+
+                let text =
+                    VisibleString::from_iso646_bytes(self.text.as_bytes()).map_err(|error| {
+                        EncodeError::alphabet_constraint_not_satisfied(error, codec)
+                    })?;
+                encoder
+                    .encode_visible_string(
+                        tag,
+                        Constraints::default(),
+                        &text,
+                        identifier.or(Self::IDENTIFIER),
+                    )
+                    .map(drop)
+            }
+        }
+
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        impl Decode for $name {
+            fn decode_with_tag_and_constraints<D: Decoder>(
+                decoder: &mut D,
+                tag: Tag,
+                _: Constraints,
+            ) -> Result<Self, D::Error> {
+                let codec = decoder.codec();
+                if matches!(codec, Codec::Aper | Codec::Uper) {
+                    let contents = OctetString::decode_with_tag(decoder, tag)?;
+                    return Ok(crate::ber::decode(&contents)?);
+                }
+
+                let text = VisibleString::decode_with_tag(decoder, tag)?.to_string();
+                let profile = match codec {
+                    Codec::Cer | Codec::Der | Codec::Coer => TimeProfile::Canonical,
+                    _ => TimeProfile::Basic,
+                };
+                ($parse)(text, profile).map_err(|error| D::Error::custom(error, codec))
+            }
+        }
+    };
+}
+
+macro_rules! time_string {
+    ($name:ident, $tag:ident, $parse:ident, $generalized:literal) => {
+        impl $name {
+            /// Parses a value, checking that it is valid according to X.680.
+            ///
+            /// # Errors
+            /// If `text` isn't a valid value.
+            pub fn new(text: impl Into<String>) -> Result<Self, ParseTimeError> {
+                let text = text.into();
+                let parts = Parts::$parse(&text)?;
+                Ok(Self { text, parts })
+            }
+
+            /// Parses a value, checking that it is valid according to
+            /// `profile`.
+            ///
+            /// # Errors
+            /// If `text` isn't a valid value, or isn't allowed by `profile`.
+            pub fn with_profile(
+                text: impl Into<String>,
+                profile: TimeProfile,
+            ) -> Result<Self, ParseTimeError> {
+                let value = Self::new(text)?;
+                value.check(profile)?;
+                Ok(value)
+            }
+
+            /// Returns the value exactly as it was written.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                &self.text
+            }
+
+            /// Converts the value into the text it was written as.
+            #[must_use]
+            pub fn into_string(self) -> String {
+                self.text
+            }
+
+            /// Returns the year.
+            #[must_use]
+            pub fn year(&self) -> u16 {
+                self.parts.year
+            }
+
+            /// Returns the month, from 1 to 12.
+            #[must_use]
+            pub fn month(&self) -> u8 {
+                self.parts.month
+            }
+
+            /// Returns the day of the month, from 1 to 31.
+            #[must_use]
+            pub fn day(&self) -> u8 {
+                self.parts.day
+            }
+
+            /// Returns the hour, from 0 to 23.
+            #[must_use]
+            pub fn hour(&self) -> u8 {
+                self.parts.hour
+            }
+
+            /// Returns the second, from 0 to 60 where 60 is a leap second,
+            /// if the value has seconds.
+            #[must_use]
+            pub fn second(&self) -> Option<u8> {
+                self.parts.second
+            }
+
+            /// Returns the time zone the value is written in.
+            #[must_use]
+            pub fn time_zone(&self) -> TimeZone {
+                self.parts.time_zone
+            }
+
+            /// Returns whether the value is a leap second.
+            #[must_use]
+            pub fn is_leap_second(&self) -> bool {
+                self.parts.second == Some(60)
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.text).finish()
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(&self.text)
+            }
+        }
+
+        impl core::str::FromStr for $name {
+            type Err = ParseTimeError;
+
+            fn from_str(text: &str) -> Result<Self, Self::Err> {
+                Self::new(text)
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.text.cmp(&other.text)
+            }
+        }
+
+        #[cfg(feature = "chrono")]
+        impl<Tz: chrono::TimeZone> TryFrom<chrono::DateTime<Tz>> for $name {
+            type Error = ParseTimeError;
+
+            /// Writes the time in the canonical form of X.690.
+            fn try_from(value: chrono::DateTime<Tz>) -> Result<Self, Self::Error> {
+                let (year, month, day, hour, minute, second, nanosecond) = chrono_fields(&value);
+                Self::new(canonical_text(
+                    $generalized,
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    nanosecond,
+                )?)
+            }
+        }
+
+        #[cfg(feature = "time")]
+        impl TryFrom<::time::OffsetDateTime> for $name {
+            type Error = ParseTimeError;
+
+            /// Writes the time in the canonical form of X.690.
+            fn try_from(value: ::time::OffsetDateTime) -> Result<Self, Self::Error> {
+                let value = value.to_offset(::time::UtcOffset::UTC);
+                Self::new(canonical_text(
+                    $generalized,
+                    value.year(),
+                    u8::from(value.month()).into(),
+                    value.day().into(),
+                    value.hour().into(),
+                    value.minute().into(),
+                    value.second().into(),
+                    value.nanosecond(),
+                )?)
+            }
+        }
+
+        #[cfg(feature = "time")]
+        impl TryFrom<&$name> for ::time::OffsetDateTime {
+            type Error = ParseTimeError;
+
+            fn try_from(value: &$name) -> Result<Self, Self::Error> {
+                value.parts.to_time(value.parts.fraction(&value.text))
+            }
+        }
+
+        text_codec!($name, $tag, Self::with_profile);
+    };
+}
+
+time_string!(
+    GeneralizedTimeString,
+    GENERALIZED_TIME,
+    parse_generalized_time,
+    true
+);
+time_string!(UtcTimeString, UTC_TIME, parse_utc_time, false);
+
+impl GeneralizedTimeString {
+    /// Returns the minute, if the value has minutes.
+    #[must_use]
+    pub fn minute(&self) -> Option<u8> {
+        self.parts.minute
+    }
+
+    /// Returns the digits of the fraction of the last unit of the time, if
+    /// the value has one.
+    #[must_use]
+    pub fn fraction(&self) -> Option<&str> {
+        self.parts.fraction(&self.text)
+    }
+
+    /// Checks that the value is allowed by `profile`.
+    ///
+    /// # Errors
+    /// If the value isn't written in a form `profile` allows.
+    pub fn check(&self, profile: TimeProfile) -> Result<(), ParseTimeError> {
+        self.parts.check_utc_with_seconds(profile)?;
+        match (profile, self.parts.fraction) {
+            (TimeProfile::Rfc5280, Some(_)) => Err(ParseTimeError::new(
+                "RFC 5280 doesn't allow fractions of a second",
+            )),
+            (TimeProfile::Canonical, Some((start, end))) => {
+                if self.text.as_bytes()[start - 1] != b'.' {
+                    Err(ParseTimeError::new("the decimal sign must be a full stop"))
+                } else if self.text[start..end].ends_with('0') {
+                    Err(ParseTimeError::new(
+                        "the fraction can't have trailing zeros",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Rewrites the value in the canonical form of X.690, which CER, DER and
+    /// CXER require: in UTC, with seconds, and with a fraction of a second
+    /// that uses a full stop and has no trailing zeros.
+    ///
+    /// ```rust
+    /// use rasn::types::{GeneralizedTimeString, TimeProfile};
+    ///
+    /// let time: GeneralizedTimeString = "202301020130,50+0200".parse().unwrap();
+    /// let canonical = time.to_canonical().unwrap();
+    /// assert_eq!(canonical.as_str(), "20230101233030Z");
+    /// assert!(canonical.check(TimeProfile::Canonical).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    /// If the value is a local time, which isn't a point in time, or if it
+    /// moves out of the years a `GeneralizedTime` can hold.
+    pub fn to_canonical(&self) -> Result<Self, ParseTimeError> {
+        Self::new(self.parts.canonical_text(self.fraction(), true)?)
+    }
+
+    /// Converts the value into a `chrono` date and time, as long as the
+    /// value has a time zone. Local times can be converted with
+    /// [`GeneralizedTimeString::to_naive_date_time`].
+    ///
+    /// # Errors
+    /// If the value is a local time, or is out of the range of `chrono`.
+    #[cfg(feature = "chrono")]
+    pub fn to_date_time(&self) -> Result<chrono::DateTime<chrono::FixedOffset>, ParseTimeError> {
+        self.parts.to_chrono(self.fraction())
+    }
+
+    /// Converts the value into a `chrono` date and time without a time zone,
+    /// holding the time as it is written.
+    ///
+    /// # Errors
+    /// If the value is out of the range of `chrono`.
+    #[cfg(feature = "chrono")]
+    pub fn to_naive_date_time(&self) -> Result<chrono::NaiveDateTime, ParseTimeError> {
+        self.assume_utc()
+            .to_chrono(self.fraction())
+            .map(|time| time.naive_utc())
+    }
+
+    /// Converts the value into a `time` date and time without an offset,
+    /// holding the time as it is written.
+    ///
+    /// # Errors
+    /// If the value is a leap second, or is out of the range of `time`.
+    #[cfg(feature = "time")]
+    pub fn to_primitive_date_time(&self) -> Result<::time::PrimitiveDateTime, ParseTimeError> {
+        self.assume_utc()
+            .to_time(self.fraction())
+            .map(|time| ::time::PrimitiveDateTime::new(time.date(), time.time()))
+    }
+
+    /// Returns the parts of the value with the time zone replaced by UTC.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn assume_utc(&self) -> Parts {
+        Parts {
+            time_zone: TimeZone::Utc,
+            ..self.parts
+        }
+    }
+}
+
+impl UtcTimeString {
+    /// Returns the minute.
+    #[must_use]
+    pub fn minute(&self) -> u8 {
+        self.parts.minute.unwrap_or_default()
+    }
+
+    /// Checks that the value is allowed by `profile`.
+    ///
+    /// # Errors
+    /// If the value isn't written in a form `profile` allows.
+    pub fn check(&self, profile: TimeProfile) -> Result<(), ParseTimeError> {
+        self.parts.check_utc_with_seconds(profile)
+    }
+
+    /// Rewrites the value in the canonical form of X.690, which CER, DER and
+    /// CXER require: in UTC and with seconds.
+    ///
+    /// # Errors
+    /// If the value moves out of the years from 1950 to 2049 that a
+    /// `UTCTime` can hold.
+    pub fn to_canonical(&self) -> Result<Self, ParseTimeError> {
+        Self::new(self.parts.canonical_text(None, false)?)
+    }
+
+    /// Converts the value into a `chrono` date and time in UTC.
+    ///
+    /// # Errors
+    /// If the value is out of the range of `chrono`.
+    #[cfg(feature = "chrono")]
+    pub fn to_date_time(&self) -> Result<chrono::DateTime<chrono::Utc>, ParseTimeError> {
+        self.parts.to_chrono(None).map(|time| time.to_utc())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&GeneralizedTimeString> for chrono::DateTime<chrono::FixedOffset> {
+    type Error = ParseTimeError;
+
+    fn try_from(value: &GeneralizedTimeString) -> Result<Self, Self::Error> {
+        value.to_date_time()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&UtcTimeString> for chrono::DateTime<chrono::Utc> {
+    type Error = ParseTimeError;
+
+    fn try_from(value: &UtcTimeString) -> Result<Self, Self::Error> {
+        value.to_date_time()
+    }
+}
+
+impl DateString {
+    /// Parses a date, checking that it is a valid `YYYYMMDD`.
+    ///
+    /// # Errors
+    /// If `text` isn't a valid date.
+    pub fn new(text: impl Into<String>) -> Result<Self, ParseTimeError> {
+        let text = text.into();
+        let mut cursor = Cursor::new(&text);
+        let year = cursor.number(4, "year")?;
+        let month = cursor.field("month", 1..=12)?;
+        let day = cursor.field("day", 1..=days_in_month(year, month))?;
+        cursor.finish()?;
+
+        Ok(Self {
+            text,
+            year,
+            month,
+            day,
+        })
+    }
+
+    /// Creates a date from its year, month and day.
+    ///
+    /// # Errors
+    /// If the year has more than four digits, or the date doesn't exist.
+    pub fn from_ymd(year: u16, month: u8, day: u8) -> Result<Self, ParseTimeError> {
+        if year > 9999 {
+            return Err(ParseTimeError::new(alloc::format!(
+                "a DATE can't hold the year {year}"
+            )));
+        }
+        Self::new(alloc::format!("{year:04}{month:02}{day:02}"))
+    }
+
+    /// Returns the date as it is written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Converts the date into the text it is written as.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns the year.
+    #[must_use]
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, from 1 to 12.
+    #[must_use]
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, from 1 to 31.
+    #[must_use]
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl core::fmt::Debug for DateString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DateString").field(&self.text).finish()
+    }
+}
+
+impl core::fmt::Display for DateString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl core::str::FromStr for DateString {
+    type Err = ParseTimeError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::new(text)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for DateString {
+    type Error = ParseTimeError;
+
+    fn try_from(value: chrono::NaiveDate) -> Result<Self, Self::Error> {
+        use chrono::Datelike;
+
+        let year = u16::try_from(value.year()).map_err(|_| {
+            ParseTimeError::new(alloc::format!(
+                "a DATE can't hold the year {}",
+                value.year()
+            ))
+        })?;
+        Self::from_ymd(year, value.month() as u8, value.day() as u8)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&DateString> for chrono::NaiveDate {
+    type Error = ParseTimeError;
+
+    fn try_from(value: &DateString) -> Result<Self, Self::Error> {
+        Self::from_ymd_opt(value.year.into(), value.month.into(), value.day.into())
+            .ok_or_else(|| ParseTimeError::new("the date is out of range for `chrono`"))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<::time::Date> for DateString {
+    type Error = ParseTimeError;
+
+    fn try_from(value: ::time::Date) -> Result<Self, Self::Error> {
+        let year = u16::try_from(value.year()).map_err(|_| {
+            ParseTimeError::new(alloc::format!(
+                "a DATE can't hold the year {}",
+                value.year()
+            ))
+        })?;
+        Self::from_ymd(year, value.month().into(), value.day())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<&DateString> for ::time::Date {
+    type Error = ParseTimeError;
+
+    fn try_from(value: &DateString) -> Result<Self, Self::Error> {
+        let out_of_range = |_| ParseTimeError::new("the date is out of range for `time`");
+        Self::from_calendar_date(
+            value.year.into(),
+            ::time::Month::try_from(value.month).map_err(out_of_range)?,
+            value.day,
+        )
+        .map_err(out_of_range)
+    }
+}
+
+text_codec!(DateString, DATE, |text, _| DateString::new(text));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+
+    #[test]
+    fn generalized_time_forms() {
+        for text in [
+            "2023010215",
+            "2023010215.5",
+            "202301021530,25+01",
+            "20230102153045.123456789012-0130",
+            "20161231235960Z",
+        ] {
+            let time = GeneralizedTimeString::new(text).unwrap();
+            assert_eq!(time.as_str(), text);
+            let encoded = crate::ber::encode(&time).unwrap();
+            assert_eq!(
+                crate::ber::decode::<GeneralizedTimeString>(&encoded).unwrap(),
+                time
+            );
+        }
+
+        for text in [
+            "20230230",
+            "20230229120000Z",
+            "2023010224",
+            "20230102153061Z",
+            "202301021530.Z",
+            "20230102153000Zx",
+            "20230102153000+2400",
+        ] {
+            assert!(GeneralizedTimeString::new(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn canonical_forms() {
+        let canonical = |text: &str| {
+            GeneralizedTimeString::new(text)
+                .and_then(|time| time.to_canonical())
+                .map(|time| time.text)
+        };
+        assert_eq!(canonical("2023010215Z").unwrap(), "20230102150000Z");
+        assert_eq!(canonical("2023010215.5Z").unwrap(), "20230102153000Z");
+        assert_eq!(canonical("202301021530,25Z").unwrap(), "20230102153015Z");
+        assert_eq!(canonical("2023010215.0001Z").unwrap(), "20230102150000.36Z");
+        assert_eq!(
+            canonical("20230102153045.10+0100").unwrap(),
+            "20230102143045.1Z"
+        );
+        assert_eq!(canonical("20231231233000-0100").unwrap(), "20240101003000Z");
+        assert_eq!(canonical("20240301003000+0100").unwrap(), "20240229233000Z");
+        assert_eq!(canonical("20161231235960Z").unwrap(), "20161231235960Z");
+        assert!(canonical("20230102153000").is_err());
+        assert!(canonical("00000101000000+0100").is_err());
+
+        let utc = |text: &str| {
+            UtcTimeString::new(text)
+                .and_then(|time| time.to_canonical())
+                .map(|time| time.text)
+        };
+        assert_eq!(utc("2301021530+0100").unwrap(), "230102143000Z");
+        assert_eq!(utc("991231233000-0100").unwrap(), "000101003000Z");
+        assert!(utc("491231233000-0100").is_err());
+        assert!(utc("500101003000+0100").is_err());
+    }
+
+    #[test]
+    fn profiles() {
+        let check = |text: &str, profile| GeneralizedTimeString::new(text).unwrap().check(profile);
+
+        assert!(check("20230102153000Z", TimeProfile::Rfc5280).is_ok());
+        assert!(check("20230102153000.5Z", TimeProfile::Rfc5280).is_err());
+        assert!(check("20230102153000.5Z", TimeProfile::Canonical).is_ok());
+        assert!(check("20230102153000,5Z", TimeProfile::Canonical).is_err());
+        assert!(check("20230102153000.50Z", TimeProfile::Canonical).is_err());
+        assert!(check("202301021530Z", TimeProfile::Canonical).is_err());
+        assert!(check("20230102153000", TimeProfile::Canonical).is_err());
+        assert!(check("20230102153000", TimeProfile::Basic).is_ok());
+
+        let encoded =
+            crate::ber::encode(&GeneralizedTimeString::new("20230102153000").unwrap()).unwrap();
+        assert!(crate::der::decode::<GeneralizedTimeString>(&encoded).is_err());
+
+        assert!(UtcTimeString::with_profile("230102153000Z", TimeProfile::Rfc5280).is_ok());
+        assert!(UtcTimeString::with_profile("2301021530Z", TimeProfile::Rfc5280).is_err());
+        assert!(UtcTimeString::new("230102153000").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_conversions() {
+        let time = GeneralizedTimeString::new("20161231235960.25Z").unwrap();
+        assert!(time.is_leap_second());
+        let converted = time.to_date_time().unwrap();
+        assert_eq!(
+            GeneralizedTimeString::try_from(converted).unwrap().as_str(),
+            "20161231235960.25Z"
+        );
+
+        let local = GeneralizedTimeString::new("202301021530.5").unwrap();
+        assert!(local.to_date_time().is_err());
+        assert_eq!(
+            local.to_naive_date_time().unwrap().to_string(),
+            "2023-01-02 15:30:30"
+        );
+
+        let time = UtcTimeString::try_from(converted).unwrap();
+        assert_eq!(time.as_str(), "161231235960Z");
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_conversions() {
+        let time = GeneralizedTimeString::new("20230102153000.5+0100").unwrap();
+        let converted = ::time::OffsetDateTime::try_from(&time).unwrap();
+        assert_eq!(converted.offset().whole_minutes(), 60);
+        assert_eq!(
+            GeneralizedTimeString::try_from(converted).unwrap().as_str(),
+            "20230102143000.5Z"
+        );
+
+        let leap = GeneralizedTimeString::new("20161231235960Z").unwrap();
+        assert!(::time::OffsetDateTime::try_from(&leap).is_err());
+
+        let local = GeneralizedTimeString::new("202301021530.5").unwrap();
+        assert!(::time::OffsetDateTime::try_from(&local).is_err());
+        assert_eq!(
+            local.to_primitive_date_time().unwrap().to_string(),
+            "2023-01-02 15:30:30.0"
+        );
+
+        let date = DateString::new("20240229").unwrap();
+        let converted = ::time::Date::try_from(&date).unwrap();
+        assert_eq!(DateString::try_from(converted).unwrap(), date);
+    }
+
+    #[test]
+    #[cfg(all(feature = "chrono", feature = "time"))]
+    fn time_types_with_chrono() {
+        let time = ::time::OffsetDateTime::try_from(
+            &GeneralizedTimeString::new("20230102153000.5+0100").unwrap(),
+        )
+        .unwrap();
+        let utc = time.to_utc().replace_nanosecond(0).unwrap();
+        let date = time.date();
+
+        for codec in [Codec::Ber, Codec::Der, Codec::Uper, Codec::Oer, Codec::Jer] {
+            let encoded = codec.encode_to_binary(&time).unwrap();
+            assert_eq!(
+                codec
+                    .decode_from_binary::<::time::OffsetDateTime>(&encoded)
+                    .unwrap(),
+                time
+            );
+            assert_eq!(
+                encoded,
+                codec
+                    .encode_to_binary(
+                        &crate::types::GeneralizedTime::try_from(
+                            &GeneralizedTimeString::try_from(time).unwrap()
+                        )
+                        .unwrap()
+                    )
+                    .unwrap()
+            );
+
+            let encoded = codec.encode_to_binary(&utc).unwrap();
+            assert_eq!(
+                codec
+                    .decode_from_binary::<::time::UtcDateTime>(&encoded)
+                    .unwrap(),
+                utc
+            );
+
+            let encoded = codec.encode_to_binary(&date).unwrap();
+            assert_eq!(
+                codec.decode_from_binary::<::time::Date>(&encoded).unwrap(),
+                date
+            );
+        }
+    }
+
+    #[test]
+    fn dates() {
+        let date = DateString::from_ymd(2024, 2, 29).unwrap();
+        assert_eq!(date.as_str(), "20240229");
+        assert!(DateString::from_ymd(2023, 2, 29).is_err());
+        assert!(DateString::from_ymd(10000, 1, 1).is_err());
+        assert!(DateString::new("2024-02-29").is_err());
+        assert!(DateString::new("202402290").is_err());
+        assert!(DateString::new("20240100").is_err());
+        assert!(date < DateString::new("20240301").unwrap());
+
+        for codec in [Codec::Ber, Codec::Der, Codec::Uper, Codec::Oer] {
+            let encoded = codec.encode_to_binary(&date).unwrap();
+            assert_eq!(
+                codec.decode_from_binary::<DateString>(&encoded).unwrap(),
+                date
+            );
+        }
+
+        let json = crate::jer::encode(&date).unwrap();
+        assert_eq!(json, "\"20240229\"");
+        assert_eq!(crate::jer::decode::<DateString>(&json).unwrap(), date);
+    }
+
+    #[test]
+    fn codecs() {
+        let time = GeneralizedTimeString::new("20230102153000.5Z").unwrap();
+        for codec in [
+            Codec::Der,
+            Codec::Uper,
+            Codec::Aper,
+            Codec::Oer,
+            Codec::Coer,
+        ] {
+            let encoded = codec.encode_to_binary(&time).unwrap();
+            assert_eq!(
+                codec
+                    .decode_from_binary::<GeneralizedTimeString>(&encoded)
+                    .unwrap(),
+                time
+            );
+        }
+
+        #[cfg(feature = "chrono")]
+        {
+            let chrono = time.to_date_time().unwrap();
+            assert_eq!(
+                crate::uper::encode(&time).unwrap(),
+                crate::uper::encode(&chrono).unwrap()
+            );
+            assert_eq!(
+                crate::oer::encode(&time).unwrap(),
+                crate::oer::encode(&chrono).unwrap()
+            );
+        }
+
+        let json = crate::jer::encode(&time).unwrap();
+        assert_eq!(json, "\"20230102153000.5Z\"");
+        assert_eq!(
+            crate::jer::decode::<GeneralizedTimeString>(&json).unwrap(),
+            time
+        );
+    }
+}
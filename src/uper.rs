@@ -35,6 +35,15 @@ pub fn encode<T: crate::Encode>(
     }
 }
 
+/// Returns the length of the UPER-CANONICAL encoding of `value`, without
+/// encoding it.
+/// # Errors
+/// Returns error specific to UPER encoder if encoding is not possible.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::error::EncodeError> {
+    // An empty encoding is written as one octet, as by `encode`.
+    crate::per::encoded_len(enc::EncoderOptions::unaligned(), value).map(|len| len.max(1))
+}
+
 /// Encodes `value` to UPER-CANONICAL into an existing `buffer`, reusing its allocation.
 /// The buffer is cleared before encoding.
 /// # Errors
@@ -75,82 +84,90 @@ pub fn encode_with_constraints<T: crate::Encode>(
 #[cfg(test)]
 mod tests {
     use crate::{
+        Codec,
         prelude::*,
+        testing::{round_trip_encoding, round_trip_encoding_with_constraints},
         types::{constraints::*, *},
     };
 
     #[test]
     fn bool() {
-        round_trip!(uper, bool, true, &[0x80]);
-        round_trip!(uper, bool, false, &[0]);
+        round_trip_encoding::<bool>(Codec::Uper, &true, &[0x80]);
+        round_trip_encoding::<bool>(Codec::Uper, &false, &[0]);
     }
 
     #[test]
     fn integer() {
-        round_trip!(uper, Integer, 32768.into(), &[0x03, 0x00, 0x80, 0x00]);
-        round_trip!(uper, Integer, 32767.into(), &[0x02, 0x7f, 0xff]);
-        round_trip!(uper, Integer, 256.into(), &[0x02, 0x01, 0x00]);
-        round_trip!(uper, Integer, 255.into(), &[0x02, 0x00, 0xff]);
-        round_trip!(uper, Integer, 128.into(), &[0x02, 0x00, 0x80]);
-        round_trip!(uper, Integer, 127.into(), &[0x01, 0x7f]);
-        round_trip!(uper, Integer, 1.into(), &[0x01, 0x01]);
-        round_trip!(uper, Integer, 0.into(), &[0x01, 0x00]);
-        round_trip!(uper, Integer, (-1).into(), &[0x01, 0xff]);
-        round_trip!(uper, Integer, (-128).into(), &[0x01, 0x80]);
-        round_trip!(uper, Integer, (-129).into(), &[0x02, 0xff, 0x7f]);
-        round_trip!(uper, Integer, (-256).into(), &[0x02, 0xff, 0x00]);
-        round_trip!(uper, Integer, (-32768).into(), &[0x02, 0x80, 0x00]);
-        round_trip!(uper, Integer, (-32769).into(), &[0x03, 0xff, 0x7f, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Uper, &32768.into(), &[0x03, 0x00, 0x80, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Uper, &32767.into(), &[0x02, 0x7f, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Uper, &256.into(), &[0x02, 0x01, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Uper, &255.into(), &[0x02, 0x00, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Uper, &128.into(), &[0x02, 0x00, 0x80]);
+        round_trip_encoding::<Integer>(Codec::Uper, &127.into(), &[0x01, 0x7f]);
+        round_trip_encoding::<Integer>(Codec::Uper, &1.into(), &[0x01, 0x01]);
+        round_trip_encoding::<Integer>(Codec::Uper, &0.into(), &[0x01, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Uper, &(-1).into(), &[0x01, 0xff]);
+        round_trip_encoding::<Integer>(Codec::Uper, &(-128).into(), &[0x01, 0x80]);
+        round_trip_encoding::<Integer>(Codec::Uper, &(-129).into(), &[0x02, 0xff, 0x7f]);
+        round_trip_encoding::<Integer>(Codec::Uper, &(-256).into(), &[0x02, 0xff, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Uper, &(-32768).into(), &[0x02, 0x80, 0x00]);
+        round_trip_encoding::<Integer>(Codec::Uper, &(-32769).into(), &[0x03, 0xff, 0x7f, 0xff]);
 
         type B = ConstrainedInteger<5, 99>;
         type C = ConstrainedInteger<-10, 10>;
         //type D = ExtensibleConstrainedInteger<5, 99>;
         type E = ConstrainedInteger<1000, 1000>;
 
-        round_trip!(uper, B, B::new(5), &[0x00]);
-        round_trip!(uper, B, B::new(6), &[0x02]);
-        round_trip!(uper, B, B::new(99), &[0xbc]);
-        round_trip!(uper, C, C::new(-10), &[0x00]);
-        round_trip!(uper, C, C::new(-1), &[0x48]);
-        round_trip!(uper, C, C::new(0), &[0x50]);
-        round_trip!(uper, C, C::new(1), &[0x58]);
-        round_trip!(uper, C, C::new(10), &[0xa0]);
+        round_trip_encoding::<B>(Codec::Uper, &B::new(5), &[0x00]);
+        round_trip_encoding::<B>(Codec::Uper, &B::new(6), &[0x02]);
+        round_trip_encoding::<B>(Codec::Uper, &B::new(99), &[0xbc]);
+        round_trip_encoding::<C>(Codec::Uper, &C::new(-10), &[0x00]);
+        round_trip_encoding::<C>(Codec::Uper, &C::new(-1), &[0x48]);
+        round_trip_encoding::<C>(Codec::Uper, &C::new(0), &[0x50]);
+        round_trip_encoding::<C>(Codec::Uper, &C::new(1), &[0x58]);
+        round_trip_encoding::<C>(Codec::Uper, &C::new(10), &[0xa0]);
         // round_trip!(uper, D, 99, &[0x5e]);
-        round_trip!(uper, E, E::new(1000), &[0x00]);
+        round_trip_encoding::<E>(Codec::Uper, &E::new(1000), &[0x00]);
+
+        type F = ConstrainedInteger<5, 99, u8>;
+        type G = ConstrainedInteger<-10, 10, i8>;
+
+        round_trip_encoding::<F>(Codec::Uper, &F::try_from(99).unwrap(), &[0xbc]);
+        round_trip_encoding::<G>(Codec::Uper, &G::try_from(-1).unwrap(), &[0x48]);
+        round_trip_encoding::<G>(Codec::Uper, &G::try_from(10).unwrap(), &[0xa0]);
+        assert!(F::try_from(100).is_err());
+        assert!(G::try_from(-11).is_err());
     }
 
     #[test]
     fn sequence_of() {
-        round_trip!(uper, Vec<u8>, vec![1; 5], &[0b0000_0101, 1, 1, 1, 1, 1]);
-        round_trip!(aper, Vec<u8>, vec![1; 5], &[0b0000_0101, 1, 1, 1, 1, 1]);
+        round_trip_encoding::<Vec<u8>>(Codec::Uper, &vec![1; 5], &[0b0000_0101, 1, 1, 1, 1, 1]);
+        round_trip_encoding::<Vec<u8>>(Codec::Aper, &vec![1; 5], &[0b0000_0101, 1, 1, 1, 1, 1]);
     }
 
     #[test]
     fn numeric_string() {
-        round_trip!(
-            uper,
-            NumericString,
-            " 0123456789".try_into().unwrap(),
-            &[0x0b, 0x01, 0x23, 0x45, 0x67, 0x89, 0xa0]
+        round_trip_encoding::<NumericString>(
+            Codec::Uper,
+            &" 0123456789".try_into().unwrap(),
+            &[0x0b, 0x01, 0x23, 0x45, 0x67, 0x89, 0xa0],
         );
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(5));
-        round_trip_with_constraints!(
-            uper,
-            NumericString,
+        round_trip_encoding_with_constraints::<NumericString>(
+            Codec::Uper,
             CONSTRAINT_1,
-            "1 9 5".try_into().unwrap(),
-            &[0x20, 0xa0, 0x60]
+            &"1 9 5".try_into().unwrap(),
+            &[0x20, 0xa0, 0x60],
         );
 
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(19, 134));
-        round_trip_with_constraints!(
-            uper,
-            NumericString,
+        round_trip_encoding_with_constraints::<NumericString>(
+            Codec::Uper,
             CONSTRAINT_2,
-            "0123456789 9876543210".try_into().unwrap(),
+            &"0123456789 9876543210".try_into().unwrap(),
             &[
-                0x04, 0x24, 0x68, 0xac, 0xf1, 0x34, 0x15, 0x30, 0xec, 0xa8, 0x64, 0x20
-            ]
+                0x04, 0x24, 0x68, 0xac, 0xf1, 0x34, 0x15, 0x30, 0xec, 0xa8, 0x64, 0x20,
+            ],
         );
         const CONSTRAINT_3: Constraints = constraints!(permitted_alphabet_constraint!(&[
             b'0' as u32,
@@ -161,35 +178,32 @@ mod tests {
             b'5' as u32
         ]));
 
-        round_trip_with_constraints!(
-            uper,
-            NumericString,
+        round_trip_encoding_with_constraints::<NumericString>(
+            Codec::Uper,
             CONSTRAINT_3,
-            "5".try_into().unwrap(),
-            &[0x01, 0xa0]
+            &"5".try_into().unwrap(),
+            &[0x01, 0xa0],
         );
     }
 
     #[test]
     fn visible_string() {
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(19, 133));
-        round_trip_with_constraints!(
-            uper,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Uper,
             CONSTRAINT_1,
-            "HejHoppHappHippAbcde".try_into().unwrap(),
+            &"HejHoppHappHippAbcde".try_into().unwrap(),
             &[
                 0x03, 0x23, 0x2e, 0xa9, 0x1b, 0xf8, 0x70, 0x91, 0x87, 0x87, 0x09, 0x1a, 0x78, 0x70,
-                0x83, 0x8b, 0x1e, 0x4c, 0xa0
-            ]
+                0x83, 0x8b, 0x1e, 0x4c, 0xa0,
+            ],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(5));
-        round_trip_with_constraints!(
-            uper,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Uper,
             CONSTRAINT_2,
-            "Hejaa".try_into().unwrap(),
-            &[0x91, 0x97, 0x56, 0x1c, 0x20]
+            &"Hejaa".try_into().unwrap(),
+            &[0x91, 0x97, 0x56, 0x1c, 0x20],
         );
 
         const ALPHABET: &[u32] = &{
@@ -213,41 +227,67 @@ mod tests {
             size_constraint!(1, 255),
             permitted_alphabet_constraint!(ALPHABET)
         );
-        round_trip_with_constraints!(
-            uper,
-            VisibleString,
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Uper,
             CONSTRAINT_3,
-            "hej".try_into().unwrap(),
-            &[0x02, 0x39, 0x12]
+            &"hej".try_into().unwrap(),
+            &[0x02, 0x39, 0x12],
+        );
+
+        const CONSTRAINT_4: Constraints = constraints!(
+            size_constraint!(1, 255),
+            Constraint::PermittedAlphabet(Extensible::new(PermittedAlphabet::from_ranges(
+                &CharacterRange::indexed([CharacterRange::new('a' as u32, 'z' as u32)])
+            )))
+        );
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Uper,
+            CONSTRAINT_4,
+            &"hej".try_into().unwrap(),
+            &[0x02, 0x39, 0x12],
+        );
+
+        // FROM ("A".."Z" | "0".."9")
+        const CONSTRAINT_5: Constraints = constraints!(
+            size_constraint!(1, 255),
+            Constraint::PermittedAlphabet(Extensible::new(PermittedAlphabet::from_ranges(
+                &CharacterRange::indexed([
+                    CharacterRange::new('0' as u32, '9' as u32),
+                    CharacterRange::new('A' as u32, 'Z' as u32),
+                ])
+            )))
+        );
+        round_trip_encoding_with_constraints::<VisibleString>(
+            Codec::Uper,
+            CONSTRAINT_5,
+            &"AB1".try_into().unwrap(),
+            &[0x02, 0x28, 0xb0, 0x40],
         );
     }
     #[test]
     fn printable_string() {
         const CONSTRAINT_1: Constraints = constraints!(size_constraint!(16));
-        round_trip_with_constraints!(
-            uper,
-            PrintableString,
+        round_trip_encoding_with_constraints::<PrintableString>(
+            Codec::Uper,
             CONSTRAINT_1,
-            PrintableString::from_bytes("0123456789abcdef".as_bytes()).unwrap(),
+            &PrintableString::from_bytes("0123456789abcdef".as_bytes()).unwrap(),
             &[
-                0x60, 0xc5, 0x93, 0x36, 0x8d, 0x5b, 0x37, 0x70, 0xe7, 0x0e, 0x2c, 0x79, 0x32, 0xe6
-            ]
+                0x60, 0xc5, 0x93, 0x36, 0x8d, 0x5b, 0x37, 0x70, 0xe7, 0x0e, 0x2c, 0x79, 0x32, 0xe6,
+            ],
         );
         const CONSTRAINT_2: Constraints = constraints!(size_constraint!(0, 31));
-        round_trip_with_constraints!(
-            uper,
-            PrintableString,
+        round_trip_encoding_with_constraints::<PrintableString>(
+            Codec::Uper,
             CONSTRAINT_2,
-            "".try_into().unwrap(),
-            &[0x00]
+            &"".try_into().unwrap(),
+            &[0x00],
         );
         const CONSTRAINT_3: Constraints = constraints!(size_constraint!(0, 31));
-        round_trip_with_constraints!(
-            uper,
-            PrintableString,
+        round_trip_encoding_with_constraints::<PrintableString>(
+            Codec::Uper,
             CONSTRAINT_3,
-            "2".try_into().unwrap(),
-            &[0x0b, 0x20]
+            &"2".try_into().unwrap(),
+            &[0x0b, 0x20],
         );
 
         #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
@@ -258,19 +298,18 @@ mod tests {
             b: PrintableString,
             c: bool,
         }
-        round_trip!(
-            uper,
-            PrintStruct,
-            PrintStruct {
+        round_trip_encoding::<PrintStruct>(
+            Codec::Uper,
+            &(PrintStruct {
                 a: true,
                 b: "123123123123123123123123123123123123".try_into().unwrap(),
-                c: true
-            },
+                c: true,
+            }),
             &[
                 0xb1, 0x64, 0xcd, 0x8b, 0x26, 0x6c, 0x59, 0x33, 0x62, 0xc9, 0x9b, 0x16, 0x4c, 0xd8,
                 0xb2, 0x66, 0xc5, 0x93, 0x36, 0x2c, 0x99, 0xb1, 0x64, 0xcd, 0x8b, 0x26, 0x6c, 0x59,
-                0x33, 0x62, 0xc9, 0x9c
-            ]
+                0x33, 0x62, 0xc9, 0x9c,
+            ],
         );
     }
 
@@ -287,9 +326,9 @@ mod tests {
             Medium,
         }
 
-        round_trip!(uper, Choice, Choice::Normal, &[0]);
-        round_trip!(uper, Choice, Choice::Medium, &[0x80, 1, 0]);
-        round_trip!(aper, Choice, Choice::Medium, &[0x80, 1, 0]);
+        round_trip_encoding::<Choice>(Codec::Uper, &Choice::Normal, &[0]);
+        round_trip_encoding::<Choice>(Codec::Uper, &Choice::Medium, &[0x80, 1, 0]);
+        round_trip_encoding::<Choice>(Codec::Aper, &Choice::Medium, &[0x80, 1, 0]);
 
         #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
         #[rasn(choice, automatic_tags)]
@@ -299,17 +338,15 @@ mod tests {
             High(u8),
         }
 
-        round_trip!(
-            uper,
-            ChoiceWithData,
-            ChoiceWithData::Normal(0xaf),
-            &[0b0010_1011, 0b1100_0000]
+        round_trip_encoding::<ChoiceWithData>(
+            Codec::Uper,
+            &ChoiceWithData::Normal(0xaf),
+            &[0b0010_1011, 0b1100_0000],
         );
-        round_trip!(
-            uper,
-            ChoiceWithData,
-            ChoiceWithData::High(0xaf),
-            &[0b0110_1011, 0b1100_0000]
+        round_trip_encoding::<ChoiceWithData>(
+            Codec::Uper,
+            &ChoiceWithData::High(0xaf),
+            &[0b0110_1011, 0b1100_0000],
         );
 
         #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
@@ -322,17 +359,15 @@ mod tests {
             Medium(u8),
         }
 
-        round_trip!(
-            uper,
-            ExtendedChoiceWithData,
-            ExtendedChoiceWithData::Normal(0xaf),
-            &[0b0010_1011, 0b1100_0000]
+        round_trip_encoding::<ExtendedChoiceWithData>(
+            Codec::Uper,
+            &ExtendedChoiceWithData::Normal(0xaf),
+            &[0b0010_1011, 0b1100_0000],
         );
-        round_trip!(
-            uper,
-            ExtendedChoiceWithData,
-            ExtendedChoiceWithData::Medium(0x42),
-            &[0x80, 1, 0x42]
+        round_trip_encoding::<ExtendedChoiceWithData>(
+            Codec::Uper,
+            &ExtendedChoiceWithData::Medium(0x42),
+            &[0x80, 1, 0x42],
         );
     }
 
@@ -346,9 +381,9 @@ mod tests {
             Blue,
         }
 
-        round_trip!(uper, Enum1, Enum1::Green, &[0]);
-        round_trip!(uper, Enum1, Enum1::Red, &[0x40]);
-        round_trip!(uper, Enum1, Enum1::Blue, &[0x80]);
+        round_trip_encoding::<Enum1>(Codec::Uper, &Enum1::Green, &[0]);
+        round_trip_encoding::<Enum1>(Codec::Uper, &Enum1::Red, &[0x40]);
+        round_trip_encoding::<Enum1>(Codec::Uper, &Enum1::Blue, &[0x80]);
 
         #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
         #[rasn(enumerated, crate_root = "crate")]
@@ -363,9 +398,9 @@ mod tests {
             Purple,
         }
 
-        round_trip!(uper, Enum2, Enum2::Red, &[0]);
-        round_trip!(uper, Enum2, Enum2::Yellow, &[0x80]);
-        round_trip!(uper, Enum2, Enum2::Purple, &[0x81]);
+        round_trip_encoding::<Enum2>(Codec::Uper, &Enum2::Red, &[0]);
+        round_trip_encoding::<Enum2>(Codec::Uper, &Enum2::Yellow, &[0x80]);
+        round_trip_encoding::<Enum2>(Codec::Uper, &Enum2::Purple, &[0x81]);
     }
 
     #[test]
@@ -743,122 +778,111 @@ mod tests {
         // round_trip!(uper, B, B { a: 0.into() }, &[0]);
         // round_trip!(uper, B, B { a: 1.into() }, &[0x80, 0x80, 0x80]);
         // round_trip!(uper, C, C {a: true}, &[0x40]);
-        round_trip!(uper, D, D { a: true, b: None }, &[0x40]);
-        round_trip!(uper, I, I { a: true, b: None }, &[0x40]);
-        round_trip!(uper, J, J { a: true, b: None }, &[0x40]);
-        round_trip!(
-            uper,
-            K,
-            K {
+        round_trip_encoding::<D>(Codec::Uper, &(D { a: true, b: None }), &[0x40]);
+        round_trip_encoding::<I>(Codec::Uper, &(I { a: true, b: None }), &[0x40]);
+        round_trip_encoding::<J>(Codec::Uper, &(J { a: true, b: None }), &[0x40]);
+        round_trip_encoding::<K>(
+            Codec::Uper,
+            &(K {
                 a: true,
                 b: None,
-                c: None
-            },
-            &[0x40]
+                c: None,
+            }),
+            &[0x40],
         );
-        round_trip!(uper, L, L { a: true, b: None }, &[0x40]);
-        round_trip!(uper, M, M { a: true, b: None }, &[0x40]);
-        round_trip!(uper, N, N { a: true }, &[0x00]);
-        round_trip!(uper, N, N { a: false }, &[0x80]);
-        round_trip!(uper, P, P { a: None }, &[0x00]);
-        round_trip!(
-            uper,
-            G,
-            G {
+        round_trip_encoding::<L>(Codec::Uper, &(L { a: true, b: None }), &[0x40]);
+        round_trip_encoding::<M>(Codec::Uper, &(M { a: true, b: None }), &[0x40]);
+        round_trip_encoding::<N>(Codec::Uper, &(N { a: true }), &[0x00]);
+        round_trip_encoding::<N>(Codec::Uper, &(N { a: false }), &[0x80]);
+        round_trip_encoding::<P>(Codec::Uper, &(P { a: None }), &[0x00]);
+        round_trip_encoding::<G>(
+            Codec::Uper,
+            &(G {
                 a: true,
                 b: Some(GE { a: true }),
                 c: Some(GE { a: true }),
-                d: true
-            },
-            &[0xe0, 0x70, 0x18, 0x00, 0x18, 0x00]
+                d: true,
+            }),
+            &[0xe0, 0x70, 0x18, 0x00, 0x18, 0x00],
         );
-        round_trip!(
-            uper,
-            M,
-            M {
+        round_trip_encoding::<M>(
+            Codec::Uper,
+            &(M {
                 a: true,
                 b: Some(ME {
                     a: Some(MESeq { a: 5.into() }),
-                    b: true
-                })
-            },
-            &[0xc0, 0x40, 0xe0, 0x20, 0xb0, 0x00]
+                    b: true,
+                }),
+            }),
+            &[0xc0, 0x40, 0xe0, 0x20, 0xb0, 0x00],
         );
-        round_trip!(
-            uper,
-            Q,
-            Q {
+        round_trip_encoding::<Q>(
+            Codec::Uper,
+            &(Q {
                 a: C { a: true },
-                b: 100.into()
-            },
-            &[0x40, 0x59, 0x00]
+                b: 100.into(),
+            }),
+            &[0x40, 0x59, 0x00],
         );
-        round_trip!(
-            uper,
-            R,
-            R {
+        round_trip_encoding::<R>(
+            Codec::Uper,
+            &(R {
                 a: D {
                     a: true,
-                    b: Some(DE { a: true })
+                    b: Some(DE { a: true }),
                 },
-                b: 100.into()
-            },
-            &[0xc0, 0x40, 0x60, 0x00, 0x59, 0x00]
+                b: 100.into(),
+            }),
+            &[0xc0, 0x40, 0x60, 0x00, 0x59, 0x00],
         );
-        round_trip!(
-            uper,
-            S,
-            S {
+        round_trip_encoding::<S>(
+            Codec::Uper,
+            &(S {
                 a: true,
                 b: Some(SSeq {
                     a: true,
-                    b: Some(true)
-                })
-            },
-            &[0xc0, 0x40, 0x5c, 0x00]
+                    b: Some(true),
+                }),
+            }),
+            &[0xc0, 0x40, 0x5c, 0x00],
         );
-        round_trip!(
-            uper,
-            T,
-            T {
-                a: Some(vec![<_>::default()])
-            },
-            &[0x80, 0x80]
+        round_trip_encoding::<T>(
+            Codec::Uper,
+            &(T {
+                a: Some(vec![<_>::default()]),
+            }),
+            &[0x80, 0x80],
         );
-        round_trip!(
-            uper,
-            T,
-            T {
-                a: Some(vec![T { a: Some(vec![]) }])
-            },
-            &[0x80, 0xc0, 0x00]
+        round_trip_encoding::<T>(
+            Codec::Uper,
+            &(T {
+                a: Some(vec![T { a: Some(vec![]) }]),
+            }),
+            &[0x80, 0xc0, 0x00],
         );
-        round_trip!(
-            uper,
-            V,
-            V {
+        round_trip_encoding::<V>(
+            Codec::Uper,
+            &(V {
                 a: Some(false),
                 ..<_>::default()
-            },
-            &[0x82, 0x80, 0x20, 0x00]
+            }),
+            &[0x82, 0x80, 0x20, 0x00],
         );
-        round_trip!(
-            uper,
-            V,
-            V {
+        round_trip_encoding::<V>(
+            Codec::Uper,
+            &(V {
                 b: Some(false),
                 ..<_>::default()
-            },
-            &[0x82, 0x40, 0x20, 0x00]
+            }),
+            &[0x82, 0x40, 0x20, 0x00],
         );
-        round_trip!(
-            uper,
-            V,
-            V {
+        round_trip_encoding::<V>(
+            Codec::Uper,
+            &(V {
                 c: Some(false),
                 ..<_>::default()
-            },
-            &[0x82, 0x20, 0x20, 0x00]
+            }),
+            &[0x82, 0x20, 0x20, 0x00],
         );
         // round_trip!(uper, W, W { a1: Some(true), ..<_>::default() }, &[0xd0, 0x60, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x00]);
     }
@@ -906,14 +930,13 @@ mod tests {
             }),
         };
 
-        round_trip!(
-            uper,
-            MySequenceVal,
-            value,
+        round_trip_encoding::<MySequenceVal>(
+            Codec::Uper,
+            &value,
             &[
                 0xc7, 0x5d, 0x39, 0x11, 0x69, 0x52, 0xb2, 0x07, 0x01, 0x80, 0x05, 0x96, 0x9a, 0x13,
-                0xe9, 0x54
-            ]
+                0xe9, 0x54,
+            ],
         );
     }
 
@@ -942,11 +965,10 @@ mod tests {
             world: 4,
         };
 
-        round_trip!(
-            uper,
-            TestSequence,
-            ext_value,
-            &[0xA0, 0x08, 0x10, 0x18, 0x20, 0x08, 0x0A, 0x00]
+        round_trip_encoding::<TestSequence>(
+            Codec::Uper,
+            &ext_value,
+            &[0xA0, 0x08, 0x10, 0x18, 0x20, 0x08, 0x0A, 0x00],
         );
     }
 
@@ -984,7 +1006,7 @@ mod tests {
                 choice: TestChoice::Number1(()),
             })),
         );
-        round_trip!(uper, TopLevel, test_value, &[8, 128]);
+        round_trip_encoding::<TopLevel>(Codec::Uper, &test_value, &[8, 128]);
     }
     #[test]
     fn deeply_nested_choice() {
@@ -996,11 +1018,10 @@ mod tests {
             High(Integer),
             Medium(Integer),
         }
-        round_trip!(
-            uper,
-            Choice,
-            Choice::Medium(333.into()),
-            &[128, 128, 83, 64]
+        round_trip_encoding::<Choice>(
+            Codec::Uper,
+            &Choice::Medium(333.into()),
+            &[128, 128, 83, 64],
         );
         #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
         #[rasn(choice, automatic_tags)]
@@ -1009,11 +1030,10 @@ mod tests {
             B(bool),
             C(Choice),
         }
-        round_trip!(
-            uper,
-            BoolChoice,
-            BoolChoice::C(Choice::Normal(333.into())),
-            &[128, 32, 20, 208]
+        round_trip_encoding::<BoolChoice>(
+            Codec::Uper,
+            &BoolChoice::C(Choice::Normal(333.into())),
+            &[128, 32, 20, 208],
         );
 
         #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
@@ -1028,32 +1048,28 @@ mod tests {
             A(TripleChoice),
             B(bool),
         }
-        round_trip!(
-            uper,
-            TripleChoice,
-            TripleChoice::B(BoolChoice::C(Choice::Normal(333.into()))),
-            &[192, 16, 10, 104]
+        round_trip_encoding::<TripleChoice>(
+            Codec::Uper,
+            &TripleChoice::B(BoolChoice::C(Choice::Normal(333.into()))),
+            &[192, 16, 10, 104],
         );
-        round_trip!(
-            uper,
-            FourthChoice,
-            FourthChoice::A(TripleChoice::B(BoolChoice::C(Choice::Normal(333.into())))),
-            &[96, 8, 5, 52]
+        round_trip_encoding::<FourthChoice>(
+            Codec::Uper,
+            &FourthChoice::A(TripleChoice::B(BoolChoice::C(Choice::Normal(333.into())))),
+            &[96, 8, 5, 52],
         );
     }
     #[test]
     fn test_object_identifier() {
-        round_trip!(
-            uper,
-            ObjectIdentifier,
-            ObjectIdentifier::new(vec![1, 2]).unwrap(),
-            &[0x01u8, 0x2a]
+        round_trip_encoding::<ObjectIdentifier>(
+            Codec::Uper,
+            &ObjectIdentifier::new(vec![1, 2]).unwrap(),
+            &[0x01u8, 0x2a],
         );
-        round_trip!(
-            uper,
-            ObjectIdentifier,
-            ObjectIdentifier::new(vec![1, 2, 3321]).unwrap(),
-            &[0x03u8, 0x2a, 0x99, 0x79]
+        round_trip_encoding::<ObjectIdentifier>(
+            Codec::Uper,
+            &ObjectIdentifier::new(vec![1, 2, 3321]).unwrap(),
+            &[0x03u8, 0x2a, 0x99, 0x79],
         );
         #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
         #[rasn(crate_root = "crate")]
@@ -1061,14 +1077,13 @@ mod tests {
             a: bool,
             b: ObjectIdentifier,
         }
-        round_trip!(
-            uper,
-            B,
-            B {
+        round_trip_encoding::<B>(
+            Codec::Uper,
+            &(B {
                 a: true,
-                b: ObjectIdentifier::new(vec![1, 2]).unwrap()
-            },
-            &[0x80, 0x95, 0x00]
+                b: ObjectIdentifier::new(vec![1, 2]).unwrap(),
+            }),
+            &[0x80, 0x95, 0x00],
         );
     }
     #[test]
@@ -1082,14 +1097,13 @@ mod tests {
             #[rasn(extension_addition)]
             pub omitted: Option<()>,
         }
-        round_trip!(
-            uper,
-            Omitted,
-            Omitted {
+        round_trip_encoding::<Omitted>(
+            Codec::Uper,
+            &(Omitted {
                 a: Some(OctetString::from_static(&[0x00, 0x01, 0x02])),
-                omitted: Some(())
-            },
-            &[192, 192, 0, 64, 128, 64, 0]
+                omitted: Some(()),
+            }),
+            &[192, 192, 0, 64, 128, 64, 0],
         );
     }
     #[test]
@@ -1102,15 +1116,14 @@ mod tests {
             pub is: Option<OctetString>,
             pub late: Option<Integer>,
         }
-        round_trip!(
-            uper,
-            SequenceOptionals,
-            SequenceOptionals {
+        round_trip_encoding::<SequenceOptionals>(
+            Codec::Uper,
+            &(SequenceOptionals {
                 it: 1.into(),
                 is: Some(OctetString::from_static(&[0x01, 0x02, 0x03])),
-                late: None
-            },
-            &[0b1000_0000, 0x40, 0x40, 0xc0, 0x40, 0x80, 0xc0]
+                late: None,
+            }),
+            &[0b1000_0000, 0x40, 0x40, 0xc0, 0x40, 0x80, 0xc0],
         );
         #[derive(AsnType, Decode, Encode, Clone, Debug, PartialEq, Eq)]
         #[non_exhaustive]
@@ -1121,15 +1134,14 @@ mod tests {
             #[rasn(extension_addition)]
             pub today: OctetString,
         }
-        round_trip!(
-            uper,
-            SequenceDuplicatesExtended,
-            SequenceDuplicatesExtended {
+        round_trip_encoding::<SequenceDuplicatesExtended>(
+            Codec::Uper,
+            &(SequenceDuplicatesExtended {
                 it: 1.into(),
                 is: Some(OctetString::from_static(&[0x01, 0x02, 0x03])),
                 late: None,
-                today: OctetString::from_static(&[0x01, 0x02, 0x03])
-            },
+                today: OctetString::from_static(&[0x01, 0x02, 0x03]),
+            }),
             &[
                 0b1100_0000,
                 0x20,
@@ -1143,8 +1155,8 @@ mod tests {
                 0x60,
                 0x20,
                 0x40,
-                0x60
-            ]
+                0x60,
+            ],
         );
     }
 
@@ -1273,14 +1285,13 @@ mod tests {
             ),
         ];
         for (case, expected) in UPER_UNALIGNED_CASES {
-            round_trip!(
-                uper,
-                Unaligned,
-                Unaligned {
+            round_trip_encoding::<Unaligned>(
+                Codec::Uper,
+                &(Unaligned {
                     offset_bits: 7,
-                    the_string: OctetString::from_static(case)
-                },
-                expected
+                    the_string: OctetString::from_static(case),
+                }),
+                expected,
             );
         }
     }
@@ -1298,14 +1309,13 @@ mod tests {
             pub the_string: OctetString,
         }
 
-        round_trip!(
-            uper,
-            Unaligned,
-            Unaligned {
+        round_trip_encoding::<Unaligned>(
+            Codec::Uper,
+            &(Unaligned {
                 offset_bits: 7,
-                the_string: OctetString::from_static(&[])
-            },
-            &[0b1110_0000, 0b0000_0000]
+                the_string: OctetString::from_static(&[]),
+            }),
+            &[0b1110_0000, 0b0000_0000],
         );
 
         #[derive(AsnType, Clone, Debug, Default, Decode, Encode, PartialEq)]
@@ -1317,14 +1327,13 @@ mod tests {
             pub the_string: OctetString,
         }
 
-        round_trip!(
-            uper,
-            UnalignedZeroLength,
-            UnalignedZeroLength {
+        round_trip_encoding::<UnalignedZeroLength>(
+            Codec::Uper,
+            &(UnalignedZeroLength {
                 offset_bits: 7,
-                the_string: OctetString::from_static(&[])
-            },
-            &[0b1110_0000]
+                the_string: OctetString::from_static(&[]),
+            }),
+            &[0b1110_0000],
         );
 
         #[derive(AsnType, Clone, Debug, Default, Decode, Encode, PartialEq)]
@@ -1334,13 +1343,12 @@ mod tests {
             pub the_string: OctetString,
         }
 
-        round_trip!(
-            uper,
-            AlignedZeroLength,
-            AlignedZeroLength {
-                the_string: OctetString::from_static(&[])
-            },
-            &[0x00]
+        round_trip_encoding::<AlignedZeroLength>(
+            Codec::Uper,
+            &(AlignedZeroLength {
+                the_string: OctetString::from_static(&[]),
+            }),
+            &[0x00],
         );
     }
     #[test]
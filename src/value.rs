@@ -0,0 +1,556 @@
+//! A dynamic representation of ASN.1 values.
+//!
+//! [`Value`] can hold a value of any ASN.1 type without a Rust type generated
+//! for it, which is useful when the schema of a message is only known at
+//! runtime. Values can be decoded without any schema from BER, CER and DER,
+//! or built by hand. Paired with a runtime [`Type`] description through
+//! [`TypedValue`], they can be encoded with every codec but AVN, and
+//! [`Type::decode`] decodes them with the same codecs. Types can also be
+//! parsed from the text of an ASN.1 module with [`Module`].
+//!
+//! ```rust
+//! use rasn::value::Value;
+//!
+//! let value: Value = rasn::ber::decode(&[0x30, 0x06, 0x02, 0x01, 0x05, 0x01, 0x01, 0xFF]).unwrap();
+//! assert_eq!(
+//!     value,
+//!     Value::SequenceOf(vec![Value::Integer(5.into()), Value::Boolean(true)])
+//! );
+//! assert_eq!(rasn::der::encode(&value).unwrap(), [0x30, 0x06, 0x02, 0x01, 0x05, 0x01, 0x01, 0xFF]);
+//! ```
+
+mod module;
+mod transcode;
+pub(crate) mod typed;
+
+pub use module::{Module, ParseError};
+pub use transcode::Unrepresentable;
+pub(crate) use transcode::transcode;
+pub use typed::{Addition, Component, Reference, Type, TypedValue};
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{
+    Codec, Decode, Encode,
+    de::Error as _,
+    enc::Error as _,
+    error::DecodeError,
+    types::{
+        AsnType, BitString, BmpString, Constraints, Constructed, Date, GeneralString,
+        GeneralizedTime, GraphicString, Ia5String, Identifier, Integer, NumericString,
+        ObjectIdentifier, OctetString, PrintableString, SetOf, Tag, TeletexString, UtcTime,
+        Utf8String, VisibleString, fields::Fields,
+    },
+};
+
+/// A dynamically typed ASN.1 value.
+///
+/// Without a schema a BER `SEQUENCE` can't be told apart from a `SEQUENCE OF`,
+/// so decoding produces [`Value::SequenceOf`] and [`Value::SetOf`] for every
+/// constructed universal value. Values with a tag that isn't known to this
+/// type are decoded as [`Value::Tagged`], wrapping an [`Value::OctetString`]
+/// with the contents of a primitive value or a [`Value::SequenceOf`] with the
+/// components of a constructed value, which encodes back to the same BER.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    /// A `BOOLEAN` value.
+    Boolean(bool),
+    /// An `INTEGER` value.
+    Integer(Integer),
+    /// A `BIT STRING` value.
+    BitString(BitString),
+    /// An `OCTET STRING` value.
+    OctetString(OctetString),
+    /// The `NULL` value.
+    Null,
+    /// An `OBJECT IDENTIFIER` value.
+    ObjectIdentifier(ObjectIdentifier),
+    /// A `REAL` value.
+    #[cfg(feature = "f64")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "f64")))]
+    Real(f64),
+    /// An `ENUMERATED` value, represented by its discriminant.
+    Enumerated(Integer),
+    /// A `UTF8String` value.
+    Utf8String(Utf8String),
+    /// A `NumericString` value.
+    NumericString(NumericString),
+    /// A `PrintableString` value.
+    PrintableString(PrintableString),
+    /// A `TeletexString` value.
+    TeletexString(TeletexString),
+    /// A `VisibleString` value.
+    VisibleString(VisibleString),
+    /// An `IA5String` value.
+    Ia5String(Ia5String),
+    /// A `GeneralString` value.
+    GeneralString(GeneralString),
+    /// A `GraphicString` value.
+    GraphicString(GraphicString),
+    /// A `BMPString` value.
+    BmpString(BmpString),
+    /// A `UniversalString` value.
+    UniversalString(Utf8String),
+    /// A `UTCTime` value.
+    UtcTime(UtcTime),
+    /// A `GeneralizedTime` value.
+    GeneralizedTime(GeneralizedTime),
+    /// A `DATE` value.
+    Date(Date),
+    /// A `SEQUENCE` value, as the identifiers and values of its present
+    /// components in order.
+    Sequence(Vec<(String, Value)>),
+    /// A `SEQUENCE OF` value.
+    SequenceOf(Vec<Value>),
+    /// A `SET` value, as the identifiers and values of its present components.
+    Set(Vec<(String, Value)>),
+    /// A `SET OF` value.
+    SetOf(SetOf<Value>),
+    /// A `CHOICE` value, as the identifier and value of the chosen
+    /// alternative.
+    Choice(String, Box<Value>),
+    /// A value encoded with `tag` in place of its own tag.
+    Tagged {
+        /// The tag of the value.
+        tag: Tag,
+        /// The tagged value.
+        value: Box<Value>,
+    },
+}
+
+impl Value {
+    /// Returns the tag the value is encoded with, [`Tag::EOC`] for a
+    /// [`Value::Choice`].
+    #[must_use]
+    pub fn tag(&self) -> Tag {
+        match self {
+            Self::Boolean(_) => Tag::BOOL,
+            Self::Integer(_) => Tag::INTEGER,
+            Self::BitString(_) => Tag::BIT_STRING,
+            Self::OctetString(_) => Tag::OCTET_STRING,
+            Self::Null => Tag::NULL,
+            Self::ObjectIdentifier(_) => Tag::OBJECT_IDENTIFIER,
+            #[cfg(feature = "f64")]
+            Self::Real(_) => Tag::REAL,
+            Self::Enumerated(_) => Tag::ENUMERATED,
+            Self::Utf8String(_) => Tag::UTF8_STRING,
+            Self::NumericString(_) => Tag::NUMERIC_STRING,
+            Self::PrintableString(_) => Tag::PRINTABLE_STRING,
+            Self::TeletexString(_) => Tag::TELETEX_STRING,
+            Self::VisibleString(_) => Tag::VISIBLE_STRING,
+            Self::Ia5String(_) => Tag::IA5_STRING,
+            Self::GeneralString(_) => Tag::GENERAL_STRING,
+            Self::GraphicString(_) => Tag::GRAPHIC_STRING,
+            Self::BmpString(_) => Tag::BMP_STRING,
+            Self::UniversalString(_) => Tag::UNIVERSAL_STRING,
+            Self::UtcTime(_) => Tag::UTC_TIME,
+            Self::GeneralizedTime(_) => Tag::GENERALIZED_TIME,
+            Self::Date(_) => Tag::DATE,
+            Self::Sequence(_) | Self::SequenceOf(_) => Tag::SEQUENCE,
+            Self::Set(_) | Self::SetOf(_) => Tag::SET,
+            Self::Choice(..) => Tag::EOC,
+            Self::Tagged { tag, .. } => *tag,
+        }
+    }
+
+    /// Decodes a value without a schema from a BER, CER or DER `decoder`.
+    fn decode_ber(decoder: &mut crate::ber::de::Decoder<'_>) -> Result<Self, DecodeError> {
+        let identifier = decoder.peek_identifier()?;
+        let tag = identifier.tag;
+        let constructed = identifier.is_constructed();
+
+        Ok(match tag {
+            Tag::BOOL => Self::Boolean(bool::decode_with_tag(decoder, tag)?),
+            Tag::INTEGER => Self::Integer(Integer::decode_with_tag(decoder, tag)?),
+            Tag::BIT_STRING => Self::BitString(BitString::decode_with_tag(decoder, tag)?),
+            Tag::OCTET_STRING => Self::OctetString(OctetString::decode_with_tag(decoder, tag)?),
+            Tag::NULL => {
+                <()>::decode_with_tag(decoder, tag)?;
+                Self::Null
+            }
+            Tag::OBJECT_IDENTIFIER => {
+                Self::ObjectIdentifier(ObjectIdentifier::decode_with_tag(decoder, tag)?)
+            }
+            Tag::ENUMERATED => Self::Enumerated(Integer::decode_with_tag(decoder, tag)?),
+            Tag::UTF8_STRING => Self::Utf8String(Utf8String::decode_with_tag(decoder, tag)?),
+            Tag::NUMERIC_STRING => {
+                Self::NumericString(NumericString::decode_with_tag(decoder, tag)?)
+            }
+            Tag::PRINTABLE_STRING => {
+                Self::PrintableString(PrintableString::decode_with_tag(decoder, tag)?)
+            }
+            Tag::TELETEX_STRING => {
+                Self::TeletexString(TeletexString::decode_with_tag(decoder, tag)?)
+            }
+            Tag::VISIBLE_STRING => {
+                Self::VisibleString(VisibleString::decode_with_tag(decoder, tag)?)
+            }
+            Tag::IA5_STRING => Self::Ia5String(Ia5String::decode_with_tag(decoder, tag)?),
+            Tag::GENERAL_STRING => {
+                Self::GeneralString(GeneralString::decode_with_tag(decoder, tag)?)
+            }
+            Tag::GRAPHIC_STRING => {
+                Self::GraphicString(GraphicString::decode_with_tag(decoder, tag)?)
+            }
+            Tag::BMP_STRING => Self::BmpString(BmpString::decode_with_tag(decoder, tag)?),
+            Tag::UNIVERSAL_STRING => {
+                Self::UniversalString(Utf8String::decode_with_tag(decoder, tag)?)
+            }
+            Tag::UTC_TIME => Self::UtcTime(UtcTime::decode_with_tag(decoder, tag)?),
+            Tag::GENERALIZED_TIME => {
+                Self::GeneralizedTime(GeneralizedTime::decode_with_tag(decoder, tag)?)
+            }
+            Tag::DATE => Self::Date(Date::decode_with_tag(decoder, tag)?),
+            Tag::SEQUENCE if constructed => {
+                Self::SequenceOf(Self::decode_ber_components(decoder, tag)?)
+            }
+            Tag::SET if constructed => {
+                Self::SetOf(SetOf::from_vec(Self::decode_ber_components(decoder, tag)?))
+            }
+            _ if constructed => Self::Tagged {
+                tag,
+                value: Box::new(Self::SequenceOf(Self::decode_ber_components(decoder, tag)?)),
+            },
+            _ => Self::Tagged {
+                tag,
+                value: Box::new(Self::OctetString(OctetString::decode_with_tag(
+                    decoder, tag,
+                )?)),
+            },
+        })
+    }
+
+    /// Decodes the components of a constructed value with `tag`.
+    fn decode_ber_components(
+        decoder: &mut crate::ber::de::Decoder<'_>,
+        tag: Tag,
+    ) -> Result<Vec<Self>, DecodeError> {
+        decoder.parse_constructed_contents(tag, true, |decoder| {
+            let mut components = Vec::new();
+            while !decoder.is_end_of_contents() {
+                components.push(Self::decode_ber(decoder)?);
+            }
+            Ok(components)
+        })
+    }
+}
+
+/// Returns whether `codec` only needs the tags of constructed values, and
+/// not the compile-time metadata of the type they belong to.
+fn is_tag_based(codec: Codec) -> bool {
+    matches!(codec, Codec::Ber | Codec::Cer | Codec::Der)
+}
+
+/// The component list used for constructed values whose type is only known at
+/// runtime, for codecs that don't read it.
+struct Dynamic;
+
+impl Constructed for Dynamic {
+    const FIELDS: Fields<0> = Fields::from_static([]);
+}
+
+/// Encodes `components` as the components of a `SEQUENCE` or `SET` value.
+fn encode_components<'e, 'b, 'v, E, V>(
+    encoder: &'b mut E,
+    tag: Tag,
+    is_set: bool,
+    components: impl IntoIterator<Item = &'v V>,
+    identifier: Identifier,
+) -> Result<(), E::Error>
+where
+    E: crate::Encoder<'e>,
+    V: Encode + 'v,
+{
+    let scope = |encoder: &mut E::AnyEncoder<'b, 0, 0>| {
+        components
+            .into_iter()
+            .try_for_each(|component| component.encode(encoder))
+    };
+
+    if is_set {
+        encoder
+            .encode_set::<0, 0, Dynamic, _>(tag, scope, identifier)
+            .map(drop)
+    } else {
+        encoder
+            .encode_sequence::<0, 0, Dynamic, _>(tag, scope, identifier)
+            .map(drop)
+    }
+}
+
+impl Eq for Value {}
+
+impl core::hash::Hash for Value {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // Only hash the kind of value, as `REAL` values aren't hashable.
+        core::mem::discriminant(self).hash(state);
+    }
+}
+
+impl AsnType for Value {
+    const TAG: Tag = Tag::EOC;
+}
+
+impl Encode for Value {
+    fn encode<'b, E: crate::Encoder<'b>>(&self, encoder: &mut E) -> Result<(), E::Error> {
+        match self {
+            Self::Choice(_, value) if is_tag_based(encoder.codec()) => value.encode(encoder),
+            _ => self.encode_with_tag_and_constraints(
+                encoder,
+                self.tag(),
+                Constraints::default(),
+                Identifier::EMPTY,
+            ),
+        }
+    }
+
+    fn encode_with_tag_and_constraints<'b, E: crate::Encoder<'b>>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<(), E::Error> {
+        let codec = encoder.codec();
+        let requires_type = |kind: &str| {
+            E::Error::custom(
+                alloc::format!(
+                    "{kind} values can only be encoded without a type description with BER, CER or DER"
+                ),
+                codec,
+            )
+        };
+
+        match self {
+            Self::Boolean(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::Integer(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::BitString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::OctetString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::Null => encoder.encode_null(tag, identifier).map(drop),
+            Self::ObjectIdentifier(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            #[cfg(feature = "f64")]
+            Self::Real(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::Enumerated(value) if is_tag_based(codec) => encoder
+                .encode_integer(tag, constraints, value, identifier)
+                .map(drop),
+            Self::Enumerated(_) => Err(requires_type("ENUMERATED")),
+            Self::Utf8String(value) | Self::UniversalString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::NumericString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::PrintableString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::TeletexString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::VisibleString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::Ia5String(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::GeneralString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::GraphicString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::BmpString(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::UtcTime(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::GeneralizedTime(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::Date(value) => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+            Self::Sequence(components) if is_tag_based(codec) => encode_components(
+                encoder,
+                tag,
+                false,
+                components.iter().map(|(_, value)| value),
+                identifier,
+            ),
+            Self::Set(components) if is_tag_based(codec) => encode_components(
+                encoder,
+                tag,
+                true,
+                components.iter().map(|(_, value)| value),
+                identifier,
+            ),
+            Self::Sequence(_) => Err(requires_type("SEQUENCE")),
+            Self::Set(_) => Err(requires_type("SET")),
+            Self::SequenceOf(values) => encoder
+                .encode_sequence_of(tag, values, constraints, identifier)
+                .map(drop),
+            Self::SetOf(values) => encoder
+                .encode_set_of(tag, values, constraints, identifier)
+                .map(drop),
+            Self::Choice(..) if !is_tag_based(codec) => Err(requires_type("CHOICE")),
+            // A CHOICE can't be implicitly tagged, so the tag is always explicit.
+            Self::Choice(_, value) => encoder
+                .encode_explicit_prefix(tag, value.as_ref(), identifier)
+                .map(drop),
+            Self::Tagged { value, .. } => {
+                value.encode_with_tag_and_constraints(encoder, tag, constraints, identifier)
+            }
+        }
+    }
+}
+
+impl Decode for Value {
+    fn decode<D: crate::Decoder>(decoder: &mut D) -> Result<Self, D::Error> {
+        Self::decode_with_tag_and_constraints(decoder, Tag::EOC, Constraints::default())
+    }
+
+    fn decode_with_tag_and_constraints<D: crate::Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        _: Constraints,
+    ) -> Result<Self, D::Error> {
+        let codec = decoder.codec();
+        let options = match codec {
+            Codec::Ber => crate::ber::de::DecoderOptions::ber(),
+            Codec::Cer => crate::ber::de::DecoderOptions::cer(),
+            Codec::Der => crate::ber::de::DecoderOptions::der(),
+            _ => {
+                return Err(D::Error::custom(
+                    "values can only be decoded without a type description from BER, CER or DER",
+                    codec,
+                ));
+            }
+        };
+
+        let any = decoder.decode_any(Tag::EOC)?;
+        let value = Self::decode_ber(&mut crate::ber::de::Decoder::new(any.as_bytes(), options))?;
+
+        if tag != Tag::EOC && value.tag() != tag {
+            return Err(D::Error::custom(
+                alloc::format!("expected a value with tag {tag}, found {}", value.tag()),
+                codec,
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn schema_less_round_trip() {
+        // SEQUENCE { INTEGER 5, [0] EXPLICIT BOOLEAN TRUE, [1] IMPLICIT OCTET STRING,
+        //            SET { NULL, UTF8String }, OBJECT IDENTIFIER }
+        let encoded = [
+            0x30, 0x19, 0x02, 0x01, 0x05, 0xA0, 0x03, 0x01, 0x01, 0xFF, 0x81, 0x02, 0xCA, 0xFE,
+            0x31, 0x06, 0x05, 0x00, 0x0C, 0x02, b'h', b'i', 0x06, 0x03, 0x2A, 0x86, 0x48,
+        ];
+
+        let value: Value = crate::der::decode(&encoded).unwrap();
+        assert_eq!(
+            value,
+            Value::SequenceOf(vec![
+                Value::Integer(5.into()),
+                Value::Tagged {
+                    tag: Tag::new_context(0),
+                    value: Box::new(Value::SequenceOf(vec![Value::Boolean(true)])),
+                },
+                Value::Tagged {
+                    tag: Tag::new_context(1),
+                    value: Box::new(Value::OctetString(vec![0xCA, 0xFE].into())),
+                },
+                Value::SetOf(SetOf::from_vec(vec![
+                    Value::Utf8String("hi".into()),
+                    Value::Null,
+                ])),
+                Value::ObjectIdentifier(ObjectIdentifier::new(vec![1, 2, 840]).unwrap()),
+            ])
+        );
+        assert_eq!(crate::der::encode(&value).unwrap(), encoded);
+        assert_eq!(crate::ber::decode::<Value>(&encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn indefinite_length() {
+        let encoded = [
+            0x30, 0x80, 0x02, 0x01, 0x01, 0xA1, 0x80, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let value: Value = crate::ber::decode(&encoded).unwrap();
+        assert_eq!(
+            value,
+            Value::SequenceOf(vec![
+                Value::Integer(1.into()),
+                Value::Tagged {
+                    tag: Tag::new_context(1),
+                    value: Box::new(Value::SequenceOf(vec![Value::Null])),
+                },
+            ])
+        );
+        assert_eq!(
+            crate::der::encode(&value).unwrap(),
+            [0x30, 0x07, 0x02, 0x01, 0x01, 0xA1, 0x02, 0x05, 0x00]
+        );
+    }
+
+    #[test]
+    fn constructed_values() {
+        let value = Value::Sequence(vec![
+            ("id".into(), Value::Integer(7.into())),
+            (
+                "kind".into(),
+                Value::Choice(
+                    "name".into(),
+                    Box::new(Value::Tagged {
+                        tag: Tag::new_context(2),
+                        value: Box::new(Value::Ia5String("x".try_into().unwrap())),
+                    }),
+                ),
+            ),
+        ]);
+        let encoded = crate::der::encode(&value).unwrap();
+        assert_eq!(encoded, [0x30, 0x06, 0x02, 0x01, 0x07, 0x82, 0x01, b'x']);
+        assert!(crate::uper::encode(&value).is_err());
+        assert!(crate::uper::decode::<Value>(&encoded).is_err());
+    }
+
+    #[test]
+    fn primitives_with_other_codecs() {
+        let value = Value::SequenceOf(vec![Value::Integer(1.into()), Value::Integer(300.into())]);
+        let expected = vec![Integer::from(1), Integer::from(300)];
+
+        assert_eq!(
+            crate::uper::encode(&value).unwrap(),
+            crate::uper::encode(&expected).unwrap()
+        );
+        assert_eq!(
+            crate::oer::encode(&value).unwrap(),
+            crate::oer::encode(&expected).unwrap()
+        );
+        assert_eq!(
+            crate::jer::encode(&value).unwrap(),
+            crate::jer::encode(&expected).unwrap()
+        );
+    }
+}
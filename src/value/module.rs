@@ -0,0 +1,2507 @@
+//! Parsing ASN.1 modules into runtime type descriptions.
+
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::fmt;
+
+use super::{Addition, Component, Reference, Type, Value};
+use crate::{
+    Codec,
+    de::Error as _,
+    enc::Error as _,
+    error::{DecodeError, EncodeError},
+    types::{
+        BitString, BmpString, Class, Constraint, Constraints, Extensible, GeneralString,
+        GraphicString, Ia5String, Integer, NumericString, ObjectIdentifier, OctetString,
+        PrintableString, SetOf, Tag, TeletexString, VisibleString,
+        constraints::{self, Bounded, CharacterRange, PermittedAlphabet},
+    },
+};
+
+/// An ASN.1 module parsed at runtime.
+///
+/// Parsing a module turns each of its type assignments into a [`Type`], which
+/// can then encode and decode [`Value`]s without generating any Rust code,
+/// similar to what `asn1tools` does for Python.
+///
+/// ```rust
+/// use rasn::{Codec, value::{Module, Value}};
+///
+/// let module = Module::parse(r#"
+///     Example DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+///         Message ::= SEQUENCE {
+///             id      INTEGER (0..255),
+///             retries INTEGER DEFAULT 3
+///         }
+///     END
+/// "#).unwrap();
+///
+/// let message = Value::Sequence(vec![("id".into(), Value::Integer(5.into()))]);
+/// let encoded = module.encode(Codec::Der, "Message", &message).unwrap();
+/// assert_eq!(encoded, [0x30, 0x03, 0x80, 0x01, 0x05]);
+/// assert_eq!(module.decode(Codec::Der, "Message", &encoded).unwrap(), message);
+/// ```
+///
+/// The parser covers the type notation of X.680 that a [`Type`] can describe:
+/// builtin types, tags and tagging environments, extension markers, `DEFAULT`
+/// values, `COMPONENTS OF`, named numbers, and value, size and permitted
+/// alphabet constraints. Value assignments are parsed as well and can be
+/// looked up with [`Module::value`]. Other definitions, such as information
+/// object classes and parameterized types, are skipped and can't be
+/// referenced by the types of the module.
+///
+/// A permitted alphabet is made of the characters of strings and of ranges
+/// such as `"A".."Z"`, which PER encodes strings with. As constraints only
+/// hold alphabets that live as long as the program, the alphabets of a module
+/// are leaked, so a module is best parsed once and kept.
+///
+/// Each type assignment is parsed once, and the types that reference it share
+/// it through a [`Type::Reference`], so types can be recursive as long as
+/// every cycle goes through a `SEQUENCE`, `SET`, `SEQUENCE OF`, `SET OF` or a
+/// tagged `CHOICE` alternative.
+///
+/// Values of the parsed types are encoded and decoded with every codec but
+/// AVN, and XER names the outermost element after the type assignment. As
+/// with [`Type`], open types can only be used with BER, CER and DER.
+#[derive(Debug, Clone)]
+pub struct Module {
+    name: String,
+    /// The type assignments, sorted by name.
+    types: Arc<Vec<(String, Type)>>,
+    values: BTreeMap<String, Value>,
+}
+
+impl Module {
+    /// Parses the ASN.1 module in `source`.
+    ///
+    /// # Errors
+    /// Returns an error if the module is malformed, or if one of its types
+    /// references a type that isn't defined in the module or isn't supported.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        Parser::new(tokenize(source)?).parse_module()
+    }
+
+    /// Returns the name of the module.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type assigned to `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Type> {
+        self.types
+            .binary_search_by(|(assigned, _)| assigned.as_str().cmp(name))
+            .ok()
+            .map(|index| &self.types[index].1)
+    }
+
+    /// Returns the value assigned to `name`.
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    /// Returns the names and types of the module's type assignments.
+    pub fn types(&self) -> impl Iterator<Item = (&str, &Type)> {
+        self.types.iter().map(|(name, ty)| (name.as_str(), ty))
+    }
+
+    /// Encodes `value` as a value of the type assigned to `name` with `codec`.
+    ///
+    /// # Errors
+    /// Returns an error if the module has no type `name`, if `value` isn't a
+    /// value of the type, or if the codec can't encode it.
+    pub fn encode(&self, codec: Codec, name: &str, value: &Value) -> Result<Vec<u8>, EncodeError> {
+        self.get(name)
+            .ok_or_else(|| {
+                EncodeError::custom(
+                    format!("type `{name}` is not defined in module `{}`", self.name),
+                    codec,
+                )
+            })?
+            .encode_named(codec, value, Cow::Owned(name.into()))
+    }
+
+    /// Decodes a value of the type assigned to `name` from `input` with
+    /// `codec`.
+    ///
+    /// # Errors
+    /// Returns an error if the module has no type `name`, if `input` isn't a
+    /// valid encoding of a value of the type, or if `codec` is AVN.
+    pub fn decode(&self, codec: Codec, name: &str, input: &[u8]) -> Result<Value, DecodeError> {
+        self.get(name)
+            .ok_or_else(|| {
+                DecodeError::custom(
+                    format!("type `{name}` is not defined in module `{}`", self.name),
+                    codec,
+                )
+            })?
+            .decode(codec, input)
+    }
+}
+
+/// An error parsing an ASN.1 module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// Returns the line of the module where the error occurred, starting at 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the description of the error.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+type Result<T, E = ParseError> = core::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    /// Identifiers, references and keywords.
+    Word(String),
+    Number(String),
+    CString(String),
+    BString(String),
+    HString(String),
+    Symbol(&'static str),
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    line: usize,
+}
+
+const SYMBOLS: &[&str] = &[
+    "::=", "...", "..", "[[", "]]", "{", "}", "(", ")", "[", "]", ",", ";", "|", "^", "<", ">",
+    ":", "-", ".", "@", "!", "&", "=",
+];
+
+/// Splits `source` into tokens, removing white-space and comments.
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let error = |line, message: String| Err(ParseError { line, message });
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while let Some(&c) = chars.get(i) {
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        } else if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c == '-' && next == Some('-') {
+            // A comment ends at the end of the line or at the next `--`.
+            i += 2;
+            while let Some(&c) = chars.get(i) {
+                if c == '\n' {
+                    break;
+                }
+                i += 1;
+                if c == '-' && chars.get(i) == Some(&'-') {
+                    i += 1;
+                    break;
+                }
+            }
+            continue;
+        } else if c == '/' && next == Some('*') {
+            let start = line;
+            let mut depth = 0;
+            loop {
+                match (chars.get(i), chars.get(i + 1)) {
+                    (None, _) => return error(start, "unterminated comment".into()),
+                    (Some('/'), Some('*')) => {
+                        depth += 1;
+                        i += 2;
+                    }
+                    (Some('*'), Some('/')) => {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    (Some(c), _) => {
+                        if *c == '\n' {
+                            line += 1;
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            continue;
+        }
+
+        let (start, start_line) = (i, line);
+        let kind = if c.is_ascii_alphabetic() {
+            while chars.get(i).is_some_and(|c| {
+                c.is_ascii_alphanumeric()
+                    || (*c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_alphanumeric))
+            }) {
+                i += 1;
+            }
+            Kind::Word(chars[start..i].iter().collect())
+        } else if c.is_ascii_digit() {
+            let digits = |i: &mut usize| {
+                while chars.get(*i).is_some_and(char::is_ascii_digit) {
+                    *i += 1;
+                }
+            };
+            digits(&mut i);
+            if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+                i += 1;
+                digits(&mut i);
+            }
+            if matches!(chars.get(i), Some('e' | 'E'))
+                && (chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                    || (chars.get(i + 1) == Some(&'-')
+                        && chars.get(i + 2).is_some_and(char::is_ascii_digit)))
+            {
+                i += 2;
+                digits(&mut i);
+            }
+            Kind::Number(chars[start..i].iter().collect())
+        } else if c == '"' {
+            let mut string = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return error(line, "unterminated string".into()),
+                    Some('"') if chars.get(i + 1) == Some(&'"') => {
+                        string.push('"');
+                        i += 2;
+                    }
+                    Some('"') => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&c) => {
+                        if c == '\n' {
+                            line += 1;
+                        }
+                        string.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            Kind::CString(string)
+        } else if c == '\'' {
+            let mut string = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return error(line, "unterminated string".into()),
+                    Some('\'') => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&c) => {
+                        if c == '\n' {
+                            line += 1;
+                        } else if !c.is_whitespace() {
+                            string.push(c);
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            i += 1;
+            match chars.get(i - 1) {
+                Some('B') if string.chars().all(|c| matches!(c, '0' | '1')) => {
+                    Kind::BString(string)
+                }
+                Some('H') if string.chars().all(|c| c.is_ascii_hexdigit()) => Kind::HString(string),
+                _ => {
+                    return error(
+                        line,
+                        format!("invalid binary or hexadecimal string `'{string}'`"),
+                    );
+                }
+            }
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| {
+            symbol
+                .chars()
+                .enumerate()
+                .all(|(j, s)| chars.get(i + j) == Some(&s))
+        }) {
+            i += symbol.len();
+            Kind::Symbol(symbol)
+        } else {
+            return error(line, format!("unexpected character `{c}`"));
+        };
+
+        tokens.push(Token {
+            kind,
+            line: start_line,
+        });
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tagging {
+    Explicit,
+    Implicit,
+    Automatic,
+}
+
+/// The position of the right-hand side of an assignment.
+#[derive(Debug, Clone, Copy)]
+enum Assignment {
+    Type(usize),
+    Value { ty: usize, value: usize },
+    Unsupported(&'static str),
+}
+
+/// The value and size bounds and the permitted alphabet of a constraint,
+/// `None` when unconstrained.
+#[derive(Debug, Default, Clone)]
+struct Bounds {
+    value: Option<(Option<i128>, Option<i128>)>,
+    value_extensible: bool,
+    size: Option<(Option<i128>, Option<i128>)>,
+    size_extensible: bool,
+    /// The permitted characters, as ascending and disjoint ranges.
+    alphabet: Option<Vec<(u32, u32)>>,
+}
+
+impl Bounds {
+    /// The smallest bounds containing both `self` and `other`.
+    fn union(self, other: Self) -> Self {
+        let hull = |a: Option<(Option<i128>, Option<i128>)>, b: Option<_>| {
+            let ((a_start, a_end), (b_start, b_end)) = (a?, b?);
+            Some((
+                a_start.zip(b_start).map(|(a, b)| a.min(b)),
+                a_end.zip(b_end).map(|(a, b)| a.max(b)),
+            ))
+        };
+        Self {
+            value: hull(self.value, other.value),
+            value_extensible: self.value_extensible || other.value_extensible,
+            size: hull(self.size, other.size),
+            size_extensible: self.size_extensible || other.size_extensible,
+            alphabet: self
+                .alphabet
+                .zip(other.alphabet)
+                .map(|(a, b)| alphabet_union(a, b)),
+        }
+    }
+
+    /// The bounds contained in both `self` and `other`.
+    fn intersection(self, other: Self) -> Self {
+        let intersect = |a: Option<(Option<i128>, Option<i128>)>, b: Option<_>| match (a, b) {
+            (Some((a_start, a_end)), Some((b_start, b_end))) => Some((
+                a_start.max(b_start),
+                match (a_end, b_end) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                },
+            )),
+            (a, b) => a.or(b),
+        };
+        Self {
+            value: intersect(self.value, other.value),
+            value_extensible: self.value_extensible && other.value_extensible,
+            size: intersect(self.size, other.size),
+            size_extensible: self.size_extensible && other.size_extensible,
+            alphabet: match (self.alphabet, other.alphabet) {
+                (Some(a), Some(b)) => Some(alphabet_intersection(&a, &b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    fn into_constraints(self) -> Constraints {
+        let bounded = |(start, end): (Option<i128>, Option<i128>)| match (start, end) {
+            (Some(start), Some(end)) if start == end => Bounded::Single(start),
+            (None, None) => Bounded::None,
+            (start, end) => Bounded::Range { start, end },
+        };
+        let mut all = Vec::new();
+        if let Some(value) = self.value {
+            all.push(Constraint::Value(
+                Extensible::new(constraints::Value::new(bounded(value)))
+                    .set_extensible(self.value_extensible),
+            ));
+        }
+        if let Some(size) = self.size {
+            let size = match bounded(size) {
+                Bounded::None => Bounded::None,
+                Bounded::Single(size) => Bounded::Single(usize::try_from(size).unwrap_or(0)),
+                Bounded::Range { start, end } => Bounded::Range {
+                    start: start.map(|start| usize::try_from(start).unwrap_or(0)),
+                    end: end.and_then(|end| usize::try_from(end).ok()),
+                },
+            };
+            all.push(Constraint::Size(
+                Extensible::new(constraints::Size::new(size)).set_extensible(self.size_extensible),
+            ));
+        }
+        // An empty alphabet only permits the empty string, which PER can't
+        // index, so it isn't a PER-visible constraint.
+        if let Some(alphabet) = self.alphabet.filter(|alphabet| !alphabet.is_empty()) {
+            let mut ranges: Vec<_> = alphabet
+                .into_iter()
+                .map(|(start, end)| CharacterRange::new(start, end))
+                .collect();
+            CharacterRange::index(&mut ranges);
+            // Constraints only hold alphabets that live as long as the program.
+            all.push(Constraint::PermittedAlphabet(Extensible::new(
+                PermittedAlphabet::from_ranges(Box::leak(ranges.into_boxed_slice())),
+            )));
+        }
+        Constraints::new(&all)
+    }
+}
+
+/// The union of two alphabets of ascending and disjoint ranges, in the same
+/// form.
+fn alphabet_union(a: Vec<(u32, u32)>, b: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    let mut ranges = a;
+    ranges.extend(b);
+    ranges.sort_unstable();
+    let mut union: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match union.last_mut() {
+            Some((_, last)) if start <= last.saturating_add(1) => *last = (*last).max(end),
+            _ => union.push((start, end)),
+        }
+    }
+    union
+}
+
+/// The intersection of two alphabets of ascending and disjoint ranges, in the
+/// same form.
+fn alphabet_intersection(a: &[(u32, u32)], b: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut intersection = Vec::new();
+    for (a_start, a_end) in a {
+        for (b_start, b_end) in b {
+            let (start, end) = ((*a_start).max(*b_start), (*a_end).min(*b_end));
+            if start <= end {
+                intersection.push((start, end));
+            }
+        }
+    }
+    alphabet_union(intersection, Vec::new())
+}
+
+/// A recursive descent parser of ASN.1 modules.
+///
+/// Parsing happens in two passes. The first pass records where each
+/// assignment starts while parsing it without resolving references, and the
+/// second parses every assignment once. References to type assignments are
+/// left as [`Type::Reference`]s, and a referenced assignment is only parsed on
+/// demand when its definition is needed to parse a value or `COMPONENTS OF`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    tagging: Tagging,
+    extensibility_implied: bool,
+    /// Whether references are left unresolved, during the first pass.
+    dry_run: bool,
+    assignments: BTreeMap<String, Assignment>,
+    /// The index of each type assignment in the module's sorted types.
+    type_indices: BTreeMap<String, usize>,
+    types: BTreeMap<String, Type>,
+    values: BTreeMap<String, Value>,
+    resolving: Vec<String>,
+    /// The named numbers of `INTEGER` types and named bits of `BIT STRING`
+    /// types, used to parse values of these types.
+    named_numbers: BTreeMap<String, i128>,
+    named_bits: BTreeMap<String, i128>,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            tagging: Tagging::Explicit,
+            extensibility_implied: false,
+            dry_run: true,
+            assignments: BTreeMap::new(),
+            type_indices: BTreeMap::new(),
+            types: BTreeMap::new(),
+            values: BTreeMap::new(),
+            resolving: Vec::new(),
+            named_numbers: BTreeMap::new(),
+            named_bits: BTreeMap::new(),
+        }
+    }
+
+    fn peek_at(&self, offset: usize) -> &Kind {
+        self.tokens
+            .get(self.pos + offset)
+            .map_or(&Kind::End, |token| &token.kind)
+    }
+
+    fn peek(&self) -> &Kind {
+        self.peek_at(0)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |token| token.line)
+    }
+
+    fn bump(&mut self) -> Kind {
+        let kind = self.peek().clone();
+        self.pos += 1;
+        kind
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T> {
+        Err(ParseError {
+            line: self.line(),
+            message: message.into(),
+        })
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T> {
+        let found = match self.peek() {
+            Kind::Word(word) => format!("`{word}`"),
+            Kind::Number(number) => format!("`{number}`"),
+            Kind::CString(_) => "a string".into(),
+            Kind::BString(_) | Kind::HString(_) => "a binary string".into(),
+            Kind::Symbol(symbol) => format!("`{symbol}`"),
+            Kind::End => "the end of the module".into(),
+        };
+        self.error(format!("expected {expected}, found {found}"))
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Kind::Symbol(s) if *s == symbol)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{symbol}`"))
+        }
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Kind::Word(w) if w == word)
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let found = self.is_word(word);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<()> {
+        if self.eat_word(word) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{word}`"))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.peek() {
+            Kind::Word(word) => {
+                let word = word.clone();
+                self.pos += 1;
+                Ok(word)
+            }
+            _ => self.unexpected("an identifier"),
+        }
+    }
+
+    /// Skips a bracketed group of tokens, including nested groups.
+    fn skip_balanced(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.bump() {
+                Kind::Symbol("{" | "(" | "[" | "[[") => depth += 1,
+                Kind::Symbol("}" | ")" | "]" | "]]") => depth = depth.saturating_sub(1),
+                Kind::End => {
+                    self.pos -= 1;
+                    return self.unexpected("a closing bracket");
+                }
+                _ => {}
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Skips a value without interpreting it.
+    fn skip_value(&mut self) -> Result<()> {
+        match self.peek() {
+            Kind::Symbol("{") => self.skip_balanced(),
+            Kind::Symbol("-") => {
+                self.pos += 1;
+                match self.bump() {
+                    Kind::Number(_) => Ok(()),
+                    _ => {
+                        self.pos -= 1;
+                        self.unexpected("a number")
+                    }
+                }
+            }
+            Kind::Word(_) if matches!(self.peek_at(1), Kind::Symbol(":")) => {
+                self.pos += 2;
+                self.skip_value()
+            }
+            Kind::Word(_)
+            | Kind::Number(_)
+            | Kind::CString(_)
+            | Kind::BString(_)
+            | Kind::HString(_) => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => self.unexpected("a value"),
+        }
+    }
+
+    /// Returns an error for a construct that [`Type`] can't describe, or a
+    /// placeholder during the first pass.
+    fn unsupported(&self, what: &str) -> Result<Type> {
+        if self.dry_run {
+            Ok(Type::Any)
+        } else {
+            self.error(format!("{what} are not supported"))
+        }
+    }
+
+    fn parse_module(mut self) -> Result<Module> {
+        let name = self.identifier()?;
+        if self.is_symbol("{") {
+            self.skip_balanced()?;
+        }
+        self.expect_word("DEFINITIONS")?;
+        loop {
+            if self.eat_word("EXPLICIT") {
+                self.tagging = Tagging::Explicit;
+                self.expect_word("TAGS")?;
+            } else if self.eat_word("IMPLICIT") {
+                self.tagging = Tagging::Implicit;
+                self.expect_word("TAGS")?;
+            } else if self.eat_word("AUTOMATIC") {
+                self.tagging = Tagging::Automatic;
+                self.expect_word("TAGS")?;
+            } else if self.eat_word("EXTENSIBILITY") {
+                self.expect_word("IMPLIED")?;
+                self.extensibility_implied = true;
+            } else if matches!(self.peek(), Kind::Word(_))
+                && matches!(self.peek_at(1), Kind::Word(word) if word == "INSTRUCTIONS")
+            {
+                self.pos += 2;
+            } else {
+                break;
+            }
+        }
+        self.expect_symbol("::=")?;
+        self.expect_word("BEGIN")?;
+
+        for section in ["EXPORTS", "IMPORTS"] {
+            if self.eat_word(section) {
+                while !self.eat_symbol(";") {
+                    if self.bump() == Kind::End {
+                        self.pos -= 1;
+                        return self.unexpected("`;`");
+                    }
+                }
+            }
+        }
+
+        while !self.is_word("END") && !self.is_word("ENCODING-CONTROL") {
+            self.parse_assignment()?;
+        }
+
+        self.dry_run = false;
+        let assignments: Vec<_> = self
+            .assignments
+            .iter()
+            .map(|(name, assignment)| (name.clone(), *assignment))
+            .collect();
+        self.type_indices = assignments
+            .iter()
+            .filter(|(_, assignment)| matches!(assignment, Assignment::Type(_)))
+            .zip(0..)
+            .map(|((name, _), index)| (name.clone(), index))
+            .collect();
+        for (name, assignment) in assignments {
+            match assignment {
+                Assignment::Type(_) => {
+                    self.definition(&name)?;
+                }
+                Assignment::Value { ty, .. } => {
+                    // Values of information objects and other unsupported
+                    // types are skipped along with their types.
+                    let unsupported = matches!(
+                        &self.tokens[ty].kind,
+                        Kind::Word(reference)
+                            if matches!(self.assignments.get(reference), Some(Assignment::Unsupported(_)))
+                    );
+                    if !unsupported {
+                        self.resolve_value(&name)?;
+                    }
+                }
+                Assignment::Unsupported(_) => {}
+            }
+        }
+
+        self.check_cycles()?;
+        let types = Arc::new_cyclic(|definitions| {
+            let mut types: Vec<_> = self.types.into_iter().collect();
+            for (_, ty) in &mut types {
+                link(ty, definitions);
+            }
+            types
+        });
+
+        Ok(Module {
+            name,
+            types,
+            values: self.values,
+        })
+    }
+
+    /// Returns an error if a type is defined in terms of itself without a
+    /// constructed type in between, which can't be encoded or decoded.
+    ///
+    /// Following tags is only a problem outside of `CHOICE` types, and
+    /// following untagged alternatives is only a problem when they're reached
+    /// without going through tags, so both kinds of cycles are looked for
+    /// separately.
+    fn check_cycles(&self) -> Result<()> {
+        for through_tags in [true, false] {
+            for (name, ty) in &self.types {
+                let mut pending = Vec::new();
+                unguarded_references(ty, through_tags, &mut pending);
+                let mut seen: Vec<&str> = Vec::new();
+                while let Some(reference) = pending.pop() {
+                    if reference == name {
+                        let line = match self.assignments.get(name) {
+                            Some(Assignment::Type(position)) => self.tokens[*position].line,
+                            _ => self.line(),
+                        };
+                        return Err(ParseError {
+                            line,
+                            message: format!("`{name}` is defined in terms of itself"),
+                        });
+                    } else if !seen.contains(&reference) {
+                        seen.push(reference);
+                        if let Some(ty) = self.types.get(reference) {
+                            unguarded_references(ty, through_tags, &mut pending);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_assignment(&mut self) -> Result<()> {
+        let line = self.line();
+        let name = self.identifier()?;
+        let is_reference = name.starts_with(|c: char| c.is_ascii_uppercase());
+        if self.assignments.contains_key(&name) {
+            return Err(ParseError {
+                line,
+                message: format!("`{name}` is defined more than once"),
+            });
+        }
+
+        let assignment = if self.is_symbol("{") {
+            self.skip_balanced()?;
+            if !self.eat_symbol("::=") {
+                self.parse_type()?;
+                self.expect_symbol("::=")?;
+                self.skip_value()?;
+            } else if self.eat_word("CLASS") {
+                self.skip_class()?;
+            } else if is_reference {
+                self.parse_type()?;
+            } else {
+                self.skip_value()?;
+            }
+            Assignment::Unsupported("parameterized definitions")
+        } else if self.eat_symbol("::=") {
+            if !is_reference {
+                return self.unexpected("a type before `::=`");
+            } else if self.eat_word("CLASS") {
+                self.skip_class()?;
+                Assignment::Unsupported("information object classes")
+            } else {
+                let position = self.pos;
+                self.parse_type()?;
+                Assignment::Type(position)
+            }
+        } else {
+            let ty = self.pos;
+            self.parse_type()?;
+            self.expect_symbol("::=")?;
+            let value = self.pos;
+            self.skip_value()?;
+            if is_reference {
+                Assignment::Unsupported("value sets and object sets")
+            } else {
+                Assignment::Value { ty, value }
+            }
+        };
+
+        self.assignments.insert(name, assignment);
+        Ok(())
+    }
+
+    fn skip_class(&mut self) -> Result<()> {
+        self.skip_balanced()?;
+        if self.eat_word("WITH") {
+            self.expect_word("SYNTAX")?;
+            self.skip_balanced()?;
+        }
+        Ok(())
+    }
+
+    /// Returns where the type assigned to `name` starts.
+    fn type_position(&self, name: &str) -> Result<usize> {
+        match self.assignments.get(name) {
+            Some(Assignment::Type(position)) => Ok(*position),
+            Some(Assignment::Unsupported(what)) => {
+                self.error(format!("`{name}` can't be used, {what} are not supported"))
+            }
+            Some(Assignment::Value { .. }) => self.error(format!("`{name}` is not a type")),
+            None => self.error(format!("type `{name}` is not defined in the module")),
+        }
+    }
+
+    /// Returns a reference to the type assigned to `name`.
+    fn resolve_type(&self, name: &str) -> Result<Type> {
+        if self.dry_run {
+            return Ok(Type::Any);
+        }
+
+        self.type_position(name)?;
+        Ok(Type::Reference(Reference {
+            name: name.into(),
+            index: self.type_indices[name],
+            definitions: Weak::new(),
+        }))
+    }
+
+    /// Returns the definition of the type assigned to `name`, parsing it if
+    /// needed.
+    fn definition(&mut self, name: &str) -> Result<&Type> {
+        if !self.types.contains_key(name) {
+            let position = self.type_position(name)?;
+            if self.resolving.iter().any(|resolving| resolving == name) {
+                return self.error(format!("`{name}` is defined in terms of itself"));
+            }
+
+            self.resolving.push(name.into());
+            let saved = core::mem::replace(&mut self.pos, position);
+            let ty = self.parse_type();
+            self.pos = saved;
+            self.resolving.pop();
+
+            self.types.insert(name.into(), ty?);
+        }
+        Ok(&self.types[name])
+    }
+
+    /// Follows `ty` through references until it isn't one.
+    fn dereference(&mut self, mut ty: Type) -> Result<Type> {
+        while let Type::Reference(reference) = &ty {
+            ty = self.definition(&reference.name)?.clone();
+        }
+        Ok(ty)
+    }
+
+    /// Returns the value assigned to `name`, parsing it if needed.
+    fn resolve_value(&mut self, name: &str) -> Result<Value> {
+        if let Some(value) = self.values.get(name) {
+            return Ok(value.clone());
+        }
+
+        let (ty, value) = match self.assignments.get(name) {
+            Some(Assignment::Value { ty, value }) => (*ty, *value),
+            _ => return self.error(format!("value `{name}` is not defined in the module")),
+        };
+        if self.resolving.iter().any(|resolving| resolving == name) {
+            return self.error(format!("`{name}` is defined in terms of itself"));
+        }
+
+        self.resolving.push(name.into());
+        let saved = core::mem::replace(&mut self.pos, ty);
+        let result = self.parse_type().and_then(|ty| {
+            self.pos = value;
+            self.parse_value(&ty)
+        });
+        self.pos = saved;
+        self.resolving.pop();
+
+        let result = result?;
+        self.values.insert(name.into(), result.clone());
+        Ok(result)
+    }
+
+    fn parse_type(&mut self) -> Result<Type> {
+        if self.eat_symbol("[") {
+            let class = if self.eat_word("UNIVERSAL") {
+                Class::Universal
+            } else if self.eat_word("APPLICATION") {
+                Class::Application
+            } else if self.eat_word("PRIVATE") {
+                Class::Private
+            } else {
+                Class::Context
+            };
+            let number = self.parse_number()?;
+            let Ok(number) = u32::try_from(number) else {
+                return self.error(format!("{number} is not a valid tag number"));
+            };
+            self.expect_symbol("]")?;
+            let explicit = if self.eat_word("IMPLICIT") {
+                false
+            } else if self.eat_word("EXPLICIT") {
+                true
+            } else {
+                self.tagging == Tagging::Explicit
+            };
+
+            return Ok(Type::Tagged {
+                tag: Tag::new(class, number),
+                explicit,
+                inner: Box::new(self.parse_type()?),
+            });
+        }
+
+        let word = self.identifier()?;
+        let mut ty = match word.as_str() {
+            "BOOLEAN" => Type::Boolean,
+            "INTEGER" => {
+                if self.is_symbol("{") {
+                    let named_numbers = self.parse_named_numbers()?;
+                    self.named_numbers.extend(named_numbers);
+                }
+                Type::Integer
+            }
+            "BIT" => {
+                self.expect_word("STRING")?;
+                if self.is_symbol("{") {
+                    let named_bits = self.parse_named_numbers()?;
+                    self.named_bits.extend(named_bits);
+                }
+                Type::BitString
+            }
+            "OCTET" => {
+                self.expect_word("STRING")?;
+                Type::OctetString
+            }
+            "NULL" => Type::Null,
+            "OBJECT" => {
+                self.expect_word("IDENTIFIER")?;
+                Type::ObjectIdentifier
+            }
+            #[cfg(feature = "f64")]
+            "REAL" => Type::Real,
+            "ENUMERATED" => self.parse_enumerated()?,
+            "UTF8String" => Type::Utf8String,
+            "NumericString" => Type::NumericString,
+            "PrintableString" => Type::PrintableString,
+            "TeletexString" | "T61String" => Type::TeletexString,
+            "VisibleString" | "ISO646String" => Type::VisibleString,
+            "IA5String" => Type::Ia5String,
+            "GeneralString" => Type::GeneralString,
+            "GraphicString" => Type::GraphicString,
+            "BMPString" => Type::BmpString,
+            "UniversalString" => Type::UniversalString,
+            "ObjectDescriptor" => Type::Tagged {
+                tag: Tag::new(Class::Universal, 7),
+                explicit: false,
+                inner: Box::new(Type::GraphicString),
+            },
+            "UTCTime" => Type::UtcTime,
+            "GeneralizedTime" => Type::GeneralizedTime,
+            "DATE" => Type::Date,
+            "SEQUENCE" | "SET" => {
+                let set = word == "SET";
+                if self.is_symbol("{") {
+                    let (components, extensions) = self.parse_components()?;
+                    if set {
+                        Type::Set {
+                            components,
+                            extensions,
+                        }
+                    } else {
+                        Type::Sequence {
+                            components,
+                            extensions,
+                        }
+                    }
+                } else {
+                    self.parse_collection(set)?
+                }
+            }
+            "CHOICE" => {
+                let (alternatives, extensions) = self.parse_components()?;
+                // The alternatives of a group are alternatives of their own.
+                let extensions = extensions.map(|additions| {
+                    additions
+                        .into_iter()
+                        .flat_map(|addition| match addition {
+                            Addition::Component(alternative) => alloc::vec![*alternative],
+                            Addition::Group(alternatives) => alternatives,
+                        })
+                        .collect()
+                });
+                Type::Choice {
+                    alternatives,
+                    extensions,
+                }
+            }
+            "ANY" => {
+                if self.eat_word("DEFINED") {
+                    self.expect_word("BY")?;
+                    self.identifier()?;
+                }
+                Type::Any
+            }
+            "EMBEDDED" | "CHARACTER" => {
+                self.identifier()?;
+                self.unsupported(&format!("`{word}` types"))?
+            }
+            "RELATIVE-OID" | "OID-IRI" | "RELATIVE-OID-IRI" | "EXTERNAL" | "TIME"
+            | "TIME-OF-DAY" | "DATE-TIME" | "DURATION" | "VideotexString" => {
+                self.unsupported(&format!("`{word}` types"))?
+            }
+            #[cfg(not(feature = "f64"))]
+            "REAL" => self.unsupported("`REAL` types without the `f64` feature")?,
+            "INSTANCE" => {
+                self.expect_word("OF")?;
+                self.parse_type()?;
+                self.unsupported("`INSTANCE OF` types")?
+            }
+            _ if !word.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                self.pos -= 1;
+                return self.unexpected("a type");
+            }
+            _ if self.eat_symbol(".") => {
+                if self.eat_symbol("&") {
+                    // Fields of information object classes are open types.
+                    self.identifier()?;
+                    while self.eat_symbol(".") {
+                        self.eat_symbol("&");
+                        self.identifier()?;
+                    }
+                    Type::Any
+                } else {
+                    let name = self.identifier()?;
+                    self.resolve_type(&name)?
+                }
+            }
+            _ if self.is_symbol("{") => {
+                self.skip_balanced()?;
+                self.unsupported("parameterized types")?
+            }
+            _ => self.resolve_type(&word)?,
+        };
+
+        while self.is_symbol("(") {
+            let constraints = self.parse_constraint()?.into_constraints();
+            ty = Type::Constrained {
+                constraints,
+                inner: Box::new(ty),
+            };
+        }
+
+        Ok(ty)
+    }
+
+    /// Parses the rest of a `SEQUENCE OF` or `SET OF` type.
+    fn parse_collection(&mut self, set: bool) -> Result<Type> {
+        let bounds = if self.eat_word("SIZE") {
+            let bounds = self.parse_constraint()?;
+            Some(Bounds {
+                size: bounds.value,
+                size_extensible: bounds.value_extensible,
+                ..Bounds::default()
+            })
+        } else if self.is_symbol("(") {
+            Some(self.parse_constraint()?)
+        } else {
+            None
+        };
+        self.expect_word("OF")?;
+        if matches!(self.peek(), Kind::Word(word) if word.starts_with(|c: char| c.is_ascii_lowercase()))
+        {
+            self.pos += 1;
+        }
+
+        let item = Box::new(self.parse_type()?);
+        let ty = if set {
+            Type::SetOf(item)
+        } else {
+            Type::SequenceOf(item)
+        };
+        Ok(match bounds {
+            Some(bounds) => Type::Constrained {
+                constraints: bounds.into_constraints(),
+                inner: Box::new(ty),
+            },
+            None => ty,
+        })
+    }
+
+    /// Parses `{ name(number), ... }`.
+    fn parse_named_numbers(&mut self) -> Result<Vec<(String, i128)>> {
+        let mut named_numbers = Vec::new();
+        self.expect_symbol("{")?;
+        loop {
+            let name = self.identifier()?;
+            self.expect_symbol("(")?;
+            named_numbers.push((name, self.parse_number()?));
+            self.expect_symbol(")")?;
+            if !self.eat_symbol(",") {
+                self.expect_symbol("}")?;
+                return Ok(named_numbers);
+            }
+        }
+    }
+
+    fn parse_enumerated(&mut self) -> Result<Type> {
+        let mut root = Vec::new();
+        let mut additions = Vec::new();
+        let mut extensible = self.extensibility_implied;
+        self.expect_symbol("{")?;
+        loop {
+            if self.eat_symbol("...") {
+                extensible = true;
+                self.skip_exception()?;
+            } else {
+                let name = self.identifier()?;
+                let number = if self.eat_symbol("(") {
+                    let number = self.parse_number()?;
+                    self.expect_symbol(")")?;
+                    let Ok(number) = isize::try_from(number) else {
+                        return self.error(format!("{number} is too large for an enumeration"));
+                    };
+                    Some(number)
+                } else {
+                    None
+                };
+                if extensible {
+                    additions.push((name, number));
+                } else {
+                    root.push((name, number));
+                }
+            }
+            if !self.eat_symbol(",") {
+                self.expect_symbol("}")?;
+                break;
+            }
+        }
+
+        // Unnumbered root enumerations take the smallest numbers not used by
+        // the numbered ones, while unnumbered additions follow the largest
+        // number of the enumerations before them.
+        let mut used: Vec<isize> = root.iter().filter_map(|(_, number)| *number).collect();
+        let mut enumerations = Vec::new();
+        let mut next = 0;
+        for (name, number) in root {
+            let number = number.unwrap_or_else(|| {
+                while used.contains(&next) {
+                    next += 1;
+                }
+                used.push(next);
+                next
+            });
+            enumerations.push((name, number));
+        }
+        let mut extensions: Vec<(String, isize)> = Vec::new();
+        for (name, number) in additions {
+            let largest = enumerations
+                .iter()
+                .chain(&extensions)
+                .map(|(_, number)| *number)
+                .max();
+            let number = number.unwrap_or_else(|| largest.map_or(0, |largest| largest + 1));
+            extensions.push((name, number));
+        }
+
+        Ok(Type::Enumerated {
+            enumerations,
+            extensions: extensible.then_some(extensions),
+        })
+    }
+
+    /// Skips the exception specification after an extension marker.
+    fn skip_exception(&mut self) -> Result<()> {
+        if self.eat_symbol("!") {
+            if matches!(self.peek(), Kind::Word(word) if word.starts_with(|c: char| c.is_ascii_uppercase()))
+                && matches!(self.peek_at(1), Kind::Symbol(":"))
+            {
+                self.pos += 2;
+            }
+            self.skip_value()?;
+        }
+        Ok(())
+    }
+
+    /// Parses the components of a `SEQUENCE` or `SET`, or the alternatives of
+    /// a `CHOICE`, into the root components and the extension additions.
+    ///
+    /// The components of both root lists of a type with two extension markers
+    /// are root components, the ones of the second list following the ones of
+    /// the first.
+    fn parse_components(&mut self) -> Result<(Vec<Component>, Option<Vec<Addition>>)> {
+        // Components are kept in textual order, which automatic tags are
+        // numbered in, along with the addition each belongs to, if any.
+        let mut components = Vec::new();
+        let mut slots: Vec<Option<usize>> = Vec::new();
+        let mut groups: Vec<bool> = Vec::new();
+        let mut extensible = self.extensibility_implied;
+        let mut in_additions = false;
+        let mut tagged = false;
+        self.expect_symbol("{")?;
+        if !self.eat_symbol("}") {
+            loop {
+                if self.eat_symbol("...") {
+                    extensible = true;
+                    in_additions = !in_additions;
+                    self.skip_exception()?;
+                } else if self.eat_symbol("[[") {
+                    if matches!(self.peek(), Kind::Number(_))
+                        && matches!(self.peek_at(1), Kind::Symbol(":"))
+                    {
+                        self.pos += 2;
+                    }
+                    groups.push(true);
+                    loop {
+                        components.push(self.parse_component(&mut tagged)?);
+                        slots.push(Some(groups.len() - 1));
+                        if !self.eat_symbol(",") {
+                            self.expect_symbol("]]")?;
+                            break;
+                        }
+                    }
+                } else if self.is_word("COMPONENTS")
+                    && matches!(self.peek_at(1), Kind::Word(word) if word == "OF")
+                {
+                    self.pos += 2;
+                    let included = self.parse_type()?;
+                    // Only the root components of the type are included.
+                    match self.dereference(included)? {
+                        Type::Sequence {
+                            components: included,
+                            ..
+                        }
+                        | Type::Set {
+                            components: included,
+                            ..
+                        } => {
+                            slots.extend(included.iter().map(|_| None));
+                            components.extend(included);
+                        }
+                        _ if self.dry_run => {}
+                        _ => {
+                            return self
+                                .error("`COMPONENTS OF` must reference a SEQUENCE or SET type");
+                        }
+                    }
+                } else {
+                    components.push(self.parse_component(&mut tagged)?);
+                    slots.push(if in_additions {
+                        groups.push(false);
+                        Some(groups.len() - 1)
+                    } else {
+                        None
+                    });
+                }
+
+                if !self.eat_symbol(",") {
+                    self.expect_symbol("}")?;
+                    break;
+                }
+            }
+        }
+
+        if self.tagging == Tagging::Automatic && !tagged {
+            for (number, component) in (0..).zip(&mut components) {
+                let inner = core::mem::replace(&mut component.ty, Type::Null);
+                component.ty = Type::Tagged {
+                    tag: Tag::new(Class::Context, number),
+                    explicit: false,
+                    inner: Box::new(inner),
+                };
+            }
+        }
+
+        let mut root = Vec::new();
+        let mut additions: Vec<Vec<Component>> = groups.iter().map(|_| Vec::new()).collect();
+        for (component, slot) in components.into_iter().zip(slots) {
+            match slot {
+                Some(index) => additions[index].push(component),
+                None => root.push(component),
+            }
+        }
+        let additions = groups
+            .into_iter()
+            .zip(additions)
+            .filter_map(|(group, mut components)| {
+                if group {
+                    Some(Addition::Group(components))
+                } else {
+                    components
+                        .pop()
+                        .map(|component| Addition::Component(Box::new(component)))
+                }
+            })
+            .collect();
+
+        Ok((root, extensible.then_some(additions)))
+    }
+
+    fn parse_component(&mut self, tagged: &mut bool) -> Result<Component> {
+        let name = self.identifier()?;
+        *tagged |= self.is_symbol("[");
+        let ty = self.parse_type()?;
+        let mut component = Component::new(name, ty);
+        if self.eat_word("OPTIONAL") {
+            component.optional = true;
+        } else if self.eat_word("DEFAULT") {
+            component.default = Some(self.parse_value(&component.ty)?);
+        }
+        Ok(component)
+    }
+
+    /// Parses a constraint in parentheses.
+    fn parse_constraint(&mut self) -> Result<Bounds> {
+        self.expect_symbol("(")?;
+        let mut bounds = if self.is_symbol("...") {
+            Bounds::default()
+        } else {
+            self.parse_element_set()?
+        };
+        if self.eat_symbol(",") || self.is_symbol("...") {
+            self.expect_symbol("...")?;
+            self.skip_exception()?;
+            bounds.value_extensible = bounds.value.is_some();
+            bounds.size_extensible = bounds.size.is_some();
+            // Extensible permitted alphabets aren't PER-visible.
+            bounds.alphabet = None;
+            if self.eat_symbol(",") {
+                self.parse_element_set()?;
+            }
+        }
+        self.skip_exception()?;
+        self.expect_symbol(")")?;
+        Ok(bounds)
+    }
+
+    fn parse_element_set(&mut self) -> Result<Bounds> {
+        if self.eat_word("ALL") {
+            self.expect_word("EXCEPT")?;
+            self.parse_elements()?;
+            return Ok(Bounds::default());
+        }
+
+        let mut bounds = self.parse_intersections()?;
+        while self.eat_symbol("|") || self.eat_word("UNION") {
+            bounds = bounds.union(self.parse_intersections()?);
+        }
+        Ok(bounds)
+    }
+
+    fn parse_intersections(&mut self) -> Result<Bounds> {
+        let mut bounds = self.parse_elements()?;
+        loop {
+            if self.eat_symbol("^") || self.eat_word("INTERSECTION") {
+                bounds = bounds.intersection(self.parse_elements()?);
+            } else if self.eat_word("EXCEPT") {
+                self.parse_elements()?;
+            } else {
+                return Ok(bounds);
+            }
+        }
+    }
+
+    /// Parses a single element of a constraint. Elements other than value
+    /// ranges, size constraints and permitted alphabets don't constrain
+    /// encodings and are skipped.
+    fn parse_elements(&mut self) -> Result<Bounds> {
+        let ignored = Ok(Bounds::default());
+        match self.peek().clone() {
+            Kind::Symbol("(") => {
+                let bounds = self.parse_constraint()?;
+                Ok(Bounds {
+                    value_extensible: false,
+                    size_extensible: false,
+                    ..bounds
+                })
+            }
+            Kind::Symbol("{") => {
+                // Table constraints and other object set references.
+                while self.is_symbol("{") {
+                    self.skip_balanced()?;
+                }
+                ignored
+            }
+            Kind::Word(word) => match word.as_str() {
+                "SIZE" => {
+                    self.pos += 1;
+                    let bounds = self.parse_constraint()?;
+                    Ok(Bounds {
+                        size: bounds.value,
+                        size_extensible: bounds.value_extensible,
+                        ..Bounds::default()
+                    })
+                }
+                "FROM" => {
+                    self.pos += 1;
+                    Ok(Bounds {
+                        alphabet: self.parse_alphabet()?,
+                        ..Bounds::default()
+                    })
+                }
+                "WITH" => {
+                    self.pos += 1;
+                    self.identifier()?;
+                    self.skip_balanced()?;
+                    ignored
+                }
+                "CONTAINING" => {
+                    self.pos += 1;
+                    self.parse_type()?;
+                    if self.eat_word("ENCODED") {
+                        self.expect_word("BY")?;
+                        self.skip_value()?;
+                    }
+                    ignored
+                }
+                "ENCODED" => {
+                    self.pos += 1;
+                    self.expect_word("BY")?;
+                    self.skip_value()?;
+                    ignored
+                }
+                "PATTERN" => {
+                    self.pos += 1;
+                    self.skip_value()?;
+                    ignored
+                }
+                "CONSTRAINED" => {
+                    self.pos += 1;
+                    self.expect_word("BY")?;
+                    self.skip_balanced()?;
+                    ignored
+                }
+                "INCLUDES" => {
+                    self.pos += 1;
+                    self.parse_type()?;
+                    ignored
+                }
+                "MIN" => self.parse_range(None),
+                "TRUE" | "FALSE" | "NULL" | "PLUS-INFINITY" | "MINUS-INFINITY" => {
+                    self.pos += 1;
+                    ignored
+                }
+                _ if word.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                    self.parse_type()?;
+                    ignored
+                }
+                _ => {
+                    let start = self.parse_bound()?;
+                    self.parse_range(start)
+                }
+            },
+            Kind::Number(_) | Kind::Symbol("-") => {
+                let start = self.parse_bound()?;
+                self.parse_range(start)
+            }
+            Kind::CString(_) | Kind::BString(_) | Kind::HString(_) => {
+                self.pos += 1;
+                ignored
+            }
+            _ => self.unexpected("a constraint"),
+        }
+    }
+
+    /// Parses the constraint of a `FROM` permitted alphabet, `None` if it
+    /// doesn't restrict the characters. Extensible alphabets aren't
+    /// PER-visible, so they don't restrict them either.
+    fn parse_alphabet(&mut self) -> Result<Option<Vec<(u32, u32)>>> {
+        self.expect_symbol("(")?;
+        let mut alphabet = if self.is_symbol("...") {
+            None
+        } else {
+            self.parse_alphabet_union()?
+        };
+        if self.eat_symbol(",") || self.is_symbol("...") {
+            self.expect_symbol("...")?;
+            self.skip_exception()?;
+            if self.eat_symbol(",") {
+                self.parse_alphabet_union()?;
+            }
+            alphabet = None;
+        }
+        self.skip_exception()?;
+        self.expect_symbol(")")?;
+        Ok(alphabet)
+    }
+
+    fn parse_alphabet_union(&mut self) -> Result<Option<Vec<(u32, u32)>>> {
+        if self.eat_word("ALL") {
+            self.expect_word("EXCEPT")?;
+            self.parse_alphabet_element()?;
+            return Ok(None);
+        }
+
+        let mut alphabet = self.parse_alphabet_intersection()?;
+        while self.eat_symbol("|") || self.eat_word("UNION") {
+            let other = self.parse_alphabet_intersection()?;
+            alphabet = alphabet.zip(other).map(|(a, b)| alphabet_union(a, b));
+        }
+        Ok(alphabet)
+    }
+
+    fn parse_alphabet_intersection(&mut self) -> Result<Option<Vec<(u32, u32)>>> {
+        let mut alphabet = self.parse_alphabet_element()?;
+        loop {
+            if self.eat_symbol("^") || self.eat_word("INTERSECTION") {
+                alphabet = match (alphabet, self.parse_alphabet_element()?) {
+                    (Some(a), Some(b)) => Some(alphabet_intersection(&a, &b)),
+                    (a, b) => a.or(b),
+                };
+            } else if self.eat_word("EXCEPT") {
+                self.parse_alphabet_element()?;
+            } else {
+                return Ok(alphabet);
+            }
+        }
+    }
+
+    /// Parses a single element of a permitted alphabet, either the characters
+    /// of a string or a range of characters. Other elements, such as size
+    /// constraints and references to string values, don't restrict the
+    /// characters.
+    fn parse_alphabet_element(&mut self) -> Result<Option<Vec<(u32, u32)>>> {
+        match self.peek().clone() {
+            Kind::Symbol("(") => self.parse_alphabet(),
+            Kind::Symbol("{") => {
+                // Characters given as quadruples or tuples.
+                self.skip_balanced()?;
+                Ok(None)
+            }
+            Kind::CString(start) => {
+                self.pos += 1;
+                if !self.eat_symbol("..") {
+                    let characters = start.chars().map(|c| (c as u32, c as u32)).collect();
+                    return Ok(Some(alphabet_union(characters, Vec::new())));
+                }
+                let Kind::CString(end) = self.peek().clone() else {
+                    return self.unexpected("a character");
+                };
+                self.pos += 1;
+                let single = |string: &str| {
+                    let mut chars = string.chars();
+                    chars.next().filter(|_| chars.next().is_none())
+                };
+                match (single(&start), single(&end)) {
+                    (Some(start), Some(end)) if start <= end => {
+                        Ok(Some(alloc::vec![(start as u32, end as u32)]))
+                    }
+                    (Some(_), Some(_)) => self.error("a character range can't start after its end"),
+                    _ => self.error("the bounds of a character range must be single characters"),
+                }
+            }
+            Kind::Word(word) if word == "SIZE" => {
+                self.pos += 1;
+                self.parse_constraint()?;
+                Ok(None)
+            }
+            Kind::Word(word) if word.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                self.parse_type()?;
+                Ok(None)
+            }
+            Kind::Word(_) => {
+                self.pos += 1;
+                Ok(None)
+            }
+            _ => self.unexpected("a permitted alphabet"),
+        }
+    }
+
+    /// Parses the rest of a value range, starting with `MIN` or after its
+    /// lower bound.
+    fn parse_range(&mut self, start: Option<i128>) -> Result<Bounds> {
+        if self.eat_word("MIN") {
+            if self.eat_symbol("<") {
+                return self.error("`MIN<` is not a valid lower bound");
+            }
+        } else if self.eat_symbol("<") {
+            return self.parse_range(start.map(|start| start + 1));
+        }
+
+        if !self.eat_symbol("..") {
+            return Ok(Bounds {
+                value: start.map(|start| (Some(start), Some(start))),
+                ..Bounds::default()
+            });
+        }
+
+        let exclusive = self.eat_symbol("<");
+        let end = if self.eat_word("MAX") {
+            None
+        } else {
+            self.parse_bound()?
+                .map(|end| if exclusive { end - 1 } else { end })
+        };
+        Ok(Bounds {
+            value: Some((start, end)),
+            ..Bounds::default()
+        })
+    }
+
+    /// Parses the integer bound of a range, `None` if the value isn't an
+    /// integer.
+    fn parse_bound(&mut self) -> Result<Option<i128>> {
+        if let Kind::Word(name) = self.peek()
+            && !self.named_numbers.contains_key(name)
+        {
+            let name = name.clone();
+            self.pos += 1;
+            if self.dry_run {
+                return Ok(Some(0));
+            }
+            return Ok(match self.resolve_value(&name)? {
+                Value::Integer(integer) => i128::try_from(integer).ok(),
+                _ => None,
+            });
+        }
+        self.parse_number().map(Some)
+    }
+
+    /// Parses a signed integer, a named number, or a reference to an integer
+    /// value.
+    fn parse_number(&mut self) -> Result<i128> {
+        match self.bump() {
+            Kind::Number(number) => match number.parse() {
+                Ok(number) => Ok(number),
+                Err(_) => {
+                    self.pos -= 1;
+                    self.error(format!("`{number}` is not a supported integer"))
+                }
+            },
+            Kind::Symbol("-") => Ok(-self.parse_number()?),
+            Kind::Word(name) if self.named_numbers.contains_key(&name) => {
+                Ok(self.named_numbers[&name])
+            }
+            Kind::Word(_) if self.dry_run => Ok(0),
+            Kind::Word(name) => match self.resolve_value(&name)? {
+                Value::Integer(integer) => match i128::try_from(integer) {
+                    Ok(integer) => Ok(integer),
+                    Err(_) => self.error(format!("`{name}` is too large")),
+                },
+                _ => self.error(format!("`{name}` is not an integer")),
+            },
+            _ => {
+                self.pos -= 1;
+                self.unexpected("an integer")
+            }
+        }
+    }
+
+    /// Parses a value of `ty`.
+    fn parse_value(&mut self, ty: &Type) -> Result<Value> {
+        if self.dry_run {
+            self.skip_value()?;
+            return Ok(Value::Null);
+        }
+
+        if let Type::Reference(reference) = ty {
+            let ty = self.definition(&reference.name)?.clone();
+            return self.parse_value(&ty);
+        }
+
+        // Values of every type can be references to value assignments.
+        if let Kind::Word(name) = self.peek()
+            && matches!(self.assignments.get(name), Some(Assignment::Value { .. }))
+            && !matches!(self.peek_at(1), Kind::Symbol(":"))
+            && !matches!(ty, Type::Enumerated { enumerations, extensions } if enumerations.iter().chain(extensions.iter().flatten()).any(|(e, _)| e == name))
+        {
+            let name = name.clone();
+            self.pos += 1;
+            return self.resolve_value(&name);
+        }
+
+        let line = self.line();
+        let invalid = |message: String| Err(ParseError { line, message });
+        Ok(match ty {
+            Type::Tagged { inner, .. } | Type::Constrained { inner, .. } => {
+                return self.parse_value(inner);
+            }
+            Type::Boolean if self.eat_word("TRUE") => Value::Boolean(true),
+            Type::Boolean if self.eat_word("FALSE") => Value::Boolean(false),
+            Type::Integer => Value::Integer(Integer::from(self.parse_number()?)),
+            Type::Null if self.eat_word("NULL") => Value::Null,
+            #[cfg(feature = "f64")]
+            Type::Real => match self.bump() {
+                Kind::Word(word) if word == "PLUS-INFINITY" => Value::Real(f64::INFINITY),
+                Kind::Word(word) if word == "MINUS-INFINITY" => Value::Real(f64::NEG_INFINITY),
+                Kind::Word(word) if word == "NOT-A-NUMBER" => Value::Real(f64::NAN),
+                Kind::Symbol("-") => match self.parse_value(ty)? {
+                    Value::Real(real) => Value::Real(-real),
+                    _ => unreachable!("REAL values are parsed as reals"),
+                },
+                Kind::Number(number) => match number.parse() {
+                    Ok(real) => Value::Real(real),
+                    Err(_) => return invalid(format!("`{number}` is not a valid REAL value")),
+                },
+                _ => {
+                    self.pos -= 1;
+                    return self.unexpected("a REAL value");
+                }
+            },
+            Type::Enumerated {
+                enumerations,
+                extensions,
+            } => {
+                let name = self.identifier()?;
+                match enumerations
+                    .iter()
+                    .chain(extensions.iter().flatten())
+                    .find(|(enumeration, _)| *enumeration == name)
+                {
+                    Some((_, number)) => Value::Enumerated(Integer::from(*number)),
+                    None => return invalid(format!("`{name}` is not an enumeration of the type")),
+                }
+            }
+            Type::BitString => match self.bump() {
+                Kind::BString(bits) => Value::BitString(bits.chars().map(|c| c == '1').collect()),
+                Kind::HString(hex) => Value::BitString(BitString::from_vec(hex_to_bytes(&hex))),
+                Kind::Symbol("{") => {
+                    let mut bits = BitString::new();
+                    while !self.eat_symbol("}") {
+                        let name = self.identifier()?;
+                        let Some(bit) = self
+                            .named_bits
+                            .get(&name)
+                            .and_then(|bit| usize::try_from(*bit).ok())
+                        else {
+                            return invalid(format!("`{name}` is not a named bit"));
+                        };
+                        if bits.len() <= bit {
+                            bits.resize(bit + 1, false);
+                        }
+                        bits.set(bit, true);
+                        if !self.eat_symbol(",") {
+                            self.expect_symbol("}")?;
+                            break;
+                        }
+                    }
+                    Value::BitString(bits)
+                }
+                _ => {
+                    self.pos -= 1;
+                    return self.unexpected("a BIT STRING value");
+                }
+            },
+            Type::OctetString => match self.bump() {
+                Kind::BString(bits) => {
+                    let mut bits: BitString = bits.chars().map(|c| c == '1').collect();
+                    bits.set_uninitialized(false);
+                    Value::OctetString(OctetString::from(bits.into_vec()))
+                }
+                Kind::HString(hex) => Value::OctetString(OctetString::from(hex_to_bytes(&hex))),
+                _ => {
+                    self.pos -= 1;
+                    return self.unexpected("an OCTET STRING value");
+                }
+            },
+            Type::ObjectIdentifier => Value::ObjectIdentifier(self.parse_object_identifier()?),
+            Type::Utf8String
+            | Type::NumericString
+            | Type::PrintableString
+            | Type::TeletexString
+            | Type::VisibleString
+            | Type::Ia5String
+            | Type::GeneralString
+            | Type::GraphicString
+            | Type::BmpString
+            | Type::UniversalString
+            | Type::UtcTime
+            | Type::GeneralizedTime
+            | Type::Date => {
+                let Kind::CString(string) = self.bump() else {
+                    self.pos -= 1;
+                    return self.unexpected("a string");
+                };
+                match string_value(ty, string) {
+                    Some(value) => value,
+                    None => {
+                        return invalid(format!("the string is not a valid {} value", ty.name()));
+                    }
+                }
+            }
+            Type::Sequence { .. } | Type::Set { .. } => {
+                let mut values = Vec::new();
+                self.expect_symbol("{")?;
+                while !self.eat_symbol("}") {
+                    let name = self.identifier()?;
+                    let Some(component) = ty.all_components().find(|c| c.name == name) else {
+                        return invalid(format!("`{name}` is not a component of the type"));
+                    };
+                    values.push((name, self.parse_value(&component.ty)?));
+                    if !self.eat_symbol(",") {
+                        self.expect_symbol("}")?;
+                        break;
+                    }
+                }
+                if matches!(ty, Type::Set { .. }) {
+                    Value::Set(values)
+                } else {
+                    Value::Sequence(values)
+                }
+            }
+            Type::SequenceOf(item) | Type::SetOf(item) => {
+                let mut values = Vec::new();
+                self.expect_symbol("{")?;
+                while !self.eat_symbol("}") {
+                    values.push(self.parse_value(item)?);
+                    if !self.eat_symbol(",") {
+                        self.expect_symbol("}")?;
+                        break;
+                    }
+                }
+                if matches!(ty, Type::SetOf(_)) {
+                    Value::SetOf(SetOf::from_vec(values))
+                } else {
+                    Value::SequenceOf(values)
+                }
+            }
+            Type::Choice { .. } => {
+                let name = self.identifier()?;
+                self.expect_symbol(":")?;
+                let Some(alternative) = ty.alternatives().find(|a| a.name == name) else {
+                    return invalid(format!("`{name}` is not an alternative of the type"));
+                };
+                Value::Choice(name, Box::new(self.parse_value(&alternative.ty)?))
+            }
+            Type::Any => {
+                let ty = self.parse_type()?;
+                self.expect_symbol(":")?;
+                return self.parse_value(&ty);
+            }
+            _ => return self.unexpected(&format!("a {} value", ty.name())),
+        })
+    }
+
+    /// Parses an `OBJECT IDENTIFIER` value such as `{ iso(1) member-body(2) 840 }`.
+    fn parse_object_identifier(&mut self) -> Result<ObjectIdentifier> {
+        let line = self.line();
+        let mut arcs: Vec<String> = Vec::new();
+        self.expect_symbol("{")?;
+        while !self.eat_symbol("}") {
+            let arc = match self.bump() {
+                Kind::Number(number) => number,
+                Kind::Word(_) if self.eat_symbol("(") => {
+                    let Kind::Number(number) = self.bump() else {
+                        self.pos -= 1;
+                        return self.unexpected("an arc number");
+                    };
+                    self.expect_symbol(")")?;
+                    number
+                }
+                Kind::Word(name) if arcs.is_empty() => match well_known_arc(&name, None) {
+                    Some(arc) => arc.to_string(),
+                    None => match self.resolve_value(&name)? {
+                        Value::ObjectIdentifier(oid) => {
+                            arcs.extend(oid.arcs().map(|arc| arc.to_string()));
+                            continue;
+                        }
+                        _ => return self.error(format!("`{name}` is not an OBJECT IDENTIFIER")),
+                    },
+                },
+                Kind::Word(name) if arcs.len() == 1 => {
+                    match well_known_arc(&name, arcs[0].parse().ok()) {
+                        Some(arc) => arc.to_string(),
+                        None => return self.error(format!("`{name}` is not a known arc")),
+                    }
+                }
+                Kind::Word(name) => return self.error(format!("`{name}` is not a known arc")),
+                _ => {
+                    self.pos -= 1;
+                    return self.unexpected("an arc");
+                }
+            };
+            if !arc.bytes().all(|c| c.is_ascii_digit()) {
+                return self.error(format!("`{arc}` is not a valid arc"));
+            }
+            arcs.push(arc);
+        }
+
+        ObjectIdentifier::from_dotted(&arcs.join(".")).map_err(|_| ParseError {
+            line,
+            message: "the value is not a valid OBJECT IDENTIFIER".into(),
+        })
+    }
+}
+
+/// Points the references in `ty` to the module's `definitions`.
+fn link(ty: &mut Type, definitions: &Weak<Vec<(String, Type)>>) {
+    match ty {
+        Type::Reference(reference) => reference.definitions = definitions.clone(),
+        Type::Tagged { inner, .. }
+        | Type::Constrained { inner, .. }
+        | Type::SequenceOf(inner)
+        | Type::SetOf(inner) => link(inner, definitions),
+        Type::Sequence {
+            components,
+            extensions,
+        }
+        | Type::Set {
+            components,
+            extensions,
+        } => {
+            let additions = extensions
+                .iter_mut()
+                .flatten()
+                .flat_map(|addition| match addition {
+                    Addition::Component(component) => core::slice::from_mut(&mut **component),
+                    Addition::Group(components) => components,
+                });
+            for component in components.iter_mut().chain(additions) {
+                link(&mut component.ty, definitions);
+            }
+        }
+        Type::Choice {
+            alternatives,
+            extensions,
+        } => {
+            for alternative in alternatives
+                .iter_mut()
+                .chain(extensions.iter_mut().flatten())
+            {
+                link(&mut alternative.ty, definitions);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects the names of the types `ty` refers to without a constructed type
+/// in between, following tags when `through_tags` is set, and untagged
+/// `CHOICE` alternatives otherwise.
+fn unguarded_references<'a>(ty: &'a Type, through_tags: bool, references: &mut Vec<&'a str>) {
+    match ty {
+        Type::Reference(reference) => references.push(&reference.name),
+        Type::Constrained { inner, .. } => unguarded_references(inner, through_tags, references),
+        Type::Tagged { inner, .. } if through_tags => {
+            unguarded_references(inner, through_tags, references);
+        }
+        Type::Choice { .. } if !through_tags => {
+            for alternative in ty.alternatives() {
+                unguarded_references(&alternative.ty, through_tags, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the number of the arcs named in X.660, after the arc `parent` if
+/// any.
+fn well_known_arc(name: &str, parent: Option<u32>) -> Option<u32> {
+    Some(match (parent, name) {
+        (None, "itu-t" | "ccitt") => 0,
+        (None, "iso") => 1,
+        (None, "joint-iso-itu-t" | "joint-iso-ccitt") => 2,
+        (Some(0), "recommendation") => 0,
+        (Some(0), "question") => 1,
+        (Some(0), "administration") => 2,
+        (Some(0), "network-operator") => 3,
+        (Some(0), "identified-organization") => 4,
+        (Some(1), "standard") => 0,
+        (Some(1), "registration-authority") => 1,
+        (Some(1), "member-body") => 2,
+        (Some(1), "identified-organization") => 3,
+        _ => return None,
+    })
+}
+
+/// Converts a hexadecimal string to bytes, padding it with a zero digit.
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    let digits: Vec<u8> = hex
+        .chars()
+        .filter_map(|c| c.to_digit(16).and_then(|digit| u8::try_from(digit).ok()))
+        .collect();
+    digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
+
+/// Converts a character string to a value of the string or time type `ty`.
+fn string_value(ty: &Type, string: String) -> Option<Value> {
+    use crate::ber::de::Decoder;
+
+    Some(match ty {
+        Type::Utf8String => Value::Utf8String(string),
+        Type::UniversalString => Value::UniversalString(string),
+        Type::NumericString => Value::NumericString(NumericString::try_from(string).ok()?),
+        Type::PrintableString => Value::PrintableString(PrintableString::try_from(string).ok()?),
+        Type::TeletexString => Value::TeletexString(TeletexString::try_from(string).ok()?),
+        Type::VisibleString => Value::VisibleString(VisibleString::try_from(string).ok()?),
+        Type::Ia5String => Value::Ia5String(Ia5String::try_from(string).ok()?),
+        Type::GeneralString => Value::GeneralString(GeneralString::try_from(string).ok()?),
+        Type::GraphicString => Value::GraphicString(GraphicString::try_from(string).ok()?),
+        Type::BmpString => Value::BmpString(BmpString::try_from(string).ok()?),
+        Type::UtcTime => Value::UtcTime(Decoder::parse_any_utc_time_string(string).ok()?),
+        Type::GeneralizedTime => {
+            Value::GeneralizedTime(Decoder::parse_any_generalized_time_string(string).ok()?)
+        }
+        Type::Date => Value::Date(Decoder::parse_date_string(&string).ok()?),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsnType as _, Decode, Decoder as _, Encode, Encoder as _};
+    use alloc::vec;
+
+    const MODULE: &str = r#"
+        Example { iso(1) 2 3 } DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS Other FROM Elsewhere { 1 2 };
+
+            -- Types can be used before they're defined.
+            Message ::= SEQUENCE {
+                id       INTEGER (0..maxId),
+                kind     Kind DEFAULT normal,
+                tags     SEQUENCE SIZE (0..4) OF Tag OPTIONAL,
+                payload  Payload,
+                ...
+            }
+
+            Kind ::= ENUMERATED { normal, urgent(5), low, ... }
+            Tag ::= IA5String (SIZE (1..16))
+            Payload ::= CHOICE { raw OCTET STRING, count INTEGER }
+
+            Legacy ::= [APPLICATION 1] IMPLICIT SEQUENCE {
+                version [0] INTEGER { v1(0), v2(1) } DEFAULT v1,
+                name [1] EXPLICIT UTF8String
+            }
+
+            maxId INTEGER ::= 255
+            example-oid OBJECT IDENTIFIER ::= { iso member-body(2) 840 113549 }
+
+            CLASS-NAME ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+            Wrapper { Param } ::= SEQUENCE { param Param }
+        END
+    "#;
+
+    #[test]
+    fn parses_types_and_values() {
+        let module = Module::parse(MODULE).unwrap();
+        assert_eq!(module.name(), "Example");
+        assert_eq!(
+            module.types().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["Kind", "Legacy", "Message", "Payload", "Tag"]
+        );
+        assert_eq!(module.value("maxId"), Some(&Value::Integer(255.into())));
+        assert_eq!(
+            module.value("example-oid"),
+            Some(&Value::ObjectIdentifier(
+                ObjectIdentifier::new(vec![1, 2, 840, 113_549]).unwrap()
+            ))
+        );
+        assert!(matches!(
+            module.get("Kind"),
+            Some(Type::Enumerated { enumerations, extensions: Some(extensions) })
+                if *enumerations == [("normal".into(), 0), ("urgent".into(), 5), ("low".into(), 1)]
+                    && extensions.is_empty()
+        ));
+    }
+
+    #[test]
+    fn encodes_and_decodes() {
+        let module = Module::parse(MODULE).unwrap();
+        let message = Value::Sequence(vec![
+            ("id".into(), Value::Integer(7.into())),
+            (
+                "tags".into(),
+                Value::SequenceOf(vec![Value::Ia5String(Ia5String::try_from("a").unwrap())]),
+            ),
+            (
+                "payload".into(),
+                Value::Choice("count".into(), Box::new(Value::Integer(1.into()))),
+            ),
+        ]);
+        let encoded = [
+            0x30, 0x0D, 0x80, 0x01, 0x07, 0xA2, 0x03, 0x16, 0x01, b'a', 0xA3, 0x03, 0x81, 0x01,
+            0x01,
+        ];
+
+        assert_eq!(
+            module.encode(Codec::Der, "Message", &message).unwrap(),
+            encoded
+        );
+        assert_eq!(
+            module.decode(Codec::Der, "Message", &encoded).unwrap(),
+            message
+        );
+        assert_eq!(
+            module.decode(Codec::Ber, "Message", &encoded).unwrap(),
+            message
+        );
+
+        // A value equal to the default is omitted in DER.
+        let mut with_default = message.clone();
+        if let Value::Sequence(components) = &mut with_default {
+            components.insert(1, ("kind".into(), Value::Enumerated(0.into())));
+        }
+        assert_eq!(
+            module.encode(Codec::Der, "Message", &with_default).unwrap(),
+            encoded
+        );
+
+        // Constraints are checked when encoding.
+        let invalid = Value::Sequence(vec![
+            ("id".into(), Value::Integer(256.into())),
+            (
+                "payload".into(),
+                Value::Choice("count".into(), Box::new(Value::Integer(1.into()))),
+            ),
+        ]);
+        assert!(module.encode(Codec::Der, "Message", &invalid).is_err());
+        assert!(module.encode(Codec::Der, "Missing", &message).is_err());
+        assert!(module.decode(Codec::Avn, "Message", &encoded).is_err());
+
+        // Constrained primitives can be used with every codec.
+        let tag = Value::Ia5String(Ia5String::try_from("ab").unwrap());
+        assert_eq!(
+            module.encode(Codec::Uper, "Tag", &tag).unwrap(),
+            [0x1C, 0x38, 0x80]
+        );
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", automatic_tags)]
+    #[non_exhaustive]
+    struct Message {
+        #[rasn(value("0..=255"))]
+        id: Integer,
+        #[rasn(default = "normal")]
+        kind: Kind,
+        #[rasn(size("0..=4"))]
+        tags: Option<crate::types::SequenceOf<Tag>>,
+        payload: Payload,
+    }
+
+    fn normal() -> Kind {
+        Kind::Normal
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Clone, Copy, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", enumerated)]
+    #[non_exhaustive]
+    enum Kind {
+        #[rasn(identifier = "normal")]
+        Normal = 0,
+        #[rasn(identifier = "low")]
+        Low = 1,
+        #[rasn(identifier = "urgent")]
+        Urgent = 5,
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", delegate, size("1..=16"))]
+    struct Tag(Ia5String);
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", choice, automatic_tags)]
+    enum Payload {
+        #[rasn(identifier = "raw")]
+        Raw(crate::types::OctetString),
+        #[rasn(identifier = "count")]
+        Count(Integer),
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", delegate, from("A..=Z", "0..=9"), size("1..=8"))]
+    struct Code(Ia5String);
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", delegate, from("0..=9", "A..=F"), size(2))]
+    struct Hex(VisibleString);
+
+    const CODECS: [Codec; 10] = [
+        Codec::Ber,
+        Codec::Cer,
+        Codec::Der,
+        Codec::Uper,
+        Codec::Aper,
+        Codec::Oer,
+        Codec::Coer,
+        Codec::Jer,
+        Codec::Xer,
+        Codec::Cxer,
+    ];
+
+    fn assert_matches_derived<T: Encode + Decode + PartialEq + core::fmt::Debug>(
+        module: &Module,
+        name: &str,
+        value: &Value,
+        expected: &T,
+    ) {
+        for codec in CODECS {
+            let encoded = module.encode(codec, name, value).unwrap();
+            assert_eq!(
+                encoded,
+                codec.encode_to_binary(expected).unwrap(),
+                "{codec}"
+            );
+            assert_eq!(
+                &module.decode(codec, name, &encoded).unwrap(),
+                value,
+                "{codec}"
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_and_decodes_with_every_codec() {
+        let module = Module::parse(MODULE).unwrap();
+        let message = Value::Sequence(vec![
+            ("id".into(), Value::Integer(7.into())),
+            ("kind".into(), Value::Enumerated(5.into())),
+            (
+                "tags".into(),
+                Value::SequenceOf(vec![
+                    Value::Ia5String(Ia5String::try_from("a").unwrap()),
+                    Value::Ia5String(Ia5String::try_from("bc").unwrap()),
+                ]),
+            ),
+            (
+                "payload".into(),
+                Value::Choice(
+                    "raw".into(),
+                    Box::new(Value::OctetString(vec![1, 2].into())),
+                ),
+            ),
+        ]);
+        let expected = Message {
+            id: 7.into(),
+            kind: Kind::Urgent,
+            tags: Some(vec![
+                Tag(Ia5String::try_from("a").unwrap()),
+                Tag(Ia5String::try_from("bc").unwrap()),
+            ]),
+            payload: Payload::Raw(vec![1, 2].into()),
+        };
+        assert_matches_derived(&module, "Message", &message, &expected);
+
+        // The outermost XER element is named after the type assignment.
+        let encoded = module.encode(Codec::Xer, "Message", &message).unwrap();
+        assert!(encoded.starts_with(b"<Message>"));
+        assert!(module.encode(Codec::Avn, "Message", &message).is_err());
+
+        let tag = Value::Ia5String(Ia5String::try_from("ab").unwrap());
+        let expected = Tag(Ia5String::try_from("ab").unwrap());
+        assert_matches_derived(&module, "Tag", &tag, &expected);
+    }
+
+    #[test]
+    fn permitted_alphabets() {
+        let module = Module::parse(
+            r#"
+            Alphabets DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Code ::= IA5String (FROM ("A".."Z" | "0".."9") ^ SIZE (1..8))
+                Hex ::= VisibleString (FROM ("0123456789" | "A".."F")) (SIZE (2))
+                Upper ::= IA5String (FROM ("A".."Z" UNION "a".."z") INTERSECTION FROM ("A".."Z"))
+                Open ::= IA5String (FROM ("a".."z", ...))
+            END
+            "#,
+        )
+        .unwrap();
+
+        let code = Value::Ia5String(Ia5String::try_from("AB12").unwrap());
+        let expected = Code(Ia5String::try_from("AB12").unwrap());
+        assert_matches_derived(&module, "Code", &code, &expected);
+
+        let hex = Value::VisibleString(VisibleString::try_from("7F").unwrap());
+        let expected = Hex(VisibleString::try_from("7F").unwrap());
+        assert_matches_derived(&module, "Hex", &hex, &expected);
+
+        // Characters outside of the alphabet are rejected.
+        let lower = Value::Ia5String(Ia5String::try_from("ab").unwrap());
+        assert!(module.encode(Codec::Uper, "Code", &lower).is_err());
+        assert!(module.encode(Codec::Uper, "Upper", &lower).is_err());
+        let upper = Value::Ia5String(Ia5String::try_from("AB").unwrap());
+        assert_eq!(
+            module.encode(Codec::Uper, "Upper", &upper).unwrap(),
+            [0x02, 0x00, 0x40]
+        );
+
+        // An extensible alphabet is not visible to PER.
+        assert_eq!(
+            module.encode(Codec::Uper, "Open", &lower).unwrap(),
+            crate::uper::encode(&Ia5String::try_from("ab").unwrap()).unwrap()
+        );
+
+        let error = Module::parse(
+            "Broken DEFINITIONS ::= BEGIN\n  A ::= IA5String (FROM (\"AB\"..\"Z\"))\nEND",
+        )
+        .unwrap_err();
+        assert_eq!(error.line(), 2);
+    }
+
+    #[test]
+    fn recursive_types() {
+        let module = Module::parse(
+            r#"
+            Ldap DEFINITIONS IMPLICIT TAGS ::= BEGIN
+                Filter ::= CHOICE {
+                    and     [0] SET OF Filter,
+                    or      [1] SET OF Filter,
+                    not     [2] Filter,
+                    present [7] OCTET STRING
+                }
+                Path ::= SEQUENCE { step INTEGER, next Path OPTIONAL }
+            END
+        "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            module.get("Path"),
+            Some(Type::Sequence { components, .. })
+                if matches!(&components[1].ty, Type::Reference(reference) if reference.name() == "Path")
+        ));
+
+        let present = |attribute: &str| {
+            Value::Choice(
+                "present".into(),
+                Box::new(Value::OctetString(attribute.as_bytes().to_vec().into())),
+            )
+        };
+        let filter = Value::Choice(
+            "and".into(),
+            Box::new(Value::SetOf(SetOf::from_vec(vec![
+                present("cn"),
+                Value::Choice("not".into(), Box::new(present("sn"))),
+            ]))),
+        );
+        let encoded = [
+            0xA0, 0x0A, 0x87, 0x02, b'c', b'n', 0xA2, 0x04, 0x87, 0x02, b's', b'n',
+        ];
+        assert_eq!(
+            module.encode(Codec::Der, "Filter", &filter).unwrap(),
+            encoded
+        );
+        assert_eq!(
+            module.decode(Codec::Der, "Filter", &encoded).unwrap(),
+            filter
+        );
+
+        let path = Value::Sequence(vec![
+            ("step".into(), Value::Integer(1.into())),
+            (
+                "next".into(),
+                Value::Sequence(vec![("step".into(), Value::Integer(2.into()))]),
+            ),
+        ]);
+        let encoded = module.encode(Codec::Der, "Path", &path).unwrap();
+        assert_eq!(module.decode(Codec::Der, "Path", &encoded).unwrap(), path);
+
+        // References only resolve while the module is alive.
+        let ty = module.get("Path").unwrap().clone();
+        drop(module);
+        assert!(ty.encode(Codec::Der, &path).is_err());
+    }
+
+    #[test]
+    fn shares_referenced_types() {
+        // Each type references the previous one twice, which takes
+        // exponential time and memory if references are expanded.
+        let mut source = String::from("Deep DEFINITIONS ::= BEGIN\n  T0 ::= INTEGER\n");
+        for depth in 1..64 {
+            let previous = depth - 1;
+            source += &format!("  T{depth} ::= SEQUENCE {{ a T{previous}, b T{previous} }}\n");
+        }
+        source += "END";
+
+        let module = Module::parse(&source).unwrap();
+        let leaf = Value::Sequence(vec![
+            ("a".into(), Value::Integer(1.into())),
+            ("b".into(), Value::Integer(2.into())),
+        ]);
+        assert_eq!(
+            module.encode(Codec::Der, "T1", &leaf).unwrap(),
+            [0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        let error = Module::parse("Broken DEFINITIONS ::= BEGIN\n  A ::= SEQUENCE {\n  a B }\nEND")
+            .unwrap_err();
+        assert_eq!(error.line(), 3);
+        assert_eq!(error.message(), "type `B` is not defined in the module");
+
+        for source in [
+            "Broken DEFINITIONS ::= BEGIN\n  A ::= [0] B\n  B ::= A (SIZE (1))\nEND",
+            "Broken DEFINITIONS ::= BEGIN\n  A ::= CHOICE { a A, b NULL }\nEND",
+        ] {
+            let error = Module::parse(source).unwrap_err();
+            assert_eq!(error.line(), 2);
+            assert_eq!(error.message(), "`A` is defined in terms of itself");
+        }
+
+        let error =
+            Module::parse("Broken DEFINITIONS ::= BEGIN\n  A ::= INTEGER (0..\nEND").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: expected `)`, found the end of the module"
+        );
+    }
+}
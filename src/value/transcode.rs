@@ -0,0 +1,171 @@
+//! Transcoding of values without a schema.
+
+use alloc::{boxed::Box, format, vec::Vec};
+
+use super::{Value, is_tag_based};
+use crate::{
+    Codec,
+    de::Error as _,
+    enc::Error as _,
+    error::{DecodeError, EncodeError, TranscodeError},
+    types::{OctetString, SetOf},
+};
+
+/// How values that the target encoding rules can't represent are handled
+/// when transcoding without a schema.
+///
+/// BER, CER and DER can represent every decoded value. Other encoding rules
+/// don't encode tags, so values with a tag other than the universal tag of a
+/// known type, such as context tagged components, open types and `REAL`
+/// values without the `f64` feature, lose their meaning. `ENUMERATED` values
+/// can't be represented either, as the names of their enumerations aren't
+/// known.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Unrepresentable {
+    /// Return an error.
+    #[default]
+    Error,
+    /// Leave the value out of the enclosing value.
+    Omit,
+    /// Replace the value with an `OCTET STRING` holding its BER encoding.
+    Encapsulate,
+}
+
+/// Decodes `input` as a [`Value`] with `from` and encodes it with `to`.
+pub(crate) fn transcode(
+    from: Codec,
+    to: Codec,
+    input: &[u8],
+    unrepresentable: Unrepresentable,
+) -> Result<Vec<u8>, TranscodeError> {
+    if !is_tag_based(from) {
+        return Err(TranscodeError::Decode {
+            source: DecodeError::custom(
+                format!(
+                    "{from} is not self-describing, so it can't be transcoded without a schema"
+                ),
+                from,
+            ),
+        });
+    }
+
+    let value: Value = from
+        .decode_from_binary(input)
+        .map_err(|source| TranscodeError::Decode { source })?;
+    let encode = || {
+        let value = value.represent(to, unrepresentable)?.ok_or_else(|| {
+            EncodeError::custom(
+                format!("{to} can't represent the value, so nothing is left to encode"),
+                to,
+            )
+        })?;
+        to.encode_to_binary(&value)
+    };
+    encode().map_err(|source| TranscodeError::Encode { source })
+}
+
+impl Value {
+    /// Returns the value with the values that `codec` can't represent handled
+    /// as `unrepresentable` asks, `None` if the value itself is omitted.
+    fn represent(
+        self,
+        codec: Codec,
+        unrepresentable: Unrepresentable,
+    ) -> Result<Option<Self>, EncodeError> {
+        if is_tag_based(codec) {
+            return Ok(Some(self));
+        }
+
+        if matches!(self, Self::Tagged { .. } | Self::Enumerated(_)) {
+            return match unrepresentable {
+                Unrepresentable::Error => Err(EncodeError::custom(
+                    format!(
+                        "{codec} can't represent a value tagged {} without a schema",
+                        self.tag()
+                    ),
+                    codec,
+                )),
+                Unrepresentable::Omit => Ok(None),
+                Unrepresentable::Encapsulate => Ok(Some(Self::OctetString(OctetString::from(
+                    crate::ber::encode(&self)?,
+                )))),
+            };
+        }
+
+        let items = |items: Vec<Self>| {
+            items
+                .into_iter()
+                .filter_map(|item| item.represent(codec, unrepresentable).transpose())
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let components = |components: Vec<(_, Self)>| {
+            components
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .represent(codec, unrepresentable)
+                        .map(|value| value.map(|value| (name, value)))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Some(match self {
+            Self::SequenceOf(values) => Self::SequenceOf(items(values)?),
+            Self::SetOf(values) => Self::SetOf(SetOf::from_vec(items(
+                values.to_vec().into_iter().cloned().collect(),
+            )?)),
+            Self::Sequence(values) => Self::Sequence(components(values)?),
+            Self::Set(values) => Self::Set(components(values)?),
+            Self::Choice(name, value) => match value.represent(codec, unrepresentable)? {
+                Some(value) => Self::Choice(name, Box::new(value)),
+                None => return Ok(None),
+            },
+            value => value,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // SEQUENCE { INTEGER 5, [0] IMPLICIT INTEGER 1 }
+    const INPUT: &[u8] = &[0x30, 0x06, 0x02, 0x01, 0x05, 0x80, 0x01, 0x01];
+
+    #[test]
+    fn unrepresentable_values() {
+        assert!(matches!(
+            transcode(Codec::Ber, Codec::Jer, INPUT, Unrepresentable::Error),
+            Err(TranscodeError::Encode { .. })
+        ));
+        assert_eq!(
+            transcode(Codec::Ber, Codec::Jer, INPUT, Unrepresentable::Omit).unwrap(),
+            crate::jer::encode(&vec![crate::types::Integer::from(5)])
+                .unwrap()
+                .into_bytes()
+        );
+        let encapsulated = Value::SequenceOf(vec![
+            Value::Integer(5.into()),
+            Value::OctetString(OctetString::from(vec![0x80, 0x01, 0x01])),
+        ]);
+        assert_eq!(
+            transcode(Codec::Der, Codec::Jer, INPUT, Unrepresentable::Encapsulate).unwrap(),
+            crate::jer::encode(&encapsulated).unwrap().into_bytes()
+        );
+        assert_eq!(
+            transcode(Codec::Ber, Codec::Der, INPUT, Unrepresentable::Error).unwrap(),
+            INPUT
+        );
+    }
+
+    #[test]
+    fn source_must_be_self_describing() {
+        assert!(matches!(
+            transcode(Codec::Uper, Codec::Jer, &[0x00], Unrepresentable::Omit),
+            Err(TranscodeError::Decode { .. })
+        ));
+    }
+}
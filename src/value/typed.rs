@@ -0,0 +1,2019 @@
+//! Runtime descriptions of ASN.1 types.
+
+use alloc::{borrow::Cow, boxed::Box, string::String, sync::Weak, vec::Vec};
+use core::fmt;
+
+use super::{Value, encode_components, is_tag_based};
+use crate::{
+    Codec, Decode, Encode,
+    ber::de::DecoderOptions,
+    de::Error as _,
+    enc::Error as _,
+    error::{DecodeError, EncodeError},
+    types::{
+        AsnType, BitStr, BitString, BmpString, Constraint, Constraints, Date, GeneralString,
+        GeneralizedTime, GraphicString, Ia5String, Identifier, Integer, NumericString,
+        ObjectIdentifier, OctetString, PrintableString, SetOf, Tag, TeletexString, UtcTime,
+        Utf8String, VisibleString,
+        constraints::{self, Bounded, Extensible},
+    },
+};
+
+/// A description of an ASN.1 type that is only known at runtime.
+///
+/// Paired with a [`Value`] in a [`TypedValue`], a type provides what the
+/// value alone can't: the identifiers and tags of components, whether tags are
+/// explicit, the constraints PER and OER encode with, which components can be
+/// omitted and which are extension additions. Values of a type can be encoded
+/// and decoded with every codec but AVN, and open types can only be encoded
+/// and decoded with BER, CER and DER.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Type {
+    /// The `BOOLEAN` type.
+    Boolean,
+    /// The `INTEGER` type.
+    Integer,
+    /// The `BIT STRING` type.
+    BitString,
+    /// The `OCTET STRING` type.
+    OctetString,
+    /// The `NULL` type.
+    Null,
+    /// The `OBJECT IDENTIFIER` type.
+    ObjectIdentifier,
+    /// The `REAL` type.
+    #[cfg(feature = "f64")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "f64")))]
+    Real,
+    /// An `ENUMERATED` type.
+    Enumerated {
+        /// The identifiers and discriminants of the root enumerations.
+        enumerations: Vec<(String, isize)>,
+        /// The identifiers and discriminants of the enumerations added after
+        /// the extension marker, `None` if the type isn't extensible.
+        extensions: Option<Vec<(String, isize)>>,
+    },
+    /// The `UTF8String` type.
+    Utf8String,
+    /// The `NumericString` type.
+    NumericString,
+    /// The `PrintableString` type.
+    PrintableString,
+    /// The `TeletexString` type.
+    TeletexString,
+    /// The `VisibleString` type.
+    VisibleString,
+    /// The `IA5String` type.
+    Ia5String,
+    /// The `GeneralString` type.
+    GeneralString,
+    /// The `GraphicString` type.
+    GraphicString,
+    /// The `BMPString` type.
+    BmpString,
+    /// The `UniversalString` type.
+    UniversalString,
+    /// The `UTCTime` type.
+    UtcTime,
+    /// The `GeneralizedTime` type.
+    GeneralizedTime,
+    /// The `DATE` type.
+    Date,
+    /// A `SEQUENCE` type.
+    Sequence {
+        /// The root components of the type, in declaration order.
+        components: Vec<Component>,
+        /// The extension additions of the type in declaration order, `None`
+        /// if the type isn't extensible.
+        extensions: Option<Vec<Addition>>,
+    },
+    /// A `SET` type.
+    Set {
+        /// The root components of the type, in declaration order.
+        components: Vec<Component>,
+        /// The extension additions of the type in declaration order, `None`
+        /// if the type isn't extensible.
+        extensions: Option<Vec<Addition>>,
+    },
+    /// A `SEQUENCE OF` type.
+    SequenceOf(Box<Type>),
+    /// A `SET OF` type.
+    SetOf(Box<Type>),
+    /// A `CHOICE` type.
+    Choice {
+        /// The root alternatives of the type.
+        alternatives: Vec<Component>,
+        /// The alternatives added after the extension marker, `None` if the
+        /// type isn't extensible.
+        extensions: Option<Vec<Component>>,
+    },
+    /// A tagged type.
+    Tagged {
+        /// The tag of the type.
+        tag: Tag,
+        /// Whether the tag is explicit. `CHOICE` and open types are always
+        /// tagged explicitly.
+        explicit: bool,
+        /// The type being tagged.
+        inner: Box<Type>,
+    },
+    /// A constrained type.
+    Constrained {
+        /// The constraints of the type.
+        constraints: Constraints,
+        /// The type being constrained.
+        inner: Box<Type>,
+    },
+    /// An open type, holding a value of any type.
+    Any,
+    /// A reference to a type assigned in a [`Module`](super::Module).
+    Reference(Reference),
+}
+
+impl Type {
+    /// Returns the ASN.1 name of the type, for error messages.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Boolean => "BOOLEAN",
+            Self::Integer => "INTEGER",
+            Self::BitString => "BIT STRING",
+            Self::OctetString => "OCTET STRING",
+            Self::Null => "NULL",
+            Self::ObjectIdentifier => "OBJECT IDENTIFIER",
+            #[cfg(feature = "f64")]
+            Self::Real => "REAL",
+            Self::Enumerated { .. } => "ENUMERATED",
+            Self::Utf8String => "UTF8String",
+            Self::NumericString => "NumericString",
+            Self::PrintableString => "PrintableString",
+            Self::TeletexString => "TeletexString",
+            Self::VisibleString => "VisibleString",
+            Self::Ia5String => "IA5String",
+            Self::GeneralString => "GeneralString",
+            Self::GraphicString => "GraphicString",
+            Self::BmpString => "BMPString",
+            Self::UniversalString => "UniversalString",
+            Self::UtcTime => "UTCTime",
+            Self::GeneralizedTime => "GeneralizedTime",
+            Self::Date => "DATE",
+            Self::Sequence { .. } => "SEQUENCE",
+            Self::Set { .. } => "SET",
+            Self::SequenceOf(_) => "SEQUENCE OF",
+            Self::SetOf(_) => "SET OF",
+            Self::Choice { .. } => "CHOICE",
+            Self::Tagged { inner, .. } | Self::Constrained { inner, .. } => inner.name(),
+            Self::Any => "open type",
+            Self::Reference(reference) => reference.with(Self::name).unwrap_or("unresolved"),
+        }
+    }
+
+    /// Returns whether the type can't be implicitly tagged.
+    fn is_untagged(&self) -> bool {
+        match self {
+            Self::Choice { .. } | Self::Any => true,
+            Self::Constrained { inner, .. } => inner.is_untagged(),
+            Self::Reference(reference) => reference.with(Self::is_untagged).unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// Returns whether `value` is a value of this primitive type.
+    fn is_primitive_of(&self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Self::Boolean, Value::Boolean(_))
+                | (Self::Integer, Value::Integer(_))
+                | (Self::BitString, Value::BitString(_))
+                | (Self::OctetString, Value::OctetString(_))
+                | (Self::Null, Value::Null)
+                | (Self::ObjectIdentifier, Value::ObjectIdentifier(_))
+                | (Self::Utf8String, Value::Utf8String(_))
+                | (Self::NumericString, Value::NumericString(_))
+                | (Self::PrintableString, Value::PrintableString(_))
+                | (Self::TeletexString, Value::TeletexString(_))
+                | (Self::VisibleString, Value::VisibleString(_))
+                | (Self::Ia5String, Value::Ia5String(_))
+                | (Self::GeneralString, Value::GeneralString(_))
+                | (Self::GraphicString, Value::GraphicString(_))
+                | (Self::BmpString, Value::BmpString(_))
+                | (Self::UniversalString, Value::UniversalString(_))
+                | (Self::UtcTime, Value::UtcTime(_))
+                | (Self::GeneralizedTime, Value::GeneralizedTime(_))
+                | (Self::Date, Value::Date(_))
+        ) || self.is_real_of(value)
+    }
+
+    #[cfg(feature = "f64")]
+    fn is_real_of(&self, value: &Value) -> bool {
+        matches!((self, value), (Self::Real, Value::Real(_)))
+    }
+
+    #[cfg(not(feature = "f64"))]
+    fn is_real_of(&self, _: &Value) -> bool {
+        false
+    }
+
+    /// Returns the tag of the type when it isn't tagged, [`Tag::EOC`] for
+    /// `CHOICE` and open types.
+    fn universal_tag(&self) -> Tag {
+        match self {
+            Self::Boolean => Tag::BOOL,
+            Self::Integer => Tag::INTEGER,
+            Self::BitString => Tag::BIT_STRING,
+            Self::OctetString => Tag::OCTET_STRING,
+            Self::Null => Tag::NULL,
+            Self::ObjectIdentifier => Tag::OBJECT_IDENTIFIER,
+            #[cfg(feature = "f64")]
+            Self::Real => Tag::REAL,
+            Self::Enumerated { .. } => Tag::ENUMERATED,
+            Self::Utf8String => Tag::UTF8_STRING,
+            Self::NumericString => Tag::NUMERIC_STRING,
+            Self::PrintableString => Tag::PRINTABLE_STRING,
+            Self::TeletexString => Tag::TELETEX_STRING,
+            Self::VisibleString => Tag::VISIBLE_STRING,
+            Self::Ia5String => Tag::IA5_STRING,
+            Self::GeneralString => Tag::GENERAL_STRING,
+            Self::GraphicString => Tag::GRAPHIC_STRING,
+            Self::BmpString => Tag::BMP_STRING,
+            Self::UniversalString => Tag::UNIVERSAL_STRING,
+            Self::UtcTime => Tag::UTC_TIME,
+            Self::GeneralizedTime => Tag::GENERALIZED_TIME,
+            Self::Date => Tag::DATE,
+            Self::Sequence { .. } | Self::SequenceOf(_) => Tag::SEQUENCE,
+            Self::Set { .. } | Self::SetOf(_) => Tag::SET,
+            Self::Tagged { tag, .. } => *tag,
+            Self::Constrained { inner, .. } => inner.universal_tag(),
+            Self::Reference(reference) => reference.with(Self::universal_tag).unwrap_or(Tag::EOC),
+            Self::Choice { .. } | Self::Any => Tag::EOC,
+        }
+    }
+
+    /// Returns whether an encoded value of the type can start with `tag`.
+    pub(crate) fn matches_tag(&self, tag: Tag) -> bool {
+        match self {
+            Self::Constrained { inner, .. } => inner.matches_tag(tag),
+            Self::Reference(reference) => reference.with(|ty| ty.matches_tag(tag)).unwrap_or(false),
+            Self::Choice { .. } => self
+                .alternatives()
+                .any(|alternative| alternative.ty.matches_tag(tag)),
+            Self::Any => true,
+            _ => self.universal_tag() == tag,
+        }
+    }
+
+    /// Returns the root alternatives of a `CHOICE` type followed by its
+    /// extension additions, and nothing for other types.
+    pub(crate) fn alternatives(&self) -> impl Iterator<Item = &Component> {
+        let (alternatives, extensions) = match self {
+            Self::Choice {
+                alternatives,
+                extensions,
+            } => (&alternatives[..], extensions.as_deref().unwrap_or_default()),
+            _ => (&[][..], &[][..]),
+        };
+        alternatives.iter().chain(extensions)
+    }
+
+    /// Returns the tag the type is sorted by in the canonical order of tags,
+    /// which is the smallest tag of its alternatives for an untagged `CHOICE`
+    /// type.
+    pub(crate) fn canonical_tag(&self) -> Tag {
+        match self {
+            Self::Constrained { inner, .. } => inner.canonical_tag(),
+            Self::Reference(reference) => reference.with(Self::canonical_tag).unwrap_or(Tag::EOC),
+            Self::Choice { .. } => self
+                .alternatives()
+                .map(|alternative| alternative.ty.canonical_tag())
+                .min()
+                .unwrap_or(Tag::EOC),
+            _ => self.universal_tag(),
+        }
+    }
+
+    /// Returns the name of the element a value of the type is written in by
+    /// XER when it has no identifier, the name of the referenced type for a
+    /// reference.
+    pub(crate) fn xml_name(&self) -> Cow<'static, str> {
+        match self {
+            Self::Reference(reference) => Cow::Owned(reference.name.clone()),
+            Self::Tagged { inner, .. } | Self::Constrained { inner, .. } => inner.xml_name(),
+            _ => Cow::Owned(self.name().replace(' ', "_")),
+        }
+    }
+
+    /// Returns the root components of a `SEQUENCE` or `SET` type followed by
+    /// the components of its extension additions, and nothing for other
+    /// types.
+    pub(crate) fn all_components(&self) -> impl Iterator<Item = &Component> {
+        let (components, extensions) = match self {
+            Self::Sequence {
+                components,
+                extensions,
+            }
+            | Self::Set {
+                components,
+                extensions,
+            } => (&components[..], extensions.as_deref().unwrap_or_default()),
+            _ => (&[][..], &[][..]),
+        };
+        components
+            .iter()
+            .chain(extensions.iter().flat_map(Addition::components))
+    }
+
+    /// Builds a value of a `SEQUENCE` or `SET` type from the decoded values of
+    /// its components, in the order of [`Self::all_components`].
+    ///
+    /// Extension additions may be missing, as the encoding may come from an
+    /// earlier version of the type, but a present group must hold all of its
+    /// required components.
+    pub(crate) fn components_value(
+        &self,
+        values: Vec<Option<Value>>,
+        codec: Codec,
+    ) -> Result<Value, DecodeError> {
+        let (Self::Sequence {
+            components,
+            extensions,
+        }
+        | Self::Set {
+            components,
+            extensions,
+        }) = self
+        else {
+            return Err(DecodeError::custom(
+                alloc::format!("{} type has no components", self.name()),
+                codec,
+            ));
+        };
+
+        let mut values = values.into_iter();
+        let mut present = Vec::new();
+        for component in components {
+            match values.next().flatten() {
+                Some(value) => present.push((component.name.clone(), value)),
+                None if component.is_optional() => {}
+                None => return Err(missing_component(&component.name, codec)),
+            }
+        }
+        for addition in extensions.iter().flatten() {
+            let values: Vec<_> = values.by_ref().take(addition.components().len()).collect();
+            let group_present =
+                matches!(addition, Addition::Group(_)) && values.iter().any(Option::is_some);
+            for (component, value) in addition.components().iter().zip(values) {
+                match value {
+                    Some(value) => present.push((component.name.clone(), value)),
+                    None if component.is_optional() || !group_present => {}
+                    None => return Err(missing_component(&component.name, codec)),
+                }
+            }
+        }
+
+        Ok(match self {
+            Self::Set { .. } => Value::Set(present),
+            _ => Value::Sequence(present),
+        })
+    }
+
+    /// Returns the alternative of a `CHOICE` type at `index`, among the root
+    /// alternatives or the extension additions, in the canonical order of
+    /// their tags.
+    pub(crate) fn alternative_at(
+        &self,
+        index: usize,
+        is_extension: bool,
+        codec: Codec,
+    ) -> Result<&Component, DecodeError> {
+        let alternatives = match self {
+            Self::Choice { extensions, .. } if is_extension => extensions.as_deref(),
+            Self::Choice { alternatives, .. } => Some(&alternatives[..]),
+            _ => None,
+        }
+        .unwrap_or_default();
+        canonical_order(alternatives)
+            .get(index)
+            .map(|index| &alternatives[*index])
+            .ok_or_else(|| {
+                DecodeError::custom(
+                    alloc::format!(
+                        "the CHOICE type has no {} alternative at index {index}",
+                        if is_extension { "extension" } else { "root" }
+                    ),
+                    codec,
+                )
+            })
+    }
+
+    /// Returns the value of the enumeration of an `ENUMERATED` type at
+    /// `index`, among the root enumerations in the order of their values or
+    /// the additions in declaration order.
+    pub(crate) fn enumeration_at(
+        &self,
+        index: usize,
+        is_extension: bool,
+        codec: Codec,
+    ) -> Result<Value, DecodeError> {
+        let enumeration = match self {
+            Self::Enumerated { extensions, .. } if is_extension => extensions
+                .as_deref()
+                .and_then(|extensions| extensions.get(index)),
+            Self::Enumerated { enumerations, .. } => {
+                sorted_by_value(enumerations).get(index).copied()
+            }
+            _ => None,
+        };
+        enumeration
+            .map(|(_, value)| Value::Enumerated(Integer::from(*value)))
+            .ok_or_else(|| DecodeError::enumeration_index_not_found(index, is_extension, codec))
+    }
+
+    /// Returns the value of an `ENUMERATED` type with `discriminant`, which
+    /// may be unknown for an extensible type, as it may be an addition of a
+    /// later version of the type.
+    pub(crate) fn enumeration_of(
+        &self,
+        discriminant: Integer,
+        codec: Codec,
+    ) -> Result<Value, DecodeError> {
+        let is_known = match self {
+            Self::Enumerated {
+                extensions: Some(_),
+                ..
+            } => true,
+            Self::Enumerated { enumerations, .. } => enumerations
+                .iter()
+                .any(|(_, known)| discriminant == Integer::from(*known)),
+            _ => false,
+        };
+        if !is_known {
+            return Err(DecodeError::custom(
+                alloc::format!("{discriminant} is not a value of the ENUMERATED type"),
+                codec,
+            ));
+        }
+        Ok(Value::Enumerated(discriminant))
+    }
+
+    /// Returns the value of the enumeration of an `ENUMERATED` type named
+    /// `identifier`.
+    pub(crate) fn enumeration_named(&self, identifier: &str) -> Option<Value> {
+        let Self::Enumerated {
+            enumerations,
+            extensions,
+        } = self
+        else {
+            return None;
+        };
+        enumerations
+            .iter()
+            .chain(extensions.iter().flatten())
+            .find(|(name, _)| name == identifier)
+            .map(|(_, value)| Value::Enumerated(Integer::from(*value)))
+    }
+
+    /// Returns whether XER writes values of the type in a `SEQUENCE OF` or
+    /// `SET OF` without an element of their own, as it does for `BOOLEAN`,
+    /// `ENUMERATED` and `CHOICE` values.
+    pub(crate) fn is_xml_value_list_item(&self) -> bool {
+        match self {
+            Self::Boolean | Self::Enumerated { .. } | Self::Choice { .. } => true,
+            Self::Tagged { inner, .. } | Self::Constrained { inner, .. } => {
+                inner.is_xml_value_list_item()
+            }
+            Self::Reference(reference) => reference
+                .with(Self::is_xml_value_list_item)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// Encodes `value` as a value of the type with `codec`.
+    ///
+    /// This is a shorthand for encoding a [`TypedValue`], except that XER
+    /// names the outermost element after the type.
+    pub fn encode(&self, codec: Codec, value: &Value) -> Result<Vec<u8>, EncodeError> {
+        self.encode_named(codec, value, self.xml_name())
+    }
+
+    /// Encodes `value` as a value of the type with `codec`, naming the
+    /// outermost element `name` in XER.
+    pub(crate) fn encode_named(
+        &self,
+        codec: Codec,
+        value: &Value,
+        name: Cow<'static, str>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let value = TypedValue::new(self, value);
+        match codec {
+            Codec::Xer | Codec::Cxer => {
+                let options = if codec == Codec::Cxer {
+                    crate::xer::enc::EncoderOptions::cxer()
+                } else {
+                    crate::xer::enc::EncoderOptions::xer()
+                };
+                let mut encoder =
+                    crate::xer::enc::Encoder::new_with_options(options).with_element_name(name);
+                value.encode(&mut encoder).map_err(|error| {
+                    if codec == Codec::Cxer {
+                        error.into_cxer()
+                    } else {
+                        error
+                    }
+                })?;
+                Ok(encoder.finish())
+            }
+            _ => codec.encode_to_binary(&value),
+        }
+    }
+
+    /// Decodes a value of the type from `input` with `codec`.
+    pub fn decode(&self, codec: Codec, input: &[u8]) -> Result<Value, DecodeError> {
+        let constraints = Constraints::default();
+        match codec {
+            Codec::Ber | Codec::Cer | Codec::Der => {
+                let options = match codec {
+                    Codec::Ber => DecoderOptions::ber(),
+                    Codec::Cer => DecoderOptions::cer(),
+                    _ => DecoderOptions::der(),
+                };
+                self.decode_ber(
+                    &mut crate::ber::de::Decoder::new(input, options),
+                    None,
+                    constraints,
+                )
+            }
+            Codec::Uper | Codec::Aper => {
+                let options = if codec == Codec::Aper {
+                    crate::per::de::DecoderOptions::aligned()
+                } else {
+                    crate::per::de::DecoderOptions::unaligned()
+                };
+                self.decode_value(
+                    &mut crate::per::de::Decoder::<0, 0>::new(BitStr::from_slice(input), options),
+                    None,
+                    constraints,
+                )
+            }
+            Codec::Oer | Codec::Coer => {
+                let options = if codec == Codec::Coer {
+                    crate::oer::de::DecoderOptions::coer()
+                } else {
+                    crate::oer::de::DecoderOptions::oer()
+                };
+                self.decode_value(
+                    &mut crate::oer::de::Decoder::<0, 0>::new(input, options),
+                    None,
+                    constraints,
+                )
+            }
+            Codec::Jer => {
+                let input = core::str::from_utf8(input).map_err(|error| {
+                    DecodeError::custom(
+                        alloc::format!("Failed to decode JER from UTF8 bytes: {error:?}"),
+                        codec,
+                    )
+                })?;
+                self.decode_value(&mut crate::jer::de::Decoder::new(input)?, None, constraints)
+            }
+            Codec::Xer => {
+                self.decode_value(&mut crate::xer::de::Decoder::new(input)?, None, constraints)
+            }
+            Codec::Cxer => crate::xer::de::Decoder::new_with_options(
+                input,
+                crate::xer::de::DecoderOptions::cxer(),
+            )
+            .and_then(|mut decoder| self.decode_value(&mut decoder, None, constraints))
+            .map_err(DecodeError::into_cxer),
+            Codec::Avn => Err(DecodeError::custom(
+                "types described at runtime can't be decoded from AVN",
+                codec,
+            )),
+        }
+    }
+
+    /// Decodes a value of the type with `tag` in place of its own tag, with a
+    /// codec that reads the type of every value from its decoder.
+    pub(crate) fn decode_value<D: crate::Decoder>(
+        &self,
+        decoder: &mut D,
+        tag: Option<Tag>,
+        constraints: Constraints,
+    ) -> Result<Value, D::Error> {
+        let codec = decoder.codec();
+        match self {
+            Self::Tagged {
+                tag: own_tag,
+                inner,
+                ..
+            } => inner.decode_value(decoder, Some(tag.unwrap_or(*own_tag)), constraints),
+            Self::Constrained {
+                constraints: own_constraints,
+                inner,
+            } => inner.decode_value(decoder, tag, own_constraints.intersect(constraints)),
+            Self::Reference(reference) => reference
+                .with(|ty| ty.decode_value(decoder, tag, constraints))
+                .unwrap_or_else(|| Err(D::Error::custom(reference.unresolved(), codec))),
+            Self::Any => Err(D::Error::custom(
+                "open types described at runtime can only be decoded from BER, CER or DER",
+                codec,
+            )),
+            Self::Sequence { .. }
+            | Self::Set { .. }
+            | Self::SequenceOf(_)
+            | Self::SetOf(_)
+            | Self::Choice { .. }
+            | Self::Enumerated { .. } => decoder.decode_typed_value(
+                self,
+                tag.unwrap_or_else(|| self.universal_tag()),
+                constraints,
+            ),
+            _ => self.decode_primitive(
+                decoder,
+                tag.unwrap_or_else(|| self.universal_tag()),
+                constraints,
+            ),
+        }
+    }
+
+    /// Decodes a value of the type with `tag` in place of its own tag.
+    fn decode_ber(
+        &self,
+        decoder: &mut crate::ber::de::Decoder<'_>,
+        tag: Option<Tag>,
+        constraints: Constraints,
+    ) -> Result<Value, DecodeError> {
+        let codec = decoder.codec();
+        let tag_or = |universal: Tag| tag.unwrap_or(universal);
+
+        Ok(match self {
+            Self::Tagged {
+                tag: own_tag,
+                explicit,
+                inner,
+            } => {
+                let tag = tag_or(*own_tag);
+                if *explicit || inner.is_untagged() {
+                    return decoder.parse_constructed_contents(tag, false, |decoder| {
+                        inner.decode_ber(decoder, None, constraints)
+                    });
+                }
+                return inner.decode_ber(decoder, Some(tag), constraints);
+            }
+            Self::Constrained {
+                constraints: own_constraints,
+                inner,
+            } => {
+                return inner.decode_ber(decoder, tag, own_constraints.intersect(constraints));
+            }
+            Self::Reference(reference) => {
+                return reference
+                    .with(|ty| ty.decode_ber(decoder, tag, constraints))
+                    .unwrap_or_else(|| Err(DecodeError::custom(reference.unresolved(), codec)));
+            }
+            Self::Any | Self::Choice { .. } if tag.is_some() => {
+                return decoder.parse_constructed_contents(tag_or(Tag::EOC), false, |decoder| {
+                    self.decode_ber(decoder, None, constraints)
+                });
+            }
+            Self::Any => Value::decode_ber(decoder)?,
+            Self::Choice { .. } => {
+                let next = decoder.peek_tag()?;
+                let alternative = self
+                    .alternatives()
+                    .find(|alternative| alternative.ty.matches_tag(next))
+                    .ok_or_else(|| {
+                        DecodeError::custom(
+                            alloc::format!("no alternative of the CHOICE type has the tag {next}"),
+                            codec,
+                        )
+                    })?;
+                Value::Choice(
+                    alternative.name.clone(),
+                    Box::new(
+                        alternative
+                            .ty
+                            .decode_ber(decoder, None, Constraints::default())?,
+                    ),
+                )
+            }
+            Self::Sequence { extensions, .. } => {
+                decoder.parse_constructed_contents(tag_or(Tag::SEQUENCE), true, |decoder| {
+                    let mut values = Vec::new();
+                    for component in self.all_components() {
+                        let present = !decoder.is_end_of_contents()
+                            && component.ty.matches_tag(decoder.peek_tag()?);
+                        values.push(if present {
+                            Some(
+                                component
+                                    .ty
+                                    .decode_ber(decoder, None, Constraints::default())?,
+                            )
+                        } else {
+                            None
+                        });
+                    }
+                    // Skip the extension additions of later versions of the type.
+                    while extensions.is_some() && !decoder.is_end_of_contents() {
+                        crate::Decoder::decode_any(decoder, Tag::EOC)?;
+                    }
+                    self.components_value(values, codec)
+                })?
+            }
+            Self::Set { extensions, .. } => {
+                decoder.parse_constructed_contents(tag_or(Tag::SET), true, |decoder| {
+                    let components: Vec<_> = self.all_components().collect();
+                    let mut values: Vec<Option<Value>> = components.iter().map(|_| None).collect();
+                    while !decoder.is_end_of_contents() {
+                        let next = decoder.peek_tag()?;
+                        let index = components
+                            .iter()
+                            .enumerate()
+                            .position(|(index, component)| {
+                                values[index].is_none() && component.ty.matches_tag(next)
+                            });
+                        match index {
+                            Some(index) => {
+                                values[index] = Some(components[index].ty.decode_ber(
+                                    decoder,
+                                    None,
+                                    Constraints::default(),
+                                )?);
+                            }
+                            None if extensions.is_some() => {
+                                crate::Decoder::decode_any(decoder, Tag::EOC)?;
+                            }
+                            None => {
+                                return Err(DecodeError::custom(
+                                    alloc::format!(
+                                        "no component of the SET type has the tag {next}"
+                                    ),
+                                    codec,
+                                ));
+                            }
+                        }
+                    }
+                    self.components_value(values, codec)
+                })?
+            }
+            Self::SequenceOf(item) => Value::SequenceOf(decoder.parse_constructed_contents(
+                tag_or(Tag::SEQUENCE),
+                true,
+                |decoder| item.decode_ber_items(decoder),
+            )?),
+            Self::SetOf(item) => Value::SetOf(SetOf::from_vec(
+                decoder.parse_constructed_contents(tag_or(Tag::SET), true, |decoder| {
+                    item.decode_ber_items(decoder)
+                })?,
+            )),
+            Self::Enumerated { .. } => {
+                let discriminant = Integer::decode_with_tag(decoder, tag_or(Tag::ENUMERATED))?;
+                self.enumeration_of(discriminant, codec)?
+            }
+            _ => self.decode_primitive(decoder, tag_or(self.universal_tag()), constraints)?,
+        })
+    }
+
+    /// Decodes a value of the primitive type with `tag`.
+    fn decode_primitive<D: crate::Decoder>(
+        &self,
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Value, D::Error> {
+        Ok(match self {
+            Self::Boolean => Value::Boolean(bool::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::Integer => Value::Integer(Integer::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::BitString => Value::BitString(BitString::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::OctetString => Value::OctetString(OctetString::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::Null => {
+                <()>::decode_with_tag(decoder, tag)?;
+                Value::Null
+            }
+            Self::ObjectIdentifier => {
+                Value::ObjectIdentifier(ObjectIdentifier::decode_with_tag(decoder, tag)?)
+            }
+            #[cfg(feature = "f64")]
+            Self::Real => Value::Real(f64::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::Utf8String => Value::Utf8String(Utf8String::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::NumericString => Value::NumericString(
+                NumericString::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::PrintableString => Value::PrintableString(
+                PrintableString::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::TeletexString => Value::TeletexString(
+                TeletexString::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::VisibleString => Value::VisibleString(
+                VisibleString::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::Ia5String => Value::Ia5String(Ia5String::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::GeneralString => Value::GeneralString(
+                GeneralString::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::GraphicString => Value::GraphicString(
+                GraphicString::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::BmpString => Value::BmpString(BmpString::decode_with_tag_and_constraints(
+                decoder,
+                tag,
+                constraints,
+            )?),
+            Self::UniversalString => Value::UniversalString(
+                Utf8String::decode_with_tag_and_constraints(decoder, tag, constraints)?,
+            ),
+            Self::UtcTime => Value::UtcTime(UtcTime::decode_with_tag(decoder, tag)?),
+            Self::GeneralizedTime => {
+                Value::GeneralizedTime(GeneralizedTime::decode_with_tag(decoder, tag)?)
+            }
+            Self::Date => Value::Date(Date::decode_with_tag(decoder, tag)?),
+            _ => {
+                return Err(D::Error::custom(
+                    alloc::format!("{} is not a primitive type", self.name()),
+                    decoder.codec(),
+                ));
+            }
+        })
+    }
+
+    /// Decodes the items of a `SEQUENCE OF` or `SET OF` value of this type.
+    fn decode_ber_items(
+        &self,
+        decoder: &mut crate::ber::de::Decoder<'_>,
+    ) -> Result<Vec<Value>, DecodeError> {
+        let mut items = Vec::new();
+        while !decoder.is_end_of_contents() {
+            items.push(self.decode_ber(decoder, None, Constraints::default())?);
+        }
+        Ok(items)
+    }
+}
+
+/// A reference to a type assigned in a [`Module`](super::Module).
+///
+/// The referenced type is shared by every reference to it and only looked up
+/// when encoding or decoding, which is what allows types to be recursive. A
+/// reference resolves as long as the module it belongs to, or a clone of it,
+/// is alive; encoding or decoding with a type taken out of a dropped module
+/// returns an error.
+#[derive(Clone)]
+pub struct Reference {
+    pub(super) name: String,
+    pub(super) index: usize,
+    pub(super) definitions: Weak<Vec<(String, Type)>>,
+}
+
+impl Reference {
+    /// Returns the name of the referenced type.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Calls `f` with the referenced type, if the module defining it is alive.
+    fn with<R>(&self, f: impl FnOnce(&Type) -> R) -> Option<R> {
+        let definitions = self.definitions.upgrade()?;
+        definitions.get(self.index).map(|(_, ty)| f(ty))
+    }
+
+    /// The description of the error for a reference that can't be resolved.
+    fn unresolved(&self) -> String {
+        alloc::format!(
+            "the module defining `{}` was dropped, so the reference can't be resolved",
+            self.name
+        )
+    }
+}
+
+impl fmt::Debug for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Reference").field(&self.name).finish()
+    }
+}
+
+/// Returns the indices of `components` in the canonical order of their tags,
+/// the order PER and OER encode the components of a `SET` and index the
+/// alternatives of a `CHOICE` in.
+pub(crate) fn canonical_order(components: &[Component]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..components.len()).collect();
+    order.sort_by_key(|index| components[*index].ty.canonical_tag());
+    order
+}
+
+/// Returns the constraints of the index of a root alternative of a `CHOICE`
+/// type with `len` root alternatives.
+pub(crate) fn index_constraints(len: usize) -> Constraints {
+    Constraints::new(&[Constraint::Value(Extensible::new(constraints::Value::new(
+        Bounded::new(0, len as i128 - 1),
+    )))])
+}
+
+/// Returns the root enumerations of an `ENUMERATED` type in the order of
+/// their values, which is the order PER indexes them in.
+fn sorted_by_value(enumerations: &[(String, isize)]) -> Vec<&(String, isize)> {
+    let mut sorted: Vec<_> = enumerations.iter().collect();
+    sorted.sort_by_key(|(_, value)| *value);
+    sorted
+}
+
+/// The error for a required component missing from an encoding.
+fn missing_component(name: &str, codec: Codec) -> DecodeError {
+    DecodeError::custom(
+        alloc::format!("the required component `{name}` is missing"),
+        codec,
+    )
+}
+
+/// A component of a `SEQUENCE` or `SET` type, or an alternative of a `CHOICE`
+/// type.
+#[derive(Debug, Clone)]
+pub struct Component {
+    /// The identifier of the component.
+    pub name: String,
+    /// The type of the component.
+    pub ty: Type,
+    /// Whether the component is `OPTIONAL`.
+    pub optional: bool,
+    /// The `DEFAULT` value of the component.
+    pub default: Option<Value>,
+}
+
+impl Component {
+    /// Creates a new required component.
+    #[must_use]
+    pub fn new(name: impl Into<String>, ty: Type) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            optional: false,
+            default: None,
+        }
+    }
+
+    /// Marks the component as `OPTIONAL`.
+    #[must_use]
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Sets the `DEFAULT` value of the component.
+    #[must_use]
+    pub fn with_default(mut self, value: Value) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Returns whether the component can be absent from a value, as an
+    /// `OPTIONAL` or `DEFAULT` component.
+    pub(crate) fn is_optional(&self) -> bool {
+        self.optional || self.default.is_some()
+    }
+}
+
+/// An extension addition of a `SEQUENCE` or `SET` type.
+#[derive(Debug, Clone)]
+pub enum Addition {
+    /// A component added on its own.
+    Component(Box<Component>),
+    /// An extension addition group, `[[ ... ]]`, whose components are present
+    /// or absent together.
+    Group(Vec<Component>),
+}
+
+impl Addition {
+    /// Returns the components of the addition.
+    #[must_use]
+    pub fn components(&self) -> &[Component] {
+        match self {
+            Self::Component(component) => core::slice::from_ref(&**component),
+            Self::Group(components) => components,
+        }
+    }
+}
+
+/// A [`Value`] paired with the [`Type`] it's a value of, which can be encoded
+/// with every codec but AVN.
+///
+/// ```rust
+/// use rasn::value::{Component, Type, TypedValue, Value};
+/// use rasn::types::Tag;
+///
+/// let ty = Type::Sequence {
+///     components: vec![
+///         Component::new("id", Type::Tagged {
+///             tag: Tag::new_context(0),
+///             explicit: false,
+///             inner: Box::new(Type::Integer),
+///         }),
+///         Component::new("flag", Type::Boolean).with_default(Value::Boolean(false)),
+///     ],
+///     extensions: None,
+/// };
+/// let value = Value::Sequence(vec![
+///     ("id".into(), Value::Integer(3.into())),
+///     ("flag".into(), Value::Boolean(false)),
+/// ]);
+///
+/// assert_eq!(
+///     rasn::der::encode(&TypedValue::new(&ty, &value)).unwrap(),
+///     [0x30, 0x03, 0x80, 0x01, 0x03]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TypedValue<'a> {
+    /// The type of the value.
+    pub ty: &'a Type,
+    /// The value.
+    pub value: &'a Value,
+}
+
+impl<'a> TypedValue<'a> {
+    /// Pairs `value` with its type.
+    #[must_use]
+    pub fn new(ty: &'a Type, value: &'a Value) -> Self {
+        Self { ty, value }
+    }
+
+    /// Encodes the value with `tag` in place of the tag of its type.
+    fn encode_as<'b, E: crate::Encoder<'b>>(
+        &self,
+        encoder: &mut E,
+        tag: Option<Tag>,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<(), E::Error> {
+        let codec = encoder.codec();
+
+        match self.ty {
+            Type::Tagged {
+                tag: own_tag,
+                explicit,
+                inner,
+            } => {
+                let value = TypedValue::new(inner, self.value);
+                let tag = tag.unwrap_or(*own_tag);
+                if (*explicit || inner.is_untagged()) && is_tag_based(codec) {
+                    encoder
+                        .encode_explicit_prefix(tag, &value, identifier)
+                        .map(drop)
+                } else {
+                    value.encode_as(encoder, Some(tag), constraints, identifier)
+                }
+            }
+            Type::Constrained {
+                constraints: own_constraints,
+                inner,
+            } => TypedValue::new(inner, self.value).encode_as(
+                encoder,
+                tag,
+                own_constraints.intersect(constraints),
+                identifier,
+            ),
+            Type::Reference(reference) => reference
+                .with(|ty| {
+                    TypedValue::new(ty, self.value).encode_as(encoder, tag, constraints, identifier)
+                })
+                .unwrap_or_else(|| Err(E::Error::custom(reference.unresolved(), codec))),
+            Type::Any if !is_tag_based(codec) => Err(E::Error::custom(
+                "open types described at runtime can only be encoded with BER, CER or DER",
+                codec,
+            )),
+            Type::Any => match tag {
+                Some(tag) => encoder
+                    .encode_explicit_prefix(tag, self.value, identifier)
+                    .map(drop),
+                None => self.value.encode(encoder),
+            },
+            Type::Sequence { .. }
+            | Type::Set { .. }
+            | Type::SequenceOf(_)
+            | Type::SetOf(_)
+            | Type::Choice { .. }
+            | Type::Enumerated { .. }
+                if !is_tag_based(codec) =>
+            {
+                encoder
+                    .encode_typed_value(
+                        *self,
+                        tag.unwrap_or_else(|| self.ty.universal_tag()),
+                        constraints,
+                        identifier,
+                    )
+                    .map(drop)
+            }
+            Type::Enumerated {
+                enumerations,
+                extensions,
+            } => {
+                let Value::Enumerated(discriminant) = self.value else {
+                    return Err(self.mismatch(codec));
+                };
+                // Values of later versions of an extensible type are kept.
+                if extensions.is_none()
+                    && !enumerations
+                        .iter()
+                        .any(|(_, known)| *discriminant == Integer::from(*known))
+                {
+                    return Err(self.mismatch(codec));
+                }
+                self.value.encode_with_tag_and_constraints(
+                    encoder,
+                    tag.unwrap_or(Tag::ENUMERATED),
+                    constraints,
+                    identifier,
+                )
+            }
+            ty if ty.is_primitive_of(self.value) => self.value.encode_with_tag_and_constraints(
+                encoder,
+                tag.unwrap_or_else(|| self.value.tag()),
+                constraints,
+                identifier,
+            ),
+            _ => {
+                let tag = tag.unwrap_or_else(|| self.ty.universal_tag());
+                match self.structure::<E::Error>(codec)? {
+                    Structure::Sequence(fields) | Structure::Set(fields) => {
+                        let present: Vec<_> = fields.present().map(|(_, value)| value).collect();
+                        let is_set = matches!(self.ty, Type::Set { .. });
+                        encode_components(encoder, tag, is_set, &present, identifier)
+                    }
+                    Structure::Choice(alternative) => {
+                        if tag == Tag::EOC {
+                            alternative.value.encode(encoder)
+                        } else {
+                            encoder
+                                .encode_explicit_prefix(tag, &alternative.value, identifier)
+                                .map(drop)
+                        }
+                    }
+                    Structure::SequenceOf(items) => encoder
+                        .encode_sequence_of(tag, &items, constraints, identifier)
+                        .map(drop),
+                    Structure::SetOf(items) => encoder
+                        .encode_set_of(tag, &SetOf::from_vec(items), constraints, identifier)
+                        .map(drop),
+                    Structure::Enumerated(_) => unreachable!("enumerated values are encoded above"),
+                }
+            }
+        }
+    }
+
+    /// The error for a value that isn't a value of its type.
+    fn mismatch<Err: crate::enc::Error>(&self, codec: Codec) -> Err {
+        Err::custom(
+            alloc::format!(
+                "{:?} is not a value of the {} type",
+                self.value,
+                self.ty.name()
+            ),
+            codec,
+        )
+    }
+
+    /// Returns the components, alternative, enumeration or items of a value of
+    /// a constructed or `ENUMERATED` type, for the codecs that encode these
+    /// values from their type.
+    pub(crate) fn structure<Err: crate::enc::Error>(
+        &self,
+        codec: Codec,
+    ) -> Result<Structure<'a>, Err> {
+        Ok(match (self.ty, self.value) {
+            (
+                Type::Sequence {
+                    components,
+                    extensions,
+                },
+                Value::Sequence(values),
+            ) => Structure::Sequence(self.fields(
+                components,
+                extensions.as_deref(),
+                values,
+                codec,
+            )?),
+            (
+                Type::Set {
+                    components,
+                    extensions,
+                },
+                Value::Set(values),
+            ) => Structure::Set(self.fields(components, extensions.as_deref(), values, codec)?),
+            (
+                Type::Choice {
+                    alternatives,
+                    extensions,
+                },
+                Value::Choice(name, value),
+            ) => {
+                let is_named = |alternatives: &&'a Vec<Component>| {
+                    alternatives
+                        .iter()
+                        .any(|alternative| alternative.name == *name)
+                };
+                let (list, is_extension) = match (alternatives, extensions) {
+                    (root, _) if is_named(&root) => (root, false),
+                    (_, Some(additions)) if is_named(&additions) => (additions, true),
+                    _ => {
+                        return Err(Err::custom(
+                            alloc::format!("`{name}` is not an alternative of the CHOICE type"),
+                            codec,
+                        ));
+                    }
+                };
+                let (index, component) = canonical_order(list)
+                    .into_iter()
+                    .map(|index| &list[index])
+                    .enumerate()
+                    .find(|(_, alternative)| alternative.name == *name)
+                    .ok_or_else(|| self.mismatch::<Err>(codec))?;
+                Structure::Choice(Alternative {
+                    component,
+                    value: TypedValue::new(&component.ty, value),
+                    index,
+                    root_len: alternatives.len(),
+                    is_extension,
+                    extensible: extensions.is_some(),
+                })
+            }
+            (
+                Type::Enumerated {
+                    enumerations,
+                    extensions,
+                },
+                Value::Enumerated(discriminant),
+            ) => {
+                let is_value =
+                    |(_, value): &&(String, isize)| *discriminant == Integer::from(*value);
+                let root = sorted_by_value(enumerations);
+                let (index, (name, value), is_extension) =
+                    match root.iter().copied().enumerate().find(|(_, e)| is_value(e)) {
+                        Some((index, enumeration)) => (index, enumeration, false),
+                        None => extensions
+                            .iter()
+                            .flatten()
+                            .enumerate()
+                            .find(|(_, e)| is_value(e))
+                            .map(|(index, enumeration)| (index, enumeration, true))
+                            .ok_or_else(|| self.mismatch::<Err>(codec))?,
+                    };
+                Structure::Enumerated(Enumeration {
+                    name,
+                    discriminant: *value,
+                    index,
+                    root_len: root.len(),
+                    is_extension,
+                    extensible: extensions.is_some(),
+                })
+            }
+            (Type::SequenceOf(item), Value::SequenceOf(values)) => Structure::SequenceOf(
+                values
+                    .iter()
+                    .map(|value| TypedValue::new(item, value))
+                    .collect(),
+            ),
+            (Type::SetOf(item), Value::SetOf(values)) => Structure::SetOf(
+                values
+                    .to_vec()
+                    .into_iter()
+                    .map(|value| TypedValue::new(item, value))
+                    .collect(),
+            ),
+            _ => return Err(self.mismatch(codec)),
+        })
+    }
+
+    /// Returns the components of a `SEQUENCE` or `SET` value, checking that
+    /// the value has no unknown components and holds every required one.
+    fn fields<Err: crate::enc::Error>(
+        &self,
+        components: &'a [Component],
+        extensions: Option<&'a [Addition]>,
+        values: &'a [(String, Value)],
+        codec: Codec,
+    ) -> Result<Fields<'a>, Err> {
+        if let Some((name, _)) = values
+            .iter()
+            .find(|(name, _)| !self.ty.all_components().any(|c| c.name == *name))
+        {
+            return Err(Err::custom(
+                alloc::format!("`{name}` is not a component of the {} type", self.ty.name()),
+                codec,
+            ));
+        }
+
+        let field = |component: &'a Component| Field {
+            component,
+            value: values
+                .iter()
+                .find(|(name, _)| *name == component.name)
+                .filter(|(_, value)| component.default.as_ref() != Some(value))
+                .map(|(_, value)| TypedValue::new(&component.ty, value)),
+        };
+        let missing = |fields: &[Field<'_>]| {
+            fields
+                .iter()
+                .find(|field| field.value.is_none() && !field.component.is_optional())
+                .map(|field| {
+                    Err::custom(
+                        alloc::format!(
+                            "the required component `{}` is missing",
+                            field.component.name
+                        ),
+                        codec,
+                    )
+                })
+        };
+
+        let root: Vec<_> = components.iter().map(field).collect();
+        if let Some(error) = missing(&root) {
+            return Err(error);
+        }
+        let extensions = extensions.map(|additions| {
+            additions
+                .iter()
+                .map(|addition| Extension {
+                    group: matches!(addition, Addition::Group(_)),
+                    fields: addition.components().iter().map(field).collect(),
+                })
+                .collect::<Vec<_>>()
+        });
+        for extension in extensions.iter().flatten() {
+            if extension.group
+                && extension.is_present()
+                && let Some(error) = missing(&extension.fields)
+            {
+                return Err(error);
+            }
+        }
+
+        Ok(Fields { root, extensions })
+    }
+}
+
+/// A value of a constructed or `ENUMERATED` type, taken apart for a codec
+/// that encodes it from its type.
+pub(crate) enum Structure<'a> {
+    /// The components of a `SEQUENCE` value.
+    Sequence(Fields<'a>),
+    /// The components of a `SET` value.
+    Set(Fields<'a>),
+    /// The chosen alternative of a `CHOICE` value.
+    Choice(Alternative<'a>),
+    /// An `ENUMERATED` value.
+    Enumerated(Enumeration<'a>),
+    /// The items of a `SEQUENCE OF` value.
+    SequenceOf(Vec<TypedValue<'a>>),
+    /// The items of a `SET OF` value.
+    SetOf(Vec<TypedValue<'a>>),
+}
+
+/// The components of a `SEQUENCE` or `SET` value.
+pub(crate) struct Fields<'a> {
+    /// The root components, in declaration order.
+    pub(crate) root: Vec<Field<'a>>,
+    /// The extension additions, `None` if the type isn't extensible.
+    pub(crate) extensions: Option<Vec<Extension<'a>>>,
+}
+
+impl<'a> Fields<'a> {
+    /// Returns the present components, root components first.
+    pub(crate) fn present(&self) -> impl Iterator<Item = (&'a Component, TypedValue<'a>)> + '_ {
+        self.root
+            .iter()
+            .chain(
+                self.extensions
+                    .iter()
+                    .flatten()
+                    .flat_map(|extension| &extension.fields),
+            )
+            .filter_map(|field| field.value.map(|value| (field.component, value)))
+    }
+
+    /// Sorts the root components into the canonical order of their tags, the
+    /// order PER and OER encode the components of a `SET` in.
+    pub(crate) fn sort_canonically(&mut self) {
+        self.root
+            .sort_by_key(|field| field.component.ty.canonical_tag());
+    }
+}
+
+/// A component of a `SEQUENCE` or `SET` value.
+#[derive(Clone, Copy)]
+pub(crate) struct Field<'a> {
+    /// The component of the type.
+    pub(crate) component: &'a Component,
+    /// The value of the component, `None` if it's absent or equal to its
+    /// default value.
+    pub(crate) value: Option<TypedValue<'a>>,
+}
+
+/// An extension addition of a `SEQUENCE` or `SET` value.
+pub(crate) struct Extension<'a> {
+    /// Whether the addition is an extension addition group.
+    pub(crate) group: bool,
+    /// The components of the addition.
+    pub(crate) fields: Vec<Field<'a>>,
+}
+
+impl Extension<'_> {
+    /// Returns whether any component of the addition is present.
+    pub(crate) fn is_present(&self) -> bool {
+        self.fields.iter().any(|field| field.value.is_some())
+    }
+}
+
+/// The chosen alternative of a `CHOICE` value.
+pub(crate) struct Alternative<'a> {
+    /// The alternative of the type.
+    pub(crate) component: &'a Component,
+    /// The value of the alternative.
+    pub(crate) value: TypedValue<'a>,
+    /// The index of the alternative among the root alternatives, or among the
+    /// extension additions, in the canonical order of their tags.
+    pub(crate) index: usize,
+    /// The number of root alternatives of the type.
+    pub(crate) root_len: usize,
+    /// Whether the alternative is an extension addition.
+    pub(crate) is_extension: bool,
+    /// Whether the type is extensible.
+    pub(crate) extensible: bool,
+}
+
+/// An `ENUMERATED` value.
+pub(crate) struct Enumeration<'a> {
+    /// The identifier of the enumeration.
+    pub(crate) name: &'a str,
+    /// The value of the enumeration.
+    pub(crate) discriminant: isize,
+    /// The index of the enumeration among the root enumerations in the order
+    /// of their values, or among the additions in declaration order.
+    pub(crate) index: usize,
+    /// The number of root enumerations of the type.
+    pub(crate) root_len: usize,
+    /// Whether the enumeration is an extension addition.
+    pub(crate) is_extension: bool,
+    /// Whether the type is extensible.
+    pub(crate) extensible: bool,
+}
+
+impl PartialEq for TypedValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for TypedValue<'_> {}
+
+impl core::hash::Hash for TypedValue<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl AsnType for TypedValue<'_> {
+    const TAG: Tag = Tag::EOC;
+}
+
+impl Encode for TypedValue<'_> {
+    fn encode<'b, E: crate::Encoder<'b>>(&self, encoder: &mut E) -> Result<(), E::Error> {
+        self.encode_as(encoder, None, Constraints::default(), Identifier::EMPTY)
+    }
+
+    fn encode_with_tag_and_constraints<'b, E: crate::Encoder<'b>>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+        identifier: Identifier,
+    ) -> Result<(), E::Error> {
+        let tag = (tag != Tag::EOC).then_some(tag);
+        self.encode_as(encoder, tag, constraints, identifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Constraint, constraints};
+    use crate::{Decoder as _, Encoder as _};
+    use alloc::vec;
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate")]
+    struct Message {
+        #[rasn(tag(explicit(1)))]
+        id: Integer,
+        #[rasn(tag(2))]
+        name: Option<crate::types::Utf8String>,
+        #[rasn(default)]
+        retries: Integer,
+        payload: Payload,
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", choice)]
+    enum Payload {
+        #[rasn(tag(0), identifier = "raw")]
+        Raw(crate::types::OctetString),
+        #[rasn(tag(1), identifier = "count")]
+        Count(Integer),
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", automatic_tags)]
+    #[non_exhaustive]
+    struct Record {
+        id: Integer,
+        label: Option<crate::types::Utf8String>,
+        level: Level,
+        shape: Shape,
+        #[rasn(extension_addition)]
+        note: Option<crate::types::Utf8String>,
+        #[rasn(extension_addition_group)]
+        extra: Option<Extra>,
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", automatic_tags)]
+    struct Extra {
+        flag: bool,
+        values: crate::types::SequenceOf<Integer>,
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Clone, Copy, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", enumerated)]
+    #[non_exhaustive]
+    enum Level {
+        Low = 0,
+        High = 5,
+        #[rasn(extension_addition)]
+        Max = 9,
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", choice, automatic_tags)]
+    #[non_exhaustive]
+    enum Shape {
+        Point(Integer),
+        Line(crate::types::SequenceOf<Integer>),
+        #[rasn(extension_addition)]
+        Ring(SetOf<Integer>),
+    }
+
+    #[derive(crate::AsnType, crate::Encode, crate::Decode, Debug, PartialEq)]
+    #[rasn(crate_root = "crate", set)]
+    struct Pair {
+        #[rasn(tag(1))]
+        first: Integer,
+        #[rasn(tag(0))]
+        second: Option<bool>,
+    }
+
+    fn tagged(number: u32, inner: Type) -> Type {
+        Type::Tagged {
+            tag: Tag::new_context(number),
+            explicit: false,
+            inner: Box::new(inner),
+        }
+    }
+
+    fn integers(values: &[i32]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|value| Value::Integer((*value).into()))
+            .collect()
+    }
+
+    fn message_type() -> Type {
+        Type::Sequence {
+            components: vec![
+                Component::new(
+                    "id",
+                    Type::Tagged {
+                        tag: Tag::new_context(1),
+                        explicit: true,
+                        inner: Box::new(Type::Integer),
+                    },
+                ),
+                Component::new("name", tagged(2, Type::Utf8String)).optional(),
+                Component::new("retries", Type::Integer).with_default(Value::Integer(0.into())),
+                Component::new(
+                    "payload",
+                    Type::Choice {
+                        alternatives: vec![
+                            Component::new("raw", tagged(0, Type::OctetString)),
+                            Component::new("count", tagged(1, Type::Integer)),
+                        ],
+                        extensions: None,
+                    },
+                ),
+            ],
+            extensions: None,
+        }
+    }
+
+    fn level_type() -> Type {
+        Type::Enumerated {
+            enumerations: vec![("Low".into(), 0), ("High".into(), 5)],
+            extensions: Some(vec![("Max".into(), 9)]),
+        }
+    }
+
+    fn shape_type() -> Type {
+        Type::Choice {
+            alternatives: vec![
+                Component::new("Point", tagged(0, Type::Integer)),
+                Component::new("Line", tagged(1, Type::SequenceOf(Box::new(Type::Integer)))),
+            ],
+            extensions: Some(vec![Component::new(
+                "Ring",
+                tagged(2, Type::SetOf(Box::new(Type::Integer))),
+            )]),
+        }
+    }
+
+    fn record_type() -> Type {
+        Type::Sequence {
+            components: vec![
+                Component::new("id", tagged(0, Type::Integer)),
+                Component::new("label", tagged(1, Type::Utf8String)).optional(),
+                Component::new("level", tagged(2, level_type())),
+                Component::new(
+                    "shape",
+                    Type::Tagged {
+                        tag: Tag::new_context(3),
+                        explicit: true,
+                        inner: Box::new(shape_type()),
+                    },
+                ),
+            ],
+            extensions: Some(vec![
+                Addition::Component(Box::new(
+                    Component::new("note", tagged(4, Type::Utf8String)).optional(),
+                )),
+                Addition::Group(vec![
+                    Component::new("flag", tagged(0, Type::Boolean)),
+                    Component::new(
+                        "values",
+                        tagged(1, Type::SequenceOf(Box::new(Type::Integer))),
+                    ),
+                ]),
+            ]),
+        }
+    }
+
+    fn pair_type() -> Type {
+        Type::Set {
+            components: vec![
+                Component::new("first", tagged(1, Type::Integer)),
+                Component::new("second", tagged(0, Type::Boolean)).optional(),
+            ],
+            extensions: None,
+        }
+    }
+
+    /// Asserts that `value` of `ty` is encoded like `expected` with `codecs`
+    /// and decoded back. XER names the outermost element after the type,
+    /// which a runtime type without a name can't match, so XER and CXER
+    /// encodings of either are instead decoded as the other.
+    fn assert_matches_derived<T: Encode + Decode + PartialEq + fmt::Debug>(
+        ty: &Type,
+        value: &Value,
+        expected: &T,
+        codecs: &[Codec],
+    ) {
+        for codec in codecs.iter().copied() {
+            let encoded = ty.encode(codec, value).unwrap();
+            let derived = codec.encode_to_binary(expected).unwrap();
+            if matches!(codec, Codec::Xer | Codec::Cxer) {
+                assert_eq!(
+                    &codec.decode_from_binary::<T>(&encoded).unwrap(),
+                    expected,
+                    "{codec}"
+                );
+                assert_eq!(&ty.decode(codec, &derived).unwrap(), value, "{codec}");
+            } else {
+                assert_eq!(encoded, derived, "{codec}");
+                assert_eq!(&ty.decode(codec, &encoded).unwrap(), value, "{codec}");
+            }
+        }
+    }
+
+    #[test]
+    fn matches_derived_encoding() {
+        let ty = message_type();
+        let value = Value::Sequence(vec![
+            ("id".into(), Value::Integer(9.into())),
+            (
+                "payload".into(),
+                Value::Choice("count".into(), Box::new(Value::Integer(4.into()))),
+            ),
+        ]);
+        let expected = Message {
+            id: 9.into(),
+            name: None,
+            retries: 0.into(),
+            payload: Payload::Count(4.into()),
+        };
+        assert_matches_derived(
+            &ty,
+            &value,
+            &expected,
+            &[
+                Codec::Ber,
+                Codec::Cer,
+                Codec::Der,
+                Codec::Uper,
+                Codec::Aper,
+                Codec::Oer,
+                Codec::Coer,
+                Codec::Jer,
+                Codec::Xer,
+                Codec::Cxer,
+            ],
+        );
+
+        // A component equal to its default value is omitted.
+        let with_default = Value::Sequence(vec![
+            ("id".into(), Value::Integer(9.into())),
+            ("retries".into(), Value::Integer(0.into())),
+            (
+                "payload".into(),
+                Value::Choice("count".into(), Box::new(Value::Integer(4.into()))),
+            ),
+        ]);
+        assert_eq!(
+            ty.encode(Codec::Uper, &with_default).unwrap(),
+            crate::uper::encode(&expected).unwrap()
+        );
+
+        let Type::Sequence { components, .. } = &ty else {
+            unreachable!()
+        };
+        let payload = &components[3].ty;
+        let raw = Value::Choice(
+            "raw".into(),
+            Box::new(Value::OctetString(vec![1, 2].into())),
+        );
+        assert_eq!(
+            crate::der::encode(&TypedValue::new(payload, &raw)).unwrap(),
+            crate::der::encode(&Payload::Raw(vec![1, 2].into())).unwrap()
+        );
+    }
+
+    #[test]
+    fn extensible_types_match_derived_encoding() {
+        let ty = record_type();
+
+        let root = Value::Sequence(vec![
+            ("id".into(), Value::Integer(7.into())),
+            ("level".into(), Value::Enumerated(5.into())),
+            (
+                "shape".into(),
+                Value::Choice(
+                    "Line".into(),
+                    Box::new(Value::SequenceOf(integers(&[1, 300]))),
+                ),
+            ),
+        ]);
+        let expected = Record {
+            id: 7.into(),
+            label: None,
+            level: Level::High,
+            shape: Shape::Line(vec![1.into(), 300.into()]),
+            note: None,
+            extra: None,
+        };
+        assert_matches_derived(
+            &ty,
+            &root,
+            &expected,
+            &[
+                Codec::Uper,
+                Codec::Oer,
+                Codec::Coer,
+                Codec::Jer,
+                Codec::Xer,
+                Codec::Cxer,
+            ],
+        );
+
+        let extended = Value::Sequence(vec![
+            ("id".into(), Value::Integer(7.into())),
+            ("label".into(), Value::Utf8String("a".into())),
+            ("level".into(), Value::Enumerated(9.into())),
+            (
+                "shape".into(),
+                Value::Choice(
+                    "Ring".into(),
+                    Box::new(Value::SetOf(SetOf::from_vec(integers(&[2, 1])))),
+                ),
+            ),
+            ("note".into(), Value::Utf8String("n".into())),
+            ("flag".into(), Value::Boolean(true)),
+            ("values".into(), Value::SequenceOf(integers(&[-1]))),
+        ]);
+        let expected = Record {
+            id: 7.into(),
+            label: Some("a".into()),
+            level: Level::Max,
+            shape: Shape::Ring(SetOf::from_vec(vec![2.into(), 1.into()])),
+            note: Some("n".into()),
+            extra: Some(Extra {
+                flag: true,
+                values: vec![(-1).into()],
+            }),
+        };
+        // Derived JER and XER encodings write the components of an extension
+        // addition group in a member of their own rather than as components
+        // of the SEQUENCE, so only the binary codecs are compared.
+        assert_matches_derived(
+            &ty,
+            &extended,
+            &expected,
+            &[Codec::Uper, Codec::Oer, Codec::Coer],
+        );
+
+        // The components of a SET are encoded in the canonical order of their
+        // tags. Derived APER encodings of a SET aren't aligned like those of a
+        // SEQUENCE, so APER is left to the round trips below.
+        let pair = Value::Set(vec![
+            ("first".into(), Value::Integer(3.into())),
+            ("second".into(), Value::Boolean(false)),
+        ]);
+        let expected = Pair {
+            first: 3.into(),
+            second: Some(false),
+        };
+        assert_matches_derived(
+            &pair_type(),
+            &pair,
+            &expected,
+            &[
+                Codec::Der,
+                Codec::Uper,
+                Codec::Oer,
+                Codec::Coer,
+                Codec::Jer,
+                Codec::Xer,
+                Codec::Cxer,
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips_with_every_codec() {
+        let values = [
+            (
+                message_type(),
+                Value::Sequence(vec![
+                    ("id".into(), Value::Integer((-3).into())),
+                    ("name".into(), Value::Utf8String("x".into())),
+                    ("retries".into(), Value::Integer(2.into())),
+                    (
+                        "payload".into(),
+                        Value::Choice("raw".into(), Box::new(Value::OctetString(vec![].into()))),
+                    ),
+                ]),
+            ),
+            (
+                record_type(),
+                Value::Sequence(vec![
+                    ("id".into(), Value::Integer(1.into())),
+                    ("level".into(), Value::Enumerated(0.into())),
+                    (
+                        "shape".into(),
+                        Value::Choice("Point".into(), Box::new(Value::Integer(2.into()))),
+                    ),
+                    ("flag".into(), Value::Boolean(false)),
+                    ("values".into(), Value::SequenceOf(Vec::new())),
+                ]),
+            ),
+            (
+                Type::SequenceOf(Box::new(level_type())),
+                Value::SequenceOf(vec![
+                    Value::Enumerated(9.into()),
+                    Value::Enumerated(0.into()),
+                ]),
+            ),
+            (
+                Type::SetOf(Box::new(shape_type())),
+                Value::SetOf(SetOf::from_vec(vec![
+                    Value::Choice("Point".into(), Box::new(Value::Integer(1.into()))),
+                    Value::Choice(
+                        "Ring".into(),
+                        Box::new(Value::SetOf(SetOf::from_vec(integers(&[5])))),
+                    ),
+                ])),
+            ),
+            (
+                Type::SequenceOf(Box::new(pair_type())),
+                Value::SequenceOf(vec![
+                    Value::Set(vec![("first".into(), Value::Integer(0.into()))]),
+                    Value::Set(vec![
+                        ("first".into(), Value::Integer(1.into())),
+                        ("second".into(), Value::Boolean(true)),
+                    ]),
+                ]),
+            ),
+            (
+                Type::SequenceOf(Box::new(Type::Boolean)),
+                Value::SequenceOf(vec![Value::Boolean(true), Value::Boolean(false)]),
+            ),
+        ];
+
+        for (ty, value) in &values {
+            for codec in [
+                Codec::Ber,
+                Codec::Cer,
+                Codec::Der,
+                Codec::Uper,
+                Codec::Aper,
+                Codec::Oer,
+                Codec::Coer,
+                Codec::Jer,
+                Codec::Xer,
+                Codec::Cxer,
+            ] {
+                let encoded = ty.encode(codec, value).unwrap();
+                assert_eq!(&ty.decode(codec, &encoded).unwrap(), value, "{codec}");
+            }
+            assert!(ty.encode(Codec::Avn, value).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_values() {
+        let ty = message_type();
+        let missing = Value::Sequence(vec![("id".into(), Value::Integer(9.into()))]);
+        let unknown = Value::Sequence(vec![("other".into(), Value::Null)]);
+        let mismatched = Value::Sequence(vec![
+            ("id".into(), Value::Boolean(true)),
+            (
+                "payload".into(),
+                Value::Choice("count".into(), Box::new(Value::Integer(4.into()))),
+            ),
+        ]);
+
+        for value in [missing, unknown, mismatched] {
+            assert!(crate::der::encode(&TypedValue::new(&ty, &value)).is_err());
+        }
+    }
+
+    #[test]
+    fn constrained_types() {
+        const RANGE: Constraints =
+            Constraints::new(&[Constraint::Value(constraints::Extensible::new(
+                constraints::Value::new(constraints::Bounded::const_new(0, 255)),
+            ))]);
+        let ty = Type::SequenceOf(Box::new(Type::Constrained {
+            constraints: RANGE,
+            inner: Box::new(Type::Integer),
+        }));
+        let value = Value::SequenceOf(vec![Value::Integer(1.into()), Value::Integer(200.into())]);
+        let typed = TypedValue::new(&ty, &value);
+
+        assert_eq!(crate::uper::encode(&typed).unwrap(), [0x02, 0x01, 0xC8]);
+        assert_eq!(crate::aper::encode(&typed).unwrap(), [0x02, 0x01, 0xC8]);
+        assert_eq!(
+            crate::oer::encode(&typed).unwrap(),
+            [0x01, 0x02, 0x01, 0xC8]
+        );
+        assert!(
+            crate::uper::encode(&TypedValue::new(
+                &ty,
+                &Value::SequenceOf(vec![Value::Integer(256.into())])
+            ))
+            .is_err()
+        );
+    }
+}
@@ -11,12 +11,37 @@ const NAN_TAG: &str = "NOT-A-NUMBER";
 const PLUS_INFINITY_VALUE: &str = "INF";
 const MINUS_INFINITY_VALUE: &str = "-INF";
 const NAN_VALUE: &str = "NaN";
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+const XSI_TYPE_ATTRIBUTE: &str = "xsi:type";
+
+/// Returns the XML name of `field`, honouring the `NAME AS` instruction.
+fn xml_name(field: &crate::types::fields::Field) -> &'static str {
+    field.xer.name.unwrap_or(field.name)
+}
+
+/// Formats a finite REAL value in its CXER form: a mantissa with a single
+/// non-zero digit before the decimal point and no trailing zeros, followed by
+/// `E` and an exponent without leading zeros or `+` sign. Zero is encoded as
+/// `0`, negative zero as `-0`.
+fn canonical_real(value: f64) -> alloc::string::String {
+    if value == 0.0 {
+        if value.is_sign_negative() {
+            "-0".into()
+        } else {
+            "0".into()
+        }
+    } else {
+        alloc::format!("{value:E}")
+    }
+}
 
 /// Attempts to decode `T` from `input` using XER.
 /// # Errors
 /// Returns error specific to XER decoder if decoding is not possible.
 pub fn decode<T: crate::Decode>(input: &[u8]) -> Result<T, crate::error::DecodeError> {
-    T::decode(&mut de::Decoder::new(input)?)
+    let mut decoder = de::Decoder::new(input)?;
+    decoder.check_namespace(T::XER_INSTRUCTIONS)?;
+    T::decode(&mut decoder)
 }
 
 /// Attempts to encode `value` to XER.
@@ -25,7 +50,7 @@ pub fn decode<T: crate::Decode>(input: &[u8]) -> Result<T, crate::error::DecodeE
 pub fn encode<T: crate::Encode>(
     value: &T,
 ) -> Result<alloc::vec::Vec<u8>, crate::error::EncodeError> {
-    let mut encoder = enc::Encoder::new();
+    let mut encoder = enc::Encoder::new().with_namespace(T::XER_INSTRUCTIONS);
     value.encode(&mut encoder)?;
     Ok(encoder.finish())
 }
@@ -285,10 +310,24 @@ mod tests {
         "UTF8String",
         "012345"
     );
+    round_trip!(
+        empty_utf8_string,
+        Utf8String,
+        String::new(),
+        "UTF8String",
+        ""
+    );
+    round_trip!(
+        empty_visible_string,
+        VisibleString,
+        VisibleString::default(),
+        "VisibleString",
+        ""
+    );
     round_trip!(
         object_identifier,
         ObjectIdentifier,
-        ObjectIdentifier::from(Oid::const_new(&[1, 654, 2, 1])),
+        ObjectIdentifier::from(crate::oid!([1, 654, 2, 1])),
         "OBJECT_IDENTIFIER",
         "1.654.2.1"
     );
@@ -377,7 +416,7 @@ mod tests {
             inner: InnerTestA {
                 hidden: Some(false),
             },
-            oid: Some(ObjectIdentifier::from(Oid::const_new(&[1, 8270, 4, 1]))),
+            oid: Some(ObjectIdentifier::from(crate::oid!([1, 8270, 4, 1]))),
         },
         "NestedTestA",
         "<wine><true /></wine><grappa>00010203</grappa><inner><hidden><false /></hidden></inner><oid>1.8270.4.1</oid>"
@@ -402,7 +441,7 @@ mod tests {
             wine: true,
             grappa: vec![0, 1, 2, 3].into(),
             inner: InnerTestA { hidden: None },
-            oid: Some(ObjectIdentifier::from(Oid::const_new(&[1, 8270, 4, 1])))
+            oid: Some(ObjectIdentifier::from(crate::oid!([1, 8270, 4, 1])))
         },
         "NestedTestA",
         "<wine><true /></wine><grappa>00010203</grappa><inner /><oid>1.8270.4.1</oid>"
@@ -545,7 +584,7 @@ mod tests {
                 inner: InnerTestA {
                     hidden: Some(false),
                 },
-                oid: Some(ObjectIdentifier::from(Oid::const_new(&[1, 8270, 4, 1]))),
+                oid: Some(ObjectIdentifier::from(crate::oid!([1, 8270, 4, 1]))),
             }
         },
         "SequenceWithChoice",
@@ -601,4 +640,143 @@ mod tests {
         assert!(decoded.contains(&first));
         assert!(decoded.contains(&second));
     }
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq)]
+    #[rasn(automatic_tags)]
+    #[rasn(
+        crate_root = "crate",
+        xer(namespace = "urn:example:orders", prefix = "ord")
+    )]
+    struct Order {
+        #[rasn(xer(attribute))]
+        id: Integer,
+        #[rasn(xer(attribute))]
+        express: bool,
+        #[rasn(xer(name = "item-codes", list))]
+        item_codes: SequenceOf<Integer>,
+        #[rasn(xer(default_for_empty = "zwei"))]
+        priority: EnumType,
+        #[rasn(xer(untagged))]
+        note: Utf8String,
+    }
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq, Copy, Clone)]
+    #[rasn(enumerated, automatic_tags)]
+    #[rasn(crate_root = "crate", xer(text))]
+    enum Colour {
+        #[rasn(identifier = "red")]
+        Red,
+        #[rasn(identifier = "green")]
+        Green,
+    }
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq)]
+    #[rasn(automatic_tags)]
+    #[rasn(
+        crate_root = "crate",
+        xer(namespace = "urn:example:parts", prefix = "p")
+    )]
+    struct Part {
+        code: Integer,
+        colour: Colour,
+    }
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq)]
+    #[rasn(automatic_tags)]
+    #[rasn(crate_root = "crate")]
+    struct Shipment {
+        main: Part,
+        spares: SequenceOf<Part>,
+        colours: SequenceOf<Colour>,
+    }
+
+    #[test]
+    fn nested_type_instructions() {
+        let shipment = Shipment {
+            main: Part {
+                code: 1.into(),
+                colour: Colour::Red,
+            },
+            spares: vec![Part {
+                code: 2.into(),
+                colour: Colour::Green,
+            }],
+            colours: vec![Colour::Green, Colour::Red],
+        };
+        let encoded = String::from_utf8(encode(&shipment).unwrap()).unwrap();
+        assert_eq!(
+            concat!(
+                r#"<Shipment><p:main xmlns:p="urn:example:parts"><code>1</code><colour>red</colour></p:main>"#,
+                r#"<spares><p:Part xmlns:p="urn:example:parts"><code>2</code><colour>green</colour></p:Part></spares>"#,
+                "<colours><Colour>green</Colour><Colour>red</Colour></colours></Shipment>"
+            ),
+            encoded
+        );
+        assert_eq!(shipment, decode::<Shipment>(encoded.as_bytes()).unwrap());
+    }
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq)]
+    #[rasn(choice, automatic_tags)]
+    #[rasn(crate_root = "crate", xer(use_type))]
+    enum Shape {
+        Circle(Integer),
+        Square(Integer),
+    }
+
+    #[derive(AsnType, Debug, Encode, Decode, PartialEq)]
+    #[rasn(automatic_tags)]
+    #[rasn(crate_root = "crate")]
+    struct Drawing {
+        shape: Shape,
+    }
+
+    #[test]
+    fn encoding_instructions() {
+        let order = Order {
+            id: 7.into(),
+            express: true,
+            item_codes: vec![1.into(), 2.into(), 3.into()],
+            priority: EnumType::Second,
+            note: "Handle with care".into(),
+        };
+        let encoded = String::from_utf8(encode(&order).unwrap()).unwrap();
+        assert_eq!(
+            r#"<ord:Order xmlns:ord="urn:example:orders" id="7" express="true"><item-codes>1 2 3</item-codes><priority><zwei /></priority>Handle with care</ord:Order>"#,
+            encoded
+        );
+        assert_eq!(order, decode::<Order>(encoded.as_bytes()).unwrap());
+        assert_eq!(
+            order,
+            decode::<Order>(
+                br#"<ord:Order xmlns:ord="urn:example:orders" express="true" id="7"><priority/>Handle with care<item-codes>1 2 3</item-codes></ord:Order>"#
+            )
+            .unwrap()
+        );
+        assert!(
+            decode::<Order>(
+                br#"<ord:Order xmlns:ord="urn:example:other" id="7" express="true"><item-codes/><priority/></ord:Order>"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn use_type_choice() {
+        let square = Drawing {
+            shape: Shape::Square(4.into()),
+        };
+        let encoded = String::from_utf8(encode(&square).unwrap()).unwrap();
+        assert_eq!(
+            r#"<Drawing><shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Square">4</shape></Drawing>"#,
+            encoded
+        );
+        assert_eq!(square, decode::<Drawing>(encoded.as_bytes()).unwrap());
+
+        let circle = Drawing {
+            shape: Shape::Circle(2.into()),
+        };
+        let encoded = String::from_utf8(encode(&circle).unwrap()).unwrap();
+        assert_eq!("<Drawing><shape>2</shape></Drawing>", encoded);
+        assert_eq!(circle, decode::<Drawing>(encoded.as_bytes()).unwrap());
+    }
 }
@@ -5,17 +5,27 @@ use crate::alloc::string::ToString;
 use core::{borrow::Borrow, f64};
 
 use xml_no_std::{
-    ParserConfig, attribute::Attribute, common::XmlVersion, name::OwnedName, namespace::Namespace,
+    ParserConfig,
+    attribute::{Attribute, OwnedAttribute},
+    common::XmlVersion,
+    name::OwnedName,
+    namespace::Namespace,
     reader::XmlEvent,
 };
 
-use crate::{Decode, error::*, types::*, xer::BOOLEAN_TRUE_TAG};
+use crate::{
+    Decode,
+    error::*,
+    types::*,
+    value::{self, Type},
+    xer::BOOLEAN_TRUE_TAG,
+};
 
-use self::fields::Field;
+use self::{fields::Field, instructions::XerInstructions};
 
 use super::{
     BOOLEAN_FALSE_TAG, MINUS_INFINITY_TAG, MINUS_INFINITY_VALUE, NAN_TAG, NAN_VALUE,
-    PLUS_INFINITY_TAG, PLUS_INFINITY_VALUE,
+    PLUS_INFINITY_TAG, PLUS_INFINITY_VALUE, XSI_NAMESPACE, canonical_real,
 };
 
 const OPTIONAL_ITEM_NOT_PRESENT: &str = "§_NOT_PRESENT_§";
@@ -66,30 +76,25 @@ macro_rules! tag {
 macro_rules! decode_string {
     ($this:ident, $tryfrom:path, $tag:path, $needed:literal) => {{
         tag!(StartElement, $this)?;
-        let value = match $this.next_element() {
-            Some(XmlEvent::Characters(value)) => $tryfrom(value).map_err(|e| {
+        let value = $this.string_value($needed).and_then(|value| {
+            $tryfrom(value).map_err(|e| {
                 DecodeError::string_conversion_failed(
                     $tag,
                     alloc::format!("Error transforming string: {e:?}"),
                     crate::Codec::Xer,
                 )
-            }),
-            Some(elem) => Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
-                needed: $needed,
-                found: alloc::format!("{elem:?}"),
-            })),
-            None => Err(error!(EndOfXmlInput)),
-        };
+            })
+        });
         tag!(EndElement, $this)?;
         value
     }};
 }
 
 macro_rules! decode_time {
-    ($this:ident, $decode_fn:path) => {{
+    ($this:ident, $decode_fn:expr) => {{
         tag!(StartElement, $this)?;
         let value = match $this.next_element() {
-            Some(XmlEvent::Characters(value)) => $decode_fn(value),
+            Some(XmlEvent::Characters(value)) => ($decode_fn)(value),
             Some(elem) => Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
                 needed: "Time value",
                 found: alloc::format!("{elem:?}"),
@@ -147,15 +152,55 @@ impl<I: IntoIterator<Item = XmlEvent>> From<I> for XerElement {
     }
 }
 
+/// Options for configuring the [`Decoder`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DecoderOptions {
+    canonical: bool,
+}
+
+impl DecoderOptions {
+    /// Returns the default configuration for Basic XER.
+    #[must_use]
+    pub const fn xer() -> Self {
+        Self { canonical: false }
+    }
+
+    /// Returns the configuration for Canonical XER (CXER). The decoder rejects
+    /// any input that is not in canonical form.
+    #[must_use]
+    pub const fn cxer() -> Self {
+        Self { canonical: true }
+    }
+
+    /// Returns the currently selected codec.
+    #[must_use]
+    pub const fn current_codec(&self) -> crate::Codec {
+        if self.canonical {
+            crate::Codec::Cxer
+        } else {
+            crate::Codec::Xer
+        }
+    }
+}
+
 /// Decoder for decoding XER-conforming ASN.1 data
 pub struct Decoder {
     stack: alloc::vec::Vec<XerElement>,
     in_list: bool,
+    options: DecoderOptions,
 }
 
 impl Decoder {
     /// Creates a new Decoder from the given input
     pub fn new(input: &[u8]) -> Result<Self, <Decoder as crate::de::Decoder>::Error> {
+        Self::new_with_options(input, DecoderOptions::xer())
+    }
+
+    /// Creates a new Decoder from the given input with the given `options`.
+    pub fn new_with_options(
+        input: &[u8],
+        options: DecoderOptions,
+    ) -> Result<Self, <Decoder as crate::de::Decoder>::Error> {
         let mut reader = ParserConfig::default().create_reader(input.iter());
         let next = reader.next().map_err(|e| error!(XmlParser, "{e:?}"))?;
         check_prolog(&next)?;
@@ -165,9 +210,21 @@ impl Decoder {
             if next == XmlEvent::EndDocument {
                 break 'read_xml;
             }
+            if options.canonical {
+                check_canonical_event(&next)?;
+            }
             elements.push_back(next);
         }
-        elements.try_into()
+        let mut decoder = Self::try_from(elements)?;
+        decoder.options = options;
+        Ok(decoder)
+    }
+
+    /// Creates a decoder for the events of a nested element, inheriting the options of `self`.
+    fn nested(&self, events: alloc::collections::VecDeque<XmlEvent>) -> Result<Self, DecodeError> {
+        let mut decoder = Self::try_from(events)?;
+        decoder.options = self.options;
+        Ok(decoder)
     }
 
     fn next_element(&mut self) -> Option<XmlEvent> {
@@ -191,43 +248,107 @@ impl Decoder {
         self.stack.last().and_then(XerElement::peek)
     }
 
-    fn sort_by_field_tag_order(
-        &mut self,
-        field_indices: &[(usize, Field)],
-    ) -> Result<(), DecodeError> {
-        let field_names = field_indices.iter().map(|(_, f)| f.name).collect();
-        self.sort_by_field_name_order(field_names)
+    /// Consumes the characters of a string element, which has none when the
+    /// string is empty.
+    fn string_value(&mut self, needed: &'static str) -> Result<alloc::string::String, DecodeError> {
+        if let Some(XmlEvent::EndElement { .. }) = self.peek() {
+            return Ok(alloc::string::String::new());
+        }
+        match self.next_element() {
+            Some(XmlEvent::Characters(value)) => Ok(value),
+            Some(elem) => Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
+                needed,
+                found: alloc::format!("{elem:?}"),
+            })),
+            None => Err(error!(EndOfXmlInput)),
+        }
     }
 
-    fn sort_by_field_name_order(
+    /// Consumes the next start element and returns its attributes.
+    fn start_element_attributes(&mut self) -> Result<alloc::vec::Vec<OwnedAttribute>, DecodeError> {
+        match self.next_element() {
+            Some(XmlEvent::StartElement { attributes, .. }) => Ok(attributes),
+            Some(elem) => Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
+                needed: "StartElement",
+                found: alloc::format!("{elem:?}"),
+            })),
+            None => Err(error!(EndOfXmlInput)),
+        }
+    }
+
+    /// Orders the elements on the stack by `fields`, applying the E-XER
+    /// encoding instructions of the fields.
+    ///
+    /// Components with the `ATTRIBUTE` instruction are read from `attributes`,
+    /// elements and text that do not belong to any other component are
+    /// collected for the `UNTAGGED` component, and empty elements are filled in
+    /// with the value of the `DEFAULT-FOR-EMPTY` instruction.
+    fn sort_by_fields(
         &mut self,
-        field_names: alloc::vec::Vec<&str>,
+        fields: &[Field],
+        attributes: &[OwnedAttribute],
     ) -> Result<(), DecodeError> {
         let stack = core::mem::take(&mut self.stack);
-        let mut reordered = stack.into_iter().try_fold(
-            alloc::collections::BTreeMap::<usize, XerElement>::new(),
-            |mut acc, elem| {
-                let index = match elem.peek() {
-                    Some(XmlEvent::StartElement { name, .. }) => field_names
-                        .iter()
-                        .enumerate()
-                        .find_map(|(i, f)| (*f == name.local_name.as_str()).then_some(i))
-                        .ok_or_else(|| {
-                            XerDecodeErrorKind::XmlTag {
-                                needed: name.local_name.clone(),
-                                found: "nothing".into(),
-                            }
-                            .into()
-                        }),
-                    e => Err(error!(XmlParser, "Expected opening tag, found {e:?}")),
-                };
-                index.map(|i| {
-                    acc.insert(i, elem);
-                    acc
-                })
-            },
-        )?;
-        for i in (0..field_names.len()).rev() {
+        let untagged = fields.iter().position(|f| f.xer.untagged);
+        let mut untagged_events = alloc::collections::VecDeque::new();
+        let mut reordered = alloc::collections::BTreeMap::<usize, XerElement>::new();
+        for elem in stack {
+            let index = match elem.peek() {
+                Some(XmlEvent::StartElement { name, .. }) => {
+                    let index = fields.iter().position(|f| {
+                        !f.xer.attribute
+                            && !f.xer.untagged
+                            && super::xml_name(f) == name.local_name.as_str()
+                    });
+                    if index.is_none() && untagged.is_none() {
+                        return Err(XerDecodeErrorKind::XmlTag {
+                            needed: name.local_name.clone(),
+                            found: "nothing".into(),
+                        }
+                        .into());
+                    }
+                    index
+                }
+                Some(XmlEvent::Characters(_)) => None,
+                e => return Err(error!(XmlParser, "Expected opening tag, found {e:?}")),
+            };
+            match index {
+                Some(i) => {
+                    reordered.insert(i, elem);
+                }
+                None if untagged.is_some() => untagged_events.extend(elem.events),
+                None => {}
+            }
+        }
+        for (i, field) in fields.iter().enumerate().filter(|(_, f)| f.xer.attribute) {
+            if let Some(attribute) = attributes
+                .iter()
+                .find(|a| a.name.prefix.is_none() && a.name.local_name == super::xml_name(field))
+            {
+                reordered.insert(
+                    i,
+                    synthetic_element(super::xml_name(field), Some(attribute.value.clone())),
+                );
+            }
+        }
+        if let Some(i) = untagged.filter(|_| !untagged_events.is_empty()) {
+            let name = super::xml_name(&fields[i]);
+            let mut elem = synthetic_element(name, None);
+            let end = elem.events.pop_back();
+            elem.events.extend(untagged_events);
+            elem.events.extend(end);
+            reordered.insert(i, elem);
+        }
+        for (i, field) in fields.iter().enumerate() {
+            if let Some(default) = field.xer.default_for_empty
+                && let Some(elem) = reordered.get_mut(&i)
+                && elem.events.len() == 2
+            {
+                elem.events
+                    .insert(1, XmlEvent::Characters(default.to_string()));
+            }
+        }
+        for i in (0..fields.len()).rev() {
             self.stack.push(reordered.remove(&i).unwrap_or(XerElement {
                 events: alloc::vec![XmlEvent::Characters(OPTIONAL_ITEM_NOT_PRESENT.into())].into(),
             }));
@@ -235,6 +356,68 @@ impl Decoder {
         Ok(())
     }
 
+    /// Checks that the outermost element is in the E-XER `NAMESPACE` of `instructions`.
+    pub(crate) fn check_namespace(&self, instructions: XerInstructions) -> Result<(), DecodeError> {
+        let Some(namespace) = instructions.namespace else {
+            return Ok(());
+        };
+        match self.peek() {
+            Some(XmlEvent::StartElement { name, .. })
+                if name.namespace.as_deref() == Some(namespace) =>
+            {
+                Ok(())
+            }
+            elem => Err(error!(
+                SpecViolation,
+                "Expected an element in namespace `{namespace}`, found {elem:?}"
+            )),
+        }
+    }
+
+    /// Decodes a `CHOICE` with the `USE-TYPE` instruction, whose alternative is
+    /// identified by the `xsi:type` attribute of its element. Without the
+    /// attribute the value is of the first alternative.
+    fn decode_use_type_choice<D: crate::types::DecodeChoice>(
+        &mut self,
+        attributes: &[OwnedAttribute],
+    ) -> Result<D, DecodeError> {
+        let index = match attributes.iter().find(|attribute| {
+            attribute.name.local_name == "type"
+                && (attribute.name.namespace.as_deref() == Some(XSI_NAMESPACE)
+                    || attribute.name.prefix.as_deref() == Some("xsi"))
+        }) {
+            Some(attribute) => {
+                // The type name may be qualified with a namespace prefix
+                let name = attribute.value.rsplit(':').next().unwrap_or_default();
+                D::IDENTIFIERS
+                    .iter()
+                    .position(|id| *id == name)
+                    .ok_or_else(|| error!(SpecViolation, "Unknown `xsi:type` value `{name}`"))?
+            }
+            None => 0,
+        };
+        let tag = variants::Variants::from_slice(
+            &[D::VARIANTS, D::EXTENDED_VARIANTS.unwrap_or(&[])].concat(),
+        )
+        .get(index)
+        .cloned()
+        .unwrap_or(Tag::EOC);
+        let mut content = self
+            .stack
+            .pop()
+            .ok_or_else(|| error!(EndOfXmlInput))?
+            .events;
+        // Remove the end of the choice element
+        content.pop_back();
+        let mut alternative =
+            synthetic_element(D::IDENTIFIERS.get(index).copied().unwrap_or_default(), None);
+        let end = alternative.events.pop_back();
+        alternative.events.extend(content);
+        alternative.events.extend(end);
+        let mut variant_decoder = self.nested(alternative.events)?;
+        D::from_tag(&mut variant_decoder, tag)
+    }
+
     fn into_list_decoder(mut self) -> Self {
         self.in_list = true;
         self
@@ -242,10 +425,42 @@ impl Decoder {
 
     fn from_stack_elems<E: IntoIterator<Item = XmlEvent>, I: IntoIterator<Item = E>>(
         elems: I,
+        options: DecoderOptions,
     ) -> Self {
         Decoder {
             stack: elems.into_iter().map(|i| XerElement::from(i)).collect(),
             in_list: false,
+            options,
+        }
+    }
+
+    /// Decodes a value of `ty`, a type only known at runtime, from the events
+    /// of `elem`, which is an item of a list written without the element of
+    /// its type if `in_list` is set.
+    fn typed_element(
+        &self,
+        ty: &Type,
+        elem: XerElement,
+        in_list: bool,
+    ) -> Result<value::Value, DecodeError> {
+        let mut decoder = Decoder {
+            stack: alloc::vec![elem],
+            in_list,
+            options: self.options,
+        };
+        ty.decode_value(&mut decoder, None, Constraints::default())
+    }
+
+    /// Rejects `text` in canonical mode unless it is equal to the canonical
+    /// representation of the decoded value.
+    fn check_canonical_text(&self, text: &str, canonical: &str) -> Result<(), DecodeError> {
+        if self.options.canonical && text != canonical {
+            Err(error!(
+                SpecViolation,
+                "CXER value `{text}` is not in its canonical form `{canonical}`"
+            ))
+        } else {
+            Ok(())
         }
     }
 }
@@ -274,6 +489,12 @@ impl TryFrom<alloc::collections::VecDeque<XmlEvent>> for Decoder {
                         namespace,
                     });
                 }
+                (None, XmlEvent::Characters(characters)) => {
+                    // Text outside of any element is content of an `UNTAGGED` component
+                    stack.push(XerElement {
+                        events: alloc::vec![XmlEvent::Characters(characters)].into(),
+                    });
+                }
                 (None, _) => {
                     continue 'xml_elements;
                 }
@@ -317,10 +538,100 @@ impl TryFrom<alloc::collections::VecDeque<XmlEvent>> for Decoder {
         Ok(Self {
             stack,
             in_list: false,
+            options: DecoderOptions::default(),
         })
     }
 }
 
+/// Creates the events of an element that is not present as such in the XML
+/// input, like the value of an attribute.
+fn synthetic_element(name: &str, text: Option<alloc::string::String>) -> XerElement {
+    let name = OwnedName {
+        local_name: name.to_string(),
+        namespace: None,
+        prefix: None,
+    };
+    let mut events = alloc::collections::VecDeque::new();
+    events.push_back(XmlEvent::StartElement {
+        name: name.clone(),
+        attributes: alloc::vec::Vec::new(),
+        namespace: Namespace::empty(),
+    });
+    events.extend(text.map(XmlEvent::Characters));
+    events.push_back(XmlEvent::EndElement { name });
+    XerElement { events }
+}
+
+fn check_canonical_event(event: &XmlEvent) -> Result<(), DecodeError> {
+    match event {
+        XmlEvent::Whitespace(_) => Err(error!(
+            SpecViolation,
+            "CXER encodings shall not contain white-space between XML elements"
+        )),
+        XmlEvent::Comment(_) => Err(error!(
+            SpecViolation,
+            "CXER encodings shall not contain XML comments"
+        )),
+        XmlEvent::ProcessingInstruction { .. } => Err(error!(
+            SpecViolation,
+            "CXER encodings shall not contain XML processing instructions"
+        )),
+        XmlEvent::CData(_) => Err(error!(
+            SpecViolation,
+            "CXER encodings shall not contain CDATA sections"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the components of the `SET OF` element in `events` are in
+/// the ascending order of their encodings that CXER requires, rendering each
+/// component the way the CXER encoder writes it.
+fn check_canonical_set_of_order(
+    events: &alloc::collections::VecDeque<XmlEvent>,
+) -> Result<(), DecodeError> {
+    let content = events.range(1..events.len().saturating_sub(1));
+    let mut encodings = alloc::vec::Vec::new();
+    let mut writer = None;
+    let mut depth = 0usize;
+    for event in content {
+        match event {
+            XmlEvent::StartElement { .. } => depth += 1,
+            XmlEvent::Characters(text) if depth == 0 => {
+                encodings.extend(text.split_ascii_whitespace().map(ToString::to_string));
+                continue;
+            }
+            _ => {}
+        }
+        let current = writer.get_or_insert_with(|| {
+            xml_no_std::EmitterConfig::new()
+                .write_document_declaration(false)
+                .pad_self_closing(false)
+                .create_writer()
+        });
+        if let Some(event) = event.as_writer_event() {
+            current
+                .write(event)
+                .map_err(|e| error!(XmlParser, "{e:?}"))?;
+        }
+        if let XmlEvent::EndElement { .. } = event {
+            depth = depth.saturating_sub(1);
+            if depth == 0
+                && let Some(finished) = writer.take()
+            {
+                encodings.push(finished.into_inner());
+            }
+        }
+    }
+    if encodings.windows(2).any(|pair| pair[0] > pair[1]) {
+        return Err(error!(
+            SpecViolation,
+            "CXER requires the components of a SET OF to be in ascending order of their encodings"
+        ));
+    }
+    Ok(())
+}
+
 fn check_prolog(prolog: &XmlEvent) -> Result<(), DecodeError> {
     if let XmlEvent::StartDocument {
         version, encoding, ..
@@ -348,7 +659,7 @@ impl crate::Decoder for Decoder {
     type Error = DecodeError;
 
     fn codec(&self) -> crate::Codec {
-        crate::Codec::Xer
+        self.options.current_codec()
     }
 
     fn decode_any(&mut self, _tag: Tag) -> Result<crate::types::Any, Self::Error> {
@@ -406,7 +717,10 @@ impl crate::Decoder for Decoder {
                 }
             }
         }
-        Ok(Any::new(xml_writer.into_inner().into_bytes()))
+        Ok(Any::with_codec(
+            xml_writer.into_inner().into_bytes(),
+            self.codec(),
+        ))
     }
 
     fn decode_bit_string(
@@ -415,6 +729,15 @@ impl crate::Decoder for Decoder {
         __constraints: Constraints,
     ) -> Result<crate::types::BitString, Self::Error> {
         tag!(StartElement, self)?;
+        if self.options.canonical
+            && let Some(XmlEvent::Characters(s)) = self.peek()
+            && s.chars().any(char::is_whitespace)
+        {
+            return Err(error!(
+                SpecViolation,
+                "CXER bitstring values shall not contain white-space"
+            ));
+        }
         let value = value_or_empty!(self, parse_bitstring_value, "`1` or `0`");
         tag!(EndElement, self)?;
         value
@@ -452,6 +775,10 @@ impl crate::Decoder for Decoder {
                     }))
                 }
             }
+            Some(XmlEvent::Characters(c)) if self.options.canonical => Err(error!(
+                SpecViolation,
+                "CXER boolean values shall be encoded as empty-element tags, found `{c}`"
+            )),
             Some(XmlEvent::Characters(c)) if c == "0" => Ok(false),
             Some(XmlEvent::Characters(c)) if c == "1" => Ok(true),
             Some(XmlEvent::Characters(c)) if c == "false" => Ok(false),
@@ -469,7 +796,9 @@ impl crate::Decoder for Decoder {
     }
 
     fn decode_enumerated<E: Enumerated>(&mut self, __tag: Tag) -> Result<E, Self::Error> {
-        if !self.in_list {
+        // Items of a list are written without their element, unless they are text.
+        let in_element = !self.in_list || E::XER_INSTRUCTIONS.text;
+        if in_element {
             tag!(StartElement, self)?;
         }
         let value = match self.next_element() {
@@ -486,6 +815,14 @@ impl crate::Decoder for Decoder {
                     }))
                 }
             }
+            Some(XmlEvent::Characters(c))
+                if self.options.canonical && !E::XER_INSTRUCTIONS.text =>
+            {
+                Err(error!(
+                    SpecViolation,
+                    "CXER enumerated values shall be encoded as empty-element tags, found `{c}`"
+                ))
+            }
             Some(XmlEvent::Characters(c)) => E::from_identifier(&c).ok_or(DecodeError::from(
                 XerDecodeErrorKind::XmlTypeMismatch {
                     needed: "enumerated value",
@@ -498,7 +835,7 @@ impl crate::Decoder for Decoder {
             })),
             None => Err(error!(EndOfXmlInput)),
         };
-        if !self.in_list {
+        if in_element {
             tag!(EndElement, self)?;
         }
         value
@@ -513,6 +850,7 @@ impl crate::Decoder for Decoder {
         let value = match self.next_element() {
             Some(XmlEvent::Characters(value)) => {
                 if let Ok(int) = value.parse::<i128>() {
+                    self.check_canonical_text(&value, &int.to_string())?;
                     int.try_into()
                         .map_err(|_| DecodeError::integer_overflow(I::WIDTH, crate::Codec::Xer))
                 } else {
@@ -565,21 +903,18 @@ impl crate::Decoder for Decoder {
         D: Constructed<RC, EC>,
         F: FnOnce(&mut Self) -> Result<D, Self::Error>,
     {
-        tag!(StartElement, self)?;
-        let mut field_names = D::FIELDS
+        let attributes = self.start_element_attributes()?;
+        let fields = D::FIELDS
             .iter()
-            .map(|f| f.name)
-            .collect::<alloc::vec::Vec<&str>>();
-        if let Some(extended_fields) = D::EXTENDED_FIELDS {
-            field_names.extend(extended_fields.iter().map(|f| f.name));
-        }
+            .chain(D::EXTENDED_FIELDS.iter().flat_map(|fields| fields.iter()))
+            .collect::<alloc::vec::Vec<_>>();
         let events = self
             .stack
             .pop()
             .ok_or_else(|| error!(EndOfXmlInput))?
             .events;
-        let mut sequence_decoder = Decoder::try_from(events)?;
-        sequence_decoder.sort_by_field_name_order(field_names)?;
+        let mut sequence_decoder = self.nested(events)?;
+        sequence_decoder.sort_by_fields(&fields, &attributes)?;
         (decode_fn)(&mut sequence_decoder)
     }
 
@@ -591,11 +926,16 @@ impl crate::Decoder for Decoder {
         decode_sequence_or_set_items(self)
     }
 
-    fn decode_set_of<D: crate::Decode + Eq + core::hash::Hash>(
+    fn decode_set_of<D: crate::Decode>(
         &mut self,
         _t: Tag,
         _c: Constraints,
     ) -> Result<SetOf<D>, Self::Error> {
+        if self.options.canonical
+            && let Some(elem) = self.stack.last()
+        {
+            check_canonical_set_of_order(&elem.events)?;
+        }
         let items = decode_sequence_or_set_items::<D>(self)?;
         Ok(SetOf::from_vec(items))
     }
@@ -607,6 +947,12 @@ impl crate::Decoder for Decoder {
     ) -> Result<T, Self::Error> {
         tag!(StartElement, self)?;
         let value = match self.peek() {
+            Some(XmlEvent::Characters(s)) if self.options.canonical && !is_canonical_hex(s) => {
+                return Err(error!(
+                    SpecViolation,
+                    "CXER octet string values shall only contain the upper-case hexadecimal digits, found `{s}`"
+                ));
+            }
             Some(XmlEvent::Characters(s)) => parse_octetstring_value(s),
             Some(XmlEvent::EndElement { .. }) => return Ok(<T as From<&'b [u8]>>::from(&[])),
             Some(elem) => {
@@ -628,14 +974,7 @@ impl crate::Decoder for Decoder {
         _constraints: Constraints,
     ) -> Result<crate::types::Utf8String, Self::Error> {
         tag!(StartElement, self)?;
-        let value = match self.next_element() {
-            Some(XmlEvent::Characters(value)) => Ok(value),
-            Some(elem) => Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
-                needed: "UTF8 string value",
-                found: alloc::format!("{elem:?}"),
-            })),
-            None => Err(error!(EndOfXmlInput)),
-        };
+        let value = self.string_value("UTF8 string value");
         tag!(EndElement, self)?;
         value
     }
@@ -744,17 +1083,30 @@ impl crate::Decoder for Decoder {
     }
 
     fn decode_utc_time(&mut self, _tag: Tag) -> Result<crate::types::UtcTime, Self::Error> {
-        decode_time!(self, crate::ber::de::Decoder::parse_any_utc_time_string)
+        if self.options.canonical {
+            decode_time!(self, |value: alloc::string::String| {
+                crate::ber::de::Decoder::parse_canonical_utc_time_string(&value)
+            })
+        } else {
+            decode_time!(self, crate::ber::de::Decoder::parse_any_utc_time_string)
+        }
     }
 
     fn decode_generalized_time(
         &mut self,
         _tag: Tag,
     ) -> Result<crate::types::GeneralizedTime, Self::Error> {
-        decode_time!(
-            self,
-            crate::ber::de::Decoder::parse_any_generalized_time_string
-        )
+        if self.options.canonical {
+            decode_time!(
+                self,
+                crate::ber::de::Decoder::parse_canonical_generalized_time_string
+            )
+        } else {
+            decode_time!(
+                self,
+                crate::ber::de::Decoder::parse_any_generalized_time_string
+            )
+        }
     }
 
     fn decode_set<const RC: usize, const EC: usize, FIELDS, SET, D, F>(
@@ -769,7 +1121,7 @@ impl crate::Decoder for Decoder {
         D: Fn(&mut Self::AnyDecoder<RC, EC>, usize, Tag) -> Result<FIELDS, Self::Error>,
         F: FnOnce(alloc::vec::Vec<FIELDS>) -> Result<SET, Self::Error>,
     {
-        tag!(StartElement, self)?;
+        let attributes = self.start_element_attributes()?;
         let events = self
             .stack
             .pop()
@@ -781,8 +1133,14 @@ impl crate::Decoder for Decoder {
             .collect::<alloc::vec::Vec<_>>();
         let mut fields = alloc::vec![];
         field_indices.sort_by_key(|(_, a)| a.tag_tree.smallest_tag());
-        let mut sequence_decoder = Decoder::try_from(events)?;
-        sequence_decoder.sort_by_field_tag_order(&field_indices)?;
+        let mut sequence_decoder = self.nested(events)?;
+        sequence_decoder.sort_by_fields(
+            &field_indices
+                .iter()
+                .map(|(_, f)| *f)
+                .collect::<alloc::vec::Vec<_>>(),
+            &attributes,
+        )?;
         for (index, field) in field_indices {
             fields.push((decode_fn)(&mut sequence_decoder, index, field.tag)?);
         }
@@ -807,7 +1165,10 @@ impl crate::Decoder for Decoder {
         D: crate::types::DecodeChoice,
     {
         if !self.in_list {
-            tag!(StartElement, self)?;
+            let attributes = self.start_element_attributes()?;
+            if D::XER_INSTRUCTIONS.use_type {
+                return self.decode_use_type_choice(&attributes);
+            }
         }
         match self.peek() {
             Some(XmlEvent::StartElement { name, .. }) => {
@@ -828,7 +1189,7 @@ impl crate::Decoder for Decoder {
                     .pop()
                     .ok_or_else(|| error!(EndOfXmlInput))?
                     .events;
-                let mut variant_decoder = Decoder::try_from(events)?;
+                let mut variant_decoder = self.nested(events)?;
                 D::from_tag(&mut variant_decoder, tag)
             }
             elem => Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
@@ -896,6 +1257,11 @@ impl crate::Decoder for Decoder {
         tag!(StartElement, self)?;
         let value = match self.next_element() {
             Some(XmlEvent::Characters(value)) => match value.as_str().parse::<f64>() {
+                Ok(real) if self.options.canonical && value != canonical_real(real) => Err(error!(
+                    SpecViolation,
+                    "CXER real value `{value}` is not in its canonical form `{}`",
+                    canonical_real(real)
+                )),
                 Ok(real) => R::try_from_float(real).ok_or_else(|| {
                     DecodeError::integer_overflow(R::BYTE_WIDTH as u32, crate::Codec::Xer)
                 }),
@@ -938,12 +1304,21 @@ impl crate::Decoder for Decoder {
         self.decode_optional()
     }
 
+    fn check_encoded_default(&self, is_default: bool) -> Result<(), Self::Error> {
+        if self.options.canonical && is_default {
+            Err(error!(
+                SpecViolation,
+                "CXER encodings shall not contain a DEFAULT component with its default value"
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     fn decode_date(&mut self, _tag: Tag) -> Result<Date, Self::Error> {
-        decode_time!(
-            self,
-            crate::ber::de::Decoder::parse_any_generalized_time_string
-        )
-        .map(|dt| dt.date_naive())
+        decode_time!(self, |value: alloc::string::String| {
+            crate::ber::de::Decoder::parse_date_string(&value)
+        })
     }
 
     fn decode_extension_addition_with_explicit_tag_and_constraints<D>(
@@ -967,6 +1342,123 @@ impl crate::Decoder for Decoder {
     {
         self.decode_extension_addition()
     }
+
+    fn decode_typed_value(
+        &mut self,
+        ty: &Type,
+        _: Tag,
+        _: Constraints,
+    ) -> Result<value::Value, Self::Error> {
+        let codec = self.codec();
+        let in_list = core::mem::take(&mut self.in_list);
+        if self.options.canonical
+            && matches!(ty, Type::SetOf(_))
+            && let Some(elem) = self.stack.last()
+        {
+            check_canonical_set_of_order(&elem.events)?;
+        }
+        // Items of a list are written without the element of their type, so
+        // the element of the item is its content.
+        let mut content = if in_list {
+            core::mem::take(&mut self.stack)
+        } else {
+            tag!(StartElement, self)?;
+            let events = self
+                .stack
+                .pop()
+                .ok_or_else(|| error!(EndOfXmlInput))?
+                .events;
+            self.nested(events)?.stack
+        };
+        Ok(match ty {
+            Type::Sequence { .. } | Type::Set { .. } => {
+                // Elements which aren't components of the type, such as the
+                // extension additions of later versions of it, are ignored.
+                let values = ty
+                    .all_components()
+                    .map(|component| {
+                        content
+                            .iter()
+                            .position(|elem| element_name(elem) == Some(component.name.as_str()))
+                            .map(|i| self.typed_element(&component.ty, content.remove(i), false))
+                            .transpose()
+                    })
+                    .collect::<Result<_, _>>()?;
+                ty.components_value(values, codec)?
+            }
+            Type::Choice { .. } => {
+                let elem = content
+                    .into_iter()
+                    .find(|elem| element_name(elem).is_some())
+                    .ok_or_else(|| error!(XmlParser, "Expected an alternative of the CHOICE"))?;
+                let name = element_name(&elem).unwrap_or_default();
+                let alternative = ty
+                    .alternatives()
+                    .find(|alternative| alternative.name == name)
+                    .ok_or_else(|| XerDecodeErrorKind::XmlTypeMismatch {
+                        needed: "alternative of the CHOICE type",
+                        found: name.into(),
+                    })?;
+                value::Value::Choice(
+                    alternative.name.clone(),
+                    alloc::boxed::Box::new(self.typed_element(&alternative.ty, elem, false)?),
+                )
+            }
+            Type::Enumerated { .. } => {
+                let identifier = match content.first().and_then(XerElement::peek) {
+                    Some(XmlEvent::StartElement { name, .. }) => name.local_name.clone(),
+                    Some(XmlEvent::Characters(c)) if self.options.canonical => {
+                        return Err(error!(
+                            SpecViolation,
+                            "CXER enumerated values shall be encoded as empty-element tags, found `{c}`"
+                        ));
+                    }
+                    Some(XmlEvent::Characters(c)) => c.trim().into(),
+                    elem => {
+                        return Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
+                            needed: "enumerated value",
+                            found: alloc::format!("{elem:?}"),
+                        }));
+                    }
+                };
+                match ty.enumeration_named(&identifier) {
+                    Some(value) => value,
+                    None => {
+                        return Err(DecodeError::from(XerDecodeErrorKind::XmlTypeMismatch {
+                            needed: "enumerated value",
+                            found: identifier,
+                        }));
+                    }
+                }
+            }
+            Type::SequenceOf(item) | Type::SetOf(item) => {
+                let items: alloc::vec::Vec<_> = content
+                    .into_iter()
+                    .filter(|elem| element_name(elem).is_some())
+                    .map(|elem| self.typed_element(item, elem, item.is_xml_value_list_item()))
+                    .collect::<Result<_, _>>()?;
+                match ty {
+                    Type::SetOf(_) => value::Value::SetOf(SetOf::from_vec(items)),
+                    _ => value::Value::SequenceOf(items),
+                }
+            }
+            _ => {
+                return Err(crate::de::Error::custom(
+                    alloc::format!("{} is not a constructed type", ty.name()),
+                    codec,
+                ));
+            }
+        })
+    }
+}
+
+/// Returns the name of the element whose events are in `elem`, `None` if
+/// it's text.
+fn element_name(elem: &XerElement) -> Option<&str> {
+    match elem.peek() {
+        Some(XmlEvent::StartElement { name, .. }) => Some(name.local_name.as_str()),
+        _ => None,
+    }
 }
 
 fn parse_bitstring_value(val: &str) -> Result<BitString, DecodeError> {
@@ -985,6 +1477,11 @@ fn parse_bitstring_value(val: &str) -> Result<BitString, DecodeError> {
     })))
 }
 
+fn is_canonical_hex(val: &str) -> bool {
+    val.chars()
+        .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+}
+
 fn parse_octetstring_value(val: &str) -> Result<alloc::vec::Vec<u8>, DecodeError> {
     (0..val.len())
         .step_by(2)
@@ -994,21 +1491,7 @@ fn parse_octetstring_value(val: &str) -> Result<alloc::vec::Vec<u8>, DecodeError
 }
 
 fn parse_object_identifier(val: &str) -> Result<ObjectIdentifier, DecodeError> {
-    let arcs = val
-        .split('.')
-        .try_fold(alloc::vec::Vec::<u32>::new(), |mut acc, curr| {
-            curr.parse()
-                .map(|i| {
-                    acc.push(i);
-                    acc
-                })
-                .map_err(|_| {
-                    DecodeError::from(XerDecodeErrorKind::InvalidInput {
-                        details: "Invalid Object Identifier value.",
-                    })
-                })
-        })?;
-    ObjectIdentifier::new(arcs).ok_or_else(|| {
+    ObjectIdentifier::from_dotted(val).map_err(|_| {
         XerDecodeErrorKind::InvalidInput {
             details: "Invalid Object Identifier value.",
         }
@@ -1053,14 +1536,14 @@ fn decode_sequence_or_set_items<D: Decode>(
             ]
         }));
         let _ = decoder.stack.pop();
-        Decoder::from_stack_elems(elems)
+        Decoder::from_stack_elems(elems, decoder.options)
     } else {
-        decoder
+        let events = decoder
             .stack
             .pop()
             .ok_or_else(|| error!(EndOfXmlInput))?
-            .events
-            .try_into()?
+            .events;
+        decoder.nested(events)?
     }
     .into_list_decoder();
 
@@ -1392,6 +1875,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
     fn generalized_time() {
         let mut decoder =
             Decoder::new(r#"<TimeType>20001231235959.999+0000</TimeType>"#.as_bytes()).unwrap();
@@ -1409,6 +1893,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
     fn utc_time() {
         let mut decoder = Decoder::new(r#"<TimeType>991231235900Z</TimeType>"#.as_bytes()).unwrap();
 
@@ -2,7 +2,7 @@
 use core::{fmt::Write, ops::Deref};
 
 use crate::{
-    AsnType,
+    AsnType, Encode,
     alloc::{
         string::{String, ToString},
         vec::Vec,
@@ -10,9 +10,10 @@ use crate::{
     types::{
         Any, BitStr, BmpString, Constraints, Date, Enumerated, GeneralString, GeneralizedTime,
         GraphicString, Ia5String, Identifier, IntegerType, NumericString, OctetString, Oid,
-        PrintableString, RealType, SetOf, Tag, TeletexString, UtcTime, VisibleString,
-        fields::Fields,
+        PrintableString, RealType, SetOf, Tag, TagTree, TeletexString, UtcTime, VisibleString,
+        fields::Fields, instructions::XerInstructions, time_backend,
     },
+    value::{TypedValue, typed::Structure},
 };
 use alloc::borrow::Cow;
 use num_bigint::BigInt;
@@ -23,34 +24,132 @@ use xml_no_std::{
 
 use crate::error::{EncodeError, XerEncodeErrorKind};
 
-use super::{BOOLEAN_FALSE_TAG, BOOLEAN_TRUE_TAG, MINUS_INFINITY_TAG, NAN_TAG, PLUS_INFINITY_TAG};
+use super::{
+    BOOLEAN_FALSE_TAG, BOOLEAN_TRUE_TAG, MINUS_INFINITY_TAG, NAN_TAG, PLUS_INFINITY_TAG,
+    XSI_NAMESPACE, XSI_TYPE_ATTRIBUTE, canonical_real,
+};
+
+/// Name of the element that encloses values which are encoded as text.
+const TEXT_VALUE_TAG: &str = "text";
 
 macro_rules! wrap_in_tags {
     ($this:ident, $tag:expr, $inner:ident, $($args:expr)*) => {{
-        let xml_tag = match $this.entering_list_item_type {
-            true => $tag,
-            false => $this.field_tag_stack.pop().unwrap_or($tag),
+        let field = match $this.entering_list_item_type {
+            true => FieldTag::new($tag),
+            false => $this.field_tag_stack.pop().unwrap_or(FieldTag::new($tag)),
         };
-        $this.write_start_element(&xml_tag)?;
-        $this.$inner($($args),*)?;
-        $this.write_end_element(&xml_tag)
+        let field = $this.qualify(field);
+        if field.instructions.attribute {
+            let value = $this.encode_as_text(|encoder| encoder.$inner($($args),*))?;
+            $this.pending_attributes.push((field.name, value));
+            Ok(())
+        } else if field.instructions.untagged {
+            $this.$inner($($args),*)
+        } else {
+            $this.write_start_element(&field.name)?;
+            $this.$inner($($args),*)?;
+            $this.write_end_element(&field.name)
+        }
     }};
 }
 
 macro_rules! try_wrap_in_tags {
     ($this:ident, $inner:ident, $($args:expr)*) => {{
-        let xml_tag = $this.field_tag_stack
+        let field = $this.field_tag_stack
             .pop()
             .ok_or_else(|| XerEncodeErrorKind::MissingIdentifier)?;
-        $this.write_start_element(&xml_tag)?;
+        let field = $this.qualify(field);
+        $this.write_start_element(&field.name)?;
         $this.$inner($($args),*)?;
-        $this.write_end_element(&xml_tag)
+        $this.write_end_element(&field.name)
     }};
 }
 
+/// The XML name of a component of a `SEQUENCE` or `SET`, together with its
+/// E-XER encoding instructions.
+struct FieldTag {
+    name: Cow<'static, str>,
+    instructions: XerInstructions,
+}
+
+impl FieldTag {
+    /// Placeholder for the items of a `LIST`, which are written as untagged text.
+    const LIST_ITEM: Self = Self {
+        name: Cow::Borrowed(""),
+        instructions: XerInstructions {
+            untagged: true,
+            text: true,
+            ..XerInstructions::NONE
+        },
+    };
+
+    fn new(name: Cow<'static, str>) -> Self {
+        Self {
+            name,
+            instructions: XerInstructions::NONE,
+        }
+    }
+
+    fn from_fields<const RL: usize, const EL: usize>(
+        fields: &Fields<RL>,
+        extended_fields: Option<&Fields<EL>>,
+    ) -> Vec<Self> {
+        let mut tags = fields
+            .iter()
+            .chain(extended_fields.into_iter().flat_map(Fields::iter))
+            .map(|field| Self {
+                name: Cow::Borrowed(super::xml_name(&field)),
+                instructions: field.xer,
+            })
+            .collect::<Vec<_>>();
+        tags.reverse();
+        tags
+    }
+
+    /// Whether the component is written outside of its own child element.
+    fn is_attribute_or_untagged(&self) -> bool {
+        self.instructions.attribute || self.instructions.untagged
+    }
+}
+
+/// Options for configuring the [`Encoder`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EncoderOptions {
+    canonical: bool,
+}
+
+impl EncoderOptions {
+    /// Returns the default configuration for Basic XER.
+    #[must_use]
+    pub const fn xer() -> Self {
+        Self { canonical: false }
+    }
+
+    /// Returns the default configuration for Canonical XER (CXER).
+    #[must_use]
+    pub const fn cxer() -> Self {
+        Self { canonical: true }
+    }
+
+    /// Returns the currently selected codec.
+    #[must_use]
+    pub const fn current_codec(&self) -> crate::Codec {
+        if self.canonical {
+            crate::Codec::Cxer
+        } else {
+            crate::Codec::Xer
+        }
+    }
+}
+
 /// Encoder for creating ASN.1 encodings using XML encoding rules (XER).
 pub struct Encoder {
-    field_tag_stack: Vec<Cow<'static, str>>,
+    options: EncoderOptions,
+    field_tag_stack: Vec<FieldTag>,
+    pending_attributes: Vec<(Cow<'static, str>, String)>,
+    type_attributes: Vec<(Cow<'static, str>, String)>,
+    namespace: XerInstructions,
+    namespace_declaration: Option<(Cow<'static, str>, String)>,
     writer: EventWriter,
     end_index_of_first_tag: Option<usize>,
     start_index_of_last_tag: usize,
@@ -68,11 +167,28 @@ impl Encoder {
     /// Creates a new XER encoder instance
     #[must_use]
     pub fn new() -> Self {
+        Self::new_with_options(EncoderOptions::xer())
+    }
+
+    /// Creates a new encoder instance with the given `options`.
+    ///
+    /// With [`EncoderOptions::cxer`] the encoder produces Canonical XER:
+    /// empty-element tags are written without padding, REAL values use the
+    /// canonical `mantissa E exponent` form and the components of a `SET OF`
+    /// are sorted by their encodings.
+    #[must_use]
+    pub fn new_with_options(options: EncoderOptions) -> Self {
         Self {
+            options,
             writer: xml_no_std::EmitterConfig::new()
                 .write_document_declaration(false)
+                .pad_self_closing(!options.canonical)
                 .create_writer(),
             field_tag_stack: Vec::new(),
+            pending_attributes: Vec::new(),
+            type_attributes: Vec::new(),
+            namespace: XerInstructions::NONE,
+            namespace_declaration: None,
             end_index_of_first_tag: None,
             start_index_of_last_tag: 0,
             entering_choice_value: false,
@@ -80,6 +196,14 @@ impl Encoder {
         }
     }
 
+    /// Declares the E-XER `NAMESPACE` of `instructions` on the outermost
+    /// element of the encoding, qualifying its name with the given prefix.
+    #[must_use]
+    pub fn with_namespace(mut self, instructions: XerInstructions) -> Self {
+        self.namespace = instructions;
+        self
+    }
+
     /// Returns the encoded XER value as UTF-8 bytes
     #[must_use]
     pub fn finish(self) -> Vec<u8> {
@@ -104,18 +228,47 @@ impl Encoder {
     }
 
     fn write_start_element<S: AsRef<str>>(&mut self, value: S) -> Result<(), EncodeError> {
+        self.write_start_element_with_attributes(value, &[])
+    }
+
+    fn write_start_element_with_attributes<S: AsRef<str>>(
+        &mut self,
+        value: S,
+        attributes: &[(Cow<'static, str>, String)],
+    ) -> Result<(), EncodeError> {
         if self.entering_choice_value {
             self.entering_choice_value = false;
         } else if self.entering_list_item_type {
             self.entering_list_item_type = false;
         }
+        let namespace_declaration = self.namespace_declaration.take();
         self.write(XmlEvent::StartElement {
             name: Name::local(value.as_ref()),
-            attributes: Cow::Borrowed(&[]),
+            attributes: namespace_declaration
+                .iter()
+                .chain(attributes)
+                .map(|(name, value)| Attribute::new(Name::local(name), value))
+                .collect(),
             namespace: Namespace::empty().borrow(),
         })
     }
 
+    /// Writes an element whose content was produced by another encoder.
+    fn write_element_with_content<S: AsRef<str>>(
+        &mut self,
+        value: S,
+        attributes: &[(Cow<'static, str>, String)],
+        content: &str,
+    ) -> Result<(), EncodeError> {
+        self.write_start_element_with_attributes(&value, attributes)?;
+        if !content.is_empty() {
+            // Prompt the XML writer to close the start tag before appending.
+            self.write_empty()?;
+            self.writer.inner_mut().push_str(content);
+        }
+        self.write_end_element(value)
+    }
+
     fn write_end_element<S: AsRef<str>>(&mut self, value: S) -> Result<(), EncodeError> {
         self.write(XmlEvent::EndElement {
             name: Some(Name::local(value.as_ref())),
@@ -129,9 +282,110 @@ impl Encoder {
     fn erase_outer_tags(&mut self) {
         if let Some(end_index) = self.end_index_of_first_tag {
             let inner = self.writer.inner_mut();
-            inner.drain(self.start_index_of_last_tag..);
-            inner.drain(..=end_index);
+            if end_index < self.start_index_of_last_tag {
+                inner.drain(self.start_index_of_last_tag..);
+                inner.drain(..=end_index);
+            } else {
+                // An empty-element tag has no content
+                inner.clear();
+            }
+        }
+    }
+
+    /// Returns the content between the outer tags of the encoded value.
+    fn into_content(self) -> String {
+        let end_index = self.start_index_of_last_tag;
+        let mut output = self.writer.into_inner();
+        match self.end_index_of_first_tag {
+            Some(start_index) if start_index < end_index => {
+                output.truncate(end_index);
+                output.split_off(start_index + 1)
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Encodes a value as unescaped character data, as it is used for XML
+    /// attributes and the items of a `LIST`.
+    fn encode_as_text(
+        &self,
+        encode: impl FnOnce(&mut Self) -> Result<(), EncodeError>,
+    ) -> Result<String, EncodeError> {
+        let mut config = xml_no_std::EmitterConfig::new().write_document_declaration(false);
+        config.perform_escaping = false;
+        let mut encoder = Self {
+            writer: config.create_writer(),
+            ..Self::new_with_options(self.options)
+        };
+        encoder.write_start_element(TEXT_VALUE_TAG)?;
+        encode(&mut encoder)?;
+        encoder.write_end_element(TEXT_VALUE_TAG)?;
+        Ok(encoder.into_content())
+    }
+
+    /// Returns the XML name and encoding instructions of the next value, a
+    /// value of a type with the given `instructions`.
+    ///
+    /// Components of a `SEQUENCE` or `SET` use the name of their field, every
+    /// other value uses `identifier`. The outermost element is qualified with
+    /// the namespace set by [`Self::with_namespace`].
+    fn next_tag(
+        &mut self,
+        identifier: Identifier,
+        instructions: XerInstructions,
+    ) -> Result<FieldTag, EncodeError> {
+        let field = match self.entering_list_item_type {
+            true => None,
+            false => self.field_tag_stack.pop(),
+        };
+        let mut field = match field {
+            Some(field) => field,
+            None => FieldTag::new(Cow::Borrowed(
+                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
+            )),
+        };
+        field.instructions = field.instructions.with_type_instructions(instructions);
+        Ok(self.qualify(field))
+    }
+
+    /// Qualifies the element of `field` with the namespace set by
+    /// [`Self::with_namespace`] for the outermost element, or else with the
+    /// `NAMESPACE` of its type.
+    fn qualify(&mut self, mut field: FieldTag) -> FieldTag {
+        let mut namespace = core::mem::take(&mut self.namespace);
+        if namespace.namespace.is_none() && !field.is_attribute_or_untagged() {
+            namespace = field.instructions;
         }
+        if let Some(uri) = namespace.namespace {
+            let declaration = match namespace.prefix {
+                Some(prefix) => {
+                    field.name = Cow::Owned(alloc::format!("{prefix}:{}", field.name));
+                    Cow::Owned(alloc::format!("xmlns:{prefix}"))
+                }
+                None => Cow::Borrowed("xmlns"),
+            };
+            self.namespace_declaration = Some((declaration, uri.to_string()));
+        }
+        field
+    }
+
+    /// Whether the next `BOOLEAN` or `ENUMERATED` value is written as text.
+    fn next_is_text(&self) -> bool {
+        !self.entering_list_item_type
+            && self
+                .field_tag_stack
+                .last()
+                .is_some_and(|field| field.instructions.text || field.instructions.attribute)
+    }
+
+    /// Encodes an item of a `SEQUENCE OF` or `SET OF` in the element of its
+    /// type, qualified with the `NAMESPACE` of the type.
+    fn encode_list_item<E: crate::Encode>(&mut self, item: &E) -> Result<(), EncodeError> {
+        self.set_entering_list_item_type(true);
+        self.namespace = E::XER_INSTRUCTIONS;
+        item.encode(self)?;
+        self.namespace = XerInstructions::NONE;
+        Ok(())
     }
 
     fn entering_choice_value(&mut self) {
@@ -150,7 +404,7 @@ impl crate::Encoder<'_> for Encoder {
     type AnyEncoder<'this, const R: usize, const E: usize> = Encoder;
 
     fn codec(&self) -> crate::Codec {
-        crate::Codec::Xer
+        self.options.current_codec()
     }
 
     fn encode_any(
@@ -159,6 +413,7 @@ impl crate::Encoder<'_> for Encoder {
         value: &Any,
         _identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
+        value.check_codec(self.codec())?;
         try_wrap_in_tags!(self, write_any, value)
     }
 
@@ -170,6 +425,13 @@ impl crate::Encoder<'_> for Encoder {
     ) -> Result<Self::Ok, Self::Error> {
         if self.entering_list_item_type {
             self.write_bool(value)
+        } else if self.next_is_text() {
+            wrap_in_tags!(
+                self,
+                Cow::Borrowed(identifier.or(bool::IDENTIFIER).unwrap()),
+                write_bool_text,
+                value
+            )
         } else {
             wrap_in_tags!(
                 self,
@@ -201,8 +463,20 @@ impl crate::Encoder<'_> for Encoder {
         value: &E,
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        if self.entering_list_item_type {
+        if self.entering_list_item_type && !E::XER_INSTRUCTIONS.text {
             self.write_enumerated(value)
+        } else if E::XER_INSTRUCTIONS.text || self.next_is_text() {
+            wrap_in_tags!(
+                self,
+                Cow::Borrowed(
+                    identifier
+                        .or(E::IDENTIFIER)
+                        .0
+                        .ok_or(XerEncodeErrorKind::MissingIdentifier)?
+                ),
+                write_enumerated_text,
+                value
+            )
         } else {
             wrap_in_tags!(
                 self,
@@ -221,7 +495,7 @@ impl crate::Encoder<'_> for Encoder {
     fn encode_object_identifier(
         &mut self,
         _tag: Tag,
-        value: &[u32],
+        value: &Oid,
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
         wrap_in_tags!(
@@ -466,18 +740,12 @@ impl crate::Encoder<'_> for Encoder {
             return value.encode_with_tag_and_constraints(self, V::TAG, V::CONSTRAINTS, identifier);
         }
 
-        // Read current xml tag
-        let xml_tag = match self.entering_list_item_type {
-            true => Cow::Borrowed(identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?),
-            false => self.field_tag_stack.pop().unwrap_or(Cow::Borrowed(
-                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
-            )),
-        };
+        let field = self.next_tag(identifier, V::XER_INSTRUCTIONS)?;
 
         if self.entering_list_item_type {
             // List items that are `CHOICE` delegate types are encoded without their outer tags
             // We use a new encoder to write the inner choice value of the delegate: <ChoiceType><option /></ChoiceType>
-            let mut inner_encoder = Self::new();
+            let mut inner_encoder = Self::new_with_options(self.options);
             // Then we write an empty string to prompt the XML writer to close any uncloses start tags.
             self.write_empty()?;
             value.encode(&mut inner_encoder)?;
@@ -488,19 +756,25 @@ impl crate::Encoder<'_> for Encoder {
             Ok(())
         } else {
             // Special handling is needed for `CHOICE` delegate types
-            // First we write the start tag of the delegate: <Delegate
-            self.write_start_element(&xml_tag)?;
-            // Then we write an empty string to prompt the XML writer to close the start tag: <Delegate>
-            self.write_empty()?;
             // We use a new encoder to write the inner choice value: <ChoiceType><option /></ChoiceType>
-            let mut inner_encoder = Self::new();
+            let mut inner_encoder = Self::new_with_options(self.options);
             value.encode(&mut inner_encoder)?;
+            // `USE-TYPE` choices identify their alternative with attributes of the delegate
+            let attributes = core::mem::take(&mut inner_encoder.type_attributes);
             // We then remove the outer tag pair: <option />
             inner_encoder.erase_outer_tags();
-            // ...append the output of the inner encoder: <Delegate><option />
-            self.append(&mut inner_encoder);
-            // ...and finally close the delegate: <Delegate><option /></Delegate>
-            self.write_end_element(&xml_tag)
+            if field.instructions.untagged {
+                // Untagged delegates are written without any tags of their own: <option />
+                self.append(&mut inner_encoder);
+                Ok(())
+            } else {
+                // ...and finally wrap the output of the inner encoder: <Delegate><option /></Delegate>
+                self.write_element_with_content(
+                    &field.name,
+                    &attributes,
+                    inner_encoder.writer.inner_mut(),
+                )
+            }
         }
     }
 
@@ -514,30 +788,9 @@ impl crate::Encoder<'_> for Encoder {
         C: crate::types::Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
-        let xml_tag = match self.entering_list_item_type {
-            true => Cow::Borrowed(identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?),
-            false => self.field_tag_stack.pop().unwrap_or(Cow::Borrowed(
-                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
-            )),
-        };
-        self.write_start_element(&xml_tag)?;
-
-        let mut ids = C::FIELDS
-            .identifiers()
-            .chain(
-                C::EXTENDED_FIELDS
-                    .as_ref()
-                    .map(Fields::identifiers)
-                    .into_iter()
-                    .flatten(),
-            )
-            .map(|id| Cow::<str>::Owned(id.to_string()))
-            .collect::<Vec<_>>();
-        ids.reverse();
-        ids.into_iter().for_each(|id| self.field_tag_stack.push(id));
-        encoder_scope(self)?;
-
-        self.write_end_element(xml_tag)
+        let field = self.next_tag(identifier, XerInstructions::NONE)?;
+        let field_tags = FieldTag::from_fields(&C::FIELDS, C::EXTENDED_FIELDS.as_ref());
+        self.encode_constructed(field, field_tags, encoder_scope)
     }
 
     fn encode_sequence_of<E: crate::Encode>(
@@ -547,18 +800,40 @@ impl crate::Encoder<'_> for Encoder {
         _constraints: Constraints,
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        let xml_tag = match self.entering_list_item_type {
-            true => Cow::Borrowed(identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?),
-            false => self.field_tag_stack.pop().unwrap_or(Cow::Borrowed(
-                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
-            )),
-        };
-        self.write_start_element(&xml_tag)?;
+        let field = self.next_tag(identifier, XerInstructions::NONE)?;
+        if field.instructions.list || field.instructions.attribute {
+            let items = value
+                .iter()
+                .map(|elem| {
+                    self.encode_as_text(|encoder| {
+                        encoder.field_tag_stack.push(FieldTag::LIST_ITEM);
+                        elem.encode(encoder)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .join(" ");
+            if field.instructions.attribute {
+                self.pending_attributes.push((field.name, items));
+                return Ok(());
+            }
+            self.write_start_element(&field.name)?;
+            if !items.is_empty() {
+                self.write(XmlEvent::Characters(&items))?;
+            }
+            return self.write_end_element(&field.name);
+        }
+        if !field.instructions.untagged {
+            self.write_start_element(&field.name)?;
+        }
         for elem in value {
-            self.set_entering_list_item_type(true);
-            elem.encode(self)?;
+            self.encode_list_item(elem)?;
+        }
+        self.set_entering_list_item_type(false);
+        if field.instructions.untagged {
+            Ok(())
+        } else {
+            self.write_end_element(field.name)
         }
-        self.write_end_element(xml_tag)
     }
 
     fn encode_set<'b, const RL: usize, const EL: usize, C, F>(
@@ -571,49 +846,44 @@ impl crate::Encoder<'_> for Encoder {
         C: crate::types::Constructed<RL, EL>,
         F: FnOnce(&mut Self::AnyEncoder<'b, RL, EL>) -> Result<(), Self::Error>,
     {
-        let xml_tag = match self.entering_list_item_type {
-            true => Cow::Borrowed(identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?),
-            false => self.field_tag_stack.pop().unwrap_or(Cow::Borrowed(
-                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
-            )),
-        };
-        self.write_start_element(&xml_tag)?;
-
-        let mut ids = C::FIELDS
-            .identifiers()
-            .chain(
-                C::EXTENDED_FIELDS
-                    .as_ref()
-                    .map(Fields::identifiers)
-                    .into_iter()
-                    .flatten(),
-            )
-            .map(|id| Cow::<str>::Owned(id.to_string()))
-            .collect::<Vec<_>>();
-        ids.reverse();
-        ids.into_iter().for_each(|id| self.field_tag_stack.push(id));
-        value(self)?;
-
-        self.write_end_element(xml_tag)
+        let field = self.next_tag(identifier, XerInstructions::NONE)?;
+        let field_tags = FieldTag::from_fields(&C::FIELDS, C::EXTENDED_FIELDS.as_ref());
+        self.encode_constructed(field, field_tags, value)
     }
 
-    fn encode_set_of<E: crate::Encode + Eq + core::hash::Hash>(
+    fn encode_set_of<E: crate::Encode>(
         &mut self,
         _tag: Tag,
         value: &SetOf<E>,
         _constraints: Constraints,
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        let xml_tag = match self.entering_list_item_type {
-            true => Cow::Borrowed(identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?),
-            false => self.field_tag_stack.pop().unwrap_or(Cow::Borrowed(
-                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
-            )),
-        };
+        let xml_tag = self.next_tag(identifier, XerInstructions::NONE)?.name;
         self.write_start_element(&xml_tag)?;
-        for elem in value.to_vec() {
-            self.set_entering_list_item_type(true);
-            elem.encode(self)?;
+        if self.options.canonical {
+            // CXER orders the components of a `SET OF` by their encodings, so each
+            // component is first encoded on its own and the results are sorted.
+            let mut encodings = value
+                .to_vec()
+                .into_iter()
+                .map(|elem| {
+                    let mut inner_encoder = Self::new_with_options(self.options);
+                    inner_encoder.encode_list_item(elem)?;
+                    Ok(inner_encoder.writer.into_inner())
+                })
+                .collect::<Result<Vec<_>, EncodeError>>()?;
+            encodings.sort_unstable();
+            if !encodings.is_empty() {
+                // Prompt the XML writer to close the start tag before appending.
+                self.write_empty()?;
+            }
+            for encoding in &encodings {
+                self.writer.inner_mut().push_str(encoding);
+            }
+        } else {
+            for elem in value.to_vec() {
+                self.encode_list_item(elem)?;
+            }
         }
         self.write_end_element(xml_tag)
     }
@@ -664,21 +934,41 @@ impl crate::Encoder<'_> for Encoder {
         encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
         identifier: Identifier,
     ) -> Result<Self::Ok, Self::Error> {
-        let xml_tag = match self.entering_list_item_type {
-            true => Cow::Borrowed(identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?),
-            false => self.field_tag_stack.pop().unwrap_or(Cow::Borrowed(
-                identifier.0.ok_or(XerEncodeErrorKind::MissingIdentifier)?,
-            )),
-        };
+        let field = self.next_tag(identifier, E::XER_INSTRUCTIONS)?;
         if self.entering_list_item_type {
             self.set_entering_list_item_type(false);
             encode_fn(self)?;
             Ok(())
+        } else if E::XER_INSTRUCTIONS.use_type {
+            // `USE-TYPE` choices encode the content of the alternative directly and
+            // identify it with an `xsi:type` attribute, unless it is the first one.
+            let mut alternative_encoder = Self::new_with_options(self.options);
+            let tag = encode_fn(&mut alternative_encoder)?;
+            let index = E::VARIANTS
+                .iter()
+                .chain(E::EXTENDED_VARIANTS.into_iter().flatten())
+                .position(|variant| TagTree::tag_contains(&tag, core::slice::from_ref(variant)))
+                .ok_or(XerEncodeErrorKind::MissingIdentifier)?;
+            if index != 0 {
+                let alternative = E::IDENTIFIERS
+                    .get(index)
+                    .ok_or(XerEncodeErrorKind::MissingIdentifier)?;
+                self.type_attributes = alloc::vec![
+                    (Cow::Borrowed("xmlns:xsi"), XSI_NAMESPACE.to_string()),
+                    (Cow::Borrowed(XSI_TYPE_ATTRIBUTE), alternative.to_string()),
+                ];
+            }
+            let attributes = self.type_attributes.clone();
+            self.write_element_with_content(
+                &field.name,
+                &attributes,
+                &alternative_encoder.into_content(),
+            )
         } else {
-            self.write_start_element(&xml_tag)?;
+            self.write_start_element(&field.name)?;
             self.entering_choice_value();
             encode_fn(self)?;
-            self.write_end_element(&xml_tag)
+            self.write_end_element(&field.name)
         }
     }
 
@@ -734,9 +1024,160 @@ impl crate::Encoder<'_> for Encoder {
             value
         )
     }
+
+    fn encode_typed_value(
+        &mut self,
+        value: TypedValue<'_>,
+        _: Tag,
+        _: Constraints,
+        identifier: Identifier,
+    ) -> Result<Self::Ok, Self::Error> {
+        let in_list = self.entering_list_item_type;
+        let field = self.next_typed_tag(value, identifier);
+        match value.structure::<EncodeError>(self.codec())? {
+            Structure::Sequence(fields) | Structure::Set(fields) => {
+                self.write_start_element(&field.name)?;
+                for (component, value) in fields.present() {
+                    self.field_tag_stack
+                        .push(FieldTag::new(Cow::Owned(component.name.clone())));
+                    value.encode(self)?;
+                }
+                self.write_end_element(&field.name)
+            }
+            // Items of a list are written without the element of their type.
+            Structure::Choice(alternative) => {
+                if !in_list {
+                    self.write_start_element(&field.name)?;
+                }
+                self.set_entering_list_item_type(false);
+                self.field_tag_stack.push(FieldTag::new(Cow::Owned(
+                    alternative.component.name.clone(),
+                )));
+                alternative.value.encode(self)?;
+                if in_list {
+                    Ok(())
+                } else {
+                    self.write_end_element(&field.name)
+                }
+            }
+            Structure::Enumerated(enumeration) if in_list => {
+                self.write_start_element(enumeration.name)?;
+                self.write_end_element(enumeration.name)
+            }
+            Structure::Enumerated(enumeration) => {
+                self.write_start_element(&field.name)?;
+                self.write_start_element(enumeration.name)?;
+                self.write_end_element(enumeration.name)?;
+                self.write_end_element(&field.name)
+            }
+            Structure::SequenceOf(items) => {
+                self.write_start_element(&field.name)?;
+                for item in items {
+                    self.encode_typed_item(item)?;
+                }
+                self.set_entering_list_item_type(false);
+                self.write_end_element(&field.name)
+            }
+            Structure::SetOf(items) => {
+                self.write_start_element(&field.name)?;
+                if self.options.canonical {
+                    let mut encodings = items
+                        .into_iter()
+                        .map(|item| {
+                            let mut inner_encoder = Self::new_with_options(self.options);
+                            inner_encoder.encode_typed_item(item)?;
+                            Ok(inner_encoder.writer.into_inner())
+                        })
+                        .collect::<Result<Vec<_>, EncodeError>>()?;
+                    encodings.sort_unstable();
+                    if !encodings.is_empty() {
+                        // Prompt the XML writer to close the start tag before appending.
+                        self.write_empty()?;
+                    }
+                    for encoding in &encodings {
+                        self.writer.inner_mut().push_str(encoding);
+                    }
+                } else {
+                    for item in items {
+                        self.encode_typed_item(item)?;
+                    }
+                    self.set_entering_list_item_type(false);
+                }
+                self.write_end_element(&field.name)
+            }
+        }
+    }
 }
 
 impl Encoder {
+    /// Encodes the components of a `SEQUENCE` or `SET` inside the element of `field`.
+    fn encode_constructed(
+        &mut self,
+        field: FieldTag,
+        field_tags: Vec<FieldTag>,
+        encoder_scope: impl FnOnce(&mut Self) -> Result<(), EncodeError>,
+    ) -> Result<(), EncodeError> {
+        if field.instructions.untagged {
+            // The components of an untagged type are components of the enclosing type.
+            self.field_tag_stack.extend(field_tags);
+            return encoder_scope(self);
+        }
+        if field_tags.iter().any(FieldTag::is_attribute_or_untagged) {
+            // Attributes have to be known when the start tag is written, so the
+            // components are encoded with a separate encoder first.
+            let mut inner_encoder = Self::new_with_options(self.options);
+            inner_encoder.field_tag_stack = field_tags;
+            encoder_scope(&mut inner_encoder)?;
+            let attributes = core::mem::take(&mut inner_encoder.pending_attributes);
+            return self.write_element_with_content(
+                &field.name,
+                &attributes,
+                &inner_encoder.writer.into_inner(),
+            );
+        }
+        self.write_start_element(&field.name)?;
+        self.field_tag_stack.extend(field_tags);
+        encoder_scope(self)?;
+        self.write_end_element(&field.name)
+    }
+
+    /// Returns the XML name of the next value of a type only known at
+    /// runtime, which is the name of its type when the value is neither a
+    /// component nor has an identifier.
+    fn next_typed_tag(&mut self, value: TypedValue<'_>, identifier: Identifier) -> FieldTag {
+        let field = match self.entering_list_item_type {
+            true => None,
+            false => self.field_tag_stack.pop(),
+        };
+        let field = field.unwrap_or_else(|| {
+            FieldTag::new(
+                identifier
+                    .0
+                    .map_or_else(|| value.ty.xml_name(), Cow::Borrowed),
+            )
+        });
+        self.qualify(field)
+    }
+
+    /// Encodes an item of a `SEQUENCE OF` or `SET OF` value of a type only
+    /// known at runtime, in the element of its type unless XER writes it
+    /// without one.
+    fn encode_typed_item(&mut self, item: TypedValue<'_>) -> Result<(), EncodeError> {
+        if item.ty.is_xml_value_list_item() {
+            self.set_entering_list_item_type(true);
+        } else {
+            self.field_tag_stack.push(FieldTag::new(item.ty.xml_name()));
+        }
+        item.encode(self)
+    }
+
+    /// Sets the name of the outermost element, which a value of a type only
+    /// known at runtime can't name by itself.
+    pub(crate) fn with_element_name(mut self, name: Cow<'static, str>) -> Self {
+        self.field_tag_stack.push(FieldTag::new(name));
+        self
+    }
+
     fn write_bool(&mut self, value: bool) -> Result<(), EncodeError> {
         if value {
             self.write_start_element(BOOLEAN_TRUE_TAG)?;
@@ -747,8 +1188,16 @@ impl Encoder {
         }
     }
 
+    fn write_bool_text(&mut self, value: bool) -> Result<(), EncodeError> {
+        self.write(XmlEvent::Characters(if value {
+            BOOLEAN_TRUE_TAG
+        } else {
+            BOOLEAN_FALSE_TAG
+        }))
+    }
+
     fn write_date(&mut self, value: &Date) -> Result<(), EncodeError> {
-        self.write(XmlEvent::Characters(&value.format("%Y%m%d").to_string()))
+        self.write(XmlEvent::Characters(&time_backend::date_text(value)))
     }
 
     fn write_bitstring(&mut self, value: &BitStr) -> Result<(), EncodeError> {
@@ -770,6 +1219,10 @@ impl Encoder {
         self.write_end_element(value.identifier())
     }
 
+    fn write_enumerated_text<E: Enumerated>(&mut self, value: &E) -> Result<(), EncodeError> {
+        self.write(XmlEvent::Characters(value.identifier()))
+    }
+
     fn write_integer(&mut self, value: &BigInt) -> Result<(), EncodeError> {
         self.write(XmlEvent::Characters(&value.to_str_radix(10)))
     }
@@ -784,19 +1237,25 @@ impl Encoder {
         } else if value.is_nan() {
             self.write_start_element(NAN_TAG)?;
             self.write_end_element(NAN_TAG)
+        } else if self.options.canonical {
+            use num_traits::ToPrimitive;
+
+            let float = value
+                .try_to_float()
+                .and_then(|f| f.to_f64())
+                .ok_or_else(|| XerEncodeErrorKind::XmlEncodingError {
+                    upstream: alloc::format!(
+                        "REAL value {value} cannot be represented canonically"
+                    ),
+                })?;
+            self.write(XmlEvent::Characters(&canonical_real(float)))
         } else {
             self.write(XmlEvent::Characters(&value.to_string()))
         }
     }
 
-    fn write_object_identifier(&mut self, value: &[u32]) -> Result<(), EncodeError> {
-        self.write(XmlEvent::Characters(
-            &value
-                .iter()
-                .map(|arc| arc.to_string())
-                .collect::<Vec<String>>()
-                .join("."),
-        ))
+    fn write_object_identifier(&mut self, value: &Oid) -> Result<(), EncodeError> {
+        self.write(XmlEvent::Characters(&value.to_string()))
     }
 
     #[allow(clippy::unnecessary_wraps)]
@@ -827,25 +1286,29 @@ impl Encoder {
     }
 
     fn write_generalized_time(&mut self, value: &GeneralizedTime) -> Result<(), EncodeError> {
-        self.write(XmlEvent::Characters(
-            &String::from_utf8(
-                crate::ber::enc::Encoder::datetime_to_canonical_generalized_time_bytes(value),
-            )
-            .map_err(|e| XerEncodeErrorKind::XmlEncodingError {
-                upstream: e.to_string(),
-            })?,
-        ))
+        let text = if self.options.canonical {
+            time_backend::canonical_generalized_time_text(value).map_err(|e| {
+                XerEncodeErrorKind::XmlEncodingError {
+                    upstream: e.to_string(),
+                }
+            })?
+        } else {
+            time_backend::generalized_time_text(value)
+        };
+        self.write(XmlEvent::Characters(&text))
     }
 
     fn write_utc_time(&mut self, value: &UtcTime) -> Result<(), EncodeError> {
-        self.write(XmlEvent::Characters(
-            &String::from_utf8(
-                crate::ber::enc::Encoder::datetime_to_canonical_utc_time_bytes(value),
-            )
-            .map_err(|e| XerEncodeErrorKind::XmlEncodingError {
-                upstream: e.to_string(),
-            })?,
-        ))
+        let text = if self.options.canonical {
+            time_backend::canonical_utc_time_text(value).map_err(|e| {
+                XerEncodeErrorKind::XmlEncodingError {
+                    upstream: e.to_string(),
+                }
+            })?
+        } else {
+            time_backend::utc_time_text(value)
+        };
+        self.write(XmlEvent::Characters(&text))
     }
 
     fn write_any(&mut self, value: &Any) -> Result<(), EncodeError> {
@@ -4,14 +4,37 @@
 extern crate alloc;
 
 use rasn::prelude::*;
+use rasn::types::oid_registry::{self, OidEntry};
 
-pub const PAL_ZONE: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 2]);
-pub const PAL_FREQUENCY: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 3]);
-pub const CBSD_FCCID: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 4]);
-pub const CBSD_SERIAL: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 5]);
-pub const SAS_FRN: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 6]);
-pub const CPIR: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 7]);
-pub const TEST: &Oid = Oid::const_new(&[1, 3, 6, 1, 4, 1, 46609, 1, 8]);
+pub const PAL_ZONE: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 2]);
+pub const PAL_FREQUENCY: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 3]);
+pub const CBSD_FCCID: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 4]);
+pub const CBSD_SERIAL: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 5]);
+pub const SAS_FRN: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 6]);
+pub const CPIR: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 7]);
+pub const TEST: &Oid = rasn::oid!([1, 3, 6, 1, 4, 1, 46609, 1, 8]);
+
+/// Names for the object identifiers defined by this crate.
+pub static OIDS: &[OidEntry] = &[
+    OidEntry::new(
+        rasn::oid!([1, 3, 6, 1, 4, 1, 46609]),
+        "wInnForum",
+        "Wireless Innovation Forum",
+    ),
+    OidEntry::new(PAL_ZONE, "pal-zone", "PAL zone"),
+    OidEntry::new(PAL_FREQUENCY, "pal-frequency", "PAL frequency"),
+    OidEntry::new(CBSD_FCCID, "cbsd-fccid", "CBSD FCC ID"),
+    OidEntry::new(CBSD_SERIAL, "cbsd-serial", "CBSD serial number"),
+    OidEntry::new(SAS_FRN, "sas-frn", "SAS FCC Registration Number"),
+    OidEntry::new(CPIR, "cpir", "CPI ID"),
+    OidEntry::new(TEST, "test", "Test"),
+];
+
+/// Adds the names of the object identifiers defined by this crate to the
+/// [`oid_registry`].
+pub fn register_oids() {
+    oid_registry::register(OIDS);
+}
 
 #[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq, Eq, Hash)]
 #[rasn(delegate)]
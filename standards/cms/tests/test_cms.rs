@@ -15,7 +15,7 @@ const PE_SIG_DATA: &[u8] = include_bytes!("data/pesig.p7");
 fn test_cms_signed() {
     let info = decode::<ContentInfo>(SIGNED_DATA).unwrap();
     assert_eq!(CONTENT_SIGNED_DATA, info.content_type);
-    let data = decode::<SignedData>(info.content.as_bytes()).unwrap();
+    let data = info.content.decode::<SignedData>().unwrap();
 
     assert_eq!(CONTENT_DATA, data.encap_content_info.content_type);
 
@@ -28,7 +28,7 @@ fn test_cms_signed() {
 fn test_cms_encrypted() {
     let info = decode::<ContentInfo>(ENCRYPTED_DATA).unwrap();
     assert_eq!(CONTENT_ENVELOPED_DATA, info.content_type);
-    let data = decode::<EnvelopedData>(info.content.as_bytes()).unwrap();
+    let data = info.content.decode::<EnvelopedData>().unwrap();
 
     assert_eq!(CONTENT_DATA, data.encrypted_content_info.content_type);
 
@@ -42,18 +42,25 @@ fn test_authenticode() {
     let info = decode::<ContentInfo>(PE_SIG_DATA).unwrap();
     assert_eq!(CONTENT_SIGNED_DATA, info.content_type);
 
-    let signed_data = decode::<pkcs7_compat::SignedData>(info.content.as_bytes()).unwrap();
+    let signed_data = info.content.decode::<pkcs7_compat::SignedData>().unwrap();
     assert_eq!(
         SPC_INDIRECT_DATA_OBJID,
         signed_data.encap_content_info.content_type
     );
 
-    let content = decode::<SpcIndirectDataContent>(
-        signed_data.encap_content_info.content.unwrap().as_bytes(),
-    )
-    .unwrap();
+    let content = signed_data
+        .encap_content_info
+        .content
+        .unwrap()
+        .decode::<SpcIndirectDataContent>()
+        .unwrap();
 
-    let image_data = decode::<SpcPeImageData>(content.data.value.unwrap().as_bytes()).unwrap();
+    let image_data = content
+        .data
+        .value
+        .unwrap()
+        .decode::<SpcPeImageData>()
+        .unwrap();
     println!("{image_data:#?}");
 
     match image_data.file {
@@ -5,7 +5,7 @@ use bon::Builder;
 use rasn::prelude::*;
 
 /// OID for IEEE 1609.2 Base Types module
-pub const IEEE1609_DOT2_BASE_TYPES_OID: &Oid = Oid::const_new(&[
+pub const IEEE1609_DOT2_BASE_TYPES_OID: &Oid = rasn::oid!([
     1,    // iso
     3,    // identified-organization
     111,  // ieee
@@ -14,7 +14,7 @@ pub mod crl_base_types;
 use base_types::*;
 
 /// OID for IEEE 1609.2 module
-pub const IEEE1609_DOT2_OID: &Oid = Oid::const_new(&[
+pub const IEEE1609_DOT2_OID: &Oid = rasn::oid!([
     1,    // iso
     3,    // identified-organization
     111,  // ieee
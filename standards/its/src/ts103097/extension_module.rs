@@ -4,8 +4,7 @@ use bon::Builder;
 use rasn::prelude::*;
 
 pub const EXTENSION_MODULE_VERSION: u8 = 1;
-pub const ETSI_TS103097_EXTENSION_MODULE_OID: &Oid =
-    Oid::const_new(&[0, 4, 0, 5, 5, 103_097, 2, 1, 1]);
+pub const ETSI_TS103097_EXTENSION_MODULE_OID: &Oid = rasn::oid!([0, 4, 0, 5, 5, 103_097, 2, 1, 1]);
 
 pub const ETSI_TS102941_CRL_REQUEST_ID: ExtId = ExtId(1);
 pub const ETSI_TS102941_DELTA_CTL_REQUEST_ID: ExtId = ExtId(2);
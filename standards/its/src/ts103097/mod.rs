@@ -9,7 +9,7 @@ use crate::ieee1609dot2::{
 // use rasn::error::InnerSubtypeConstraintError;
 use rasn::prelude::*;
 
-pub const ETSI_TS103097_MODULE_OID: &Oid = Oid::const_new(&[0, 4, 0, 5, 5, 103_097, 1, 3, 1]);
+pub const ETSI_TS103097_MODULE_OID: &Oid = rasn::oid!([0, 4, 0, 5, 5, 103_097, 1, 3, 1]);
 
 /// ETSI TS 103 097 certificate
 #[derive(Debug, Clone, AsnType, Encode, Decode, PartialEq, Eq)]
@@ -47,6 +47,11 @@ pub struct PrincipalName {
     pub string: SequenceOf<KerberosString>,
 }
 
+/// A time in UTC without fractions of a second, `YYYYMMDDHHMMSSZ`.
+///
+/// The time is a [`GeneralizedTime`], which is a `chrono` date and time with
+/// the default `chrono` feature, a `time` date and time with the `time`
+/// feature instead, and the text of the time without either.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(delegate)]
 pub struct KerberosTime(pub GeneralizedTime);
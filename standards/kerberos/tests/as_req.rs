@@ -30,10 +30,16 @@ fn as_req() {
             }),
             from: None,
             till: KerberosTime(
-                GeneralizedTime::parse_from_rfc3339("1970-01-01T00:00:00Z").unwrap(),
+                rasn::ber::de::Decoder::parse_canonical_generalized_time_string(
+                    "19700101000000Z".into(),
+                )
+                .unwrap(),
             ),
             rtime: Some(KerberosTime(
-                GeneralizedTime::parse_from_rfc3339("2052-03-04T11:11:11Z").unwrap(),
+                rasn::ber::de::Decoder::parse_canonical_generalized_time_string(
+                    "20520304111111Z".into(),
+                )
+                .unwrap(),
             )),
             nonce: 12345678,
             etype: vec![18, 23, -133, -128, 24, -135],
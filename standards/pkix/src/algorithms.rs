@@ -1,5 +1,6 @@
 //! Implementation of [RFC 3279](https://www.rfc-editor.org/rfc/rfc3279).
 use rasn::prelude::*;
+use rasn::types::oid_registry::{self, OidEntry};
 
 pub const ID_SHA1: &Oid = Oid::ISO_IDENTIFIED_ORGANISATION_OIW_SECSIG_ALGORITHM_SHA1;
 pub const DH_PUBLIC_NUMBER: &Oid = Oid::ISO_MEMBER_BODY_US_ANSI_X942_NUMBER_TYPE_PUBLIC;
@@ -57,6 +58,119 @@ pub const ID_DSA_WITH_SHA1: &Oid = Oid::ISO_MEMBER_BODY_US_X957_X9CM_DSA_SHA1;
 pub const ID_KEY_EXCHANGE_ALGORITHM: &Oid =
     Oid::JOINT_ISO_ITU_T_COUNTRY_US_ORGANIZATION_GOV_CSOR_INFOSEC_ALGORITHMS_KEY_EXCHANGE;
 
+/// Names for the object identifiers defined by RFC 3279.
+pub static OIDS: &[OidEntry] = &[
+    OidEntry::new(ID_SHA1, "id-sha1", "SHA-1 hash algorithm"),
+    OidEntry::new(
+        DH_PUBLIC_NUMBER,
+        "dhpublicnumber",
+        "Diffie-Hellman public key",
+    ),
+    OidEntry::new(
+        ELLIPTIC_CURVE,
+        "ellipticCurve",
+        "ANSI X9.62 elliptic curves",
+    ),
+    OidEntry::new(
+        C_TWO_CURVE,
+        "c-TwoCurve",
+        "ANSI X9.62 characteristic two curves",
+    ),
+    OidEntry::new(C2ONB191V4, "c2onb191v4", "ANSI X9.62 curve c2onb191v4"),
+    OidEntry::new(C2ONB191V5, "c2onb191v5", "ANSI X9.62 curve c2onb191v5"),
+    OidEntry::new(C2ONB239V4, "c2onb239v4", "ANSI X9.62 curve c2onb239v4"),
+    OidEntry::new(C2ONB239V5, "c2onb239v5", "ANSI X9.62 curve c2onb239v5"),
+    OidEntry::new(C2PNB163V1, "c2pnb163v1", "ANSI X9.62 curve c2pnb163v1"),
+    OidEntry::new(C2PNB163V2, "c2pnb163v2", "ANSI X9.62 curve c2pnb163v2"),
+    OidEntry::new(C2PNB163V3, "c2pnb163v3", "ANSI X9.62 curve c2pnb163v3"),
+    OidEntry::new(C2PNB176W1, "c2pnb176w1", "ANSI X9.62 curve c2pnb176w1"),
+    OidEntry::new(C2PNB208W1, "c2pnb208w1", "ANSI X9.62 curve c2pnb208w1"),
+    OidEntry::new(C2PNB272W1, "c2pnb272w1", "ANSI X9.62 curve c2pnb272w1"),
+    OidEntry::new(C2PNB304W1, "c2pnb304w1", "ANSI X9.62 curve c2pnb304w1"),
+    OidEntry::new(C2PNB368W1, "c2pnb368w1", "ANSI X9.62 curve c2pnb368w1"),
+    OidEntry::new(C2TNB191V1, "c2tnb191v1", "ANSI X9.62 curve c2tnb191v1"),
+    OidEntry::new(C2TNB191V2, "c2tnb191v2", "ANSI X9.62 curve c2tnb191v2"),
+    OidEntry::new(C2TNB191V3, "c2tnb191v3", "ANSI X9.62 curve c2tnb191v3"),
+    OidEntry::new(C2TNB239V1, "c2tnb239v1", "ANSI X9.62 curve c2tnb239v1"),
+    OidEntry::new(C2TNB239V2, "c2tnb239v2", "ANSI X9.62 curve c2tnb239v2"),
+    OidEntry::new(C2TNB239V3, "c2tnb239v3", "ANSI X9.62 curve c2tnb239v3"),
+    OidEntry::new(C2TNB359V1, "c2tnb359v1", "ANSI X9.62 curve c2tnb359v1"),
+    OidEntry::new(C2TNB431R1, "c2tnb431r1", "ANSI X9.62 curve c2tnb431r1"),
+    OidEntry::new(PRIME, "primeCurve", "ANSI X9.62 prime curves"),
+    OidEntry::new(PRIME_192V1, "prime192v1", "ANSI X9.62 curve prime192v1"),
+    OidEntry::new(PRIME_192V2, "prime192v2", "ANSI X9.62 curve prime192v2"),
+    OidEntry::new(PRIME_192V3, "prime192v3", "ANSI X9.62 curve prime192v3"),
+    OidEntry::new(PRIME_239V1, "prime239v1", "ANSI X9.62 curve prime239v1"),
+    OidEntry::new(PRIME_239V2, "prime239v2", "ANSI X9.62 curve prime239v2"),
+    OidEntry::new(PRIME_239V3, "prime239v3", "ANSI X9.62 curve prime239v3"),
+    OidEntry::new(PRIME_256V1, "prime256v1", "ANSI X9.62 curve prime256v1"),
+    OidEntry::new(ID_EC_SIG_TYPE, "id-ecSigType", "ANSI X9.62 signature types"),
+    OidEntry::new(
+        ECDSA_WITH_SHA1,
+        "ecdsa-with-SHA1",
+        "ECDSA signature with SHA-1",
+    ),
+    OidEntry::new(ID_FIELD_TYPE, "id-fieldType", "ANSI X9.62 field types"),
+    OidEntry::new(
+        CHARACTERISTIC_TWO_FIELD,
+        "characteristic-two-field",
+        "Characteristic two field",
+    ),
+    OidEntry::new(
+        ID_CHARACTERISTIC_TWO_BASIS,
+        "id-characteristic-two-basis",
+        "Characteristic two field bases",
+    ),
+    OidEntry::new(GN_BASIS, "gnBasis", "Gaussian normal basis"),
+    OidEntry::new(PRIME_FIELD, "prime-field", "Prime field"),
+    OidEntry::new(
+        ID_PUBLIC_KEY_TYPE,
+        "id-publicKeyType",
+        "ANSI X9.62 public key types",
+    ),
+    OidEntry::new(
+        ID_EC_PUBLIC_KEY,
+        "id-ecPublicKey",
+        "Elliptic curve public key",
+    ),
+    OidEntry::new(MD2, "md2", "MD2 hash algorithm"),
+    OidEntry::new(MD5, "md5", "MD5 hash algorithm"),
+    OidEntry::new(PKCS1, "pkcs-1", "PKCS #1"),
+    OidEntry::new(
+        MD2_WITH_RSA_ENCRYPTION,
+        "md2WithRSAEncryption",
+        "RSA signature with MD2",
+    ),
+    OidEntry::new(
+        MD5_WITH_RSA_ENCRYPTION,
+        "md5WithRSAEncryption",
+        "RSA signature with MD5",
+    ),
+    OidEntry::new(RSA_ENCRYPTION, "rsaEncryption", "RSA public key"),
+    OidEntry::new(
+        SHA1_WITH_RSA_ENCRYPTION,
+        "sha1WithRSAEncryption",
+        "RSA signature with SHA-1",
+    ),
+    OidEntry::new(ID_DSA, "id-dsa", "DSA public key"),
+    OidEntry::new(
+        ID_DSA_WITH_SHA1,
+        "id-dsa-with-sha1",
+        "DSA signature with SHA-1",
+    ),
+    OidEntry::new(
+        ID_KEY_EXCHANGE_ALGORITHM,
+        "id-keyExchangeAlgorithm",
+        "Key Exchange Algorithm (KEA) public key",
+    ),
+];
+
+/// Adds the names of the object identifiers defined by RFC 3279 to the
+/// [`oid_registry`].
+pub fn register_oids() {
+    oid_registry::register(OIDS);
+}
+
 pub type DsaPublicKey = Integer;
 pub type DhPublicKey = Integer;
 pub type KeaParamsId = OctetString;
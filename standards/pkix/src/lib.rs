@@ -318,14 +318,16 @@ impl core::fmt::Display for TrustAnchorInfoVersion {
 }
 
 /// The validity period of the certificate.
-#[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(feature = "chrono", feature = "time"), derive(Copy))]
 pub struct Validity {
     pub not_before: Time,
     pub not_after: Time,
 }
 
 /// A general time type.
-#[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(feature = "chrono", feature = "time"), derive(Copy))]
 #[rasn(choice)]
 pub enum Time {
     Utc(UtcTime),
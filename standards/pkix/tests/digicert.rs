@@ -1,4 +1,3 @@
-use chrono::TimeZone;
 use pretty_assertions::assert_eq;
 use rasn::prelude::*;
 use rasn_pkix::*;
@@ -117,13 +116,11 @@ fn lets_encrypt_x3() {
             ]),
             validity: Validity {
                 not_before: Time::Utc(
-                    chrono::Utc
-                        .with_ymd_and_hms(2016, 3, 17, 16, 40, 46)
+                    rasn::ber::de::Decoder::parse_canonical_utc_time_string("160317164046Z")
                         .unwrap(),
                 ),
                 not_after: Time::Utc(
-                    chrono::Utc
-                        .with_ymd_and_hms(2021, 3, 17, 16, 40, 46)
+                    rasn::ber::de::Decoder::parse_canonical_utc_time_string("210317164046Z")
                         .unwrap(),
                 ),
             },
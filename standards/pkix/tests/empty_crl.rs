@@ -3,7 +3,6 @@
 //! > When there are no revoked certificates, the revoked certificates list
 //! > MUST be absent.
 
-use chrono::{TimeZone as _, Utc};
 use rasn::prelude::*;
 use rasn_pkix::{
     AlgorithmIdentifier, CertificateSerialNumber, Extensions, Name, RevokedCertificate,
@@ -38,6 +37,10 @@ struct TbsCertListEmitsEmpty {
     pub crl_extensions: Option<Extensions>,
 }
 
+fn utc(time: &str) -> UtcTime {
+    rasn::ber::de::Decoder::parse_canonical_utc_time_string(time).unwrap()
+}
+
 fn tbs_cert_list(revoked: Vec<RevokedCertificate>) -> TbsCertList {
     TbsCertList {
         version: Version::V2,
@@ -46,10 +49,8 @@ fn tbs_cert_list(revoked: Vec<RevokedCertificate>) -> TbsCertList {
             parameters: None,
         },
         issuer: Name::RdnSequence(vec![]),
-        this_update: Time::Utc(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()),
-        next_update: Some(Time::Utc(
-            Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap(),
-        )),
+        this_update: Time::Utc(utc("260101000000Z")),
+        next_update: Some(Time::Utc(utc("260401000000Z"))),
         revoked_certificates: revoked,
         crl_extensions: None,
     }
@@ -63,10 +64,8 @@ fn tbs_cert_list_no_revoked() -> TbsCertListNoRevoked {
             parameters: None,
         },
         issuer: Name::RdnSequence(vec![]),
-        this_update: Time::Utc(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()),
-        next_update: Some(Time::Utc(
-            Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap(),
-        )),
+        this_update: Time::Utc(utc("260101000000Z")),
+        next_update: Some(Time::Utc(utc("260401000000Z"))),
         crl_extensions: None,
     }
 }
@@ -79,10 +78,8 @@ fn tbs_cert_list_emits_empty() -> TbsCertListEmitsEmpty {
             parameters: None,
         },
         issuer: Name::RdnSequence(vec![]),
-        this_update: Time::Utc(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()),
-        next_update: Some(Time::Utc(
-            Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap(),
-        )),
+        this_update: Time::Utc(utc("260101000000Z")),
+        next_update: Some(Time::Utc(utc("260401000000Z"))),
         revoked_certificates: vec![],
         crl_extensions: None,
     }
@@ -101,12 +98,12 @@ fn populated_revoked_certificates_roundtrips() {
     let revoked = vec![
         RevokedCertificate {
             user_certificate: CertificateSerialNumber::from(0x1234u32),
-            revocation_date: Time::Utc(Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap()),
+            revocation_date: Time::Utc(utc("260102030405Z")),
             crl_entry_extensions: None,
         },
         RevokedCertificate {
             user_certificate: CertificateSerialNumber::from(0x5678u32),
-            revocation_date: Time::Utc(Utc.with_ymd_and_hms(2026, 2, 3, 4, 5, 6).unwrap()),
+            revocation_date: Time::Utc(utc("260203040506Z")),
             crl_entry_extensions: None,
         },
     ];
@@ -83,19 +83,11 @@ pub struct VarBind {
 #[cfg(test)]
 mod tests {
     use super::{Message, Trap, VarBind};
-    use alloc::{string::String, string::ToString, vec, vec::Vec};
+    use alloc::{string::ToString, vec};
     use pretty_assertions::assert_eq;
     use rasn::types::ObjectIdentifier;
     use smi::v1::{Gauge, IpAddress, NetworkAddress, TimeTicks};
 
-    fn string_oid(oid: impl AsRef<[u32]>) -> String {
-        oid.as_ref()
-            .iter()
-            .map(ToString::to_string)
-            .collect::<Vec<_>>()
-            .join(".")
-    }
-
     #[test]
     fn trap() {
         #[rustfmt::skip]
@@ -157,7 +149,7 @@ mod tests {
         assert_eq!(decode_msg.version, 0.into());
         assert_eq!(decode_msg.community, "public".as_bytes());
         assert_eq!(
-            string_oid(decode_msg.data.enterprise),
+            decode_msg.data.enterprise.to_string(),
             "1.3.6.1.4.1.11779.1.42.3.7.8"
         );
         assert_eq!(
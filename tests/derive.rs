@@ -356,3 +356,117 @@ fn decode_enum_with_generics() {
         NewtypeNoGeneric(String),
     }
 }
+
+#[test]
+fn builder() {
+    fn default_priority() -> u8 {
+        3
+    }
+
+    #[derive(AsnType, Debug, Encode, PartialEq)]
+    #[rasn(automatic_tags, builder)]
+    struct Message {
+        #[rasn(value("0..=99"))]
+        id: u8,
+        #[rasn(size("1..=8"))]
+        name: Utf8String,
+        #[rasn(default = "default_priority")]
+        priority: u8,
+        comment: Option<Utf8String>,
+    }
+
+    #[derive(AsnType, Debug, Encode, PartialEq)]
+    #[rasn(delegate, builder, value("1..=12"))]
+    struct Month(u8);
+
+    let message = Message::builder().id(7).name("rasn").build().unwrap();
+    assert_eq!(
+        message,
+        Message {
+            id: 7,
+            name: "rasn".into(),
+            priority: 3,
+            comment: None,
+        }
+    );
+
+    let message = Message::builder()
+        .id(7)
+        .name("rasn")
+        .comment("ok".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(message.comment.as_deref(), Some("ok"));
+
+    assert!(matches!(
+        Message::builder().id(7).build(),
+        Err(error::BuildError::MissingField { name: "name" })
+    ));
+    assert!(matches!(
+        Message::builder().id(100).name("rasn").build(),
+        Err(error::BuildError::Constraint { .. })
+    ));
+    assert!(matches!(
+        Message::builder().id(7).name("too long!").build(),
+        Err(error::BuildError::Constraint { .. })
+    ));
+
+    assert_eq!(Month::new(12u8).unwrap(), Month(12));
+    assert!(Month::new(13u8).is_err());
+}
+
+#[test]
+fn named_bits() {
+    #[derive(AsnType, Clone, Debug, Default, Decode, Encode, PartialEq)]
+    #[rasn(named_bits(
+        digital_signature = 0,
+        non_repudiation = 1,
+        key_encipherment = 2,
+        data_encipherment = 3,
+        key_agreement = 4,
+        key_cert_sign = 5,
+        crl_sign = 6,
+        encipher_only = 7,
+        decipher_only = 8,
+    ))]
+    struct KeyUsage(BitString);
+
+    #[derive(AsnType, Clone, Debug, Default, Decode, Encode, PartialEq)]
+    #[rasn(named_bits(reserved = 0, forwardable = 1, forwarded = 2), size("9.."))]
+    struct Flags(BitString);
+
+    let mut usage = KeyUsage::default();
+    usage.set_key_cert_sign(true);
+    usage.set_crl_sign(true);
+    usage.set_decipher_only(false);
+    assert_eq!(KeyUsage::CRL_SIGN, 6);
+    assert!(usage.key_cert_sign() && usage.crl_sign());
+    assert!(!usage.digital_signature() && !usage.decipher_only());
+
+    let encoded = der::encode(&usage).unwrap();
+    assert_eq!(encoded, [0x03, 0x02, 0x01, 0x06]);
+    let decoded: KeyUsage = der::decode(&encoded).unwrap();
+    assert!(decoded.key_cert_sign() && decoded.crl_sign());
+
+    // Trailing zero bits are removed regardless of how the value was built.
+    let padded = KeyUsage(
+        bitvec::bitvec![u8, bitvec::order::Msb0; 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    );
+    assert_eq!(der::encode(&padded).unwrap(), encoded);
+    assert_eq!(
+        der::encode(&KeyUsage::default()).unwrap(),
+        [0x03, 0x01, 0x00]
+    );
+
+    usage.set_key_cert_sign(false);
+    usage.set_crl_sign(false);
+    assert_eq!(der::encode(&usage).unwrap(), [0x03, 0x01, 0x00]);
+
+    // PER keeps the value as long as the lower bound of its size constraint.
+    let mut flags = Flags::default();
+    flags.set_forwardable(true);
+    let encoded = uper::encode(&flags).unwrap();
+    let decoded: Flags = uper::decode(&encoded).unwrap();
+    assert_eq!(decoded.0.len(), 9);
+    assert!(decoded.forwardable() && !decoded.forwarded());
+}